@@ -1,6 +1,11 @@
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 1,
+    title: "Not Quite Lisp",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let p1 = part1(input);
     let p2 = part2(input);
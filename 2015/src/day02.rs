@@ -3,6 +3,11 @@ use std::str::FromStr;
 use aoc_rust::Solution;
 use eyre::{ContextCompat, Report, Result};
 
+aoc_rust::register_day! {
+    day: 2,
+    title: "I Was Told There Would Be No Math",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let dims = input
         .trim()
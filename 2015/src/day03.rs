@@ -2,6 +2,11 @@ use aoc_rust::Solution;
 use eyre::Result;
 use hashbrown::HashSet;
 
+aoc_rust::register_day! {
+    day: 3,
+    title: "Perfectly Spherical Houses in a Vacuum",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let p1 = part1(input);
     let p2 = part2(input);
@@ -3,6 +3,11 @@ use eyre::Result;
 use itoa::Buffer;
 use md5::Digest;
 
+aoc_rust::register_day! {
+    day: 4,
+    title: "The Ideal Stocking Stuffer",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
@@ -1,200 +1,199 @@
-use aoc_rust::Solution;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use aoc_rust::{
+    netlist::{Gate, Netlist, Wire},
+    util::parse::{parse_at, ParseContext, ParseError},
+    Solution,
+};
 use eyre::Result;
-use hashbrown::HashMap;
-use std::cell::RefCell;
-use std::rc::Rc;
 
-pub fn run(input: &str) -> Result<Solution> {
-    let mut part1_wires = HashMap::new();
-    let mut part2_wires = HashMap::new();
+aoc_rust::register_day! {
+    day: 7,
+    title: "Some Assembly Required",
+}
 
-    for line in input.lines() {
-        let mut words = line.trim_end().split(' ');
+pub fn run(input: &str) -> Result<Solution> {
+    let mut netlist = parse_netlist(input)?;
 
-        let first = words.next().unwrap();
-        let second = words.next().unwrap();
+    let wire = |name: &str| {
+        netlist
+            .get(name)
+            .ok_or_else(|| eyre::eyre!("missing wire `{name}`"))
+    };
 
-        if first == "NOT" {
-            let third = words.nth(1).unwrap();
+    let a = wire("a")?;
+    let b = wire("b")?;
 
-            let src = part1_wires.get_or_insert_source(second);
-            let gate = Gate::Not { src };
-            let target = part1_wires.get_or_insert_target(third);
-            target.borrow_mut().src.replace(gate.into());
+    let part1 = signal(&netlist, a)?;
 
-            let src = part2_wires.get_or_insert_source(second);
-            let gate = Gate::Not { src };
-            let target = part2_wires.get_or_insert_target(third);
-            target.borrow_mut().src.replace(gate.into());
-        } else if second == "->" {
-            let third = words.next().unwrap();
+    netlist.drive(b, Op::Value(part1), Vec::new());
+    let part2 = signal(&netlist, a)?;
 
-            let src = part1_wires.get_or_insert_source(first);
-            let target = part1_wires.get_or_insert_target(third);
-            target.borrow_mut().src.replace(src);
+    Ok(Solution::new().part1(part1).part2(part2))
+}
 
-            let src = part2_wires.get_or_insert_source(first);
-            let target = part2_wires.get_or_insert_target(third);
-            target.borrow_mut().src.replace(src);
-        } else {
-            let third = words.next().unwrap();
-            let fourth = words.nth(1).unwrap();
-
-            let lhs = part1_wires.get_or_insert_source(first);
-            let rhs = part1_wires.get_or_insert_source(third);
-
-            #[rustfmt::skip]
-            let gate = match second {
-                "AND" => Gate::And { lhs, rhs },
-                "OR" => Gate::Or{ lhs, rhs },
-                "RSHIFT" => Gate::Rshift { src: lhs, shift: rhs },
-                "LSHIFT" => Gate::Lshift { src: lhs, shift: rhs },
-                _ => unreachable!(),
-            };
+/// The circuit in Graphviz' DOT format.
+pub fn dot(input: &str) -> Result<String> {
+    let netlist = parse_netlist(input)?;
 
-            let target = part1_wires.get_or_insert_target(fourth);
-            target.borrow_mut().src.replace(gate.into());
+    Ok(netlist.dot().to_string())
+}
 
-            let lhs = part2_wires.get_or_insert_source(first);
-            let rhs = part2_wires.get_or_insert_source(third);
+type Signal = u16;
 
-            #[rustfmt::skip]
-            let gate = match second {
-                "AND" => Gate::And { lhs, rhs },
-                "OR" => Gate::Or{ lhs, rhs },
-                "RSHIFT" => Gate::Rshift { src: lhs, shift: rhs },
-                "LSHIFT" => Gate::Lshift { src: lhs, shift: rhs },
-                _ => unreachable!(),
-            };
+fn signal(netlist: &Netlist<'_, Op>, wire: Wire) -> Result<Signal> {
+    netlist
+        .evaluate([])?
+        .get(wire)
+        .ok_or_else(|| eyre::eyre!("wire `{}` without signal", netlist.name(wire)))
+}
 
-            let target = part2_wires.get_or_insert_target(fourth);
-            target.borrow_mut().src.replace(gate.into());
-        }
-    }
+fn parse_netlist(input: &str) -> Result<Netlist<'_, Op>, ParseError> {
+    let mut netlist = Netlist::new();
 
-    let part1 = part1_wires[&"a".hash()].borrow_mut().value();
+    for line in input.lines() {
+        let mut words = line.trim_end().split(' ');
+        let mut word = |skip| {
+            words
+                .nth(skip)
+                .context_at(input, line, "expected more words")
+        };
 
-    part2_wires[&"b".hash()]
-        .borrow_mut()
-        .src
-        .replace(part1.into());
+        let first = word(0)?;
+        let second = word(0)?;
 
-    let part2 = part2_wires[&"a".hash()].borrow_mut().value();
+        let (op, inputs, target) = if first == "NOT" {
+            (Op::Not, vec![second], word(1)?)
+        } else if second == "->" {
+            (Op::Assign, vec![first], word(0)?)
+        } else {
+            let third = word(0)?;
+            let fourth = word(1)?;
+
+            let op = match second {
+                "AND" => Op::And,
+                "OR" => Op::Or,
+                "RSHIFT" => Op::Rshift,
+                "LSHIFT" => Op::Lshift,
+                _ => return Err(ParseError::at(input, second, "unknown gate")),
+            };
 
-    Ok(Solution::new().part1(part1).part2(part2))
-}
+            (op, vec![first, third], fourth)
+        };
 
-type Signal = u16;
-type RcWire = Rc<RefCell<Wire>>;
+        let inputs = inputs
+            .into_iter()
+            .map(|name| source(&mut netlist, input, name))
+            .collect::<Result<_, _>>()?;
 
-trait StrExt {
-    fn hash(&self) -> u32;
-}
+        let wire = netlist.wire(target);
 
-impl StrExt for &str {
-    #[inline]
-    fn hash(&self) -> u32 {
-        self.chars().fold(0, |hash, c| (hash << 8) + c as u32)
+        if netlist.drive(wire, op, inputs).is_some() {
+            return Err(ParseError::at(input, target, "wire is driven twice"));
+        }
     }
-}
 
-trait HashMapExt {
-    fn get_or_insert_source(&mut self, name: &str) -> Source;
-    fn get_or_insert_target(&mut self, name: &str) -> RcWire;
+    Ok(netlist)
 }
 
-impl HashMapExt for HashMap<u32, Rc<RefCell<Wire>>> {
-    #[inline]
-    fn get_or_insert_source(&mut self, name: &str) -> Source {
-        if name.chars().all(|c| c.is_numeric()) {
-            let n: Signal = name.parse().unwrap();
-
-            return Source::Value(n);
-        }
-
-        Source::Wire(self.get_or_insert_target(name))
+/// The wire of the given name, numbers being wires that are driven by their
+/// value once they're first used.
+fn source<'a>(
+    netlist: &mut Netlist<'a, Op>,
+    input: &str,
+    name: &'a str,
+) -> Result<Wire, ParseError> {
+    let wire = netlist.wire(name);
+
+    if name.chars().all(|c| c.is_numeric()) && netlist.driver(wire).is_none() {
+        let n: Signal = parse_at(input, name)?;
+        netlist.drive(wire, Op::Value(n), Vec::new());
     }
 
-    #[inline]
-    fn get_or_insert_target(&mut self, name: &str) -> RcWire {
-        let value = self
-            .entry(name.hash())
-            .or_insert_with(|| Rc::new(RefCell::new(Wire::new())));
-
-        Rc::clone(value)
-    }
+    Ok(wire)
 }
 
-#[derive(Clone, Debug)]
-enum Source {
-    Wire(Rc<RefCell<Wire>>),
+#[derive(Copy, Clone, Debug)]
+enum Op {
     Value(Signal),
-    Gate(Box<Gate>),
-}
-
-impl Source {
-    #[inline]
-    fn value(&self) -> Signal {
-        match self {
-            Source::Wire(wire) => wire.borrow_mut().value(),
-            Source::Value(value) => *value,
-            Source::Gate(gate) => gate.value(),
-        }
-    }
+    Assign,
+    And,
+    Or,
+    Not,
+    Lshift,
+    Rshift,
 }
 
-impl From<Signal> for Source {
-    #[inline]
-    fn from(value: Signal) -> Self {
-        Self::Value(value)
-    }
-}
+impl Gate for Op {
+    type Signal = Signal;
 
-impl From<Gate> for Source {
     #[inline]
-    fn from(gate: Gate) -> Self {
-        Self::Gate(Box::new(gate))
+    fn eval(&self, inputs: &[Signal]) -> Option<Signal> {
+        let value = match (self, inputs) {
+            (Op::Value(value), []) => *value,
+            (Op::Assign, [src]) => *src,
+            (Op::And, [lhs, rhs]) => lhs & rhs,
+            (Op::Or, [lhs, rhs]) => lhs | rhs,
+            (Op::Not, [src]) => !src,
+            (Op::Lshift, [src, shift]) => src.checked_shl(*shift as u32)?,
+            (Op::Rshift, [src, shift]) => src.checked_shr(*shift as u32)?,
+            _ => return None,
+        };
+
+        Some(value)
     }
 }
 
-#[derive(Clone, Debug)]
-enum Gate {
-    And { lhs: Source, rhs: Source },
-    Or { lhs: Source, rhs: Source },
-    Not { src: Source },
-    Lshift { src: Source, shift: Source },
-    Rshift { src: Source, shift: Source },
-}
-
-impl Gate {
-    #[inline]
-    fn value(&self) -> Signal {
+impl Display for Op {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
-            Gate::And { lhs, rhs } => lhs.value() & rhs.value(),
-            Gate::Or { lhs, rhs } => lhs.value() | rhs.value(),
-            Gate::Not { src } => !src.value(),
-            Gate::Lshift { src, shift } => src.value() << shift.value(),
-            Gate::Rshift { src, shift } => src.value() >> shift.value(),
+            Op::Value(value) => Display::fmt(value, f),
+            Op::Assign => Ok(()),
+            Op::And => f.write_str("AND"),
+            Op::Or => f.write_str("OR"),
+            Op::Not => f.write_str("NOT"),
+            Op::Lshift => f.write_str("LSHIFT"),
+            Op::Rshift => f.write_str("RSHIFT"),
         }
     }
 }
 
-#[derive(Clone, Debug)]
-struct Wire {
-    src: Option<Source>,
-}
-
-impl Wire {
-    #[inline]
-    fn new() -> Self {
-        Self { src: None }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CIRCUIT: &str = "\
+123 -> x
+456 -> y
+x AND y -> d
+NOT y -> i
+d -> a
+x -> b";
+
+    #[test]
+    fn example() {
+        let solution = run(CIRCUIT).unwrap();
+        assert_eq!(solution.part1.to_string(), "72");
+        assert_eq!(solution.part2.to_string(), "72");
+
+        let dot = dot(CIRCUIT).unwrap();
+        assert!(dot.contains("\"d\" [label=\"d\\nAND\"];"), "{dot}");
+        assert!(dot.contains("\"y\" -> \"i\";"), "{dot}");
     }
 
-    #[inline]
-    fn value(&mut self) -> Signal {
-        let value = self.src.as_ref().map(Source::value).unwrap();
-        self.src.replace(value.into());
+    #[test]
+    fn cycle() {
+        let err = run("a -> b\nb -> a").unwrap_err();
+        assert_eq!(err.to_string(), "wires form a cycle: b -> a -> b");
+    }
 
-        value
+    #[test]
+    fn driven_twice() {
+        let err = run("1 -> a\n1 AND 2 -> b\na OR b -> a").unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("line 3, column 11: wire is driven twice"),
+            "{err}"
+        );
     }
 }
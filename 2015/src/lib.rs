@@ -3,3 +3,8 @@ pub mod day02;
 pub mod day03;
 pub mod day04;
 pub mod day07;
+
+aoc_rust::register_year! {
+    2015:
+        day01, day02, day03, day04, day07,
+}
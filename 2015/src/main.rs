@@ -1,12 +1,12 @@
-use std::{fs::read_to_string, time::Instant};
+use std::fs::read_to_string;
+
+use aoc_rust::timing;
 
 fn main() {
     let input = read_to_string("./inputs/day04.txt").unwrap();
 
-    let start = Instant::now();
-    let solution = aoc15::day04::run(&input);
-    let elapsed = start.elapsed();
+    let (solution, timings) = timing::time(|| aoc15::day04::run(&input));
 
     print!("{}", solution.unwrap());
-    println!("Elapsed: {elapsed:?}");
+    println!("Elapsed: {timings}");
 }
@@ -0,0 +1,110 @@
+//! The assembunny instruction set of day 12.
+
+use aoc_rust::{
+    machine::{self, Fused, Instruction, Machine, Operand, Shape, Shaped, Step, Words},
+    util::parse::ParseError,
+};
+
+pub const REGISTERS: &str = "abcd";
+
+pub type Registers = [i64; 4];
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Op {
+    /// Copying into a value is skipped, only toggling produces it
+    Cpy(Operand, Operand),
+    Inc(Operand),
+    Dec(Operand),
+    Jnz(Operand, Operand),
+    Tgl(Operand),
+    Out(Operand),
+    Fused(Fused),
+}
+
+impl Op {
+    fn toggle(&self) -> Self {
+        match *self {
+            Self::Inc(x) => Self::Dec(x),
+            Self::Dec(x) | Self::Tgl(x) | Self::Out(x) => Self::Inc(x),
+            Self::Jnz(x, y) => Self::Cpy(x, y),
+            Self::Cpy(x, y) => Self::Jnz(x, y),
+            Self::Fused(fused) => Self::Fused(fused),
+        }
+    }
+}
+
+impl Instruction for Op {
+    type State = Registers;
+    type Output = i64;
+
+    #[inline]
+    fn execute(&self, regs: &mut Registers) -> Step<Self> {
+        match *self {
+            Self::Cpy(src, Operand::Register(dst)) => regs[dst as usize] = src.get(regs),
+            Self::Inc(Operand::Register(reg)) => regs[reg as usize] += 1,
+            Self::Dec(Operand::Register(reg)) => regs[reg as usize] -= 1,
+            Self::Jnz(cond, offset) if cond.get(regs) != 0 => return Step::Jump(offset.get(regs)),
+            Self::Tgl(offset) => {
+                return Step::Patch {
+                    offset: offset.get(regs),
+                    patch: Self::toggle,
+                }
+            }
+            Self::Out(value) => return Step::Output(value.get(regs)),
+            Self::Fused(fused) => return fused.execute(regs),
+            // invalid instructions are skipped
+            Self::Cpy(..) | Self::Inc(_) | Self::Dec(_) | Self::Jnz(..) => {}
+        }
+
+        Step::Next
+    }
+}
+
+impl Shaped for Op {
+    fn shape(&self) -> Shape {
+        match *self {
+            Self::Cpy(src, Operand::Register(dst)) => Shape::Copy { src, dst },
+            Self::Inc(Operand::Register(dst)) => Shape::Add {
+                dst,
+                amount: Operand::Value(1),
+            },
+            Self::Dec(Operand::Register(dst)) => Shape::Add {
+                dst,
+                amount: Operand::Value(-1),
+            },
+            Self::Jnz(cond, offset) => Shape::JumpNonZero { cond, offset },
+            _ => Shape::Other,
+        }
+    }
+
+    fn fuse(fused: Fused) -> Self {
+        Self::Fused(fused)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Op>, ParseError> {
+    machine::parse_program(input, |mnemonic, words| {
+        let op = match mnemonic {
+            "cpy" => Op::Cpy(words.operand(REGISTERS)?, register(words)?),
+            "inc" => Op::Inc(register(words)?),
+            "dec" => Op::Dec(register(words)?),
+            "jnz" => Op::Jnz(words.operand(REGISTERS)?, words.operand(REGISTERS)?),
+            "tgl" => Op::Tgl(words.operand(REGISTERS)?),
+            "out" => Op::Out(words.operand(REGISTERS)?),
+            _ => return Err(words.unknown(mnemonic)),
+        };
+
+        Ok(op)
+    })
+}
+
+fn register(words: &mut Words<'_>) -> Result<Operand, ParseError> {
+    words.register(REGISTERS).map(Operand::Register)
+}
+
+/// A machine that runs the program with multiply and add loops fused.
+pub fn machine(ops: Vec<Op>, regs: Registers) -> Machine<Op> {
+    Machine::new(ops, regs)
+        .with_peephole(machine::mul_loop)
+        .with_peephole(machine::add_loop)
+}
@@ -1,18 +1,31 @@
-use std::{convert::identity, str::FromStr};
+use std::convert::identity;
 
-use aoc_rust::Solution;
+use aoc_rust::{
+    timing::{self, Phase},
+    util::parse::{ParseContext, ParseError},
+    Solution,
+};
+
+aoc_rust::register_day! {
+    day: 3,
+    title: "Squares With Three Sides",
+}
 
 pub fn run(input: &str) -> eyre::Result<Solution> {
-    Ok(Solution::new().part1(part1(input)).part2(part2(input)))
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input));
+
+    Ok(Solution::new().part1(p1).part2(p2))
 }
 
-pub fn part1(input: &str) -> usize {
-    input
-        .lines()
-        .map(str::parse)
-        .map(Result::unwrap)
-        .filter(Triangle::valid)
-        .count()
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    let mut count = 0;
+
+    for line in input.lines() {
+        count += Triangle::parse(input, line)?.valid() as usize;
+    }
+
+    Ok(count)
 }
 
 pub fn part2(input: &str) -> u16 {
@@ -73,20 +86,18 @@ impl Triangle {
     fn valid(&self) -> bool {
         2 * self.a.max(self.b).max(self.c) < self.a + self.b + self.c
     }
-}
-
-impl FromStr for Triangle {
-    type Err = ();
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut nums = s
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let mut nums = line
             .split_ascii_whitespace()
             .map(|num| num.chars().fold(0, |n, c| n * 10 + (c as u16 & 0xF)));
 
-        let a = nums.next().ok_or(())?;
-        let b = nums.next().ok_or(())?;
-        let c = nums.next().ok_or(())?;
+        let mut side = || nums.next().context_at(input, line, "expected three sides");
 
-        Ok(Self { a, b, c })
+        Ok(Self {
+            a: side()?,
+            b: side()?,
+            c: side()?,
+        })
     }
 }
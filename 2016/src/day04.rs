@@ -1,60 +1,80 @@
 use std::collections::HashMap;
 
-use aoc_rust::Solution;
+use aoc_rust::{
+    util::parse::{parse_at, ParseContext, ParseError},
+    Solution,
+};
+
+aoc_rust::register_day! {
+    day: 4,
+    title: "Security Through Obscurity",
+}
 
 pub fn run(input: &str) -> eyre::Result<Solution> {
-    Ok(Solution::new().part1(part1(input)).part2(part2(input)))
+    Ok(Solution::new().part1(part1(input)?).part2(part2(input)?))
 }
 
-pub fn part1(input: &str) -> usize {
+pub fn part1(input: &str) -> Result<usize, ParseError> {
     let mut counts = HashMap::with_capacity(26);
+    let mut sum = 0;
+
+    for line in input.lines() {
+        if let Some((_, id)) = parse_line(input, line, &mut counts)? {
+            sum += id;
+        }
+    }
 
-    input
-        .lines()
-        .filter_map(|line| parse_line(line, &mut counts))
-        .sum()
+    Ok(sum)
 }
 
-pub fn part2(input: &str) -> usize {
+pub fn part2(input: &str) -> eyre::Result<usize> {
     let mut counts = HashMap::with_capacity(26);
 
-    input
-        .lines()
-        .filter_map(|line| {
-            let id = parse_line(line, &mut counts)?;
-
-            Some((&line[..line.len() - 11], id))
-        })
-        .find_map(|(line, id)| {
-            for (c, required) in line.chars().zip("northpole object storage".chars()) {
-                let decrypted = match c {
-                    '-' => ' ',
-                    _ => ((((c as u8 - b'a') as usize + id) % 26) as u8 + b'a') as char,
-                };
-
-                if decrypted != required {
-                    return None;
-                }
-            }
-
-            Some(id)
-        })
-        .unwrap()
-}
-
-fn parse_line(line: &str, counts: &mut HashMap<char, u8>) -> Option<usize> {
-    let checksum = &line[line.len() - 6..line.len() - 1];
+    for line in input.lines() {
+        let Some((name, id)) = parse_line(input, line, &mut counts)? else {
+            continue;
+        };
 
-    let mut names = line[..line.len() - 7].split('-');
+        let decrypted = name.chars().map(|c| match c {
+            '-' => ' ',
+            _ => ((((c as u8 - b'a') as usize + id) % 26) as u8 + b'a') as char,
+        });
 
-    let id = names.next_back().unwrap();
+        let mut zipped = decrypted.zip("northpole object storage".chars());
 
-    for name in names {
-        for c in name.chars() {
-            *counts.entry(c).or_insert(0) += 1;
+        if zipped.all(|(decrypted, required)| decrypted == required) {
+            return Ok(id);
         }
     }
 
+    eyre::bail!("no room with north pole objects")
+}
+
+/// Name and sector id of the room if it's real
+fn parse_line<'a>(
+    input: &str,
+    line: &'a str,
+    counts: &mut HashMap<char, u8>,
+) -> Result<Option<(&'a str, usize)>, ParseError> {
+    let (room, checksum) = line
+        .strip_suffix(']')
+        .and_then(|line| line.rsplit_once('['))
+        .context_at(input, line, "expected `[checksum]`")?;
+
+    let (name, id) = room
+        .rsplit_once('-')
+        .context_at(input, line, "expected sector id")?;
+
+    let id = parse_at(input, id)?;
+
+    if let Some(i) = name.find(|c: char| c != '-' && !c.is_ascii_lowercase()) {
+        return Err(ParseError::at(input, &name[i..], "invalid name"));
+    }
+
+    for c in name.chars().filter(|&c| c != '-') {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+
     let mut sorted: Vec<_> = counts.iter().collect();
     sorted.sort_unstable_by(|(l1, c1), (l2, c2)| c2.cmp(c1).then_with(|| l1.cmp(l2)));
 
@@ -66,9 +86,5 @@ fn parse_line(line: &str, counts: &mut HashMap<char, u8>) -> Option<usize> {
 
     counts.clear();
 
-    if valid == 5 {
-        Some(id.parse().unwrap())
-    } else {
-        None
-    }
+    Ok((valid == 5).then_some((name, id)))
 }
@@ -6,6 +6,11 @@ use std::{
 
 use aoc_rust::Solution;
 
+aoc_rust::register_day! {
+    day: 5,
+    title: "How About a Nice Game of Chess?",
+}
+
 pub fn run(input: &str) -> eyre::Result<Solution> {
     Ok(Solution::new().part1(part1(input)).part2(part2(input)))
 }
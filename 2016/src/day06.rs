@@ -1,11 +1,29 @@
 use std::collections::{BTreeMap, HashMap};
 
-use aoc_rust::Solution;
+use aoc_rust::{util::parse::ParseError, Solution};
+
+aoc_rust::register_day! {
+    day: 6,
+    title: "Signals and Noise",
+}
 
 pub fn run(input: &str) -> eyre::Result<Solution> {
     let mut columns = BTreeMap::new();
+    let lines = input.lines().filter(|line| !line.is_empty());
+
+    let len = lines
+        .clone()
+        .next()
+        .ok_or_else(|| ParseError::new("expected at least one message"))?
+        .len();
+
+    for line in lines {
+        if line.len() != len {
+            return Err(
+                ParseError::at(input, line, format_args!("expected {len} characters")).into(),
+            );
+        }
 
-    for line in input.lines() {
         for (i, c) in line.chars().enumerate() {
             *columns
                 .entry(i)
@@ -24,14 +42,37 @@ type Columns = BTreeMap<usize, HashMap<char, usize>>;
 
 pub fn part1(columns: &Columns) -> String {
     columns
-        .iter()
-        .map(|(_, counts)| counts.iter().max_by_key(|(_, count)| *count).unwrap().0)
+        .values()
+        .filter_map(|counts| counts.iter().max_by_key(|(_, count)| *count))
+        .map(|(&c, _)| c)
         .collect()
 }
 
 pub fn part2(columns: &Columns) -> String {
     columns
-        .iter()
-        .map(|(_, counts)| counts.iter().min_by_key(|(_, count)| *count).unwrap().0)
+        .values()
+        .filter_map(|counts| counts.iter().min_by_key(|(_, count)| *count))
+        .map(|(&c, _)| c)
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "eedadn\ndrvtee\neandsr\nraavrd\natevrs\ntsrnev\nsdttsa\nrasrtv\nnssdts\nntnada\nsvetve\ntesnvt\nvntsnd\nvrdear\ndvrsen\nenarar\n";
+
+        let solution = run(input).unwrap();
+        assert_eq!(solution.part1.to_string(), "easter");
+        assert_eq!(solution.part2.to_string(), "advent");
+    }
+
+    #[test]
+    fn ragged() {
+        let err = run("abc\nab\n").unwrap_err();
+        assert!(err.to_string().contains("expected 3 characters"), "{err}");
+        assert!(run("").is_err());
+    }
+}
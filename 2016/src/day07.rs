@@ -2,6 +2,11 @@ use std::collections::HashMap;
 
 use aoc_rust::Solution;
 
+aoc_rust::register_day! {
+    day: 7,
+    title: "Internet Protocol Version 7",
+}
+
 pub fn run(input: &str) -> eyre::Result<Solution> {
     Ok(Solution::new().part1(part1(input)).part2(part2(input)))
 }
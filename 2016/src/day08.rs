@@ -1,14 +1,22 @@
-use std::{fmt, str::FromStr, thread, time::Duration};
-
-use aoc_rust::Solution;
+use aoc_rust::{
+    util::parse::{parse_at, ParseContext, ParseError},
+    AsciiArt, Solution,
+};
+
+aoc_rust::register_day! {
+    day: 8,
+    title: "Two-Factor Authentication",
+}
 
 pub fn run(input: &str) -> eyre::Result<Solution> {
     let ops = input
         .lines()
-        .map(Op::from_str)
+        .map(|line| Op::parse(input, line))
         .collect::<Result<Vec<_>, _>>()?;
 
-    Ok(Solution::new().part1(part1(&ops)))
+    let display = apply_all(&ops);
+
+    Ok(Solution::new().part1(part1(&display)).part2(part2(display)))
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -21,22 +29,24 @@ pub enum Op {
 const W: usize = 50;
 const H: usize = 6;
 
-pub fn part1(ops: &[Op]) -> usize {
+fn apply_all(ops: &[Op]) -> Display {
     let mut display = Display::default();
 
     for &op in ops {
-        print!("\x1B[2J\x1B[1;1H");
         display.apply(op);
-        println!("{}", display);
-        thread::sleep(Duration::from_millis(100));
     }
 
-    print!("\x1B[2J\x1B[1;1H");
-    println!("{}", display);
+    display
+}
 
+fn part1(display: &Display) -> usize {
     display.grid.iter().filter(|&&b| b).count()
 }
 
+fn part2(display: Display) -> AsciiArt {
+    AsciiArt::new(W, display.grid)
+}
+
 struct Display {
     grid: [bool; W * H],
 }
@@ -51,7 +61,7 @@ impl Display {
                     }
                 }
             }
-            Op::RotateRow(y, k) => self.grid[y * W..(y + 1) * W].rotate_right(k),
+            Op::RotateRow(y, k) => self.grid[y * W..(y + 1) * W].rotate_right(k % W),
             Op::RotateCol(x, rot) => {
                 for _ in 0..rot {
                     for y in 0..H {
@@ -71,87 +81,53 @@ impl Default for Display {
     }
 }
 
-impl fmt::Display for Display {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut rows = self.grid.chunks_exact(W);
-
-        if let Some(first) = rows.next() {
-            for &e in first {
-                write!(f, "{}", if e { '#' } else { '.' })?;
-            }
-
-            for row in rows {
-                f.write_str("\n")?;
-
-                for &e in row {
-                    write!(f, "{}", if e { '#' } else { '.' })?;
-                }
-            }
-        }
-
-        Ok(())
-    }
-}
-
-#[derive(Debug)]
-pub enum OpParseError {
-    InsufficientWords,
-    UnknownOperation,
-    InvalidRectDimension,
-    MissingRotateValue,
-}
-
-impl std::error::Error for OpParseError {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        None
-    }
-}
+impl Op {
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let mut words = line.split(' ');
+        let mut word = || words.next().context_at(input, line, "expected more words");
 
-impl fmt::Display for OpParseError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Debug::fmt(self, f)
-    }
-}
+        let op = match word()? {
+            "rect" => {
+                let dims = word()?;
 
-impl FromStr for Op {
-    type Err = OpParseError;
+                let (w, h) = dims
+                    .split_once('x')
+                    .context_at(input, dims, "expected `WxH`")?;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut words = s.split(' ');
-
-        let op = words.next().ok_or(OpParseError::InsufficientWords)?;
-        let dim = words.next().ok_or(OpParseError::InsufficientWords)?;
-
-        let op = match (op, dim) {
-            ("rect", dim) => {
-                let mut dims = dim.split('x').map(|s| s.parse().unwrap());
-
-                let w = dims.next().ok_or(OpParseError::InvalidRectDimension)?;
-                let h = dims.next().ok_or(OpParseError::InvalidRectDimension)?;
+                let w = parse_bounded(input, w, W + 1)?;
+                let h = parse_bounded(input, h, H + 1)?;
 
                 Op::Rect(w, h)
             }
-            ("rotate", dim) => {
-                let val = words
-                    .next()
-                    .ok_or(OpParseError::InsufficientWords)?
-                    .split('=')
-                    .nth(1)
-                    .ok_or(OpParseError::MissingRotateValue)?;
-                let val = val.parse().unwrap();
-
-                let rotation = words.nth(1).ok_or(OpParseError::InsufficientWords)?;
-                let rotation = rotation.parse().unwrap();
+            "rotate" => {
+                let dim = word()?;
+                let target = word()?;
+
+                let (_, val) =
+                    target
+                        .split_once('=')
+                        .context_at(input, target, "expected `x=` or `y=`")?;
+
+                let rotation = word().and_then(|_| word())?;
+                let rotation = parse_at(input, rotation)?;
 
                 match dim {
-                    "row" => Op::RotateRow(val, rotation),
-                    "column" => Op::RotateCol(val, rotation),
-                    _ => return Err(OpParseError::UnknownOperation),
+                    "row" => Op::RotateRow(parse_bounded(input, val, H)?, rotation),
+                    "column" => Op::RotateCol(parse_bounded(input, val, W)?, rotation),
+                    _ => return Err(ParseError::at(input, dim, "expected `row` or `column`")),
                 }
             }
-            _ => return Err(OpParseError::UnknownOperation),
+            op => return Err(ParseError::at(input, op, "unknown operation")),
         };
 
         Ok(op)
     }
 }
+
+fn parse_bounded(input: &str, s: &str, bound: usize) -> Result<usize, ParseError> {
+    parse_at(input, s).ok().filter(|&n| n < bound).context_at(
+        input,
+        s,
+        format_args!("expected a number below {bound}"),
+    )
+}
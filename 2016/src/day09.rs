@@ -1,22 +1,27 @@
-use aoc_rust::Solution;
+use aoc_rust::{util::parse::ParseError, Solution};
 
-pub fn run(input: &str) -> eyre::Result<Solution> {
-    let input = input.as_bytes();
+aoc_rust::register_day! {
+    day: 9,
+    title: "Explosives in Cyberspace",
+}
 
-    Ok(Solution::new().part1(part1(input)).part2(part2(input)))
+pub fn run(input: &str) -> eyre::Result<Solution> {
+    Ok(Solution::new().part1(part1(input)?).part2(part2(input)?))
 }
 
-pub fn part1(bytes: &[u8]) -> usize {
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    let bytes = input.as_bytes();
     let mut i = 0;
     let mut len = 0;
 
     while i < bytes.len() {
-        match get!(bytes, i) {
+        match bytes[i] {
             b' ' => i += 1,
-            b'(' => match parse_marker(get_ref!(bytes, i + 1..)) {
+            b'(' => match parse_marker(&bytes[i + 1..]) {
                 Some((size, reps, j)) => {
                     i += j + 2;
-                    let spaces = bytecount::count(get_ref!(bytes, i..i + size), b' ');
+                    let section = marked_section(input, i, size, bytes.len())?;
+                    let spaces = bytecount::count(section, b' ');
                     len += reps * (size - spaces);
                     i += size;
                 }
@@ -32,20 +37,26 @@ pub fn part1(bytes: &[u8]) -> usize {
         }
     }
 
-    len
+    Ok(len)
 }
 
-pub fn part2(bytes: &[u8]) -> usize {
-    let mut i = 0;
+pub fn part2(input: &str) -> Result<usize, ParseError> {
+    decompressed_len(input, 0, input.len())
+}
+
+fn decompressed_len(input: &str, start: usize, end: usize) -> Result<usize, ParseError> {
+    let bytes = input.as_bytes();
+    let mut i = start;
     let mut len = 0;
 
-    while i < bytes.len() {
-        match get!(bytes, i) {
+    while i < end {
+        match bytes[i] {
             b' ' => i += 1,
-            b'(' => match parse_marker(get_ref!(bytes, i + 1..)) {
+            b'(' => match parse_marker(&bytes[i + 1..end]) {
                 Some((size, reps, j)) => {
                     i += j + 2;
-                    let decompressed = part2(get_ref!(bytes, i..i + size));
+                    marked_section(input, i, size, end)?;
+                    let decompressed = decompressed_len(input, i, i + size)?;
                     len += reps * decompressed;
                     i += size;
                 }
@@ -61,7 +72,16 @@ pub fn part2(bytes: &[u8]) -> usize {
         }
     }
 
-    len
+    Ok(len)
+}
+
+/// The `size` bytes following the marker that ends right before `start`
+fn marked_section(input: &str, start: usize, size: usize, end: usize) -> Result<&[u8], ParseError> {
+    input
+        .as_bytes()
+        .get(start..start + size)
+        .filter(|_| start + size <= end)
+        .ok_or_else(|| ParseError::at_offset(input, start, "marker exceeds its section"))
 }
 
 fn parse_marker(bytes: &[u8]) -> Option<(usize, usize, usize)> {
@@ -70,7 +90,7 @@ fn parse_marker(bytes: &[u8]) -> Option<(usize, usize, usize)> {
     let mut b = None;
 
     loop {
-        match get!(bytes, i) {
+        match *bytes.get(i)? {
             n if n.is_ascii_digit() => {
                 let val = a.get_or_insert(0);
                 *val = *val * 10 + (n & 0xF) as usize;
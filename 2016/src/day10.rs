@@ -1,36 +1,49 @@
 use std::collections::{HashMap, VecDeque};
 
-use aoc_rust::Solution;
-
-macro_rules! parse {
-    ($num:expr) => {
-        $num.unwrap().parse().unwrap()
-    };
+use aoc_rust::{
+    util::parse::{parse_at, ParseContext, ParseError},
+    Solution,
+};
+
+aoc_rust::register_day! {
+    day: 10,
+    title: "Balance Bots",
 }
 
 pub fn run(input: &str) -> eyre::Result<Solution> {
     let mut bots: HashMap<_, Bot> = HashMap::with_capacity(32);
     let mut instructions = VecDeque::with_capacity(256);
-    let mut outputs = [0; 32];
+    let mut outputs = [0; OUTPUTS];
     let mut p1 = None;
 
     for line in input.lines() {
         let mut words = line.split(' ');
+        let mut word = |skip| {
+            words
+                .nth(skip)
+                .context_at(input, line, "expected more words")
+        };
 
-        match words.next().unwrap() {
+        match word(0)? {
             "value" => {
-                let val = parse!(words.next());
-                let id = parse!(words.next_back());
+                let val = parse_at(input, word(0)?)?;
+                let id = parse_at(input, word(3)?)?;
 
-                assert_ne!(bots.entry(id).or_default().receive(val), None);
+                match bots.entry(id).or_default().receive(val) {
+                    Some(true) => {}
+                    Some(false) => eyre::bail!("bot {id} can't hold another value"),
+                    None => {
+                        p1.get_or_insert(id);
+                    }
+                }
             }
             "bot" => {
-                let id = parse!(words.next());
-                let low = (words.nth(3).unwrap(), parse!(words.next())).into();
-                let high = (words.nth(3).unwrap(), parse!(words.next())).into();
+                let id = parse_at(input, word(0)?)?;
+                let low = Receiver::parse(input, word(3)?, word(0)?)?;
+                let high = Receiver::parse(input, word(3)?, word(0)?)?;
                 instructions.push_back(Instruction { id, low, high });
             }
-            _ => unsafe { std::hint::unreachable_unchecked() },
+            word => return Err(ParseError::at(input, word, "unknown instruction").into()),
         }
     }
 
@@ -78,12 +91,13 @@ pub fn run(input: &str) -> eyre::Result<Solution> {
         }
     }
 
-    let p1 = p1.unwrap();
+    let p1 = p1.ok_or_else(|| eyre::eyre!("no bot compared {LOW} with {HIGH}"))?;
     let p2 = outputs[0] as usize * outputs[1] as usize * outputs[2] as usize;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
+const OUTPUTS: usize = 32;
 const LOW: u8 = 17;
 const HIGH: u8 = 61;
 
@@ -93,12 +107,15 @@ enum Receiver {
     Output(u8),
 }
 
-impl From<(&str, u8)> for Receiver {
-    fn from((ty, id): (&str, u8)) -> Self {
+impl Receiver {
+    fn parse(input: &str, ty: &str, id: &str) -> Result<Self, ParseError> {
+        let n = parse_at(input, id)?;
+
         match ty {
-            "output" => Self::Output(id),
-            "bot" => Self::Bot(id),
-            _ => unsafe { std::hint::unreachable_unchecked() },
+            "output" if (n as usize) < OUTPUTS => Ok(Self::Output(n)),
+            "output" => Err(ParseError::at(input, id, "unknown output")),
+            "bot" => Ok(Self::Bot(n)),
+            _ => Err(ParseError::at(input, ty, "expected `bot` or `output`")),
         }
     }
 }
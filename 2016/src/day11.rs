@@ -1,14 +1,162 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
 use aoc_rust::Solution;
+use eyre::Result;
+
+const TOP_FLOOR: u8 = 3;
+
+/// Pairs that fit into a state key next to the elevator
+const MAX_PAIRS: usize = (u64::BITS as usize - 2) / 4;
+
+aoc_rust::register_day! {
+    day: 11,
+    title: "Radioisotope Thermoelectric Generators",
+}
+
+pub fn run(input: &str) -> Result<Solution> {
+    let mut pairs = parse_pairs(input)?;
+    let p1 = min_steps(&pairs)?;
+
+    // elerium and dilithium generators and microchips on the first floor
+    pairs.extend([(0, 0), (0, 0)]);
+    let p2 = min_steps(&pairs)?;
+
+    Ok(Solution::new().part1(p1).part2(p2))
+}
+
+/// Floors of the generator and microchip for each element
+fn parse_pairs(input: &str) -> Result<Vec<(u8, u8)>> {
+    let mut elements: HashMap<&str, (Option<u8>, Option<u8>)> = HashMap::new();
+
+    for (floor, line) in input.lines().enumerate() {
+        if floor > TOP_FLOOR as usize {
+            eyre::bail!("expected at most {} floors", TOP_FLOOR + 1);
+        }
+
+        let floor = floor as u8;
+        let mut prev = "";
+
+        for word in line.split([' ', ',', '.']).filter(|word| !word.is_empty()) {
+            match word {
+                "generator" => elements.entry(prev).or_default().0 = Some(floor),
+                "microchip" => {
+                    let element = prev.trim_end_matches("-compatible");
+                    elements.entry(element).or_default().1 = Some(floor);
+                }
+                _ => {}
+            }
+
+            prev = word;
+        }
+    }
+
+    elements
+        .into_iter()
+        .map(|(element, pair)| match pair {
+            (Some(generator), Some(microchip)) => Ok((generator, microchip)),
+            (None, _) => Err(eyre::eyre!("missing generator for `{element}`")),
+            (_, None) => Err(eyre::eyre!("missing microchip for `{element}`")),
+        })
+        .collect()
+}
+
+/// BFS over states in which elements are interchangeable, i.e. only the
+/// elevator and the sorted floor pairs matter.
+fn min_steps(pairs: &[(u8, u8)]) -> Result<usize> {
+    if pairs.len() > MAX_PAIRS {
+        eyre::bail!("expected at most {MAX_PAIRS} elements, got {}", pairs.len());
+    }
 
-pub fn run(_input: &str) -> eyre::Result<Solution> {
-    Ok(Solution::new().part1(part1()).part2(part2()))
+    // even indices are generators, odd indices are their microchips
+    let items: Vec<u8> = pairs
+        .iter()
+        .flat_map(|&(generator, chip)| [generator, chip])
+        .collect();
+
+    let mut seen = HashSet::new();
+    seen.insert(state_key(0, &items));
+
+    let mut queue = VecDeque::new();
+    queue.push_back((0, items, 0));
+
+    while let Some((elevator, items, steps)) = queue.pop_front() {
+        if items.iter().all(|&floor| floor == TOP_FLOOR) {
+            return Ok(steps);
+        }
+
+        let here: Vec<_> = (0..items.len()).filter(|&i| items[i] == elevator).collect();
+
+        // moving below every item is never useful
+        let lowest = items.iter().copied().min().unwrap_or(0);
+
+        let up = (elevator < TOP_FLOOR).then(|| elevator + 1);
+        let down = elevator.checked_sub(1).filter(|&floor| floor >= lowest);
+
+        for next in [up, down].into_iter().flatten() {
+            for (i, &a) in here.iter().enumerate() {
+                let moves = here[i..].iter().map(|&b| (a, b));
+
+                for (a, b) in moves {
+                    let mut items = items.clone();
+                    items[a] = next;
+                    items[b] = next;
+
+                    if is_safe(&items) && seen.insert(state_key(next, &items)) {
+                        queue.push_back((next, items, steps + 1));
+                    }
+                }
+            }
+        }
+    }
+
+    eyre::bail!("no way to bring all items to the top floor")
+}
+
+/// Whether no microchip is on a floor with another generator
+/// without its own generator being there too
+fn is_safe(items: &[u8]) -> bool {
+    items.chunks_exact(2).all(|pair| {
+        let (generator, chip) = (pair[0], pair[1]);
+
+        generator == chip || items.iter().step_by(2).all(|&other| other != chip)
+    })
 }
 
-// Guessing ftw
-pub fn part1() -> usize {
-    33
+/// The elevator's floor in the top bits followed by four bits per sorted
+/// floor pair.
+fn state_key(elevator: u8, items: &[u8]) -> u64 {
+    let mut pairs: Vec<_> = items
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .collect();
+    pairs.sort_unstable();
+
+    pairs
+        .into_iter()
+        .fold(elevator as u64, |key, (generator, chip)| {
+            (key << 4) | ((generator as u64) << 2) | chip as u64
+        })
 }
 
-pub fn part2() -> usize {
-    57
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "\
+The first floor contains a hydrogen-compatible microchip and a lithium-compatible microchip.
+The second floor contains a hydrogen generator.
+The third floor contains a lithium generator.
+The fourth floor contains nothing relevant.";
+
+        let pairs = parse_pairs(input).unwrap();
+        assert_eq!(min_steps(&pairs).unwrap(), 11);
+    }
+
+    #[test]
+    fn too_many_elements() {
+        let err = min_steps(&[(TOP_FLOOR, TOP_FLOOR); MAX_PAIRS + 1]).unwrap_err();
+        assert_eq!(err.to_string(), "expected at most 15 elements, got 16");
+    }
 }
@@ -1,47 +1,72 @@
 use std::collections::{HashSet, VecDeque};
 
-use aoc_rust::Solution;
+use aoc_rust::{search::bidirectional_bfs, Solution};
+use eyre::WrapErr;
 
-pub fn run(_input: &str) -> eyre::Result<Solution> {
-    Ok(Solution::new().part1(part1()).part2(part2()))
+aoc_rust::params! {
+    pub struct Params {
+        goal_x: i64 = 31,
+        goal_y: i64 = 39,
+    }
 }
 
-fn is_open(x: i64, y: i64) -> bool {
-    (x * x + 3 * x + 2 * x * y + y + y * y + FAVOURITE_NUMBER).count_ones() % 2 == 0
+aoc_rust::register_day! {
+    day: 13,
+    title: "A Maze of Twisty Little Cubicles",
+    params: Params,
 }
 
-static OFFSETS: [(i64, i64); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+pub fn run(input: &str) -> eyre::Result<Solution> {
+    run_with_params(input, &Params::default())
+}
 
-const FAVOURITE_NUMBER: i64 = 1358;
-const GOAL_X: i64 = 31;
-const GOAL_Y: i64 = 39;
+pub fn run_with_params(input: &str, params: &Params) -> eyre::Result<Solution> {
+    let favourite_number = input
+        .trim()
+        .parse()
+        .wrap_err("expected the favourite number as input")?;
 
-pub fn part1() -> usize {
-    let mut queue = VecDeque::with_capacity(16);
-    let mut visited = HashSet::with_capacity(128);
-    queue.push_back((1, 1, 0));
-    visited.insert((1, 1));
+    let goal = (params.goal_x, params.goal_y);
 
-    while let Some((x, y, d)) = queue.pop_front() {
-        if x == GOAL_X && y == GOAL_Y {
-            return d;
-        }
+    let p1 = part1(favourite_number, goal)?;
+    let p2 = part2(favourite_number);
 
-        let neighbors = OFFSETS
-            .iter()
-            .map(|&(dx, dy)| (dx + x, dy + y))
-            .filter(|&(cx, cy)| cx >= 0 && cy >= 0)
-            .filter(|&(cx, cy)| is_open(cx, cy))
-            .filter(|&(cx, cy)| visited.insert((cx, cy)))
-            .map(|(cx, cy)| (cx, cy, d + 1));
+    Ok(Solution::new().part1(p1).part2(p2))
+}
 
-        queue.extend(neighbors);
+fn is_open(x: i64, y: i64, favourite_number: i64) -> bool {
+    (x * x + 3 * x + 2 * x * y + y + y * y + favourite_number).count_ones() % 2 == 0
+}
+
+static OFFSETS: [(i64, i64); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// Shortest path from `(1,1)` to the goal.
+///
+/// Paths leaving the area of twice the goal's coordinates are not considered
+/// so that the search ends if the goal is walled in.
+pub fn part1(favourite_number: i64, (goal_x, goal_y): (i64, i64)) -> eyre::Result<usize> {
+    if goal_x < 0 || goal_y < 0 || !is_open(goal_x, goal_y, favourite_number) {
+        eyre::bail!("goal ({goal_x},{goal_y}) is a wall");
     }
 
-    unsafe { std::hint::unreachable_unchecked() }
+    let (max_x, max_y) = (2 * goal_x.max(1), 2 * goal_y.max(1));
+
+    // moving between open cells is undirected so both sides share their
+    // neighbors
+    let neighbors = |&(x, y): &(i64, i64)| {
+        OFFSETS
+            .iter()
+            .map(move |&(dx, dy)| (dx + x, dy + y))
+            .filter(move |&(cx, cy)| (0..=max_x).contains(&cx) && (0..=max_y).contains(&cy))
+            .filter(move |&(cx, cy)| is_open(cx, cy, favourite_number))
+    };
+
+    bidirectional_bfs((1, 1), (goal_x, goal_y), neighbors, neighbors)
+        .map(|path| path.cost)
+        .ok_or_else(|| eyre::eyre!("goal ({goal_x},{goal_y}) is unreachable"))
 }
 
-pub fn part2() -> usize {
+pub fn part2(favourite_number: i64) -> usize {
     let mut stack = VecDeque::with_capacity(16);
     let mut visited = HashSet::with_capacity(256);
     stack.push_back((1, 1, 0));
@@ -56,7 +81,7 @@ pub fn part2() -> usize {
             .iter()
             .map(|&(dx, dy)| (dx + x, dy + y))
             .filter(|&(cx, cy)| cx >= 0 && cy >= 0)
-            .filter(|&(cx, cy)| is_open(cx, cy))
+            .filter(|&(cx, cy)| is_open(cx, cy, favourite_number))
             .filter(|&(cx, cy)| visited.insert((cx, cy)))
             .map(|(cx, cy)| (cx, cy, d + 1));
 
@@ -65,3 +90,26 @@ pub fn part2() -> usize {
 
     visited.len()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let params = Params {
+            goal_x: 7,
+            goal_y: 4,
+        };
+        let solution = run_with_params("10\n", &params).unwrap();
+        assert_eq!(solution.part1.to_string(), "11");
+        assert_eq!(solution.part2.to_string(), "151");
+    }
+
+    #[test]
+    fn unreachable() {
+        // (5,3) is open but enclosed by walls
+        let err = part1(10, (5, 3)).unwrap_err();
+        assert_eq!(err.to_string(), "goal (5,3) is unreachable");
+    }
+}
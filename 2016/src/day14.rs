@@ -3,20 +3,29 @@ use std::fmt::Write;
 use aoc_rust::Solution;
 use fxhash::FxHashMap as HashMap;
 
-pub fn run(_input: &str) -> eyre::Result<Solution> {
-    Ok(Solution::new().part1(part1()).part2(part2()))
+aoc_rust::register_day! {
+    day: 14,
+    title: "One-Time Pad",
 }
 
-const INPUT: &str = "zpqevtbw";
+pub fn run(input: &str) -> eyre::Result<Solution> {
+    let salt = input.trim();
 
-pub fn part1() -> u16 {
+    if salt.is_empty() {
+        eyre::bail!("expected the salt as input");
+    }
+
+    Ok(Solution::new().part1(part1(salt)).part2(part2(salt)))
+}
+
+pub fn part1(salt: &str) -> u16 {
     let mut idx = 0;
 
     let mut awaiting = HashMap::default();
     let mut done = 0;
 
     loop {
-        let hash = md5::compute(format!("{}{}", INPUT, idx));
+        let hash = md5::compute(format!("{salt}{idx}"));
 
         if let Some(i) = check_hash(idx, &*hash, &mut awaiting, &mut done) {
             return i;
@@ -26,14 +35,14 @@ pub fn part1() -> u16 {
     }
 }
 
-pub fn part2() -> u16 {
+pub fn part2(salt: &str) -> u16 {
     let mut idx = 0;
 
     let mut awaiting = HashMap::default();
     let mut done = 0;
 
     loop {
-        let mut buf = format!("{}{}", INPUT, idx);
+        let mut buf = format!("{salt}{idx}");
 
         let hash = (0..2016).fold(md5::compute(&buf), |last, _| {
             buf.clear();
@@ -1,4 +1,15 @@
-use aoc_rust::Solution;
+use aoc_rust::{
+    util::{
+        numbers,
+        parse::{parse_at, ParseContext, ParseError},
+    },
+    Solution,
+};
+
+aoc_rust::register_day! {
+    day: 15,
+    title: "Timing is Everything",
+}
 
 pub fn run(input: &str) -> eyre::Result<Solution> {
     let mut residues = Vec::new();
@@ -7,59 +18,45 @@ pub fn run(input: &str) -> eyre::Result<Solution> {
     for (i, line) in input.lines().enumerate() {
         let mut words = line.split(' ');
 
-        let m = words.nth(3).unwrap().parse().unwrap();
-        let last = words.last().unwrap();
-        let pos = &last[..last.len() - 1].parse().unwrap();
+        let m = words
+            .nth(3)
+            .context_at(input, line, "expected amount of positions")?;
+
+        let m: i64 = match parse_at(input, m)? {
+            0 => return Err(ParseError::at(input, m, "expected positions").into()),
+            m => m,
+        };
+
+        let pos = words
+            .next_back()
+            .and_then(|last| last.strip_suffix('.'))
+            .context_at(input, line, "expected position at the end")?;
+
+        let pos: i64 = parse_at(input, pos)?;
 
         modulii.push(m);
-        residues.push(m - pos - i as i32 - 1);
+        residues.push(m - pos - i as i64 - 1);
     }
 
     Ok(Solution::new()
-        .part1(part1(&residues, &modulii))
-        .part2(part2(&mut residues, &mut modulii)))
+        .part1(part1(&residues, &modulii)?)
+        .part2(part2(&mut residues, &mut modulii)?))
 }
 
-pub fn part1(residues: &[i32], modulii: &[i32]) -> i32 {
-    chinese_remainder(residues, modulii).unwrap()
+pub fn part1(residues: &[i64], modulii: &[i64]) -> eyre::Result<i64> {
+    lowest_time(residues, modulii)
 }
 
-pub fn part2(residues: &mut Vec<i32>, modulii: &mut Vec<i32>) -> i32 {
-    let r = 10 - residues.len() as i32;
+pub fn part2(residues: &mut Vec<i64>, modulii: &mut Vec<i64>) -> eyre::Result<i64> {
+    let r = 10 - residues.len() as i64;
     residues.push(r);
     modulii.push(11);
 
-    chinese_remainder(residues, modulii).unwrap()
+    lowest_time(residues, modulii)
 }
 
-fn chinese_remainder(residues: &[i32], modulii: &[i32]) -> Option<i32> {
-    let prod: i32 = modulii.iter().product();
-    let mut sum = 0;
-
-    for (&residue, &modulus) in residues.iter().zip(modulii) {
-        let p = prod / modulus;
-        sum += residue * mod_inv(p, modulus)? * p
-    }
-
-    Some(sum % prod)
-}
-
-fn mod_inv(x: i32, n: i32) -> Option<i32> {
-    let (g, x, _) = egcd(x, n);
-
-    if g == 1 {
-        Some((x % n + n) % n)
-    } else {
-        None
-    }
-}
-
-fn egcd(a: i32, b: i32) -> (i32, i32, i32) {
-    if a == 0 {
-        (b, 0, 1)
-    } else {
-        let (g, x, y) = egcd(b % a, a);
-
-        (g, y - (b / a) * x, x)
-    }
+fn lowest_time(residues: &[i64], modulii: &[i64]) -> eyre::Result<i64> {
+    numbers::crt(residues.iter().copied().zip(modulii.iter().copied()))
+        .map(|(time, _)| time)
+        .ok_or_else(|| eyre::eyre!("discs never line up"))
 }
@@ -1,10 +1,35 @@
 use aoc_rust::Solution;
 
-const DISK_SIZE_PART1: usize = 272;
-const DISK_SIZE_PART2: usize = 35_651_584;
+aoc_rust::params! {
+    pub struct Params {
+        disk_size_part1: usize = 272,
+        disk_size_part2: usize = 35_651_584,
+    }
+}
+
+aoc_rust::register_day! {
+    day: 16,
+    title: "Dragon Checksum",
+    params: Params,
+}
 
 pub fn run(input: &str) -> eyre::Result<Solution> {
-    let mut input = input.as_bytes().to_owned();
+    run_with_params(input, &Params::default())
+}
+
+pub fn run_with_params(input: &str, params: &Params) -> eyre::Result<Solution> {
+    let &Params {
+        disk_size_part1,
+        disk_size_part2,
+    } = params;
+
+    for size in [disk_size_part1, disk_size_part2] {
+        if size == 0 || size % 2 == 1 {
+            eyre::bail!("disk size must be even and positive, got {size}");
+        }
+    }
+
+    let mut input = input.trim().as_bytes().to_owned();
 
     for byte in input.iter_mut() {
         *byte = *byte & 1;
@@ -12,11 +37,11 @@ pub fn run(input: &str) -> eyre::Result<Solution> {
 
     let mut input_clone = input.clone();
 
-    input_clone.reserve(DISK_SIZE_PART1 * 2);
-    let p1 = calc_checksum(input_clone, DISK_SIZE_PART1);
+    input_clone.reserve(disk_size_part1 * 2);
+    let p1 = calc_checksum(input_clone, disk_size_part1);
 
-    input.reserve(DISK_SIZE_PART2 * 2);
-    let p2 = calc_checksum(input, DISK_SIZE_PART2);
+    input.reserve(disk_size_part2 * 2);
+    let p2 = calc_checksum(input, disk_size_part2);
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -51,3 +76,19 @@ fn calc_checksum(mut data: Vec<u8>, size: usize) -> String {
 
     buf.into_iter().map(|byte| (byte + b'0') as char).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let params = Params {
+            disk_size_part1: 20,
+            disk_size_part2: 20,
+        };
+        let solution = run_with_params("10000\n", &params).unwrap();
+        assert_eq!(solution.part1.to_string(), "01100");
+        assert_eq!(solution.part2.to_string(), "01100");
+    }
+}
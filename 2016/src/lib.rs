@@ -1,6 +1,8 @@
 #[macro_use]
 extern crate aoc_rust;
 
+pub mod assembunny;
+
 pub mod day03;
 pub mod day04;
 pub mod day05;
@@ -15,3 +17,9 @@ pub mod day13;
 pub mod day14;
 pub mod day15;
 pub mod day16;
+
+aoc_rust::register_year! {
+    2016:
+        day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13, day14, day15,
+        day16,
+}
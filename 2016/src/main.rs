@@ -1,16 +1,16 @@
-use std::{fs::read_to_string, time::Instant};
+use std::fs::read_to_string;
 
-fn main() {
-    let input = read_to_string("./inputs/day03.txt").unwrap();
+use aoc_rust::timing;
+use eyre::Result;
 
-    let start = Instant::now();
-    let p1 = aoc16::day03::part1(&input);
-    println!("Part 1: {p1} [{:?}]", start.elapsed());
+fn main() -> Result<()> {
+    let input = read_to_string("./inputs/day03.txt")?;
 
-    let start = Instant::now();
-    let p2 = aoc16::day03::part2(&input);
-    println!("Part 2: {p2} [{:?}]", start.elapsed());
+    let (solution, timings) = timing::time(|| aoc16::day03::run(&input));
+    let solution = solution?;
 
-    assert_eq!(p1, 983);
-    assert_eq!(p2, 1836);
+    print!("{solution}");
+    println!("Elapsed: {timings}");
+
+    Ok(())
 }
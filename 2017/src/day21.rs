@@ -6,6 +6,11 @@ use self::{
     rule::{Rule23, Rule34, Rules},
 };
 
+aoc_rust::register_day! {
+    day: 21,
+    title: "Fractal Art",
+}
+
 pub fn run(input: &str) -> Result<Solution, Report> {
     let rules: Rules = input.trim().parse()?;
 
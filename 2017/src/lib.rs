@@ -1 +1,6 @@
 pub mod day21;
+
+aoc_rust::register_year! {
+    2017:
+        day21,
+}
@@ -0,0 +1,180 @@
+use std::fmt::{Display, Formatter, Result as FmtResult, Write};
+
+use eyre::Result;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Opcode {
+    Add,
+    Mul,
+    Input,
+    Output,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    RelativeBase,
+    Halt,
+}
+
+impl Opcode {
+    pub fn new(code: i64) -> Result<Self> {
+        let opcode = match code % 100 {
+            1 => Self::Add,
+            2 => Self::Mul,
+            3 => Self::Input,
+            4 => Self::Output,
+            5 => Self::JumpIfTrue,
+            6 => Self::JumpIfFalse,
+            7 => Self::LessThan,
+            8 => Self::Equals,
+            9 => Self::RelativeBase,
+            99 => Self::Halt,
+            opcode => bail!("Can't process opcode {opcode}"),
+        };
+
+        Ok(opcode)
+    }
+
+    /// Amount of parameters following the opcode
+    pub fn params(self) -> usize {
+        match self {
+            Self::Add | Self::Mul | Self::LessThan | Self::Equals => 3,
+            Self::JumpIfTrue | Self::JumpIfFalse => 2,
+            Self::Input | Self::Output | Self::RelativeBase => 1,
+            Self::Halt => 0,
+        }
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Mul => "mul",
+            Self::Input => "in",
+            Self::Output => "out",
+            Self::JumpIfTrue => "jnz",
+            Self::JumpIfFalse => "jz",
+            Self::LessThan => "lt",
+            Self::Equals => "eq",
+            Self::RelativeBase => "arb",
+            Self::Halt => "halt",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Mode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+impl Mode {
+    fn new(mode: i64) -> Result<Self> {
+        match mode {
+            0 => Ok(Self::Position),
+            1 => Ok(Self::Immediate),
+            2 => Ok(Self::Relative),
+            mode => bail!("Unknown parameter mode {mode}"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Param {
+    pub mode: Mode,
+    pub value: i64,
+}
+
+impl Display for Param {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.mode {
+            Mode::Position => write!(f, "[{}]", self.value),
+            Mode::Immediate => write!(f, "{}", self.value),
+            Mode::Relative => write!(f, "[rb{:+}]", self.value),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub params: Vec<Param>,
+}
+
+impl Instruction {
+    /// Decode the instruction at `pc`.
+    /// Memory past the end of `mem` is considered to be zero.
+    pub fn decode(mem: &[i64], pc: usize) -> Result<Self> {
+        let code = mem.get(pc).copied().unwrap_or(0);
+        let opcode = Opcode::new(code)?;
+
+        let params = (1..=opcode.params())
+            .map(|i| {
+                let mode = Mode::new((code / 10_i64.pow(i as u32 + 1)) % 10)?;
+                let value = mem.get(pc + i).copied().unwrap_or(0);
+
+                Ok(Param { mode, value })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { opcode, params })
+    }
+
+    /// Amount of memory cells occupied by the instruction
+    pub fn size(&self) -> usize {
+        1 + self.params.len()
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.opcode.mnemonic())?;
+
+        for (i, param) in self.params.iter().enumerate() {
+            let sep = if i == 0 { " " } else { ", " };
+            write!(f, "{sep}{param}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Disassemble a program into one line per instruction, prefixed by its address.
+///
+/// Cells that can't be decoded are displayed as raw data.
+pub fn disassemble(mem: &[i64]) -> String {
+    let mut listing = String::new();
+    let mut pc = 0;
+
+    while pc < mem.len() {
+        match Instruction::decode(mem, pc) {
+            Ok(instruction) => {
+                let _ = writeln!(listing, "{pc:>5}: {instruction}");
+                pc += instruction.size();
+            }
+            Err(_) => {
+                let _ = writeln!(listing, "{pc:>5}: data {}", mem[pc]);
+                pc += 1;
+            }
+        }
+    }
+
+    listing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disassemble() {
+        let listing = disassemble(&[1002, 4, 3, 4, 33, 109, -1, 204, 1, 99]);
+        let expected = "    0: mul [4], 3, [4]
+    4: data 33
+    5: arb -1
+    7: out [rb+1]
+    9: halt
+";
+        assert_eq!(listing, expected);
+    }
+}
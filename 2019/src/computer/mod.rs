@@ -1,8 +1,14 @@
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 
-use crate::Error;
+use eyre::{Result, WrapErr};
 
-type Result<T> = std::result::Result<T, Error>;
+pub use self::{
+    disasm::{disassemble, Instruction, Mode, Opcode, Param},
+    network::{Network, Packet, Topology},
+};
+
+mod disasm;
+mod network;
 
 pub struct Computer {
     memory: Vec<i64>,
@@ -11,14 +17,19 @@ pub struct Computer {
     input: VecDeque<i64>,
     output: VecDeque<i64>,
     state: State,
+    instructions: u64,
+    breakpoints: HashSet<usize>,
+    trace: bool,
 }
 
 impl Computer {
     pub fn new(input: &str) -> Result<Self> {
         let memory = input
+            .trim_end()
             .split(',')
-            .map(|n| n.parse().map_err(Error::from))
-            .collect::<Result<Vec<_>>>()?;
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .wrap_err("Invalid intcode program")?;
 
         Ok(Computer {
             memory,
@@ -27,9 +38,15 @@ impl Computer {
             input: VecDeque::new(),
             output: VecDeque::new(),
             state: State::Ready,
+            instructions: 0,
+            breakpoints: HashSet::new(),
+            trace: false,
         })
     }
 
+    /// Run until the program halts, waits for input, or reaches a breakpoint.
+    ///
+    /// When resuming from a breakpoint, the instruction at that breakpoint is executed.
     pub fn run(&mut self) -> Result<&mut Self> {
         if self.state == State::Done {
             return Ok(self);
@@ -37,25 +54,49 @@ impl Computer {
 
         if self.state == State::Wait {
             if self.input.is_empty() {
-                bail!("Cannot run while waiting for input");
+                let instruction = Instruction::decode(&self.memory, self.pc)?;
+
+                bail!(
+                    "Cannot run while waiting for input (pc={}: {instruction})",
+                    self.pc
+                );
             }
 
             self.state = State::Ready;
         }
 
-        if self.memory[self.pc] == 99 {
-            self.state = State::Done;
+        let mut resume = self.state == State::Break;
+        self.state = State::Ready;
 
-            return Ok(self);
-        }
+        loop {
+            if !resume && self.breakpoints.contains(&self.pc) {
+                self.state = State::Break;
+
+                return Ok(self);
+            }
 
-        while self.step()? == State::Ready {}
+            resume = false;
 
-        Ok(self)
+            if self.step()? != State::Ready {
+                return Ok(self);
+            }
+        }
     }
 
     pub fn step(&mut self) -> Result<State> {
-        let Some(op) = Operation::new(&mut self.memory, self.pc, self.rb)? else {
+        if self.trace {
+            match Instruction::decode(&self.memory, self.pc) {
+                Ok(instruction) => eprintln!("{:>5}: {instruction}", self.pc),
+                Err(err) => eprintln!("{:>5}: {err}", self.pc),
+            }
+        }
+
+        let op = Operation::new(&mut self.memory, self.pc, self.rb)
+            .wrap_err_with(|| format!("Invalid instruction at pc={}", self.pc))?;
+
+        let Some(op) = op else {
+            self.state = State::Done;
+
             return Ok(State::Done);
         };
 
@@ -91,6 +132,8 @@ impl Computer {
         }
 
         self.pc += op.pc();
+        self.instructions += 1;
+        self.state = State::Ready;
 
         Ok(State::Ready)
     }
@@ -108,13 +151,98 @@ impl Computer {
     pub fn output_iter(&self) -> impl Iterator<Item = &'_ i64> {
         self.output.iter()
     }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn memory(&self) -> &[i64] {
+        &self.memory
+    }
+
+    pub fn memory_mut(&mut self) -> &mut [i64] {
+        &mut self.memory
+    }
+
+    /// Amount of instructions executed so far
+    pub fn instructions(&self) -> u64 {
+        self.instructions
+    }
+
+    /// Disassemble the current memory, see [`disassemble`].
+    pub fn disassemble(&self) -> String {
+        disassemble(&self.memory)
+    }
+
+    /// Pause [`Computer::run`] before executing the instruction at `addr`.
+    pub fn add_breakpoint(&mut self, addr: usize) -> &mut Self {
+        self.breakpoints.insert(addr);
+
+        self
+    }
+
+    pub fn remove_breakpoint(&mut self, addr: usize) -> &mut Self {
+        self.breakpoints.remove(&addr);
+
+        self
+    }
+
+    /// Print each instruction to stderr before it's executed.
+    pub fn trace(&mut self, trace: bool) -> &mut Self {
+        self.trace = trace;
+
+        self
+    }
+
+    /// Capture the current state of execution.
+    ///
+    /// Breakpoints and tracing are not part of the snapshot.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            memory: self.memory.clone(),
+            pc: self.pc,
+            rb: self.rb,
+            input: self.input.clone(),
+            output: self.output.clone(),
+            state: self.state,
+            instructions: self.instructions,
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: &Snapshot) -> &mut Self {
+        self.memory.clone_from(&snapshot.memory);
+        self.pc = snapshot.pc;
+        self.rb = snapshot.rb;
+        self.input.clone_from(&snapshot.input);
+        self.output.clone_from(&snapshot.output);
+        self.state = snapshot.state;
+        self.instructions = snapshot.instructions;
+
+        self
+    }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum State {
     Ready,
     Done,
     Wait,
+    Break,
+}
+
+#[derive(Clone)]
+pub struct Snapshot {
+    memory: Vec<i64>,
+    pc: usize,
+    rb: i64,
+    input: VecDeque<i64>,
+    output: VecDeque<i64>,
+    state: State,
+    instructions: u64,
 }
 
 enum Operation {
@@ -214,3 +342,45 @@ impl Operation {
         Ok(idx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Outputs 1 if the input is equal to 8, 0 otherwise
+    const EQUALS_8: &str = "3,9,8,9,10,9,4,9,99,-1,8";
+
+    #[test]
+    fn test_breakpoint_snapshot() {
+        let mut computer = Computer::new(EQUALS_8).unwrap();
+        computer.add_breakpoint(6).insert(8).run().unwrap();
+        assert_eq!(computer.state(), State::Break);
+        assert_eq!(computer.pc(), 6);
+        assert_eq!(computer.instructions(), 2);
+        assert_eq!(computer.memory()[9], 1);
+
+        let snapshot = computer.snapshot();
+        computer.run().unwrap();
+        assert_eq!(computer.state(), State::Done);
+        assert_eq!(computer.pop(), Some(1));
+        assert_eq!(computer.instructions(), 3);
+
+        computer.restore(&snapshot);
+        computer.memory_mut()[9] = 42;
+        computer.run().unwrap();
+        assert_eq!(computer.pop(), Some(42));
+    }
+
+    #[test]
+    fn test_wait_for_input() {
+        let mut computer = Computer::new(EQUALS_8).unwrap();
+        computer.run().unwrap();
+        assert_eq!(computer.state(), State::Wait);
+
+        let err = computer.run().err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Cannot run while waiting for input (pc=0: in [9])"
+        );
+    }
+}
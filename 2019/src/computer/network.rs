@@ -0,0 +1,242 @@
+use std::collections::VecDeque;
+
+use eyre::Result;
+
+use super::{Computer, State};
+
+/// Values sent from one machine to another
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Packet {
+    /// Address of the sending machine, `None` if sent from outside the network
+    pub src: Option<usize>,
+    pub dst: usize,
+    pub data: Vec<i64>,
+}
+
+/// How outputs of a machine are routed to the inputs of other machines
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Topology {
+    /// Every output goes to the next machine, outputs of the last machine leave the network
+    Chain,
+    /// Every output goes to the next machine, the last machine feeds into the first one
+    Ring,
+    /// Outputs are grouped into packets of the given length whose first value is the
+    /// destination address; addresses outside of the network leave the network
+    Addressed { packet_len: usize },
+}
+
+pub struct Network {
+    machines: Vec<Computer>,
+    topology: Topology,
+    queues: Vec<VecDeque<i64>>,
+    buffers: Vec<Vec<i64>>,
+    idle_input: Option<i64>,
+    idle: bool,
+    external: VecDeque<Packet>,
+    log: Vec<Packet>,
+}
+
+impl Network {
+    pub fn new(machines: Vec<Computer>, topology: Topology) -> Result<Self> {
+        if let Topology::Addressed { packet_len: 0 } = topology {
+            eyre::bail!("packets must at least contain their destination address");
+        }
+
+        let len = machines.len();
+
+        Ok(Self {
+            machines,
+            topology,
+            queues: vec![VecDeque::new(); len],
+            buffers: vec![Vec::new(); len],
+            idle_input: None,
+            idle: false,
+            external: VecDeque::new(),
+            log: Vec::new(),
+        })
+    }
+
+    /// Value to provide to machines that wait for input while their queue is empty.
+    ///
+    /// Without it, such machines are not run until they receive a packet.
+    pub fn idle_input(mut self, value: i64) -> Self {
+        self.idle_input = Some(value);
+
+        self
+    }
+
+    /// Send values from outside the network to the machine at `dst`.
+    pub fn send(&mut self, dst: usize, data: Vec<i64>) -> &mut Self {
+        self.deliver(Packet {
+            src: None,
+            dst,
+            data,
+        });
+
+        self
+    }
+
+    /// Run each machine in order of their address until it waits for input or halts.
+    ///
+    /// Returns whether any values were sent or received.
+    pub fn round(&mut self) -> Result<bool> {
+        let mut active = false;
+
+        for addr in 0..self.machines.len() {
+            let machine = &mut self.machines[addr];
+
+            if machine.state() == State::Done {
+                continue;
+            }
+
+            if !self.queues[addr].is_empty() {
+                for value in self.queues[addr].drain(..) {
+                    machine.insert(value);
+                }
+
+                active = true;
+            } else if let Some(value) = self.idle_input {
+                machine.insert(value);
+            } else if machine.state() == State::Wait {
+                continue;
+            }
+
+            machine.run()?;
+
+            while let Some(value) = machine.pop() {
+                self.buffers[addr].push(value);
+                active = true;
+            }
+
+            self.route(addr);
+        }
+
+        self.idle = !active;
+
+        Ok(active)
+    }
+
+    /// Run rounds until the network is idle or all machines halted.
+    pub fn run_until_idle(&mut self) -> Result<&mut Self> {
+        while self.round()? {}
+
+        Ok(self)
+    }
+
+    /// Whether no values were sent or received during the last round
+    pub fn is_idle(&self) -> bool {
+        self.idle
+    }
+
+    /// Whether all machines halted
+    pub fn is_done(&self) -> bool {
+        self.machines
+            .iter()
+            .all(|machine| machine.state() == State::Done)
+    }
+
+    /// Next packet that was sent to an address outside the network
+    pub fn pop_external(&mut self) -> Option<Packet> {
+        self.external.pop_front()
+    }
+
+    /// All packets sent so far, in order
+    pub fn log(&self) -> &[Packet] {
+        &self.log
+    }
+
+    pub fn machine(&self, addr: usize) -> &Computer {
+        &self.machines[addr]
+    }
+
+    pub fn machine_mut(&mut self, addr: usize) -> &mut Computer {
+        &mut self.machines[addr]
+    }
+
+    fn route(&mut self, src: usize) {
+        let len = self.machines.len();
+
+        let packets: Vec<_> = match self.topology {
+            Topology::Chain | Topology::Ring => {
+                let dst = match self.topology {
+                    Topology::Ring => (src + 1) % len,
+                    _ => src + 1,
+                };
+
+                self.buffers[src]
+                    .drain(..)
+                    .map(|value| (dst, vec![value]))
+                    .collect()
+            }
+            Topology::Addressed { packet_len } => {
+                let complete = self.buffers[src].len() - self.buffers[src].len() % packet_len;
+
+                self.buffers[src]
+                    .drain(..complete)
+                    .collect::<Vec<_>>()
+                    .chunks_exact(packet_len)
+                    .map(|chunk| (chunk[0] as usize, chunk[1..].to_vec()))
+                    .collect()
+            }
+        };
+
+        for (dst, data) in packets {
+            self.deliver(Packet {
+                src: Some(src),
+                dst,
+                data,
+            });
+        }
+    }
+
+    fn deliver(&mut self, packet: Packet) {
+        match self.queues.get_mut(packet.dst) {
+            Some(queue) => queue.extend(&packet.data),
+            None => self.external.push_back(packet.clone()),
+        }
+
+        self.log.push(packet);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reads its address, then forwards every value other than -1 to address 255
+    const FORWARD: &str = "3,100,3,101,1008,101,-1,102,1005,102,2,104,255,4,100,4,101,1105,1,2";
+
+    #[test]
+    fn test_addressed() {
+        let machines = (0..2)
+            .map(|addr| {
+                let mut computer = Computer::new(FORWARD).unwrap();
+                computer.insert(addr);
+                computer
+            })
+            .collect();
+        let mut network = Network::new(machines, Topology::Addressed { packet_len: 3 })
+            .unwrap()
+            .idle_input(-1);
+        network.send(1, vec![8]).send(0, vec![7]);
+        network.run_until_idle().unwrap();
+        assert!(network.is_idle());
+        assert!(!network.is_done());
+
+        let external = |src: usize, data: Vec<i64>| Packet {
+            src: Some(src),
+            dst: 255,
+            data,
+        };
+        assert_eq!(network.pop_external(), Some(external(0, vec![0, 7])));
+        assert_eq!(network.pop_external(), Some(external(1, vec![1, 8])));
+        assert_eq!(network.pop_external(), None);
+        assert_eq!(network.log().len(), 4);
+    }
+
+    #[test]
+    fn test_empty_packets() {
+        let machines = vec![Computer::new(FORWARD).unwrap()];
+        assert!(Network::new(machines, Topology::Addressed { packet_len: 0 }).is_err());
+    }
+}
@@ -1,14 +1,12 @@
-use crate::{Error, Solution};
+use aoc_rust::Solution;
+use eyre::Result;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 1,
+    title: "The Tyranny of the Rocket Equation",
 }
 
-pub fn solve(input: &str) -> Result<Solution<i32, i32>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     let (mut p1, mut p2) = (0, 0);
     let f: fn(i32) -> i32 = |n| n / 3 - 2;
     for line in input.lines() {
@@ -19,18 +17,19 @@ pub fn solve(input: &str) -> Result<Solution<i32, i32>, Error> {
             num = f(num);
         }
     }
-    Ok(Solution::new(p1, p2))
+    Ok(Solution::new().part1(p1).part2(p2))
 } // 0.085ms
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::tests::assert_solution;
 
     #[test]
     fn test01() {
-        assert_eq!(solve("14").unwrap(), Solution::new(2, 2));
-        assert_eq!(solve("1969").unwrap(), Solution::new(654, 966));
-        assert_eq!(solve("100756").unwrap(), Solution::new(33_583, 50_346));
-        crate::util::tests::test_full_problem(1, solve, 3_296_560, 4_941_976);
+        assert_solution(&run("14").unwrap(), 2i32, 2i32);
+        assert_solution(&run("1969").unwrap(), 654i32, 966i32);
+        assert_solution(&run("100756").unwrap(), 33_583i32, 50_346i32);
+        crate::util::tests::test_full_problem(1, run, 3_296_560i32, 4_941_976i32);
     }
 }
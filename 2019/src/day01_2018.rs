@@ -1,15 +1,21 @@
-use crate::{Error, Solution};
+use aoc_rust::Solution;
+use eyre::Result;
 
 use std::cell::Cell;
 use std::collections::HashSet;
 
-#[allow(dead_code)]
-pub fn solve(input: String) -> Result<Solution<i32, i32>, Error> {
+aoc_rust::register_day! {
+    year: 2018,
+    day: 1,
+    title: "Chronal Calibration",
+}
+
+pub fn run(input: &str) -> Result<Solution> {
     let input: Vec<i32> = input
         .lines()
-        .map(|line| line.parse().map_err(Error::from))
-        .collect::<Result<Vec<_>, Error>>()?;
-    let p1 = input.iter().sum();
+        .map(str::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    let p1: i32 = input.iter().sum();
     let p2 = Cell::new(0);
     let mut seen = HashSet::new();
     input
@@ -17,18 +23,16 @@ pub fn solve(input: String) -> Result<Solution<i32, i32>, Error> {
         .cycle()
         .take_while(|_| seen.insert(p2.get()))
         .for_each(|n| p2.set(p2.get() + n));
-    Ok(Solution::new(p1, p2.get()))
+    Ok(Solution::new().part1(p1).part2(p2.get()))
 } // 236.41ms
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::tests::assert_solution;
 
     #[test]
     fn test01() {
-        assert_eq!(
-            solve(String::from("+1\n-2\n+3\n+1")).unwrap(),
-            Solution::new(3, 2)
-        );
+        assert_solution(&run("+1\n-2\n+3\n+1").unwrap(), 3, 2);
     }
 }
@@ -1,32 +1,31 @@
-use crate::{Error, Solution};
+use aoc_rust::Solution;
+use eyre::Result;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 2,
+    title: "1202 Program Alarm",
 }
 
-pub fn solve(input: &str) -> Result<Solution<usize, usize>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     let intcodes: Vec<usize> = input
+        .trim_end()
         .split(',')
-        .map(|n| n.parse().map_err(Error::from))
-        .collect::<Result<Vec<_>, Error>>()?;
+        .map(str::parse)
+        .collect::<Result<Vec<_>, _>>()?;
     let mut memory = intcodes.clone();
     let p1 = execute(12, 2, &mut memory)?;
     for noun in 0..100 {
         for verb in 0..100 {
             memory = intcodes.clone();
             if execute(noun, verb, &mut memory)? == 19_690_720 {
-                return Ok(Solution::new(p1, 100 * noun + verb));
+                return Ok(Solution::new().part1(p1).part2(100 * noun + verb));
             }
         }
     }
     bail!("No noun-verb combination resulted in the desired solution");
 } // 8.52ms
 
-fn execute(noun: usize, verb: usize, memory: &mut [usize]) -> Result<usize, Error> {
+fn execute(noun: usize, verb: usize, memory: &mut [usize]) -> Result<usize> {
     memory[1] = noun;
     memory[2] = verb;
     let mut i = 0;
@@ -51,6 +50,6 @@ mod tests {
         let input = [1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
         let mut memory = input.clone();
         assert_eq!(execute(9, 10, &mut memory).unwrap(), 3500);
-        crate::util::tests::test_full_problem(2, solve, 3_562_624, 8298);
+        crate::util::tests::test_full_problem(2, run, 3_562_624usize, 8298usize);
     }
 }
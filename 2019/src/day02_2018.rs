@@ -1,9 +1,15 @@
-use crate::{Error, Solution};
+use aoc_rust::Solution;
+use eyre::Result;
 
 use std::collections::HashMap;
 
-#[allow(dead_code)]
-pub fn solve(input: String) -> Result<Solution<i32, String>, Error> {
+aoc_rust::register_day! {
+    year: 2018,
+    day: 2,
+    title: "Inventory Management System",
+}
+
+pub fn run(input: &str) -> Result<Solution> {
     let (mut twice, mut thrice) = (0, 0);
     for line in input.lines() {
         let mut characters = HashMap::with_capacity(26);
@@ -20,13 +26,13 @@ pub fn solve(input: String) -> Result<Solution<i32, String>, Error> {
     for (i, w1) in input.lines().enumerate() {
         for w2 in input.lines().skip(i + 1) {
             if w1.chars().zip(w2.chars()).filter(|(a, b)| a != b).count() == 1 {
-                let p2 = w1
+                let p2: String = w1
                     .chars()
                     .zip(w2.chars())
                     .filter(|(a, b)| a == b)
                     .map(|(a, _)| a)
                     .collect();
-                return Ok(Solution::new(twice * thrice, p2));
+                return Ok(Solution::new().part1(twice * thrice).part2(p2));
             }
         }
     }
@@ -40,20 +46,16 @@ mod tests {
     #[test]
     fn test02() {
         assert_eq!(
-            solve(String::from(
-                "abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab"
-            ))
-            .unwrap()
-            .part1,
-            12
+            run("abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab")
+                .unwrap()
+                .part1,
+            12_i32
         );
         assert_eq!(
-            solve(String::from(
-                "abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz"
-            ))
-            .unwrap()
-            .part2,
-            "fgij"
+            run("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz")
+                .unwrap()
+                .part2,
+            *"fgij"
         );
     }
 }
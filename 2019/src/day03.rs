@@ -1,20 +1,17 @@
-use crate::{
-    util::{Direction, GridMap, Point2},
-    Error, Solution,
+use aoc_rust::{
+    grid::{Direction, Pos2, SparseGrid},
+    Solution,
 };
+use eyre::Result;
 
-use num::Signed;
-use std::{cmp, convert::TryFrom};
+use std::cmp;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 3,
+    title: "Crossed Wires",
 }
 
-pub fn solve(input: &str) -> Result<Solution<i32, i32>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     let wires: Vec<Vec<(Direction, i32)>> = input
         .lines()
         .map(|line| {
@@ -24,33 +21,41 @@ pub fn solve(input: &str) -> Result<Solution<i32, i32>, Error> {
                     let letter = a
                         .chars()
                         .next()
-                        .ok_or_else(|| error!("Could not call next() on empty Chars"))?;
-                    let direction = Direction::try_from(letter)?;
+                        .ok_or_else(|| eyre!("Could not call next() on empty Chars"))?;
+                    let direction = u8::try_from(letter)
+                        .ok()
+                        .and_then(Direction::from_byte)
+                        .ok_or_else(|| eyre!("Cannot parse direction from char {}", letter))?;
                     let num = b.parse()?;
                     Ok((direction, num))
                 })
-                .collect::<Result<Vec<(Direction, i32)>, Error>>()
+                .collect::<Result<Vec<(Direction, i32)>>>()
         })
-        .collect::<Result<Vec<Vec<(Direction, i32)>>, Error>>()?;
-    let mut visited = GridMap::new();
+        .collect::<Result<Vec<Vec<(Direction, i32)>>>>()?;
+    let mut visited = SparseGrid::new();
     follow_wire(&wires[0], &mut visited, false);
     let (p1, p2) = follow_wire(&wires[1], &mut visited, true);
-    Ok(Solution::new(p1, p2))
+    Ok(Solution::new().part1(p1).part2(p2))
 } // 777.47ms
 
-fn follow_wire(wire: &[(Direction, i32)], visited: &mut GridMap<i32>, output: bool) -> (i32, i32) {
-    let mut pos = Point2::new(0, 0);
+fn follow_wire(
+    wire: &[(Direction, i32)],
+    visited: &mut SparseGrid<i32>,
+    output: bool,
+) -> (i32, i32) {
+    let origin = Pos2::new(0, 0);
+    let mut pos = origin;
     let mut path = 0;
     let mut closest_cross = i32::max_value();
     let mut shortest_cross = i32::max_value();
     for (dir, len) in wire {
         for _ in 1..=*len {
-            pos += dir.shift();
+            pos += dir.delta();
             path += 1;
             if output {
-                if visited.contains_key(&pos) {
-                    closest_cross = cmp::min(closest_cross, pos.abs().sum());
-                    shortest_cross = cmp::min(shortest_cross, path + visited[&pos]);
+                if let Some(&steps) = visited.get(pos) {
+                    closest_cross = cmp::min(closest_cross, pos.manhattan_dist(&origin));
+                    shortest_cross = cmp::min(shortest_cross, path + steps);
                 }
             } else {
                 visited.entry(pos).or_insert(path);
@@ -63,24 +68,24 @@ fn follow_wire(wire: &[(Direction, i32)], visited: &mut GridMap<i32>, output: bo
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::tests::assert_solution;
 
     #[test]
     fn test03() {
-        assert_eq!(
-            solve("R8,U5,L5,D3\nU7,R6,D4,L4").unwrap(),
-            Solution::new(6, 30)
-        );
-        assert_eq!(
-            solve("R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83").unwrap(),
-            Solution::new(159, 610)
+        assert_solution(&run("R8,U5,L5,D3\nU7,R6,D4,L4").unwrap(), 6i32, 30i32);
+        assert_solution(
+            &run("R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83").unwrap(),
+            159i32,
+            610i32,
         );
-        assert_eq!(
-            solve(
-                "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,U7,R15,U6,R7"
+        assert_solution(
+            &run(
+                "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
             )
             .unwrap(),
-            Solution::new(135, 410)
+            135i32,
+            410i32,
         );
-        crate::util::tests::test_full_problem(3, solve, 855, 11_238);
+        crate::util::tests::test_full_problem(3, run, 855i32, 11_238i32);
     }
 }
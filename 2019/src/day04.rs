@@ -1,20 +1,19 @@
-use crate::{Error, Solution};
+use aoc_rust::Solution;
+use eyre::Result;
 
 use std::cmp::Ordering;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 4,
+    title: "Secure Container",
 }
 
-pub fn solve(input: &str) -> Result<Solution<i32, i32>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     let input_split: Vec<i32> = input
+        .trim_end()
         .split('-')
-        .map(|n| n.parse().map_err(Error::from))
-        .collect::<Result<Vec<_>, Error>>()?;
+        .map(str::parse)
+        .collect::<Result<Vec<_>, _>>()?;
     let (min, max) = (input_split[0], input_split[1]);
     let (mut p1, mut p2) = (0, 0);
     for x in min..=max {
@@ -25,7 +24,7 @@ pub fn solve(input: &str) -> Result<Solution<i32, i32>, Error> {
             }
         }
     }
-    Ok(Solution::new(p1, p2))
+    Ok(Solution::new().part1(p1).part2(p2))
 } // 39.78ms
 
 fn check_p1(n: i32) -> bool {
@@ -81,6 +80,6 @@ mod tests {
         assert!(check_p2(112233));
         assert!(!check_p2(123444));
         assert!(check_p2(111122));
-        crate::util::tests::test_full_problem(4, solve, 895, 591);
+        crate::util::tests::test_full_problem(4, run, 895i32, 591i32);
     }
 }
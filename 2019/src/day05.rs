@@ -1,29 +1,27 @@
-use crate::{computer::Computer, Error, Solution};
+use crate::computer::Computer;
+use aoc_rust::Solution;
+use eyre::Result;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 5,
+    title: "Sunny with a Chance of Asteroids",
 }
 
-pub fn solve(input: &str) -> Result<Solution<i64, i64>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     let mut computer = Computer::new(input)?;
     let p1 = computer
         .insert(1)
         .run()?
         .output_iter()
         .last()
-        .ok_or_else(|| error!("No output produced for part 1"))?;
+        .ok_or_else(|| eyre!("No output produced for part 1"))?;
     let mut computer = Computer::new(input)?;
     let p2 = computer
         .insert(5)
         .run()?
         .pop()
-        .ok_or_else(|| error!("No output produced for part 2"))?;
-    //println!("Memory:\n{:?}", computer.memory);
-    Ok(Solution::new(*p1, p2))
+        .ok_or_else(|| eyre!("No output produced for part 2"))?;
+    Ok(Solution::new().part1(*p1).part2(p2))
 } // 0.77ms
 
 #[cfg(test)]
@@ -45,6 +43,6 @@ mod tests {
         assert_eq!(computer.insert(8).run().unwrap().pop().unwrap(), 1000);
         let mut computer = Computer::new(input).unwrap();
         assert_eq!(computer.insert(9).run().unwrap().pop().unwrap(), 1001);
-        crate::util::tests::test_full_problem(5, solve, 7_265_618, 7_731_427);
+        crate::util::tests::test_full_problem(5, run, 7_265_618i64, 7_731_427i64);
     }
 }
@@ -1,35 +1,33 @@
-use crate::{Error, Solution};
+use aoc_rust::Solution;
+use eyre::Result;
 
 use std::collections::{HashMap, HashSet, VecDeque};
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
+type Relations = HashMap<usize, Vec<usize>>;
 
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 6,
+    title: "Universal Orbit Map",
 }
 
-type Relations = HashMap<usize, Vec<usize>>;
-
-pub fn solve(input: &str) -> Result<Solution<usize, usize>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     let (ids, directed, undirected) = prepare_maps(input)?;
     let p1 = solve_part1(
         &directed,
         *ids.get("COM")
-            .ok_or_else(|| error!("Could not find key 'COM' in ids"))?,
+            .ok_or_else(|| eyre!("Could not find key 'COM' in ids"))?,
     );
     let p2 = solve_part2(
         &undirected,
         *ids.get("YOU")
-            .ok_or_else(|| error!("Could not find key 'YOU' in ids"))?,
+            .ok_or_else(|| eyre!("Could not find key 'YOU' in ids"))?,
         *ids.get("SAN")
-            .ok_or_else(|| error!("Could not find key 'SAN' in ids"))?,
+            .ok_or_else(|| eyre!("Could not find key 'SAN' in ids"))?,
     )?;
-    Ok(Solution::new(p1, p2))
+    Ok(Solution::new().part1(p1).part2(p2))
 } // 47.8ms
 
-fn prepare_maps(input: &str) -> Result<(HashMap<String, usize>, Relations, Relations), Error> {
+fn prepare_maps(input: &str) -> Result<(HashMap<String, usize>, Relations, Relations)> {
     let mut ids: HashMap<String, usize> = HashMap::new();
     let mut directed: Relations = Relations::new();
     let mut undirected: Relations = Relations::new();
@@ -39,7 +37,7 @@ fn prepare_maps(input: &str) -> Result<(HashMap<String, usize>, Relations, Relat
         let center_name = String::from(
             line_iter
                 .next()
-                .ok_or_else(|| error!("Could not find name of center"))?,
+                .ok_or_else(|| eyre!("Could not find name of center"))?,
         );
         let center = *ids.entry(center_name).or_insert_with(|| {
             id += 1;
@@ -48,7 +46,7 @@ fn prepare_maps(input: &str) -> Result<(HashMap<String, usize>, Relations, Relat
         let orbiter_name = String::from(
             line_iter
                 .next()
-                .ok_or_else(|| error!("Could not find name of orbiter"))?,
+                .ok_or_else(|| eyre!("Could not find name of orbiter"))?,
         );
         let orbiter = *ids.entry(orbiter_name).or_insert_with(|| {
             id += 1;
@@ -96,7 +94,7 @@ fn solve_part1(map: &Relations, start: usize) -> usize {
     sum
 }
 
-fn solve_part2(map: &Relations, start: usize, end: usize) -> Result<usize, Error> {
+fn solve_part2(map: &Relations, start: usize, end: usize) -> Result<usize> {
     let mut depths = HashMap::new();
     let mut queue = VecDeque::new();
     let mut visited = HashSet::new();
@@ -126,6 +124,7 @@ fn solve_part2(map: &Relations, start: usize, end: usize) -> Result<usize, Error
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::tests::assert_solution;
 
     #[test]
     fn test06() {
@@ -133,7 +132,7 @@ mod tests {
         let (ids, map, _) = prepare_maps(input).unwrap();
         assert_eq!(solve_part1(&map, *ids.get("COM").unwrap()), 42);
         let input = "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN";
-        assert_eq!(solve(input).unwrap(), Solution::new(54, 4));
-        crate::util::tests::test_full_problem(6, solve, 453028, 562);
+        assert_solution(&run(input).unwrap(), 54usize, 4usize);
+        crate::util::tests::test_full_problem(6, run, 453028usize, 562usize);
     }
 }
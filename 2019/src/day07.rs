@@ -1,70 +1,63 @@
-use crate::{computer::Computer, Error, Solution};
+use crate::computer::{Computer, Network, Topology};
+use aoc_rust::Solution;
+use eyre::Result;
 
 use itertools::Itertools;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 7,
+    title: "Amplification Circuit",
 }
 
-pub fn solve(input: &str) -> Result<Solution<i64, i64>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     let p1 = solve_part1(input)?;
     let p2 = solve_part2(input)?;
-    Ok(Solution::new(p1, p2))
+    Ok(Solution::new().part1(p1).part2(p2))
 } // 230.73ms
 
-fn solve_part1(input: &str) -> Result<i64, Error> {
+fn solve_part1(input: &str) -> Result<i64> {
     let mut max_signal = 0;
     for phases in (0..5).permutations(5) {
-        let mut amplifiers: Vec<Computer> = phases
-            .iter()
-            .map(|&phase| {
-                let mut computer = Computer::new(input)?;
-                computer.insert(phase);
-                Ok(computer)
-            })
-            .collect::<Result<Vec<_>, Error>>()?;
-        let mut signal = 0;
-        for amplifier in &mut amplifiers {
-            amplifier.insert(signal).run()?;
-            signal = amplifier
-                .pop()
-                .ok_or_else(|| error!("Expected output from ampifier, none found"))?;
-        }
+        let mut network = amplifiers(input, &phases, Topology::Chain)?;
+        network.send(0, vec![0]).run_until_idle()?;
+        let signal = network
+            .pop_external()
+            .map(|packet| packet.data[0])
+            .ok_or_else(|| eyre!("Expected output from ampifier, none found"))?;
         max_signal = max_signal.max(signal);
     }
     Ok(max_signal)
 }
 
-fn solve_part2(input: &str) -> Result<i64, Error> {
+fn solve_part2(input: &str) -> Result<i64> {
     let mut max_signal = 0;
     for phases in (5..10).permutations(5) {
-        let mut amplifiers: Vec<Computer> = phases
+        let mut network = amplifiers(input, &phases, Topology::Ring)?;
+        network.send(0, vec![0]).run_until_idle()?;
+        let signal = network
+            .log()
             .iter()
-            .map(|&phase| {
-                let mut computer = Computer::new(input)?;
-                computer.insert(phase);
-                Ok(computer)
-            })
-            .collect::<Result<Vec<_>, Error>>()?;
-        let mut idx = 0;
-        let mut signal = 0;
-        loop {
-            amplifiers[idx].insert(signal).run()?;
-            match amplifiers[idx].pop() {
-                Some(output) => signal = output,
-                None => break,
-            }
-            max_signal = max_signal.max(signal);
-            idx = (idx + 1) % 5;
-        }
+            .rev()
+            .find(|packet| packet.src == Some(phases.len() - 1))
+            .map(|packet| packet.data[0])
+            .ok_or_else(|| eyre!("Expected output from ampifier, none found"))?;
+        max_signal = max_signal.max(signal);
     }
     Ok(max_signal)
 }
 
+fn amplifiers(input: &str, phases: &[i64], topology: Topology) -> Result<Network> {
+    let amplifiers = phases
+        .iter()
+        .map(|&phase| {
+            let mut computer = Computer::new(input)?;
+            computer.insert(phase);
+            Ok(computer)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Network::new(amplifiers, topology)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,6 +69,6 @@ mod tests {
         let input =
             "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5";
         assert_eq!(solve_part2(input).unwrap(), 139629729);
-        crate::util::tests::test_full_problem(7, solve, 65464, 1518124);
+        crate::util::tests::test_full_problem(7, run, 65464i64, 1518124i64);
     }
 }
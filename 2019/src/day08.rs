@@ -1,34 +1,39 @@
-use crate::{Error, Solution};
-
+use aoc_rust::{util::parse::ParseError, AsciiArt, Solution};
+use eyre::Result;
 use itertools::Itertools;
 use std::collections::HashMap;
-use std::fmt;
-
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
 
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 8,
+    title: "Space Image Format",
 }
 
-pub fn solve(input: &str) -> Result<Solution<usize, String>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     solve_with_dimensions(input, 25, 6)
 } // 14.96ms
 
-pub fn solve_with_dimensions(
-    input: &str,
-    width: usize,
-    height: usize,
-) -> Result<Solution<usize, String>, Error> {
-    let layers: Vec<Layer> = input
+pub fn solve_with_dimensions(input: &str, width: usize, height: usize) -> Result<Solution> {
+    let digits = input.trim_end();
+
+    if let Some(i) = digits.find(|c: char| !c.is_ascii_digit()) {
+        return Err(ParseError::at_offset(input, i, "expected a digit").into());
+    }
+
+    if digits.is_empty() || !digits.len().is_multiple_of(width * height) {
+        bail!(
+            "image of {} digits does not consist of {width}x{height} layers",
+            digits.len()
+        );
+    }
+
+    let layers: Vec<Layer> = digits
         .chars()
         .map(|digit| digit as usize - '0' as usize)
         .chunks(width * height)
         .into_iter()
         .map(|layer| Layer::new(layer.collect::<Vec<usize>>(), width))
         .collect();
-    let p1 = layers
+    let p1: usize = layers
         .iter()
         .map(|layer| layer.digit_count())
         .min_by_key(|count| *count.get(&0).unwrap_or(&0))
@@ -41,8 +46,8 @@ pub fn solve_with_dimensions(
         .iter()
         .skip(1)
         .fold(layers[0].clone(), |stacked, layer| stacked.stack(layer))
-        .to_string();
-    Ok(Solution::new(p1, p2))
+        .to_art();
+    Ok(Solution::new().part1(p1).part2(p2))
 }
 
 #[derive(Clone)]
@@ -73,25 +78,14 @@ impl Layer {
         }
         count
     }
-}
 
-impl fmt::Display for Layer {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
+    fn to_art(&self) -> AsciiArt {
+        AsciiArt::new(
+            self.width,
             self.data
-                .chunks(self.width)
-                .map(|row| row
-                    .iter()
-                    .map(|digit| match digit {
-                        0 => ' ',
-                        1 => '█',
-                        2 => ' ',
-                        _ => panic!("Found wrong digit: {}", digit), // TODO: Handle?
-                    })
-                    .collect::<String>())
-                .join("\n")
+                .iter()
+                .map(|&digit| digit == 1)
+                .collect::<Vec<_>>(),
         )
     }
 }
@@ -99,14 +93,16 @@ impl fmt::Display for Layer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::tests::assert_solution;
 
     #[test]
     fn test08() {
         let input = "0222112222120000";
-        assert_eq!(
-            solve_with_dimensions(input, 2, 2).unwrap(),
-            Solution::new(4, " █\n█ ".to_owned())
+        assert_solution(
+            &solve_with_dimensions(input, 2, 2).unwrap(),
+            4usize,
+            AsciiArt::from_chars(" █\n█ ", '█'),
         );
-        crate::util::tests::test_full_problem(8, solve, 2480, "████ █   ████  █    █  █ \n   █ █   ██  █ █    █  █ \n  █   █ █ ███  █    ████ \n █     █  █  █ █    █  █ \n█      █  █  █ █    █  █ \n████   █  ███  ████ █  █ ".to_owned());
+        crate::util::tests::test_full_problem(8, run, 2480usize, AsciiArt::from_chars("████ █   ████  █    █  █ \n   █ █   ██  █ █    █  █ \n  █   █ █ ███  █    ████ \n █     █  █  █ █    █  █ \n█      █  █  █ █    █  █ \n████   █  ███  ████ █  █ ", '█'));
     }
 }
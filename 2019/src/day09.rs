@@ -1,22 +1,21 @@
-use crate::{computer::Computer, Error, Solution};
+use crate::computer::Computer;
+use aoc_rust::Solution;
+use eyre::Result;
 
 use itertools::Itertools;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 9,
+    title: "Sensor Boost",
 }
 
-pub fn solve(input: &str) -> Result<Solution<String, String>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     let p1 = solve_with_input(input, Some(1))?;
     let p2 = solve_with_input(input, Some(2))?;
-    Ok(Solution::new(p1, p2))
+    Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn solve_with_input(input: &str, computer_input: Option<i64>) -> Result<String, Error> {
+fn solve_with_input(input: &str, computer_input: Option<i64>) -> Result<String> {
     let mut computer = Computer::new(input)?;
     computer.run()?;
     if let Some(input) = computer_input {
@@ -52,7 +51,7 @@ mod tests {
         assert_eq!(1_125_899_906_842_624, answer);
         crate::util::tests::test_full_problem(
             9,
-            solve,
+            run,
             3_345_854_957_i64.to_string(),
             68_938.to_string(),
         );
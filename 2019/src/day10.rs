@@ -1,30 +1,26 @@
-use crate::{
-    util::{gcd, Point2, Point2i},
-    Error, Solution,
-};
+use crate::util::gcd;
+use aoc_rust::{grid::Pos2, Solution};
+use eyre::Result;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 10,
+    title: "Monitoring Station",
 }
 
-pub fn solve(input: &str) -> Result<Solution<usize, i32>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     let asteroids: Vec<Vec<bool>> = input
         .lines()
         .map(|line| line.chars().map(|c| c == '#').collect())
         .collect();
     let (p1, station) = solve_part1(&asteroids);
     let p2 = solve_part2(&asteroids, station, 200)?;
-    Ok(Solution::new(p1, p2))
+    Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn solve_part1(asteroids: &[Vec<bool>]) -> (usize, Point2<usize>) {
+fn solve_part1(asteroids: &[Vec<bool>]) -> (usize, Pos2<usize>) {
     let w = asteroids[0].len();
     let h = asteroids.len();
-    let mut station = Point2::default();
+    let mut station = Pos2::default();
     let mut result = 0;
     for y in 0..h {
         for x in 0..w {
@@ -32,10 +28,10 @@ fn solve_part1(asteroids: &[Vec<bool>]) -> (usize, Point2<usize>) {
                 let mut sights = 0;
                 for cy in 0..h {
                     for cx in 0..w {
-                        let delta = Point2::new(cx as i32 - x as i32, cy as i32 - y as i32);
+                        let delta = Pos2::new(cx as i32 - x as i32, cy as i32 - y as i32);
                         if gcd(delta.x.abs(), delta.y.abs()) == 1 {
-                            let mut curr = Point2::new(cx as i32, cy as i32);
-                            while curr.in_bounds(0, 0, w as i32, h as i32) {
+                            let mut curr = Pos2::new(cx as i32, cy as i32);
+                            while in_bounds(curr, w, h) {
                                 if asteroids[curr.y as usize][curr.x as usize] {
                                     sights += 1;
                                     break;
@@ -47,7 +43,7 @@ fn solve_part1(asteroids: &[Vec<bool>]) -> (usize, Point2<usize>) {
                 }
                 if sights > result {
                     result = sights;
-                    station = Point2::new(x, y);
+                    station = Pos2::new(x, y);
                 }
             }
         }
@@ -55,11 +51,7 @@ fn solve_part1(asteroids: &[Vec<bool>]) -> (usize, Point2<usize>) {
     (result, station)
 } // 127.23ms
 
-fn solve_part2(
-    asteroids: &[Vec<bool>],
-    station: Point2<usize>,
-    destroy_num: usize,
-) -> Result<i32, Error> {
+fn solve_part2(asteroids: &[Vec<bool>], station: Pos2<usize>, destroy_num: usize) -> Result<i32> {
     let w = asteroids[0].len();
     let h = asteroids.len();
     if w != h {
@@ -72,7 +64,7 @@ fn solve_part2(
         coords
             .clone()
             .into_iter()
-            .map(|p| Point2i::new(p.y, p.x))
+            .map(|p| Pos2::new(p.y, p.x))
             .rev()
             .skip(1),
     );
@@ -81,7 +73,7 @@ fn solve_part2(
         coords
             .clone()
             .into_iter()
-            .map(|p| Point2i::new(p.x, -p.y))
+            .map(|p| Pos2::new(p.x, -p.y))
             .rev()
             .skip(1),
     );
@@ -90,22 +82,19 @@ fn solve_part2(
         coords
             .clone()
             .into_iter()
-            .map(|p| Point2i::new(-p.x, p.y))
+            .map(|p| Pos2::new(-p.x, p.y))
             .rev()
             .skip(1),
     );
     coords.pop();
     // matrix-coordinates have flipped y values
-    coords = coords
-        .into_iter()
-        .map(|p| Point2i::new(p.x, -p.y))
-        .collect();
-    let station = Point2i::new(station.x as i32, station.y as i32);
-    let mut destroy_order: Vec<Vec<Point2<i32>>> = Vec::with_capacity(w.max(h));
+    coords = coords.into_iter().map(|p| Pos2::new(p.x, -p.y)).collect();
+    let station = Pos2::new(station.x as i32, station.y as i32);
+    let mut destroy_order: Vec<Vec<Pos2<i32>>> = Vec::with_capacity(w.max(h));
     for &delta in &coords {
         let mut curr = station + delta;
         let mut rotation = 0;
-        while curr.in_bounds(0, 0, w as i32, h as i32) {
+        while in_bounds(curr, w, h) {
             if asteroids[curr.y as usize][curr.x as usize] {
                 if destroy_order.len() <= rotation {
                     destroy_order.push(Vec::new());
@@ -121,19 +110,23 @@ fn solve_part2(
         .flatten()
         .take(destroy_num)
         .last()
-        .ok_or_else(|| error!("Found no destroyed asteroids"))?;
+        .ok_or_else(|| eyre!("Found no destroyed asteroids"))?;
     Ok(result.x * 100 + result.y)
 }
 
+fn in_bounds(pos: Pos2<i32>, w: usize, h: usize) -> bool {
+    (0..w as i32).contains(&pos.x) && (0..h as i32).contains(&pos.y)
+}
+
 // generate fractions from 0 to 1 with max denumerator n
-fn farey(n: i32) -> Vec<Point2i> {
-    let mut ab = Point2i::new(0, 1);
-    let mut cd = Point2i::new(1, n);
+fn farey(n: i32) -> Vec<Pos2<i32>> {
+    let mut ab = Pos2::new(0, 1);
+    let mut cd = Pos2::new(1, n);
     let mut sequence = vec![ab];
     while cd.x < n {
         let k = (n + ab.y) / cd.y;
         let old_cd = cd;
-        cd = Point2i::new(k * cd.x - ab.x, k * cd.y - ab.y);
+        cd = Pos2::new(k * cd.x - ab.x, k * cd.y - ab.y);
         ab = old_cd;
         sequence.push(ab);
     }
@@ -143,6 +136,7 @@ fn farey(n: i32) -> Vec<Point2i> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::tests::assert_solution;
 
     #[test]
     fn test10() {
@@ -151,15 +145,15 @@ mod tests {
             .lines()
             .map(|line| line.chars().map(|c| c == '#').collect())
             .collect();
-        assert_eq!(solve_part1(&asteroids), (33, Point2::new(5, 8)));
+        assert_eq!(solve_part1(&asteroids), (33, Pos2::new(5, 8)));
         let input = ".#..#..###\n####.###.#\n....###.#.\n..###.##.#\n##.##.#.#.\n....###..#\n..#.#..#.#\n#..#.#.###\n.##...##.#\n.....#.#..";
         let asteroids: Vec<Vec<bool>> = input
             .lines()
             .map(|line| line.chars().map(|c| c == '#').collect())
             .collect();
-        assert_eq!(solve_part1(&asteroids), (41, Point2::new(6, 3)));
+        assert_eq!(solve_part1(&asteroids), (41, Pos2::new(6, 3)));
         let input = ".#..##.###...#######\n##.############..##.\n.#.######.########.#\n.###.#######.####.#.\n#####.##.#.##.###.##\n..#####..#.#########\n####################\n#.####....###.#.#.##\n##.#################\n#####.##.###..####..\n..######..##.#######\n####.##.####...##..#\n.#####..#.######.###\n##...#.##########...\n#.##########.#######\n.####.#.###.###.#.##\n....##.##.###..#####\n.#.#.###########.###\n#.#.#.#####.####.###\n###.##.####.##.#..##";
-        assert_eq!(solve(input).unwrap(), Solution::new(210, 802));
-        crate::util::tests::test_full_problem(10, solve, 344, 2732);
+        assert_solution(&run(input).unwrap(), 210usize, 802i32);
+        crate::util::tests::test_full_problem(10, run, 344usize, 2732i32);
     }
 }
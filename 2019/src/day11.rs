@@ -1,42 +1,34 @@
-use crate::{
-    computer::Computer,
-    util::{Direction, GridMap, Point2},
-    Error, Solution,
+use crate::computer::Computer;
+use aoc_rust::{
+    grid::{Direction, Pos2, SparseGrid},
+    AsciiArt, Solution,
 };
-use std::collections::HashMap;
+use eyre::Result;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 11,
+    title: "Space Police",
 }
 
-pub fn solve(input: &str) -> Result<Solution<usize, String>, Error> {
-    let mut grid = GridMap::new();
+pub fn run(input: &str) -> Result<Solution> {
+    let mut grid = SparseGrid::new();
     execute(0, input, &mut grid)?;
     let p1 = grid.len();
     grid.clear();
     execute(1, input, &mut grid)?;
-    let mut mapping = HashMap::new();
-    mapping.insert(0, ' ');
-    mapping.insert(1, '█');
-    let p2 = grid
-        .iter()
-        .filter(|(_, v)| **v != 0)
-        .map(|(&p, &v)| (p, v))
-        .collect::<GridMap<i64>>()
-        .map_values(&mapping, Some(' '))?
-        .to_string();
-    Ok(Solution::new(p1, p2))
+    let p2 = AsciiArt::from_points(
+        grid.iter()
+            .filter(|(_, v)| **v != 0)
+            .map(|(p, _)| (i64::from(p.x), i64::from(p.y))),
+    );
+    Ok(Solution::new().part1(p1).part2(p2))
 } // 69.16ms
 
-fn execute(start: i64, input: &str, grid: &mut GridMap<i64>) -> Result<(), Error> {
+fn execute(start: i64, input: &str, grid: &mut SparseGrid<i64>) -> Result<()> {
     let mut brain = Computer::new(input)?;
-    let mut pos = Point2::new(0, 0);
+    let mut pos = Pos2::new(0, 0);
     grid.insert(pos, start);
-    let mut direction = Direction::N;
+    let mut direction = Direction::Up;
     loop {
         brain.insert(*grid.entry(pos).or_insert(0)).run()?;
         match brain.pop() {
@@ -45,13 +37,13 @@ fn execute(start: i64, input: &str, grid: &mut GridMap<i64>) -> Result<(), Error
         };
         direction = match brain
             .pop()
-            .ok_or_else(|| error!("Expected output for direction, none found"))?
+            .ok_or_else(|| eyre!("Expected output for direction, none found"))?
         {
-            0 => direction.to_left(),
-            1 => direction.to_right(),
+            0 => direction.turn_left(),
+            1 => direction.turn_right(),
             other => bail!("Found neither 0 nor 1 for directions, but {}", other),
         };
-        pos += direction.shift();
+        pos += direction.delta();
     }
     Ok(())
 }
@@ -62,9 +54,6 @@ mod tests {
 
     #[test]
     fn test11() {
-        crate::util::tests::test_full_problem(11, solve, 2319,
-            "█  █ ████ ███  ███  ███  ████  ██    ██\n█  █ █    █  █ █  █ █  █ █    █  █    █\n█  █ ███  █  █ █  █ █  █ ███  █       █\n█  █ █    ███  ███  ███  █    █ ██    █\n█  █ █    █ █  █    █ █  █    █  █ █  █\n ██  ████ █  █ █    █  █ █     ███  ██ "
-                .to_owned()
-        );
+        crate::util::tests::test_full_problem(11, run, 2319usize, AsciiArt::from_chars("█  █ ████ ███  ███  ███  ████  ██    ██\n█  █ █    █  █ █  █ █  █ █    █  █    █\n█  █ ███  █  █ █  █ █  █ ███  █       █\n█  █ █    ███  ███  ███  █    █ ██    █\n█  █ █    █ █  █    █ █  █    █  █ █  █\n ██  ████ █  █ █    █  █ █     ███  ██ ", '█'));
     }
 }
@@ -1,26 +1,19 @@
-use crate::{
-    util::{lcm, Point3i},
-    Error, Solution,
-};
+use aoc_rust::{grid::Pos3, util::cycle, Solution};
+use eyre::Result;
 
-use num::Signed;
-
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 12,
+    title: "The N-Body Problem",
 }
 
-pub fn solve(input: &str) -> Result<Solution<i32, i64>, Error> {
-    let mut moons = get_moons(input)?;
+pub fn run(input: &str) -> Result<Solution> {
+    let moons = get_moons(input)?;
     let p1 = solve_part1(1000, &mut moons.to_vec());
-    let p2 = solve_part2(&mut moons);
-    Ok(Solution::new(p1, p2))
+    let p2 = solve_part2(&moons);
+    Ok(Solution::new().part1(p1).part2(p2))
 } // 231.14ms
 
-fn get_moons(input: &str) -> Result<Vec<Moon>, Error> {
+fn get_moons(input: &str) -> Result<Vec<Moon>> {
     let mut moons = Vec::with_capacity(4);
     for line in input.lines() {
         let mut moon = Vec::with_capacity(3);
@@ -29,7 +22,7 @@ fn get_moons(input: &str) -> Result<Vec<Moon>, Error> {
             let value = split
                 .split('=')
                 .last()
-                .ok_or_else(|| error!("Missing characters after '='"))?
+                .ok_or_else(|| eyre!("Missing characters after '='"))?
                 .parse::<i32>()?;
             moon.push(value);
         }
@@ -45,30 +38,37 @@ fn solve_part1(steps: usize, moons: &mut [Moon]) -> i32 {
     moons.iter().map(|moon| moon.energy()).sum::<i32>()
 }
 
-fn solve_part2(moons: &mut [Moon]) -> i64 {
-    let (mut steps, mut x_loop, mut y_loop, mut z_loop) = (0, 0, 0, 0);
-    loop {
-        steps += 1;
-        move_moons(moons);
-        if x_loop == 0 && moons.iter().all(|moon| moon.vel.x == 0) {
-            x_loop = steps;
-        }
-        if y_loop == 0 && moons.iter().all(|moon| moon.vel.y == 0) {
-            y_loop = steps;
-        }
-        if z_loop == 0 && moons.iter().all(|moon| moon.vel.z == 0) {
-            z_loop = steps;
-        }
-        if x_loop > 0 && y_loop > 0 && z_loop > 0 {
-            return 2 * lcm(lcm(x_loop, y_loop), z_loop);
+fn solve_part2(moons: &[Moon]) -> i64 {
+    // Axes are independent of each other so each one cycles on its own
+    let axis = |coord: fn(&Pos3<i32>) -> i32| {
+        let axis: Vec<_> = moons
+            .iter()
+            .map(|moon| (coord(&moon.pos), coord(&moon.vel)))
+            .collect();
+        cycle::brent(axis, move_axis, Vec::clone).len
+    };
+    let lens = [axis(|p| p.x), axis(|p| p.y), axis(|p| p.z)];
+    cycle::combine_lengths(lens) as i64
+}
+
+fn move_axis(axis: &Vec<(i32, i32)>) -> Vec<(i32, i32)> {
+    let mut next = axis.clone();
+    for (i, &(pos_a, _)) in axis.iter().enumerate() {
+        for &(pos_b, _) in axis {
+            next[i].1 += (pos_b - pos_a).signum();
         }
     }
+    for (pos, vel) in next.iter_mut() {
+        *pos += *vel;
+    }
+    next
 }
 
 fn move_moons(moons: &mut [Moon]) {
     for i in 0..moons.len() {
         for j in i + 1..moons.len() {
-            let diff = (moons[i].pos - moons[j].pos).restrict(-1, 1);
+            let Pos3 { x, y, z } = moons[i].pos - moons[j].pos;
+            let diff = Pos3::new(x.signum(), y.signum(), z.signum());
             moons[i].vel -= diff;
             moons[j].vel += diff;
         }
@@ -80,20 +80,21 @@ fn move_moons(moons: &mut [Moon]) {
 
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
 struct Moon {
-    pos: Point3i,
-    vel: Point3i,
+    pos: Pos3<i32>,
+    vel: Pos3<i32>,
 }
 
 impl Moon {
     fn new(pos: &[i32]) -> Self {
         Moon {
-            pos: Point3i::new(pos[0], pos[1], pos[2]),
-            vel: Point3i::new(0, 0, 0),
+            pos: Pos3::new(pos[0], pos[1], pos[2]),
+            vel: Pos3::default(),
         }
     }
 
     fn energy(&self) -> i32 {
-        self.pos.abs().sum() * self.vel.abs().sum()
+        let origin = Pos3::default();
+        self.pos.manhattan_dist(&origin) * self.vel.manhattan_dist(&origin)
     }
 }
 
@@ -104,19 +105,19 @@ mod tests {
     #[test]
     fn test12() {
         let input = "<x=-1, y=0, z=2>\n<x=2, y=-10, z=-7>\n<x=4, y=-8, z=8>\n<x=3, y=5, z=-1>";
-        let mut moons = get_moons(input).unwrap();
+        let moons = get_moons(input).unwrap();
         assert_eq!(
             solve_part1(10, &mut moons.iter().cloned().collect::<Vec<_>>()),
             179
         );
-        assert_eq!(solve_part2(&mut moons), 2772);
+        assert_eq!(solve_part2(&moons), 2772);
         let input = "<x=-8, y=-10, z=0>\n<x=5, y=5, z=10>\n<x=2, y=-7, z=3>\n<x=9, y=-8, z=-3>";
-        let mut moons = get_moons(input).unwrap();
+        let moons = get_moons(input).unwrap();
         assert_eq!(
             solve_part1(100, &mut moons.iter().cloned().collect::<Vec<_>>()),
             1940
         );
-        assert_eq!(solve_part2(&mut moons), 4_686_774_924i64);
-        crate::util::tests::test_full_problem(12, solve, 9127, 353_620_566_035_124i64);
+        assert_eq!(solve_part2(&moons), 4_686_774_924i64);
+        crate::util::tests::test_full_problem(12, run, 9127i32, 353_620_566_035_124i64);
     }
 }
@@ -1,50 +1,48 @@
-use crate::{
-    computer::Computer,
-    util::{Direction, GridMap, Point2i},
-    Error, Solution,
+use crate::computer::Computer;
+use aoc_rust::{
+    grid::{Direction, Pos2, SparseGrid},
+    Solution,
 };
+use eyre::Result;
 
 use std::collections::HashSet;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 15,
+    title: "Oxygen System",
 }
 
-pub fn solve(input: &str) -> Result<Solution<i32, i32>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     let mut computer = Computer::new(input)?;
-    let mut curr_pos = Point2i::new(0, 0);
-    let mut curr_dir = Direction::N;
+    let mut curr_pos = Pos2::new(0, 0);
+    let mut curr_dir = Direction::Up;
     // 0: Wall; 1: Path; 2: Blocked; 3: Oxygenated
-    let mut grid = GridMap::new();
+    let mut grid = SparseGrid::new();
     grid.insert(curr_pos, 1u8);
-    let mut oxy_pos = Point2i::default();
+    let mut oxy_pos = Pos2::default();
     let mut p1 = 0;
     let p2;
     loop {
         match curr_dir {
-            Direction::N => computer.insert(1),
-            Direction::S => computer.insert(2),
-            Direction::W => computer.insert(3),
-            Direction::E => computer.insert(4),
+            Direction::Up => computer.insert(1),
+            Direction::Down => computer.insert(2),
+            Direction::Left => computer.insert(3),
+            Direction::Right => computer.insert(4),
         };
         match computer
             .run()?
             .pop()
-            .ok_or_else(|| error!("Expected output for terrain status, none found"))?
+            .ok_or_else(|| eyre!("Expected output for terrain status, none found"))?
         {
             0 => {
-                grid.insert(curr_pos + curr_dir.shift(), 0);
+                grid.insert(curr_pos + curr_dir.delta(), 0);
             }
             1 => {
-                curr_pos += curr_dir.shift();
+                curr_pos += curr_dir.delta();
                 grid.insert(curr_pos, 1);
             }
             2 => {
-                curr_pos += curr_dir.shift();
+                curr_pos += curr_dir.delta();
                 oxy_pos = curr_pos;
                 grid.insert(curr_pos, 4);
             }
@@ -52,9 +50,9 @@ pub fn solve(input: &str) -> Result<Solution<i32, i32>, Error> {
         }
         let mut found = false;
         // Find unvisited neighbor
-        for &dir in Direction::iter() {
-            let next_pos = curr_pos + dir.shift();
-            if !grid.contains_key(&next_pos) {
+        for dir in Direction::ALL {
+            let next_pos = curr_pos + dir.delta();
+            if !grid.contains(next_pos) {
                 curr_dir = dir;
                 found = true;
                 break;
@@ -63,9 +61,9 @@ pub fn solve(input: &str) -> Result<Solution<i32, i32>, Error> {
         // Find unblocked neighbor
         if !found {
             grid.insert(curr_pos, 2);
-            for &dir in Direction::iter() {
-                let next_pos = curr_pos + dir.shift();
-                if grid[&next_pos] == 1 {
+            for dir in Direction::ALL {
+                let next_pos = curr_pos + dir.delta();
+                if grid[next_pos] == 1 {
                     curr_dir = dir;
                     found = true;
                     break;
@@ -74,23 +72,15 @@ pub fn solve(input: &str) -> Result<Solution<i32, i32>, Error> {
         }
         // Grid fully discovered, start DFS to flood
         if !found {
-            /*
-            let mut mapping = std::collections::HashMap::new();
-            mapping.insert(0, '█');
-            mapping.insert(2, ' ');
-            mapping.insert(5, 'O');
-            let draw_grid = grid.map_values(&mapping, Some(' '));
-            println!("Grid:\n{}", draw_grid);
-            */
             // Part 1
-            let pos = Point2i::new(0, 0);
+            let pos = Pos2::new(0, 0);
             let mut visited = HashSet::new();
             visited.insert(pos);
             let mut backtrack = vec![(pos, 0)];
             while let Some((pos, dist)) = backtrack.pop() {
-                for dir in Direction::iter() {
-                    let next_pos = pos + dir.shift();
-                    if grid[&next_pos] != 0 && !visited.contains(&next_pos) {
+                for dir in Direction::ALL {
+                    let next_pos = pos + dir.delta();
+                    if grid[next_pos] != 0 && !visited.contains(&next_pos) {
                         if next_pos == oxy_pos {
                             p1 = dist + 1;
                             backtrack.clear();
@@ -110,9 +100,9 @@ pub fn solve(input: &str) -> Result<Solution<i32, i32>, Error> {
             while let Some((pos, m)) = backtrack.pop() {
                 minutes = minutes.max(m);
                 grid.insert(pos, 3);
-                for dir in Direction::iter() {
-                    let next_pos = pos + dir.shift();
-                    if grid[&next_pos] != 0 && !visited.contains(&next_pos) {
+                for dir in Direction::ALL {
+                    let next_pos = pos + dir.delta();
+                    if grid[next_pos] != 0 && !visited.contains(&next_pos) {
                         visited.insert(next_pos);
                         backtrack.push((next_pos, m + 1));
                     }
@@ -122,7 +112,7 @@ pub fn solve(input: &str) -> Result<Solution<i32, i32>, Error> {
             break;
         }
     }
-    Ok(Solution::new(p1, p2))
+    Ok(Solution::new().part1(p1).part2(p2))
 } // 59.31ms
 
 #[cfg(test)]
@@ -131,6 +121,6 @@ mod tests {
 
     #[test]
     fn test15() {
-        crate::util::tests::test_full_problem(15, solve, 272, 398);
+        crate::util::tests::test_full_problem(15, run, 272i32, 398i32);
     }
 }
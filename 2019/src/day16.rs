@@ -1,28 +1,27 @@
-use crate::{Error, Solution};
+use aoc_rust::Solution;
+use eyre::Result;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 16,
+    title: "Flawed Frequency Transmission",
 }
 
-pub fn solve(input: &str) -> Result<Solution<i32, i32>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     let signal = parse_input(input)?;
     let p1 = solve_part1(signal.clone());
     let p2 = solve_part2(signal)?;
-    Ok(Solution::new(p1, p2))
+    Ok(Solution::new().part1(p1).part2(p2))
 } // 6.07s
 
-fn parse_input(input: &str) -> Result<Vec<i32>, Error> {
+fn parse_input(input: &str) -> Result<Vec<i32>> {
     Ok(input
+        .trim_end()
         .chars()
         .map(|c| {
             c.to_digit(10)
-                .ok_or_else(|| error!("Can not parse {} to digit", c))
+                .ok_or_else(|| eyre!("Can not parse {} to digit", c))
         })
-        .collect::<Result<Vec<_>, Error>>()? // TODO: Nicer way?
+        .collect::<Result<Vec<_>>>()? // TODO: Nicer way?
         .into_iter()
         .map(|d| d as i32)
         .collect::<Vec<_>>())
@@ -53,7 +52,7 @@ fn solve_part1(mut signal: Vec<i32>) -> i32 {
         .fold(0, |sum, next| 10 * sum + next)
 }
 
-fn solve_part2(mut signal: Vec<i32>) -> Result<i32, Error> {
+fn solve_part2(mut signal: Vec<i32>) -> Result<i32> {
     let offset = signal[..7].iter().fold(0, |sum, &next| 10 * sum + next) as usize;
     if offset < signal.len() / 2 {
         bail!(
@@ -98,6 +97,6 @@ mod tests {
         assert_eq!(solve_part2(signal).unwrap(), 78_725_270);
         let signal = parse_input("03081770884921959731165446850517").unwrap();
         assert_eq!(solve_part2(signal).unwrap(), 53_553_731);
-        crate::util::tests::test_full_problem(16, solve, 36627552, 79723033);
+        crate::util::tests::test_full_problem(16, run, 36627552i32, 79723033i32);
     }
 }
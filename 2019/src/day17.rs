@@ -1,16 +1,15 @@
-use crate::{computer::Computer, Error, Solution};
+use crate::computer::Computer;
+use aoc_rust::Solution;
+use eyre::Result;
 #[allow(unused_imports)]
 use itertools::Itertools;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 17,
+    title: "Set and Forget",
 }
 
-pub fn solve(input: &str) -> Result<Solution<usize, i64>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     let mut input = input.to_owned();
     input.replace_range(..1, "2");
     let mut computer = Computer::new(&input)?;
@@ -141,8 +140,8 @@ pub fn solve(input: &str) -> Result<Solution<usize, i64>, Error> {
     let p2 = computer
         .output_iter()
         .last()
-        .ok_or_else(|| error!("Computer did not procude an output"))?;
-    Ok(Solution::new(p1, *p2))
+        .ok_or_else(|| eyre!("Computer did not procude an output"))?;
+    Ok(Solution::new().part1(p1).part2(*p2))
 } // 36.79ms
 
 #[cfg(test)]
@@ -151,6 +150,6 @@ mod tests {
 
     #[test]
     fn test17() {
-        crate::util::tests::test_full_problem(17, solve, 4044, 893283);
+        crate::util::tests::test_full_problem(17, run, 4044usize, 893283i64);
     }
 }
@@ -1,18 +1,17 @@
-use crate::{
-    util::{Direction, Point2us},
-    Error, Solution,
+use aoc_rust::{
+    grid::{Direction, Pos2},
+    search,
+    util::parse::ParseError,
+    Solution,
 };
-use pathfinding::directed::bfs::bfs;
+use eyre::Result;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 18,
+    title: "Many-Worlds Interpretation",
 }
 
-pub fn solve(input: &str) -> Result<Solution<usize, usize>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     // let input = "\
     // #################\n\
     // #i.G..c...e..H.p#\n\
@@ -23,18 +22,18 @@ pub fn solve(input: &str) -> Result<Solution<usize, usize>, Error> {
     // ########.########\n\
     // #l.F..d...h..C.m#\n\
     // #################";
-    let (mut map, keys) = parse_input(input);
+    let (mut map, keys) = parse_input(input)?;
     let p1 = solve_part1(&map, keys)?;
     transform_map(&mut map);
     let p2 = solve_part2(&mut map, keys)?;
-    Ok(Solution::new(p1, p2))
+    Ok(Solution::new().part1(p1).part2(p2))
 } // 151.24s
 
-fn solve_part1(map: &[Vec<char>], all_keys: u32) -> Result<usize, Error> {
+fn solve_part1(map: &[Vec<char>], all_keys: u32) -> Result<usize> {
     let start_pos = get_entrances(map)[0];
     let start = Cell::new(start_pos, 0);
-    let path = bfs(
-        &start,
+    let path = search::bfs(
+        start,
         |&cell| {
             let pos = cell.pos;
             let keys = cell.keys;
@@ -66,15 +65,15 @@ fn solve_part1(map: &[Vec<char>], all_keys: u32) -> Result<usize, Error> {
         },
         |Cell { keys, .. }| *keys == all_keys,
     )
-    .ok_or_else(|| error!("No path found for part1"))?;
-    Ok(path.len() - 1)
+    .ok_or_else(|| eyre!("No path found for part1"))?;
+    Ok(path.cost)
 }
 
-fn solve_part2(map: &mut Vec<Vec<char>>, all_keys: u32) -> Result<usize, Error> {
+fn solve_part2(map: &mut Vec<Vec<char>>, all_keys: u32) -> Result<usize> {
     let start_poss = get_entrances(&map);
     let start = Cells::new(start_poss, 0, None);
-    let path = bfs(
-        &start,
+    let path = search::bfs(
+        start,
         |Cells { poss, keys, active }| {
             let mut successors: Vec<Cells> = Vec::new();
             for (vault, pos) in poss.iter().enumerate() {
@@ -111,20 +110,31 @@ fn solve_part2(map: &mut Vec<Vec<char>>, all_keys: u32) -> Result<usize, Error>
         },
         |Cells { keys, .. }| *keys == all_keys,
     )
-    .ok_or_else(|| error!("No path found for part2"))?;
-    Ok(path.len() - 1)
+    .ok_or_else(|| eyre!("No path found for part2"))?;
+    Ok(path.cost)
 }
 
-fn parse_input(input: &str) -> (Vec<Vec<char>>, u32) {
+fn parse_input(input: &str) -> Result<(Vec<Vec<char>>, u32), ParseError> {
+    let invalid = |c: char| !matches!(c, '#' | '.' | '@' | '\n' | '\r') && !c.is_ascii_alphabetic();
+
+    if let Some(i) = input.find(invalid) {
+        return Err(ParseError::at_offset(input, i, "invalid tile"));
+    }
+
     let map: Vec<Vec<char>> = input
         .lines()
         .map(|line| line.chars().collect::<Vec<_>>())
         .collect();
+
+    if get_entrances(&map).is_empty() {
+        return Err(ParseError::new("missing entrance `@`"));
+    }
+
     let all_keys = input
         .chars()
         .filter(char::is_ascii_lowercase)
         .fold(0, |keys, key| keys + (1 << (key as u8 - 97)));
-    (map, all_keys)
+    Ok((map, all_keys))
 }
 
 fn transform_map(map: &mut [Vec<char>]) {
@@ -139,30 +149,19 @@ fn transform_map(map: &mut [Vec<char>]) {
     map[start.y - 1][start.x - 1] = '@';
 }
 
-fn get_neighbors(pos: &Point2us) -> Vec<Point2us> {
-    let mut neighbors = Vec::new();
-    for direction in Direction::iter() {
-        let nx = match direction {
-            Direction::W => pos.x - 1,
-            Direction::E => pos.x + 1,
-            _ => pos.x,
-        };
-        let ny = match direction {
-            Direction::N => pos.y - 1,
-            Direction::S => pos.y + 1,
-            _ => pos.y,
-        };
-        neighbors.push(Point2us::new(nx, ny));
-    }
-    neighbors
+fn get_neighbors(pos: &Pos2<usize>) -> Vec<Pos2<usize>> {
+    Direction::ALL
+        .into_iter()
+        .filter_map(|direction| direction.step(*pos))
+        .collect()
 }
 
-fn get_entrances(map: &[Vec<char>]) -> Vec<Point2us> {
+fn get_entrances(map: &[Vec<char>]) -> Vec<Pos2<usize>> {
     let mut entrances = Vec::new();
     for (y, row) in map.iter().enumerate() {
         for (x, c) in row.iter().enumerate() {
             if *c == '@' {
-                entrances.push(Point2us::new(x, y));
+                entrances.push(Pos2::new(x, y));
             }
         }
     }
@@ -171,13 +170,13 @@ fn get_entrances(map: &[Vec<char>]) -> Vec<Point2us> {
 
 #[derive(Eq, PartialEq, Debug, Clone, Hash)]
 struct Cells {
-    poss: Vec<Point2us>,
+    poss: Vec<Pos2<usize>>,
     keys: u32,
     active: Option<usize>,
 }
 
 impl Cells {
-    fn new(poss: Vec<Point2us>, keys: u32, status: Option<usize>) -> Self {
+    fn new(poss: Vec<Pos2<usize>>, keys: u32, status: Option<usize>) -> Self {
         Self {
             poss,
             keys,
@@ -188,12 +187,12 @@ impl Cells {
 
 #[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
 struct Cell {
-    pos: Point2us,
+    pos: Pos2<usize>,
     keys: u32,
 }
 
 impl Cell {
-    fn new(pos: Point2us, keys: u32) -> Self {
+    fn new(pos: Pos2<usize>, keys: u32) -> Self {
         Self { pos, keys }
     }
 }
@@ -206,21 +205,21 @@ mod tests {
     fn test18() {
         let input =
             "########################\n#f.D.E.e.C.b.A.@.a.B.c.#\n######################.#\n#d.....................#\n########################";
-        let (map, keys) = parse_input(input);
+        let (map, keys) = parse_input(input).unwrap();
         assert_eq!(solve_part1(&map, keys).unwrap(), 86);
         let input =
             "########################\n#@..............ac.GI.b#\n###d#e#f################\n###A#B#C################\n###g#h#i################\n########################";
-        let (map, keys) = parse_input(input);
+        let (map, keys) = parse_input(input).unwrap();
         assert_eq!(solve_part1(&map, keys).unwrap(), 81);
         let input =
             "#############\n#g#f.D#..h#l#\n#F###e#E###.#\n#dCba@#@BcIJ#\n#############\n#nK.L@#@G...#\n#M###N#H###.#\n#o#m..#i#jk.#\n#############";
-        let (mut map, keys) = parse_input(input);
+        let (mut map, keys) = parse_input(input).unwrap();
         assert_eq!(solve_part2(&mut map, keys).unwrap(), 72);
     }
 
     #[test]
     // #[ignore = "test takes much time"]
     fn test18_actual() {
-        crate::util::tests::test_full_problem(18, solve, 4420, 2128);
+        crate::util::tests::test_full_problem(18, run, 4420usize, 2128usize);
     }
 }
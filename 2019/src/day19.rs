@@ -1,14 +1,13 @@
-use crate::{computer::Computer, Error, Solution};
+use crate::computer::Computer;
+use aoc_rust::Solution;
+use eyre::Result;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 19,
+    title: "Tractor Beam",
 }
 
-pub fn solve(input: &str) -> Result<Solution<usize, i64>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     let mut p1 = 0;
     for x in 0..50 {
         for y in 0..50 {
@@ -24,20 +23,20 @@ pub fn solve(input: &str) -> Result<Solution<usize, i64>, Error> {
             x += 1;
         }
         if x > 99 && gets_pulled(input, x - 100, y + 99)? {
-            return Ok(Solution::new(p1, (x - 100) * 10_000 + y));
+            return Ok(Solution::new().part1(p1).part2((x - 100) * 10_000 + y));
         }
         y += 1;
     }
 } // 1.4s
 
-fn gets_pulled(input: &str, x: i64, y: i64) -> Result<bool, Error> {
+fn gets_pulled(input: &str, x: i64, y: i64) -> Result<bool> {
     let mut drone = Computer::new(input)?;
     Ok(drone
         .insert(x)
         .insert(y)
         .run()?
         .pop()
-        .ok_or_else(|| error!("Expected output for beam feedback, none found"))?
+        .ok_or_else(|| eyre!("Expected output for beam feedback, none found"))?
         == 1)
 }
 
@@ -47,6 +46,6 @@ mod tests {
 
     #[test]
     fn test19() {
-        crate::util::tests::test_full_problem(19, solve, 189, 7621042);
+        crate::util::tests::test_full_problem(19, run, 189usize, 7621042i64);
     }
 }
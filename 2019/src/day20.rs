@@ -1,65 +1,63 @@
 #![allow(clippy::map_entry)]
-use crate::{
-    util::{Direction, GridMap, Point2i},
-    Error, Solution,
+use aoc_rust::{
+    grid::{Direction, Pos2, SparseGrid},
+    search, Solution,
 };
-use pathfinding::directed::astar::astar;
-use pathfinding::directed::bfs::bfs;
+use eyre::Result;
 use std::collections::HashMap;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 20,
+    title: "Donut Maze",
 }
 
-pub fn solve(input: &str) -> Result<Solution<usize, usize>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     let (maze, start, end) = parse_maze(input)?;
     let p1 = solve_part1(&maze, start, end)?;
     let p2 = solve_part2(&maze, start, end)?;
-    Ok(Solution::new(p1, p2))
+    Ok(Solution::new().part1(p1).part2(p2))
 } // 2.86s
 
-fn solve_part1(maze: &GridMap<Cell>, start: Point2i, end: Point2i) -> Result<usize, Error> {
-    let path = bfs(
-        &maze[&start],
+fn solve_part1(maze: &SparseGrid<Cell>, start: Pos2<i32>, end: Pos2<i32>) -> Result<usize> {
+    let path = search::bfs(
+        maze[start],
         |Cell { pos, portal, .. }| {
             let mut neighbors = Vec::with_capacity(5);
-            for direction in Direction::iter() {
-                let neighbor = *pos + direction.shift();
-                if let Some(Cell { passage, .. }) = maze.get(&neighbor) {
+            for direction in Direction::ALL {
+                let neighbor = *pos + direction.delta();
+                if let Some(Cell { passage, .. }) = maze.get(neighbor) {
                     if *passage {
-                        neighbors.push(maze[&neighbor]);
+                        neighbors.push(maze[neighbor]);
                     }
                 }
             }
             if let Some((portal, _)) = portal {
-                neighbors.push(maze[&portal]);
+                neighbors.push(maze[*portal]);
             }
             neighbors
         },
         |Cell { pos, .. }| pos == &end,
     )
-    .ok_or_else(|| error!("No path found for part1"))?;
-    Ok(path.len() - 1)
+    .ok_or_else(|| eyre!("No path found for part1"))?;
+    Ok(path.cost)
 }
 
-fn solve_part2(maze: &GridMap<Cell>, start: Point2i, end: Point2i) -> Result<usize, Error> {
+fn solve_part2(maze: &SparseGrid<Cell>, start: Pos2<i32>, end: Pos2<i32>) -> Result<usize> {
     // Using A* (same interface as BFS but with heuristic and costs between cells)
-    let h = maze.get_height();
-    let path = astar(
-        &maze[&start],
+    let h = maze
+        .bounds()
+        .map_or(0, |(min, max)| (max.y - min.y + 1) as usize);
+    let path = search::astar(
+        maze[start],
         |Cell {
              pos, portal, level, ..
          }| {
             let mut neighbors = Vec::with_capacity(5);
-            for direction in Direction::iter() {
-                let neighbor = *pos + direction.shift();
-                if let Some(Cell { passage, .. }) = maze.get(&neighbor) {
+            for direction in Direction::ALL {
+                let neighbor = *pos + direction.delta();
+                if let Some(Cell { passage, .. }) = maze.get(neighbor) {
                     if *passage {
-                        let mut n_cell = maze[&neighbor];
+                        let mut n_cell = maze[neighbor];
                         n_cell.level = *level;
                         neighbors.push((n_cell, 1));
                     }
@@ -67,7 +65,7 @@ fn solve_part2(maze: &GridMap<Cell>, start: Point2i, end: Point2i) -> Result<usi
             }
             if let Some((portal, outer)) = portal {
                 if *level != 0 || !outer {
-                    let mut portal_cell = maze[&portal];
+                    let mut portal_cell = maze[*portal];
                     if *outer {
                         portal_cell.level = level - 1;
                     } else {
@@ -81,23 +79,25 @@ fn solve_part2(maze: &GridMap<Cell>, start: Point2i, end: Point2i) -> Result<usi
         |Cell { level, .. }| *level * h,
         |Cell { pos, level, .. }| *level == 0 && *pos == end,
     )
-    .ok_or_else(|| error!("No path found for part2"))?;
-    Ok(path.1)
+    .ok_or_else(|| eyre!("No path found for part2"))?;
+    Ok(path.cost)
 }
 
-fn parse_maze(input: &str) -> Result<(GridMap<Cell>, Point2i, Point2i), Error> {
+fn parse_maze(input: &str) -> Result<(SparseGrid<Cell>, Pos2<i32>, Pos2<i32>)> {
     let mut maze_matrix: Vec<Vec<char>> =
         input.lines().map(|line| line.chars().collect()).collect();
     let h = maze_matrix.len();
-    let w = maze_matrix[0].len();
+    let w = maze_matrix.first().map_or(0, Vec::len);
 
-    let last_row = maze_matrix.last_mut().unwrap();
+    let Some(last_row) = maze_matrix.last_mut() else {
+        bail!("Empty maze");
+    };
     if last_row.len() < w {
         last_row.resize(w, ' ');
     }
 
-    let mut maze: GridMap<Cell> = GridMap::new();
-    let mut portals: HashMap<String, (Point2i, bool)> = HashMap::new();
+    let mut maze: SparseGrid<Cell> = SparseGrid::new();
+    let mut portals: HashMap<String, (Pos2<i32>, bool)> = HashMap::new();
     let mut start = None;
     let mut end = None;
     for y in 0..h {
@@ -105,11 +105,11 @@ fn parse_maze(input: &str) -> Result<(GridMap<Cell>, Point2i, Point2i), Error> {
             match maze_matrix[y][x] {
                 ' ' => continue,
                 '#' => {
-                    let pos = Point2i::new(x as i32 - 2, y as i32 - 2);
+                    let pos = Pos2::new(x as i32 - 2, y as i32 - 2);
                     maze.insert(pos, Cell::new(pos, false, None, 0));
                 }
                 '.' => {
-                    let pos = Point2i::new(x as i32 - 2, y as i32 - 2);
+                    let pos = Pos2::new(x as i32 - 2, y as i32 - 2);
                     maze.entry(pos)
                         .or_insert_with(|| Cell::new(pos, true, None, 0));
                 }
@@ -120,18 +120,18 @@ fn parse_maze(input: &str) -> Result<(GridMap<Cell>, Point2i, Point2i), Error> {
                             let pos = {
                                 if x == nx {
                                     if ny + 1 < h && maze_matrix[ny + 1][nx] == '.' {
-                                        Point2i::new(x as i32 - 2, ny as i32 - 1)
+                                        Pos2::new(x as i32 - 2, ny as i32 - 1)
                                     } else if y > 0 && maze_matrix[y - 1][nx] == '.' {
-                                        Point2i::new(x as i32 - 2, y as i32 - 3)
+                                        Pos2::new(x as i32 - 2, y as i32 - 3)
                                     } else {
                                         bail!("x == nx but could not find '.' on either end");
                                     }
                                 } else {
                                     // y == ny
                                     if nx + 1 < w && maze_matrix[ny][nx + 1] == '.' {
-                                        Point2i::new(nx as i32 - 1, y as i32 - 2)
+                                        Pos2::new(nx as i32 - 1, y as i32 - 2)
                                     } else if x > 0 && maze_matrix[ny][x - 1] == '.' {
-                                        Point2i::new(x as i32 - 3, y as i32 - 2)
+                                        Pos2::new(x as i32 - 3, y as i32 - 2)
                                     } else {
                                         bail!("y == ny but could not find '.' on either end");
                                     }
@@ -171,7 +171,9 @@ fn parse_maze(input: &str) -> Result<(GridMap<Cell>, Point2i, Point2i), Error> {
             }
         }
     }
-    Ok((maze, start.unwrap(), end.unwrap()))
+    let start = start.ok_or_else(|| eyre!("Missing start portal AA"))?;
+    let end = end.ok_or_else(|| eyre!("Missing end portal ZZ"))?;
+    Ok((maze, start, end))
 }
 
 fn get_successors(x: usize, y: usize, w: usize, h: usize) -> Vec<(usize, usize)> {
@@ -187,14 +189,14 @@ fn get_successors(x: usize, y: usize, w: usize, h: usize) -> Vec<(usize, usize)>
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 struct Cell {
-    pos: Point2i,
+    pos: Pos2<i32>,
     passage: bool,
-    portal: Option<(Point2i, bool)>,
+    portal: Option<(Pos2<i32>, bool)>,
     level: usize,
 }
 
 impl Cell {
-    fn new(pos: Point2i, passage: bool, portal: Option<(Point2i, bool)>, level: usize) -> Self {
+    fn new(pos: Pos2<i32>, passage: bool, portal: Option<(Pos2<i32>, bool)>, level: usize) -> Self {
         Self {
             pos,
             passage,
@@ -207,7 +209,7 @@ impl Cell {
 impl Default for Cell {
     fn default() -> Self {
         Self {
-            pos: Point2i::default(),
+            pos: Pos2::default(),
             passage: false,
             portal: None,
             level: 0,
@@ -218,19 +220,16 @@ impl Default for Cell {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::tests::assert_solution;
 
     #[test]
     fn test20() {
         let input =
             "         A           \n         A           \n  #######.#########  \n  #######.........#  \n  #######.#######.#  \n  #######.#######.#  \n  #######.#######.#  \n  #####  B    ###.#  \nBC...##  C    ###.#  \n  ##.##       ###.#  \n  ##...DE  F  ###.#  \n  #####    G  ###.#  \n  #########.#####.#  \nDE..#######...###.#  \n  #.#########.###.#  \nFG..#########.....#  \n  ###########.#####  \n             Z       \n             Z       ";
-        let solution = solve(input).unwrap();
-        assert_eq!(solution.part1, 23);
-        assert_eq!(solution.part2, 26);
+        assert_solution(&run(input).unwrap(), 23usize, 26usize);
         let input =
             "             Z L X W       C                 \n             Z P Q B       K                 \n  ###########.#.#.#.#######.###############  \n  #...#.......#.#.......#.#.......#.#.#...#  \n  ###.#.#.#.#.#.#.#.###.#.#.#######.#.#.###  \n  #.#...#.#.#...#.#.#...#...#...#.#.......#  \n  #.###.#######.###.###.#.###.###.#.#######  \n  #...#.......#.#...#...#.............#...#  \n  #.#########.#######.#.#######.#######.###  \n  #...#.#    F       R I       Z    #.#.#.#  \n  #.###.#    D       E C       H    #.#.#.#  \n  #.#...#                           #...#.#  \n  #.###.#                           #.###.#  \n  #.#....OA                       WB..#.#..ZH\n  #.###.#                           #.#.#.#  \nCJ......#                           #.....#  \n  #######                           #######  \n  #.#....CK                         #......IC\n  #.###.#                           #.###.#  \n  #.....#                           #...#.#  \n  ###.###                           #.#.#.#  \nXF....#.#                         RF..#.#.#  \n  #####.#                           #######  \n  #......CJ                       NM..#...#  \n  ###.#.#                           #.###.#  \nRE....#.#                           #......RF\n  ###.###        X   X       L      #.#.#.#  \n  #.....#        F   Q       P      #.#.#.#  \n  ###.###########.###.#######.#########.###  \n  #.....#...#.....#.......#...#.....#.#...#  \n  #####.#.###.#######.#######.###.###.#.#.#  \n  #.......#.......#.#.#.#.#...#...#...#.#.#  \n  #####.###.#####.#.#.#.#.###.###.#.###.###  \n  #.......#.....#.#...#...............#...#  \n  #############.#.#.###.###################  \n               A O F   N                     \n               A A D   M                     ";
-        let solution = solve(input).unwrap();
-        assert_eq!(solution.part1, 77);
-        assert_eq!(solution.part2, 396);
-        crate::util::tests::test_full_problem(20, solve, 686, 8384);
+        assert_solution(&run(input).unwrap(), 77usize, 396usize);
+        crate::util::tests::test_full_problem(20, run, 686usize, 8384usize);
     }
 }
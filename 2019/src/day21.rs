@@ -1,14 +1,13 @@
-use crate::{computer::Computer, Error, Solution};
+use crate::computer::Computer;
+use aoc_rust::Solution;
+use eyre::Result;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 21,
+    title: "Springdroid Adventure",
 }
 
-pub fn solve(input: &str) -> Result<Solution<i64, i64>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     let mut computer = Computer::new(input)?;
     computer
         // !C && D
@@ -22,7 +21,7 @@ pub fn solve(input: &str) -> Result<Solution<i64, i64>, Error> {
     let p1 = computer
         .output_iter()
         .last()
-        .ok_or_else(|| error!("Computer did not prodocude an output for part1"))?;
+        .ok_or_else(|| eyre!("Computer did not prodocude an output for part1"))?;
     let mut computer = Computer::new(input)?;
     computer
         // !B && !E && D
@@ -48,8 +47,8 @@ pub fn solve(input: &str) -> Result<Solution<i64, i64>, Error> {
     let p2 = computer
         .output_iter()
         .last()
-        .ok_or_else(|| error!("Computer did not prodocude an output for part2"))?;
-    Ok(Solution::new(*p1, *p2))
+        .ok_or_else(|| eyre!("Computer did not prodocude an output for part2"))?;
+    Ok(Solution::new().part1(*p1).part2(*p2))
 } // 275.18ms
 
 trait SpringScript {
@@ -117,6 +116,6 @@ mod tests {
 
     #[test]
     fn test21() {
-        crate::util::tests::test_full_problem(21, solve, 19349939, 1142412777);
+        crate::util::tests::test_full_problem(21, run, 19349939i64, 1142412777i64);
     }
 }
@@ -1,27 +1,23 @@
-use crate::{Error, Solution};
-use std::str::FromStr;
-
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+use aoc_rust::{
+    util::parse::{parse_at, ParseContext, ParseError},
+    Solution,
+};
+use eyre::Result;
+
+aoc_rust::register_day! {
+    day: 22,
+    title: "Slam Shuffle",
 }
 
-pub fn solve(input: &str) -> Result<Solution<u64, u64>, Error> {
-    let p1 = part1(input);
-    let p2 = part2(input);
+pub fn run(input: &str) -> Result<Solution> {
+    let p1 = part1(input)?;
+    let p2 = part2(input)?;
 
-    Ok(Solution::new(p1, p2))
+    Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> u64 {
-    let mut shuffle: Vec<_> = input
-        .lines()
-        .map(Step::from_str)
-        .map(Result::unwrap)
-        .collect();
+fn part1(input: &str) -> Result<u64, ParseError> {
+    let mut shuffle = parse_shuffle(input)?;
 
     const LEN: u64 = 10_007;
 
@@ -33,18 +29,14 @@ fn part1(input: &str) -> u64 {
         p1 = step.predict_next(p1, LEN);
     }
 
-    p1
+    Ok(p1)
 }
 
-fn part2(input: &str) -> u64 {
+fn part2(input: &str) -> Result<u64> {
     let len = 119_315_717_514_047;
     let mut iters: u64 = 101_741_582_076_661;
 
-    let mut shuffle: Vec<_> = input
-        .lines()
-        .map(Step::from_str)
-        .map(Result::unwrap)
-        .collect();
+    let mut shuffle = parse_shuffle(input)?;
 
     minimize_shuffle(&mut shuffle, len);
 
@@ -76,10 +68,16 @@ fn part2(input: &str) -> u64 {
     let mut p2 = 2020;
 
     for &step in final_shuffle.iter().rev() {
-        p2 = step.predict_prev(p2, len);
+        p2 = step
+            .predict_prev(p2, len)
+            .ok_or_else(|| eyre!("Shuffle can not be reversed for {} cards", len))?;
     }
 
-    p2
+    Ok(p2)
+}
+
+fn parse_shuffle(input: &str) -> Result<Vec<Step>, ParseError> {
+    input.lines().map(|line| Step::parse(input, line)).collect()
 }
 
 // Swap & remove elements until the shuffle contains only one element per Step variant
@@ -143,24 +141,24 @@ enum Step {
     Increment(u64),
 }
 
-impl FromStr for Step {
-    type Err = ();
+impl Step {
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let mut split = line.split(' ');
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split(' ');
+        let technique = match (split.next(), split.next()) {
+            (Some("cut"), Some(n)) => Self::Cut(parse_at(input, n)?),
+            (Some("deal"), Some("into")) => Self::NewStack,
+            (Some("deal"), Some("with")) => {
+                let n = split.nth(1).context_at(input, line, "expected increment")?;
 
-        let technique = match (split.next().unwrap(), split.next().unwrap()) {
-            ("cut", n) => Self::Cut(n.parse().unwrap()),
-            ("deal", "into") => Self::NewStack,
-            ("deal", "with") => Self::Increment(split.nth(1).unwrap().parse().unwrap()),
-            _ => return Err(()),
+                Self::Increment(parse_at(input, n)?)
+            }
+            _ => return Err(ParseError::at(input, line, "unknown technique")),
         };
 
         Ok(technique)
     }
-}
 
-impl Step {
     // No longer required, replaced by predict_next and predict_prev
     fn _apply(self, cards: &mut [usize], buf: &mut [usize]) {
         match self {
@@ -193,11 +191,11 @@ impl Step {
     }
 
     // What position is being mapped to position `pos` after one shuffle
-    fn predict_prev(self, pos: u64, len: u64) -> u64 {
+    fn predict_prev(self, pos: u64, len: u64) -> Option<u64> {
         match self {
-            Self::NewStack => len - pos - 1,
-            Self::Increment(n) => linear_congruence(n, pos, len).unwrap(),
-            Self::Cut(n) => (pos as i64 + n + len as i64) as u64 % len,
+            Self::NewStack => Some(len - pos - 1),
+            Self::Increment(n) => linear_congruence(n, pos, len),
+            Self::Cut(n) => Some((pos as i64 + n + len as i64) as u64 % len),
         }
     }
 }
@@ -261,6 +259,6 @@ mod tests {
 
     #[test]
     fn test22() {
-        crate::util::tests::test_full_problem(22, solve, 4284, 96_797_432_275_571);
+        crate::util::tests::test_full_problem(22, run, 4284u64, 96_797_432_275_571u64);
     }
 }
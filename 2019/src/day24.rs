@@ -1,16 +1,14 @@
-use crate::{Error, Solution};
+use aoc_rust::Solution;
+use eyre::Result;
 
 use std::collections::HashSet;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
-
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+aoc_rust::register_day! {
+    day: 24,
+    title: "Planet of Discord",
 }
 
-pub fn solve(input: &str) -> Result<Solution<usize, usize>, Error> {
+pub fn run(input: &str) -> Result<Solution> {
     let area: Vec<char> = input.lines().flat_map(|line| line.chars()).collect();
     let p1 = solve_part1(area.clone());
     const STEPS: usize = 200;
@@ -19,7 +17,7 @@ pub fn solve(input: &str) -> Result<Solution<usize, usize>, Error> {
         layers.step();
     }
     let p2 = layers.count_bugs();
-    Ok(Solution::new(p1, p2))
+    Ok(Solution::new().part1(p1).part2(p2))
 } // 591.25ms
 
 struct Layers {
@@ -195,6 +193,6 @@ mod tests {
 
     #[test]
     fn test24() {
-        crate::util::tests::test_full_problem(24, solve, 18844281, 1872);
+        crate::util::tests::test_full_problem(24, run, 18844281usize, 1872usize);
     }
 }
@@ -1,80 +1,197 @@
-use crate::{computer::Computer, Error, Solution};
+use crate::computer::Computer;
+use aoc_rust::Solution;
+use eyre::Result;
 
-use std::io::{self, BufRead};
+use std::collections::HashSet;
 
-pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
-    let solution = solve(input)?;
+/// Items that end the game or get the droid stuck when taken
+const FATAL_ITEMS: [&str; 5] = [
+    "escape pod",
+    "giant electromagnet",
+    "infinite loop",
+    "molten lava",
+    "photons",
+];
 
-    Ok(aoc_rust::Solution::new()
-        .part1(solution.part1)
-        .part2(solution.part2))
+const CHECKPOINT: &str = "Security Checkpoint";
+
+aoc_rust::register_day! {
+    day: 25,
+    title: "Cryostasis",
 }
 
-pub fn solve(input: &str) -> Result<Solution<i32, i32>, Error> {
-    let mut robot = Computer::new(input)?;
-    robot.run()?.print();
-    /* Play yourself
-    // Take sand, fixed point, wreath, and space law space brochure
-    while robot.get_instruction() {
-        robot.run()?.print();
+pub fn run(input: &str) -> Result<Solution> {
+    let mut droid = Droid::new(input)?;
+    let start = Room::parse(&droid.output())?;
+    let mut exploration = Exploration::default();
+    droid.explore(&start, &mut Vec::new(), &mut exploration)?;
+    let (path, floor) = exploration
+        .checkpoint
+        .ok_or_else(|| eyre!("Did not find the {}", CHECKPOINT))?;
+    for door in path {
+        droid.command(&door)?;
     }
-    */
-    robot
-        .write_instruction("east")?
-        .write_instruction("take sand")?
-        .write_instruction("west")?
-        .write_instruction("west")?
-        .write_instruction("north")?
-        .write_instruction("take wreath")?
-        .write_instruction("east")?
-        .write_instruction("take fixed point")?
-        .write_instruction("west")?
-        .write_instruction("south")?
-        .write_instruction("south")?
-        .write_instruction("east")?
-        .write_instruction("east")?
-        .write_instruction("east")?
-        .write_instruction("take space law space brochure")?
-        .write_instruction("south")?
-        .write_instruction("south")?
-        .write_instruction("west")?
-        .run()?
-        .print(); // Answer: 16778274
-    Ok(Solution::new(0, 0))
+    let p1 = droid.pass_floor(&exploration.items, &floor)?;
+    Ok(Solution::new().part1(p1))
 } // 73.37ms
 
-trait ShipRobot {
-    #[allow(unused)]
-    fn get_instruction(&mut self) -> bool;
-    fn write_instruction(&mut self, instruction: &str) -> Result<&mut Self, Error>;
-    fn print(&mut self);
+struct Room {
+    name: String,
+    doors: Vec<String>,
+    items: Vec<String>,
+}
+
+impl Room {
+    /// Parse the last room described in the droid's output
+    fn parse(output: &str) -> Result<Self> {
+        let start = output
+            .rfind("== ")
+            .ok_or_else(|| eyre!("No room description found in output:\n{}", output))?;
+        let mut lines = output[start..].lines();
+        let name = lines
+            .next()
+            .unwrap_or_default()
+            .trim_matches(|c| c == '=' || c == ' ')
+            .to_owned();
+        let (mut doors, mut items) = (Vec::new(), Vec::new());
+        let mut in_doors = true;
+        for line in lines {
+            match line {
+                "Doors here lead:" => in_doors = true,
+                "Items here:" => in_doors = false,
+                _ => {
+                    if let Some(entry) = line.strip_prefix("- ") {
+                        if in_doors {
+                            doors.push(entry.to_owned());
+                        } else {
+                            items.push(entry.to_owned());
+                        }
+                    }
+                }
+            }
+        }
+        Ok(Self { name, doors, items })
+    }
+}
+
+#[derive(Default)]
+struct Exploration {
+    visited: HashSet<String>,
+    items: Vec<String>,
+    /// Path from the start to the checkpoint and the door to the pressure-sensitive floor
+    checkpoint: Option<(Vec<String>, String)>,
+}
+
+struct Droid {
+    computer: Computer,
 }
 
-impl ShipRobot for Computer {
-    fn write_instruction(&mut self, instruction: &str) -> Result<&mut Self, Error> {
-        for c in instruction.chars() {
-            self.insert(c as i64);
+impl Droid {
+    fn new(input: &str) -> Result<Self> {
+        let mut computer = Computer::new(input)?;
+        computer.run()?;
+        Ok(Self { computer })
+    }
+
+    fn output(&mut self) -> String {
+        let mut output = String::new();
+        while let Some(c) = self.computer.pop() {
+            output.push(c as u8 as char);
         }
-        self.insert(10).run()
+        output
     }
 
-    fn print(&mut self) {
-        while let Some(output) = self.pop() {
-            print!("{}", output as u8 as char);
+    fn command(&mut self, command: &str) -> Result<String> {
+        for c in command.chars() {
+            self.computer.insert(c as i64);
         }
+        self.computer.insert(10).run()?;
+        Ok(self.output())
     }
 
-    fn get_instruction(&mut self) -> bool {
-        let mut line = String::new();
-        let stdin = io::stdin();
-        if stdin.lock().read_line(&mut line).is_err() || line.trim() == "break" {
-            return false;
+    /// Visit all rooms reachable from the given one, picking up all safe items,
+    /// and return to it afterwards
+    fn explore(
+        &mut self,
+        room: &Room,
+        path: &mut Vec<String>,
+        exploration: &mut Exploration,
+    ) -> Result<()> {
+        exploration.visited.insert(room.name.clone());
+        for item in room.items.iter() {
+            if !FATAL_ITEMS.contains(&item.as_str()) {
+                self.command(&format!("take {}", item))?;
+                exploration.items.push(item.clone());
+            }
+        }
+        let back = path.last().map(|door| opposite(door)).transpose()?;
+        if room.name == CHECKPOINT {
+            // the other door leads to the floor which would eject the droid
+            let floor = room
+                .doors
+                .iter()
+                .find(|door| Some(door.as_str()) != back)
+                .ok_or_else(|| eyre!("{} has no door to the floor", CHECKPOINT))?;
+            exploration.checkpoint = Some((path.clone(), floor.clone()));
+            return Ok(());
         }
-        for c in line.trim().chars() {
-            self.insert(c as i64);
+        for door in room.doors.iter() {
+            if Some(door.as_str()) == back {
+                continue;
+            }
+            let next = Room::parse(&self.command(door)?)?;
+            if !exploration.visited.contains(&next.name) {
+                path.push(door.clone());
+                self.explore(&next, path, exploration)?;
+                path.pop();
+            }
+            self.command(opposite(door)?)?;
         }
-        self.insert(10);
-        true
+        Ok(())
+    }
+
+    /// Try all subsets of the items while standing at the checkpoint, starting
+    /// with all items taken, until the floor lets the droid pass
+    fn pass_floor(&mut self, items: &[String], floor: &str) -> Result<u64> {
+        let all = (1_u32 << items.len()) - 1;
+        let mut held = all;
+        for i in 0..1_u32 << items.len() {
+            // inverted gray code so that the first attempt holds all items and
+            // only one item changes per attempt
+            let subset = !(i ^ (i >> 1)) & all;
+            for (j, item) in items.iter().enumerate() {
+                if (held ^ subset) & (1 << j) != 0 {
+                    let action = if subset & (1 << j) != 0 {
+                        "take"
+                    } else {
+                        "drop"
+                    };
+                    self.command(&format!("{} {}", action, item))?;
+                }
+            }
+            held = subset;
+            let output = self.command(floor)?;
+            if output.contains("Alert!") {
+                continue;
+            }
+            return output
+                .split("typing ")
+                .nth(1)
+                .and_then(|rest| rest.split_whitespace().next())
+                .and_then(|code| code.parse().ok())
+                .ok_or_else(|| eyre!("No keypad code found in output:\n{}", output));
+        }
+        bail!("No combination of items passed the pressure-sensitive floor");
+    }
+}
+
+fn opposite(door: &str) -> Result<&'static str> {
+    match door {
+        "north" => Ok("south"),
+        "south" => Ok("north"),
+        "east" => Ok("west"),
+        "west" => Ok("east"),
+        _ => bail!("Unknown door '{}'", door),
     }
 }
 
@@ -84,6 +201,7 @@ mod tests {
 
     #[test]
     fn test25() {
-        crate::util::tests::test_full_problem(25, solve, 0, 0);
+        let input = std::fs::read_to_string("inputs/day25.txt").unwrap();
+        assert_eq!(run(&input).unwrap().part1, 16_778_274u64);
     }
 }
@@ -1,13 +0,0 @@
-#[macro_export]
-macro_rules! error {
-    ($($arg:tt)*) => {
-        $crate::Error::Custom(format!("{}", format_args!($($arg)*)))
-    };
-}
-
-#[macro_export]
-macro_rules! bail {
-    ($($arg:tt)*) => {
-        return Err($crate::Error::Custom(format!("{}", format_args!($($arg)*))))
-    };
-}
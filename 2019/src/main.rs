@@ -1,68 +1,49 @@
 use aoc19::*;
-use std::time::Instant;
-use std::{env, fs, process::exit};
+use aoc_rust::{timing, Solution};
+use eyre::{Result, WrapErr};
+use std::{env, fs};
 
-fn main() {
-    if let Err(e) = run() {
-        eprintln!("{}", e);
-        let mut e: &dyn std::error::Error = &e;
-        while let Some(source) = e.source() {
-            eprintln!("  - caused by: {}", source);
-            e = source;
-        }
-        exit(1);
-    }
-}
+fn main() -> Result<()> {
+    let day: u8 = env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(25);
 
-fn run() -> Result<(), Error> {
-    let days = vec![
-        1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
-    ];
+    let path = format!("inputs/day{:02}.txt", day);
+    let input = fs::read_to_string(&path).wrap_err_with(|| format!("Failed to read {path}"))?;
 
-    let day: i32 = if let Some(arg) = env::args().nth(1) {
-        arg.parse().unwrap_or(*days.last().unwrap())
-    } else {
-        *days.last().unwrap()
+    let run: fn(&str) -> Result<Solution> = match day {
+        1 => day01::run,
+        2 => day02::run,
+        3 => day03::run,
+        4 => day04::run,
+        5 => day05::run,
+        6 => day06::run,
+        7 => day07::run,
+        8 => day08::run,
+        9 => day09::run,
+        10 => day10::run,
+        11 => day11::run,
+        12 => day12::run,
+        13 => day13::run,
+        14 => day14::run,
+        15 => day15::run,
+        16 => day16::run,
+        17 => day17::run,
+        18 => day18::run,
+        19 => day19::run,
+        20 => day20::run,
+        21 => day21::run,
+        22 => day22::run,
+        23 => day23::run,
+        24 => day24::run,
+        25 => day25::run,
+        _ => eyre::bail!("Invalid day {day}"),
     };
-    let input =
-        fs::read_to_string(format!("inputs/day{:02}.txt", day)).expect("Error: Invalid day");
-    let input = input.trim_end();
 
-    let start = Instant::now();
-    let solution = match day {
-        1 => day01::solve(input)?.to_string(),
-        2 => day02::solve(input)?.to_string(),
-        3 => day03::solve(input)?.to_string(),
-        4 => day04::solve(input)?.to_string(),
-        5 => day05::solve(input)?.to_string(),
-        6 => day06::solve(input)?.to_string(),
-        7 => day07::solve(input)?.to_string(),
-        8 => day08::solve(input)?.to_string(),
-        9 => day09::solve(input)?.to_string(),
-        10 => day10::solve(input)?.to_string(),
-        11 => day11::solve(input)?.to_string(),
-        12 => day12::solve(input)?.to_string(),
-        13 => day13::solve(input)?.to_string(),
-        14 => day14::solve(input)?.to_string(),
-        15 => day15::solve(input)?.to_string(),
-        16 => day16::solve(input)?.to_string(),
-        17 => day17::solve(input)?.to_string(),
-        18 => day18::solve(input)?.to_string(),
-        19 => day19::solve(input)?.to_string(),
-        20 => day20::solve(input)?.to_string(),
-        21 => day21::solve(input)?.to_string(),
-        22 => day22::solve(input)?.to_string(),
-        23 => day23::solve(input)?.to_string(),
-        24 => day24::solve(input)?.to_string(),
-        25 => day25::solve(input)?.to_string(),
-        d if 0 < d && d < 26 => bail!("Day {} not yet implemented", d),
-        _ => bail!("Invalid day"),
-    };
-    println!(
-        "[Day {}] Elapsed time: {:?}\n{}",
-        day,
-        Instant::now().checked_duration_since(start).unwrap(),
-        solution,
-    );
+    let (solution, timings) = timing::time(|| run(&input));
+    let solution = solution?;
+    println!("[Day {day}] Elapsed time: {timings}\n{solution}");
+
     Ok(())
 }
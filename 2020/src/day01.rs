@@ -1,57 +1,53 @@
-use std::hint::unreachable_unchecked;
-
-use aoc_rust::Solution;
+use aoc_rust::{util::parse::parse_at, Solution};
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 1,
+    title: "Report Repair",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut numbers = Vec::with_capacity(200);
 
     for line in input.lines() {
-        let n = line.parse().unwrap();
+        let n = parse_at(input, line)?;
         numbers.push(n);
     }
 
     numbers.sort_unstable();
 
-    let p1 = part1(&numbers);
-    let p2 = part2(&numbers);
+    let p1 = part1(&numbers).ok_or_else(|| eyre::eyre!("no two entries sum to 2020"))?;
+    let p2 = part2(&numbers).ok_or_else(|| eyre::eyre!("no three entries sum to 2020"))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(numbers: &[u32]) -> u32 {
+fn part1(numbers: &[u32]) -> Option<u32> {
     for i in 0..numbers.len() {
-        let res = unsafe {
-            numbers
-                .get_unchecked(i + 1..)
-                .binary_search(&(2020 - numbers.get_unchecked(i)))
+        let Some(rest) = 2020_u32.checked_sub(numbers[i]) else {
+            break;
         };
-        if let Ok(j) = res {
-            return unsafe { numbers.get_unchecked(i) * numbers.get_unchecked(j + i + 1) };
+
+        if let Ok(j) = numbers[i + 1..].binary_search(&rest) {
+            return Some(numbers[i] * numbers[j + i + 1]);
         }
     }
-    unsafe { unreachable_unchecked() }
+
+    None
 }
 
-fn part2(numbers: &[u32]) -> u32 {
+fn part2(numbers: &[u32]) -> Option<u32> {
     for i in 0..numbers.len() {
         for j in i + 1..numbers.len() {
-            if unsafe { numbers.get_unchecked(i) + numbers.get_unchecked(j) } > 2020 {
+            let Some(rest) = 2020_u32.checked_sub(numbers[i] + numbers[j]) else {
                 break;
-            }
-            let res = unsafe {
-                numbers
-                    .get_unchecked(j + 1..)
-                    .binary_search(&(2020 - numbers.get_unchecked(i) - numbers.get_unchecked(j)))
             };
-            if let Ok(k) = res {
-                return unsafe {
-                    numbers.get_unchecked(i)
-                        * numbers.get_unchecked(j)
-                        * numbers.get_unchecked(k + j + 1)
-                };
+
+            if let Ok(k) = numbers[j + 1..].binary_search(&rest) {
+                return Some(numbers[i] * numbers[j] * numbers[k + j + 1]);
             }
         }
     }
-    unsafe { unreachable_unchecked() }
+
+    None
 }
@@ -1,32 +1,44 @@
-use std::hint::unreachable_unchecked;
-
-use aoc_rust::Solution;
+use aoc_rust::{
+    util::parse::{parse_at, ParseContext, ParseError},
+    Solution,
+};
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 2,
+    title: "Password Philosophy",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut p1 = 0;
     let mut p2 = 0;
 
     for line in input.lines() {
-        let mut split = line.split('-');
-        let min = split.next().unwrap().parse().unwrap();
-        let mut split = split
-            .next()
-            .unwrap_or_else(|| unsafe { unreachable_unchecked() })
-            .split(' ');
-        let max = split.next().unwrap().parse().unwrap();
-        let letter = unsafe {
-            *split
-                .next()
-                .unwrap_or_else(|| unreachable_unchecked())
-                .as_bytes()
-                .get_unchecked(0)
+        let (min, rest) = line
+            .split_once('-')
+            .context_at(input, line, "expected `-`")?;
+        let min = parse_at(input, min)?;
+
+        let mut split = rest.split(' ');
+        let max = split.next().context_at(input, line, "expected maximum")?;
+        let max = parse_at(input, max)?;
+
+        let policy = split.next().context_at(input, line, "expected letter")?;
+
+        let letter = match policy.as_bytes() {
+            [letter, b':'] => *letter,
+            _ => return Err(ParseError::at(input, policy, "expected `<letter>:`").into()),
         };
+
         let password = split
             .next()
-            .unwrap_or_else(|| unsafe { unreachable_unchecked() })
+            .context_at(input, line, "expected password")?
             .as_bytes();
 
+        if min == 0 || max == 0 {
+            return Err(ParseError::at(input, line, "positions start at 1").into());
+        }
+
         p1 += part1(min, max, letter, password) as u16;
         p2 += part2(min - 1, max - 1, letter, password) as u16;
     }
@@ -41,5 +53,5 @@ fn part1(min: usize, max: usize, letter: u8, password: &[u8]) -> bool {
 }
 
 fn part2(min: usize, max: usize, letter: u8, password: &[u8]) -> bool {
-    unsafe { (*password.get_unchecked(min) == letter) ^ (*password.get_unchecked(max) == letter) }
+    (password.get(min) == Some(&letter)) ^ (password.get(max) == Some(&letter))
 }
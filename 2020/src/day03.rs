@@ -1,36 +1,49 @@
-use aoc_rust::Solution;
+use aoc_rust::{util::parse::ParseError, Solution};
 use eyre::Result;
 
-#[rustfmt::skip]
-static mut COUNTERS: [Counter; 4] = [
-    Counter { step: 1, x: 0, count: 0 },
-    Counter { step: 3, x: 0, count: 0 },
-    Counter { step: 5, x: 0, count: 0 },
-    Counter { step: 7, x: 0, count: 0 },
-];
+aoc_rust::register_day! {
+    day: 3,
+    title: "Toboggan Trajectory",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
+    #[rustfmt::skip]
+    let mut counters = [
+        Counter { step: 1, x: 0, count: 0 },
+        Counter { step: 3, x: 0, count: 0 },
+        Counter { step: 5, x: 0, count: 0 },
+        Counter { step: 7, x: 0, count: 0 },
+    ];
+
     let mut skipper = 0;
     let mut skipper_x = 0;
     let mut y = 0;
+    let mut width = None;
 
     for line in input.lines() {
         let trimmed = line.trim_end().as_bytes();
 
-        for counter in unsafe { COUNTERS.iter_mut() } {
-            counter.count += (unsafe { *trimmed.get_unchecked(counter.x) } == b'#') as u32;
+        if trimmed.is_empty() || *width.get_or_insert(trimmed.len()) != trimmed.len() {
+            return Err(
+                ParseError::at(input, line, "rows must be non-empty and of equal width").into(),
+            );
+        }
+
+        for counter in counters.iter_mut() {
+            counter.count += (trimmed[counter.x] == b'#') as u32;
             counter.x = (counter.x + counter.step) % trimmed.len();
         }
 
         if y % 2 == 0 {
-            skipper += (unsafe { *trimmed.get_unchecked(skipper_x) } == b'#') as u32;
+            skipper += (trimmed[skipper_x] == b'#') as u32;
             skipper_x = (skipper_x + 1) % trimmed.len();
         }
         y += 1;
     }
 
-    let p1 = unsafe { COUNTERS.get_unchecked(1).count };
-    let p2 = unsafe { COUNTERS.iter() }
+    let p1 = counters[1].count;
+    let p2 = counters
+        .iter()
         .map(|counter| counter.count)
         .fold(skipper, |product, count| product * count);
 
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use aoc_rust::Solution;
+use aoc_rust::{util::parse::ParseError, Solution};
 use eyre::Result;
 
 macro_rules! parse {
@@ -12,6 +12,11 @@ macro_rules! parse {
     };
 }
 
+aoc_rust::register_day! {
+    day: 4,
+    title: "Passport Processing",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut p1_count = 0;
     let mut p1 = 0;
@@ -36,11 +41,15 @@ pub fn run(input: &str) -> Result<Solution> {
             p1_count = 0;
             p2_count = 0;
         } else {
-            let keys = line
-                .split(' ')
-                .map(|kvp| unsafe { (kvp.get_unchecked(..2), kvp.get_unchecked(4..).trim_end()) });
+            let keys = line.split(' ').map(|kvp| match kvp.split_once(':') {
+                Some((key, value)) if key.len() == 3 && key.is_ascii() => {
+                    Ok((&key[..2], value.trim_end()))
+                }
+                _ => Err(ParseError::at(input, kvp, "expected `key:value`")),
+            });
 
-            'outer: for (key, value) in keys {
+            'outer: for kvp in keys {
+                let (key, value) = kvp?;
                 p1_count += (key != "ci") as u16;
 
                 match key {
@@ -57,6 +66,10 @@ pub fn run(input: &str) -> Result<Solution> {
                         p2_count += (year >= 2020 && year <= 2030) as u16;
                     }
                     "hg" => {
+                        if value.len() < 2 || !value.is_ascii() {
+                            continue;
+                        }
+
                         let (num, unit) = value.split_at(value.len() - 2);
                         let num: u8 = parse!(num);
 
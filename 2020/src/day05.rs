@@ -1,19 +1,34 @@
-use aoc_rust::Solution;
+use aoc_rust::{util::parse::ParseError, Solution};
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 5,
+    title: "Binary Boarding",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut p1 = 0;
-    static mut SEATS: [bool; 1024] = [false; 1024];
+    let mut seats = [false; 1024];
 
     for line in input.lines() {
-        let bytes = line.as_bytes();
+        let bytes = line.trim_end().as_bytes();
+
+        let valid = bytes.len() == 10
+            && bytes[..7].iter().all(|&b| b == b'F' || b == b'B')
+            && bytes[7..].iter().all(|&b| b == b'L' || b == b'R');
+
+        if !valid {
+            return Err(
+                ParseError::at(input, line, "expected seven `F`/`B` and three `L`/`R`").into(),
+            );
+        }
 
         let mut row = 0;
         let mut pow = 64;
 
         let mut i = 0;
         while i != 7 {
-            row += (unsafe { *bytes.get_unchecked(i) } == b'B') as usize * pow;
+            row += (bytes[i] == b'B') as usize * pow;
             pow /= 2;
             i += 1;
         }
@@ -22,7 +37,7 @@ pub fn run(input: &str) -> Result<Solution> {
         let mut pow = 4;
 
         while i != 10 {
-            col += (unsafe { *bytes.get_unchecked(i) } == b'R') as usize * pow;
+            col += (bytes[i] == b'R') as usize * pow;
             pow /= 2;
             i += 1;
         }
@@ -30,23 +45,12 @@ pub fn run(input: &str) -> Result<Solution> {
         let id = 8 * row + col;
 
         p1 = p1.max(id);
-        unsafe { *SEATS.get_unchecked_mut(id) = true }
+        seats[id] = true;
     }
 
-    let mut p2 = 1;
-    loop {
-        if unsafe { *SEATS.get_unchecked(p2) } {
-            p2 += 1;
-        } else if unsafe { *SEATS.get_unchecked(p2 + 1) } {
-            if unsafe { *SEATS.get_unchecked(p2 - 1) } {
-                break;
-            } else {
-                p2 += 2;
-            }
-        } else {
-            p2 += 3;
-        }
-    }
+    let p2 = (1..seats.len() - 1)
+        .find(|&id| !seats[id] && seats[id - 1] && seats[id + 1])
+        .ok_or_else(|| eyre::eyre!("no free seat between two taken ones"))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -1,8 +1,13 @@
-use aoc_rust::Solution;
+use aoc_rust::{util::parse::ParseError, Solution};
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 6,
+    title: "Custom Customs",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    static mut QUESTIONS: [u8; 26] = [0; 26];
+    let mut questions = [0_u8; 26];
 
     let mut p1 = 0;
     let mut p2 = 0;
@@ -14,19 +19,21 @@ pub fn run(input: &str) -> Result<Solution> {
 
         if bytes.is_empty() {
             while i != 26 {
-                unsafe {
-                    p1 += (*QUESTIONS.get_unchecked(i) > 0) as u16;
-                    p2 += (*QUESTIONS.get_unchecked(i) == group_size) as u16;
-                    *QUESTIONS.get_unchecked_mut(i) = 0;
-                }
+                p1 += (questions[i] > 0) as u16;
+                p2 += (questions[i] == group_size) as u16;
+                questions[i] = 0;
                 i += 1;
             }
             group_size = 0;
         } else {
             while i != bytes.len() {
-                unsafe {
-                    *QUESTIONS.get_unchecked_mut((*bytes.get_unchecked(i) - b'a') as usize) += 1
+                if !bytes[i].is_ascii_lowercase() {
+                    return Err(
+                        ParseError::at(input, &line[i..], "expected a question `a`-`z`").into(),
+                    );
                 }
+
+                questions[(bytes[i] - b'a') as usize] += 1;
                 i += 1;
             }
             group_size += 1;
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
-use std::hint::unreachable_unchecked;
 
 use aoc_rust::util::int_hasher::IntHasher;
+use aoc_rust::util::parse::{parse_at, ParseContext};
 use aoc_rust::Solution;
 use eyre::Result;
 
@@ -9,45 +9,50 @@ type Bags = HashMap<u16, Vec<(u8, u16)>, IntHasher>;
 type CachePart1 = HashMap<u16, bool>;
 type CachePart2 = HashMap<u16, u32>;
 
-const MY_BAG: &str = "iny go"; // always omit the first and last two characters
+const MY_BAG: &str = "shiny gold";
+
+aoc_rust::register_day! {
+    day: 7,
+    title: "Handy Haversacks",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
     let mut names = HashMap::with_capacity(594);
-    names.insert(MY_BAG.to_owned(), 0);
+    names.insert(MY_BAG, 0);
     let mut id = 1;
 
     let mut bags = HashMap::with_capacity_and_hasher(590, IntHasher);
 
+    let mut bag_id = |name| {
+        *names.entry(name).or_insert_with(|| {
+            id += 1;
+
+            id - 1
+        })
+    };
+
     for line in input.lines() {
-        let bytes = line.as_bytes();
-        let i = bag_end_idx(bytes) - 2;
+        let (outer, contents) = line.split_once(" bags contain ").context_at(
+            input,
+            line,
+            "expected `<bag> bags contain <bags>`",
+        )?;
+
+        let contents = contents.trim_end().trim_end_matches('.');
 
-        if unsafe { *bytes.get_unchecked(i + 16) } == b'n' {
+        if contents == "no other bags" {
             continue;
         }
 
-        let outer = if let Some(bag) = names.get(unsafe { line.get_unchecked(2..i) }) {
-            *bag
-        } else {
-            names.insert(unsafe { line.get_unchecked(2..i) }.to_owned(), id);
-            id += 1;
+        let outer = bag_id(outer);
 
-            id - 1
-        };
+        for inner in contents.split(", ") {
+            let (n, name) = inner
+                .split_once(' ')
+                .and_then(|(n, bag)| Some((n, bag.rsplit_once(' ')?.0)))
+                .context_at(input, inner, "expected `<amount> <bag> bag(s)`")?;
 
-        for inner in unsafe { line.get_unchecked(i + 16..) }.split(", ") {
-            let bytes = inner.as_bytes();
-            let n = unsafe { *bytes.get_unchecked(0) } - b'0';
-            let i = bag_end_idx(unsafe { bytes.get_unchecked(4..) }) + 2;
-
-            let inner = if let Some(bag) = names.get(unsafe { inner.get_unchecked(4..i) }) {
-                (n, *bag)
-            } else {
-                names.insert(unsafe { inner.get_unchecked(4..i).to_owned() }, id);
-                id += 1;
-
-                (n, id - 1)
-            };
+            let inner = (parse_at(input, n)?, bag_id(name));
 
             bags.entry(outer)
                 .or_insert_with(|| Vec::with_capacity(2))
@@ -59,16 +64,15 @@ pub fn run(input: &str) -> Result<Solution> {
 
     let p1 = bags
         .keys()
-        .filter(|&&bag| contains_recursive(bag, &bags, &mut cache))
-        .count()
-        - 1; // my bag does not contain itself
+        .filter(|&&bag| bag != 0 && contains_recursive(bag, &bags, &mut cache))
+        .count(); // my bag does not contain itself
 
     let mut cache = HashMap::with_capacity(64);
 
     let p2: u32 = bags
         .get(&0)
-        .unwrap_or_else(|| unsafe { unreachable_unchecked() })
-        .iter()
+        .into_iter()
+        .flatten()
         .map(|(amount, bag)| *amount as u32 * count_recursive(*bag, &bags, &mut cache))
         .sum();
 
@@ -113,20 +117,3 @@ fn count_recursive(bag: u16, bags: &Bags, cache: &mut CachePart2) -> u32 {
     cache.insert(bag, count);
     count
 }
-
-fn bag_end_idx(bag: &[u8]) -> usize {
-    let mut i = 1;
-    let mut found_first = false;
-
-    loop {
-        if unsafe { *bag.get_unchecked(i) } == b' ' {
-            if found_first {
-                return i;
-            }
-
-            found_first = true;
-        }
-
-        i += 1;
-    }
-}
@@ -1,81 +1,46 @@
-use std::hint::unreachable_unchecked;
+use std::convert::Infallible;
 
-use aoc_rust::Solution;
+use aoc_rust::{
+    machine::{self, Exit, Instruction, Machine, Step},
+    util::parse::ParseError,
+    Solution,
+};
 use eyre::Result;
 
-static mut SEEN: [bool; 1024] = [false; 1024];
+aoc_rust::register_day! {
+    day: 8,
+    title: "Handheld Halting",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
-    let mut instructions: Vec<Op> = Vec::with_capacity(640);
-
-    for line in input.lines() {
-        instructions.push(parse_op(&line));
-    }
+    let machine = Machine::new(parse_ops(input)?, 0);
 
-    let (p1_result, max) = part1(&instructions);
-    let p1 = p1_result.unwrap_err();
-    clear_seen(max);
+    let p1 = part1(machine.clone())?;
 
-    let p2 = part2(&mut instructions);
+    let p2 =
+        part2(&machine).ok_or_else(|| eyre::eyre!("no single swap makes the program terminate"))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(instructions: &[Op]) -> (Result<i32, i32>, usize) {
-    let mut acc = 0;
-    let mut pc = 0;
-    let mut prev;
-    let mut max = 0;
-
-    while (pc as usize) < instructions.len() {
-        prev = acc;
-
-        match unsafe { instructions.get_unchecked(pc as usize) } {
-            Op::Acc(n) => {
-                acc += n;
-                pc += 1;
-            }
-            Op::Jmp(n) => pc += n,
-            Op::Nop(_) => pc += 1,
-        }
-
-        if unsafe { *SEEN.get_unchecked(pc as usize) } {
-            return (Err(prev), max as usize);
-        }
-
-        max = max.max(pc);
-        unsafe { *SEEN.get_unchecked_mut(pc as usize) = true }
+fn part1(mut machine: Machine<Op>) -> Result<i32> {
+    match machine.run_until_loop() {
+        Exit::Loop => Ok(machine.state),
+        _ => eyre::bail!("program terminates without a loop"),
     }
-
-    (Ok(acc), max as usize)
 }
 
-fn part2(instructions: &mut [Op]) -> i32 {
-    let mut i = 0;
-
-    loop {
-        let replaced = loop {
-            match unsafe { *instructions.get_unchecked(i) } {
-                Op::Acc(_) => i += 1,
-                Op::Jmp(n) => {
-                    unsafe { *instructions.get_unchecked_mut(i) = Op::Nop(n) }
-                    break Op::Jmp(n);
-                }
-                Op::Nop(n) => {
-                    unsafe { *instructions.get_unchecked_mut(i) = Op::Jmp(n) }
-                    break Op::Nop(n);
-                }
-            }
-        };
-
-        match part1(&instructions) {
-            (Ok(n), _) => return n,
-            (Err(_), max) => clear_seen(max),
-        };
-
-        unsafe { *instructions.get_unchecked_mut(i) = replaced }
-        i += 1;
-    }
+fn part2(machine: &Machine<Op>) -> Option<i32> {
+    let swap = |op: &Op| match *op {
+        Op::Acc(_) => None,
+        Op::Jmp(n) => Some(Op::Nop(n)),
+        Op::Nop(n) => Some(Op::Jmp(n)),
+    };
+
+    machine.try_patches(swap, |machine| match machine.run_until_loop() {
+        Exit::Halted => Some(machine.state),
+        _ => None,
+    })
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -85,41 +50,50 @@ enum Op {
     Nop(i32),
 }
 
-fn parse_op(line: &str) -> Op {
-    let n = parse(unsafe { line.trim_end().as_bytes().get_unchecked(4..) });
+impl Instruction for Op {
+    /// The accumulator
+    type State = i32;
+    type Output = Infallible;
 
-    match unsafe { line.as_bytes().get_unchecked(0) } {
-        b'a' => Op::Acc(n),
-        b'j' => Op::Jmp(n),
-        b'n' => Op::Nop(n),
-        _ => unsafe { unreachable_unchecked() },
-    }
-}
-
-fn clear_seen(max: usize) {
-    let mut j = 0;
+    fn execute(&self, acc: &mut i32) -> Step<Self> {
+        match *self {
+            Op::Acc(n) => *acc += n,
+            Op::Jmp(n) => return Step::Jump(n as i64),
+            Op::Nop(_) => {}
+        }
 
-    while j <= max {
-        unsafe { *SEEN.get_unchecked_mut(j) = false }
-        j += 1;
+        Step::Next
     }
 }
 
-fn parse(bytes: &[u8]) -> i32 {
-    let mut n = 0;
-    let mut i = 1;
-    let sig = (unsafe { *bytes.get_unchecked(0) } == b'+') as i32 * 2 - 1;
-
-    while i < bytes.len() {
-        let c = unsafe { *bytes.get_unchecked(i) };
-
-        if c == b'\r' {
-            return n * sig;
-        }
+fn parse_ops(input: &str) -> Result<Vec<Op>, ParseError> {
+    machine::parse_program(input, |op, words| match op {
+        "acc" => Ok(Op::Acc(words.value()?)),
+        "jmp" => Ok(Op::Jmp(words.value()?)),
+        "nop" => Ok(Op::Nop(words.value()?)),
+        _ => Err(words.unknown(op)),
+    })
+}
 
-        n = n * 10 + (unsafe { *bytes.get_unchecked(i) } & 0x0F) as i32;
-        i += 1;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "\
+nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+        let solution = run(input).unwrap();
+        assert_eq!(solution.part1.to_string(), "5");
+        assert_eq!(solution.part2.to_string(), "8");
     }
-
-    n * sig
 }
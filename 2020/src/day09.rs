@@ -1,18 +1,33 @@
 use std::cmp::Ordering;
 
-use aoc_rust::Solution;
+use aoc_rust::{
+    util::parse::{parse_at, ParseError},
+    Solution,
+};
 use eyre::Result;
 
 static mut PREV: [u64; 25] = [0; 25];
 
+aoc_rust::register_day! {
+    day: 9,
+    title: "Encoding Error",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut nums = Vec::with_capacity(768);
     let mut i = 0;
 
     let mut lines = input.lines();
 
+    let mut next = || match lines.next() {
+        Some(line) => parse_at::<u64>(input, line),
+        None => Err(ParseError::new(
+            "ran out of numbers before one is not the sum of two predecessors",
+        )),
+    };
+
     while i < 25 {
-        let n = lines.next().unwrap().parse().unwrap();
+        let n = next()?;
         unsafe { *PREV.get_unchecked_mut(i) = n }
         nums.push(n);
         i += 1;
@@ -21,7 +36,7 @@ pub fn run(input: &str) -> Result<Solution> {
     i = 0;
 
     let p1 = loop {
-        let n = lines.next().unwrap().parse().unwrap();
+        let n = next()?;
 
         if part1(n) {
             unsafe { *PREV.get_unchecked_mut(i) = n }
@@ -38,7 +53,7 @@ pub fn run(input: &str) -> Result<Solution> {
         Ok(n) => n,
         Err(i) => {
             for line in lines {
-                nums.push(line.parse().unwrap());
+                nums.push(parse_at(input, line)?);
             }
 
             part2_continue(&nums, p1, i)
@@ -1,66 +1,80 @@
-use aoc_rust::Solution;
+use aoc_rust::{util::parse::parse_at, Solution};
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 10,
+    title: "Adapter Array",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut adapters: Vec<u8> = Vec::with_capacity(128);
 
     for line in input.lines() {
-        adapters.push(line.parse().unwrap());
+        adapters.push(parse_at(input, line)?);
     }
 
     adapters.sort_unstable_by_key(|k| std::cmp::Reverse(*k));
-    adapters.insert(0, unsafe { adapters.get_unchecked(0) } + 3);
+
+    let device = adapters
+        .first()
+        .and_then(|max| max.checked_add(3))
+        .ok_or_else(|| eyre::eyre!("adapters must be between 1 and 252 jolts"))?;
+
+    adapters.insert(0, device);
     adapters.push(0);
 
+    if let Some(pair) = adapters
+        .windows(2)
+        .find(|pair| !(1..=3).contains(&(pair[0] - pair[1])))
+    {
+        eyre::bail!(
+            "can not chain adapters of {} and {} jolts",
+            pair[1],
+            pair[0]
+        );
+    }
+
     Ok(Solution::new()
         .part1(part1(&adapters))
         .part2(part2(&adapters)))
 }
 
 fn part1(adapters: &[u8]) -> u64 {
-    static mut DIFFS: [u64; 3] = [0; 3];
+    let mut diffs = [0_u64; 3];
 
     let mut i = adapters.len() - 1;
 
     while i > 0 {
-        unsafe {
-            let idx = adapters.get_unchecked(i - 1) - adapters.get_unchecked(i);
-            *DIFFS.get_unchecked_mut(idx as usize - 1) += 1
-        }
+        let idx = adapters[i - 1] - adapters[i];
+        diffs[idx as usize - 1] += 1;
 
         i -= 1;
     }
 
-    unsafe { DIFFS.get_unchecked(0) * DIFFS.get_unchecked(2) }
+    diffs[0] * diffs[2]
 }
 
 fn part2(adapters: &[u8]) -> u64 {
-    static mut POSSIBS: [u64; 3] = [0; 3];
+    let mut possibs = [0_u64; 3];
 
-    unsafe {
-        *POSSIBS.get_unchecked_mut(0) = 1;
-        *POSSIBS.get_unchecked_mut(1) = 1;
-        *POSSIBS.get_unchecked_mut(2) =
-            (adapters.get_unchecked(0) - adapters.get_unchecked(2) <= 3) as u64 + 1
-    }
+    possibs[0] = 1;
+    possibs[1] = 1;
+    possibs[2] = (adapters[0] - adapters[2] <= 3) as u64 + 1;
 
     let mut i = 2;
 
     while i < adapters.len() {
-        unsafe {
-            let mut possibs = *POSSIBS.get_unchecked((i - 1) % 3);
+        let mut curr = possibs[(i - 1) % 3];
 
-            if adapters.get_unchecked(i - 2) - adapters.get_unchecked(i) <= 3 {
-                possibs += POSSIBS.get_unchecked((i - 2) % 3)
-                    + (adapters.get_unchecked(i - 3) - adapters.get_unchecked(i) <= 3) as u64
-                        * POSSIBS.get_unchecked(i % 3)
-            }
-
-            *POSSIBS.get_unchecked_mut(i % 3) = possibs
+        if adapters[i - 2] - adapters[i] <= 3 {
+            curr +=
+                possibs[(i - 2) % 3] + (adapters[i - 3] - adapters[i] <= 3) as u64 * possibs[i % 3]
         }
 
+        possibs[i % 3] = curr;
+
         i += 1;
     }
 
-    unsafe { *POSSIBS.get_unchecked((adapters.len() - 1) % 3) }
+    possibs[(adapters.len() - 1) % 3]
 }
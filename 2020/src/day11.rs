@@ -1,11 +1,11 @@
 use std::collections::HashSet;
 use std::fmt;
 use std::hint::unreachable_unchecked;
-use std::iter::FromIterator;
 use std::ops::{Deref, DerefMut, Not};
 use std::time::Instant;
 
 use aoc_rust::util::int_hasher::IntHasher;
+use aoc_rust::util::parse::ParseError;
 use aoc_rust::Solution;
 use eyre::Result;
 
@@ -22,8 +22,13 @@ struct Seats {
     seats: Vec<Seat>,
 }
 
+aoc_rust::register_day! {
+    day: 11,
+    title: "Seating System",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let seats = parse_seats(input);
+    let seats = parse_seats(input)?;
 
     let p1 = part1(seats.clone());
     let p2 = part2(seats);
@@ -31,43 +36,32 @@ pub fn run(input: &str) -> Result<Solution> {
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn parse_seats(input: &str) -> Seats {
-    let mut lines = input.lines();
-
-    let mut seats: Seats = lines
-        .next()
-        .unwrap()
-        .trim_end()
-        .chars()
-        .map(|c| {
-            if c == 'L' {
-                Seat::Occupied
-            } else {
-                Seat::Floor
-            }
-        })
-        .collect();
+fn parse_seats(input: &str) -> Result<Seats, ParseError> {
+    let mut width = None;
+    let mut seats = Vec::new();
 
-    let width = seats.width;
-    seats.reserve(width * 90);
+    for line in input.lines() {
+        let bytes = line.trim_end().as_bytes();
 
-    for line in lines {
-        let bytes = line.as_bytes();
-        let mut i = 0;
+        if *width.get_or_insert(bytes.len()) != bytes.len() {
+            return Err(ParseError::at(input, line, "rows must be of equal width"));
+        }
 
-        while i < width {
-            let seat = match unsafe { *bytes.get_unchecked(i) } {
+        for (i, byte) in bytes.iter().enumerate() {
+            let seat = match byte {
                 b'L' => Seat::Occupied,
                 b'.' => Seat::Floor,
-                _ => unsafe { unreachable_unchecked() },
+                _ => return Err(ParseError::at(input, &line[i..], "expected `L` or `.`")),
             };
 
             seats.push(seat);
-            i += 1;
         }
     }
 
-    seats
+    match width {
+        Some(width) if width > 0 && seats.len() >= 2 * width => Ok(Seats { width, seats }),
+        _ => Err(ParseError::new("expected at least two non-empty rows")),
+    }
 }
 
 const EMP: Seat = Seat::Empty;
@@ -655,25 +649,6 @@ impl DerefMut for Seats {
     }
 }
 
-impl FromIterator<Seat> for Seats {
-    fn from_iter<I: IntoIterator<Item = Seat>>(iter: I) -> Self {
-        let iter = iter.into_iter();
-
-        let width = iter
-            .size_hint()
-            .1
-            .unwrap_or_else(|| unsafe { unreachable_unchecked() });
-
-        let mut seats = Vec::with_capacity(width);
-
-        for seat in iter {
-            seats.push(seat);
-        }
-
-        Seats { width, seats }
-    }
-}
-
 impl fmt::Display for Seats {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut rows = self.seats.chunks_exact(self.width);
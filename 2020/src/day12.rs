@@ -1,6 +1,9 @@
-use std::hint::unreachable_unchecked;
+use std::{fmt::Display, str::FromStr};
 
-use aoc_rust::Solution;
+use aoc_rust::{
+    util::parse::{parse_at, ParseError},
+    Solution,
+};
 use eyre::Result;
 
 const NORTH: i16 = 0;
@@ -8,20 +11,24 @@ const EAST: i16 = 1;
 const SOUTH: i16 = 2;
 const WEST: i16 = 3;
 
+aoc_rust::register_day! {
+    day: 12,
+    title: "Rain Risk",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    Ok(Solution::new().part1(part1(input)).part2(part2(input)))
+    Ok(Solution::new().part1(part1(input)?).part2(part2(input)?))
 }
 
-fn part1(input: &str) -> i16 {
+fn part1(input: &str) -> Result<i16, ParseError> {
     let mut dir = EAST;
     let mut x = 0;
     let mut y = 0;
 
     for line in input.lines() {
-        let bytes = line.as_bytes();
-        let n: i16 = line[1..].parse().unwrap();
+        let (action, n): (_, i16) = parse_action(input, line)?;
 
-        match unsafe { *bytes.get_unchecked(0) } {
+        match action {
             b'N' => y -= n,
             b'W' => x -= n,
             b'S' => y += n,
@@ -33,26 +40,25 @@ fn part1(input: &str) -> i16 {
                 EAST => x += n,
                 SOUTH => y += n,
                 WEST => x -= n,
-                _ => unsafe { unreachable_unchecked() },
+                _ => unreachable!(),
             },
-            _ => unsafe { unreachable_unchecked() },
+            _ => unreachable!(),
         }
     }
 
-    x.abs() + y.abs()
+    Ok(x.abs() + y.abs())
 }
 
-fn part2(input: &str) -> i32 {
+fn part2(input: &str) -> Result<i32, ParseError> {
     let mut wx = 10;
     let mut wy = -1;
     let mut x = 0;
     let mut y = 0;
 
     for line in input.lines() {
-        let bytes = line.as_bytes();
-        let n: i32 = line[1..].parse().unwrap();
+        let (action, n): (_, i32) = parse_action(input, line)?;
 
-        match unsafe { *bytes.get_unchecked(0) } {
+        match action {
             b'N' => wy -= n,
             b'W' => wx -= n,
             b'S' => wy += n,
@@ -64,7 +70,7 @@ fn part2(input: &str) -> i32 {
                     wy *= -1;
                 }
                 270 => swap(&mut wx, &mut wy),
-                _ => unsafe { unreachable_unchecked() },
+                _ => unreachable!(),
             },
             b'R' => match n {
                 90 => swap(&mut wx, &mut wy),
@@ -73,20 +79,43 @@ fn part2(input: &str) -> i32 {
                     wy *= -1;
                 }
                 270 => swap(&mut wy, &mut wx),
-                _ => unsafe { unreachable_unchecked() },
+                _ => unreachable!(),
             },
             b'F' => {
                 x += n * wx;
                 y += n * wy;
             }
-            _ => unsafe { unreachable_unchecked() },
+            _ => unreachable!(),
         }
     }
 
-    x.abs() + y.abs()
+    Ok(x.abs() + y.abs())
 }
 
 fn swap(a: &mut i32, b: &mut i32) {
     std::mem::swap(a, b);
     *a *= -1;
 }
+
+fn parse_action<T>(input: &str, line: &str) -> Result<(u8, T), ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let line = line.trim_end();
+
+    let action = match line.as_bytes().first() {
+        Some(action @ (b'N' | b'W' | b'S' | b'E' | b'F')) => *action,
+        Some(action @ (b'L' | b'R')) if matches!(&line[1..], "90" | "180" | "270") => *action,
+        Some(b'L' | b'R') => {
+            return Err(ParseError::at(
+                input,
+                &line[1..],
+                "expected 90, 180, or 270 degrees",
+            ))
+        }
+        _ => return Err(ParseError::at(input, line, "expected one of `NWSELRF`")),
+    };
+
+    Ok((action, parse_at(input, &line[1..])?))
+}
@@ -1,46 +1,59 @@
-use aoc_rust::Solution;
+use aoc_rust::{
+    util::parse::{parse_at, ParseContext, ParseError},
+    Solution,
+};
 use eyre::Result;
 
-pub fn run(input: &str) -> Result<Solution> {
-    Ok(Solution::new().part1(part1(input)).part2(part2(input)))
+aoc_rust::register_day! {
+    day: 13,
+    title: "Shuttle Search",
 }
 
-fn part1(input: &str) -> u32 {
-    let mut lines = input.lines();
+pub fn run(input: &str) -> Result<Solution> {
+    let (earliest, ids) = parse_notes(input)?;
 
-    let earliest: u32 = lines.next().unwrap().parse().unwrap();
+    Ok(Solution::new()
+        .part1(part1(earliest, &ids))
+        .part2(part2(ids)))
+}
 
-    let bytes = lines.next().unwrap().as_bytes();
-    let mut min = u32::MAX;
-    let mut p1 = 0;
-    let mut i = 0;
-    let mut n = 0;
+/// Earliest departure and the ids of all buses with their index in the list
+fn parse_notes(input: &str) -> Result<(u64, Vec<(u64, u64)>), ParseError> {
+    let mut lines = input.lines();
 
-    while i < bytes.len() {
-        let byte = unsafe { *bytes.get_unchecked(i) };
-        if byte == b',' {
-            let candidate = n - earliest % n;
+    let earliest = lines.next().context_at(input, input, "missing timestamp")?;
+    let earliest = parse_at(input, earliest.trim_end())?;
 
-            if candidate < min {
-                min = candidate;
-                p1 = candidate * n;
-            }
+    let buses = lines.next().context_at(input, input, "missing bus ids")?;
+    let mut ids = Vec::with_capacity(10);
 
-            n = 0;
-        } else if byte == b'x' {
-            i += 2;
+    for (idx, id) in buses.trim_end().split(',').enumerate() {
+        if id == "x" {
             continue;
-        } else {
-            n = n * 10 + (byte & 0x0F) as u32;
         }
 
-        i += 1;
+        match parse_at(input, id)? {
+            0 => return Err(ParseError::at(input, id, "bus ids must be positive")),
+            n => ids.push((n, idx as u64)),
+        }
     }
 
-    if n > 0 {
+    if ids.is_empty() {
+        return Err(ParseError::at(input, buses, "no bus ids"));
+    }
+
+    Ok((earliest, ids))
+}
+
+fn part1(earliest: u64, ids: &[(u64, u64)]) -> u64 {
+    let mut min = u64::MAX;
+    let mut p1 = 0;
+
+    for &(n, _) in ids {
         let candidate = n - earliest % n;
 
         if candidate < min {
+            min = candidate;
             p1 = candidate * n;
         }
     }
@@ -48,37 +61,7 @@ fn part1(input: &str) -> u32 {
     p1
 }
 
-fn part2(input: &str) -> u64 {
-    let mut lines = input.lines();
-
-    lines.next();
-    let bytes = lines.next().unwrap().as_bytes();
-    let mut ids = Vec::with_capacity(10);
-    let mut idx = 0;
-    let mut i = 0;
-    let mut n = 0;
-
-    while i < bytes.len() {
-        let byte = unsafe { *bytes.get_unchecked(i) };
-        if byte == b',' {
-            ids.push((n, idx));
-            idx += 1;
-            n = 0;
-        } else if byte == b'x' {
-            idx += 1;
-            i += 2;
-            continue;
-        } else {
-            n = n * 10 + (byte & 0x0F) as u64;
-        }
-
-        i += 1;
-    }
-
-    if n > 0 {
-        ids.push((n, idx));
-    }
-
+fn part2(mut ids: Vec<(u64, u64)>) -> u64 {
     let (first, _) = ids.swap_remove(0);
     ids.sort_by_key(|(a, _)| std::cmp::Reverse(*a));
 
@@ -92,7 +75,7 @@ fn part2(input: &str) -> u64 {
         let mut next_step = first;
 
         while j <= i {
-            let (n, offset) = unsafe { *ids.get_unchecked(j) };
+            let (n, offset) = ids[j];
 
             if (t + offset) % n != 0 {
                 continue 'outer;
@@ -2,49 +2,66 @@ use std::collections::HashMap;
 use std::hint::unreachable_unchecked;
 
 use aoc_rust::util::int_hasher::IntHasher;
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 14,
+    title: "Docking Data",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    Ok(Solution::new().part1(part1(input)).part2(part2(input)))
+    Ok(Solution::new().part1(part1(input)?).part2(part2(input)?))
 }
 
-fn part1(input: &str) -> u64 {
-    let mut mask: [u8; 36] = [b'X'; 36];
-    let mut mem = HashMap::with_capacity_and_hasher(512, IntHasher);
+enum Instruction<'a> {
+    Mask(&'a [u8; 36]),
+    Mem { adr: usize, val: u64 },
+}
 
-    for line in input.lines() {
-        let bytes = line.as_bytes();
+fn parse_instruction<'a>(input: &str, line: &'a str) -> Result<Instruction<'a>, ParseError> {
+    let line = line.trim_end();
 
-        match unsafe { *bytes.get_unchecked(1) } {
-            b'e' => {
-                let mut i = 4;
-                let mut adr = 0;
+    if let Some(mask) = line.strip_prefix("mask = ") {
+        return match <&[u8; 36]>::try_from(mask.as_bytes()) {
+            Ok(bits) if bits.iter().all(|b| matches!(b, b'0' | b'1' | b'X')) => {
+                Ok(Instruction::Mask(bits))
+            }
+            _ => Err(ParseError::at(
+                input,
+                mask,
+                "expected 36 chars of `0`, `1`, or `X`",
+            )),
+        };
+    }
 
-                loop {
-                    let byte = unsafe { *bytes.get_unchecked(i) };
+    let (adr, val) = line
+        .strip_prefix("mem[")
+        .and_then(|rest| rest.split_once("] = "))
+        .context_at(input, line, "expected `mask = ..` or `mem[..] = ..`")?;
 
-                    if byte == b']' {
-                        break;
-                    }
+    Ok(Instruction::Mem {
+        adr: parse_at(input, adr)?,
+        val: parse_at(input, val)?,
+    })
+}
 
-                    adr = adr * 10 + (byte & 0x0F) as usize;
-                    i += 1;
-                }
+fn part1(input: &str) -> Result<u64, ParseError> {
+    let mut mask: [u8; 36] = [b'X'; 36];
+    let mut mem = HashMap::with_capacity_and_hasher(512, IntHasher);
 
-                i += 4;
-                let mut val: u64 =
-                    unsafe { std::str::from_utf8_unchecked(bytes.get_unchecked(i..)) }
-                        .parse()
-                        .unwrap();
+    for line in input.lines() {
+        match parse_instruction(input, line)? {
+            Instruction::Mem { adr, mut val } => {
                 let mut j = 0;
 
                 while j < 36 {
-                    match unsafe { *mask.get_unchecked(j) } {
+                    match mask[j] {
                         b'X' => {}
                         b'0' => val &= !(1 << (35 - j)),
                         b'1' => val |= 1 << (35 - j),
-                        _ => unsafe { unreachable_unchecked() },
+                        _ => unreachable!(),
                     }
 
                     j += 1;
@@ -52,44 +69,22 @@ fn part1(input: &str) -> u64 {
 
                 mem.insert(adr, val);
             }
-            b'a' => mask.copy_from_slice(unsafe { bytes.get_unchecked(7..43) }),
-            _ => unsafe { unreachable_unchecked() },
+            Instruction::Mask(bits) => mask = *bits,
         }
     }
 
-    mem.values().copied().sum()
+    Ok(mem.values().copied().sum())
 }
 
-fn part2(input: &str) -> u64 {
+fn part2(input: &str) -> Result<u64, ParseError> {
     let mut mask_zeroed = 0;
     let mut mask_ones = 0;
     let mut xs = Vec::with_capacity(8);
     let mut mem = HashMap::with_capacity_and_hasher(100_000, IntHasher);
 
     for line in input.lines() {
-        let bytes = line.as_bytes();
-
-        match unsafe { *bytes.get_unchecked(1) } {
-            b'e' => {
-                let mut i = 4;
-                let mut adr = 0;
-
-                loop {
-                    let byte = unsafe { *bytes.get_unchecked(i) };
-
-                    if byte == b']' {
-                        break;
-                    }
-
-                    adr = adr * 10 + (byte & 0x0F) as usize;
-                    i += 1;
-                }
-
-                let val: u64 = std::str::from_utf8(unsafe { bytes.get_unchecked(i + 4..) })
-                    .unwrap()
-                    .parse()
-                    .unwrap();
-
+        match parse_instruction(input, line)? {
+            Instruction::Mem { mut adr, val } => {
                 adr = (adr | mask_zeroed) & mask_ones;
 
                 for i in 0..(1 << xs.len()) {
@@ -97,21 +92,21 @@ fn part2(input: &str) -> u64 {
                     let mut j = 0;
 
                     while j < xs.len() {
-                        a |= ((i >> j) & 1 as usize) << unsafe { *xs.get_unchecked(j) };
+                        a |= ((i >> j) & 1 as usize) << xs[j];
                         j += 1;
                     }
 
                     mem.insert(a, val);
                 }
             }
-            b'a' => {
+            Instruction::Mask(bits) => {
                 xs.clear();
                 mask_zeroed = 0;
                 mask_ones = 0;
                 let mut i = 7;
 
                 while i < 43 {
-                    match unsafe { *bytes.get_unchecked(i) } {
+                    match bits[i - 7] {
                         b'0' => {
                             mask_zeroed <<= 1;
                             mask_ones = (mask_ones << 1) + 1;
@@ -125,73 +120,49 @@ fn part2(input: &str) -> u64 {
                             mask_ones <<= 1;
                             xs.push(42 - i);
                         }
-                        _ => unsafe { unreachable_unchecked() },
+                        _ => unreachable!(),
                     }
 
                     i += 1;
                 }
             }
-            _ => unsafe { unreachable_unchecked() },
         }
     }
 
-    mem.values().copied().sum()
+    Ok(mem.values().copied().sum())
 }
 
 #[allow(dead_code)]
-fn part2_old(input: &str) -> u64 {
+fn part2_old(input: &str) -> Result<u64, ParseError> {
     let mut mask: [u8; 36] = [b'X'; 36];
     let mut xs = Vec::with_capacity(8);
     let mut mem = HashMap::with_capacity_and_hasher(512, IntHasher);
 
     for line in input.lines() {
-        let bytes = line.as_bytes();
-
-        match unsafe { *bytes.get_unchecked(1) } {
-            b'e' => {
-                let mut i = 4;
-                let mut adr = 0;
-
-                loop {
-                    let byte = unsafe { *bytes.get_unchecked(i) };
-
-                    if byte == b']' {
-                        break;
-                    }
-
-                    adr = adr * 10 + (byte & 0x0F) as usize;
-                    i += 1;
-                }
-
-                i += 4;
-                let val: u64 = std::str::from_utf8(unsafe { bytes.get_unchecked(i..) })
-                    .unwrap()
-                    .parse()
-                    .unwrap();
-
+        match parse_instruction(input, line)? {
+            Instruction::Mem { adr, val } => {
                 for a in nums(adr, &mask, &xs) {
                     mem.insert(a, val);
                 }
             }
-            b'a' => {
+            Instruction::Mask(bits) => {
                 xs.clear();
-                mask.copy_from_slice(unsafe { bytes.get_unchecked(7..43) });
+                mask = *bits;
 
-                let mut i = 7;
+                let mut i = 0;
 
-                while i < 43 {
-                    if unsafe { *bytes.get_unchecked(i) == b'X' } {
-                        xs.push(i - 7);
+                while i < 36 {
+                    if mask[i] == b'X' {
+                        xs.push(i);
                     }
 
                     i += 1;
                 }
             }
-            _ => unsafe { unreachable_unchecked() },
         }
     }
 
-    mem.values().copied().sum()
+    Ok(mem.values().copied().sum())
 }
 
 fn nums<'a>(mut num: usize, mask: &'a [u8], xs: &'a [usize]) -> impl Iterator<Item = usize> + 'a {
@@ -6,13 +6,101 @@ use std::hash::{Hash, Hasher};
 use std::hint::unreachable_unchecked;
 
 use aoc_rust::util::int_hasher::IntHasher;
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 16,
+    title: "Ticket Translation",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
+    validate(input)?;
+
     Ok(Solution::new().part1(part1(input)).part2(part2(input)))
 }
 
+/// Check the notes upfront so that the byte-wise parsing of
+/// [`part1`] and [`part2`] can rely on their format.
+fn validate(input: &str) -> Result<(), ParseError> {
+    let mut lines = input.lines();
+    let mut fields = 0;
+
+    for line in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+
+        validate_rule(input, line)?;
+        fields += 1;
+    }
+
+    if fields == 0 {
+        return Err(ParseError::new("expected at least one rule"));
+    }
+
+    let mut expect_line = |expected: &str| {
+        let line = lines
+            .next()
+            .context_at(input, input, format_args!("missing `{expected}`"))?;
+
+        if !expected.is_empty() && line != expected {
+            return Err(ParseError::at(
+                input,
+                line,
+                format_args!("expected `{expected}`"),
+            ));
+        }
+
+        Ok(line)
+    };
+
+    expect_line("your ticket:")?;
+    validate_ticket(input, expect_line("")?, fields)?;
+    expect_line("")?;
+    expect_line("nearby tickets:")?;
+
+    lines.try_for_each(|line| validate_ticket(input, line, fields))
+}
+
+fn validate_rule(input: &str, line: &str) -> Result<(), ParseError> {
+    let (_, ranges) = line
+        .split_once(": ")
+        .and_then(|(name, ranges)| Some((name, ranges.split_once(" or ")?)))
+        .context_at(input, line, "expected `<field>: <a>-<b> or <c>-<d>`")?;
+
+    for range in [ranges.0, ranges.1] {
+        let (a, b) = range
+            .split_once('-')
+            .context_at(input, range, "expected `<a>-<b>`")?;
+
+        parse_at::<u16>(input, a)?;
+        parse_at::<u16>(input, b)?;
+    }
+
+    Ok(())
+}
+
+fn validate_ticket(input: &str, line: &str, fields: usize) -> Result<(), ParseError> {
+    let mut values = 0;
+
+    for n in line.split(',') {
+        parse_at::<u16>(input, n)?;
+        values += 1;
+    }
+
+    if values != fields {
+        return Err(ParseError::at(
+            input,
+            line,
+            format_args!("expected {fields} values"),
+        ));
+    }
+
+    Ok(())
+}
+
 fn part1(input: &str) -> u16 {
     let mut rules = Vec::with_capacity(4);
 
@@ -60,6 +60,11 @@ impl Hash for Pos4 {
     }
 }
 
+aoc_rust::register_day! {
+    day: 17,
+    title: "Conway Cubes",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     Ok(Solution::new().part1(part1(input)).part2(part2(input)))
 }
@@ -1,6 +1,6 @@
 use std::hint::unreachable_unchecked;
 
-use aoc_rust::Solution;
+use aoc_rust::{util::parse::ParseError, Solution};
 use eyre::Result;
 
 enum Op {
@@ -8,10 +8,72 @@ enum Op {
     Mul,
 }
 
+aoc_rust::register_day! {
+    day: 18,
+    title: "Operation Order",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
+    for line in input.lines() {
+        validate(input, line)?;
+    }
+
     Ok(Solution::new().part1(part1(input)).part2(part2(input)))
 }
 
+/// The evaluators below expect every expression and every parenthesized group
+/// to consist of at least two operands that are separated by ` + ` or ` * `.
+fn validate(input: &str, line: &str) -> Result<(), ParseError> {
+    let bytes = line.as_bytes();
+    let error = |idx: usize, message| ParseError::at(input, &line[idx.min(line.len())..], message);
+
+    let idx = validate_expr(bytes, 0).map_err(|(idx, message)| error(idx, message))?;
+
+    if idx < bytes.len() {
+        return Err(error(idx, "expected ` + `, ` * `, or end of line"));
+    }
+
+    Ok(())
+}
+
+/// Returns the index after the expression or the index of the offending byte
+fn validate_expr(bytes: &[u8], mut idx: usize) -> Result<usize, (usize, &'static str)> {
+    idx = validate_operand(bytes, idx)?;
+    let mut operators = 0;
+
+    while let [b' ', b'+' | b'*', b' ', ..] = bytes.get(idx..).unwrap_or_default() {
+        idx = validate_operand(bytes, idx + 3)?;
+        operators += 1;
+    }
+
+    if operators == 0 {
+        return Err((idx, "expected ` + ` or ` * `"));
+    }
+
+    Ok(idx)
+}
+
+fn validate_operand(bytes: &[u8], mut idx: usize) -> Result<usize, (usize, &'static str)> {
+    match bytes.get(idx) {
+        Some(b'(') => {
+            idx = validate_expr(bytes, idx + 1)?;
+
+            match bytes.get(idx) {
+                Some(b')') => Ok(idx + 1),
+                _ => Err((idx, "expected `)`")),
+            }
+        }
+        Some(digit) if digit.is_ascii_digit() => {
+            while bytes.get(idx).is_some_and(u8::is_ascii_digit) {
+                idx += 1;
+            }
+
+            Ok(idx)
+        }
+        _ => Err((idx, "expected a number or `(`")),
+    }
+}
+
 fn part1(input: &str) -> u64 {
     let mut sum = 0;
 
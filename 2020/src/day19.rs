@@ -1,6 +1,9 @@
 use hashbrown::{HashMap, HashSet};
 
-use aoc_rust::Solution;
+use aoc_rust::{
+    util::parse::{parse_at, ParseContext, ParseError},
+    Solution,
+};
 use eyre::Result;
 
 #[derive(Copy, Clone, Debug)]
@@ -15,23 +18,33 @@ enum RightSide {
     Nonterminal(u8, u8),
 }
 
+type Substitutions = HashMap<u8, HashSet<u8>>;
+
+/// Rule id that does not occur in the input, used to split up rule 11 in part 2
+const HELPER: u8 = u8::MAX;
+
+aoc_rust::register_day! {
+    day: 19,
+    title: "Monster Messages",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    Ok(Solution::new().part1(part1(input)).part2(part2(input)))
+    Ok(Solution::new().part1(part1(input)?).part2(part2(input)?))
 }
 
 #[allow(dead_code)]
-fn part1(input: &str) -> u16 {
+fn part1(input: &str) -> Result<u16, ParseError> {
     let mut rules = Vec::with_capacity(32);
     let mut substitutions = HashMap::with_capacity(1);
 
     let mut lines = input.lines();
 
     for line in lines.by_ref() {
-        if line.len() <= 1 {
+        if line.trim_end().is_empty() {
             break;
         }
 
-        parse_line(line.as_bytes(), &mut rules, &mut substitutions);
+        parse_line(input, line.trim_end(), &mut rules, &mut substitutions)?;
     }
 
     let mut substitute = || {
@@ -72,32 +85,42 @@ fn part1(input: &str) -> u16 {
     let mut valid = 0;
 
     for line in lines {
-        let accepted = unsafe { cyk(line.trim_end(), &rules) };
+        let accepted = cyk(line.trim_end(), &rules);
         valid += accepted as u16;
     }
 
-    valid
+    Ok(valid)
 }
 
-fn part2(input: &str) -> u16 {
+fn part2(input: &str) -> Result<u16, ParseError> {
     let mut rules = Vec::with_capacity(32);
     let mut substitutions = HashMap::with_capacity(1);
 
     let mut lines = input.lines();
 
     for line in lines.by_ref() {
-        if line.len() <= 1 {
+        let line = line.trim_end();
+
+        if line.is_empty() {
             break;
         }
 
-        if line == "8: 42\n" {
-            parse_line(b"8: 42 | 42 8\n", &mut rules, &mut substitutions);
-        } else if line == "11: 42 31\n" {
-            parse_line(b"11: 42 31\n", &mut rules, &mut substitutions);
-            parse_line(b"11: 42 511\n", &mut rules, &mut substitutions);
-            parse_line(b"511: 11 31\n", &mut rules, &mut substitutions);
+        if line == "8: 42" {
+            parse_line(input, "8: 42 | 42 8", &mut rules, &mut substitutions)?;
+        } else if line == "11: 42 31" {
+            parse_line(input, line, &mut rules, &mut substitutions)?;
+
+            rules.push(Rule {
+                left: 11,
+                right: RightSide::Nonterminal(42, HELPER),
+            });
+
+            rules.push(Rule {
+                left: HELPER,
+                right: RightSide::Nonterminal(11, 31),
+            });
         } else {
-            parse_line(line.as_bytes(), &mut rules, &mut substitutions);
+            parse_line(input, line, &mut rules, &mut substitutions)?;
         }
     }
 
@@ -139,42 +162,83 @@ fn part2(input: &str) -> u16 {
     let mut valid = 0;
 
     for line in lines {
-        let accepted = unsafe { cyk(line.trim_end(), &rules) };
+        let accepted = cyk(line.trim_end(), &rules);
         valid += accepted as u16;
     }
 
-    valid
+    Ok(valid)
 }
 
-fn parse_line(bytes: &[u8], rules: &mut Vec<Rule>, substitutions: &mut HashMap<u8, HashSet<u8>>) {
-    let mut i = 0;
-    let mut n = 0;
+fn parse_line(
+    input: &str,
+    line: &str,
+    rules: &mut Vec<Rule>,
+    substitutions: &mut Substitutions,
+) -> Result<(), ParseError> {
+    let (left, right) =
+        line.split_once(": ")
+            .context_at(input, line, "expected `<id>: <rules>`")?;
+
+    let left = parse_id(input, left)?;
+
+    if let Some(terminal) = right.strip_prefix('"') {
+        let right = match terminal.as_bytes() {
+            [c, b'"'] => RightSide::Terminal(*c as char),
+            _ => {
+                return Err(ParseError::at(
+                    input,
+                    right,
+                    "expected a single quoted char",
+                ))
+            }
+        };
 
-    loop {
-        match get!(bytes, i) {
-            b':' => break,
-            digit => n = n * 10 + (digit & 0x0F) as u8,
-        }
+        rules.push(Rule { left, right });
 
-        i += 1;
+        return Ok(());
     }
 
-    i += 2;
+    for alternative in right.split(" | ") {
+        let mut ids = alternative.split(' ');
+
+        let first = ids
+            .next()
+            .context_at(input, alternative, "expected a rule")?;
+        let first = parse_id(input, first)?;
 
-    if get!(bytes, i) == b'"' {
-        let rule = Rule {
-            left: n,
-            right: RightSide::Terminal(get!(bytes, i + 1) as char),
+        let Some(second) = ids.next() else {
+            substitutions.entry(left).or_default().insert(first);
+
+            continue;
         };
 
-        rules.push(rule);
-        return;
+        if ids.next().is_some() {
+            return Err(ParseError::at(
+                input,
+                alternative,
+                "expected at most two rules",
+            ));
+        }
+
+        let right = RightSide::Nonterminal(first, parse_id(input, second)?);
+        rules.push(Rule { left, right });
     }
 
-    parse_right_side(bytes, n, &mut i, rules, substitutions);
+    Ok(())
+}
+
+fn parse_id(input: &str, id: &str) -> Result<u8, ParseError> {
+    match parse_at(input, id)? {
+        HELPER => Err(ParseError::at(input, id, "rule id is reserved")),
+        id => Ok(id),
+    }
 }
 
-unsafe fn cyk(word: &str, rules: &[Rule]) -> bool {
+fn cyk(word: &str, rules: &[Rule]) -> bool {
+    if word.is_empty() {
+        return false;
+    }
+
     let mut table = Vec::with_capacity(word.len());
 
     for i in 0..word.len() {
@@ -185,10 +249,7 @@ unsafe fn cyk(word: &str, rules: &[Rule]) -> bool {
         for rule in rules {
             if let RightSide::Terminal(c) = rule.right {
                 if c == letter {
-                    table
-                        .get_unchecked_mut(i)
-                        .get_unchecked_mut(i)
-                        .insert(rule.left);
+                    table[i][i].insert(rule.left);
                 }
             }
         }
@@ -199,8 +260,8 @@ unsafe fn cyk(word: &str, rules: &[Rule]) -> bool {
             for k in j..i + j {
                 let mut to_add = HashSet::new();
 
-                for &s1 in table.get_unchecked(k).get_unchecked(j).iter() {
-                    for &s2 in table.get_unchecked(i + j).get_unchecked(k + 1).iter() {
+                for &s1 in table[k][j].iter() {
+                    for &s2 in table[i + j][k + 1].iter() {
                         for rule in rules {
                             if let RightSide::Nonterminal(a, b) = rule.right {
                                 if a == s1 && b == s2 {
@@ -211,86 +272,10 @@ unsafe fn cyk(word: &str, rules: &[Rule]) -> bool {
                     }
                 }
 
-                table
-                    .get_unchecked_mut(j + i)
-                    .get_unchecked_mut(j)
-                    .extend(to_add);
+                table[j + i][j].extend(to_add);
             }
         }
     }
 
-    table
-        .get_unchecked(word.len() - 1)
-        .get_unchecked(0)
-        .contains(&0)
-}
-
-fn parse_right_side(
-    bytes: &[u8],
-    left: u8,
-    i: &mut usize,
-    rules: &mut Vec<Rule>,
-    substitutions: &mut HashMap<u8, HashSet<u8>>,
-) {
-    let mut first = 0;
-
-    loop {
-        match get!(bytes, *i) {
-            b' ' | b'\n' => break,
-            digit => first = first * 10 + (digit & 0x0F) as u8,
-        }
-
-        *i += 1;
-    }
-
-    if get!(bytes, *i) == b'\n' {
-        substitutions
-            .entry(left)
-            .or_insert_with(HashSet::new)
-            .insert(first);
-
-        return;
-    }
-
-    *i += 1;
-
-    if get!(bytes, *i) == b'|' {
-        substitutions
-            .entry(left)
-            .or_insert_with(HashSet::new)
-            .insert(first);
-
-        *i += 2;
-
-        return parse_right_side(bytes, left, i, rules, substitutions);
-    }
-
-    let mut second = 0;
-
-    loop {
-        match get!(bytes, *i) {
-            b' ' | b'\n' => break,
-            digit => second = second * 10 + (digit & 0x0F) as u8,
-        }
-
-        *i += 1;
-    }
-
-    let rule = Rule {
-        left,
-        right: RightSide::Nonterminal(first, second),
-    };
-
-    rules.push(rule);
-
-    if get!(bytes, *i) == b'\n' {
-        return;
-    }
-
-    *i += 1;
-
-    if get!(bytes, *i) == b'|' {
-        *i += 2;
-        parse_right_side(bytes, left, i, rules, substitutions);
-    }
+    table[word.len() - 1][0].contains(&0)
 }
@@ -1,3 +1,4 @@
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
 use hashbrown::HashMap;
 use std::fmt;
@@ -167,88 +168,87 @@ impl fmt::Display for Tile {
     }
 }
 
-pub fn run(input: &str) -> Result<Solution> {
-    Ok(Solution::new().part1(part1(input)).part2(part2(input)))
+aoc_rust::register_day! {
+    day: 20,
+    title: "Jurassic Jigsaw",
 }
 
-fn part1(input: &str) -> u64 {
-    let mut lines = input.lines();
-
-    let w;
-    let mut tiles = Vec::with_capacity(16);
-    let mut edges = HashMap::with_capacity(1024);
-
-    let bytes = lines.next().unwrap().as_bytes();
-    let mut id = 0;
-    let mut i = 5;
-
-    loop {
-        match get!(bytes, i) {
-            b':' => break,
-            digit => id = id * 10 + (digit & 0x0F) as u16,
-        }
-
-        i += 1;
-    }
-
-    let line = lines.next().unwrap();
+pub fn run(input: &str) -> Result<Solution> {
+    let (tiles, edges) = parse_tiles(input)?;
 
-    w = line.len() - 1;
+    Ok(Solution::new()
+        .part1(part1(tiles.clone(), &edges))
+        .part2(part2(tiles, &edges)))
+}
 
-    let mut tile = Tile::new(id, w);
-    tile.extend(&line.as_bytes()[..w]);
+fn parse_tiles(input: &str) -> Result<(Vec<Tile>, HashMap<u16, u8>), ParseError> {
+    let mut lines = input.lines().map(str::trim_end).peekable();
 
-    loop {
-        let line = lines.next().unwrap();
+    let mut w = None;
+    let mut tiles = Vec::with_capacity(144);
+    let mut edges = HashMap::with_capacity(1024);
 
-        if line.len() == 1 {
-            break;
+    while let Some(header) = lines.next() {
+        if header.is_empty() {
+            continue;
         }
 
-        tile.extend(&line.as_bytes()[..w]);
-    }
+        let id = header
+            .strip_prefix("Tile ")
+            .and_then(|id| id.strip_suffix(':'))
+            .context_at(input, header, "expected `Tile <id>:`")?;
 
-    tile.borders();
-    count_edges(&tile, &mut edges);
-    tiles.push(tile);
+        let mut tile = Tile::new(parse_at(input, id)?, 0);
+        let mut rows = 0;
 
-    while let Some(line) = lines.next() {
-        if line.is_empty() {
-            break;
-        }
+        while let Some(line) = lines.next_if(|line| !line.is_empty()) {
+            let w = *w.get_or_insert(line.len());
 
-        let bytes = line.as_bytes();
-        let mut id = 0;
-        let mut i = 5;
+            if line.len() != w || !(3..=16).contains(&w) {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    "rows must be 3 to 16 pixels wide",
+                ));
+            }
 
-        loop {
-            match get!(bytes, i) {
-                b':' => break,
-                digit => id = id * 10 + (digit & 0x0F) as u16,
+            if let Some(i) = line.find(|c| c != '#' && c != '.') {
+                return Err(ParseError::at(input, &line[i..], "expected `#` or `.`"));
             }
 
-            i += 1;
+            tile.extend(line.as_bytes());
+            rows += 1;
         }
 
-        let mut tile = Tile::new(id, w);
+        let w = w.unwrap_or(0);
 
-        while let Some(line) = lines.next() {
-            if line.len() > 1 {
-                tile.extend(&line.as_bytes()[..w]);
-            } else {
-                break;
-            }
+        if rows != w {
+            return Err(ParseError::at(input, header, "tile is not square"));
         }
 
+        tile.w = w;
         tile.borders();
         count_edges(&tile, &mut edges);
         tiles.push(tile);
     }
 
+    let w_outer = (tiles.len() as f32).sqrt() as usize;
+
+    if tiles.is_empty() || w_outer * w_outer != tiles.len() {
+        return Err(ParseError::new(format_args!(
+            "{} tiles can not form a square",
+            tiles.len()
+        )));
+    }
+
+    Ok((tiles, edges))
+}
+
+fn part1(tiles: Vec<Tile>, edges: &HashMap<u16, u8>) -> u64 {
     let mut p1 = 1;
 
     for mut tile in tiles {
-        if valid_corner_tile(&tile, &edges) {
+        if valid_corner_tile(&tile, edges) {
             p1 *= tile.id as u64;
             continue;
         }
@@ -256,7 +256,7 @@ fn part1(input: &str) -> u64 {
         tile.flip_horizontal();
         tile.flip_vertical();
 
-        if valid_corner_tile(&tile, &edges) {
+        if valid_corner_tile(&tile, edges) {
             p1 *= tile.id as u64;
         }
     }
@@ -282,91 +282,21 @@ fn count_edges(tile: &Tile, edges: &mut HashMap<u16, u8>) {
     }
 }
 
-fn part2(input: &str) -> usize {
-    let mut lines = input.lines();
-
-    let w;
-    let mut tiles = Vec::with_capacity(128);
-    let mut edges = HashMap::with_capacity(1024);
-
-    let bytes = lines.next().unwrap().as_bytes();
-    let mut id = 0;
-    let mut i = 5;
-
-    loop {
-        match get!(bytes, i) {
-            b':' => break,
-            digit => id = id * 10 + (digit & 0x0F) as u16,
-        }
-
-        i += 1;
-    }
-
-    let line = lines.next().unwrap();
-
-    w = line.len() - 1;
-
-    let mut tile = Tile::new(id, w);
-    tile.extend(&line.as_bytes()[..w]);
-
-    for line in lines.by_ref() {
-        if line.len() == 1 {
-            break;
-        }
-
-        tile.extend(&line.as_bytes()[..w]);
-    }
-
-    tile.borders();
-
-    count_edges(&tile, &mut edges);
-    tiles.push(tile);
-
-    while let Some(line) = lines.next() {
-        if line.is_empty() {
-            break;
-        }
-
-        let bytes = line.as_bytes();
-        let mut id = 0;
-        let mut i = 5;
-
-        loop {
-            match get!(bytes, i) {
-                b':' => break,
-                digit => id = id * 10 + (digit & 0x0F) as u16,
-            }
-
-            i += 1;
-        }
-
-        let mut tile = Tile::new(id, w);
-
-        while let Some(line) = lines.next() {
-            if line.len() > 1 {
-                tile.extend(&line.as_bytes()[..w]);
-            } else {
-                break;
-            }
-        }
-
-        tile.borders();
-        count_edges(&tile, &mut edges);
-        tiles.push(tile);
-    }
+fn part2(mut tiles: Vec<Tile>, edges: &HashMap<u16, u8>) -> usize {
+    let w = tiles[0].w;
 
     let w_outer = (tiles.len() as f32).sqrt() as usize;
     let mut i = tiles.len() - 1;
 
     let mut corner = loop {
         let tile = &mut tiles[i];
-        if valid_corner_tile(&tile, &edges) {
+        if valid_corner_tile(&tile, edges) {
             break tiles.swap_remove(i);
         } else {
             tile.flip_horizontal();
             tile.flip_vertical();
 
-            if valid_corner_tile(&tile, &edges) {
+            if valid_corner_tile(&tile, edges) {
                 break tiles.swap_remove(i);
             }
         }
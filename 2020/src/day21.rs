@@ -1,21 +1,30 @@
 use std::collections::{BTreeMap, HashMap, HashSet};
-use std::hint::unreachable_unchecked;
 
+use aoc_rust::util::parse::ParseContext;
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 21,
+    title: "Allergen Assessment",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut foods = HashMap::with_capacity(16);
     let mut allergens: HashMap<_, HashSet<_>> = HashMap::with_capacity(8);
 
-    for line in input.lines() {
-        let split = line
-            .find(" (contains ")
-            .unwrap_or_else(|| unsafe { unreachable_unchecked() });
+    for line in input.lines().map(str::trim_end) {
+        let (food, allergen_list) = line
+            .strip_suffix(')')
+            .and_then(|line| line.split_once(" (contains "))
+            .context_at(
+                input,
+                line,
+                "expected `<ingredients> (contains <allergens>)`",
+            )?;
 
-        let food: HashSet<_> = line[..split].split(' ').map(str::to_owned).collect();
-        let allergen_list =
-            unsafe { line.get_unchecked((split + 11)..(line.trim_end().len() - 1)) }.split(", ");
+        let food: HashSet<_> = food.split(' ').map(str::to_owned).collect();
+        let allergen_list = allergen_list.split(", ");
 
         for allergen in allergen_list {
             if let Some(entry) = allergens.get_mut(allergen) {
@@ -41,6 +50,8 @@ pub fn run(input: &str) -> Result<Solution> {
     let mut mappings = BTreeMap::new();
 
     while !allergens.is_empty() {
+        let resolved = mappings.len();
+
         for (allergen, food) in allergens.iter() {
             if food.len() == 1 {
                 let ingredient = food.iter().next().unwrap();
@@ -48,6 +59,10 @@ pub fn run(input: &str) -> Result<Solution> {
             }
         }
 
+        if mappings.len() == resolved {
+            eyre::bail!("allergens can not be assigned to ingredients uniquely");
+        }
+
         for allergen in mappings.keys() {
             allergens.remove(allergen);
         }
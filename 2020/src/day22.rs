@@ -1,6 +1,7 @@
 use std::collections::VecDeque;
 use std::hint::unreachable_unchecked;
 
+use aoc_rust::util::parse::{parse_at, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 use hashbrown::HashSet;
@@ -18,8 +19,18 @@ macro_rules! min_max {
 
 type Deck = VecDeque<u8>;
 
+aoc_rust::register_day! {
+    day: 22,
+    title: "Crab Combat",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    Ok(Solution::new().part1(part1(input)).part2(part2(input)))
+    let (deck1, deck2) = parse_decks(input)?;
+
+    let p1 = part1(deck1.clone(), deck2.clone());
+    let p2 = part2(deck1, deck2);
+
+    Ok(Solution::new().part1(p1).part2(p2))
 }
 
 enum Player {
@@ -27,9 +38,7 @@ enum Player {
     Two,
 }
 
-fn part1(input: &str) -> usize {
-    let (mut deck1, mut deck2) = parse_decks(input);
-
+fn part1(mut deck1: Deck, mut deck2: Deck) -> usize {
     while !(deck1.is_empty() || deck2.is_empty()) {
         let card1 = deck1
             .pop_front()
@@ -50,8 +59,7 @@ fn part1(input: &str) -> usize {
     score(if deck1.is_empty() { deck2 } else { deck1 })
 }
 
-fn part2(input: &str) -> usize {
-    let (deck1, deck2) = parse_decks(input);
+fn part2(deck1: Deck, deck2: Deck) -> usize {
     let (_, deck) = recurse(deck1, deck2);
 
     score(deck)
@@ -70,6 +78,8 @@ fn recurse(mut deck1: Deck, mut deck2: Deck) -> (Player, Deck) {
             None => return (Player::Two, deck2),
         };
 
+        // Decks start non-empty and cards are positive so sub-games never
+        // start with an empty deck either
         let card2 = deck2
             .pop_front()
             .unwrap_or_else(|| unsafe { unreachable_unchecked() });
@@ -123,27 +133,46 @@ fn hash(deck: &Deck) -> u64 {
     deck.iter().fold(0, |hash, card| hash | (1 << *card))
 }
 
-fn parse_decks(input: &str) -> (Deck, Deck) {
-    let mut deck1 = VecDeque::with_capacity(16);
+fn parse_decks(input: &str) -> Result<(Deck, Deck), ParseError> {
+    let mut lines = input.lines().map(str::trim_end);
 
-    let mut lines = input.lines();
-    lines.next();
+    let deck1 = parse_deck(input, &mut lines, "Player 1:")?;
+    let deck2 = parse_deck(input, &mut lines, "Player 2:")?;
 
-    while let Some(line) = lines.next() {
-        if line.len() <= 1 {
-            break;
-        }
+    Ok((deck1, deck2))
+}
 
-        deck1.push_back(line.parse().unwrap());
+fn parse_deck<'a>(
+    input: &str,
+    lines: &mut impl Iterator<Item = &'a str>,
+    header: &str,
+) -> Result<Deck, ParseError> {
+    match lines.find(|line| !line.is_empty()) {
+        Some(line) if line == header => {}
+        Some(line) => return Err(ParseError::at(input, line, format!("expected `{header}`"))),
+        None => return Err(ParseError::new(format!("missing `{header}`"))),
     }
 
-    lines.next();
+    let mut deck = VecDeque::with_capacity(16);
+
+    for line in lines.take_while(|line| !line.is_empty()) {
+        let card: u8 = parse_at(input, line)?;
 
-    let mut deck2 = VecDeque::with_capacity(16);
+        // Decks are hashed into a u64 bitset
+        if !(1..64).contains(&card) {
+            return Err(ParseError::at(
+                input,
+                line,
+                "cards must be between 1 and 63",
+            ));
+        }
+
+        deck.push_back(card);
+    }
 
-    while let Some(line) = lines.next() {
-        deck2.push_back(line.parse().unwrap());
+    if deck.is_empty() {
+        return Err(ParseError::at(input, header, "deck is empty"));
     }
 
-    (deck1, deck2)
+    Ok(deck)
 }
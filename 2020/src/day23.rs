@@ -1,9 +1,17 @@
+use aoc_rust::util::parse::ParseError;
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 23,
+    title: "Crab Cups",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let cups = parse_input(input)?;
+
+    let p1 = part1(&cups);
+    let p2 = part2(&cups);
 
     // let p1 = _part1_old(input);
     // let p2 = _part2_old(input);
@@ -11,22 +19,10 @@ pub fn run(input: &str) -> Result<Solution> {
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> usize {
-    let mut n = parse_input(input);
+fn part1(cups: &[usize; 9]) -> usize {
     let mut circle = vec![0; 10];
-
-    let last = n % 10;
-    let mut curr = last;
-    n /= 10;
-
-    while n > 0 {
-        let prev = n % 10;
-        n /= 10;
-        set!(circle, prev, curr);
-        curr = prev;
-    }
-
-    set!(circle, last, curr);
+    let curr = link(&mut circle, cups);
+    circle[cups[8]] = curr;
 
     execute(&mut circle, curr, 100);
 
@@ -38,23 +34,11 @@ fn part1(input: &str) -> usize {
     p1
 }
 
-fn part2(input: &str) -> usize {
-    let mut n = parse_input(input);
+fn part2(cups: &[usize; 9]) -> usize {
     let mut circle: Vec<_> = (1..=1_000_001).collect();
-
-    let last = n % 10;
-    let mut curr = last;
-    n /= 10;
-
-    while n > 0 {
-        let prev = n % 10;
-        n /= 10;
-        set!(circle, prev, curr);
-        curr = prev;
-    }
-
-    set!(circle, last, 10);
-    set!(circle, 1_000_000, curr);
+    let curr = link(&mut circle, cups);
+    circle[cups[8]] = 10;
+    circle[1_000_000] = curr;
 
     execute(&mut circle, curr, 10_000_000);
 
@@ -84,8 +68,40 @@ fn execute(circle: &mut [usize], mut curr: usize, moves: usize) {
     }
 }
 
-fn parse_input(input: &str) -> usize {
-    input.lines().next().unwrap().parse().unwrap()
+/// Let each cup point to its successor and return the first cup
+fn link(circle: &mut [usize], cups: &[usize; 9]) -> usize {
+    for pair in cups.windows(2) {
+        circle[pair[0]] = pair[1];
+    }
+
+    cups[0]
+}
+
+fn parse_input(input: &str) -> Result<[usize; 9], ParseError> {
+    let line = input.lines().next().unwrap_or_default().trim_end();
+    let mut cups = [0; 9];
+    let mut seen = [false; 10];
+
+    if line.len() != cups.len() {
+        return Err(ParseError::at(input, line, "expected nine cups"));
+    }
+
+    for ((i, byte), cup) in line.bytes().enumerate().zip(cups.iter_mut()) {
+        *cup = match byte {
+            b'1'..=b'9' if !seen[(byte & 0xF) as usize] => (byte & 0xF) as usize,
+            _ => {
+                return Err(ParseError::at_offset(
+                    input,
+                    i,
+                    "expected each digit 1-9 once",
+                ))
+            }
+        };
+
+        seen[*cup] = true;
+    }
+
+    Ok(cups)
 }
 
 fn _part1_old(input: &str) -> u32 {
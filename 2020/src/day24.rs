@@ -1,7 +1,7 @@
 use std::collections::HashSet;
-use std::hint::unreachable_unchecked;
 use std::ops::{Add, AddAssign};
 
+use aoc_rust::util::parse::ParseError;
 use aoc_rust::Solution;
 use eyre::Result;
 
@@ -53,39 +53,35 @@ static OFFSETS: [Pos; 6] = [
     Pos { x: -1, y: 0, z: 1 },
 ];
 
+aoc_rust::register_day! {
+    day: 24,
+    title: "Lobby Layout",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut blacks = HashSet::with_capacity(2048);
 
-    for line in input.lines() {
+    for line in input.lines().map(str::trim_end) {
         let mut pos = Pos::default();
         let bytes = line.as_bytes();
         let mut i = 0;
 
         while i < bytes.len() {
-            match get!(bytes, i) {
-                b'e' => pos += (-1, -1, 0),
-                b'w' => pos += (1, 1, 0),
-                b'n' => {
-                    i += 1;
-                    match get!(bytes, i) {
-                        b'e' => pos += (-1, 0, 1),
-                        b'w' => pos += (0, 1, 1),
-                        _ => unsafe { unreachable_unchecked() },
-                    }
-                }
-                b's' => {
-                    i += 1;
-                    match get!(bytes, i) {
-                        b'e' => pos += (0, -1, -1),
-                        b'w' => pos += (1, 0, -1),
-                        _ => unsafe { unreachable_unchecked() },
-                    }
+            match &bytes[i..] {
+                [b'e', ..] => pos += (-1, -1, 0),
+                [b'w', ..] => pos += (1, 1, 0),
+                [b'n', b'e', ..] => pos += (-1, 0, 1),
+                [b'n', b'w', ..] => pos += (0, 1, 1),
+                [b's', b'e', ..] => pos += (0, -1, -1),
+                [b's', b'w', ..] => pos += (1, 0, -1),
+                _ => {
+                    let msg = "expected `e`, `se`, `sw`, `w`, `nw`, or `ne`";
+
+                    return Err(ParseError::at(input, &line[i..], msg).into());
                 }
-                b'\n' => break,
-                _ => unsafe { unreachable_unchecked() },
             }
 
-            i += 1;
+            i += 1 + matches!(bytes[i], b'n' | b's') as usize;
         }
 
         if !blacks.insert(pos) {
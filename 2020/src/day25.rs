@@ -1,19 +1,41 @@
+use aoc_rust::util::parse::{parse_at, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
+const MODULUS: usize = 20_201_227;
+
+aoc_rust::register_day! {
+    day: 25,
+    title: "Combo Breaker",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let mut lines = input.lines();
+    let mut lines = input.lines().map(str::trim_end);
+
+    let mut public_key = || {
+        let line = lines
+            .next()
+            .ok_or_else(|| ParseError::new("missing public key"))?;
+        let key = parse_at(input, line)?;
+
+        // Keys outside of the group would never be reached
+        if (1..MODULUS).contains(&key) {
+            Ok(key)
+        } else {
+            Err(ParseError::at(input, line, "public key out of range"))
+        }
+    };
 
-    let card_key: usize = lines.next().unwrap().parse().unwrap();
-    let door_key: usize = lines.next().unwrap().parse().unwrap();
+    let card_key = public_key()?;
+    let door_key = public_key()?;
 
     let mut val = 1;
     let mut encryption = 1;
     let subject_number = 7;
 
     while val != card_key {
-        val = (subject_number * val) % 20_201_227;
-        encryption = (encryption * door_key) % 20_201_227;
+        val = (subject_number * val) % MODULUS;
+        encryption = (encryption * door_key) % MODULUS;
     }
 
     Ok(Solution::new().part1(encryption))
@@ -1,29 +1,22 @@
+use aoc_rust::util::parse::{parse_at, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
-pub fn run(input: &str) -> Result<Solution> {
-    let mut lines = input.lines();
-
-    let mut p1 = 0;
-    let mut p2 = 0;
-
-    let mut a: u16 = lines.next().unwrap().parse()?;
-    let mut b: u16 = lines.next().unwrap().parse()?;
-
-    p1 += (b > a) as usize;
+aoc_rust::register_day! {
+    day: 1,
+    title: "Sonar Sweep",
+}
 
-    let mut c: u16 = lines.next().unwrap().parse()?;
+pub fn run(input: &str) -> Result<Solution> {
+    let depths = input
+        .lines()
+        .map(|line| parse_at(input, line.trim_end()))
+        .collect::<Result<Vec<u16>, ParseError>>()?;
 
-    p1 += (c > b) as usize;
+    let p1 = depths.windows(2).filter(|w| w[1] > w[0]).count();
 
-    for line in lines {
-        let curr = line.parse()?;
-        p1 += (curr > c) as usize;
-        p2 += (curr > a) as usize;
-        a = b;
-        b = c;
-        c = curr;
-    }
+    // Consecutive windows share two values so only the outer ones matter
+    let p2 = depths.windows(4).filter(|w| w[3] > w[0]).count();
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -1,24 +1,36 @@
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 2,
+    title: "Dive!",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut horizontal = 0;
     let mut depth = 0;
     let mut aim = 0;
 
-    for line in input.lines() {
-        let mut split = line.split(' ');
-        let dir = split.next().unwrap().as_bytes()[0];
-        let val: i64 = split.next().unwrap().parse()?;
+    for line in input.lines().map(str::trim_end) {
+        let (dir, val) =
+            line.split_once(' ')
+                .context_at(input, line, "expected `<direction> <amount>`")?;
+
+        let val: i64 = parse_at(input, val)?;
 
         match dir {
-            b'f' => {
+            "forward" => {
                 horizontal += val;
                 depth += aim * val;
             }
-            b'd' => aim += val,
-            b'u' => aim -= val,
-            _ => unreachable!(),
+            "down" => aim += val,
+            "up" => aim -= val,
+            _ => {
+                let msg = "expected `forward`, `down`, or `up`";
+
+                return Err(ParseError::at(input, dir, msg).into());
+            }
         }
     }
 
@@ -1,6 +1,12 @@
+use aoc_rust::util::parse::ParseError;
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 3,
+    title: "Binary Diagnostic",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     const LEN: usize = 12;
 
@@ -11,10 +17,20 @@ pub fn run(input: &str) -> Result<Solution> {
 
     // Parse input
     for line in input.lines() {
+        let line = line.trim_end();
+
+        if line.len() != LEN {
+            return Err(ParseError::at(input, line, format!("expected {LEN} bits")).into());
+        }
+
         count += 1;
         let mut num = 0;
 
-        for (one, &byte) in ones.iter_mut().zip(line.as_bytes()) {
+        for ((one, &byte), i) in ones.iter_mut().zip(line.as_bytes()).zip(0..) {
+            if !matches!(byte, b'0' | b'1') {
+                return Err(ParseError::at(input, &line[i..], "expected `0` or `1`").into());
+            }
+
             *one += (byte == b'1') as usize;
             num = num * 2 + (byte == b'1') as u32;
         }
@@ -83,8 +99,10 @@ pub fn run(input: &str) -> Result<Solution> {
         }
     }
 
-    let oxy = oxy_lines[0];
-    let co2 = lines[0];
+    let (Some(oxy), Some(co2)) = (oxy_lines.first(), lines.first()) else {
+        eyre::bail!("no rating remains after filtering");
+    };
+
     let p2 = oxy * co2;
 
     Ok(Solution::new().part1(p1).part2(p2))
@@ -1,51 +1,59 @@
 use std::str::Lines;
 
+use aoc_rust::util::parse::{parse_at, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 4,
+    title: "Giant Squid",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let (numbers, mut bingos) = parse_input(input)?;
 
     let mut p1_bingos = bingos.clone();
 
-    let p1 = part1(&numbers, &mut p1_bingos);
-    let p2 = part2(&numbers, &mut bingos);
+    let p1 = part1(&numbers, &mut p1_bingos).ok_or_else(|| eyre::eyre!("no board wins"))?;
+    let p2 =
+        part2(&numbers, &mut bingos).ok_or_else(|| eyre::eyre!("no single board wins last"))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn parse_input(input: &str) -> Result<(Vec<u8>, Vec<Bingo>)> {
+fn parse_input(input: &str) -> Result<(Vec<u8>, Vec<Bingo>), ParseError> {
     let mut lines = input.lines();
 
     let numbers = lines
         .next()
-        .unwrap()
+        .unwrap_or_default()
+        .trim_end()
         .split(',')
-        .map(str::parse)
+        .map(|n| parse_at(input, n))
         .collect::<Result<Vec<u8>, _>>()?;
 
     let mut bingos = Vec::new();
 
-    while let Some(bingo) = Bingo::parse(&mut lines)? {
+    while let Some(bingo) = Bingo::parse(input, &mut lines)? {
         bingos.push(bingo);
     }
 
     Ok((numbers, bingos))
 }
 
-fn part1(numbers: &[u8], bingos: &mut [Bingo]) -> u32 {
+fn part1(numbers: &[u8], bingos: &mut [Bingo]) -> Option<u32> {
     for &n in numbers {
         for bingo in bingos.iter_mut() {
             if bingo.mark(n) {
-                return bingo.sum() * n as u32;
+                return Some(bingo.sum() * n as u32);
             }
         }
     }
 
-    unreachable!()
+    None
 }
 
-fn part2(numbers: &[u8], bingos: &mut Vec<Bingo>) -> u32 {
+fn part2(numbers: &[u8], bingos: &mut Vec<Bingo>) -> Option<u32> {
     let mut numbers = numbers.iter();
 
     for &n in &mut numbers {
@@ -64,15 +72,17 @@ fn part2(numbers: &[u8], bingos: &mut Vec<Bingo>) -> u32 {
         }
     }
 
-    let bingo = &mut bingos[0];
+    let [bingo] = bingos.as_mut_slice() else {
+        return None;
+    };
 
     for &n in numbers {
         if bingo.mark(n) {
-            return bingo.sum() * n as u32;
+            return Some(bingo.sum() * n as u32);
         }
     }
 
-    unreachable!()
+    None
 }
 
 #[derive(Clone)]
@@ -96,20 +106,24 @@ const DONE: [u32; 10] = [
 ];
 
 impl Bingo {
-    fn parse(lines: &mut Lines<'_>) -> Result<Option<Self>> {
+    fn parse(input: &str, lines: &mut Lines<'_>) -> Result<Option<Self>, ParseError> {
         let mut field = Vec::with_capacity(25);
 
         if lines.next().is_none() {
             return Ok(None);
         }
 
-        for line in lines.take(5) {
-            let row = line
-                .trim_end()
-                .split_whitespace()
-                .map(|s| s.parse::<u8>().unwrap());
+        for _ in 0..5 {
+            let line = lines.next().unwrap_or_default().trim_end();
+            let len = field.len();
 
-            field.extend(row);
+            for n in line.split_whitespace() {
+                field.push(parse_at(input, n)?);
+            }
+
+            if field.len() - len != 5 {
+                return Err(ParseError::at(input, line, "expected row of 5 numbers"));
+            }
         }
 
         let field = field.into_boxed_slice();
@@ -1,30 +1,25 @@
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 5,
+    title: "Hydrothermal Venture",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut grid = vec![[0_u8; 1000]; 1000];
 
     const BOTH_PARTS: u8 = 0b0001_0001;
     const PART2: u8 = 0b0000_0001;
 
-    for line in input.lines() {
-        let bytes = line.trim_end().as_bytes();
-
-        let mut i = 0;
-        let x1 = parse_num(bytes, &mut i, b',');
-        i += 1;
-        let y1 = parse_num(bytes, &mut i, b' ');
-        i += 4;
-        let x2 = parse_num(bytes, &mut i, b',');
-        i += 1;
-
-        let mut y2 = (bytes[i] & 0x0F) as usize;
-        i += 1;
+    for line in input.lines().map(str::trim_end) {
+        let (start, end) =
+            line.split_once(" -> ")
+                .context_at(input, line, "expected `x1,y1 -> x2,y2`")?;
 
-        while i < bytes.len() {
-            y2 = y2 * 10 + (bytes[i] & 0x0F) as usize;
-            i += 1
-        }
+        let (x1, y1) = parse_point(input, start)?;
+        let (x2, y2) = parse_point(input, end)?;
 
         if x1 == x2 {
             let range = if y1 < y2 { y1..=y2 } else { y2..=y1 };
@@ -71,16 +66,15 @@ pub fn run(input: &str) -> Result<Solution> {
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn parse_num(bytes: &[u8], i: &mut usize, delim: u8) -> usize {
-    let mut n = (bytes[*i] & 0x0F) as usize;
-
-    loop {
-        *i += 1;
+fn parse_point(input: &str, point: &str) -> Result<(usize, usize), ParseError> {
+    let (x, y) = point
+        .split_once(',')
+        .context_at(input, point, "expected `x,y`")?;
 
-        if bytes[*i] == delim {
-            return n;
-        }
+    let coord = |n: &str| match parse_at(input, n)? {
+        n @ 0..1000 => Ok(n),
+        _ => Err(ParseError::at(input, n, "coordinates must be below 1000")),
+    };
 
-        n = n * 10 + (bytes[*i] & 0x0F) as usize;
-    }
+    Ok((coord(x)?, coord(y)?))
 }
@@ -1,14 +1,21 @@
+use aoc_rust::util::parse::{parse_at, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 6,
+    title: "Lanternfish",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let mut lines = input.lines();
+    let line = input.lines().next().unwrap_or_default().trim_end();
 
-    let nums: Vec<u8> = lines
-        .next()
-        .unwrap()
+    let nums = line
         .split(',')
-        .map(str::parse)
+        .map(|n| match parse_at(input, n)? {
+            timer @ 0..=8 => Ok(timer),
+            _ => Err(ParseError::at(input, n, "timers must be between 0 and 8")),
+        })
         .collect::<Result<Vec<u8>, _>>()?;
 
     let p1 = solve(80, &nums);
@@ -1,22 +1,28 @@
+use aoc_rust::util::parse::{parse_at, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 7,
+    title: "The Treachery of Whales",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let mut lines = input.lines();
+    let line = input.lines().next().unwrap_or_default().trim_end();
 
     let mut min = i32::MAX;
     let mut max = 0;
 
-    let mut nums: Vec<i32> = lines
-        .next()
-        .unwrap()
+    let mut nums = line
         .split(',')
-        .map(|s| s.parse().unwrap())
-        .inspect(|&n| {
-            min = min.min(n);
-            max = max.max(n);
+        .map(|n| parse_at(input, n))
+        .inspect(|n| {
+            if let Ok(n) = *n {
+                min = min.min(n);
+                max = max.max(n);
+            }
         })
-        .collect();
+        .collect::<Result<Vec<i32>, ParseError>>()?;
 
     let p2 = part2(&nums, min, max);
     let p1 = part1(&mut nums);
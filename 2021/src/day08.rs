@@ -1,16 +1,35 @@
 use std::collections::HashSet;
 
+use aoc_rust::util::parse::{ParseContext, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 8,
+    title: "Seven Segment Search",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut p1 = 0;
     let mut p2 = 0;
 
-    for line in input.lines() {
+    for line in input.lines().map(str::trim_end) {
         let [mut a, mut b, mut c, mut d, mut e, mut f, mut g] =
             [0; 7].map(|_| HashSet::with_capacity(10));
-        let (left, right) = line.trim_end().split_once(" | ").unwrap();
+
+        let (left, right) =
+            line.split_once(" | ")
+                .context_at(input, line, "expected `<patterns> | <output>`")?;
+
+        let inconsistent = || ParseError::at(input, line, "inconsistent signal patterns");
+
+        for word in left.split(' ').chain(right.split(' ')) {
+            if let Some(i) = word.find(|c| !('a'..='g').contains(&c)) {
+                return Err(ParseError::at(input, &word[i..], "expected segment `a`-`g`").into());
+            } else if !(2..=7).contains(&word.len()) {
+                return Err(ParseError::at(input, word, "expected 2 to 7 segments").into());
+            }
+        }
 
         for word in right.split(' ') {
             p1 += ([2, 3, 4, 7].contains(&word.len())) as usize;
@@ -19,20 +38,15 @@ pub fn run(input: &str) -> Result<Solution> {
         let mut words: Vec<_> = left.split(' ').map(str::as_bytes).collect();
         words.sort_unstable_by_key(|bytes| bytes.len());
 
-        let mut words = words.into_iter();
-
-        let [one, seven, four, _, _, _, six_1, six_2, six_3, eight] = [
-            words.next().unwrap(),
-            words.next().unwrap(),
-            words.next().unwrap(),
-            words.next().unwrap(),
-            words.next().unwrap(),
-            words.next().unwrap(),
-            words.next().unwrap(),
-            words.next().unwrap(),
-            words.next().unwrap(),
-            words.next().unwrap(),
-        ];
+        let [one, seven, four, _, _, _, six_1, six_2, six_3, eight] = words[..] else {
+            return Err(ParseError::at(input, left, "expected ten patterns").into());
+        };
+
+        let lens = [one, seven, four, six_1, six_2, six_3, eight].map(<[u8]>::len);
+
+        if lens != [2, 3, 4, 6, 6, 6, 7] {
+            return Err(inconsistent().into());
+        }
 
         // eight
         for &byte in eight {
@@ -86,7 +100,7 @@ pub fn run(input: &str) -> Result<Solution> {
                 1 => {
                     word.retain(|byte| !c.contains(byte));
                     word.retain(|byte| !f.contains(byte));
-                    let remaining = word.into_iter().next().unwrap();
+                    let remaining = word.into_iter().next().ok_or_else(inconsistent)?;
                     b.retain(|&byte| byte == remaining);
                     // d.retain(|&byte| byte != remaining);
                 }
@@ -113,7 +127,7 @@ pub fn run(input: &str) -> Result<Solution> {
                         }
                     }
                 }
-                _ => unreachable!(),
+                _ => return Err(inconsistent().into()),
             }
         }
 
@@ -139,9 +153,9 @@ pub fn run(input: &str) -> Result<Solution> {
         //     }
         // }
 
-        let b = b.into_iter().next().unwrap();
-        let c = c.into_iter().next().unwrap();
-        let e = e.into_iter().next().unwrap();
+        let b = b.into_iter().next().ok_or_else(inconsistent)?;
+        let c = c.into_iter().next().ok_or_else(inconsistent)?;
+        let e = e.into_iter().next().ok_or_else(inconsistent)?;
 
         let mut n = 0;
 
@@ -161,7 +175,7 @@ pub fn run(input: &str) -> Result<Solution> {
                         (None, None) => n += 3,
                         (None, Some(_)) => n += 2,
                         (Some(_), None) => n += 5,
-                        (Some(_), Some(_)) => unreachable!(),
+                        (Some(_), Some(_)) => return Err(inconsistent().into()),
                     }
                 }
                 6 => {
@@ -169,7 +183,7 @@ pub fn run(input: &str) -> Result<Solution> {
                     let e_opt = word.iter().position(|&byte| byte == e);
 
                     match (c_opt, e_opt) {
-                        (None, None) => unreachable!(),
+                        (None, None) => return Err(inconsistent().into()),
                         (None, Some(_)) => n += 6,
                         (Some(_), None) => n += 9,
                         (Some(_), Some(_)) => n += 0,
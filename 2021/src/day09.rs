@@ -3,22 +3,19 @@ use std::{
     collections::{HashSet, VecDeque},
 };
 
+use aoc_rust::grid::{Grid, Pos2};
 use aoc_rust::Solution;
 use eyre::Result;
 
-use crate::util::{Matrix, Pos2};
+aoc_rust::register_day! {
+    day: 9,
+    title: "Smoke Basin",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
-    let mut grid = Vec::new();
-    let mut w = 0;
-
-    for line in input.lines() {
-        let line = line.trim_end().as_bytes();
-        w = line.len();
-        grid.extend(line.iter().map(|&b| b - b'0'));
-    }
-
-    let grid = Matrix::from_vec(grid, w);
+    let grid = Grid::parse(input, "a digit", |byte| {
+        byte.is_ascii_digit().then_some(byte - b'0')
+    })?;
 
     let (p1, _) = part1(&grid);
 
@@ -28,7 +25,7 @@ pub fn run(input: &str) -> Result<Solution> {
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(grid: &Matrix<u8>) -> (u32, Vec<Pos2<isize>>) {
+fn part1(grid: &Grid<u8>) -> (u32, Vec<Pos2<isize>>) {
     let mut answer = 0;
     let mut lows = Vec::new();
 
@@ -66,7 +63,7 @@ fn part1(grid: &Matrix<u8>) -> (u32, Vec<Pos2<isize>>) {
     (answer, lows)
 }
 
-fn part2(grid: &Matrix<u8>, lows: &[Pos2<isize>]) -> usize {
+fn part2(grid: &Grid<u8>, lows: &[Pos2<isize>]) -> usize {
     let mut basins = Vec::with_capacity(lows.len());
 
     let mut visited = HashSet::new();
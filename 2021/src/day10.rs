@@ -1,23 +1,33 @@
+use aoc_rust::util::parse::ParseError;
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 10,
+    title: "Syntax Scoring",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut stack = Vec::new();
     let mut p1 = 0;
     let mut scores = Vec::new();
 
-    for line in input.lines() {
+    for line in input.lines().map(str::trim_end) {
         stack.clear();
         let before = p1;
 
-        for c in line.trim_end().chars() {
+        for (i, c) in line.char_indices() {
             match c {
                 '(' | '[' | '{' | '<' => stack.push(c),
                 ')' => p1 += (stack.pop() != Some('(')) as usize * 3,
                 ']' => p1 += (stack.pop() != Some('[')) as usize * 57,
                 '}' => p1 += (stack.pop() != Some('{')) as usize * 1197,
                 '>' => p1 += (stack.pop() != Some('<')) as usize * 25_137,
-                _ => unreachable!("invalid {c}"),
+                _ => {
+                    let msg = "expected a bracket";
+
+                    return Err(ParseError::at(input, &line[i..], msg).into());
+                }
             }
         }
 
@@ -41,7 +51,10 @@ pub fn run(input: &str) -> Result<Solution> {
     }
 
     scores.sort_unstable();
-    let p2 = scores[scores.len() / 2];
+
+    let Some(&p2) = scores.get(scores.len() / 2) else {
+        eyre::bail!("no incomplete lines");
+    };
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
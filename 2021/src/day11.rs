@@ -1,45 +1,43 @@
+use aoc_rust::grid::{Grid, Pos2};
+use aoc_rust::util::parse::ParseError;
 use aoc_rust::Solution;
 use eyre::Result;
 
-use crate::util::{Matrix, Pos2};
+aoc_rust::register_day! {
+    day: 11,
+    title: "Dumbo Octopus",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
     let mut grid = parse_grid(input)?;
 
     let p1 = part1(&mut grid);
-    let p2 = part2(&mut grid);
+    let p2 = part2(&mut grid).ok_or_else(|| eyre::eyre!("octopuses never flash simultaneously"))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn parse_grid(input: &str) -> Result<Matrix<u8>> {
-    let mut lines = input.lines();
-    let mut grid = Vec::new();
-
-    let line = lines.next().unwrap().trim_end().as_bytes();
-    let w = line.len();
-    grid.extend(line.iter().map(|byte| byte & 0x0F));
-
-    for line in lines {
-        let row = line.trim_end().as_bytes().iter().map(|byte| byte & 0x0F);
-        grid.extend(row);
-    }
-
-    Ok(Matrix::from_vec(grid, w))
+fn parse_grid(input: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(input, "a digit", |byte| {
+        byte.is_ascii_digit().then_some(byte & 0x0F)
+    })
 }
 
-fn part1(grid: &mut Matrix<u8>) -> usize {
+fn part1(grid: &mut Grid<u8>) -> usize {
     (0..100).map(|_| step(grid)).sum()
 }
 
-fn part2(grid: &mut Matrix<u8>) -> usize {
-    (101..).find(|_| step(grid) == grid.len()).unwrap()
+fn part2(grid: &mut Grid<u8>) -> Option<usize> {
+    // Bound the search so grids that never synchronize still terminate
+    const LIMIT: usize = 100_000;
+
+    (101..LIMIT).find(|_| step(grid) == grid.len())
 }
 
-fn step(grid: &mut Matrix<u8>) -> usize {
+fn step(grid: &mut Grid<u8>) -> usize {
     let mut flashes = 0;
 
-    for pos in grid.pos_iter() {
+    for pos in grid.positions() {
         let value = &mut grid[pos];
         *value += 1;
 
@@ -56,7 +54,7 @@ fn step(grid: &mut Matrix<u8>) -> usize {
     flashes
 }
 
-fn increment_neighbors(grid: &mut Matrix<u8>, pos: Pos2<usize>, flashes: &mut usize) {
+fn increment_neighbors(grid: &mut Grid<u8>, pos: Pos2<usize>, flashes: &mut usize) {
     for n in grid.neighbors_8(pos) {
         let value = &mut grid[n];
         *value += 1;
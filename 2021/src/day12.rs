@@ -1,8 +1,14 @@
 use std::collections::HashMap;
 
+use aoc_rust::util::parse::{ParseContext, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 12,
+    title: "Passage Pathing",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let (map, start, end) = parse_input(input)?;
 
@@ -69,9 +75,11 @@ impl Id {
 }
 
 // Assuming there are no more than 20 caves
-struct Map([Vec<(Id, usize)>; 20]);
+struct Map([Vec<(Id, usize)>; Map::CAPACITY]);
 
 impl Map {
+    const CAPACITY: usize = 20;
+
     fn new() -> Self {
         let arr = [
             Vec::new(),
@@ -100,48 +108,71 @@ impl Map {
     }
 
     fn get(&self, id: Id) -> &[(Id, usize)] {
-        unsafe { self.0.get_unchecked((id.0 & !Id::SMALL) as usize) }
+        &self.0[(id.0 & !Id::SMALL) as usize]
     }
 
     fn get_mut(&mut self, id: Id) -> &mut Vec<(Id, usize)> {
-        unsafe { self.0.get_unchecked_mut((id.0 & !Id::SMALL) as usize) }
+        &mut self.0[(id.0 & !Id::SMALL) as usize]
     }
 }
 
-fn parse_input(input: &str) -> Result<(Map, Id, Id)> {
+fn parse_input(input: &str) -> Result<(Map, Id, Id), ParseError> {
     let mut ids = HashMap::new();
     let mut curr_id = 0;
 
     let mut get_id = |s: &str| match ids.get(s) {
-        Some(id) => *id,
+        Some(id) => Ok(*id),
+        None if s.is_empty() || !s.bytes().all(|byte| byte.is_ascii_alphabetic()) => {
+            Err(ParseError::at(input, s, "expected cave name"))
+        }
+        None if curr_id as usize == Map::CAPACITY => Err(ParseError::at(
+            input,
+            s,
+            format!("at most {} caves are supported", Map::CAPACITY),
+        )),
         None => {
             let id = Id::new(curr_id, s);
             ids.insert(s.to_owned(), id);
             curr_id += 1;
 
-            id
+            Ok(id)
         }
     };
 
     let mut map = Map::new();
 
-    for line in input.lines() {
-        let (left, right) = line.trim_end().split_once('-').unwrap();
-
-        let left = get_id(left);
-        let right = get_id(right);
+    for line in input.lines().map(str::trim_end) {
+        let (left, right) =
+            line.split_once('-')
+                .context_at(input, line, "expected `<cave>-<cave>`")?;
+
+        let left = get_id(left)?;
+        let right = get_id(right)?;
+
+        // Paths could bounce between them forever
+        if !left.is_small() && !right.is_small() {
+            return Err(ParseError::at(
+                input,
+                line,
+                "big caves must not be connected",
+            ));
+        }
 
         map.get_mut(left).push((right, 1));
         map.get_mut(right).push((left, 1));
     }
 
-    let start = *ids.get("start").unwrap();
+    let start = *ids
+        .get("start")
+        .ok_or_else(|| ParseError::new("missing `start` cave"))?;
 
     for values in &mut map.0 {
         values.retain(|(value, _)| *value != start);
     }
 
-    let end = *ids.get("end").unwrap();
+    let end = *ids
+        .get("end")
+        .ok_or_else(|| ParseError::new("missing `end` cave"))?;
 
     // Remove big caves and connect small caves directly instead
     for from in (0..map.0.len() as u8).map(Id) {
@@ -1,43 +1,51 @@
-use std::{collections::HashSet, fmt::Write};
+use std::collections::HashSet;
 
-use aoc_rust::Solution;
+use aoc_rust::grid::Pos2;
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
+use aoc_rust::{AsciiArt, Solution};
 use eyre::Result;
 
-use crate::util::{Parse, Pos2};
+aoc_rust::register_day! {
+    day: 13,
+    title: "Transparent Origami",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
-    let mut lines = input.lines();
+    let mut lines = input.lines().map(str::trim_end);
     let mut dots: Vec<Pos2<usize>> = Vec::new();
 
     for line in lines.by_ref() {
-        if line.len() == 1 {
+        if line.is_empty() {
             break;
         }
 
-        let (left, right) = line.trim_end().split_once(',').unwrap();
-
-        let left = left.as_bytes().parse();
-        let right = right.as_bytes().parse();
-        let pos = Pos2 { x: left, y: right };
+        let (x, y) = line
+            .split_once(',')
+            .context_at(input, line, "expected `x,y`")?;
 
-        dots.push(pos);
+        dots.push(Pos2::new(parse_at(input, x)?, parse_at(input, y)?));
     }
 
-    let p1 = {
-        let line_ = lines.next().unwrap().trim_end().as_bytes();
-        let axis = line_[11];
-        let n = (&line_[13..]).parse();
-        fold(axis, n, &mut dots);
+    let mut folds = lines.map(|line| {
+        let (axis, n) = line
+            .strip_prefix("fold along ")
+            .and_then(|fold| fold.split_once('='))
+            .filter(|(axis, _)| matches!(*axis, "x" | "y"))
+            .context_at(input, line, "expected `fold along <x|y>=<n>`")?;
 
-        dots.iter().collect::<HashSet<_>>().len()
-    };
+        Ok::<_, ParseError>((axis.as_bytes()[0], parse_at(input, n)?))
+    });
 
-    for line in lines {
-        let line = line.trim_end().as_bytes();
+    let (axis, n) = folds
+        .next()
+        .ok_or_else(|| ParseError::new("missing fold instructions"))??;
 
-        let axis = line[11];
-        let n = (&line[13..]).parse();
-        fold(axis, n, &mut dots);
+    fold(axis, n, &mut dots)?;
+    let p1 = dots.iter().collect::<HashSet<_>>().len();
+
+    for instruction in folds {
+        let (axis, n) = instruction?;
+        fold(axis, n, &mut dots)?;
     }
 
     let p2 = print(&dots);
@@ -45,43 +53,23 @@ pub fn run(input: &str) -> Result<Solution> {
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn fold(axis: u8, n: usize, dots: &mut [Pos2<usize>]) {
-    if axis == b'x' {
-        for dot in dots.iter_mut() {
-            if dot.x > n {
-                dot.x = n - (dot.x - n);
-            }
-        }
-    } else {
-        for dot in dots.iter_mut() {
-            if dot.y > n {
-                dot.y = n - (dot.y - n);
-            }
+fn fold(axis: u8, n: usize, dots: &mut [Pos2<usize>]) -> Result<()> {
+    for dot in dots.iter_mut() {
+        let coord = if axis == b'x' { &mut dot.x } else { &mut dot.y };
+
+        if *coord > n {
+            *coord = n.checked_sub(*coord - n).ok_or_else(|| {
+                eyre::eyre!(
+                    "folding along {}={n} moves dots past the edge",
+                    axis as char
+                )
+            })?;
         }
     }
-}
-
-fn print(dots: &[Pos2<usize>]) -> String {
-    let mut max_x = 0;
-    let mut max_y = 0;
-
-    for dot in dots {
-        max_x = max_x.max(dot.x);
-        max_y = max_y.max(dot.y);
-    }
-
-    let mut grid = vec![vec![b'.'; max_x + 1]; max_y + 1];
-    let mut ret = String::with_capacity(grid.len() + max_y);
-
-    for dot in dots {
-        grid[dot.y as usize][dot.x] = b'#';
-    }
 
-    for row in grid {
-        let _ = writeln!(ret, "{}", std::str::from_utf8(&row).unwrap());
-    }
-
-    ret.pop();
+    Ok(())
+}
 
-    ret
+fn print(dots: &[Pos2<usize>]) -> AsciiArt {
+    AsciiArt::from_points(dots.iter().map(|dot| (dot.x as i64, dot.y as i64)))
 }
@@ -1,20 +1,37 @@
 use std::collections::{BTreeMap, HashMap};
 
+use aoc_rust::util::parse::{ParseContext, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 14,
+    title: "Extended Polymerization",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let mut lines = input.lines();
+    let mut lines = input.lines().map(str::trim_end);
+
+    let template = lines.next().unwrap_or_default();
+
+    if template.len() < 2 {
+        return Err(ParseError::at(input, template, "expected at least two elements").into());
+    }
 
-    let template = lines.next().unwrap().as_bytes().to_owned();
+    let template = template.as_bytes().to_owned();
 
     let mut pairs = Pairs::new();
 
-    for line in lines {
-        let (left, right) = line.trim_end().split_once(" -> ").unwrap();
-        let left = left.as_bytes();
+    for line in lines.filter(|line| !line.is_empty()) {
+        let (left, right) =
+            line.split_once(" -> ")
+                .context_at(input, line, "expected `<pair> -> <element>`")?;
+
+        let (&[a, b], &[c]) = (left.as_bytes(), right.as_bytes()) else {
+            return Err(ParseError::at(input, line, "expected `AB -> C`").into());
+        };
 
-        pairs.insert((left[0], left[1]), right.as_bytes()[0]);
+        pairs.insert((a, b), c);
     }
 
     let p1 = solve(&template, &pairs, 10);
@@ -32,6 +49,10 @@ fn solve(template: &[u8], pairs: &Pairs, depth: u8) -> usize {
         recurse(*a, *b, depth, &pairs, &mut counts, &mut cache);
     }
 
+    if let Some(last) = template.last() {
+        *counts.entry(*last).or_default() += 1;
+    }
+
     let (min, max) = counts
         .into_iter()
         .map(|(_, v)| v)
@@ -1,10 +1,16 @@
+use aoc_rust::util::parse::ParseError;
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 16,
+    title: "Packet Decoder",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut binary: Vec<u8> = Vec::with_capacity(input.len() * 4);
 
-    for c in input.trim_end().chars() {
+    for (i, c) in input.trim_end().char_indices() {
         match c {
             '0' => binary.extend([0, 0, 0, 0]),
             '1' => binary.extend([0, 0, 0, 1]),
@@ -22,34 +28,34 @@ pub fn run(input: &str) -> Result<Solution> {
             'D' => binary.extend([1, 1, 0, 1]),
             'E' => binary.extend([1, 1, 1, 0]),
             'F' => binary.extend([1, 1, 1, 1]),
-            _ => unreachable!(),
+            _ => return Err(ParseError::at_offset(input, i, "expected a hex digit").into()),
         }
     }
 
     let mut p1 = 0;
     let mut len = 0;
 
-    let p2 = process_packet(&binary, &mut p1, &mut len);
+    let p2 = process_packet(&binary, &mut p1, &mut len)
+        .ok_or_else(|| ParseError::new("transmission ends within a packet"))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
 macro_rules! parse {
-    ($bytes:ident[*$len:ident..$end:literal] => $ty:ty) => {
-        $bytes[*$len..]
-            .iter()
-            .copied()
-            .inspect(|_| *$len += 1)
-            .take($end)
-            .fold(0, |v, byte| v * 2 + byte as $ty)
-    };
+    ($bytes:ident[*$len:ident..$end:literal] => $ty:ty) => {{
+        let bits = $bytes.get(*$len..*$len + $end)?;
+        *$len += $end;
+
+        bits.iter().fold(0, |v, &byte| v * 2 + byte as $ty)
+    }};
 }
 
-fn process_packet(bytes: &[u8], versions: &mut u32, len: &mut usize) -> u64 {
+/// Returns `None` if the bits end within a packet
+fn process_packet(bytes: &[u8], versions: &mut u32, len: &mut usize) -> Option<u64> {
     *versions += parse!(bytes[*len..3] => u32);
     let type_id = parse!(bytes[*len..3] => u8);
 
-    let process_subpackets = if bytes[*len] == 0 {
+    let process_subpackets = if *bytes.get(*len)? == 0 {
         process_subpackets_by_len
     } else {
         process_subpackets_by_count
@@ -68,10 +74,10 @@ fn process_packet(bytes: &[u8], versions: &mut u32, len: &mut usize) -> u64 {
     }
 }
 
-fn process_literal(bytes: &[u8], len: &mut usize) -> u64 {
+fn process_literal(bytes: &[u8], len: &mut usize) -> Option<u64> {
     let mut literal = 0;
 
-    for chunk in bytes[*len..].chunks_exact(5) {
+    for chunk in bytes.get(*len..)?.chunks_exact(5) {
         *len += 5;
 
         for &byte in &chunk[1..] {
@@ -79,11 +85,11 @@ fn process_literal(bytes: &[u8], len: &mut usize) -> u64 {
         }
 
         if chunk[0] == 0 {
-            break;
+            return Some(literal);
         }
     }
 
-    literal
+    None
 }
 
 fn process_subpackets_by_len(
@@ -91,17 +97,17 @@ fn process_subpackets_by_len(
     versions: &mut u32,
     len: &mut usize,
     fold_op: fn(u64, u64) -> u64,
-) -> u64 {
+) -> Option<u64> {
     *len += 1;
     let number = parse!(bytes[*len..15] => usize);
     let goal = *len + number;
-    let mut value = process_packet(bytes, versions, len);
+    let mut value = process_packet(bytes, versions, len)?;
 
     while *len < goal {
-        value = fold_op(value, process_packet(bytes, versions, len));
+        value = fold_op(value, process_packet(bytes, versions, len)?);
     }
 
-    value
+    Some(value)
 }
 
 fn process_subpackets_by_count(
@@ -109,14 +115,14 @@ fn process_subpackets_by_count(
     versions: &mut u32,
     len: &mut usize,
     fold_op: fn(u64, u64) -> u64,
-) -> u64 {
+) -> Option<u64> {
     *len += 1;
     let count = parse!(bytes[*len..11] => usize);
-    let value = process_packet(bytes, versions, len);
+    let value = process_packet(bytes, versions, len)?;
 
-    (0..count - 1)
-        .map(|_| process_packet(bytes, versions, len))
-        .fold(value, fold_op)
+    (1..count).try_fold(value, |value, _| {
+        process_packet(bytes, versions, len).map(|next| fold_op(value, next))
+    })
 }
 
 fn process_two_subpackets(
@@ -124,12 +130,12 @@ fn process_two_subpackets(
     versions: &mut u32,
     len: &mut usize,
     cmp_op: fn(u64, u64) -> u64,
-) -> u64 {
-    let len_type_id = bytes[*len];
+) -> Option<u64> {
+    let len_type_id = *bytes.get(*len)?;
     *len += 12 + 4 * (len_type_id == 0) as usize;
 
-    let a = process_packet(bytes, versions, len);
-    let b = process_packet(bytes, versions, len);
+    let a = process_packet(bytes, versions, len)?;
+    let b = process_packet(bytes, versions, len)?;
 
-    cmp_op(a, b)
+    Some(cmp_op(a, b))
 }
@@ -1,15 +1,29 @@
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 17,
+    title: "Trick Shot",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let (x, y) = &input[13..].trim_end().split_once(", ").unwrap();
-    let (x_min, x_max) = x[2..].split_once("..").unwrap();
-    let (y_min, y_max) = y[2..].split_once("..").unwrap();
+    let line = input.lines().next().unwrap_or_default().trim_end();
+
+    let (x, y) = line
+        .strip_prefix("target area: x=")
+        .and_then(|area| area.split_once(", y="))
+        .context_at(input, line, "expected `target area: x=<range>, y=<range>`")?;
 
-    let x_min: i32 = x_min.parse()?;
-    let x_max: i32 = x_max.parse()?;
-    let y_min: i32 = y_min.parse()?;
-    let y_max: i32 = y_max.parse()?;
+    let (x_min, x_max) = parse_range(input, x)?;
+    let (y_min, y_max) = parse_range(input, y)?;
+
+    // Probes are launched from the origin towards the bottom right
+    if x_min <= 0 || y_max >= 0 {
+        return Err(
+            ParseError::at(input, line, "target must be right of and below the origin").into(),
+        );
+    }
 
     let mut p1 = 0;
     let mut p2 = 0;
@@ -46,3 +60,18 @@ pub fn run(input: &str) -> Result<Solution> {
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
+
+fn parse_range(input: &str, range: &str) -> Result<(i32, i32), ParseError> {
+    let (min, max) = range
+        .split_once("..")
+        .context_at(input, range, "expected `<min>..<max>`")?;
+
+    let min = parse_at(input, min)?;
+    let max = parse_at(input, max)?;
+
+    if min > max {
+        return Err(ParseError::at(input, range, "range is empty"));
+    }
+
+    Ok((min, max))
+}
@@ -1,15 +1,21 @@
-use std::{fmt, mem::transmute, ops::Add, str::Chars};
+use std::{fmt, mem::transmute, ops::Add};
 
+use aoc_rust::util::parse::ParseError;
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 18,
+    title: "Snailfish",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut elems: Vec<Elem> = Vec::new();
     let mut sum = Elem::Number(0);
     let mut p2 = 0;
 
     for line in input.lines() {
-        let elem = Elem::from_str(line.trim_end());
+        let elem = Elem::parse(input, line.trim_end())?;
         sum = sum + elem.clone();
 
         for prev in &elems {
@@ -63,24 +69,62 @@ impl fmt::Debug for ToExplode<'_> {
 }
 
 impl Elem {
-    fn from_str(s: &str) -> Self {
-        let mut chars = s.chars();
-
-        Self::from_chars(&mut chars)
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let mut idx = 0;
+        let elem = Self::parse_nested(input, line, &mut idx, 0)?;
+
+        match line.get(idx..) {
+            Some("") => Ok(elem),
+            rest => Err(ParseError::at(
+                input,
+                rest.unwrap_or(line),
+                "expected end of line",
+            )),
+        }
     }
 
-    fn from_chars(chars: &mut Chars<'_>) -> Self {
-        match chars.next() {
-            Some('[') => {
-                let lhs = Self::from_chars(chars);
-                assert_eq!(chars.next(), Some(','));
-                let rhs = Self::from_chars(chars);
-                assert_eq!(chars.next(), Some(']'));
+    fn parse_nested(
+        input: &str,
+        line: &str,
+        idx: &mut usize,
+        depth: u8,
+    ) -> Result<Self, ParseError> {
+        let rest = &line[*idx..];
+
+        let expect = |idx: &mut usize, byte: u8| {
+            if line.as_bytes().get(*idx) == Some(&byte) {
+                *idx += 1;
+
+                Ok(())
+            } else {
+                let msg = format!("expected `{}`", byte as char);
+
+                Err(ParseError::at(input, &line[*idx..], msg))
+            }
+        };
+
+        match rest.as_bytes().first() {
+            // Reducing relies on explosions never being nested
+            Some(b'[') if depth == 4 => Err(ParseError::at(
+                input,
+                rest,
+                "pairs must be nested in at most four pairs",
+            )),
+            Some(b'[') => {
+                expect(idx, b'[')?;
+                let lhs = Self::parse_nested(input, line, idx, depth + 1)?;
+                expect(idx, b',')?;
+                let rhs = Self::parse_nested(input, line, idx, depth + 1)?;
+                expect(idx, b']')?;
+
+                Ok(Elem::Pair(Box::new((lhs, rhs))))
+            }
+            Some(&n @ b'0'..=b'9') => {
+                *idx += 1;
 
-                Elem::Pair(Box::new((lhs, rhs)))
+                Ok(Elem::Number((n & 0x0F) as u32))
             }
-            Some(n @ '0'..='9') => Elem::Number(n as u32 & 0x0F),
-            c => unreachable!("expected [ or num, got {:?}", c),
+            _ => Err(ParseError::at(input, rest, "expected `[` or a digit")),
         }
     }
 
@@ -5,49 +5,31 @@ use std::{
     slice::Iter,
 };
 
+use aoc_rust::grid::Pos3;
+use aoc_rust::util::parse::{parse_at, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
-use crate::util::Pos3;
+aoc_rust::register_day! {
+    day: 19,
+    title: "Beacon Scanner",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
-    let mut lines = input.lines();
-
-    let mut scanner_queue = VecDeque::new();
-    let mut id = 0;
-
-    while lines.next().is_some() {
-        let mut scanner = Scanner::new(id);
-        id += 1;
-
-        while let Some(line) = lines.next() {
-            if line.len() <= 1 {
-                break;
-            }
-
-            let mut split = line
-                .trim_end()
-                .split(',')
-                .map(str::parse)
-                .map(Result::unwrap);
-
-            let pos = Pos3 {
-                x: split.next().unwrap(),
-                y: split.next().unwrap(),
-                z: split.next().unwrap(),
-            };
-
-            scanner.reports.push(pos);
-        }
-
-        scanner_queue.push_back(scanner);
-    }
+    let mut scanner_queue = parse_scanners(input)?;
 
     let mut corrected = Vec::with_capacity(scanner_queue.len());
-    corrected.push(scanner_queue.pop_front().unwrap());
+    corrected.extend(scanner_queue.pop_front());
     let mut compared = HashSet::new();
 
+    // Amount of scanners that were re-queued since the last correction
+    let mut stale = 0;
+
     'outer: while let Some(to_correct) = scanner_queue.pop_front() {
+        if stale > scanner_queue.len() {
+            eyre::bail!("scanner {} does not overlap with any other", to_correct.id);
+        }
+
         for correct in &corrected {
             if !compared.insert((correct.id, to_correct.id)) {
                 continue;
@@ -60,6 +42,7 @@ pub fn run(input: &str) -> Result<Solution> {
                     adjusted.apply_offset(offset);
                     adjusted.pos = offset;
                     corrected.push(adjusted);
+                    stale = 0;
 
                     continue 'outer;
                 }
@@ -82,6 +65,7 @@ pub fn run(input: &str) -> Result<Solution> {
         }
 
         scanner_queue.push_back(to_correct);
+        stale += 1;
     }
 
     let p1 = part1(&corrected);
@@ -90,6 +74,48 @@ pub fn run(input: &str) -> Result<Solution> {
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
+fn parse_scanners(input: &str) -> Result<VecDeque<Scanner>, ParseError> {
+    let mut lines = input.lines().map(str::trim_end);
+    let mut scanners = VecDeque::new();
+
+    while let Some(header) = lines.next() {
+        if header.is_empty() {
+            continue;
+        } else if !header.starts_with("--- scanner") {
+            return Err(ParseError::at(
+                input,
+                header,
+                "expected `--- scanner <id> ---`",
+            ));
+        }
+
+        let id = u8::try_from(scanners.len())
+            .map_err(|_| ParseError::at(input, header, "at most 256 scanners are supported"))?;
+
+        let mut scanner = Scanner::new(id);
+
+        for line in lines.by_ref().take_while(|line| !line.is_empty()) {
+            let mut split = line.split(',').map(|n| parse_at(input, n));
+
+            let (Some(x), Some(y), Some(z), None) =
+                (split.next(), split.next(), split.next(), split.next())
+            else {
+                return Err(ParseError::at(input, line, "expected `x,y,z`"));
+            };
+
+            scanner.reports.push(Pos3 {
+                x: x?,
+                y: y?,
+                z: z?,
+            });
+        }
+
+        scanners.push_back(scanner);
+    }
+
+    Ok(scanners)
+}
+
 fn part1(scanners: &[Scanner]) -> usize {
     let mut beacons: HashSet<&Pos3<i32>> = HashSet::new();
 
@@ -103,9 +129,9 @@ fn part1(scanners: &[Scanner]) -> usize {
 fn part2(scanners: &[Scanner]) -> i32 {
     let mut max = 0;
 
-    for i in 0..scanners.len() - 1 {
+    for i in 0..scanners.len().saturating_sub(1) {
         for j in i + 1..scanners.len() {
-            let dist = scanners[i].pos.manhatten_dist(&scanners[j].pos);
+            let dist = scanners[i].pos.manhattan_dist(&scanners[j].pos);
             max = max.max(dist);
         }
     }
@@ -1,77 +1,86 @@
 use std::{collections::HashMap, mem};
 
+use aoc_rust::grid::Grid;
+use aoc_rust::util::parse::ParseError;
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 20,
+    title: "Trench Map",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let mut lines = input.lines();
+    let (algorithm, image) = input
+        .split_once("\n\n")
+        .or_else(|| input.split_once("\r\n\r\n"))
+        .ok_or_else(|| ParseError::new("expected blank line after the algorithm"))?;
 
-    let algorithm: Vec<_> = lines
-        .next()
-        .unwrap()
-        .trim_end()
-        .as_bytes()
-        .iter()
-        .map(parse_byte)
-        .collect();
+    let algorithm = algorithm.trim_end();
 
-    lines.next();
+    if algorithm.len() != 512 {
+        return Err(ParseError::at(input, algorithm, "algorithm must have 512 pixels").into());
+    }
 
-    let mut grid = Vec::new();
+    let algorithm = Grid::parse_section(input, algorithm, "`#` or `.`", parse_byte)?;
+    let grid = Grid::parse_section(input, image, "`#` or `.`", parse_byte)?;
 
-    let line = lines.next().unwrap();
-    grid.extend(line.trim_end().as_bytes().iter().map(parse_byte));
-    let w = line.len() - 1;
+    let mut map = HashMap::with_capacity(grid.len());
 
-    for line in lines {
-        grid.extend(line.trim_end().as_bytes().iter().map(parse_byte));
+    for pos in grid.positions() {
+        map.insert((pos.x as isize, pos.y as isize), grid[pos]);
     }
 
-    let mut map = HashMap::with_capacity(w * w);
-
-    for (x, chunk) in grid.chunks_exact(w).enumerate() {
-        for (y, &byte) in chunk.iter().enumerate() {
-            map.insert((y as isize, x as isize), byte);
-        }
-    }
+    let size = (grid.width() as isize, grid.height() as isize);
+    let algorithm: Vec<_> = algorithm.iter().copied().collect();
 
     let mut next_map = HashMap::with_capacity(map.len());
     let mut p1_map = map.clone();
-    let p1 = solve(&mut p1_map, &mut next_map, &algorithm, w as isize, 2);
-    let p2 = solve(&mut map, &mut next_map, &algorithm, w as isize, 50);
+    let p1 = solve(&mut p1_map, &mut next_map, &algorithm, size, 2);
+    let p2 = solve(&mut map, &mut next_map, &algorithm, size, 50);
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
 type Map = HashMap<(isize, isize), u8>;
 
-fn solve(map: &mut Map, next_map: &mut Map, algorithm: &[u8], w: isize, iterations: u8) -> usize {
-    for i in 0..iterations {
-        for x in -(i as isize) - 1..=w + i as isize {
-            for y in -(i as isize) - 1..=w + i as isize {
+fn solve(
+    map: &mut Map,
+    next_map: &mut Map,
+    algorithm: &[u8],
+    (w, h): (isize, isize),
+    iterations: u8,
+) -> usize {
+    // Value of all pixels in the infinite area outside of the map
+    let mut background = 0;
+
+    for i in 0..iterations as isize {
+        for x in -i - 1..=w + i {
+            for y in -i - 1..=h + i {
                 let pos = (x, y);
 
                 let idx = OFFSETS
                     .iter()
                     .map(|(x, y)| (pos.0 + x, pos.1 + y))
-                    .map(|pos| map.get(&pos).map_or(i as u8 % 2, |v| *v))
+                    .map(|pos| map.get(&pos).map_or(background, |v| *v))
                     .fold(0, |binary, bit| binary * 2 + bit as usize);
 
                 next_map.insert(pos, algorithm[idx]);
             }
         }
 
+        background = algorithm[background as usize * 511];
         mem::swap(map, next_map);
     }
 
     map.values().filter(|&value| *value == 1).count()
 }
 
-fn parse_byte(byte: &u8) -> u8 {
-    match *byte {
-        b'.' => 0,
-        b'#' => 1,
-        _ => unreachable!(),
+fn parse_byte(byte: u8) -> Option<u8> {
+    match byte {
+        b'.' => Some(0),
+        b'#' => Some(1),
+        _ => None,
     }
 }
 
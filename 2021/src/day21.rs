@@ -1,18 +1,19 @@
 use std::collections::HashMap;
 
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
-pub fn run(input: &str) -> Result<Solution> {
-    let mut lines = input
-        .lines()
-        .map(|line| *line.trim_end().as_bytes().last().unwrap() & 0x0F);
+aoc_rust::register_day! {
+    day: 21,
+    title: "Dirac Dice",
+}
 
-    let pos1 = lines.next().unwrap();
-    let pos2 = lines.next().unwrap();
+pub fn run(input: &str) -> Result<Solution> {
+    let mut lines = input.lines().map(str::trim_end);
 
-    let player1 = Player::new(pos1);
-    let player2 = Player::new(pos2);
+    let player1 = Player::parse(input, lines.next())?;
+    let player2 = Player::parse(input, lines.next())?;
 
     let p1 = part1(player1, player2);
     let p2 = part2(player1, player2);
@@ -79,10 +80,22 @@ struct Player {
 }
 
 impl Player {
-    fn new(pos: u8) -> Self {
-        Self {
-            pos: pos as u32,
-            score: 0,
+    fn parse(input: &str, line: Option<&str>) -> Result<Self, ParseError> {
+        let line = line.ok_or_else(|| ParseError::new("expected two players"))?;
+
+        let (_, pos) = line.split_once(" starting position: ").context_at(
+            input,
+            line,
+            "expected `Player <n> starting position: <pos>`",
+        )?;
+
+        match parse_at(input, pos)? {
+            pos @ 1..=10 => Ok(Self { pos, score: 0 }),
+            _ => Err(ParseError::at(
+                input,
+                pos,
+                "position must be between 1 and 10",
+            )),
         }
     }
 
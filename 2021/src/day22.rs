@@ -3,9 +3,15 @@ use std::{
     mem,
 };
 
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 22,
+    title: "Reactor Reboot",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut steps = Vec::new();
 
@@ -14,7 +20,7 @@ pub fn run(input: &str) -> Result<Solution> {
     let mut z_values = HashSet::new();
 
     for line in input.lines() {
-        let step = Cube::from_str(line.trim_end());
+        let step = Cube::parse(input, line.trim_end())?;
 
         x_values.insert(step.x_min);
         x_values.insert(step.x_max);
@@ -116,10 +122,9 @@ fn process_axis(values: HashSet<i64>) -> (HashMap<i64, Int>, Vec<i64>) {
         })
         .collect();
 
-    let last = *values.last().unwrap();
-    let idx = values.len() - 1;
-
-    indices.insert(last, idx as Int);
+    if let Some(&last) = values.last() {
+        indices.insert(last, (values.len() - 1) as Int);
+    }
 
     (indices, lengths)
 }
@@ -136,31 +141,42 @@ struct Cube {
 }
 
 impl Cube {
-    fn from_str(line: &str) -> Self {
-        let (on, pos) = line.split_once(' ').unwrap();
-        let on = on == "on";
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let (on, pos) =
+            line.split_once(' ')
+                .context_at(input, line, "expected `<on|off> x=..,y=..,z=..`")?;
+
+        let on = match on {
+            "on" => true,
+            "off" => false,
+            _ => return Err(ParseError::at(input, on, "expected `on` or `off`")),
+        };
 
-        let mut split = pos
-            .split(',')
-            .map(|s| s.split('=').last())
-            .flatten()
-            .map(|s| s.split_once(".."))
-            .flatten();
+        let mut split = pos.split(',');
 
-        let x_range = split.next().unwrap();
-        let y_range = split.next().unwrap();
-        let z_range = split.next().unwrap();
+        let mut range = |axis: &str| {
+            let range = split.next().unwrap_or(&pos[pos.len()..]);
 
-        let x_min = x_range.0.parse().unwrap();
-        let x_max = x_range.1.parse::<i64>().unwrap() + 1;
+            let (min, max) = range
+                .strip_prefix(axis)
+                .and_then(|range| range.split_once(".."))
+                .context_at(input, range, format!("expected `{axis}<min>..<max>`"))?;
 
-        let y_min = y_range.0.parse().unwrap();
-        let y_max = y_range.1.parse::<i64>().unwrap() + 1;
+            let min = parse_at(input, min)?;
+            let max: i64 = parse_at(input, max)?;
 
-        let z_min = z_range.0.parse().unwrap();
-        let z_max = z_range.1.parse::<i64>().unwrap() + 1;
+            if min > max {
+                return Err(ParseError::at(input, range, "range is empty"));
+            }
+
+            Ok((min, max + 1))
+        };
+
+        let (x_min, x_max) = range("x=")?;
+        let (y_min, y_max) = range("y=")?;
+        let (z_min, z_max) = range("z=")?;
 
-        Self {
+        Ok(Self {
             on,
             x_min,
             x_max,
@@ -168,6 +184,6 @@ impl Cube {
             y_max,
             z_min,
             z_max,
-        }
+        })
     }
 }
@@ -1,120 +1,70 @@
 use std::{
-    cmp::Ordering,
-    collections::BinaryHeap,
     fmt,
     hash::{Hash, Hasher},
 };
 
+use aoc_rust::search::{self, Visited};
+use aoc_rust::util::parse::ParseError;
 use aoc_rust::Solution;
 use eyre::Result;
 use rustc_hash::FxHashMap as HashMap;
 
-pub fn run(input: &str) -> Result<Solution> {
-    let p1 = part1(input)?;
-    let p2 = part2(input)?;
-
-    Ok(Solution::new().part1(p1).part2(p2))
-}
-
-fn part1(input: &str) -> Result<u32> {
-    Ok(solve(Burrow::<2>::from_input(input)?))
-}
-
-fn part2(input: &str) -> Result<u32> {
-    Ok(solve(Burrow::<4>::from_input(input)?))
+aoc_rust::register_day! {
+    day: 23,
+    title: "Amphipod",
 }
 
-fn solve<const N: usize>(burrow: Burrow<N>) -> u32 {
-    let mut dists = Distances::default();
-    dists.set(&burrow, 0);
-
-    let mut heap = BinaryHeap::new();
-    heap.push(State::new(burrow));
-
-    let mut successor_buf = Vec::new();
-
-    while let Some(State { burrow, path }) = heap.pop() {
-        let energy = burrow.energy();
+pub fn run(input: &str) -> Result<Solution> {
+    let rows = parse_rows(input)?;
 
-        if burrow.is_sorted() {
-            // println!("Path:");
-            // for burrow in path {
-            //     println!("{}\n---", burrow);
-            // }
+    let p1 = solve(Burrow::<2>::from_rows(rows));
+    let p2 = solve(Burrow::<4>::from_rows(rows));
 
-            return energy;
-        } else if energy > dists.get(&burrow) {
-            continue;
-        }
+    let (Some(p1), Some(p2)) = (p1, p2) else {
+        eyre::bail!("amphipods can not be organized");
+    };
 
-        for successor in burrow.successors(&mut successor_buf).drain(..) {
-            let energy = successor.energy();
+    Ok(Solution::new().part1(p1).part2(p2))
+}
 
-            if energy < dists.get(&successor) {
-                dists.set(&successor, energy);
+/// The two rows of amphipods in the rooms
+fn parse_rows(input: &str) -> Result<[[Amphipod; 4]; 2], ParseError> {
+    let mut lines = input.lines().skip(2);
+    let mut rows = [[Amphipod::A; 4]; 2];
 
-                let mut path = path.clone();
-                path.push(successor.clone());
+    for row in rows.iter_mut() {
+        let line = lines.next().unwrap_or_default();
 
-                let state = State {
-                    burrow: successor,
-                    path,
-                };
+        for (amphipod, i) in row.iter_mut().zip([3, 5, 7, 9]) {
+            *amphipod = line
+                .as_bytes()
+                .get(i)
+                .and_then(|&byte| Amphipod::from_byte(byte))
+                .ok_or_else(|| {
+                    let span = line.get(i..).unwrap_or(line);
 
-                heap.push(state);
-            }
+                    ParseError::at(input, span, "expected amphipod `A`, `B`, `C`, or `D`")
+                })?;
         }
     }
 
-    unreachable!()
-}
-
-#[derive(Eq, PartialEq)]
-struct State<const N: usize> {
-    burrow: Burrow<N>,
-    path: Vec<Burrow<N>>,
+    Ok(rows)
 }
 
-impl<const N: usize> State<N> {
-    fn new(burrow: Burrow<N>) -> Self {
-        Self {
-            path: vec![burrow.clone()],
-            burrow,
-        }
-    }
-}
+fn solve<const N: usize>(burrow: Burrow<N>) -> Option<u32> {
+    let successors = |burrow: &Burrow<N>| {
+        let energy = burrow.energy();
 
-impl<const N: usize> Ord for State<N> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.burrow.cmp(&other.burrow)
-    }
-}
+        burrow.successors().into_iter().map(move |successor| {
+            let cost = successor.energy() - energy;
 
-impl<const N: usize> PartialOrd for State<N> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
+            (successor, cost)
+        })
+    };
 
-#[derive(Default)]
-struct Distances<const N: usize>(HashMap<Burrow<N>, u32>);
+    let visited = Visited::new(Burrow::clone, HashMap::default());
 
-impl<const N: usize> Distances<N> {
-    fn get(&mut self, burrow: &Burrow<N>) -> u32 {
-        if let Some(dist) = self.0.get(burrow) {
-            *dist
-        } else {
-            *self.0.entry(burrow.to_owned()).or_insert(u32::MAX)
-        }
-    }
-
-    fn set(&mut self, burrow: &Burrow<N>, dist: u32) {
-        if let Some(value) = self.0.get_mut(burrow) {
-            *value = dist;
-        } else {
-            self.0.insert(burrow.to_owned(), dist);
-        }
-    }
+    search::dijkstra_with(burrow, successors, Burrow::is_sorted, visited).map(|path| path.cost)
 }
 
 #[derive(Clone, Eq)]
@@ -127,67 +77,39 @@ struct Burrow<const N: usize> {
 static ROOM_OPENINGS: [usize; 4] = [2, 4, 6, 8];
 
 impl Burrow<2> {
-    fn from_input(input: &str) -> Result<Self> {
-        let mut lines = input.lines();
+    fn from_rows(rows: [[Amphipod; 4]; 2]) -> Self {
         let mut burrow = Burrow::new();
 
-        lines.next();
-        lines.next();
-
-        let line = lines.next().unwrap();
-
-        burrow.rooms[0].positions[0] = Field::from_byte(line.as_bytes()[3]);
-        burrow.rooms[1].positions[0] = Field::from_byte(line.as_bytes()[5]);
-        burrow.rooms[2].positions[0] = Field::from_byte(line.as_bytes()[7]);
-        burrow.rooms[3].positions[0] = Field::from_byte(line.as_bytes()[9]);
-
-        let line = lines.next().unwrap();
-
-        burrow.rooms[0].positions[1] = Field::from_byte(line.as_bytes()[3]);
-        burrow.rooms[1].positions[1] = Field::from_byte(line.as_bytes()[5]);
-        burrow.rooms[2].positions[1] = Field::from_byte(line.as_bytes()[7]);
-        burrow.rooms[3].positions[1] = Field::from_byte(line.as_bytes()[9]);
+        for (room, [top, bot]) in burrow.rooms.iter_mut().zip(transpose(rows)) {
+            room.positions = [Field::new(top), Field::new(bot)];
+        }
 
-        Ok(burrow)
+        burrow
     }
 }
 
 impl Burrow<4> {
-    fn from_input(input: &str) -> Result<Self> {
-        let mut lines = input.lines();
-        let mut burrow = Burrow::new();
-
-        lines.next();
-        lines.next();
-
-        let line = lines.next().unwrap();
-
-        burrow.rooms[0].positions[0] = Field::from_byte(line.as_bytes()[3]);
-        burrow.rooms[1].positions[0] = Field::from_byte(line.as_bytes()[5]);
-        burrow.rooms[2].positions[0] = Field::from_byte(line.as_bytes()[7]);
-        burrow.rooms[3].positions[0] = Field::from_byte(line.as_bytes()[9]);
-
-        let line = lines.next().unwrap();
+    fn from_rows(rows: [[Amphipod; 4]; 2]) -> Self {
+        use Amphipod::{A, B, C, D};
 
-        burrow.rooms[0].positions[3] = Field::from_byte(line.as_bytes()[3]);
-        burrow.rooms[1].positions[3] = Field::from_byte(line.as_bytes()[5]);
-        burrow.rooms[2].positions[3] = Field::from_byte(line.as_bytes()[7]);
-        burrow.rooms[3].positions[3] = Field::from_byte(line.as_bytes()[9]);
+        // Folded out part of the diagram
+        const MIDDLE: [[Amphipod; 2]; 4] = [[D, D], [C, B], [B, A], [A, C]];
 
-        burrow.rooms[0].positions[1] = Field::new(Amphipod::D);
-        burrow.rooms[1].positions[1] = Field::new(Amphipod::C);
-        burrow.rooms[2].positions[1] = Field::new(Amphipod::B);
-        burrow.rooms[3].positions[1] = Field::new(Amphipod::A);
+        let mut burrow = Burrow::new();
+        let rooms = burrow.rooms.iter_mut().zip(transpose(rows)).zip(MIDDLE);
 
-        burrow.rooms[0].positions[2] = Field::new(Amphipod::D);
-        burrow.rooms[1].positions[2] = Field::new(Amphipod::B);
-        burrow.rooms[2].positions[2] = Field::new(Amphipod::A);
-        burrow.rooms[3].positions[2] = Field::new(Amphipod::C);
+        for ((room, [top, bot]), [mid1, mid2]) in rooms {
+            room.positions = [top, mid1, mid2, bot].map(Field::new);
+        }
 
-        Ok(burrow)
+        burrow
     }
 }
 
+fn transpose(rows: [[Amphipod; 4]; 2]) -> [[Amphipod; 2]; 4] {
+    [0, 1, 2, 3].map(|i| [rows[0][i], rows[1][i]])
+}
+
 impl<const N: usize> Burrow<N> {
     fn new() -> Self {
         Self {
@@ -218,7 +140,9 @@ impl<const N: usize> Burrow<N> {
         &mut self.rooms[kind as usize]
     }
 
-    fn successors<'s>(&self, successors: &'s mut Vec<Self>) -> &'s mut Vec<Self> {
+    fn successors(&self) -> Vec<Self> {
+        let mut successors = Vec::new();
+
         // From room to hallway
         for (room_idx, room) in self.rooms.iter().enumerate() {
             'room: for i in 0..room.positions.len() {
@@ -329,18 +253,6 @@ impl<const N: usize> Hash for Burrow<N> {
     }
 }
 
-impl<const N: usize> Ord for Burrow<N> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.energy().cmp(&self.energy())
-    }
-}
-
-impl<const N: usize> PartialOrd for Burrow<N> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
 #[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
 struct Field {
     amphipod: Option<Amphipod>,
@@ -353,10 +265,6 @@ impl Field {
         }
     }
 
-    fn from_byte(byte: u8) -> Self {
-        Self::new(Amphipod::from_byte(byte))
-    }
-
     fn map_or<U, F: FnOnce(&Amphipod) -> U>(&self, default: U, f: F) -> U {
         self.amphipod.as_ref().map_or(default, f)
     }
@@ -438,13 +346,13 @@ enum Amphipod {
 }
 
 impl Amphipod {
-    fn from_byte(byte: u8) -> Self {
+    fn from_byte(byte: u8) -> Option<Self> {
         match byte {
-            b'A' => Self::A,
-            b'B' => Self::B,
-            b'C' => Self::C,
-            b'D' => Self::D,
-            _ => unreachable!(),
+            b'A' => Some(Self::A),
+            b'B' => Some(Self::B),
+            b'C' => Some(Self::C),
+            b'D' => Some(Self::D),
+            _ => None,
         }
     }
 
@@ -1,44 +1,181 @@
+use aoc_rust::machine::{self, Exit, Instruction, Machine, Operand, Step};
+use aoc_rust::util::parse::ParseError;
 use aoc_rust::Solution;
 use eyre::Result;
 
-use crate::util::Parse;
+aoc_rust::register_day! {
+    day: 24,
+    title: "Arithmetic Logic Unit",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
-    let values = parse_values(input)?;
+    let program = parse_program(input)?;
+    let values = block_values(input, &program)?;
     let conditions = calculate_conditions(&values);
 
+    if let Some(&(w, r, _)) = conditions.iter().find(|(.., offset)| offset.abs() > 8) {
+        eyre::bail!("digits {r} and {w} can not satisfy their condition");
+    }
+
     let p1 = part1(&conditions);
     let p2 = part2(&conditions);
 
+    let mut machine = Machine::new(program, Alu::default());
+
+    for model in [p1, p2] {
+        if !is_valid(&mut machine, model) {
+            eyre::bail!("the program rejects model number {model}");
+        }
+    }
+
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn parse_values(input: &str) -> Result<Vec<(i64, i64)>> {
-    let mut lines = input.lines();
+const REGISTERS: &str = "wxyz";
+const W: u8 = 0;
+const X: u8 = 1;
+const Y: u8 = 2;
+const Z: u8 = 3;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Op {
+    Inp(u8),
+    Add(u8, Operand),
+    Mul(u8, Operand),
+    Div(u8, Operand),
+    Mod(u8, Operand),
+    Eql(u8, Operand),
+}
 
-    let mut values = Vec::with_capacity(14);
+#[derive(Clone, Default)]
+struct Alu {
+    regs: [i64; 4],
+    /// Remaining digits of the model number, the next one being last
+    digits: Vec<i64>,
+    /// Whether an instruction was invalid or there were too few digits
+    crashed: bool,
+}
 
-    for _ in 0..14 {
-        for _ in 0..5 {
-            lines.next();
-        }
+impl Instruction for Op {
+    type State = Alu;
+    type Output = ();
 
-        let add_x: i64 = lines.next().unwrap().trim_end().as_bytes()[6..].parse();
+    fn execute(&self, alu: &mut Alu) -> Step<Self> {
+        let regs = &mut alu.regs;
 
-        for _ in 0..9 {
-            lines.next();
-        }
+        let (dst, value) = match *self {
+            Op::Inp(dst) => (dst, alu.digits.pop()),
+            Op::Add(dst, b) => (dst, Some(regs[dst as usize] + b.get(regs))),
+            Op::Mul(dst, b) => (dst, Some(regs[dst as usize] * b.get(regs))),
+            Op::Div(dst, b) => (dst, regs[dst as usize].checked_div(b.get(regs))),
+            Op::Mod(dst, b) => match (regs[dst as usize], b.get(regs)) {
+                (a, b) if a >= 0 && b > 0 => (dst, Some(a % b)),
+                _ => (dst, None),
+            },
+            Op::Eql(dst, b) => (dst, Some((regs[dst as usize] == b.get(regs)) as i64)),
+        };
 
-        let add_y: i64 = lines.next().unwrap().trim_end().as_bytes()[6..].parse();
+        let Some(value) = value else {
+            alu.crashed = true;
 
-        for _ in 0..2 {
-            lines.next();
+            return Step::Halt;
+        };
+
+        regs[dst as usize] = value;
+
+        Step::Next
+    }
+}
+
+fn parse_program(input: &str) -> Result<Vec<Op>, ParseError> {
+    machine::parse_program(input, |mnemonic, words| {
+        let dst = words.register(REGISTERS)?;
+
+        if mnemonic == "inp" {
+            return Ok(Op::Inp(dst));
         }
 
-        values.push((add_x, add_y));
+        let b = words.operand(REGISTERS)?;
+
+        let op = match mnemonic {
+            "add" => Op::Add(dst, b),
+            "mul" => Op::Mul(dst, b),
+            "div" => Op::Div(dst, b),
+            "mod" => Op::Mod(dst, b),
+            "eql" => Op::Eql(dst, b),
+            _ => return Err(words.unknown(mnemonic)),
+        };
+
+        Ok(op)
+    })
+}
+
+/// The program consists of 14 blocks of 18 instructions that only
+/// differ in the values added to `x` and `y`
+fn block_values(input: &str, program: &[Op]) -> Result<Vec<(i64, i64)>, ParseError> {
+    let lines: Vec<_> = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if let Some(line) = lines.get(14 * 18) {
+        return Err(ParseError::at(
+            input,
+            line,
+            "expected 14 blocks of 18 instructions",
+        ));
+    } else if program.len() != 14 * 18 {
+        return Err(ParseError::new("expected 14 blocks of 18 instructions"));
     }
 
-    Ok(values)
+    program
+        .chunks_exact(18)
+        .zip(lines.chunks_exact(18))
+        .map(|(block, lines)| {
+            let Op::Inp(W) = block[0] else {
+                return Err(ParseError::at(input, lines[0], "expected `inp w`"));
+            };
+
+            let Op::Add(X, Operand::Value(add_x)) = block[5] else {
+                return Err(ParseError::at(
+                    input,
+                    lines[5],
+                    "expected `add x` with a value",
+                ));
+            };
+
+            let Op::Add(Y, Operand::Value(add_y)) = block[15] else {
+                return Err(ParseError::at(
+                    input,
+                    lines[15],
+                    "expected `add y` with a value",
+                ));
+            };
+
+            Ok((add_x, add_y))
+        })
+        .collect()
+}
+
+/// Run the program on the model number's digits and check that `z` ends up
+/// as zero.
+fn is_valid(machine: &mut Machine<Op>, model: i64) -> bool {
+    let mut digits = Vec::with_capacity(14);
+    let mut n = model;
+
+    while n > 0 {
+        digits.push(n % 10);
+        n /= 10;
+    }
+
+    machine.reset(Alu {
+        regs: [0; 4],
+        digits,
+        crashed: false,
+    });
+
+    machine.run() == Exit::Halted && !machine.state.crashed && machine.state.regs[Z as usize] == 0
 }
 
 fn calculate_conditions(values: &[(i64, i64)]) -> Vec<(u32, u32, i64)> {
@@ -1,26 +1,17 @@
 use std::fmt;
 
+use aoc_rust::grid::Grid;
 use aoc_rust::Solution;
 use eyre::Result;
 
-use crate::util::Matrix;
+aoc_rust::register_day! {
+    day: 25,
+    title: "Sea Cucumber",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
-    let mut lines = input.lines();
-    let mut grid = Vec::new();
-
-    let line = lines.next().unwrap().trim_end().as_bytes();
-    let w = line.len();
-    grid.extend(line.iter().map(Field::from_byte));
-
-    for line in lines {
-        let row = line.trim_end().as_bytes().iter().map(Field::from_byte);
-
-        grid.extend(row);
-    }
-
-    let mut matrix = Matrix::from_vec(grid, w);
-    let mut next_matrix = Matrix::new(w, matrix.height());
+    let mut matrix = Grid::parse(input, "`>`, `v`, or `.`", Field::from_byte)?;
+    let mut next_matrix = Grid::new(matrix.width(), matrix.height())?;
     let mut step = 0;
 
     let w = matrix.width();
@@ -114,12 +105,12 @@ enum Field {
 }
 
 impl Field {
-    fn from_byte(byte: &u8) -> Self {
+    fn from_byte(byte: u8) -> Option<Self> {
         match byte {
-            b'>' => Self::Right,
-            b'v' => Self::Down,
-            b'.' => Self::Free,
-            _ => unreachable!("{}", *byte as char),
+            b'>' => Some(Self::Right),
+            b'v' => Some(Self::Down),
+            b'.' => Some(Self::Free),
+            _ => None,
         }
     }
 }
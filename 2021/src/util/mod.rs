@@ -1,6 +0,0 @@
-mod matrix;
-mod parse;
-mod pos2;
-mod pos3;
-
-pub use self::{matrix::*, parse::*, pos2::*, pos3::*};
@@ -2,6 +2,11 @@ use std::iter;
 
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 1,
+    title: "Calorie Counting",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut max = 0;
     let mut mid = 0;
@@ -24,7 +29,7 @@ pub fn run(input: &str) -> Result<Solution> {
 
             sum = 0;
         } else {
-            sum += line.parse::<u32>().map_err(|_| eyre!("invalid number"))?;
+            sum += parse_at::<u32>(input, line)?;
         }
     }
 
@@ -3,14 +3,40 @@ use core_simd::simd::{u8x4, u8x8, SimdUint};
 
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 2,
+    title: "Rock Paper Scissors",
+    variants: [
+        "const-lookup" => run_const_lookup,
+        #[cfg(feature = "nightly")]
+        "simd" => run_simd,
+        #[cfg(feature = "nightly")]
+        "simd-rayon" => run_simd_rayon,
+    ],
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.as_bytes();
+    run_with(input, part1_naive)
+}
+
+pub fn run_const_lookup(input: &str) -> Result<Solution> {
+    run_with(input, |input| Ok(part1_const_lookup(input)))
+}
 
-    // let p1 = part1_const_lookup(input)?;
-    // let p1 = part1_simd_rayon(input);
-    // let p1 = part1_simd(input);
-    let p1 = part1_naive(input)?;
+#[cfg(feature = "nightly")]
+pub fn run_simd(input: &str) -> Result<Solution> {
+    run_with(input, |input| Ok(part1_simd(input)))
+}
+
+#[cfg(feature = "nightly")]
+pub fn run_simd_rayon(input: &str) -> Result<Solution> {
+    run_with(input, |input| Ok(part1_simd_rayon(input)))
+}
 
+fn run_with(input: &str, part1: fn(&[u8]) -> Result<u16>) -> Result<Solution> {
+    let input = input.as_bytes();
+
+    let p1 = part1(input)?;
     let p2 = part2(input)?;
 
     Ok(Solution::new().part1(p1).part2(p2))
@@ -48,7 +74,6 @@ const fn gen_lookup_p1() -> [u16; 9] {
 
 const LOOKUP_P1: [u16; 9] = gen_lookup_p1();
 
-#[allow(unused)]
 pub fn part1_const_lookup(input: &[u8]) -> u16 {
     input.chunks(4).fold(0, |score, chunk| {
         let opponent = get!(chunk, 0) - b'A';
@@ -60,7 +85,6 @@ pub fn part1_const_lookup(input: &[u8]) -> u16 {
 }
 
 #[cfg(feature = "nightly")]
-#[allow(unused)]
 pub fn part1_simd_rayon(input: &[u8]) -> u16 {
     use std::ops::Add;
 
@@ -104,7 +128,6 @@ pub fn part1_simd_rayon(input: &[u8]) -> u16 {
 }
 
 #[cfg(feature = "nightly")]
-#[allow(unused)]
 pub fn part1_simd(input: &[u8]) -> u16 {
     use std::ops::Add;
 
@@ -181,47 +204,54 @@ pub fn part1_simd(input: &[u8]) -> u16 {
     aligned_score + suffix_score
 }
 
-#[allow(unused)]
 pub fn part1_naive(input: &[u8]) -> Result<u16> {
-    input
-        .chunks(4)
-        .try_fold(RockPaperScissors::default(), |rps, chunk| {
-            match &chunk[..3] {
-                [b'A', _, b'X'] => Ok(rps.draw(1)),
-                [b'A', _, b'Y'] => Ok(rps.win(2)),
-                [b'A', _, b'Z'] => Ok(rps.loss(3)),
-                [b'B', _, b'X'] => Ok(rps.loss(1)),
-                [b'B', _, b'Y'] => Ok(rps.draw(2)),
-                [b'B', _, b'Z'] => Ok(rps.win(3)),
-                [b'C', _, b'X'] => Ok(rps.win(1)),
-                [b'C', _, b'Y'] => Ok(rps.loss(2)),
-                [b'C', _, b'Z'] => Ok(rps.draw(3)),
-                chunk => Err(Report::msg(format!("invalid chunk `{chunk:?}`"))),
-            }
+    rounds(input)
+        .try_fold(RockPaperScissors::default(), |rps, round| match round {
+            [b'A', b' ', b'X'] => Ok(rps.draw(1)),
+            [b'A', b' ', b'Y'] => Ok(rps.win(2)),
+            [b'A', b' ', b'Z'] => Ok(rps.loss(3)),
+            [b'B', b' ', b'X'] => Ok(rps.loss(1)),
+            [b'B', b' ', b'Y'] => Ok(rps.draw(2)),
+            [b'B', b' ', b'Z'] => Ok(rps.win(3)),
+            [b'C', b' ', b'X'] => Ok(rps.win(1)),
+            [b'C', b' ', b'Y'] => Ok(rps.loss(2)),
+            [b'C', b' ', b'Z'] => Ok(rps.draw(3)),
+            _ => Err(invalid_round(input, round)),
         })
         .map(|rps| rps.score)
 }
 
 fn part2(input: &[u8]) -> Result<u16> {
-    input
-        .chunks(4)
-        .try_fold(RockPaperScissors::default(), |rps, chunk| {
-            match &chunk[..3] {
-                [b'A', _, b'X'] => Ok(rps.loss(3)),
-                [b'A', _, b'Y'] => Ok(rps.draw(1)),
-                [b'A', _, b'Z'] => Ok(rps.win(2)),
-                [b'B', _, b'X'] => Ok(rps.loss(1)),
-                [b'B', _, b'Y'] => Ok(rps.draw(2)),
-                [b'B', _, b'Z'] => Ok(rps.win(3)),
-                [b'C', _, b'X'] => Ok(rps.loss(2)),
-                [b'C', _, b'Y'] => Ok(rps.draw(3)),
-                [b'C', _, b'Z'] => Ok(rps.win(1)),
-                chunk => Err(Report::msg(format!("invalid chunk `{chunk:?}`"))),
-            }
+    rounds(input)
+        .try_fold(RockPaperScissors::default(), |rps, round| match round {
+            [b'A', b' ', b'X'] => Ok(rps.loss(3)),
+            [b'A', b' ', b'Y'] => Ok(rps.draw(1)),
+            [b'A', b' ', b'Z'] => Ok(rps.win(2)),
+            [b'B', b' ', b'X'] => Ok(rps.loss(1)),
+            [b'B', b' ', b'Y'] => Ok(rps.draw(2)),
+            [b'B', b' ', b'Z'] => Ok(rps.win(3)),
+            [b'C', b' ', b'X'] => Ok(rps.loss(2)),
+            [b'C', b' ', b'Y'] => Ok(rps.draw(3)),
+            [b'C', b' ', b'Z'] => Ok(rps.win(1)),
+            _ => Err(invalid_round(input, round)),
         })
         .map(|rps| rps.score)
 }
 
+fn rounds(input: &[u8]) -> impl Iterator<Item = &[u8]> {
+    input
+        .split(|&byte| byte == b'\n')
+        .map(<[u8]>::trim_ascii_end)
+        .filter(|round| !round.is_empty())
+}
+
+fn invalid_round(input: &[u8], round: &[u8]) -> Report {
+    let offset = round.as_ptr() as usize - input.as_ptr() as usize;
+    let input = String::from_utf8_lossy(input);
+
+    ParseError::at_offset(&input, offset, "expected `<A|B|C> <X|Y|Z>`").into()
+}
+
 #[derive(Default)]
 struct RockPaperScissors {
     score: u16,
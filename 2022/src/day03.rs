@@ -1,12 +1,11 @@
-use std::mem;
-
-use memchr::memchr_iter;
-
 use crate::prelude::*;
 
-pub fn run(input: &str) -> Result<Solution> {
-    let input = input.as_bytes();
+aoc_rust::register_day! {
+    day: 3,
+    title: "Rucksack Reorganization",
+}
 
+pub fn run(input: &str) -> Result<Solution> {
     let mut p1 = 0;
     let mut p2 = 0;
 
@@ -14,9 +13,19 @@ pub fn run(input: &str) -> Result<Solution> {
     let mut p2_count1 = [0; 26 * 2];
     let mut p2_count2 = [0; 26 * 2];
 
-    let lines = memchr_iter(b'\n', input).scan(0, |i, j| Some(&input[mem::replace(i, j + 1)..j]));
+    let lines = input
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty());
 
     for (i, line) in lines.enumerate() {
+        if let Some(offset) = line.bytes().position(|byte| !byte.is_ascii_alphabetic()) {
+            let msg = "expected only letters";
+
+            return Err(ParseError::at(input, &line[offset..], msg).into());
+        }
+
+        let line = line.as_bytes();
         let (front, back) = line.split_at(line.len() / 2);
 
         front
@@ -1,18 +1,25 @@
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 4,
+    title: "Camp Cleanup",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     input
         .lines()
         .try_fold((0, 0), |(mut p1, mut p2), line| {
-            let (front, back) = line.split_once(',').wrap_err("missing comma")?;
+            let line = line.trim_end();
 
-            let (Some((a, b)), Some((c, d))) = (front.split_once('-'), back.split_once('-')) else {
-                return Err(Report::msg("missing dash"));
-            };
+            let ((a, b), (c, d)) = line
+                .split_once(',')
+                .and_then(|(front, back)| front.split_once('-').zip(back.split_once('-')))
+                .context_at(input, line, "expected `<a>-<b>,<c>-<d>`")?;
 
-            let (Ok(a), Ok(b), Ok(c), Ok(d)) = (a.parse(), b.parse(), c.parse(), d.parse()) else {
-                return Err(Report::msg("invalid number"));
-            };
+            let a = parse_at(input, a)?;
+            let b = parse_at(input, b)?;
+            let c = parse_at(input, c)?;
+            let d = parse_at(input, d)?;
 
             p1 += p1_check(a, b, c, d) as usize;
             p2 += p2_check(a, b, c, d) as usize;
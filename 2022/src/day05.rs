@@ -1,61 +1,66 @@
-use std::{
-    cmp::Ordering,
-    ops::{Deref, DerefMut},
-    str::{FromStr, Lines},
-};
+use std::{cmp::Ordering, ops::Deref};
 
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 5,
+    title: "Supply Stacks",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let mut lines = input.lines();
+    let mut lines = input.lines().map(str::trim_end);
 
-    let mut stacks = parse_stacks(&mut lines)?;
+    let mut stacks = parse_stacks(input, &mut lines)?;
 
     lines.next();
 
     let instructions = lines
-        .map(str::parse)
-        .collect::<Result<Vec<Instruction>>>()?;
+        .filter(|line| !line.is_empty())
+        .map(|line| Instruction::parse(input, line, stacks.len()))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    let p1 = part1(&mut stacks.clone(), &instructions);
-    let p2 = part2(&mut stacks, &instructions);
+    let p1 = part1(&mut stacks.clone(), &instructions)?;
+    let p2 = part2(&mut stacks, &instructions)?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn parse_stacks(lines: &mut Lines<'_>) -> Result<Vec<Vec<u8>>> {
-    let Some(line) = lines.next() else {
-        return Err(Report::msg("missing crates"));
+fn parse_stacks<'a>(
+    input: &str,
+    lines: &mut impl Iterator<Item = &'a str>,
+) -> Result<Vec<Vec<u8>>, ParseError> {
+    let mut rows = Vec::new();
+
+    let labels = loop {
+        match lines.next() {
+            Some(line) if line.trim_start().starts_with(|c: char| c.is_ascii_digit()) => {
+                break line
+            }
+            Some(line) => rows.push(line),
+            None => return Err(ParseError::new("missing stack labels")),
+        }
     };
 
-    let mut stacks: Vec<_> = line
-        .bytes()
-        .skip(1)
-        .step_by(4)
-        .map(|c| if c != b' ' { vec![c] } else { Vec::new() })
-        .collect();
+    let mut stacks = vec![Vec::new(); labels.split_whitespace().count()];
 
-    for line in lines {
-        let bytes = line.as_bytes();
+    for row in rows.into_iter().rev() {
+        for (i, c) in row.bytes().enumerate().skip(1).step_by(4) {
+            if c == b' ' {
+                continue;
+            }
 
-        if bytes.get(1).filter(|c| c.is_ascii_digit()).is_some() {
-            break;
-        }
+            let Some(stack) = stacks.get_mut(i / 4) else {
+                return Err(ParseError::at(
+                    input,
+                    &row[i..],
+                    "crate is not on a labeled stack",
+                ));
+            };
 
-        bytes
-            .iter()
-            .skip(1)
-            .step_by(4)
-            .zip(stacks.iter_mut())
-            .filter(|(&c, _)| c != b' ')
-            .for_each(|(&c, stack)| stack.push(c));
+            stack.push(c);
+        }
     }
 
-    stacks
-        .iter_mut()
-        .map(Vec::deref_mut)
-        .for_each(<[u8]>::reverse);
-
     Ok(stacks)
 }
 
@@ -65,58 +70,76 @@ struct Instruction {
     count: usize,
 }
 
-impl FromStr for Instruction {
-    type Err = Report;
-
-    fn from_str(line: &str) -> Result<Self, Self::Err> {
-        let rest = line.trim_start_matches("move ");
-        let (count, rest) = rest.split_once(' ').wrap_err("invalid line")?;
-        let rest = rest.trim_start_matches("from ");
-        let (from, rest) = rest.split_once(' ').wrap_err("invalid line")?;
-        let to = rest.trim_start_matches("to ");
-
-        let count: usize = count
-            .parse()
-            .map_err(|_| eyre!("invalid number for `count`"))?;
-
-        let from = from
-            .parse()
-            .map(|n: usize| n - 1)
-            .map_err(|_| eyre!("invalid number for `from`"))?;
-
-        let to = to
-            .parse()
-            .map(|n: usize| n - 1)
-            .map_err(|_| eyre!("invalid number for `to`"))?;
+impl Instruction {
+    fn parse(input: &str, line: &str, stacks: usize) -> Result<Self, ParseError> {
+        let (count, from, to) = line
+            .strip_prefix("move ")
+            .and_then(|rest| rest.split_once(" from "))
+            .and_then(|(count, rest)| rest.split_once(" to ").map(|(from, to)| (count, from, to)))
+            .context_at(
+                input,
+                line,
+                "expected `move <count> from <stack> to <stack>`",
+            )?;
+
+        let stack = |n: &str| match parse_at::<usize>(input, n)? {
+            n @ 1.. if n <= stacks => Ok(n - 1),
+            _ => Err(ParseError::at(
+                input,
+                n,
+                format!("stack must be between 1 and {stacks}"),
+            )),
+        };
+
+        let count = parse_at(input, count)?;
+        let from = stack(from)?;
+        let to = stack(to)?;
+
+        if from == to {
+            return Err(ParseError::at(
+                input,
+                line,
+                "can not move within the same stack",
+            ));
+        }
 
         Ok(Self { from, to, count })
     }
 }
 
-fn part1(stacks: &mut [Vec<u8>], instructions: &[Instruction]) -> String {
+fn part1(stacks: &mut [Vec<u8>], instructions: &[Instruction]) -> Result<String> {
     for instruction in instructions {
         let Instruction { from, to, count } = instruction;
 
         let (from, to) = from_to(stacks, *from, *to);
-        let new_len = from.len() - count;
+        let new_len = remaining(from, *count)?;
         let drain = from.drain(new_len..).rev();
         to.extend(drain);
     }
 
-    read_top_crates(stacks)
+    Ok(read_top_crates(stacks))
 }
 
-fn part2(stacks: &mut [Vec<u8>], instructions: &[Instruction]) -> String {
+fn part2(stacks: &mut [Vec<u8>], instructions: &[Instruction]) -> Result<String> {
     for instruction in instructions {
         let Instruction { from, to, count } = instruction;
 
         let (from, to) = from_to(stacks, *from, *to);
-        let new_len = from.len() - count;
+        let new_len = remaining(from, *count)?;
         to.extend_from_slice(&from[new_len..]);
         from.truncate(new_len);
     }
 
-    read_top_crates(stacks)
+    Ok(read_top_crates(stacks))
+}
+
+fn remaining(stack: &[u8], count: usize) -> Result<usize> {
+    stack.len().checked_sub(count).wrap_err_with(|| {
+        format!(
+            "can not move {count} crates from a stack of {}",
+            stack.len()
+        )
+    })
 }
 
 fn read_top_crates(stacks: &[Vec<u8>]) -> String {
@@ -141,6 +164,6 @@ fn from_to(stacks: &mut [Vec<u8>], from: usize, to: usize) -> (&mut Vec<u8>, &mu
 
             (&mut back[0], get_mut!(front, to))
         }
-        Ordering::Equal => unreachable!("from == to"),
+        Ordering::Equal => unreachable!("instructions never move within the same stack"),
     }
 }
@@ -1,10 +1,18 @@
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 6,
+    title: "Tuning Trouble",
+    variants: [
+        "array" => solve_with_array,
+        "bitflags" => solve_with_bitflags,
+    ],
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     solve_with_dynamic(input)
 }
 
-#[allow(unused)]
 pub fn solve_with_array(input: &str) -> Result<Solution> {
     let bytes = input.as_bytes();
 
@@ -14,7 +22,6 @@ pub fn solve_with_array(input: &str) -> Result<Solution> {
     Ok(Solution::new().part1(p1 + 4).part2(p1 + p2 + 14))
 }
 
-#[allow(unused)]
 pub fn solve_with_dynamic(input: &str) -> Result<Solution> {
     let bytes = input.as_bytes();
 
@@ -24,7 +31,6 @@ pub fn solve_with_dynamic(input: &str) -> Result<Solution> {
     Ok(Solution::new().part1(p1 + 4).part2(p1 + p2 + 14))
 }
 
-#[allow(unused)]
 pub fn solve_with_bitflags(input: &str) -> Result<Solution> {
     let bytes = input.as_bytes();
 
@@ -3,11 +3,15 @@ use std::{
     fmt::{Display, Formatter, Result as FmtResult},
     ops::Add,
     rc::{Rc, Weak},
-    str::Lines,
 };
 
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 7,
+    title: "No Space Left On Device",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let fs = FileSystem::parse(input)?;
     // println!("{fs}");
@@ -32,7 +36,7 @@ pub fn run(input: &str) -> Result<Solution> {
     const TOTAL_MEM: usize = 70_000_000;
     const REQUIRED_MEM: usize = 30_000_000;
     const FREE_MEM: usize = TOTAL_MEM - REQUIRED_MEM;
-    let to_delete = fs.size() - FREE_MEM;
+    let to_delete = fs.size().saturating_sub(FREE_MEM);
 
     let p2 = fs.dir_min_by_key(|dir| {
         if dir.size < to_delete {
@@ -51,7 +55,7 @@ struct FileSystem<'i> {
 
 impl<'i> FileSystem<'i> {
     fn parse(input: &'i str) -> Result<Self> {
-        let mut lines = input.lines();
+        let mut lines = input.lines().map(str::trim_end);
 
         let this = Self {
             root: Rc::new(RefCell::new(Directory::new("/"))),
@@ -59,7 +63,7 @@ impl<'i> FileSystem<'i> {
 
         let mut dir = Rc::clone(&this.root);
 
-        while let Some(next) = read_command(dir, &mut lines)? {
+        while let Some(next) = read_command(input, dir, &mut lines)? {
             dir = next;
         }
 
@@ -99,35 +103,45 @@ impl Display for FileSystem<'_> {
     }
 }
 
-fn read_command<'i>(curr: Dir<'i>, lines: &mut Lines<'i>) -> Result<Option<Dir<'i>>> {
-    match lines.next().map(Command::parse) {
-        Some(Ok(Command::Cd(cmd))) => process_cd(cmd, curr),
-        Some(Ok(Command::Ls)) => {
-            for line in &mut *lines {
-                if let Ok(entry) = DirEntry::parse(line) {
+fn read_command<'i>(
+    input: &'i str,
+    curr: Dir<'i>,
+    lines: &mut impl Iterator<Item = &'i str>,
+) -> Result<Option<Dir<'i>>, ParseError> {
+    let Some(line) = lines.find(|line| !line.is_empty()) else {
+        return Ok(None);
+    };
+
+    match Command::parse(input, line)? {
+        Command::Cd(cmd) => process_cd(input, cmd, curr),
+        Command::Ls => {
+            for line in lines.filter(|line| !line.is_empty()) {
+                if line.starts_with("$ ") {
+                    match Command::parse(input, line)? {
+                        Command::Cd(cmd) => return process_cd(input, cmd, curr),
+                        Command::Ls => {}
+                    }
+                } else {
+                    let entry = DirEntry::parse(input, line)?;
+
                     if let DirEntry::Dir(ref dir) = entry {
                         dir.borrow_mut().parent = Some(Rc::downgrade(&curr));
                     }
 
                     curr.borrow_mut().entries.push(entry);
-                } else if let Ok(cmd) = Command::parse(line) {
-                    match cmd {
-                        Command::Cd(cmd) => return process_cd(cmd, curr),
-                        Command::Ls => {}
-                    }
-                } else {
-                    bail!("line `{line}` is neither dir entry nor command");
                 }
             }
 
             Ok(None)
         }
-        Some(Err(err)) => Err(err),
-        None => Ok(None),
     }
 }
 
-fn process_cd<'i>(cmd: CdCommand<'i>, curr: Dir<'i>) -> Result<Option<Dir<'i>>> {
+fn process_cd<'i>(
+    input: &str,
+    cmd: CdCommand<'i>,
+    curr: Dir<'i>,
+) -> Result<Option<Dir<'i>>, ParseError> {
     match cmd {
         CdCommand::Root => Ok(Some(Directory::root(curr))),
         CdCommand::StepOut => Ok(curr.borrow().parent.as_ref().and_then(Weak::upgrade)),
@@ -142,8 +156,9 @@ fn process_cd<'i>(cmd: CdCommand<'i>, curr: Dir<'i>) -> Result<Option<Dir<'i>>>
             .map(Rc::clone)
             .ok_or_else(|| {
                 let parent = curr.borrow().name;
+                let msg = format!("directory `{parent}` contains no directory `{dir}`");
 
-                eyre!("directory `{parent}` contains no directory `{dir}`")
+                ParseError::at(input, dir, msg)
             })
             .map(Some),
     }
@@ -155,15 +170,19 @@ enum Command<'d> {
 }
 
 impl<'d> Command<'d> {
-    fn parse(cmd: &'d str) -> Result<Self> {
-        let cmd = cmd.strip_prefix("$ ").wrap_err("missing command prefix")?;
+    fn parse(input: &str, line: &'d str) -> Result<Self, ParseError> {
+        let cmd = line.strip_prefix("$ ").context_at(
+            input,
+            line,
+            "expected command starting with `$ `",
+        )?;
 
-        if cmd.starts_with("ls") {
+        if cmd == "ls" {
             Ok(Self::Ls)
         } else if let Some(suffix) = cmd.strip_prefix("cd ") {
             Ok(Self::Cd(CdCommand::parse(suffix)))
         } else {
-            bail!("invalid command `{cmd}`")
+            Err(ParseError::at(input, cmd, "expected `ls` or `cd <dir>`"))
         }
     }
 }
@@ -190,15 +209,19 @@ enum DirEntry<'i> {
 }
 
 impl<'i> DirEntry<'i> {
-    fn parse(s: &'i str) -> Result<Self> {
-        let (prefix, suffix) = s.split_once(' ').wrap_err("invalid dir entry")?;
+    fn parse(input: &str, line: &'i str) -> Result<Self, ParseError> {
+        let (prefix, suffix) = line.split_once(' ').context_at(
+            input,
+            line,
+            "expected `dir <name>` or `<size> <name>`",
+        )?;
 
         if prefix == "dir" {
             Ok(Self::Dir(Rc::new(RefCell::new(Directory::new(suffix)))))
-        } else if let Ok(size) = prefix.parse() {
-            Ok(Self::File(File { name: suffix, size }))
         } else {
-            bail!("invalid pefix `{prefix}` for dir entry");
+            let size = parse_at(input, prefix)?;
+
+            Ok(Self::File(File { name: suffix, size }))
         }
     }
 
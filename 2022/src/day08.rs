@@ -2,8 +2,13 @@ use std::slice::ChunksExact;
 
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 8,
+    title: "Treetop Tree House",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let trees = Matrix::parse(input);
+    let trees = Matrix::parse(input)?;
 
     let p1 = part1(&trees);
     let p2 = part2(&trees);
@@ -68,26 +73,40 @@ pub struct Matrix {
 }
 
 impl Matrix {
-    fn parse(input: &str) -> Self {
-        let mut lines = input.lines();
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines = input
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty());
+
+        let width = lines.clone().next().map_or(0, str::len);
 
-        let mut inner = if let Some(line) = lines.next() {
-            line.bytes().map(|byte| byte - b'0').collect()
-        } else {
-            Vec::new()
-        };
+        if width == 0 {
+            return Err(ParseError::new("empty grid"));
+        }
 
-        let width = inner.len();
-        inner.reserve(width * width);
+        let mut inner = Vec::with_capacity(width * width);
 
         for line in lines {
-            inner.extend(line.bytes().map(|byte| byte - b'0'));
+            if line.len() != width {
+                let msg = format!("expected {width} trees per row");
+
+                return Err(ParseError::at(input, line, msg));
+            }
+
+            for (i, byte) in line.bytes().enumerate() {
+                if !byte.is_ascii_digit() {
+                    return Err(ParseError::at(input, &line[i..], "expected digit"));
+                }
+
+                inner.push(byte - b'0');
+            }
         }
 
-        Self {
+        Ok(Self {
             inner: inner.into(),
             width,
-        }
+        })
     }
 
     fn width(&self) -> usize {
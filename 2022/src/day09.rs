@@ -2,6 +2,11 @@ use std::{collections::HashSet, hash::Hash};
 
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 9,
+    title: "Rope Bridge",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let p1 = simulate_rope::<2>(input)?;
     let p2 = simulate_rope::<10>(input)?;
@@ -13,9 +18,13 @@ fn simulate_rope<const N: usize>(input: &str) -> Result<usize> {
     let mut rope = [Pos::default(); N];
     let mut seen = HashSet::new();
 
-    for line in input.lines() {
-        let (direction, num) = line.split_once(' ').wrap_err("invalid line")?;
-        let count = num.parse().wrap_err("invalid num")?;
+    for line in input.lines().map(str::trim_end) {
+        let (direction, num) = line
+            .split_once(' ')
+            .filter(|(direction, _)| matches!(*direction, "R" | "U" | "L" | "D"))
+            .context_at(input, line, "expected `<R|U|L|D> <count>`")?;
+
+        let count: u32 = parse_at(input, num)?;
 
         for _ in 0..count {
             let head = get_mut!(rope, 0);
@@ -25,7 +34,7 @@ fn simulate_rope<const N: usize>(input: &str) -> Result<usize> {
                 "U" => head.y += 1,
                 "L" => head.x -= 1,
                 "D" => head.y -= 1,
-                _ => bail!("invalid direction `{direction}`"),
+                _ => unreachable!(),
             }
 
             for i in 1..N {
@@ -1,32 +1,34 @@
-use std::fmt::{Display, Formatter, Result as FmtResult};
+use aoc_rust::AsciiArt;
 
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 10,
+    title: "Cathode-Ray Tube",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut x = 1;
     let mut cycle = 0;
     let mut sum = 0;
     let mut screen = Crt::new();
 
-    for line in input.lines() {
-        match line.split_once(' ') {
-            Some((op, val)) => {
-                ensure!(op == "addx", "invalid line");
-
-                process_cycle(x, &mut cycle, &mut sum, &mut screen);
-                process_cycle(x, &mut cycle, &mut sum, &mut screen);
+    for line in input.lines().map(str::trim_end) {
+        if line == "noop" {
+            process_cycle(x, &mut cycle, &mut sum, &mut screen);
+        } else if let Some(val) = line.strip_prefix("addx ") {
+            let val: i32 = parse_at(input, val)?;
 
-                x += val.parse::<i32>().map_err(|_| eyre!("invalid val"))?;
-            }
-            None => {
-                ensure!(line == "noop", "invalid line");
+            process_cycle(x, &mut cycle, &mut sum, &mut screen);
+            process_cycle(x, &mut cycle, &mut sum, &mut screen);
 
-                process_cycle(x, &mut cycle, &mut sum, &mut screen);
-            }
+            x += val;
+        } else if !line.is_empty() {
+            return Err(ParseError::at(input, line, "expected `noop` or `addx <value>`").into());
         }
     }
 
-    Ok(Solution::new().part1(sum).part2(screen.to_string()))
+    Ok(Solution::new().part1(sum).part2(screen.into_art()))
 }
 
 fn process_cycle(x: i32, cycle: &mut i32, sum: &mut i32, screen: &mut Crt) {
@@ -52,30 +54,13 @@ impl Crt {
     }
 
     fn draw(&mut self, x: i32, cycle: i32) {
-        self.inner[cycle as usize] |= ((x - 1)..=(x + 1)).contains(&(cycle % 40));
-    }
-}
-
-impl Display for Crt {
-    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        let mut chunks = self.inner.chunks_exact(Self::W);
-        const OFF: &str = " ";
-        const ON: &str = "█";
-
-        if let Some(chunk) = chunks.next() {
-            for &px in chunk {
-                f.write_str(if px { ON } else { OFF })?;
-            }
-        }
-
-        for chunk in chunks {
-            f.write_str("\n")?;
-
-            for &px in chunk {
-                f.write_str(if px { ON } else { OFF })?;
-            }
+        // Cycles past the last row are not displayed
+        if let Some(pixel) = self.inner.get_mut(cycle as usize) {
+            *pixel |= ((x - 1)..=(x + 1)).contains(&(cycle % 40));
         }
+    }
 
-        Ok(())
+    fn into_art(self) -> AsciiArt {
+        AsciiArt::new(Self::W, self.inner)
     }
 }
@@ -2,9 +2,16 @@ use std::{collections::VecDeque, convert::identity, str::FromStr};
 
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 11,
+    title: "Monkey in the Middle",
+    variants: [
+        "dyn-op" => dyn_monkey_op::run,
+        "dyn-op-no-pop" => dyn_monkey_op_no_pop::run,
+    ],
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    // dyn_monkey_op::run(input)
-    // dyn_monkey_op_no_pop::run(input)
     enum_monkey_op_no_pop::run(input)
 }
 
@@ -382,8 +389,32 @@ pub mod enum_monkey_op_no_pop {
         Ok(Solution::new().part1(p1).part2(p2))
     }
 
-    fn parse_monkeys(input: &str) -> Result<Vec<Monkey>> {
-        input.split("\n\n").map(str::parse).collect()
+    fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, ParseError> {
+        let mut lines = input
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty());
+
+        let mut monkeys = Vec::new();
+        let mut spans = Vec::new();
+
+        while let Some(header) = lines.next() {
+            let (monkey, targets) = Monkey::parse(input, header, &mut lines)?;
+            monkeys.push(monkey);
+            spans.push(targets);
+        }
+
+        for (monkey, targets) in monkeys.iter().zip(&spans) {
+            for (target, span) in [monkey.if_true, monkey.if_false].into_iter().zip(targets) {
+                if target >= monkeys.len() {
+                    let msg = format!("there is no monkey {target}");
+
+                    return Err(ParseError::at(input, span, msg));
+                }
+            }
+        }
+
+        Ok(monkeys)
     }
 
     fn part1(monkeys: &mut [Monkey]) -> Result<u64> {
@@ -454,37 +485,26 @@ pub mod enum_monkey_op_no_pop {
         Assign { val: Option<Worry> },
     }
 
-    impl FromStr for MonkeyOp {
-        type Err = Report;
-
-        fn from_str(line: &str) -> Result<Self, Self::Err> {
-            let mut op_iter = line.split(' ');
+    impl MonkeyOp {
+        fn parse(input: &str, expr: &str) -> Result<Self, ParseError> {
+            let term = |term: &str| match term {
+                "old" => Ok(None),
+                _ => parse_at(input, term).map(Some),
+            };
 
-            let term1 = op_iter.next().wrap_err("missing term1")?;
-            let op = op_iter.next();
-            let term2 = op_iter.next();
+            let mut op_iter = expr.split(' ');
 
-            let a = match term1 {
-                "old" => None,
-                _ => term1
-                    .parse()
-                    .map(Some)
-                    .map_err(|_| eyre!("invalid term1 `{term1}`"))?,
-            };
+            let a = term(op_iter.next().unwrap_or_default())?;
 
-            let b = match term2 {
-                Some("old") | None => None,
-                Some(term2) => term2
-                    .parse()
-                    .map(Some)
-                    .map_err(|_| eyre!("invalid term2 `{term2}`"))?,
-            };
+            let op = match (op_iter.next(), op_iter.next(), op_iter.next()) {
+                (Some("+"), Some(b), None) => Self::Add { a, b: term(b)? },
+                (Some("*"), Some(b), None) => Self::Mult { a, b: term(b)? },
+                (None, ..) => Self::Assign { val: a },
+                _ => {
+                    let msg = "expected `<term>`, `<term> + <term>`, or `<term> * <term>`";
 
-            let op = match op {
-                Some("+") => Self::Add { a, b },
-                Some("*") => Self::Mult { a, b },
-                None => Self::Assign { val: a },
-                Some(op) => bail!("unknow operation `{op}`"),
+                    return Err(ParseError::at(input, expr, msg));
+                }
             };
 
             Ok(op)
@@ -521,48 +541,47 @@ pub mod enum_monkey_op_no_pop {
         inspect_count: usize,
     }
 
-    impl FromStr for Monkey {
-        type Err = Report;
+    impl Monkey {
+        /// Parses a monkey and also returns the spans of its throw targets.
+        fn parse<'a>(
+            input: &str,
+            header: &'a str,
+            lines: &mut impl Iterator<Item = &'a str>,
+        ) -> Result<(Self, [&'a str; 2]), ParseError> {
+            if !(header.starts_with("Monkey ") && header.ends_with(':')) {
+                return Err(ParseError::at(input, header, "expected `Monkey <id>:`"));
+            }
 
-        fn from_str(group: &str) -> Result<Self, Self::Err> {
-            let mut lines = group.lines().skip(1);
+            let mut field = |prefix: &str| match lines.next() {
+                Some(line) => line.trim_start().strip_prefix(prefix).context_at(
+                    input,
+                    line,
+                    format!("expected `{prefix}...`"),
+                ),
+                None => Err(ParseError::new(format!("missing `{prefix}...`"))),
+            };
 
-            let items = lines
-                .next()
-                .and_then(|line| line.strip_prefix("  Starting items: "))
-                .wrap_err("invalid items line")?
-                .split(", ")
-                .map(str::parse)
-                .collect::<Result<_, _>>()
-                .wrap_err("invalid items")?;
+            let items = field("Starting items:")?
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| parse_at(input, item))
+                .collect::<Result<_, _>>()?;
 
-            let op = lines
-                .next()
-                .and_then(|line| line.strip_prefix("  Operation: new = "))
-                .wrap_err("invalid op line")?
-                .parse()
-                .wrap_err("invalid monkey op")?;
+            let op = MonkeyOp::parse(input, field("Operation: new = ")?)?;
 
-            let test_div = lines
-                .next()
-                .and_then(|line| line.strip_prefix("  Test: divisible by "))
-                .wrap_err("invalid test line")?
-                .parse()
-                .wrap_err("invalid test value")?;
+            let test_line = field("Test: divisible by ")?;
+            let test_div = parse_at(input, test_line)?;
 
-            let if_true = lines
-                .next()
-                .and_then(|line| line.strip_prefix("    If true: throw to monkey "))
-                .wrap_err("invalid if-true line")?
-                .parse()
-                .wrap_err("invalid if-true value")?;
+            if test_div == 0 {
+                return Err(ParseError::at(input, test_line, "can not divide by zero"));
+            }
 
-            let if_false = lines
-                .next()
-                .and_then(|line| line.strip_prefix("    If false: throw to monkey "))
-                .wrap_err("invalid if-false line")?
-                .parse()
-                .wrap_err("invalid if-false value")?;
+            let if_true_line = field("If true: throw to monkey ")?;
+            let if_true = parse_at(input, if_true_line)?;
+
+            let if_false_line = field("If false: throw to monkey ")?;
+            let if_false = parse_at(input, if_false_line)?;
 
             let monkey = Self {
                 items,
@@ -573,7 +592,7 @@ pub mod enum_monkey_op_no_pop {
                 inspect_count: 0,
             };
 
-            Ok(monkey)
+            Ok((monkey, [if_true_line, if_false_line]))
         }
     }
 }
@@ -7,10 +7,14 @@ use nom::{
 
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 13,
+    title: "Distress Signal",
+    variants: ["nom" => run_nom, "wrapped" => run_wrapped],
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     run_manual(input)
-    // run_nom(input)
-    // run_wrapped(input)
 }
 
 pub fn run_manual(input: &str) -> Result<Solution> {
@@ -26,25 +30,38 @@ pub fn run_wrapped(input: &str) -> Result<Solution> {
 }
 
 trait FromStr<'s>: Sized {
-    fn from_str(s: &'s str) -> Result<Self>;
+    fn from_str(input: &str, line: &'s str) -> Result<Self, ParseError>;
 }
 
-fn run_with_fn<'s, 'i, P>(input: &'i str, f: fn(&'i str) -> Result<P>) -> Result<Solution>
+type ParseFn<'i, P> = fn(&'i str, &'i str) -> Result<P, ParseError>;
+
+fn run_with_fn<'s, 'i, P>(input: &'i str, f: ParseFn<'i, P>) -> Result<Solution>
 where
     P: FromStr<'s> + PartialOrd + 'i,
 {
     let mut p1 = 0;
 
-    let divider1 = P::from_str("[2]").unwrap();
+    let divider1 = P::from_str("[2]", "[2]")?;
     let mut idx1 = 1;
 
-    let divider2 = P::from_str("[6]").unwrap();
+    let divider2 = P::from_str("[6]", "[6]")?;
     let mut idx2 = 2;
 
-    for (group, i) in input.split("\n\n").zip(1..) {
-        let (line_a, line_b) = group.split_once('\n').wrap_err("invalid group")?;
-        let packet_a = (f)(line_a)?;
-        let packet_b = (f)(line_b)?;
+    let mut lines = input
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty());
+
+    for i in 1.. {
+        let Some(line_a) = lines.next() else { break };
+        let line_b = lines.next().context_at(
+            input,
+            &line_a[line_a.len()..],
+            "missing second packet of the pair",
+        )?;
+
+        let packet_a = (f)(input, line_a)?;
+        let packet_b = (f)(input, line_b)?;
 
         p1 += (packet_a <= packet_b) as i32 * i;
 
@@ -75,8 +92,8 @@ mod wrap_packet {
 
     impl<'s> FromStr<'s> for Packet<'s> {
         #[inline]
-        fn from_str(s: &'s str) -> Result<Self> {
-            Ok(Self::new(s.as_bytes()))
+        fn from_str(_: &str, line: &'s str) -> Result<Self, ParseError> {
+            Ok(Self::new(line.as_bytes()))
         }
     }
 
@@ -200,6 +217,9 @@ mod wrap_packet {
 mod naive_packet {
     use super::*;
 
+    /// The parsed packet and remaining bytes, or the bytes at which parsing failed.
+    type PartialParse<'b> = Result<(Packet, &'b [u8]), (&'b [u8], &'static str)>;
+
     #[derive(PartialEq)]
     pub enum Packet {
         Num(u8),
@@ -208,21 +228,29 @@ mod naive_packet {
 
     impl FromStr<'_> for Packet {
         #[inline]
-        fn from_str(s: &str) -> Result<Self> {
-            Self::parse_manual(s)
+        fn from_str(input: &str, line: &str) -> Result<Self, ParseError> {
+            Self::parse_manual(input, line)
         }
     }
 
     impl Packet {
-        pub fn parse_manual(line: &str) -> Result<Self> {
+        pub fn parse_manual(input: &str, line: &str) -> Result<Self, ParseError> {
+            // Errors point at the remaining bytes which are a suffix of `line`
+            let err =
+                |bytes: &[u8], msg| ParseError::at(input, &line[line.len() - bytes.len()..], msg);
+
             let Some((b'[', rest)) = line.as_bytes().split_first() else {
-                bail!("packet must start with `[`");
+                return Err(err(line.as_bytes(), "packet must start with `[`"));
             };
 
-            Self::parse_list(rest).map(|(packet, _)| packet)
+            match Self::parse_list(rest) {
+                Ok((packet, [])) => Ok(packet),
+                Ok((_, rest)) => Err(err(rest, "unexpected bytes after packet")),
+                Err((rest, msg)) => Err(err(rest, msg)),
+            }
         }
 
-        fn parse_list(mut bytes: &[u8]) -> Result<(Self, &[u8])> {
+        fn parse_list(mut bytes: &[u8]) -> PartialParse<'_> {
             let mut list = Vec::new();
 
             loop {
@@ -239,29 +267,31 @@ mod naive_packet {
                     }
                     Some((b',', rest)) => bytes = rest,
                     Some((b']', rest)) => return Ok((Self::List(list), rest)),
-                    None => bail!("unexpected end of list"),
-                    Some((byte, _)) => bail!("invalid byte `{byte}`"),
+                    None => return Err((bytes, "unexpected end of list")),
+                    Some(_) => return Err((bytes, "expected `[`, `]`, `,`, or digit")),
                 }
             }
         }
 
-        fn parse_num(start: u8, mut bytes: &[u8]) -> Result<(Self, &[u8])> {
+        fn parse_num(start: u8, mut bytes: &[u8]) -> PartialParse<'_> {
             let mut num = start & 0xF;
 
             loop {
                 match bytes.split_first() {
                     Some((byte @ b'0'..=b'9', rest)) => {
-                        num *= 10;
-                        num += byte & 0xF;
+                        num = num
+                            .checked_mul(10)
+                            .and_then(|num| num.checked_add(byte & 0xF))
+                            .ok_or((bytes, "number is too large"))?;
                         bytes = rest;
                     }
                     Some((b',' | b']', _)) | None => return Ok((Self::Num(num), bytes)),
-                    Some((byte, _)) => bail!("unexpected byte `{byte}` while parsing number"),
+                    Some(_) => return Err((bytes, "expected `,`, `]`, or digit")),
                 }
             }
         }
 
-        pub fn parse_nom(input: &str) -> Result<Self> {
+        pub fn parse_nom(input: &str, line: &str) -> Result<Self, ParseError> {
             fn list(input: &str) -> IResult<&str, Packet> {
                 let num = map(ch::u8, Packet::Num);
                 let list_or_num = alt((list, num));
@@ -272,9 +302,7 @@ mod naive_packet {
                 (mapped)(input)
             }
 
-            let (_, packet) = list(input)
-                .map_err(|e| e.to_owned())
-                .wrap_err("failed to parse packet")?;
+            let (_, packet) = list(line).map_err(|err| ParseError::from_nom(input, err))?;
 
             Ok(packet)
         }
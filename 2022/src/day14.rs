@@ -2,8 +2,13 @@ use std::ops::{Add, AddAssign, Index, IndexMut};
 
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 14,
+    title: "Regolith Reservoir",
+    variants: ["naive" => run_naive],
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    // run_naive(input)
     run_dfs(input)
 }
 
@@ -146,7 +151,7 @@ impl Cave {
         }
     }
 
-    fn parse(input: &str, part: Part) -> Result<Self> {
+    fn parse(input: &str, part: Part) -> Result<Self, ParseError> {
         struct Edge {
             pos: Pos,
             dist: i32,
@@ -169,17 +174,31 @@ impl Cave {
 
         let mut left = Edge::new(0);
 
-        for line in input.lines() {
-            let mut path = Vec::new();
+        for line in input.lines().map(str::trim_end) {
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut path: Vec<Pos> = Vec::new();
 
             for coord in line.split(" -> ") {
-                let (x, y) = coord.split_once(',').wrap_err("invalid coord")?;
+                let (x, y) = coord
+                    .split_once(',')
+                    .context_at(input, coord, "expected `x,y`")?;
 
                 let pos = Pos {
-                    x: x.parse().wrap_err("invalid x")?,
-                    y: y.parse().wrap_err("invalid y")?,
+                    x: i32::from(parse_at::<u16>(input, x)?),
+                    y: i32::from(parse_at::<u16>(input, y)?),
                 };
 
+                if let Some(prev) = path.last() {
+                    if prev.x != pos.x && prev.y != pos.y {
+                        let msg = "rock paths must be horizontal or vertical";
+
+                        return Err(ParseError::at(input, coord, msg));
+                    }
+                }
+
                 min_x = min_x.min(pos.x);
                 max_x = max_x.max(pos.x);
 
@@ -199,6 +218,10 @@ impl Cave {
             paths.push(path);
         }
 
+        if paths.is_empty() {
+            return Err(ParseError::new("no rock paths"));
+        }
+
         let mut w = max_x - min_x + 1;
         let mut h = max_y - min_y + 1;
         let mut x_off = min_x;
@@ -223,8 +246,8 @@ impl Cave {
                         }
                     });
 
-            let left_pad = h - left.pos.y - (left.pos.x - x_off + 1);
-            let right_pad = h - right.pos.y - (w - (right.pos.x - x_off));
+            let left_pad = (h - left.pos.y - (left.pos.x - x_off + 1)).max(0);
+            let right_pad = (h - right.pos.y - (w - (right.pos.x - x_off))).max(0);
 
             w += left_pad + right_pad;
             x_off -= left_pad;
@@ -243,15 +266,13 @@ impl Cave {
                         let idx = y * w + x;
                         inner[idx as usize] = true;
                     }
-                } else if start.y == end.y {
+                } else {
                     let min = (start.y * w + start.x.min(end.x) - x_off) as usize;
                     let max = (start.y * w + start.x.max(end.x) - x_off) as usize;
 
                     for square in get_mut!(inner, min..=max) {
                         *square = true;
                     }
-                } else {
-                    bail!("cannot do diagonal line of rocks");
                 }
             }
         }
@@ -1,32 +1,52 @@
-use std::{mem, str::FromStr};
+use std::mem;
 
 use crate::prelude::*;
 
+aoc_rust::params! {
+    pub struct Params {
+        /// Row whose positions without a beacon are counted in part 1
+        row: i32 = 2_000_000,
+        /// Largest coordinate of the distress beacon in part 2
+        max: i32 = 4_000_000,
+    }
+}
+
+aoc_rust::register_day! {
+    day: 15,
+    title: "Beacon Exclusion Zone",
+    params: Params,
+}
+
 pub fn run(input: &str) -> Result<Solution> {
+    run_with_params(input, &Params::default())
+}
+
+pub fn run_with_params(input: &str, params: &Params) -> Result<Solution> {
+    let &Params { row, max } = params;
+
     let sensors = input
         .lines()
-        .map(str::parse)
-        .collect::<Result<Vec<Sensor>>>()?;
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .map(|line| Sensor::parse(input, line))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    // let p1 = part1::<10>(&sensors)?;
-    // let p2 = part2_border_intersection::<20>(&sensors)?;
-
-    let p1 = part1::<2_000_000>(&sensors)?;
-    // let p2 = part2_quadrants::<4_000_000>(&sensors)?;
-    let p2 = part2_border_intersection::<4_000_000>(&sensors)?;
+    let p1 = part1(&sensors, row)?;
+    // let p2 = part2_quadrants(&sensors, max)?;
+    let p2 = part2_border_intersection(&sensors, max)?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1<const Y: i32>(sensors: &[Sensor]) -> Result<i32> {
-    let lines = Line::generate(sensors, Y)?;
+fn part1(sensors: &[Sensor], row: i32) -> Result<i32> {
+    let lines = Line::generate(sensors, row)?;
     let mut p1 = 0;
 
     for line in lines {
         p1 += line.end - line.start + 1;
 
         for Sensor { pos, .. } in sensors {
-            if pos.y == Y && pos.x >= line.start && pos.x <= line.end {
+            if pos.y == row && pos.x >= line.start && pos.x <= line.end {
                 p1 -= 1;
             }
         }
@@ -36,10 +56,10 @@ fn part1<const Y: i32>(sensors: &[Sensor]) -> Result<i32> {
 }
 
 #[allow(unused)]
-fn part2_quadrants<const MAX: i32>(sensors: &[Sensor]) -> Result<i64> {
+fn part2_quadrants(sensors: &[Sensor], max: i32) -> Result<i64> {
     let full_area = Quadrant {
         top_l: Pos::new(0, 0),
-        bot_r: Pos::new(MAX, MAX),
+        bot_r: Pos::new(max, max),
     };
 
     let mut stack = vec![full_area];
@@ -81,8 +101,8 @@ fn part2_quadrants<const MAX: i32>(sensors: &[Sensor]) -> Result<i64> {
 
     bail!("no matching pos")
 }
-#[allow(unused)]
-fn part2_border_intersection<const MAX: i32>(sensors: &[Sensor]) -> Result<i64> {
+
+fn part2_border_intersection(sensors: &[Sensor], max: i32) -> Result<i64> {
     let mut verticals = Vec::with_capacity(sensors.len() * 2);
     let mut horizontals = Vec::with_capacity(sensors.len() * 2);
 
@@ -117,7 +137,7 @@ fn part2_border_intersection<const MAX: i32>(sensors: &[Sensor]) -> Result<i64>
     buf.clear();
 
     for window in horizontals.windows(2) {
-        let [a ,b] = window else { unreachable!() };
+        let [a, b] = window else { unreachable!() };
 
         if a == b {
             buf.push(*a);
@@ -134,7 +154,7 @@ fn part2_border_intersection<const MAX: i32>(sensors: &[Sensor]) -> Result<i64>
             let y = (h - v) / 2;
             let x = v + y;
 
-            if x < 0 || x > MAX || y < 0 || y > MAX {
+            if x < 0 || x > max || y < 0 || y > max {
                 continue;
             }
 
@@ -258,7 +278,9 @@ impl Line {
                 LineEventKind::End => {
                     lines.push(Line { start, end: x });
 
-                    let Some(LineEvent { x, kind }) = events.next() else { break };
+                    let Some(LineEvent { x, kind }) = events.next() else {
+                        break;
+                    };
 
                     match kind {
                         LineEventKind::Start => start = x,
@@ -294,20 +316,19 @@ impl Quadrant {
     }
 }
 
-impl FromStr for Sensor {
-    type Err = Report;
-
-    #[inline]
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let rest = s.strip_prefix("Sensor at ").wrap_err("missing prefix")?;
-        let (sensor, rest) = rest.split_once(':').wrap_err("missing colon")?;
+impl Sensor {
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let (sensor, beacon) = line
+            .strip_prefix("Sensor at ")
+            .and_then(|rest| rest.split_once(": closest beacon is at "))
+            .context_at(
+                input,
+                line,
+                "expected `Sensor at <pos>: closest beacon is at <pos>`",
+            )?;
 
-        let beacon = rest
-            .strip_prefix(" closest beacon is at ")
-            .wrap_err("missing infix")?;
-
-        let sensor_pos = sensor.parse().wrap_err("invalid sensor")?;
-        let beacon_pos = beacon.parse().wrap_err("invalid beacon")?;
+        let sensor_pos = Pos::parse(input, sensor)?;
+        let beacon_pos = Pos::parse(input, beacon)?;
 
         Ok(Sensor {
             pos: sensor_pos,
@@ -317,25 +338,45 @@ impl FromStr for Sensor {
     }
 }
 
-impl FromStr for Pos {
-    type Err = Report;
-
-    #[inline]
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y) = s.split_once(", ").wrap_err("missing comma")?;
-
-        let x = x
+impl Pos {
+    fn parse(input: &str, span: &str) -> Result<Self, ParseError> {
+        let (x, y) = span
             .strip_prefix("x=")
-            .map(str::parse)
-            .and_then(Result::ok)
-            .wrap_err("invalid x")?;
+            .and_then(|rest| rest.split_once(", y="))
+            .context_at(input, span, "expected `x=<x>, y=<y>`")?;
 
-        let y = y
-            .strip_prefix("y=")
-            .map(str::parse)
-            .and_then(Result::ok)
-            .wrap_err("invalid y")?;
+        Ok(Self {
+            x: parse_at(input, x)?,
+            y: parse_at(input, y)?,
+        })
+    }
+}
 
-        Ok(Self { x, y })
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "\
+Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+        let params = Params { row: 10, max: 20 };
+        let solution = run_with_params(input, &params).unwrap();
+        assert_eq!(solution.part1.to_string(), "26");
+        assert_eq!(solution.part2.to_string(), "56000011");
     }
 }
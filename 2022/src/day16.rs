@@ -1,17 +1,21 @@
 use std::{
     cmp::Reverse,
-    collections::{hash_map::Entry, BinaryHeap, HashMap},
+    collections::{BinaryHeap, HashMap},
     hash::Hash,
     mem,
-    str::FromStr,
 };
 
 use ahash::RandomState;
 
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 16,
+    title: "Proboscidea Volcanium",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let valves = Valves::from_str(input)?;
+    let valves = Valves::parse(input)?;
 
     let p1 = part1(&valves);
     let p2 = part2(&valves);
@@ -44,7 +48,9 @@ fn part1(valves: &Valves) -> u16 {
                 continue;
             }
 
-            let Some(next_time) = state.time.checked_sub(*dist + 1) else { continue };
+            let Some(next_time) = state.time.checked_sub(*dist + 1) else {
+                continue;
+            };
             let next_pressure = state.pressure + next_time as u16 * valves.flow_rates[next_valve];
             let next_opened = state.opened.open(next_valve);
 
@@ -116,7 +122,9 @@ fn part2(valves: &Valves) -> u16 {
                 continue;
             }
 
-            let Some(next_time) = state.time.checked_sub(*dist + 1) else { continue };
+            let Some(next_time) = state.time.checked_sub(*dist + 1) else {
+                continue;
+            };
             let next_pressure = state.pressure + next_time as u16 * valves.flow_rates[next_valve];
             let next_opened = state.opened.open(next_valve);
 
@@ -202,71 +210,69 @@ impl Valves {
 
         &self.dists[idx..idx + len]
     }
-}
-
-impl FromStr for Valves {
-    type Err = Report;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let mut indices: HashMap<_, _, RandomState> = HashMap::default();
-        indices.insert("AA", 0);
-        let mut next_idx = 1;
-
-        let mut get_idx = |name| match indices.entry(name) {
-            Entry::Occupied(e) => *e.get(),
-            Entry::Vacant(e) => {
-                let idx = *e.insert(next_idx);
-                next_idx += 1;
-
-                idx
-            }
-        };
 
+    fn parse(input: &str) -> Result<Self, ParseError> {
         // validate input and pick out key data
         let mut valves = input
             .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
             .map(|line| {
-                let rest = line.strip_prefix("Valve ").wrap_err("invalid line")?;
-                let (name, rest) = rest.split_once(' ').wrap_err("invalid name")?;
-                let idx = get_idx(name);
+                let (name, rest) = line
+                    .strip_prefix("Valve ")
+                    .and_then(|rest| rest.split_once(" has flow rate="))
+                    .context_at(input, line, "expected `Valve <name> has flow rate=<n>`")?;
 
-                let rest = rest
-                    .strip_prefix("has flow rate=")
-                    .wrap_err("invalid line `flow rate`")?;
+                let (n, rest) =
+                    rest.split_once(';')
+                        .context_at(input, rest, "expected `<n>; <tunnels>`")?;
 
-                let (n, rest) = rest.split_once(';').wrap_err("invalid line `;`")?;
-                let flow_rate: u16 = n.parse().wrap_err("invalid flow rate")?;
+                let flow_rate: u16 = parse_at(input, n)?;
 
                 let tunnels = rest
                     .strip_prefix(" tunnel leads to valve ")
                     .or_else(|| rest.strip_prefix(" tunnels lead to valves "))
-                    .wrap_err("invalid line")?;
+                    .context_at(input, rest, "expected `; tunnels lead to valves <names>`")?;
 
-                Ok((idx, flow_rate, tunnels))
+                Ok((name, flow_rate, tunnels))
             })
-            .collect::<Result<Vec<_>>>()
-            .wrap_err("invalid input")?;
+            .collect::<Result<Vec<_>, ParseError>>()?;
+
+        // valve `AA` gets index 0
+        valves.sort_by_key(|(name, ..)| *name != "AA");
 
-        valves.sort_unstable_by_key(|(idx, ..)| *idx);
+        if valves.first().map(|(name, ..)| *name) != Some("AA") {
+            return Err(ParseError::new("missing valve `AA`"));
+        }
+
+        let mut indices: HashMap<_, _, RandomState> = HashMap::default();
+
+        for (i, (name, ..)) in valves.iter().enumerate() {
+            if indices.insert(*name, i).is_some() {
+                let msg = format!("valve `{name}` is declared twice");
+
+                return Err(ParseError::at(input, name, msg));
+            }
+        }
 
         let mut width = valves.len();
         let mut adjacency = vec![u8::MAX; width * width];
+        let mut flow_rates = Vec::with_capacity(width);
 
         // parse tunnels and initialize adjacency matrix in one go
-        let mut flow_rates: Vec<_> = valves
-            .into_iter()
-            .map(|(i, flow_rate, tunnels)| {
-                let idx = i * width + i;
-                adjacency[idx] = 0;
-
-                for j in tunnels.split(", ").map(&mut get_idx) {
-                    let idx = i * width + j;
-                    adjacency[idx] = 1;
-                }
+        for (i, (_, flow_rate, tunnels)) in valves.into_iter().enumerate() {
+            adjacency[i * width + i] = 0;
 
-                flow_rate
-            })
-            .collect();
+            for tunnel in tunnels.split(", ") {
+                let j = *indices
+                    .get(tunnel)
+                    .context_at(input, tunnel, "unknown valve")?;
+
+                adjacency[i * width + j] = 1;
+            }
+
+            flow_rates.push(flow_rate);
+        }
 
         // floyd-warshall to calculate shortest distances
         for k in 0..width {
@@ -308,12 +314,13 @@ impl FromStr for Valves {
             flow_rates.remove(adjusted);
         }
 
-        assert!(
-            flow_rates.len() <= mem::size_of::<Opened>() * 8,
-            "got {} valves after reducing but we can't have more than {}",
-            flow_rates.len(),
-            mem::size_of::<Opened>() * 8,
-        );
+        if flow_rates.len() > mem::size_of::<Opened>() * 8 {
+            return Err(ParseError::new(format!(
+                "got {} valves after reducing but we can't have more than {}",
+                flow_rates.len(),
+                mem::size_of::<Opened>() * 8,
+            )));
+        }
 
         // pre-calculate a sorted list for flow rates for later use
         let mut sorted_flow_rate_tuples: Vec<_> = flow_rates.iter().copied().enumerate().collect();
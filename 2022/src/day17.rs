@@ -1,12 +1,14 @@
-use std::{
-    collections::{hash_map::Entry, HashMap},
-    iter,
-};
+use std::iter;
 
-use ahash::RandomState;
+use aoc_rust::util::cycle;
 
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 17,
+    title: "Pyroclastic Flow",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let p1 = part1(input)?;
     let p2 = part2(input)?;
@@ -14,109 +16,117 @@ pub fn run(input: &str) -> Result<Solution> {
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-pub fn part1(input: &str) -> Result<u64> {
-    let mut cave = vec![0b00000001; 9000];
-    cave[0] = u8::MAX;
-    let mut max_height = 0;
+fn parse_jets(input: &str) -> Result<&[u8], ParseError> {
+    let jets = input.trim_end();
 
-    let mut jets = input.bytes().cycle();
+    if jets.is_empty() {
+        return Err(ParseError::new("missing jets"));
+    }
 
-    for mut rock in ROCKS.iter().cycle().take(2022).copied() {
-        let mut rock_bottom = max_height + 4;
+    if let Some(i) = jets.bytes().position(|jet| jet != b'<' && jet != b'>') {
+        return Err(ParseError::at(input, &jets[i..], "expected `<` or `>`"));
+    }
 
-        for jet in &mut jets {
-            match jet {
-                b'<' => shift_left(&mut rock, &cave, rock_bottom),
-                b'>' => shift_right(&mut rock, &cave, rock_bottom),
-                _ => bail!("invalid jet"),
-            }
+    Ok(jets.as_bytes())
+}
 
-            if shift_down(rock, &mut cave, rock_bottom) {
-                rock_bottom -= 1;
-            } else {
-                let rock_height = rock.iter().rev().take_while(|&&row| row > 0).count();
-                max_height = max_height.max(rock_height + rock_bottom - 1);
+pub fn part1(input: &str) -> Result<u64> {
+    let jets = parse_jets(input)?;
+    let mut cave = vec![0b00000001; 9000];
+    cave[0] = u8::MAX;
+    let mut max_height = 0;
+    let mut jet_idx = 0;
 
-                break;
-            }
-        }
+    for rock in ROCKS.iter().cycle().take(2022).copied() {
+        max_height = drop_rock(rock, &mut cave, jets, &mut jet_idx, max_height);
     }
 
     Ok(max_height as u64)
 }
 
 pub fn part2(input: &str) -> Result<u64> {
-    const ROCKS_COUNT: u64 = 1_000_000_000_000;
+    const ROCKS_COUNT: usize = 1_000_000_000_000;
 
+    let jets = parse_jets(input)?;
     let mut cave = vec![0b00000001; 10_000];
     cave[0] = u8::MAX;
-    let mut max_height = 0;
 
-    let rocks = ROCKS.iter().copied().zip(0..).cycle();
-    let mut jets = input.bytes().zip(0..).cycle();
+    let start = Tower {
+        rock_idx: 0,
+        jet_idx: 0,
+        height: 0,
+        top: 0,
+    };
 
-    let mut rock_count = 0;
-    let mut height_from_cycles = 0;
-    let mut cache = HashMap::with_capacity_and_hasher(2048, RandomState::new());
+    let step = |tower: &Tower| {
+        let mut jet_idx = tower.jet_idx;
+        let rock = ROCKS[tower.rock_idx];
+        let height = drop_rock(rock, &mut cave, jets, &mut jet_idx, tower.height);
 
-    for (mut rock, rock_idx) in rocks {
-        rock_count += 1;
+        if cave.len() - height < 10 {
+            cave.extend(iter::repeat(0b00000001).take(1000));
+        }
 
-        if rock_count > ROCKS_COUNT {
-            break;
+        let top = cave[..height]
+            .iter()
+            .rev()
+            .take(8)
+            .fold(0, |top, &row| (top << 8) | row as u64);
+
+        Tower {
+            rock_idx: (tower.rock_idx + 1) % ROCKS.len(),
+            jet_idx,
+            height,
+            top,
         }
+    };
 
-        let mut rock_bottom = max_height + 4;
+    let key = |tower: &Tower| (tower.rock_idx, tower.jet_idx, tower.top);
+    let height =
+        cycle::find_cycle(start, step, key).extrapolate(ROCKS_COUNT, |tower| tower.height as i64);
 
-        for (jet, jet_idx) in &mut jets {
-            match jet {
-                b'<' => shift_left(&mut rock, &cave, rock_bottom),
-                b'>' => shift_right(&mut rock, &cave, rock_bottom),
-                _ => bail!("invalid jet"),
-            }
+    Ok(height as u64)
+}
 
-            if shift_down(rock, &mut cave, rock_bottom) {
-                rock_bottom -= 1;
-                continue;
-            }
+/// State of the tower after a rock came to rest.
+struct Tower {
+    /// Index of the next rock
+    rock_idx: usize,
+    /// Index of the next jet
+    jet_idx: usize,
+    height: usize,
+    /// Topmost rows of the tower
+    top: u64,
+}
+
+/// Let the rock fall until it comes to rest and return the new height of the
+/// tower.
+fn drop_rock(
+    mut rock: Rock,
+    cave: &mut [Row],
+    jets: &[u8],
+    jet_idx: &mut usize,
+    max_height: usize,
+) -> usize {
+    let mut rock_bottom = max_height + 4;
+
+    loop {
+        match jets[*jet_idx] {
+            b'<' => shift_left(&mut rock, cave, rock_bottom),
+            b'>' => shift_right(&mut rock, cave, rock_bottom),
+            _ => unreachable!(),
+        }
 
+        *jet_idx = (*jet_idx + 1) % jets.len();
+
+        if shift_down(rock, cave, rock_bottom) {
+            rock_bottom -= 1;
+        } else {
             let rock_height = rock.iter().rev().take_while(|&&row| row > 0).count();
 
-            if rock_height + rock_bottom - 1 > max_height {
-                max_height = rock_height + rock_bottom - 1;
-
-                if cave.len() - max_height < 10 {
-                    cave.extend(iter::repeat(0b00000001).take(1000));
-                }
-            }
-
-            if max_height < 8 {
-                break;
-            }
-
-            let last_8 = cave[max_height - 8..max_height]
-                .try_into()
-                .map(u64::from_le_bytes)
-                .unwrap();
-
-            match cache.entry((rock_idx, jet_idx, last_8)) {
-                Entry::Occupied(e) => {
-                    let (old_rock_count, old_height) = e.get();
-                    let rocks_per_cycle = rock_count - old_rock_count;
-                    let remaining_cycles = (ROCKS_COUNT - rock_count) / rocks_per_cycle;
-                    rock_count += rocks_per_cycle * remaining_cycles;
-                    height_from_cycles += remaining_cycles * (max_height - old_height) as u64;
-                }
-                Entry::Vacant(e) => {
-                    e.insert((rock_count, max_height));
-                }
-            }
-
-            break;
+            return max_height.max(rock_height + rock_bottom - 1);
         }
     }
-
-    Ok(max_height as u64 + height_from_cycles)
 }
 
 fn shift_left(rock: &mut Rock, cave: &[Row], rock_bottom: usize) {
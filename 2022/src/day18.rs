@@ -9,6 +9,11 @@ use std::{
 
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 18,
+    title: "Boiling Boulders",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut cubes = parse_cubes(input)?;
 
@@ -18,28 +23,42 @@ pub fn run(input: &str) -> Result<Solution> {
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn parse_cubes(input: &str) -> Result<HashSet<Pos3>> {
-    input
+fn parse_cubes(input: &str) -> Result<HashSet<Pos3>, ParseError> {
+    let coord = |n: &str| match parse_at(input, n)? {
+        n @ 0..=100 => Ok(n),
+        _ => Err(ParseError::at(
+            input,
+            n,
+            "coordinates must be between 0 and 100",
+        )),
+    };
+
+    let cubes: HashSet<_> = input
         .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
         .map(|line| {
             let mut iter = line.split(',');
 
-            let x = iter
-                .next()
-                .and_then(|x| x.parse().ok())
-                .wrap_err("invalid x")?;
-            let y = iter
-                .next()
-                .and_then(|y| y.parse().ok())
-                .wrap_err("invalid y")?;
-            let z = iter
-                .next()
-                .and_then(|z| z.parse().ok())
-                .wrap_err("invalid z")?;
-
-            Ok(Pos3 { x, y, z })
+            let (Some(x), Some(y), Some(z), None) =
+                (iter.next(), iter.next(), iter.next(), iter.next())
+            else {
+                return Err(ParseError::at(input, line, "expected `x,y,z`"));
+            };
+
+            Ok(Pos3 {
+                x: coord(x)?,
+                y: coord(y)?,
+                z: coord(z)?,
+            })
         })
-        .collect()
+        .collect::<Result<_, _>>()?;
+
+    if cubes.is_empty() {
+        return Err(ParseError::new("no cubes"));
+    }
+
+    Ok(cubes)
 }
 
 fn generate_events(cubes: &HashSet<Pos3>) -> (Vec<Event>, Vec<Event>, Vec<Event>) {
@@ -1,7 +1,10 @@
-use std::str::FromStr;
-
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 19,
+    title: "Not Enough Minerals",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let blueprints = parse_blueprints(input)?;
 
@@ -27,8 +30,13 @@ fn part2(blueprints: &[Blueprint]) -> u16 {
         .product()
 }
 
-fn parse_blueprints(input: &str) -> Result<Box<[Blueprint]>> {
-    input.lines().map(Blueprint::from_str).collect()
+fn parse_blueprints(input: &str) -> Result<Box<[Blueprint]>, ParseError> {
+    input
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .map(|line| Blueprint::parse(input, line))
+        .collect()
 }
 
 #[derive(Copy, Clone, Default)]
@@ -220,36 +228,32 @@ impl Blueprint {
     }
 }
 
-impl FromStr for Blueprint {
-    type Err = Report;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, rest) = s.split_once(": ").wrap_err("missing `: `")?;
-        let mut robots = rest.split(". ");
-
-        let ore: OreRobot = robots
-            .next()
-            .wrap_err("missing line")?
-            .parse()
-            .wrap_err("invalid ore robot")?;
-
-        let clay: ClayRobot = robots
-            .next()
-            .wrap_err("missing line")?
-            .parse()
-            .wrap_err("invalid clay robot")?;
-
-        let obsidian: ObsidianRobot = robots
-            .next()
-            .wrap_err("missing line")?
-            .parse()
-            .wrap_err("invalid obsidian robot")?;
-
-        let geode: GeodeRobot = robots
-            .next()
-            .wrap_err("missing line")?
-            .parse()
-            .wrap_err("invalid geode robot")?;
+impl Blueprint {
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let (_, rest) =
+            line.split_once(": ")
+                .context_at(input, line, "expected `Blueprint <id>: <robots>`")?;
+
+        let mut robots = rest.trim_end_matches('.').split(". ");
+        let mut next = || robots.next().unwrap_or(&line[line.len()..]);
+
+        let (ore, _) = parse_costs(input, next(), "ore", None)?;
+        let (clay, _) = parse_costs(input, next(), "clay", None)?;
+        let (obsidian_ore, obsidian_clay) = parse_costs(input, next(), "obsidian", Some("clay"))?;
+        let (geode_ore, geode_obsidian) = parse_costs(input, next(), "geode", Some("obsidian"))?;
+
+        let ore = OreRobot { cost_ore: ore };
+        let clay = ClayRobot { cost_ore: clay };
+
+        let obsidian = ObsidianRobot {
+            cost_ore: obsidian_ore,
+            cost_clay: obsidian_clay,
+        };
+
+        let geode = GeodeRobot {
+            cost_ore: geode_ore,
+            cost_obsidian: geode_obsidian,
+        };
 
         let max_cost_ore = ore
             .cost_ore
@@ -273,94 +277,55 @@ impl FromStr for Blueprint {
     }
 }
 
-struct OreRobot {
-    cost_ore: u16,
+/// Parses `Each <robot> robot costs <n> ore[ and <m> <second>]`.
+fn parse_costs(
+    input: &str,
+    sentence: &str,
+    robot: &str,
+    second: Option<&str>,
+) -> Result<(u16, u16), ParseError> {
+    let expected = || match second {
+        Some(second) => format!("expected `Each {robot} robot costs <n> ore and <m> {second}`"),
+        None => format!("expected `Each {robot} robot costs <n> ore`"),
+    };
+
+    let costs = sentence
+        .strip_prefix("Each ")
+        .and_then(|rest| rest.strip_prefix(robot))
+        .and_then(|rest| rest.strip_prefix(" robot costs "))
+        .ok_or_else(|| ParseError::at(input, sentence, expected()))?;
+
+    let Some(second) = second else {
+        let ore = costs
+            .strip_suffix(" ore")
+            .ok_or_else(|| ParseError::at(input, costs, expected()))?;
+
+        return Ok((parse_at(input, ore)?, 0));
+    };
+
+    let (ore, other) = costs
+        .split_once(" ore and ")
+        .and_then(|(ore, rest)| rest.strip_suffix(second).map(|rest| (ore, rest)))
+        .and_then(|(ore, rest)| rest.strip_suffix(' ').map(|other| (ore, other)))
+        .ok_or_else(|| ParseError::at(input, costs, expected()))?;
+
+    Ok((parse_at(input, ore)?, parse_at(input, other)?))
 }
 
-impl FromStr for OreRobot {
-    type Err = Report;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let cost_ore = s
-            .split(' ')
-            .nth_back(1)
-            .and_then(|n| n.parse().ok())
-            .wrap_err("invalid ore cost")?;
-
-        Ok(Self { cost_ore })
-    }
+struct OreRobot {
+    cost_ore: u16,
 }
 
 struct ClayRobot {
     cost_ore: u16,
 }
 
-impl FromStr for ClayRobot {
-    type Err = Report;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let cost_ore = s
-            .split(' ')
-            .nth_back(1)
-            .and_then(|n| n.parse().ok())
-            .wrap_err("invalid ore cost")?;
-
-        Ok(Self { cost_ore })
-    }
-}
-
 struct ObsidianRobot {
     cost_ore: u16,
     cost_clay: u16,
 }
 
-impl FromStr for ObsidianRobot {
-    type Err = Report;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s.split(' ');
-
-        let cost_clay = iter
-            .nth_back(1)
-            .and_then(|n| n.parse().ok())
-            .wrap_err("invalid clay cost")?;
-
-        let cost_ore = iter
-            .nth_back(2)
-            .and_then(|n| n.parse().ok())
-            .wrap_err("invalid ore cost")?;
-
-        Ok(Self {
-            cost_ore,
-            cost_clay,
-        })
-    }
-}
-
 struct GeodeRobot {
     cost_ore: u16,
     cost_obsidian: u16,
 }
-
-impl FromStr for GeodeRobot {
-    type Err = Report;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s.split(' ');
-
-        let cost_obsidian = iter
-            .nth_back(1)
-            .and_then(|n| n.parse().ok())
-            .wrap_err("invalid obsidian cost")?;
-
-        let cost_ore = iter
-            .nth_back(2)
-            .and_then(|n| n.parse().ok())
-            .wrap_err("invalid ore cost")?;
-
-        Ok(Self {
-            cost_ore,
-            cost_obsidian,
-        })
-    }
-}
@@ -2,12 +2,21 @@ use std::cmp::Ordering;
 
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 20,
+    title: "Grove Positioning System",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let original = input
-        .split('\n')
-        .map(str::parse)
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .map(|line| parse_at(input, line))
         .collect::<Result<Vec<i64>, _>>()?;
 
+    ensure!(original.len() >= 2, "expected at least two numbers");
+
     let p1 = solve::<1, 1>(&original)?;
     let p2 = solve::<10, 811_589_153>(&original)?;
 
@@ -6,16 +6,26 @@ use crate::prelude::*;
 
 type Monkeys<'m> = HashMap<&'m str, Monkey<'m>, RandomState>;
 
+aoc_rust::register_day! {
+    day: 21,
+    title: "Monkey Math",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let mut monkeys = input
         .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
         .map(|line| {
-            let (name, monkey) = line.split_once(": ").wrap_err("invalid line")?;
-            let monkey = Monkey::from_str(monkey)?;
+            let (name, monkey) =
+                line.split_once(": ")
+                    .context_at(input, line, "expected `<name>: <job>`")?;
+
+            let monkey = Monkey::parse(input, monkey)?;
 
             Ok((name, monkey))
         })
-        .collect::<Result<Monkeys<'_>>>()?;
+        .collect::<Result<Monkeys<'_>, ParseError>>()?;
 
     // assert monkeys to justify later unwraps
     let invalid_monkey = monkeys.values().find_map(|monkey| match monkey {
@@ -27,10 +37,24 @@ pub fn run(input: &str) -> Result<Solution> {
     });
 
     if let Some(name) = invalid_monkey {
-        bail!("monkey `{name}` appears in expression but has no expression itself");
+        let msg = format!("monkey `{name}` appears in expression but has no expression itself");
+
+        return Err(ParseError::at(input, name, msg).into());
+    }
+
+    if !monkeys.contains_key("root") {
+        return Err(ParseError::new("missing monkey `root`").into());
     }
 
-    let p1 = eval("root", &monkeys);
+    let mut visiting = HashMap::default();
+
+    if let Some(name) = find_cycle("root", &monkeys, &mut visiting) {
+        let msg = format!("monkey `{name}` depends on its own number");
+
+        return Err(ParseError::at(input, name, msg).into());
+    }
+
+    let p1 = eval("root", &monkeys).wrap_err(OVERFLOW)?;
 
     let humans = count_humans("root", &monkeys);
 
@@ -47,23 +71,54 @@ pub fn run(input: &str) -> Result<Solution> {
     let mut chain = Vec::new();
 
     let p2 = if find_human(a, &monkeys, &mut chain) {
-        part2(eval(b, &monkeys), &monkeys, &chain)
+        eval(b, &monkeys).and_then(|solution| part2(solution, &monkeys, &chain))
     } else if find_human(b, &monkeys, &mut chain) {
-        part2(eval(a, &monkeys), &monkeys, &chain)
+        eval(a, &monkeys).and_then(|solution| part2(solution, &monkeys, &chain))
     } else {
         unreachable!()
     };
 
+    let p2 = p2.wrap_err(OVERFLOW)?;
+
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn eval<'m>(name: &'m str, monkeys: &Monkeys<'m>) -> i64 {
+const OVERFLOW: &str = "numbers overflow or get divided by zero";
+
+/// Returns the name of a monkey whose number depends on itself.
+fn find_cycle<'m>(
+    name: &'m str,
+    monkeys: &Monkeys<'m>,
+    visiting: &mut HashMap<&'m str, bool, RandomState>,
+) -> Option<&'m str> {
+    match visiting.get(name) {
+        Some(true) => return Some(name),
+        Some(false) => return None,
+        None => visiting.insert(name, true),
+    };
+
+    if let Monkey::Add(a, b) | Monkey::Sub(a, b) | Monkey::Mul(a, b) | Monkey::Div(a, b) =
+        monkeys.get(name).unwrap()
+    {
+        if let Some(name) = find_cycle(a, monkeys, visiting) {
+            return Some(name);
+        } else if let Some(name) = find_cycle(b, monkeys, visiting) {
+            return Some(name);
+        }
+    }
+
+    visiting.insert(name, false);
+
+    None
+}
+
+fn eval<'m>(name: &'m str, monkeys: &Monkeys<'m>) -> Option<i64> {
     match monkeys.get(name).unwrap() {
-        Monkey::Value(n) => *n,
-        Monkey::Add(a, b) => eval(a, monkeys) + eval(b, monkeys),
-        Monkey::Sub(a, b) => eval(a, monkeys) - eval(b, monkeys),
-        Monkey::Mul(a, b) => eval(a, monkeys) * eval(b, monkeys),
-        Monkey::Div(a, b) => eval(a, monkeys) / eval(b, monkeys),
+        Monkey::Value(n) => Some(*n),
+        Monkey::Add(a, b) => eval(a, monkeys)?.checked_add(eval(b, monkeys)?),
+        Monkey::Sub(a, b) => eval(a, monkeys)?.checked_sub(eval(b, monkeys)?),
+        Monkey::Mul(a, b) => eval(a, monkeys)?.checked_mul(eval(b, monkeys)?),
+        Monkey::Div(a, b) => eval(a, monkeys)?.checked_div(eval(b, monkeys)?),
     }
 }
 
@@ -109,24 +164,24 @@ fn find_human<'m>(
     }
 }
 
-fn part2(mut solution: i64, monkeys: &Monkeys<'_>, chain: &[(&Monkey<'_>, Side)]) -> i64 {
+fn part2(mut solution: i64, monkeys: &Monkeys<'_>, chain: &[(&Monkey<'_>, Side)]) -> Option<i64> {
     for entry in chain.iter().rev() {
-        match entry {
+        solution = match entry {
             (Monkey::Add(_, m), Side::Left) | (Monkey::Add(m, _), Side::Right) => {
-                solution -= eval(m, monkeys)
+                solution.checked_sub(eval(m, monkeys)?)?
             }
             (Monkey::Mul(_, m), Side::Left) | (Monkey::Mul(m, _), Side::Right) => {
-                solution /= eval(m, monkeys)
+                solution.checked_div(eval(m, monkeys)?)?
             }
-            (Monkey::Sub(_, m), Side::Left) => solution += eval(m, monkeys),
-            (Monkey::Div(_, m), Side::Left) => solution *= eval(m, monkeys),
-            (Monkey::Sub(m, _), Side::Right) => solution = eval(m, monkeys) - solution,
-            (Monkey::Div(m, _), Side::Right) => solution = eval(m, monkeys) / solution,
+            (Monkey::Sub(_, m), Side::Left) => solution.checked_add(eval(m, monkeys)?)?,
+            (Monkey::Div(_, m), Side::Left) => solution.checked_mul(eval(m, monkeys)?)?,
+            (Monkey::Sub(m, _), Side::Right) => eval(m, monkeys)?.checked_sub(solution)?,
+            (Monkey::Div(m, _), Side::Right) => eval(m, monkeys)?.checked_div(solution)?,
             (Monkey::Value(_), _) => unreachable!(),
-        }
+        };
     }
 
-    solution
+    Some(solution)
 }
 
 enum Monkey<'m> {
@@ -138,23 +193,23 @@ enum Monkey<'m> {
 }
 
 impl<'m> Monkey<'m> {
-    fn from_str(s: &'m str) -> Result<Self> {
-        if let Ok(n) = s.parse() {
-            return Ok(Self::Value(n));
+    fn parse(input: &str, job: &'m str) -> Result<Self, ParseError> {
+        if job.starts_with(|c: char| c == '-' || c.is_ascii_digit()) {
+            return parse_at(input, job).map(Self::Value);
         }
 
-        let mut iter = s.split(' ');
-
-        let a = iter.next().wrap_err("missing term 1")?;
-        let op = iter.next().wrap_err("missing operation")?;
-        let b = iter.next().wrap_err("missing term 2")?;
-
-        match op {
-            "+" => Ok(Self::Add(a, b)),
-            "-" => Ok(Self::Sub(a, b)),
-            "*" => Ok(Self::Mul(a, b)),
-            "/" => Ok(Self::Div(a, b)),
-            _ => bail!("invalid op"),
+        let mut iter = job.split(' ');
+
+        match (iter.next(), iter.next(), iter.next(), iter.next()) {
+            (Some(a), Some("+"), Some(b), None) => Ok(Self::Add(a, b)),
+            (Some(a), Some("-"), Some(b), None) => Ok(Self::Sub(a, b)),
+            (Some(a), Some("*"), Some(b), None) => Ok(Self::Mul(a, b)),
+            (Some(a), Some("/"), Some(b), None) => Ok(Self::Div(a, b)),
+            _ => Err(ParseError::at(
+                input,
+                job,
+                "expected number or `<name> <+|-|*|/> <name>`",
+            )),
         }
     }
 }
@@ -1,10 +1,16 @@
-use std::{
-    iter,
-    ops::{Add, Mul, Rem, Sub},
-};
+use std::iter;
+
+use aoc_rust::grid::{Direction, Pos2, Turn};
 
 use crate::prelude::*;
 
+type Pos = Pos2<i32>;
+
+aoc_rust::register_day! {
+    day: 22,
+    title: "Monkey Map",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let (board, path) = Board::parse(input)?;
 
@@ -16,26 +22,41 @@ pub fn run(input: &str) -> Result<Solution> {
         .wrap_err("missing open tile")?;
 
     let p1 = solve(&board, path, start, Board::wrap_grid)?;
-    let p2 = solve(&board, path, start, Board::wrap_cube)?;
+
+    let cube = Cube::fold(&board).wrap_err("board is not a cube net")?;
+    let p2 = solve(&board, path, start, |board, pos, direction, delta| {
+        cube.wrap(board, pos, direction, delta)
+    })?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-type WrapFn = fn(&Board, &mut Pos, &mut Direction, &mut Pos) -> Wrap;
+/// Returns `None` if the board can not be wrapped around.
+trait WrapFn: Fn(&Board, &mut Pos, &mut Direction, &mut Pos) -> Option<Wrap> {}
 
-fn solve(board: &Board, mut path: Path, start: Pos, wrap: WrapFn) -> Result<i32> {
+impl<F: Fn(&Board, &mut Pos, &mut Direction, &mut Pos) -> Option<Wrap>> WrapFn for F {}
+
+fn solve(board: &Board, mut path: Path, start: Pos, wrap: impl WrapFn) -> Result<i32> {
     let mut pos = start;
-    let mut direction = RIGHT;
+    let mut direction = Direction::Right;
 
     while let Some(next) = path.next() {
-        match next? {
-            Move::Distance(n) => board.steps(&mut pos, n, &mut direction, wrap),
-            Move::Turn(Turn::Left) => direction = direction.turn_left(),
-            Move::Turn(Turn::Right) => direction = direction.turn_right(),
+        match next {
+            Move::Distance(n) => board
+                .steps(&mut pos, n, &mut direction, &wrap)
+                .wrap_err("failed to wrap around the board")?,
+            Move::Turn(turn) => direction = direction.turn(turn),
         }
     }
 
-    Ok(1000 * (pos.y + 1) + 4 * (pos.x + 1) + direction.0 as i32)
+    let facing = match direction {
+        Direction::Right => 0,
+        Direction::Down => 1,
+        Direction::Left => 2,
+        Direction::Up => 3,
+    };
+
+    Ok(1000 * (pos.y + 1) + 4 * (pos.x + 1) + facing)
 }
 
 struct Board {
@@ -49,168 +70,328 @@ enum Wrap {
 }
 
 impl Board {
-    fn steps(&self, pos: &mut Pos, count: i16, direction: &mut Direction, wrap: WrapFn) {
+    fn steps(
+        &self,
+        pos: &mut Pos,
+        count: i16,
+        direction: &mut Direction,
+        wrap: &impl WrapFn,
+    ) -> Option<()> {
         let mut delta = direction.delta();
 
         for _ in 0..count {
             let next = *pos + delta;
 
             match self.get(next) {
-                Tile::Wall => return,
+                Tile::Wall => break,
                 Tile::Open => *pos = next,
-                Tile::Closed => match (wrap)(self, pos, direction, &mut delta) {
+                Tile::Closed => match (wrap)(self, pos, direction, &mut delta)? {
                     Wrap::Continue => {}
                     Wrap::Stop => break,
                 },
             }
         }
+
+        Some(())
     }
 
-    fn wrap_grid(&self, pos: &mut Pos, _: &mut Direction, delta: &mut Pos) -> Wrap {
+    fn wrap_grid(&self, pos: &mut Pos, _: &mut Direction, delta: &mut Pos) -> Option<Wrap> {
         let mut next = *pos - *delta;
 
         while !matches!(self.get(next), Tile::Closed) {
-            next = next - *delta;
+            next -= *delta;
         }
 
-        next = next + *delta;
+        next += *delta;
 
         match self.get(next) {
-            Tile::Wall => Wrap::Stop,
+            Tile::Wall => Some(Wrap::Stop),
             Tile::Open => {
                 *pos = next;
 
-                Wrap::Continue
+                Some(Wrap::Continue)
             }
             Tile::Closed => unreachable!(),
         }
     }
 
-    fn wrap_cube(&self, pos: &mut Pos, direction: &mut Direction, delta: &mut Pos) -> Wrap {
-        const SIDE_LEN: i32 = 50;
+    fn is_valid_pos(&self, pos: Pos) -> bool {
+        pos.x >= 0
+            && pos.x < self.width
+            && pos.y >= 0
+            && pos.y < self.tiles.len() as i32 / self.width
+    }
+
+    fn get(&self, pos @ Pos { x, y }: Pos) -> Tile {
+        if self.is_valid_pos(pos) {
+            self.tiles[(y * self.width + x) as usize]
+        } else {
+            Tile::Closed
+        }
+    }
 
-        // draw faces on paper, connect sides with lines, hardcode those lines
-        let (face, next_direction) = match (pos.x / SIDE_LEN, pos.y / SIDE_LEN, *direction) {
-            (0, 2, UP) => (Pos::new(1, 1), RIGHT),
-            (0, 3, DOWN) => (Pos::new(2, 0), DOWN),
+    fn parse(input: &str) -> Result<(Self, Path<'_>), ParseError> {
+        let mut lines = input.lines().map(str::trim_end);
+        let board: Vec<_> = lines.by_ref().take_while(|line| !line.is_empty()).collect();
 
-            (1, 0, UP) => (Pos::new(0, 3), RIGHT),
-            (1, 2, DOWN) => (Pos::new(0, 3), LEFT),
+        let path = lines
+            .find(|line| !line.is_empty())
+            .ok_or_else(|| ParseError::new("missing path after the board"))?;
 
-            (2, 0, UP) => (Pos::new(0, 3), UP),
-            (2, 0, DOWN) => (Pos::new(1, 1), LEFT),
+        let path = Path::parse(input, path)?;
 
-            (1, 0, LEFT) => (Pos::new(0, 2), RIGHT),
-            (2, 0, RIGHT) => (Pos::new(1, 2), LEFT),
+        let height = board.len();
+        let width = board.iter().map(|line| line.len()).max().unwrap_or(0);
 
-            (1, 1, LEFT) => (Pos::new(0, 2), DOWN),
-            (1, 1, RIGHT) => (Pos::new(2, 0), UP),
+        let mut tiles = Vec::with_capacity(height * width);
 
-            (0, 2, LEFT) => (Pos::new(1, 0), RIGHT),
-            (1, 2, RIGHT) => (Pos::new(2, 0), LEFT),
+        for line in board {
+            for (i, byte) in line.bytes().enumerate() {
+                let tile = match byte {
+                    b' ' => Tile::Closed,
+                    b'.' => Tile::Open,
+                    b'#' => Tile::Wall,
+                    _ => {
+                        return Err(ParseError::at(
+                            input,
+                            &line[i..],
+                            "expected ` `, `.`, or `#`",
+                        ))
+                    }
+                };
 
-            (0, 3, LEFT) => (Pos::new(1, 0), DOWN),
-            (0, 3, RIGHT) => (Pos::new(1, 2), UP),
+                tiles.push(tile);
+            }
 
-            // EXAMPLE:
-            // (0, 1, UP) => (Pos::new(2, 0), DOWN),
-            // (0, 1, DOWN) => (Pos::new(2, 2), UP),
+            let remaining = width - line.len();
 
-            // (1, 1, UP) => (Pos::new(2, 0), RIGHT),
-            // (1, 1, DOWN) => (Pos::new(2, 2), RIGHT),
+            if remaining > 0 {
+                tiles.extend(iter::repeat(Tile::Closed).take(remaining))
+            }
+        }
 
-            // (2, 0, UP) => (Pos::new(0, 1), DOWN),
-            // (2, 2, DOWN) => (Pos::new(0, 1), UP),
+        let this = Self {
+            tiles: tiles.into_boxed_slice(),
+            width: width as i32,
+        };
 
-            // (3, 2, UP) => (Pos::new(2, 1), LEFT),
-            // (3, 2, DOWN) => (Pos::new(0, 1), RIGHT),
+        Ok((this, path))
+    }
+}
 
-            // (2, 0, LEFT) => (Pos::new(1, 1), DOWN),
-            // (2, 0, RIGHT) => (Pos::new(3, 2), LEFT),
+/// The board's faces glued together by folding the net into a cube.
+struct Cube {
+    side_len: i32,
+    /// Position of each face on the net in units of `side_len`
+    faces: Vec<Pos>,
+    /// Face and direction after leaving a face, indexed by the face and
+    /// the direction in which it's left
+    edges: Vec<[(usize, Direction); 4]>,
+}
 
-            // (0, 1, LEFT) => (Pos::new(3, 2), UP),
-            // (2, 1, RIGHT) => (Pos::new(3, 2), DOWN),
+/// Axis-aligned unit vector in 3D.
+type Vec3 = [i8; 3];
 
-            // (2, 2, LEFT) => (Pos::new(1, 1), UP),
-            // (3, 2, RIGHT) => (Pos::new(2, 0), RIGHT),
-            _ => unreachable!(),
-        };
+/// Placement of a face on the cube as its outward normal and the 3D
+/// directions of the net's right and down.
+#[derive(Copy, Clone)]
+struct Orientation {
+    normal: Vec3,
+    right: Vec3,
+    down: Vec3,
+}
 
-        let in_face = *pos % SIDE_LEN;
+impl Orientation {
+    /// Orientation of the neighbor in `direction` after folding it down.
+    fn fold(self, direction: Direction) -> Self {
+        let Self {
+            normal,
+            right,
+            down,
+        } = self;
+
+        match direction {
+            Direction::Up => Self {
+                normal: neg(down),
+                right,
+                down: normal,
+            },
+            Direction::Right => Self {
+                normal: right,
+                right: neg(normal),
+                down,
+            },
+            Direction::Down => Self {
+                normal: down,
+                right,
+                down: neg(normal),
+            },
+            Direction::Left => Self {
+                normal: neg(right),
+                right: normal,
+                down,
+            },
+        }
+    }
 
-        let i = match *direction {
-            RIGHT => in_face.y,
-            DOWN => SIDE_LEN - 1 - in_face.x,
-            LEFT => SIDE_LEN - 1 - in_face.y,
-            UP => in_face.x,
-            _ => unreachable!(),
-        };
+    /// 3D direction of leaving the face in `direction`.
+    fn edge(self, direction: Direction) -> Vec3 {
+        match direction {
+            Direction::Up => neg(self.down),
+            Direction::Right => self.right,
+            Direction::Down => self.down,
+            Direction::Left => neg(self.right),
+        }
+    }
+}
 
-        let in_face_delta = match next_direction {
-            RIGHT => Pos::new(0, i),
-            DOWN => Pos::new(SIDE_LEN - 1 - i, 0),
-            LEFT => Pos::new(SIDE_LEN - 1, SIDE_LEN - 1 - i),
-            UP => Pos::new(i, SIDE_LEN - 1),
-            _ => unreachable!(),
-        };
+fn neg([x, y, z]: Vec3) -> Vec3 {
+    [-x, -y, -z]
+}
 
-        let next = face * SIDE_LEN + in_face_delta;
+impl Cube {
+    /// Detect the faces of the net and glue their edges together.
+    ///
+    /// Returns `None` if the board is not a cube net.
+    fn fold(board: &Board) -> Option<Self> {
+        let area = board
+            .tiles
+            .iter()
+            .filter(|&&tile| tile != Tile::Closed)
+            .count() as i32;
 
-        match self.get(next) {
-            Tile::Wall => Wrap::Stop,
-            Tile::Open => {
-                *pos = next;
-                *direction = next_direction;
-                *delta = direction.delta();
+        let side_len = (area / 6).isqrt();
 
-                Wrap::Continue
-            }
-            Tile::Closed => unreachable!(),
+        if side_len == 0 || 6 * side_len * side_len != area {
+            return None;
         }
-    }
 
-    fn is_valid_pos(&self, pos: Pos) -> bool {
-        pos.x >= 0
-            && pos.x < self.width
-            && pos.y >= 0
-            && pos.y < self.tiles.len() as i32 / self.width
-    }
+        let height = board.tiles.len() as i32 / board.width;
 
-    fn get(&self, pos @ Pos { x, y }: Pos) -> Tile {
-        if self.is_valid_pos(pos) {
-            self.tiles[(y * self.width + x) as usize]
-        } else {
-            Tile::Closed
+        let faces: Vec<_> = (0..height / side_len)
+            .flat_map(|y| (0..board.width / side_len).map(move |x| Pos::new(x, y)))
+            .filter(|&face| board.get(face * side_len) != Tile::Closed)
+            .collect();
+
+        if faces.len() != 6 {
+            return None;
         }
-    }
 
-    fn parse(input: &str) -> Result<(Self, Path<'_>)> {
-        let (board, path) = input.split_once("\n\n").wrap_err("missing blank line")?;
+        // fold the net face by face, starting with the first one lying flat
+        let mut orientations = vec![None; 6];
 
-        let (height, width) = board.lines().fold((0, 0), |(height, width), line| {
-            (height + 1, width.max(line.len()))
+        orientations[0] = Some(Orientation {
+            normal: [0, 0, 1],
+            right: [1, 0, 0],
+            down: [0, 1, 0],
         });
 
-        let mut tiles = Vec::with_capacity(height * width);
+        let mut stack = vec![0];
+
+        while let Some(idx) = stack.pop() {
+            let orientation = orientations[idx]?;
+
+            for direction in Direction::ALL {
+                let neighbor = faces[idx] + direction.delta();
 
-        for line in board.lines().map(str::as_bytes) {
-            for &byte in line.iter() {
-                tiles.push(Tile::try_from(byte)?);
+                let Some(next) = faces.iter().position(|&face| face == neighbor) else {
+                    continue;
+                };
+
+                if orientations[next].is_none() {
+                    orientations[next] = Some(orientation.fold(direction));
+                    stack.push(next);
+                }
             }
+        }
 
-            let remaining = width - line.len();
+        let orientations: Vec<_> = orientations.into_iter().collect::<Option<_>>()?;
 
-            if remaining > 0 {
-                tiles.extend(iter::repeat(Tile::Closed).take(remaining))
+        // connected squares that overlap once folded, e.g. a strip, are no net
+        for (i, orientation) in orientations.iter().enumerate() {
+            if orientations[..i]
+                .iter()
+                .any(|other| other.normal == orientation.normal)
+            {
+                return None;
             }
         }
 
-        let this = Self {
-            tiles: tiles.into_boxed_slice(),
-            width: width as i32,
+        // leaving a face enters the face whose normal points along the edge
+        // and does so through that face's edge pointing back
+        let mut edges = Vec::with_capacity(6);
+
+        for orientation in orientations.iter() {
+            let mut face_edges = [(0, Direction::Up); 4];
+
+            for direction in Direction::ALL {
+                let edge = orientation.edge(direction);
+
+                let next = orientations.iter().position(|other| other.normal == edge)?;
+
+                let entry = Direction::ALL
+                    .into_iter()
+                    .find(|&entry| orientations[next].edge(entry) == orientation.normal)?;
+
+                face_edges[direction as usize] = (next, entry.reverse());
+            }
+
+            edges.push(face_edges);
+        }
+
+        Some(Self {
+            side_len,
+            faces,
+            edges,
+        })
+    }
+
+    fn wrap(
+        &self,
+        board: &Board,
+        pos: &mut Pos,
+        direction: &mut Direction,
+        delta: &mut Pos,
+    ) -> Option<Wrap> {
+        let side_len = self.side_len;
+
+        let face = self
+            .faces
+            .iter()
+            .position(|&face| face == *pos / side_len)?;
+
+        let (next_face, next_direction) = self.edges[face][*direction as usize];
+        let in_face = *pos % side_len;
+
+        // offset along the edge, clockwise around the face
+        let i = match *direction {
+            Direction::Right => in_face.y,
+            Direction::Down => side_len - 1 - in_face.x,
+            Direction::Left => side_len - 1 - in_face.y,
+            Direction::Up => in_face.x,
         };
 
-        Ok((this, Path(path.as_bytes())))
+        // glued edges run in opposite directions when going clockwise
+        let in_face_delta = match next_direction {
+            Direction::Right => Pos::new(0, i),
+            Direction::Down => Pos::new(side_len - 1 - i, 0),
+            Direction::Left => Pos::new(side_len - 1, side_len - 1 - i),
+            Direction::Up => Pos::new(i, side_len - 1),
+        };
+
+        let next = self.faces[next_face] * side_len + in_face_delta;
+
+        match board.get(next) {
+            Tile::Wall => Some(Wrap::Stop),
+            Tile::Open => {
+                *pos = next;
+                *direction = next_direction;
+                *delta = direction.delta();
+
+                Some(Wrap::Continue)
+            }
+            Tile::Closed => None,
+        }
     }
 }
 
@@ -222,39 +403,50 @@ enum Move {
     Turn(Turn),
 }
 
-enum Turn {
-    Left,
-    Right,
-}
+impl<'p> Path<'p> {
+    fn parse(input: &str, line: &'p str) -> Result<Self, ParseError> {
+        for (i, byte) in line.bytes().enumerate() {
+            match byte {
+                b'L' | b'R' => {}
+                // validate the whole number once it starts
+                b'0'..=b'9' if i == 0 || !line.as_bytes()[i - 1].is_ascii_digit() => {
+                    let len = line[i..].bytes().take_while(u8::is_ascii_digit).count();
+                    parse_at::<i16>(input, &line[i..i + len])?;
+                }
+                b'0'..=b'9' => {}
+                _ => {
+                    return Err(ParseError::at(
+                        input,
+                        &line[i..],
+                        "expected `L`, `R`, or digit",
+                    ))
+                }
+            }
+        }
+
+        Ok(Self(line.as_bytes()))
+    }
 
-impl Path<'_> {
     #[inline]
-    fn next(&mut self) -> Option<Result<Move>> {
+    fn next(&mut self) -> Option<Move> {
         let [first, rest @ ..] = self.0 else {
             return None;
         };
         self.0 = rest;
 
         match first {
-            b'L' => Some(Ok(Move::Turn(Turn::Left))),
-            b'R' => Some(Ok(Move::Turn(Turn::Right))),
-            b'0'..=b'9' => {
+            b'L' => Some(Move::Turn(Turn::Left)),
+            b'R' => Some(Move::Turn(Turn::Right)),
+            _ => {
                 let mut n = (*first & 0xF) as i16;
 
-                loop {
-                    match self.0 {
-                        [b'L' | b'R', ..] | [] => break,
-                        [next @ b'0'..=b'9', rest @ ..] => {
-                            n = n * 10 + (*next & 0xF) as i16;
-                            self.0 = rest;
-                        }
-                        [next, ..] => return Some(Err(eyre!("invalid digit `{}`", *next as char))),
-                    }
+                while let [next @ b'0'..=b'9', rest @ ..] = self.0 {
+                    n = n * 10 + (*next & 0xF) as i16;
+                    self.0 = rest;
                 }
 
-                Some(Ok(Move::Distance(n)))
+                Some(Move::Distance(n))
             }
-            _ => Some(Err(eyre!("invalid move `{}`", *first as char))),
         }
     }
 }
@@ -266,105 +458,86 @@ enum Tile {
     Closed,
 }
 
-impl TryFrom<u8> for Tile {
-    type Error = Report;
-
-    #[inline]
-    fn try_from(byte: u8) -> Result<Self, Self::Error> {
-        match byte {
-            b' ' => Ok(Self::Closed),
-            b'.' => Ok(Self::Open),
-            b'#' => Ok(Self::Wall),
-            _ => bail!("invalid tile `{}`", byte as char),
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5";
+
+        let solution = run(input).unwrap();
+        assert_eq!(solution.part1.to_string(), "6032");
+        assert_eq!(solution.part2.to_string(), "5031");
     }
-}
-
-#[derive(Copy, Clone, PartialEq, Eq)]
-struct Direction(u8);
 
-const RIGHT: Direction = Direction(0);
-const DOWN: Direction = Direction(1);
-const LEFT: Direction = Direction(2);
-const UP: Direction = Direction(3);
-
-impl Direction {
-    fn turn_right(self) -> Self {
-        Self((self.0 + 1) % 4)
-    }
-
-    fn turn_left(self) -> Self {
-        Self((self.0 + 3) % 4)
-    }
-
-    fn delta(self) -> Pos {
-        const DELTAS: [Pos; 4] = [
-            Pos::new(1, 0),
-            Pos::new(0, 1),
-            Pos::new(-1, 0),
-            Pos::new(0, -1),
+    #[test]
+    fn cube_nets() {
+        // all 11 nets, walking around the cube along each axis leads back
+        // to the start
+        let nets = [
+            "#...\n####\n#...",
+            "#...\n####\n.#..",
+            "#...\n####\n..#.",
+            "#...\n####\n...#",
+            ".#..\n####\n.#..",
+            ".#..\n####\n..#.",
+            "##..\n.###\n.#..",
+            "##..\n.###\n..#.",
+            "##..\n.###\n...#",
+            "##..\n.##.\n..##",
+            "###..\n..###",
         ];
 
-        get!(DELTAS, self.0 as usize)
-    }
-}
+        const SIDE_LEN: usize = 3;
 
-#[derive(Copy, Clone, PartialEq, Eq)]
-struct Pos {
-    x: i32,
-    y: i32,
-}
+        for net in nets {
+            let mut board = String::new();
 
-impl Pos {
-    const fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-}
-
-impl Add for Pos {
-    type Output = Self;
+            for row in net.lines() {
+                let line: String = row
+                    .chars()
+                    .flat_map(|c| [if c == '#' { '.' } else { ' ' }; SIDE_LEN])
+                    .collect();
 
-    #[inline]
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
-}
-
-impl Sub for Pos {
-    type Output = Self;
-
-    #[inline]
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-        }
-    }
-}
+                for _ in 0..SIDE_LEN {
+                    board.push_str(line.trim_end());
+                    board.push('\n');
+                }
+            }
 
-impl Mul<i32> for Pos {
-    type Output = Self;
+            let steps = 4 * SIDE_LEN;
+            let input = format!("{board}\n{steps}R{steps}R{steps}R{steps}R");
+            let solution = run(&input).unwrap();
 
-    #[inline]
-    fn mul(self, rhs: i32) -> Self::Output {
-        Self {
-            x: self.x * rhs,
-            y: self.y * rhs,
+            let start = net.find('#').unwrap() * SIDE_LEN;
+            let expected = 1000 + 4 * (start + 1);
+            assert_eq!(solution.part2.to_string(), expected.to_string(), "{net}");
         }
-    }
-}
 
-impl Rem<i32> for Pos {
-    type Output = Self;
+        assert!(run("....\n....\n\n1R1").is_err());
 
-    #[inline]
-    fn rem(self, rhs: i32) -> Self::Output {
-        Self {
-            x: self.x % rhs,
-            y: self.y % rhs,
+        // six connected faces that overlap when folded
+        for strip in [
+            "......\n\n1R1",
+            ".\n.\n.\n.\n.\n.\n\n1R1",
+            "....\n..\n\n1R1",
+        ] {
+            let err = run(strip).unwrap_err();
+            assert_eq!(err.to_string(), "board is not a cube net", "{strip}");
         }
     }
 }
@@ -4,8 +4,13 @@ use ahash::RandomState;
 
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 23,
+    title: "Unstable Diffusion",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let elves = parse_elves(input);
+    let elves = parse_elves(input)?;
     let mut state = State::new(elves);
 
     let p1 = part1(&mut state);
@@ -14,17 +19,22 @@ pub fn run(input: &str) -> Result<Solution> {
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn parse_elves(input: &str) -> HashSet<Pos, RandomState> {
-    input
-        .lines()
-        .zip(0..)
-        .flat_map(|(line, y)| {
-            line.bytes()
-                .zip(0..)
-                .filter(|(tile, _)| *tile == b'#')
-                .map(move |(_, x)| Pos { x, y })
-        })
-        .collect()
+fn parse_elves(input: &str) -> Result<HashSet<Pos, RandomState>, ParseError> {
+    let mut elves = HashSet::default();
+
+    for (line, y) in input.lines().map(str::trim_end).zip(0..) {
+        for ((i, tile), x) in line.bytes().enumerate().zip(0..) {
+            match tile {
+                b'#' => {
+                    elves.insert(Pos { x, y });
+                }
+                b'.' => {}
+                _ => return Err(ParseError::at(input, &line[i..], "expected `#` or `.`")),
+            }
+        }
+    }
+
+    Ok(elves)
 }
 
 fn part1(state: &mut State) -> i16 {
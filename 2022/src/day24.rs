@@ -1,61 +1,50 @@
 use std::{
-    cmp::Reverse,
-    collections::{BinaryHeap, HashSet},
+    collections::HashMap,
     fmt::{Display, Formatter, Result as FmtResult},
     ops::{Add, Index, IndexMut},
 };
 
 use ahash::RandomState;
+use aoc_rust::search::{self, Visited};
 
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 24,
+    title: "Blizzard Basin",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let (grid, start, end) = Grid::parse(input)?;
     let cycle = lcm(grid.width as u16 - 2, grid.height() as u16 - 2);
     let mut grids = vec![grid];
-    let mut seen = HashSet::default();
 
-    let p1 = dijkstra(start, end, &mut grids, cycle, 0, &mut seen);
-    let back = dijkstra(end, start, &mut grids, cycle, p1, &mut seen);
-    let p2 = dijkstra(start, end, &mut grids, cycle, back, &mut seen);
+    let p1 = fastest_path(start, end, &mut grids, cycle, 0).wrap_err(NO_PATH)?;
+    let back = fastest_path(end, start, &mut grids, cycle, p1).wrap_err(NO_PATH)?;
+    let p2 = fastest_path(start, end, &mut grids, cycle, back).wrap_err(NO_PATH)?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn dijkstra(
+const NO_PATH: &str = "no path through the valley";
+
+/// Minute at which `end` is reached when leaving `start` at minute `offset`.
+fn fastest_path(
     start: Pos,
     end: Pos,
     grids: &mut Vec<Grid>,
     cycle: u16,
     offset: u16,
-    seen: &mut HashSet<(Pos, u16), RandomState>,
-) -> u16 {
-    #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
-    struct State {
-        steps: u16,
-        pos: Pos,
-    }
-
-    let mut heap = BinaryHeap::new();
-
-    let state = State {
-        pos: start,
-        steps: offset,
-    };
-
-    let mut shortest = u16::MAX;
-    heap.push((Reverse(start.dist(end)), state));
-    seen.clear();
-
-    while let Some((Reverse(dist), State { pos, steps })) = heap.pop() {
-        if dist == 0 {
-            shortest = shortest.min(steps);
-
-            continue;
-        } else if shortest < steps || !seen.insert((pos, steps % cycle)) {
-            continue;
-        }
-
+) -> Option<u16> {
+    const DELTAS: [Pos; 5] = [
+        Pos { x: 0, y: -1 },
+        Pos { x: 0, y: 1 },
+        Pos { x: 1, y: 0 },
+        Pos { x: -1, y: 0 },
+        Pos { x: 0, y: 0 },
+    ];
+
+    let successors = |&(pos, steps): &(Pos, u16)| {
         let grid_idx = (steps as usize + 1) % cycle as usize;
 
         let grid = match grids.get(grid_idx) {
@@ -69,31 +58,23 @@ fn dijkstra(
             }
         };
 
-        const DELTAS: [Pos; 5] = [
-            Pos { x: 0, y: -1 },
-            Pos { x: 0, y: 1 },
-            Pos { x: 1, y: 0 },
-            Pos { x: -1, y: 0 },
-            Pos { x: 0, y: 0 },
-        ];
-
-        for delta in DELTAS {
-            let next = pos + delta;
-
-            if !(grid.is_valid(next) && grid[next].is_empty()) {
-                continue;
-            }
-
-            let state = State {
-                pos: next,
-                steps: steps + 1,
-            };
+        DELTAS
+            .map(|delta| {
+                Some(pos + delta).filter(|&next| grid.is_valid(next) && grid[next].is_empty())
+            })
+            .into_iter()
+            .flatten()
+            .map(move |next| (next, steps + 1))
+    };
 
-            heap.push((Reverse(state.pos.dist(end)), state));
-        }
-    }
+    // blizzards repeat every `cycle` minutes
+    let visited = Visited::new(
+        |&(pos, steps): &(Pos, u16)| (pos, steps % cycle),
+        HashMap::<_, _, RandomState>::default(),
+    );
 
-    shortest
+    search::bfs_with((start, offset), successors, |&(pos, _)| pos == end, visited)
+        .map(|path| offset + path.cost as u16)
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -102,12 +83,6 @@ struct Pos {
     y: i16,
 }
 
-impl Pos {
-    fn dist(self, other: Self) -> u16 {
-        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
-    }
-}
-
 impl Add for Pos {
     type Output = Self;
 
@@ -231,48 +206,73 @@ impl Grid {
         self.inner = next;
     }
 
-    fn parse(input: &str) -> Result<(Self, Pos, Pos)> {
-        let mut lines = input.lines().map(str::as_bytes);
+    fn parse(input: &str) -> Result<(Self, Pos, Pos), ParseError> {
+        let lines: Vec<_> = input
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .collect();
 
-        fn extend_from_line(line: &[u8], grid: &mut Vec<Space>) -> Result<()> {
-            grid.reserve(line.len());
+        let width = lines.first().map_or(0, |line| line.len());
+
+        if width < 3 || lines.len() < 3 {
+            return Err(ParseError::new("valley must be at least 3x3 tiles"));
+        }
+
+        let inner_area = (width - 2) * (lines.len() - 2);
+
+        if inner_area > u16::MAX as usize {
+            return Err(ParseError::new("valley is too large"));
+        }
+
+        let mut grid = Vec::with_capacity(width * lines.len());
+
+        for (y, line) in lines.iter().enumerate() {
+            if line.len() != width {
+                let msg = format!("expected {width} tiles per row");
+
+                return Err(ParseError::at(input, line, msg));
+            }
+
+            let is_border_row = y == 0 || y == lines.len() - 1;
+
+            for (x, byte) in line.bytes().enumerate() {
+                let is_border = is_border_row || x == 0 || x == width - 1;
 
-            for byte in line {
                 let tile = match byte {
                     b'#' => Tile::Wall,
                     b'.' => Tile::Empty,
-                    b'>' => Tile::BlizzardRight,
-                    b'<' => Tile::BlizzardLeft,
-                    b'^' => Tile::BlizzardUp,
-                    b'v' => Tile::BlizzardDown,
-                    _ => bail!("unexpected tile `{}`", *byte as char),
+                    b'>' if !is_border => Tile::BlizzardRight,
+                    b'<' if !is_border => Tile::BlizzardLeft,
+                    b'^' if !is_border => Tile::BlizzardUp,
+                    b'v' if !is_border => Tile::BlizzardDown,
+                    b'>' | b'<' | b'^' | b'v' => {
+                        let msg = "blizzards must be inside the walls";
+
+                        return Err(ParseError::at(input, &line[x..], msg));
+                    }
+                    _ => {
+                        let msg = "expected `#`, `.`, `>`, `<`, `^`, or `v`";
+
+                        return Err(ParseError::at(input, &line[x..], msg));
+                    }
                 };
 
+                if is_border && !is_border_row && tile != Tile::Wall {
+                    return Err(ParseError::at(input, &line[x..], "expected wall"));
+                }
+
                 let tiles = [tile, Tile::Empty, Tile::Empty, Tile::Empty];
                 grid.push(Space { tiles });
             }
-
-            Ok(())
         }
 
-        let (mut grid, width) = if let Some(line) = lines.next() {
-            let mut grid = Vec::new();
-            extend_from_line(line, &mut grid)?;
-
-            (grid, line.len())
-        } else {
-            (Vec::new(), 0)
-        };
-
         let start = grid
             .iter()
+            .take(width)
             .position(|space| space.tiles[0] == Tile::Empty)
             .map(|x| Pos { x: x as i16, y: 0 })
-            .wrap_err("missing empty tile in first line")?;
-
-        for line in lines {
-            extend_from_line(line, &mut grid)?;
-        }
+            .context_at(input, lines[0], "missing empty tile in first line")?;
 
         let end = grid
             .iter()
@@ -283,7 +283,11 @@ impl Grid {
                 x: (width - x - 1) as i16,
                 y: (grid.len() / width - 1) as i16,
             })
-            .wrap_err("missing empty tile in last line")?;
+            .context_at(
+                input,
+                lines[lines.len() - 1],
+                "missing empty tile in last line",
+            )?;
 
         let grid = Self {
             inner: grid.into(),
@@ -1,26 +1,39 @@
 use crate::prelude::*;
 
+aoc_rust::register_day! {
+    day: 25,
+    title: "Full of Hot Air",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let p1 = input
         .lines()
-        .map(str::as_bytes)
-        .map(to_snafu)
+        .map(str::trim_end)
+        .map(|line| to_snafu(input, line))
         .try_fold(0, |sum, res| res.map(|n| sum + n))
         .map(from_snafu)?;
 
     Ok(Solution::new().part1(p1).part2("x".to_string()))
 }
 
-fn to_snafu(line: &[u8]) -> Result<i64> {
-    line.iter().try_fold(0, |n, byte| {
+fn to_snafu(input: &str, line: &str) -> Result<i64, ParseError> {
+    line.bytes().enumerate().try_fold(0_i64, |n, (i, byte)| {
         let term = match byte {
             b'0'..=b'2' => (byte & 0xF) as i64,
             b'-' => -1,
             b'=' => -2,
-            _ => bail!("invalid digit `{}`", *byte as char),
+            _ => {
+                return Err(ParseError::at(
+                    input,
+                    &line[i..],
+                    "expected `0`, `1`, `2`, `-`, or `=`",
+                ))
+            }
         };
 
-        Ok(n * 5 + term)
+        n.checked_mul(5)
+            .and_then(|n| n.checked_add(term))
+            .context_at(input, line, "number is too large")
     })
 }
 
@@ -35,6 +35,7 @@ modules! {
 }
 
 pub mod prelude {
+    pub use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
     pub use aoc_rust::Solution;
     pub use eyre::{ContextCompat, Report, Result, WrapErr};
 }
@@ -67,3 +68,9 @@ macro_rules! modules {
         }
     }
 }
+
+aoc_rust::register_year! {
+    2022:
+        day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+        day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+}
@@ -1,11 +1,12 @@
+use aoc_rust::timing;
+
 fn main() {
-    let start = std::time::Instant::now();
+    let (res, timings) = timing::time(aoc22::current::run);
 
-    match aoc22::current::run() {
+    match res {
         Ok(solution) => {
-            let elapsed = start.elapsed();
             print!("{solution}");
-            println!("Elapsed: {elapsed:?}");
+            println!("Elapsed: {timings}");
         }
         Err(err) => eprintln!("{err:?}"),
     }
@@ -1,13 +1,19 @@
 use std::str::Chars;
 
+use aoc_rust::timing::{self, Phase};
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 1,
+    title: "Trebuchet?!",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input));
+    let p2 = timing::phase(Phase::Part2, || part2(input));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
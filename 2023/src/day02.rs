@@ -1,17 +1,27 @@
-use std::{slice::Iter, str::FromStr};
+use std::slice::Iter;
 
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
-use eyre::{ContextCompat, Report, Result, WrapErr};
+use eyre::Result;
 
-pub fn run(input: &str) -> Result<Solution> {
-    let games = input
-        .trim()
-        .lines()
-        .map(str::parse)
-        .collect::<Result<Vec<Game>>>()?;
+aoc_rust::register_day! {
+    day: 2,
+    title: "Cube Conundrum",
+}
 
-    let p1 = part1(&games);
-    let p2 = part2(&games);
+pub fn run(input: &str) -> Result<Solution> {
+    let games = timing::phase(Phase::Parse, || {
+        input
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .map(|line| Game::parse(input, line))
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+
+    let p1 = timing::phase(Phase::Part1, || part1(&games));
+    let p2 = timing::phase(Phase::Part2, || part2(&games));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -33,6 +43,18 @@ struct Game {
 }
 
 impl Game {
+    fn parse(input: &str, game: &str) -> Result<Self, ParseError> {
+        let (id, suffix) = game
+            .strip_prefix("Game ")
+            .and_then(|suffix| suffix.split_once(": "))
+            .context_at(input, game, "expected `Game <id>: <subsets>`")?;
+
+        let id = parse_at(input, id)?;
+        let subsets = Subsets::parse(input, suffix)?;
+
+        Ok(Self { id, subsets })
+    }
+
     fn is_possible(&self) -> bool {
         self.subsets
             .iter()
@@ -69,15 +91,19 @@ enum Color {
     Blue,
 }
 
-impl FromStr for Color {
-    type Err = Report;
-
-    fn from_str(color: &str) -> Result<Self, Self::Err> {
+impl Color {
+    fn parse(input: &str, color: &str) -> Result<Self, ParseError> {
         let color = match color {
             "red" => Self::Red,
             "green" => Self::Green,
             "blue" => Self::Blue,
-            _ => eyre::bail!("invalid color `{color}`"),
+            _ => {
+                return Err(ParseError::at(
+                    input,
+                    color,
+                    "expected `red`, `green`, or `blue`",
+                ))
+            }
         };
 
         Ok(color)
@@ -89,17 +115,15 @@ struct Cubes {
     color: Color,
 }
 
-impl FromStr for Cubes {
-    type Err = Report;
-
-    fn from_str(cubes: &str) -> Result<Self, Self::Err> {
-        let (count, color) = cubes.split_once(' ').wrap_err("missing whitespace")?;
+impl Cubes {
+    fn parse(input: &str, cubes: &str) -> Result<Self, ParseError> {
+        let (count, color) =
+            cubes
+                .split_once(' ')
+                .context_at(input, cubes, "expected `<count> <color>`")?;
 
-        let count = count
-            .parse()
-            .map_err(|_| eyre::eyre!("incorrect count `{count}`"))?;
-
-        let color = color.parse().wrap_err("incorrect color")?;
+        let count = parse_at(input, count)?;
+        let color = Color::parse(input, color)?;
 
         Ok(Self { count, color })
     }
@@ -109,15 +133,12 @@ struct Reveal {
     cubes: Vec<Cubes>,
 }
 
-impl FromStr for Reveal {
-    type Err = Report;
-
-    fn from_str(reveal: &str) -> Result<Self, Self::Err> {
+impl Reveal {
+    fn parse(input: &str, reveal: &str) -> Result<Self, ParseError> {
         reveal
             .split(", ")
-            .map(str::parse)
-            .collect::<Result<_>>()
-            .wrap_err("invalid cubes")
+            .map(|cubes| Cubes::parse(input, cubes))
+            .collect::<Result<_, _>>()
             .map(|cubes| Self { cubes })
     }
 }
@@ -139,30 +160,12 @@ impl Subsets {
     fn iter(&self) -> Iter<'_, Reveal> {
         self.reveals.iter()
     }
-}
 
-impl FromStr for Subsets {
-    type Err = Report;
-
-    fn from_str(subsets: &str) -> Result<Self, Self::Err> {
+    fn parse(input: &str, subsets: &str) -> Result<Self, ParseError> {
         subsets
             .split("; ")
-            .map(str::parse)
-            .collect::<Result<_>>()
-            .wrap_err("invalid reveal")
+            .map(|reveal| Reveal::parse(input, reveal))
+            .collect::<Result<_, _>>()
             .map(|reveals| Self { reveals })
     }
 }
-
-impl FromStr for Game {
-    type Err = Report;
-
-    fn from_str(game: &str) -> Result<Self, Self::Err> {
-        let suffix = game.strip_prefix("Game ").wrap_err("missing prefix")?;
-        let (id, suffix) = suffix.split_once(": ").wrap_err("missing colon")?;
-        let id = id.parse().map_err(|_| eyre::eyre!("incorrect id `{id}`"))?;
-        let subsets = suffix.parse().wrap_err("invalid subsets")?;
-
-        Ok(Self { id, subsets })
-    }
-}
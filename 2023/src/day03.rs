@@ -1,13 +1,20 @@
 use std::ops::Index;
 
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::ParseError;
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 3,
+    title: "Gear Ratios",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    let schematic = timing::phase(Phase::Parse, || Schematic::parse(input))?;
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(&schematic));
+    let p2 = timing::phase(Phase::Part2, || part2(&schematic));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -15,16 +22,34 @@ pub fn run(input: &str) -> Result<Solution> {
 struct Schematic<'i> {
     width: usize,
     height: usize,
-    input: &'i [u8],
+    rows: Vec<&'i [u8]>,
 }
 
 impl<'i> Schematic<'i> {
-    fn new(input: &'i str) -> Self {
-        Self {
-            width: input.find('\n').unwrap_or(input.len()),
-            height: input.lines().count(),
-            input: input.as_bytes(),
+    fn parse(input: &'i str) -> Result<Self, ParseError> {
+        let rows: Vec<_> = input
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        let width = rows.first().map_or(0, |row| row.len());
+
+        if width == 0 {
+            return Err(ParseError::new("empty schematic"));
         }
+
+        if let Some(row) = rows.iter().find(|row| row.len() != width) {
+            let msg = format!("expected {width} characters per row");
+
+            return Err(ParseError::at(input, row, msg));
+        }
+
+        Ok(Self {
+            width,
+            height: rows.len(),
+            rows: rows.into_iter().map(str::as_bytes).collect(),
+        })
     }
 }
 
@@ -32,21 +57,17 @@ impl Index<usize> for Schematic<'_> {
     type Output = [u8];
 
     fn index(&self, index: usize) -> &Self::Output {
-        let y = index * (self.width + 1);
-
-        &self.input[y..][..self.width]
+        self.rows[index]
     }
 }
 
-fn part1(input: &str) -> u32 {
-    let schematic = Schematic::new(input);
-
+fn part1(schematic: &Schematic<'_>) -> u32 {
     let mut sum = 0;
 
-    for (i, line) in input.lines().map(str::as_bytes).enumerate() {
+    for (i, line) in schematic.rows.iter().enumerate() {
         let mut start = 0;
 
-        while start < line.len() - 1 {
+        while start < line.len() {
             if !line[start].is_ascii_digit() {
                 start += 1;
 
@@ -86,15 +107,13 @@ fn part1(input: &str) -> u32 {
     sum
 }
 
-fn part2(input: &str) -> u32 {
-    let schematic = Schematic::new(input);
-
+fn part2(schematic: &Schematic<'_>) -> u32 {
     let mut sum = 0;
 
-    for (y, line) in input.lines().enumerate() {
-        for (x, byte) in line.bytes().enumerate() {
-            if byte == b'*' {
-                sum += handle_gear(&schematic, x, y);
+    for (y, line) in schematic.rows.iter().enumerate() {
+        for (x, byte) in line.iter().enumerate() {
+            if *byte == b'*' {
+                sum += handle_gear(schematic, x, y);
             }
         }
     }
@@ -107,7 +126,7 @@ fn handle_gear(schematic: &Schematic<'_>, x: usize, y: usize) -> u32 {
     let mut second = None;
 
     let x_range_start = x.saturating_sub(1);
-    let x_range_end = if x == schematic.width - 2 { x } else { x + 1 };
+    let x_range_end = if x == schematic.width - 1 { x } else { x + 1 };
 
     let y_range_start = y.saturating_sub(1);
     let y_range_end = if y == schematic.height - 1 { y } else { y + 1 };
@@ -1,57 +1,72 @@
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
-use eyre::{ContextCompat, Result, WrapErr};
+use eyre::Result;
 
-pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+aoc_rust::register_day! {
+    day: 4,
+    title: "Scratchcards",
+}
 
-    let p1 = part1(input)?;
-    let p2 = part2(input)?;
+pub fn run(input: &str) -> Result<Solution> {
+    let matches = timing::phase(Phase::Parse, || {
+        input
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .map(|line| parse_matches(input, line))
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+
+    let p1 = timing::phase(Phase::Part1, || part1(&matches));
+    let p2 = timing::phase(Phase::Part2, || part2(&matches));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> Result<u32> {
+fn part1(matches: &[u8]) -> u32 {
     let mut sum = 0;
 
-    for line in input.lines() {
-        if let Some(matches) = parse_matches(line)?.checked_sub(1) {
+    for matches in matches {
+        if let Some(matches) = matches.checked_sub(1) {
             sum += 1 << matches;
         }
     }
 
-    Ok(sum)
+    sum
 }
 
-fn part2(input: &str) -> Result<usize> {
-    let matches = input
-        .lines()
-        .map(parse_matches)
-        .collect::<Result<Vec<_>>>()
-        .wrap_err("invalid cards")?;
-
+fn part2(matches: &[u8]) -> usize {
     let mut counts = vec![1; matches.len()];
 
     for (i, matches) in matches.iter().enumerate() {
-        for j in 1..=*matches as usize {
-            counts[i + j] += counts[i];
+        let count = counts[i];
+
+        // copies past the end of the table are dropped
+        for next in counts.iter_mut().skip(i + 1).take(*matches as usize) {
+            *next += count;
         }
     }
 
-    Ok(counts.iter().sum())
+    counts.iter().sum()
 }
 
-fn parse_matches(line: &str) -> Result<u8> {
-    let (_, suffix) = line.split_once(':').wrap_err("missing colon")?;
-    let (winning, owned) = suffix.split_once(" | ").wrap_err("missing split")?;
+fn parse_matches(input: &str, line: &str) -> Result<u8, ParseError> {
+    let (winning, owned) = line
+        .split_once(':')
+        .and_then(|(_, suffix)| suffix.split_once(" | "))
+        .context_at(input, line, "expected `Card <id>: <numbers> | <numbers>`")?;
 
     let winning = winning
         .split(' ')
         .filter(|n| !n.is_empty())
-        .map(str::parse)
-        .collect::<Result<Vec<u8>, _>>()
-        .wrap_err("invalid winning numbers")?;
+        .map(|n| parse_at(input, n))
+        .collect::<Result<Vec<u8>, _>>()?;
 
-    let owned = owned.split(' ').filter(|n| !n.is_empty()).map(str::parse);
+    let owned = owned
+        .split(' ')
+        .filter(|n| !n.is_empty())
+        .map(|n| parse_at(input, n));
 
     let mut matches = 0;
 
@@ -1,36 +1,55 @@
-use std::{cmp, mem, ops::Range, str::FromStr};
+use std::{cmp, mem, ops::Range};
 
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
-use eyre::{ContextCompat, Report, Result, WrapErr};
+use eyre::Result;
 
-pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+aoc_rust::register_day! {
+    day: 5,
+    title: "If You Give A Seed A Fertilizer",
+}
 
-    let (seeds, maps) = parse_input(input)?;
+pub fn run(input: &str) -> Result<Solution> {
+    let (seeds, maps) = timing::phase(Phase::Parse, || parse_input(input))?;
 
-    let p1 = part1(&seeds, &maps);
-    let p2 = part2(&seeds, &maps);
+    let p1 = timing::phase(Phase::Part1, || part1(&seeds, &maps));
+    let p2 = timing::phase(Phase::Part2, || part2(&seeds, &maps));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn parse_input(input: &str) -> Result<(Vec<u64>, Vec<Map>)> {
-    let mut split = input.split("\n\n");
+fn parse_input(input: &str) -> Result<(Vec<u64>, Vec<Map>), ParseError> {
+    let mut lines = input
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty());
 
-    let seeds = split
+    let seeds_line = lines
         .next()
-        .wrap_err("missing seeds line")?
+        .ok_or_else(|| ParseError::new("missing seeds line"))?;
+
+    let seeds = seeds_line
         .strip_prefix("seeds: ")
-        .wrap_err("invalid seeds prefix")?
+        .context_at(input, seeds_line, "expected `seeds: <seeds>`")?
         .split(' ')
-        .map(str::parse)
-        .collect::<Result<_, _>>()
-        .wrap_err("invalid seed value")?;
-
-    let maps = split
-        .map(str::parse)
-        .collect::<Result<Vec<_>, _>>()
-        .wrap_err("invalid map")?;
+        .filter(|seed| !seed.is_empty())
+        .map(|seed| parse_at(input, seed))
+        .collect::<Result<_, _>>()?;
+
+    let mut maps: Vec<Map> = Vec::new();
+
+    for line in lines {
+        if line.ends_with(':') {
+            maps.push(Map {
+                entries: Vec::new(),
+            });
+        } else if let Some(map) = maps.last_mut() {
+            map.entries.push(Entry::parse(input, line)?);
+        } else {
+            return Err(ParseError::at(input, line, "expected `<name> map:`"));
+        }
+    }
 
     Ok((seeds, maps))
 }
@@ -51,7 +70,7 @@ fn part2(seeds: &[u64], maps: &[Map]) -> u64 {
         .filter_map(|chunk| {
             // false positive, a vec of a single range really is what's required
             #[allow(clippy::single_range_in_vec_init)]
-            let ranges = vec![chunk[0]..chunk[0] + chunk[1]];
+            let ranges = vec![chunk[0]..chunk[0].saturating_add(chunk[1])];
 
             maps.iter()
                 .fold(ranges, |ranges, map| map.apply_range(ranges, &mut bufs))
@@ -117,20 +136,6 @@ impl Map {
     }
 }
 
-impl FromStr for Map {
-    type Err = Report;
-
-    fn from_str(section: &str) -> Result<Self, Self::Err> {
-        section
-            .lines()
-            .skip(1)
-            .map(str::parse)
-            .collect::<Result<Vec<_>, _>>()
-            .map(|entries| Self { entries })
-            .wrap_err("invalid entry")
-    }
-}
-
 struct Entry {
     dst: u64,
     src: Range<u64>,
@@ -146,20 +151,31 @@ impl Entry {
     }
 }
 
-impl FromStr for Entry {
-    type Err = Report;
-
-    fn from_str(line: &str) -> Result<Self, Self::Err> {
-        let mut split = line.split(' ').map(str::parse).flat_map(Result::ok);
-
-        split
-            .next()
-            .zip(split.next())
-            .zip(split.next())
-            .map(|((dst, src), len)| Self {
-                dst,
-                src: src..src + len,
-            })
-            .wrap_err("invalid entry value")
+impl Entry {
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let mut split = line.split(' ');
+
+        let (Some(dst), Some(src), Some(len), None) =
+            (split.next(), split.next(), split.next(), split.next())
+        else {
+            return Err(ParseError::at(input, line, "expected `<dst> <src> <len>`"));
+        };
+
+        let dst: u64 = parse_at(input, dst)?;
+        let src: u64 = parse_at(input, src)?;
+        let len = parse_at(input, len)?;
+
+        if src.checked_add(len).zip(dst.checked_add(len)).is_none() {
+            return Err(ParseError::at(
+                input,
+                line,
+                "range exceeds the maximum value",
+            ));
+        }
+
+        Ok(Self {
+            dst,
+            src: src..src + len,
+        })
     }
 }
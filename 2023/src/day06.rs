@@ -1,56 +1,76 @@
-use std::num::ParseIntError;
-
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
-use eyre::{ContextCompat, Report, Result, WrapErr};
+use eyre::Result;
+
+aoc_rust::register_day! {
+    day: 6,
+    title: "Wait For It",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    let (times, dists) = timing::phase(Phase::Parse, || parse_lines(input))?;
 
-    let p1 = part1(input)?;
-    let p2 = part2(input)?;
+    let p1 = timing::phase(Phase::Part1, || part1(input, times, dists))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input, times, dists))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> Result<u64> {
-    fn parse_line<'l>(
-        line: &'l str,
-        prefix: &str,
-    ) -> impl Iterator<Item = Result<u64, ParseIntError>> + 'l {
-        line.trim_start_matches(prefix)
-            .trim_start()
-            .split(' ')
+/// Returns the numbers of the `Time:` and `Distance:` lines.
+fn parse_lines(input: &str) -> Result<(&str, &str), ParseError> {
+    let mut lines = input
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty());
+
+    let mut parse_line = |prefix: &str| {
+        let line = lines
+            .next()
+            .ok_or_else(|| ParseError::new(format!("missing `{prefix}` line")))?;
+
+        line.strip_prefix(prefix)
+            .context_at(input, line, format!("expected `{prefix} <numbers>`"))
+    };
+
+    let times = parse_line("Time:")?;
+    let dists = parse_line("Distance:")?;
+
+    Ok((times, dists))
+}
+
+fn part1(input: &str, times: &str, dists: &str) -> Result<u64, ParseError> {
+    let parse_line = |line: &str| {
+        line.split(' ')
             .filter(|n| !n.is_empty())
-            .map(str::parse)
-    }
+            .map(|n| parse_at(input, n))
+            .collect::<Result<Vec<u64>, _>>()
+    };
 
-    let (time, dist) = input.split_once('\n').wrap_err("invalid input lines")?;
-    let times = parse_line(time, "Time:");
-    let dists = parse_line(dist, "Distance:");
+    let times = parse_line(times)?;
+    let dists = parse_line(dists)?;
 
-    times
+    if times.len() != dists.len() {
+        return Err(ParseError::new("expected as many distances as times"));
+    }
+
+    Ok(times
+        .into_iter()
         .zip(dists)
-        .map(|(time, dist)| Ok(count(time?, dist?)))
-        .try_fold(1, |prod, n| Ok::<_, ParseIntError>(prod * n?))
-        .wrap_err("invalid integer")
+        .map(|(time, dist)| count(time, dist))
+        .product())
 }
 
-fn part2(input: &str) -> Result<u64> {
-    fn parse_line(line: &str, prefix: &str) -> Result<u64> {
-        line.trim_start_matches(prefix)
-            .trim_start()
-            .bytes()
-            .filter_map(|byte| match byte {
-                b' ' => None,
-                b'0'..=b'9' => Some(Ok(byte)),
-                _ => Some(Err(eyre::eyre!("invalid digit byte `{byte}`"))),
-            })
-            .try_fold(0, |n, byte| Ok::<_, Report>(n * 10 + (byte? & 0xF) as u64))
-    }
+fn part2(input: &str, times: &str, dists: &str) -> Result<u64, ParseError> {
+    let parse_line = |line: &str| {
+        let digits: String = line.split(' ').collect();
+
+        parse_at::<u64>(&digits, &digits)
+            .map_err(|_| ParseError::at(input, line.trim(), "expected number split by spaces"))
+    };
 
-    let (time, dist) = input.split_once('\n').wrap_err("invalid input lines")?;
-    let time = parse_line(time, "Time:").wrap_err("invalid time")?;
-    let dist = parse_line(dist, "Distance:").wrap_err("invalid dist")?;
+    let time = parse_line(times)?;
+    let dist = parse_line(dists)?;
 
     Ok(count(time, dist))
 }
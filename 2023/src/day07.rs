@@ -1,13 +1,18 @@
-use std::{marker::PhantomData, str::FromStr};
+use std::marker::PhantomData;
 
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::{parse_at, ParseError};
 use aoc_rust::Solution;
-use eyre::{ContextCompat, Report, Result, WrapErr};
+use eyre::Result;
 
-pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+aoc_rust::register_day! {
+    day: 7,
+    title: "Camel Cards",
+}
 
-    let p1 = part1(input)?;
-    let p2 = part2(input)?;
+pub fn run(input: &str) -> Result<Solution> {
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -22,13 +27,15 @@ fn part2(input: &str) -> Result<u32> {
 
 fn solve<Part>(input: &str) -> Result<u32>
 where
-    Hand<Part>: FromStr<Err = Report>,
+    Hand<Part>: HandExt<Part>,
+    Card<Part>: Copy + TryFrom<u8>,
 {
     let mut hands = input
         .lines()
-        .map(str::parse)
-        .collect::<Result<Vec<Hand<Part>>>>()
-        .wrap_err("invalid hand")?;
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .map(|line| Hand::parse(input, line))
+        .collect::<Result<Vec<Hand<Part>>, _>>()?;
 
     hands.sort_unstable_by_key(|hand| hand.value);
 
@@ -104,34 +111,29 @@ impl HandExt<Part2> for Hand<Part2> {
     }
 }
 
-impl<Part> FromStr for Hand<Part>
+impl<Part> Hand<Part>
 where
     Hand<Part>: HandExt<Part>,
-    Card<Part>: Copy + TryFrom<u8, Error = Report>,
+    Card<Part>: Copy + TryFrom<u8>,
 {
-    type Err = Report;
-
-    fn from_str(line: &str) -> Result<Self, Self::Err> {
-        let mut bytes = line.bytes();
-
-        let cards = [
-            Card::try_from(bytes.next().wrap_err("missing card")?)?,
-            Card::try_from(bytes.next().wrap_err("missing card")?)?,
-            Card::try_from(bytes.next().wrap_err("missing card")?)?,
-            Card::try_from(bytes.next().wrap_err("missing card")?)?,
-            Card::try_from(bytes.next().wrap_err("missing card")?)?,
-        ];
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let (cards, bid) = match line.split_once(' ') {
+            Some((cards, bid)) if cards.len() == 5 => (cards, bid),
+            _ => return Err(ParseError::at(input, line, "expected `<5 cards> <bid>`")),
+        };
 
-        eyre::ensure!(bytes.next() == Some(b' '), "missing whitespace");
+        let mut parsed = [Card::new(0); 5];
 
-        let bid = bytes.try_fold(0, |num, byte| match byte {
-            b'0'..=b'9' => Ok(num * 10 + (byte & 0xF) as u32),
-            _ => Err(eyre::eyre!("invalid digit byte `{byte}`")),
-        })?;
+        for ((i, byte), card) in cards.bytes().enumerate().zip(parsed.iter_mut()) {
+            *card = Card::try_from(byte).map_err(|_| {
+                ParseError::at(input, &cards[i..], "expected one of `23456789TJQKA`")
+            })?;
+        }
 
-        let combo = Self::combo(cards);
+        let bid = parse_at(input, bid)?;
+        let combo = Self::combo(parsed);
 
-        let value = cards
+        let value = parsed
             .into_iter()
             .fold(combo as u32, |value, card| (value << 4) | card.0 as u32);
 
@@ -152,7 +154,7 @@ impl<Part> Card<Part> {
 }
 
 impl TryFrom<u8> for Card<Part1> {
-    type Error = Report;
+    type Error = ();
 
     fn try_from(byte: u8) -> Result<Self, Self::Error> {
         let card = match byte {
@@ -162,7 +164,7 @@ impl TryFrom<u8> for Card<Part1> {
             b'Q' => 12,
             b'K' => 13,
             b'A' => 14,
-            _ => eyre::bail!("invalid card byte `{byte}`"),
+            _ => return Err(()),
         };
 
         Ok(Self::new(card))
@@ -184,7 +186,7 @@ impl Card<Part2> {
 }
 
 impl TryFrom<u8> for Card<Part2> {
-    type Error = Report;
+    type Error = ();
 
     fn try_from(byte: u8) -> Result<Self, Self::Error> {
         let card = match byte {
@@ -194,7 +196,7 @@ impl TryFrom<u8> for Card<Part2> {
             b'Q' => 12,
             b'K' => 13,
             b'A' => 14,
-            _ => eyre::bail!("invalid card byte `{byte}`"),
+            _ => return Err(()),
         };
 
         Ok(Self::new(card))
@@ -1,36 +1,70 @@
 use std::ops::ControlFlow;
 
-use aoc_rust::{util::numbers, Solution};
-use eyre::{ContextCompat, Result, WrapErr};
+use aoc_rust::util::parse::{ParseContext, ParseError};
+use aoc_rust::{
+    timing::{self, Phase},
+    util::cycle,
+    Solution,
+};
+use eyre::Result;
+
+aoc_rust::register_day! {
+    day: 8,
+    title: "Haunted Wasteland",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
-    let mut lines = input.trim().lines();
+    let mut lines = input
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty());
 
-    let dirs = lines.next().wrap_err("missing instructions")?;
+    let dirs = lines
+        .next()
+        .ok_or_else(|| ParseError::new("missing instructions"))?;
 
-    lines.next().wrap_err("missing blank line")?;
+    if let Some(i) = dirs.bytes().position(|byte| byte != b'L' && byte != b'R') {
+        return Err(ParseError::at(input, &dirs[i..], "expected `L` or `R`").into());
+    }
 
     let mut instructions = lines
-        .map(Instruction::from_str)
-        .collect::<Result<Vec<_>>>()
-        .wrap_err("invalid instruction")?;
+        .map(|line| Instruction::parse(input, line))
+        .collect::<Result<Vec<_>, _>>()?;
 
     instructions.sort_unstable_by_key(|instr| instr.from);
 
-    let p1 = part1(dirs.bytes().cycle(), &instructions)?;
-    let p2 = part2(dirs.bytes().cycle(), &instructions)?;
+    for instr in instructions.iter() {
+        for node in [instr.left, instr.right] {
+            if instructions
+                .binary_search_by_key(&node, |instr| instr.from)
+                .is_err()
+            {
+                return Err(ParseError::at(input, node, "unknown node").into());
+            }
+        }
+    }
+
+    timing::lap(Phase::Parse);
+
+    let p1 = timing::phase(Phase::Part1, || part1(dirs.bytes().cycle(), &instructions))?;
+    let p2 = timing::phase(Phase::Part2, || part2(dirs.bytes().cycle(), &instructions));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
 fn part1(dirs: impl Iterator<Item = u8>, instructions: &[Instruction]) -> Result<u32> {
+    if instructions
+        .binary_search_by_key(&"AAA", |instr| instr.from)
+        .is_err()
+    {
+        eyre::bail!("missing node `AAA`");
+    }
+
     let steps = dirs.zip(1..).try_fold("AAA", |mut curr, (dir, step)| {
-        if let Err(err) = process_curr(&mut curr, dir, instructions) {
-            return ControlFlow::Break(Err(err));
-        }
+        process_curr(&mut curr, dir, instructions);
 
         if curr.ends_with('Z') {
-            ControlFlow::Break(Ok(step))
+            ControlFlow::Break(step)
         } else {
             ControlFlow::Continue(curr)
         }
@@ -40,54 +74,49 @@ fn part1(dirs: impl Iterator<Item = u8>, instructions: &[Instruction]) -> Result
         unreachable!()
     };
 
-    steps
+    Ok(steps)
 }
 
-fn part2(dirs: impl Iterator<Item = u8>, instructions: &[Instruction]) -> Result<u64> {
+fn part2(dirs: impl Iterator<Item = u8>, instructions: &[Instruction]) -> u64 {
     let mut currs: Vec<_> = instructions
         .iter()
         .filter_map(|instr| instr.from.ends_with('A').then_some(instr.from))
         .collect();
 
-    let steps = dirs.zip(1..).try_fold(1, |mut steps, (dir, step)| {
+    let mut lens = Vec::with_capacity(currs.len());
+
+    // Each ghost reaches its `Z` node in a loop that starts with its `A` node
+    for (dir, step) in dirs.zip(1..) {
+        if currs.is_empty() {
+            break;
+        }
+
         currs.retain_mut(|curr| {
-            process_curr(curr, dir, instructions).unwrap();
+            process_curr(curr, dir, instructions);
 
             if curr.ends_with('Z') {
-                steps = numbers::lcm(steps, step);
+                lens.push(step);
 
                 false
             } else {
                 true
             }
         });
+    }
 
-        if currs.is_empty() {
-            ControlFlow::Break(steps)
-        } else {
-            ControlFlow::Continue(steps)
-        }
-    });
-
-    let ControlFlow::Break(steps) = steps else {
-        unreachable!()
-    };
-
-    Ok(steps)
+    cycle::combine_lengths(lens)
 }
 
-fn process_curr<'a>(curr: &mut &'a str, dir: u8, instructions: &[Instruction<'a>]) -> Result<()> {
-    let idx = instructions
-        .binary_search_by_key(curr, |instr| instr.from)
-        .map_err(|_| eyre::eyre!("missing instruction for `{curr}`"))?;
+fn process_curr<'a>(curr: &mut &'a str, dir: u8, instructions: &[Instruction<'a>]) {
+    let Ok(idx) = instructions.binary_search_by_key(curr, |instr| instr.from) else {
+        unreachable!("all nodes are validated while parsing")
+    };
 
     match dir {
         b'L' => *curr = instructions[idx].left,
         b'R' => *curr = instructions[idx].right,
-        _ => return Err(eyre::eyre!("invalid direction byte `{dir}`")),
+        _ => unreachable!("directions are validated while parsing"),
     }
-
-    Ok(())
 }
 
 struct Instruction<'a> {
@@ -97,14 +126,16 @@ struct Instruction<'a> {
 }
 
 impl<'a> Instruction<'a> {
-    fn from_str(line: &'a str) -> Result<Self> {
-        let (from, back) = line.split_once(" = ").wrap_err("missing equal")?;
-
-        let (left, right) = back
-            .trim_start_matches('(')
-            .trim_end_matches(')')
-            .split_once(", ")
-            .wrap_err("missing comma")?;
+    fn parse(input: &str, line: &'a str) -> Result<Self, ParseError> {
+        let (from, left, right) = line
+            .split_once(" = (")
+            .and_then(|(from, back)| back.strip_suffix(')').map(|back| (from, back)))
+            .and_then(|(from, back)| {
+                let (left, right) = back.split_once(", ")?;
+
+                Some((from, left, right))
+            })
+            .context_at(input, line, "expected `<node> = (<left>, <right>)`")?;
 
         Ok(Self { from, left, right })
     }
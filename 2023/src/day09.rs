@@ -1,38 +1,57 @@
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::{parse_at, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
-pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+aoc_rust::register_day! {
+    day: 9,
+    title: "Mirage Maintenance",
+}
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+pub fn run(input: &str) -> Result<Solution> {
+    let histories = timing::phase(Phase::Parse, || {
+        input
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                line.split(' ')
+                    .filter(|n| !n.is_empty())
+                    .map(|n| parse_at(input, n))
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<i64>>, ParseError>>()
+    })?;
+
+    let p1 = timing::phase(Phase::Part1, || part1(&histories));
+    let p2 = timing::phase(Phase::Part2, || part2(&histories));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> i64 {
-    solve(input.lines().map(|line| line.split(' ')))
+fn part1(histories: &[Vec<i64>]) -> i64 {
+    solve(histories.iter().map(|history| history.iter()))
 }
 
-fn part2(input: &str) -> i64 {
-    solve(input.lines().map(|line| line.rsplit(' ')))
+fn part2(histories: &[Vec<i64>]) -> i64 {
+    solve(histories.iter().map(|history| history.iter().rev()))
 }
 
-fn solve<'i, I, L>(input: I) -> i64
+fn solve<'h, I, L>(histories: I) -> i64
 where
     I: Iterator<Item = L>,
-    L: Iterator<Item = &'i str>,
+    L: Iterator<Item = &'h i64>,
 {
     let mut curr = Vec::new();
     let mut next = Vec::new();
     let mut sum = 0;
 
-    for line in input {
+    for history in histories {
         curr.clear();
-        curr.extend(line.map(str::parse::<i64>).flat_map(Result::ok));
+        curr.extend(history.copied());
 
         if curr.is_empty() {
-            return 0;
+            continue;
         }
 
         loop {
@@ -1,13 +1,19 @@
+use aoc_rust::timing::{self, Phase};
 use aoc_rust::Solution;
 use eyre::Result;
 
 use self::model::*;
 
+aoc_rust::register_day! {
+    day: 10,
+    title: "Pipe Maze",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let field: Field = input.trim().parse()?;
+    let field = timing::phase(Phase::Parse, || Field::parse(input))?;
 
-    let p1 = part1(&field);
-    let p2 = part2(&field);
+    let p1 = timing::phase(Phase::Part1, || part1(&field));
+    let p2 = timing::phase(Phase::Part2, || part2(&field));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -62,12 +68,9 @@ fn path_crossings(pos: Pos, dir: Direction, field: &Field) -> u8 {
 }
 
 mod model {
-    use std::{
-        ops::{Add, AddAssign, Index, IndexMut},
-        str::FromStr,
-    };
+    use std::ops::{Add, AddAssign, Index, IndexMut};
 
-    use eyre::{ContextCompat, Report, Result};
+    use aoc_rust::util::parse::ParseError;
     use fxhash::FxHashSet as HashSet;
 
     pub struct Field {
@@ -93,27 +96,69 @@ mod model {
         }
     }
 
-    impl FromStr for Field {
-        type Err = Report;
+    impl Field {
+        pub fn parse(input: &str) -> Result<Self, ParseError> {
+            let lines: Vec<_> = input
+                .lines()
+                .map(str::trim_end)
+                .filter(|line| !line.is_empty())
+                .collect();
 
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            let width = s.lines().next().map_or(0, str::len) as i32;
+            let width = lines.first().map_or(0, |line| line.len());
 
-            let field = s
-                .lines()
-                .flat_map(|line| line.bytes().map(Cell::try_from))
-                .collect::<Result<Vec<_>>>()?;
+            if width == 0 {
+                return Err(ParseError::new("empty field"));
+            }
+
+            let mut field = Vec::with_capacity(width * lines.len());
+
+            for line in lines.iter() {
+                if line.len() != width {
+                    let msg = format!("expected {width} cells per row");
+
+                    return Err(ParseError::at(input, line, msg));
+                }
+
+                for (i, byte) in line.bytes().enumerate() {
+                    let cell = Cell::try_from(byte).map_err(|_| {
+                        ParseError::at(input, &line[i..], "expected one of `.JL7FS|-`")
+                    })?;
+
+                    field.push(cell);
+                }
+            }
 
-            let mut inner = FieldInner { width, field };
+            let mut inner = FieldInner {
+                width: width as i32,
+                field,
+            };
+
+            let span = |pos: Pos| &lines[pos.y as usize][pos.x as usize..];
+
+            let start = inner
+                .field
+                .iter()
+                .zip(0..)
+                .find(|(&c, _)| c == Cell::Start)
+                .map(|(_, i)| Pos::new(i % inner.width, i / inner.width))
+                .ok_or_else(|| ParseError::new("missing start `S`"))?;
+
+            let mut dir = inner.start_dir(start).ok_or_else(|| {
+                ParseError::at(input, span(start), "start must connect to two pipes")
+            })?;
 
-            let (start, mut dir) = inner.start_dir()?;
             let mut curr = start + dir.offset();
 
             let mut path = HashSet::default();
             path.insert(start);
 
             while path.insert(curr) {
-                dir = inner[curr].follow(dir.opposite()).unwrap();
+                dir = inner
+                    .contains(curr)
+                    .then(|| inner[curr].follow(dir.opposite()))
+                    .flatten()
+                    .ok_or_else(|| ParseError::at(input, span(curr), "pipe loop is broken"))?;
+
                 curr += dir.offset();
             }
 
@@ -142,23 +187,16 @@ mod model {
                 && pos.y < self.field.len() as i32 / self.width
         }
 
-        fn start_dir(&mut self) -> Result<(Pos, Direction)> {
-            let start = self
-                .field
-                .iter()
-                .zip(0..)
-                .find(|(&c, _)| c == Cell::Start)
-                .map(|(_, i)| Pos::new(i % self.width, i / self.width))
-                .wrap_err("missing start")?;
-
+        /// Replaces the start cell with the pipe it must be and returns one of its directions.
+        fn start_dir(&mut self, start: Pos) -> Option<Direction> {
             let mut neighbors = Direction::ALL.into_iter().filter(|dir| {
                 let n_pos = start + dir.offset();
 
                 self.contains(n_pos) && self[n_pos].follow(dir.opposite()).is_some()
             });
 
-            let first = neighbors.next().wrap_err("missing neighbor of start")?;
-            let second = neighbors.next().wrap_err("missing neighbor of start")?;
+            let first = neighbors.next()?;
+            let second = neighbors.next()?;
 
             let replace = match (first, second) {
                 (Direction::North, Direction::West) | (Direction::West, Direction::North) => {
@@ -184,7 +222,7 @@ mod model {
 
             self[start] = replace;
 
-            Ok((start, first))
+            Some(first)
         }
     }
 
@@ -271,7 +309,7 @@ mod model {
     }
 
     impl TryFrom<u8> for Cell {
-        type Error = Report;
+        type Error = ();
 
         fn try_from(byte: u8) -> Result<Self, Self::Error> {
             let cell = match byte {
@@ -283,7 +321,7 @@ mod model {
                 b'S' => Self::Start,
                 b'|' => Self::Vertical,
                 b'-' => Self::Horizontal,
-                _ => eyre::bail!("invalid cell byte `{byte}`"),
+                _ => return Err(()),
             };
 
             Ok(cell)
@@ -1,14 +1,22 @@
-use std::{ops::Index, str::FromStr};
+use std::ops::Index;
 
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::ParseError;
 use aoc_rust::Solution;
-use eyre::{Report, Result};
+use eyre::Result;
+
+aoc_rust::register_day! {
+    day: 11,
+    title: "Cosmic Expansion",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
-    let image = input.trim().parse::<Image>()?;
+    let image = Image::parse(input)?;
     let galaxies = image.galaxies();
+    timing::lap(Phase::Parse);
 
-    let p1 = part1(&image, galaxies.clone());
-    let p2 = part2(&image, galaxies);
+    let p1 = timing::phase(Phase::Part1, || part1(&image, galaxies.clone()));
+    let p2 = timing::phase(Phase::Part2, || part2(&image, galaxies));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -104,18 +112,44 @@ impl Image {
     }
 }
 
-impl FromStr for Image {
-    type Err = Report;
+impl Image {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .peekable();
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let width = s.lines().next().map_or(0, str::len) as u64;
+        let width = lines.peek().map_or(0, |line| line.len());
 
-        let inner = s
-            .lines()
-            .flat_map(|line| line.bytes().map(Pixel::try_from))
-            .collect::<Result<_>>()?;
+        if width == 0 {
+            return Err(ParseError::new("empty image"));
+        }
+
+        let mut inner = Vec::new();
+
+        for line in lines {
+            if line.len() != width {
+                let msg = format!("expected {width} pixels per row");
+
+                return Err(ParseError::at(input, line, msg));
+            }
 
-        Ok(Self { width, inner })
+            for (i, byte) in line.bytes().enumerate() {
+                let pixel = match byte {
+                    b'.' => Pixel::Empty,
+                    b'#' => Pixel::Galaxy,
+                    _ => return Err(ParseError::at(input, &line[i..], "expected `.` or `#`")),
+                };
+
+                inner.push(pixel);
+            }
+        }
+
+        Ok(Self {
+            width: width as u64,
+            inner,
+        })
     }
 }
 
@@ -134,15 +168,3 @@ enum Pixel {
     Empty,
     Galaxy,
 }
-
-impl TryFrom<u8> for Pixel {
-    type Error = Report;
-
-    fn try_from(byte: u8) -> Result<Self, Self::Error> {
-        match byte {
-            b'.' => Ok(Self::Empty),
-            b'#' => Ok(Self::Galaxy),
-            _ => eyre::bail!("invalid pixel byte `{byte}`"),
-        }
-    }
-}
@@ -1,20 +1,25 @@
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
-use eyre::{ContextCompat, Report, Result};
+use eyre::Result;
 
-pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+aoc_rust::register_day! {
+    day: 12,
+    title: "Hot Springs",
+}
 
-    let p1 = part1(input)?;
-    let p2 = part2(input)?;
+pub fn run(input: &str) -> Result<Solution> {
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> Result<u64> {
+fn part1(input: &str) -> Result<u64, ParseError> {
     solve(input, |_| (), |_| ())
 }
 
-fn part2(input: &str) -> Result<u64> {
+fn part2(input: &str) -> Result<u64, ParseError> {
     let modify_springs = |springs: &mut Vec<_>| {
         let len = springs.len();
         springs.push(Status::Unknown);
@@ -35,7 +40,7 @@ fn part2(input: &str) -> Result<u64> {
     solve(input, modify_springs, modify_nums)
 }
 
-fn solve<S, N>(input: &str, modify_springs: S, modify_nums: N) -> Result<u64>
+fn solve<S, N>(input: &str, modify_springs: S, modify_nums: N) -> Result<u64, ParseError>
 where
     S: Fn(&mut Vec<Status>),
     N: Fn(&mut Vec<usize>),
@@ -43,19 +48,35 @@ where
     let mut sum = 0;
     let mut cache = Cache::default();
 
-    for line in input.lines() {
-        let (springs, nums) = line.split_once(' ').wrap_err("missing whitespace")?;
+    let lines = input
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty());
+
+    for line in lines {
+        let (springs, nums) =
+            line.split_once(' ')
+                .context_at(input, line, "expected `<springs> <groups>`")?;
 
         let mut springs = springs
             .bytes()
-            .map(Status::try_from)
-            .collect::<Result<Vec<_>>>()?;
+            .enumerate()
+            .map(|(i, byte)| match byte {
+                b'.' => Ok(Status::Operational),
+                b'#' => Ok(Status::Damaged),
+                b'?' => Ok(Status::Unknown),
+                _ => Err(ParseError::at(
+                    input,
+                    &springs[i..],
+                    "expected `.`, `#`, or `?`",
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
         let mut nums = nums
             .split(',')
-            .map(str::parse)
-            .collect::<Result<Vec<usize>, _>>()
-            .map_err(|_| eyre::eyre!("invalid number"))?;
+            .map(|n| parse_at(input, n))
+            .collect::<Result<Vec<usize>, _>>()?;
 
         modify_springs(&mut springs);
         modify_nums(&mut nums);
@@ -145,16 +166,3 @@ enum Status {
     Damaged,
     Unknown,
 }
-
-impl TryFrom<u8> for Status {
-    type Error = Report;
-
-    fn try_from(byte: u8) -> Result<Self, Self::Error> {
-        match byte {
-            b'.' => Ok(Self::Operational),
-            b'#' => Ok(Self::Damaged),
-            b'?' => Ok(Self::Unknown),
-            _ => eyre::bail!("invalid status byte `{byte}`"),
-        }
-    }
-}
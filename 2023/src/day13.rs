@@ -1,21 +1,44 @@
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::ParseError;
 use aoc_rust::Solution;
 use eyre::{ContextCompat, Result};
 
 use self::grid::Grid;
 
+aoc_rust::register_day! {
+    day: 13,
+    title: "Point of Incidence",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let mut grids = input
-        .trim()
-        .split("\n\n")
-        .map(str::parse)
-        .collect::<Result<Vec<Grid>>>()?;
+    let mut grids = timing::phase(Phase::Parse, || parse_grids(input))?;
 
-    let p1 = part1(&grids)?;
-    let p2 = part2(&mut grids)?;
+    let p1 = timing::phase(Phase::Part1, || part1(&grids))?;
+    let p2 = timing::phase(Phase::Part2, || part2(&mut grids))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
+fn parse_grids(input: &str) -> Result<Vec<Grid>, ParseError> {
+    let mut grids = Vec::new();
+    let mut lines = Vec::new();
+
+    for line in input.lines().map(str::trim_end) {
+        if !line.is_empty() {
+            lines.push(line);
+        } else if !lines.is_empty() {
+            grids.push(Grid::parse(input, &lines)?);
+            lines.clear();
+        }
+    }
+
+    if !lines.is_empty() {
+        grids.push(Grid::parse(input, &lines)?);
+    }
+
+    Ok(grids)
+}
+
 fn part1(grids: &[Grid]) -> Result<usize> {
     grids
         .iter()
@@ -103,10 +126,9 @@ mod grid {
     use std::{
         ops::{Index, IndexMut},
         slice::SliceIndex,
-        str::FromStr,
     };
 
-    use eyre::Report;
+    use aoc_rust::util::parse::ParseError;
 
     #[derive(Copy, Clone, Debug, PartialEq, Eq)]
     pub enum Field {
@@ -123,18 +145,6 @@ mod grid {
         }
     }
 
-    impl TryFrom<u8> for Field {
-        type Error = Report;
-
-        fn try_from(byte: u8) -> Result<Self, Self::Error> {
-            match byte {
-                b'.' => Ok(Self::Ash),
-                b'#' => Ok(Self::Rock),
-                _ => eyre::bail!("invalid field byte `{byte}`"),
-            }
-        }
-    }
-
     pub struct Grid {
         width: usize,
         inner: Vec<Field>,
@@ -162,16 +172,29 @@ mod grid {
         }
     }
 
-    impl FromStr for Grid {
-        type Err = Report;
+    impl Grid {
+        /// Parses the non-empty `lines` of a single pattern.
+        pub fn parse(input: &str, lines: &[&str]) -> Result<Self, ParseError> {
+            let width = lines[0].len();
+            let mut inner = Vec::with_capacity(width * lines.len());
+
+            for line in lines {
+                if line.len() != width {
+                    let msg = format!("expected {width} fields per row");
+
+                    return Err(ParseError::at(input, line, msg));
+                }
 
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            let width = s.lines().next().map_or(0, str::len);
+                for (i, byte) in line.bytes().enumerate() {
+                    let field = match byte {
+                        b'.' => Field::Ash,
+                        b'#' => Field::Rock,
+                        _ => return Err(ParseError::at(input, &line[i..], "expected `.` or `#`")),
+                    };
 
-            let inner = s
-                .lines()
-                .flat_map(|line| line.bytes().map(Field::try_from))
-                .collect::<Result<Vec<_>, Report>>()?;
+                    inner.push(field);
+                }
+            }
 
             Ok(Self { width, inner })
         }
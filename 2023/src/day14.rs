@@ -1,13 +1,22 @@
-use aoc_rust::Solution;
+use aoc_rust::{
+    timing::{self, Phase},
+    util::cycle,
+    Solution,
+};
 use eyre::Result;
 
 use self::dish::Dish;
 
+aoc_rust::register_day! {
+    day: 14,
+    title: "Parabolic Reflector Dish",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let dish: Dish = input.trim().parse()?;
+    let dish = timing::phase(Phase::Parse, || Dish::parse(input))?;
 
-    let p1 = part1(dish.clone());
-    let p2 = part2(dish);
+    let p1 = timing::phase(Phase::Part1, || part1(dish.clone()));
+    let p2 = timing::phase(Phase::Part2, || part2(dish));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -16,47 +25,25 @@ fn part1(mut dish: Dish) -> usize {
     dish.move_north().load()
 }
 
-fn part2(mut dish: Dish) -> usize {
+fn part2(dish: Dish) -> usize {
     const TARGET: usize = 1_000_000_000;
 
-    let mut history = Vec::new();
-    history.push(dish.clone());
-    let mut cycle_start = None;
-
-    for _ in 0..TARGET {
+    let spin = |dish: &Dish| {
+        let mut dish = dish.clone();
         dish.cycle();
 
-        if let Some(i) = history.iter().position(|entry| entry == &dish) {
-            cycle_start = Some(i);
-            break;
-        }
-
-        history.push(dish.clone());
-    }
-
-    let remaining = match cycle_start {
-        Some(cycle_start) => {
-            let cycle_len = history.len() - cycle_start;
-            let count = (TARGET - cycle_start) / cycle_len;
-
-            TARGET - cycle_start - count * cycle_len
-        }
-        _ => 0,
+        dish
     };
 
-    for _ in 0..remaining {
-        dish.cycle();
-    }
-
-    dish.load()
+    cycle::find_cycle(dish, spin, Dish::clone)
+        .state_at(TARGET)
+        .load()
 }
 
 mod dish {
-    use std::str::FromStr;
+    use aoc_rust::util::parse::ParseError;
 
-    use eyre::Report;
-
-    #[derive(Clone, PartialEq)]
+    #[derive(Clone, PartialEq, Eq, Hash)]
     pub struct Dish {
         width: usize,
         dish: Box<[Rock]>,
@@ -166,38 +153,56 @@ mod dish {
         }
     }
 
-    impl FromStr for Dish {
-        type Err = Report;
+    impl Dish {
+        pub fn parse(input: &str) -> Result<Self, ParseError> {
+            let mut lines = input
+                .lines()
+                .map(str::trim_end)
+                .filter(|line| !line.is_empty())
+                .peekable();
 
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            let width = s.lines().next().map_or(0, str::len);
+            let width = lines.peek().map_or(0, |line| line.len());
 
-            let dish = s
-                .lines()
-                .flat_map(|line| line.bytes().map(Rock::try_from))
-                .collect::<Result<_, Report>>()?;
+            if width == 0 {
+                return Err(ParseError::new("empty dish"));
+            }
+
+            let mut dish = Vec::new();
+
+            for line in lines {
+                if line.len() != width {
+                    let msg = format!("expected {width} rocks per row");
+
+                    return Err(ParseError::at(input, line, msg));
+                }
+
+                for (i, byte) in line.bytes().enumerate() {
+                    let rock = match byte {
+                        b'O' => Rock::Rounded,
+                        b'#' => Rock::Cube,
+                        b'.' => Rock::Empty,
+                        _ => {
+                            let msg = "expected `O`, `#`, or `.`";
 
-            Ok(Self { width, dish })
+                            return Err(ParseError::at(input, &line[i..], msg));
+                        }
+                    };
+
+                    dish.push(rock);
+                }
+            }
+
+            Ok(Self {
+                width,
+                dish: dish.into(),
+            })
         }
     }
 
-    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
     enum Rock {
         Rounded,
         Cube,
         Empty,
     }
-
-    impl TryFrom<u8> for Rock {
-        type Error = Report;
-
-        fn try_from(byte: u8) -> Result<Self, Self::Error> {
-            match byte {
-                b'O' => Ok(Self::Rounded),
-                b'#' => Ok(Self::Cube),
-                b'.' => Ok(Self::Empty),
-                _ => eyre::bail!("invalid rock byte `{byte}`"),
-            }
-        }
-    }
 }
@@ -1,11 +1,18 @@
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 15,
+    title: "Lens Library",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    let sequence = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input)?;
+    let p1 = timing::phase(Phase::Part1, || part1(sequence));
+    let p2 = timing::phase(Phase::Part2, || part2(input, sequence))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -14,13 +21,13 @@ fn part1(input: &str) -> usize {
     input.split(',').map(hash).sum()
 }
 
-fn part2(input: &str) -> Result<usize> {
+fn part2(input: &str, sequence: &str) -> Result<usize, ParseError> {
     let mut boxes = [0_u8; 256].map(|_| Vec::new());
 
-    for step in input.split(',') {
+    for step in sequence.split(',') {
         match step.split_once('=') {
             Some((label, value)) => {
-                let value: u8 = value.parse().map_err(|_| eyre::eyre!("invalid value"))?;
+                let value: u8 = parse_at(input, value)?;
                 let idx = hash(label);
 
                 if let Some((_, focal_len)) = boxes[idx].iter_mut().find(|(a, _)| *a == label) {
@@ -30,7 +37,12 @@ fn part2(input: &str) -> Result<usize> {
                 }
             }
             None => {
-                let label = &step[..step.len() - 1];
+                let label = step.strip_suffix('-').context_at(
+                    input,
+                    step,
+                    "expected `<label>=<focal length>` or `<label>-`",
+                )?;
+
                 let idx = hash(label);
                 boxes[idx].retain(|(a, _)| *a != label);
             }
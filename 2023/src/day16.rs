@@ -1,13 +1,19 @@
+use aoc_rust::timing::{self, Phase};
 use aoc_rust::Solution;
 use eyre::Result;
 
 use self::grid::{Bufs, Direction, Grid};
 
+aoc_rust::register_day! {
+    day: 16,
+    title: "The Floor Will Be Lava",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let grid = Grid::new(input.trim());
+    let grid = timing::phase(Phase::Parse, || Grid::parse(input))?;
 
-    let p1 = part1(&grid);
-    let p2 = part2(&grid);
+    let p1 = timing::phase(Phase::Part1, || part1(&grid));
+    let p2 = timing::phase(Phase::Part2, || part2(&grid));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -41,6 +47,7 @@ fn part2(grid: &Grid) -> usize {
 mod grid {
     use std::collections::VecDeque;
 
+    use aoc_rust::util::parse::ParseError;
     use fxhash::FxHashSet as HashSet;
 
     pub struct Grid {
@@ -49,11 +56,38 @@ mod grid {
     }
 
     impl Grid {
-        pub fn new(input: &str) -> Self {
-            let width = input.lines().next().map_or(0, str::len);
-            let grid = input.lines().flat_map(str::bytes).collect();
+        pub fn parse(input: &str) -> Result<Self, ParseError> {
+            let mut lines = input
+                .lines()
+                .map(str::trim_end)
+                .filter(|line| !line.is_empty())
+                .peekable();
+
+            let width = lines.peek().map_or(0, |line| line.len());
+
+            if width == 0 {
+                return Err(ParseError::new("empty grid"));
+            }
+
+            let mut grid = Vec::new();
+
+            for line in lines {
+                if line.len() != width {
+                    let msg = format!("expected {width} tiles per row");
+
+                    return Err(ParseError::at(input, line, msg));
+                }
+
+                if let Some(i) = line.bytes().position(|byte| !b"./\\|-".contains(&byte)) {
+                    let msg = "expected one of `./\\|-`";
+
+                    return Err(ParseError::at(input, &line[i..], msg));
+                }
+
+                grid.extend_from_slice(line.as_bytes());
+            }
 
-            Self { width, grid }
+            Ok(Self { width, grid })
         }
 
         pub fn width(&self) -> usize {
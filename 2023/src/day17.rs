@@ -1,13 +1,19 @@
+use aoc_rust::timing::{self, Phase};
 use aoc_rust::Solution;
 use eyre::Result;
 
 use self::map::Map;
 
+aoc_rust::register_day! {
+    day: 17,
+    title: "Clumsy Crucible",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let map: Map = input.trim().parse()?;
+    let map = timing::phase(Phase::Parse, || Map::parse(input))?;
 
-    let p1 = part1(&map)?;
-    let p2 = part2(&map)?;
+    let p1 = timing::phase(Phase::Part1, || part1(&map))?;
+    let p2 = timing::phase(Phase::Part2, || part2(&map))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -21,9 +27,10 @@ fn part2(map: &Map) -> Result<u32> {
 }
 
 mod map {
-    use std::{collections::BinaryHeap, str::FromStr};
-
+    use aoc_rust::search::{self, Visited};
+    use aoc_rust::util::parse::ParseError;
     use eyre::{ContextCompat, Report};
+
     pub struct Map {
         width: usize,
         map: Vec<u8>,
@@ -39,67 +46,28 @@ mod map {
         }
 
         pub fn dijkstra(&self, min_steps: i32, max_steps: i32) -> Result<u32, Report> {
-            #[derive(Clone, Copy, PartialEq, Eq)]
+            #[derive(Clone, Copy)]
             struct State {
                 x: i32,
                 y: i32,
-                dir: Direction,
-                heat_loss: u32,
+                /// `None` for the start which may go in any direction
+                dir: Option<Direction>,
             }
 
-            impl Ord for State {
-                fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-                    other.heat_loss.cmp(&self.heat_loss)
-                }
-            }
-
-            impl PartialOrd for State {
-                fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-                    Some(self.cmp(other))
-                }
-            }
-
-            let w = self.width();
-            let h = self.height();
-
-            let dst_x = w as i32 - 1;
-            let dst_y = h as i32 - 1;
-
-            let mut queue = BinaryHeap::new();
-
-            queue.push(State {
-                x: 0,
-                y: 0,
-                dir: Direction::Right,
-                heat_loss: 0,
-            });
-            queue.push(State {
-                x: 0,
-                y: 0,
-                dir: Direction::Down,
-                heat_loss: 0,
-            });
-
-            let mut dist = [0_u8; 4].map(|_| vec![u32::MAX; self.map.len()]);
-
-            while let Some(State {
-                x,
-                y,
-                dir,
-                heat_loss,
-            }) = queue.pop()
-            {
-                if x == dst_x && y == dst_y {
-                    return Ok(heat_loss);
-                }
+            let w = self.width() as i32;
+            let h = self.height() as i32;
 
+            let successors = |&State { x, y, dir }: &State| {
                 let ndirs = match dir {
-                    Direction::Up | Direction::Down => [Direction::Left, Direction::Right],
-                    Direction::Left | Direction::Right => [Direction::Up, Direction::Down],
+                    None => [Direction::Right, Direction::Down],
+                    Some(Direction::Up | Direction::Down) => [Direction::Left, Direction::Right],
+                    Some(Direction::Left | Direction::Right) => [Direction::Up, Direction::Down],
                 };
 
+                let mut next = Vec::new();
+
                 for ndir in ndirs {
-                    let mut nheat_loss = heat_loss;
+                    let mut heat_loss = 0;
 
                     for steps in 1..=max_steps {
                         let (nx, ny) = match ndir {
@@ -109,49 +77,80 @@ mod map {
                             Direction::Right => (x + steps, y),
                         };
 
-                        if nx < 0 || nx >= w as i32 || ny < 0 || ny >= h as i32 {
+                        if nx < 0 || nx >= w || ny < 0 || ny >= h {
                             break;
                         }
 
-                        nheat_loss += self.map[ny as usize * w + nx as usize] as u32;
-
-                        if steps < min_steps {
-                            continue;
-                        }
+                        heat_loss += self.map[(ny * w + nx) as usize] as u32;
 
-                        let curr_best = dist[ndir as usize][ny as usize * w + nx as usize];
-
-                        if nheat_loss < curr_best {
-                            dist[ndir as usize][ny as usize * w + nx as usize] = nheat_loss;
-
-                            let nstate = State {
+                        if steps >= min_steps {
+                            let state = State {
                                 x: nx,
                                 y: ny,
-                                dir: ndir,
-                                heat_loss: nheat_loss,
+                                dir: Some(ndir),
                             };
 
-                            queue.push(nstate);
+                            next.push((state, heat_loss));
                         }
                     }
                 }
-            }
 
-            eyre::bail!("no path found")
+                next
+            };
+
+            let key = |state: &State| match state.dir {
+                Some(dir) => (state.y * w + state.x) as usize * 4 + dir as usize,
+                None => self.map.len() * 4,
+            };
+
+            let start = State {
+                x: 0,
+                y: 0,
+                dir: None,
+            };
+
+            search::dijkstra_with(
+                start,
+                successors,
+                |state| state.x == w - 1 && state.y == h - 1,
+                Visited::dense(key, self.map.len() * 4 + 1),
+            )
+            .map(|path| path.cost)
+            .wrap_err("no path found")
         }
     }
 
-    impl FromStr for Map {
-        type Err = Report;
+    impl Map {
+        pub fn parse(input: &str) -> Result<Self, ParseError> {
+            let mut lines = input
+                .lines()
+                .map(str::trim_end)
+                .filter(|line| !line.is_empty())
+                .peekable();
 
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            let width = s.lines().next().map_or(0, str::len);
+            let width = lines.peek().map_or(0, |line| line.len());
 
-            let map = s
-                .lines()
-                .flat_map(|line| line.chars().map(|c| c.to_digit(10).map(|d| d as u8)))
-                .collect::<Option<Vec<_>>>()
-                .wrap_err("invalid digit")?;
+            if width == 0 {
+                return Err(ParseError::new("empty map"));
+            }
+
+            let mut map = Vec::new();
+
+            for line in lines {
+                if line.len() != width {
+                    let msg = format!("expected {width} blocks per row");
+
+                    return Err(ParseError::at(input, line, msg));
+                }
+
+                for (i, byte) in line.bytes().enumerate() {
+                    if !byte.is_ascii_digit() {
+                        return Err(ParseError::at(input, &line[i..], "expected digit"));
+                    }
+
+                    map.push(byte & 0xF);
+                }
+            }
 
             Ok(Self { width, map })
         }
@@ -1,65 +1,109 @@
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 18,
+    title: "Lavaduct Lagoon",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    let plan = timing::phase(Phase::Parse, || parse_plan(input))?;
 
-    let p1 = part1(input)?;
-    let p2 = part2(input)?;
+    let p1 = timing::phase(Phase::Part1, || part1(&plan));
+    let p2 = timing::phase(Phase::Part2, || part2(&plan));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> Result<u64> {
-    let points = input.lines().filter_map(|line| {
-        let mut split = line.split(' ');
+fn part1(plan: &[[Step; 2]]) -> u64 {
+    polygon_area(plan.iter().map(|[step, _]| *step))
+}
+
+fn part2(plan: &[[Step; 2]]) -> u64 {
+    polygon_area(plan.iter().map(|[_, step]| *step))
+}
 
-        split.next().zip(split.next()?.parse().ok())
-    });
+type Step = (Direction, i64);
 
-    polygon_area(points)
+#[derive(Copy, Clone)]
+enum Direction {
+    Right,
+    Down,
+    Left,
+    Up,
 }
 
-fn part2(input: &str) -> Result<u64> {
-    let points = input.lines().filter_map(|line| {
-        line.rsplit(' ')
-            .next()
-            .and_then(|color| color.strip_prefix("(#")?.strip_suffix(')'))
-            .and_then(|color| {
-                let len = i64::from_str_radix(color.get(..5)?, 16).ok()?;
-
-                let dir = match color.get(5..)? {
-                    "0" => "R",
-                    "1" => "D",
-                    "2" => "L",
-                    "3" => "U",
-                    _ => return None,
-                };
-
-                Some((dir, len))
-            })
-    });
-
-    polygon_area(points)
+/// Parses each line into the plain step and the step encoded in its color.
+fn parse_plan(input: &str) -> Result<Vec<[Step; 2]>, ParseError> {
+    input
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let mut split = line.split(' ');
+
+            let (dir, len, color) = split
+                .next()
+                .zip(split.next())
+                .zip(split.next())
+                .map(|((dir, len), color)| (dir, len, color))
+                .context_at(input, line, "expected `<direction> <meters> (#<color>)`")?;
+
+            let dir = match dir {
+                "R" => Direction::Right,
+                "D" => Direction::Down,
+                "L" => Direction::Left,
+                "U" => Direction::Up,
+                _ => return Err(ParseError::at(input, dir, "expected `R`, `D`, `L`, or `U`")),
+            };
+
+            let len = parse_at::<u32>(input, len)?;
+
+            let color = color
+                .strip_prefix("(#")
+                .and_then(|color| color.strip_suffix(')'))
+                .filter(|color| color.len() == 6)
+                .context_at(input, color, "expected `(#<6 hex digits>)`")?;
+
+            let (color_len, color_dir) = color.split_at(5);
+
+            let color_len = i64::from_str_radix(color_len, 16).ok().context_at(
+                input,
+                color_len,
+                "expected hex digits",
+            )?;
+
+            let color_dir = match color_dir {
+                "0" => Direction::Right,
+                "1" => Direction::Down,
+                "2" => Direction::Left,
+                "3" => Direction::Up,
+                _ => return Err(ParseError::at(input, color_dir, "expected digit 0 to 3")),
+            };
+
+            Ok([(dir, len as i64), (color_dir, color_len)])
+        })
+        .collect()
 }
 
-fn polygon_area<'a, I: Iterator<Item = (&'a str, i64)>>(points: I) -> Result<u64> {
+fn polygon_area<I: Iterator<Item = Step>>(steps: I) -> u64 {
     let mut x = 0;
     let mut y = 0;
 
     let mut area = 0;
     let mut circum = 0;
 
-    for (direction, len) in points {
+    for (direction, len) in steps {
         let mut nx = x;
         let mut ny = y;
 
         match direction {
-            "R" => nx += len,
-            "L" => nx -= len,
-            "U" => ny -= len,
-            "D" => ny += len,
-            _ => eyre::bail!("invalid direction `{direction}`"),
+            Direction::Right => nx += len,
+            Direction::Left => nx -= len,
+            Direction::Up => ny -= len,
+            Direction::Down => ny += len,
         }
 
         area += (y + ny) * (x - nx);
@@ -69,5 +113,5 @@ fn polygon_area<'a, I: Iterator<Item = (&'a str, i64)>>(points: I) -> Result<u64
         y = ny;
     }
 
-    Ok(((area.abs() + circum) / 2 + 1) as u64)
+    ((area.abs() + circum) / 2 + 1) as u64
 }
@@ -1,16 +1,20 @@
-use std::{
-    ops::{Index, IndexMut, Range},
-    str::FromStr,
-};
+use std::ops::{Index, IndexMut, Range};
 
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
-use eyre::{ContextCompat, Report, Result, WrapErr};
+use eyre::{ContextCompat, Result};
+
+aoc_rust::register_day! {
+    day: 19,
+    title: "Aplenty",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
-    let (workflows, mut ratings) = parse_input(input.trim())?;
+    let (workflows, mut ratings) = timing::phase(Phase::Parse, || parse_input(input))?;
 
-    let p1 = part1(&workflows, &mut ratings)?;
-    let p2 = part2(&workflows)?;
+    let p1 = timing::phase(Phase::Part1, || part1(&workflows, &mut ratings))?;
+    let p2 = timing::phase(Phase::Part2, || part2(&workflows))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -160,73 +164,58 @@ impl Default for Ranges {
     }
 }
 
-fn parse_input(input: &str) -> Result<(Vec<WorkFlow<'_>>, Vec<RatingSet<'_>>)> {
-    let (workflows, ratings) = input
-        .split_once("\n\n")
-        .wrap_err("missing double newline")?;
-
-    let workflows: Vec<_> = workflows
+fn parse_input(input: &str) -> Result<(Vec<WorkFlow<'_>>, Vec<RatingSet<'_>>), ParseError> {
+    let mut lines = input
         .lines()
-        .map(|line| {
-            let (name, rules) = line
-                .trim_end_matches('}')
-                .split_once('{')
-                .wrap_err("missing parentheses")?;
-
-            let rules = rules
-                .split(',')
-                .map(|rule| {
-                    let Some((condition, send_to)) = rule.split_once(':') else {
-                        return Ok(Rule {
-                            condition: None,
-                            send_to: rule,
-                        });
-                    };
+        .map(str::trim_end)
+        .skip_while(|line| line.is_empty());
 
-                    Ok(Rule {
-                        condition: Some(condition.parse()?),
-                        send_to,
-                    })
-                })
-                .collect::<Result<Vec<_>>>()?;
+    let workflows = lines
+        .by_ref()
+        .take_while(|line| !line.is_empty())
+        .map(|line| WorkFlow::parse(input, line))
+        .collect::<Result<Vec<_>, _>>()?;
 
-            Ok(WorkFlow { name, rules })
-        })
-        .collect::<Result<_>>()?;
+    let is_known = |name: &str| {
+        matches!(name, "A" | "R") || workflows.iter().any(|workflow| workflow.name == name)
+    };
 
-    let ratings = ratings
-        .lines()
+    if !is_known("in") {
+        return Err(ParseError::new("missing workflow `in`"));
+    }
+
+    for rule in workflows.iter().flat_map(|workflow| workflow.rules.iter()) {
+        if !is_known(rule.send_to) {
+            return Err(ParseError::at(input, rule.send_to, "unknown workflow"));
+        }
+    }
+
+    let ratings = lines
+        .filter(|line| !line.is_empty())
         .map(|line| {
-            let mut ratings = line
-                .trim_start_matches('{')
-                .trim_end_matches('}')
+            let mut split = line
+                .strip_prefix('{')
+                .and_then(|line| line.strip_suffix('}'))
+                .context_at(input, line, "expected `{<ratings>}`")?
                 .split(',');
 
-            let ratings = [
-                ratings
-                    .next()
-                    .map(Rating::from_str)
-                    .wrap_err("missing rating")??,
-                ratings
-                    .next()
-                    .map(Rating::from_str)
-                    .wrap_err("missing rating")??,
-                ratings
-                    .next()
-                    .map(Rating::from_str)
-                    .wrap_err("missing rating")??,
-                ratings
-                    .next()
-                    .map(Rating::from_str)
-                    .wrap_err("missing rating")??,
-            ];
+            let mut rating = || {
+                let rating =
+                    split
+                        .next()
+                        .context_at(input, &line[line.len()..], "missing rating")?;
+
+                Rating::parse(input, rating)
+            };
+
+            let ratings = [rating()?, rating()?, rating()?, rating()?];
 
             Ok(RatingSet {
                 workflow: "in",
                 ratings,
             })
         })
-        .collect::<Result<_>>()?;
+        .collect::<Result<_, ParseError>>()?;
 
     Ok((workflows, ratings))
 }
@@ -249,29 +238,31 @@ enum Part {
     S,
 }
 
-impl FromStr for Part {
-    type Err = Report;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
+impl Part {
+    fn parse(input: &str, span: &str) -> Result<Self, ParseError> {
+        match span {
             "x" => Ok(Self::X),
             "m" => Ok(Self::M),
             "a" => Ok(Self::A),
             "s" => Ok(Self::S),
-            _ => eyre::bail!("invalid part `{s}`"),
+            _ => Err(ParseError::at(
+                input,
+                span,
+                "expected `x`, `m`, `a`, or `s`",
+            )),
         }
     }
 }
 
-impl FromStr for Rating {
-    type Err = Report;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (part, value) = s.split_once('=').wrap_err("missing equal")?;
+impl Rating {
+    fn parse(input: &str, span: &str) -> Result<Self, ParseError> {
+        let (part, value) =
+            span.split_once('=')
+                .context_at(input, span, "expected `<part>=<value>`")?;
 
         Ok(Self {
-            part: part.parse()?,
-            value: value.parse().wrap_err("invalid value")?,
+            part: Part::parse(input, part)?,
+            value: parse_at(input, value)?,
         })
     }
 }
@@ -281,6 +272,42 @@ struct WorkFlow<'a> {
     rules: Vec<Rule<'a>>,
 }
 
+impl<'a> WorkFlow<'a> {
+    fn parse(input: &str, line: &'a str) -> Result<Self, ParseError> {
+        let (name, rules) = line
+            .strip_suffix('}')
+            .and_then(|line| line.split_once('{'))
+            .context_at(input, line, "expected `<name>{<rules>}`")?;
+
+        let rules = rules
+            .split(',')
+            .map(|rule| {
+                let Some((condition, send_to)) = rule.split_once(':') else {
+                    return Ok(Rule {
+                        condition: None,
+                        send_to: rule,
+                    });
+                };
+
+                Ok(Rule {
+                    condition: Some(Condition::parse(input, condition)?),
+                    send_to,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(last) = rules.last().filter(|rule| rule.condition.is_some()) {
+            return Err(ParseError::at(
+                input,
+                last.send_to,
+                "last rule must not have a condition",
+            ));
+        }
+
+        Ok(Self { name, rules })
+    }
+}
+
 struct Rule<'a> {
     condition: Option<Condition>,
     send_to: &'a str,
@@ -292,23 +319,34 @@ struct Condition {
     value: i16,
 }
 
-impl FromStr for Condition {
-    type Err = Report;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (left, op, right) = match s.split_once('>') {
+impl Condition {
+    fn parse(input: &str, span: &str) -> Result<Self, ParseError> {
+        let (left, op, right) = match span.split_once('>') {
             Some((left, right)) => (left, Op::Greater, right),
             None => {
-                let (left, right) = s.split_once('<').wrap_err("missing op")?;
+                let (left, right) =
+                    span.split_once('<')
+                        .context_at(input, span, "expected `<part><op><value>`")?;
 
                 (left, Op::Less, right)
             }
         };
 
+        let value = match parse_at(input, right)? {
+            value @ 0..=4000 => value,
+            _ => {
+                return Err(ParseError::at(
+                    input,
+                    right,
+                    "value must be between 0 and 4000",
+                ))
+            }
+        };
+
         Ok(Self {
-            part: left.parse()?,
+            part: Part::parse(input, left)?,
             op,
-            value: right.parse().wrap_err("invalid value")?,
+            value,
         })
     }
 }
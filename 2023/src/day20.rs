@@ -1,133 +1,162 @@
-use std::collections::VecDeque;
-
-use aoc_rust::{util::numbers::lcm, Solution};
+use std::{
+    collections::VecDeque,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+use aoc_rust::netlist::{Netlist, Wire};
+use aoc_rust::util::parse::{ParseContext, ParseError};
+use aoc_rust::{
+    timing::{self, Phase},
+    util::numbers::lcm,
+    Solution,
+};
 use eyre::{ContextCompat, Result};
 use fxhash::FxHashMap as HashMap;
 
+aoc_rust::register_day! {
+    day: 20,
+    title: "Pulse Propagation",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let (modules, dsts) = parse_input(input.trim())?;
+    let modules = timing::phase(Phase::Parse, || parse_input(input))?;
 
-    let p1 = part1(modules.clone(), &dsts);
-    let p2 = part2(modules, &dsts)?;
+    let p1 = timing::phase(Phase::Part1, || part1(&modules));
+    let p2 = timing::phase(Phase::Part2, || part2(&modules))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-type Modules<'a> = HashMap<&'a str, Module<'a>>;
-type Destinations<'a> = HashMap<&'a str, Vec<&'a str>>;
+/// The modules in Graphviz' DOT format.
+pub fn dot(input: &str) -> Result<String> {
+    let modules = parse_input(input)?;
+
+    Ok(modules.netlist.dot().to_string())
+}
+
+/// Each module drives the wire of its name and reads the modules that send
+/// pulses to it. Destinations are the readers in their listed order.
+struct Modules<'a> {
+    netlist: Netlist<'a, Module>,
+    button: Wire,
+    broadcaster: Wire,
+}
+
+fn parse_input(input: &str) -> Result<Modules<'_>, ParseError> {
+    let mut netlist = Netlist::new();
+    let mut dsts = Vec::new();
 
-fn parse_input(input: &str) -> Result<(Modules<'_>, Destinations<'_>)> {
-    let mut modules = Modules::default();
-    let mut dsts = Destinations::default();
+    let lines = input
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty());
 
-    for line in input.lines() {
-        let (front, back) = line.split_once(" -> ").wrap_err("missing arrow")?;
+    for line in lines {
+        let (front, back) = line.split_once(" -> ").context_at(
+            input,
+            line,
+            "expected `<module> -> <destinations>`",
+        )?;
 
         let (name, module) = if let Some(name) = front.strip_prefix('%') {
-            (name, Module::FlipFlop { is_on: false })
+            (name, Module::FlipFlop)
         } else if let Some(name) = front.strip_prefix('&') {
-            (
-                name,
-                Module::Conjunction {
-                    prev_inputs: Vec::new(),
-                },
-            )
-        } else {
+            (name, Module::Conjunction)
+        } else if front == "broadcaster" {
             (front, Module::Broadcaster)
+        } else {
+            let msg = "expected `broadcaster` or a name prefixed with `%` or `&`";
+
+            return Err(ParseError::at(input, front, msg));
         };
 
-        modules.insert(name, module);
-        dsts.insert(name, back.split(',').map(str::trim).collect());
+        let wire = netlist.wire(name);
+
+        if netlist.drive(wire, module, Vec::new()).is_some() {
+            let msg = format!("module `{name}` is declared twice");
+
+            return Err(ParseError::at(input, name, msg));
+        }
+
+        dsts.push((wire, back));
     }
 
-    for (dst, module) in modules.iter_mut() {
-        if let Module::Conjunction { prev_inputs } = module {
-            for (src, dsts) in dsts.iter() {
-                if dsts.contains(dst) {
-                    prev_inputs.push((src, Pulse::Low));
-                }
+    let broadcaster = netlist
+        .get("broadcaster")
+        .ok_or_else(|| ParseError::new("missing module `broadcaster`"))?;
+
+    // connecting in the listed order keeps the order of each module's
+    // destinations
+    for (src, back) in dsts {
+        for dst in back.split(',').map(str::trim) {
+            let dst = netlist.wire(dst);
+
+            if netlist.driver(dst).is_none() {
+                netlist.drive(dst, Module::Other, Vec::new());
             }
+
+            netlist.connect(src, dst);
         }
     }
 
-    Ok((modules, dsts))
+    let button = netlist.wire("button");
+    netlist.connect(button, broadcaster);
+
+    Ok(Modules {
+        netlist,
+        button,
+        broadcaster,
+    })
 }
 
-fn part1<'a>(mut modules: Modules<'a>, dsts: &Destinations<'a>) -> u64 {
-    let mut pending = VecDeque::new();
+fn part1(modules: &Modules<'_>) -> u64 {
+    let mut simulation = Simulation::new(modules);
 
     let mut count_low = 0;
     let mut count_high = 0;
 
     for _ in 1..=1000 {
-        let broadcaster = modules.entry("broadcaster").or_default();
-        count_low += 1;
-
-        if let Some(next_pulse) = broadcaster.propagate(Pulse::Low, "button") {
-            for dst in dsts["broadcaster"].iter() {
-                pending.push_back(("broadcaster", *dst, next_pulse));
-            }
-        }
-
-        while let Some((src, dst, pulse)) = pending.pop_front() {
-            match pulse {
-                Pulse::High => count_high += 1,
-                Pulse::Low => count_low += 1,
-            }
-
-            let dst_module = modules.entry(dst).or_default();
-
-            if let Some(next_pulse) = dst_module.propagate(pulse, src) {
-                for next_dst in dsts[dst].iter() {
-                    pending.push_back((dst, *next_dst, next_pulse));
-                }
-            }
-        }
+        simulation.push_button(|_, pulse| match pulse {
+            Pulse::High => count_high += 1,
+            Pulse::Low => count_low += 1,
+        });
     }
 
     count_low * count_high
 }
 
-fn part2<'a>(mut modules: Modules<'a>, dsts: &Destinations<'a>) -> Result<u64> {
-    let (rx_src, _) = dsts
-        .iter()
-        .find(|(src, dsts)| {
-            dsts.contains(&"rx") && matches!(modules[*src], Module::Conjunction { .. })
+fn part2(modules: &Modules<'_>) -> Result<u64> {
+    let netlist = &modules.netlist;
+
+    let rx_src = netlist
+        .get("rx")
+        .and_then(|rx| netlist.driver(rx))
+        .into_iter()
+        .flat_map(|driver| driver.inputs.iter().copied())
+        .find(|&src| {
+            netlist
+                .driver(src)
+                .is_some_and(|driver| driver.gate == Module::Conjunction)
         })
         .wrap_err("missing conjunction module that sends to rx")?;
 
-    let mut cycles: HashMap<_, Option<u64>> = dsts
-        .iter()
-        .filter(|(_, dsts)| dsts.contains(rx_src))
-        .map(|(name, _)| (*name, None))
+    let mut cycles: HashMap<Wire, Option<u64>> = netlist
+        .driver(rx_src)
+        .into_iter()
+        .flat_map(|driver| driver.inputs.iter())
+        .map(|&src| (src, None))
         .collect();
 
-    let mut pending = VecDeque::new();
+    let mut simulation = Simulation::new(modules);
 
     for i in 1.. {
-        let broadcaster = modules.entry("broadcaster").or_default();
-
-        if let Some(next_pulse) = broadcaster.propagate(Pulse::Low, "button") {
-            for dst in dsts["broadcaster"].iter() {
-                pending.push_back(("broadcaster", *dst, next_pulse));
-            }
-        }
-
-        while let Some((src, dst, pulse)) = pending.pop_front() {
-            if let Some(cycle) = cycles.get_mut(src) {
+        simulation.push_button(|src, pulse| {
+            if let Some(cycle) = cycles.get_mut(&src) {
                 if matches!(pulse, Pulse::High) {
                     cycle.get_or_insert(i);
                 }
             }
-
-            let dst_module = modules.entry(dst).or_default();
-
-            if let Some(next_pulse) = dst_module.propagate(pulse, src) {
-                for next_dst in dsts[dst].iter() {
-                    pending.push_back((dst, *next_dst, next_pulse));
-                }
-            }
-        }
+        });
 
         let cycle = cycles
             .values()
@@ -148,38 +177,101 @@ enum Pulse {
     Low,
 }
 
-#[derive(Clone, Default)]
-enum Module<'a> {
-    FlipFlop {
-        is_on: bool,
-    },
-    Conjunction {
-        prev_inputs: Vec<(&'a str, Pulse)>,
-    },
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Module {
+    FlipFlop,
+    Conjunction,
     Broadcaster,
-    #[default]
     Other,
 }
 
-impl Module<'_> {
-    fn propagate(&mut self, pulse: Pulse, src: &str) -> Option<Pulse> {
+impl Display for Module {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
-            Module::FlipFlop { is_on } => {
+            Module::FlipFlop => f.write_str("flip-flop"),
+            Module::Conjunction => f.write_str("conjunction"),
+            Module::Broadcaster | Module::Other => Ok(()),
+        }
+    }
+}
+
+struct Simulation<'m, 'a> {
+    modules: &'m Modules<'a>,
+    /// Whether each flip-flop is on
+    is_on: Vec<bool>,
+    /// For each conjunction the last pulse of each input
+    prev_inputs: Vec<Vec<Pulse>>,
+    pending: VecDeque<(Wire, Wire, Pulse)>,
+}
+
+impl<'m, 'a> Simulation<'m, 'a> {
+    fn new(modules: &'m Modules<'a>) -> Self {
+        let netlist = &modules.netlist;
+
+        let prev_inputs = netlist
+            .wires()
+            .map(|wire| match netlist.driver(wire) {
+                Some(driver) if driver.gate == Module::Conjunction => {
+                    vec![Pulse::Low; driver.inputs.len()]
+                }
+                _ => Vec::new(),
+            })
+            .collect();
+
+        Self {
+            modules,
+            is_on: vec![false; netlist.len()],
+            prev_inputs,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Send a low pulse to the broadcaster and propagate pulses until none
+    /// are left, passing each pulse alongside its source to `on_pulse`.
+    fn push_button(&mut self, mut on_pulse: impl FnMut(Wire, Pulse)) {
+        let Modules {
+            netlist,
+            button,
+            broadcaster,
+        } = self.modules;
+
+        self.pending.push_back((*button, *broadcaster, Pulse::Low));
+
+        while let Some((src, dst, pulse)) = self.pending.pop_front() {
+            on_pulse(src, pulse);
+
+            if let Some(next_pulse) = self.propagate(pulse, src, dst) {
+                for next_dst in netlist.readers(dst) {
+                    self.pending.push_back((dst, next_dst, next_pulse));
+                }
+            }
+        }
+    }
+
+    fn propagate(&mut self, pulse: Pulse, src: Wire, dst: Wire) -> Option<Pulse> {
+        let driver = self.modules.netlist.driver(dst)?;
+
+        match driver.gate {
+            Module::FlipFlop => {
                 if pulse == Pulse::High {
                     return None;
                 }
 
+                let is_on = &mut self.is_on[dst.index()];
                 *is_on = !*is_on;
 
                 Some(if *is_on { Pulse::High } else { Pulse::Low })
             }
-            Module::Conjunction { prev_inputs } => {
-                if let Some((_, prev_pulse)) = prev_inputs.iter_mut().find(|(name, _)| *name == src)
-                {
-                    *prev_pulse = pulse;
+            Module::Conjunction => {
+                let prev_inputs = &mut self.prev_inputs[dst.index()];
+
+                for (input, prev_pulse) in driver.inputs.iter().zip(prev_inputs.iter_mut()) {
+                    if *input == src {
+                        *prev_pulse = pulse;
+                    }
                 }
 
-                let all_high = prev_inputs.iter().all(|(_, pulse)| *pulse == Pulse::High);
+                let all_high = prev_inputs.iter().all(|pulse| *pulse == Pulse::High);
 
                 Some(if all_high { Pulse::Low } else { Pulse::High })
             }
@@ -188,3 +280,25 @@ impl Module<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dot_graph() {
+        let modules = "\
+broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output";
+
+        let dot = dot(modules).unwrap();
+        assert!(dot.contains("\"button\" -> \"broadcaster\";"), "{dot}");
+        assert!(
+            dot.contains("\"con\" [label=\"con\\nconjunction\"];"),
+            "{dot}"
+        );
+    }
+}
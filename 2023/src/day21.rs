@@ -1,19 +1,28 @@
 use std::{collections::VecDeque, hash::Hash};
 
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::ParseError;
 use aoc_rust::Solution;
 use eyre::{ContextCompat, Result};
 use fxhash::FxHashSet as HashSet;
 
+aoc_rust::register_day! {
+    day: 21,
+    title: "Step Counter",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let grid = Grid::new(input)?;
+    let grid = Grid::parse(input)?;
     let (x, y) = grid.start().wrap_err("missing start")?;
 
     if x != y || x != grid.size() / 2 {
         eyre::bail!("start must be in the middle of a square grid");
     }
 
-    let p1 = part1(&grid, (x, y));
-    let p2 = part2(&grid, (x, y));
+    timing::lap(Phase::Parse);
+
+    let p1 = timing::phase(Phase::Part1, || part1(&grid, (x, y)));
+    let p2 = timing::phase(Phase::Part2, || part2(&grid, (x, y)));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -121,11 +130,45 @@ pub struct Grid {
 }
 
 impl Grid {
-    pub fn new(input: &str) -> eyre::Result<Self> {
-        let w = input.lines().next().unwrap().len();
-        let grid: Vec<_> = input.lines().flat_map(str::bytes).collect();
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .peekable();
+
+        let w = lines.peek().map_or(0, |line| line.len());
+
+        if w == 0 {
+            return Err(ParseError::new("empty grid"));
+        }
+
+        let mut grid = Vec::with_capacity(w * w);
+
+        for line in lines {
+            if line.len() != w {
+                let msg = format!("expected {w} plots per row");
 
-        eyre::ensure!(grid.len() / w == w, "grid must be square");
+                return Err(ParseError::at(input, line, msg));
+            }
+
+            if let Some(i) = line
+                .bytes()
+                .position(|byte| !matches!(byte, b'.' | b'#' | b'S'))
+            {
+                return Err(ParseError::at(
+                    input,
+                    &line[i..],
+                    "expected `.`, `#`, or `S`",
+                ));
+            }
+
+            grid.extend_from_slice(line.as_bytes());
+        }
+
+        if grid.len() != w * w {
+            return Err(ParseError::new("grid must be square"));
+        }
 
         Ok(Self {
             size: w as i32,
@@ -1,23 +1,32 @@
-use std::{cmp, convert, ops::Range, str::FromStr};
+use std::{cmp, convert, ops::Range};
 
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::{parse_at, ParseContext, ParseError};
 use aoc_rust::Solution;
-use eyre::{ContextCompat, Report, Result};
+use eyre::Result;
 use fxhash::FxHashSet as HashSet;
 
+aoc_rust::register_day! {
+    day: 22,
+    title: "Sand Slabs",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let bricks = prepare_bricks(input.trim())?;
+    let bricks = timing::phase(Phase::Parse, || prepare_bricks(input))?;
 
-    let p1 = part1(&bricks);
-    let p2 = part2(&bricks);
+    let p1 = timing::phase(Phase::Part1, || part1(&bricks));
+    let p2 = timing::phase(Phase::Part2, || part2(&bricks));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn prepare_bricks(input: &str) -> Result<Vec<Brick>> {
+fn prepare_bricks(input: &str) -> Result<Vec<Brick>, ParseError> {
     let mut bricks = input
         .lines()
-        .map(str::parse)
-        .collect::<Result<Vec<Brick>>>()?;
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .map(|line| Brick::parse(input, line))
+        .collect::<Result<Vec<_>, _>>()?;
 
     bricks.sort_unstable_by_key(|brick| brick.z.start);
 
@@ -163,13 +172,14 @@ impl Brick {
     }
 }
 
-impl FromStr for Brick {
-    type Err = Report;
+impl Brick {
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let (front, back) =
+            line.split_once('~')
+                .context_at(input, line, "expected `<x>,<y>,<z>~<x>,<y>,<z>`")?;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (front, back) = s.split_once('~').wrap_err("invalid brick")?;
-        let front: Triple = front.parse()?;
-        let back: Triple = back.parse()?;
+        let front = Triple::parse(input, front)?;
+        let back = Triple::parse(input, back)?;
 
         let x = cmp::min(front.x, back.x)..cmp::max(front.x, back.x) + 1;
         let y = cmp::min(front.y, back.y)..cmp::max(front.y, back.y) + 1;
@@ -185,17 +195,31 @@ struct Triple {
     z: u16,
 }
 
-impl FromStr for Triple {
-    type Err = Report;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split(',').map(str::parse).flat_map(Result::ok);
+impl Triple {
+    fn parse(input: &str, span: &str) -> Result<Self, ParseError> {
+        let mut split = span.split(',');
 
         let ((x, y), z) = split
             .next()
             .zip(split.next())
             .zip(split.next())
-            .wrap_err("invalid triple")?;
+            .filter(|_| split.next().is_none())
+            .context_at(input, span, "expected `<x>,<y>,<z>`")?;
+
+        let coord = |n: &str| match parse_at::<u16>(input, n)? {
+            u16::MAX => Err(ParseError::at(input, n, "coordinate is too large")),
+            n => Ok(n),
+        };
+
+        let (x, y, z) = (coord(x)?, coord(y)?, coord(z)?);
+
+        if z == 0 {
+            return Err(ParseError::at(
+                input,
+                span,
+                "bricks can not be below the ground",
+            ));
+        }
 
         Ok(Self { x, y, z })
     }
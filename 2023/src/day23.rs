@@ -1,14 +1,21 @@
 use std::{cmp, ops::Index};
 
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::ParseError;
 use aoc_rust::Solution;
 use eyre::{ContextCompat, Result};
 use fxhash::{FxHashMap as HashMap, FxHashSet as HashSet};
 
+aoc_rust::register_day! {
+    day: 23,
+    title: "A Long Walk",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let grid = Grid::new(input.trim());
+    let grid = timing::phase(Phase::Parse, || Grid::parse(input))?;
 
-    let p1 = part1(&grid)?;
-    let p2 = part2(&grid)?;
+    let p1 = timing::phase(Phase::Part1, || part1(&grid))?;
+    let p2 = timing::phase(Phase::Part2, || part2(&grid))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -38,7 +45,7 @@ fn part1(grid: &Grid) -> Result<u32> {
             b'>' => &[(1, 0)],
             b'<' => &[(-1, 0)],
             b'^' => &[(0, -1)],
-            byte => eyre::bail!("invalid byte `{byte}`"),
+            _ => unreachable!("tiles are validated while parsing"),
         };
 
         for (dx, dy) in neighbors {
@@ -69,10 +76,7 @@ fn part2(grid: &Grid) -> Result<u32> {
 
     let neighbor_map = neighbor_map(grid, start, end);
 
-    eyre::ensure!(
-        neighbor_map.len() <= 256,
-        "can have at most 256 3-way forks",
-    );
+    eyre::ensure!(neighbor_map.len() <= 64, "can have at most 64 3-way forks",);
 
     let mut seen = 0_u64;
     seen |= 1 << START_ID;
@@ -185,11 +189,43 @@ struct Grid {
 }
 
 impl Grid {
-    fn new(input: &str) -> Self {
-        let width = input.lines().next().map_or(0, str::len) as i32;
-        let grid = input.lines().flat_map(str::bytes).collect();
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .peekable();
+
+        let width = lines.peek().map_or(0, |line| line.len());
+
+        if width == 0 {
+            return Err(ParseError::new("empty grid"));
+        }
+
+        let mut grid = Vec::new();
+
+        for line in lines {
+            if line.len() != width {
+                let msg = format!("expected {width} tiles per row");
+
+                return Err(ParseError::at(input, line, msg));
+            }
+
+            let is_tile = |byte| matches!(byte, b'#' | b'.' | b'v' | b'>' | b'<' | b'^');
+
+            if let Some(i) = line.bytes().position(|byte| !is_tile(byte)) {
+                let msg = "expected one of `#.v><^`";
+
+                return Err(ParseError::at(input, &line[i..], msg));
+            }
+
+            grid.extend_from_slice(line.as_bytes());
+        }
 
-        Self { width, grid }
+        Ok(Self {
+            width: width as i32,
+            grid,
+        })
     }
 
     fn width(&self) -> i32 {
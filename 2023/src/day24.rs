@@ -1,26 +1,54 @@
-use std::str::FromStr;
+use std::ops::RangeInclusive;
+
+use aoc_rust::{
+    timing::{self, Phase},
+    util::{
+        linear::{BigRational, LinearSystem},
+        parse::{parse_at, ParseContext, ParseError},
+    },
+    Solution,
+};
+use eyre::{Result, WrapErr};
+use nalgebra::{vector, Vector3};
+
+aoc_rust::params! {
+    pub struct Params {
+        /// Smallest x and y coordinate of the test area in part 1
+        min_area: f64 = 200_000_000_000_000.0,
+        /// Largest x and y coordinate of the test area in part 1
+        max_area: f64 = 400_000_000_000_000.0,
+    }
+}
 
-use aoc_rust::Solution;
-use eyre::{ContextCompat, Report, Result};
-use nalgebra::{matrix, vector, Vector3, LU};
+aoc_rust::register_day! {
+    day: 24,
+    title: "Never Tell Me The Odds",
+    params: Params,
+}
 
 pub fn run(input: &str) -> Result<Solution> {
-    let hail = input
-        .trim()
-        .lines()
-        .map(str::parse)
-        .collect::<Result<Vec<Hailstone>>>()?;
+    run_with_params(input, &Params::default())
+}
+
+pub fn run_with_params(input: &str, params: &Params) -> Result<Solution> {
+    let area = params.min_area..=params.max_area;
+
+    let hail = timing::phase(Phase::Parse, || {
+        input
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .map(|line| Hailstone::parse(input, line))
+            .collect::<Result<Vec<_>, _>>()
+    })?;
 
-    let p1 = part1(&hail);
-    let p2 = part2(&hail)?;
+    let p1 = timing::phase(Phase::Part1, || part1(&hail, area));
+    let p2 = timing::phase(Phase::Part2, || part2(&hail))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(hail: &[Hailstone]) -> u32 {
-    const MIN_AREA: f64 = 200_000_000_000_000.0;
-    const MAX_AREA: f64 = 400_000_000_000_000.0;
-
+fn part1(hail: &[Hailstone], area: RangeInclusive<f64>) -> u32 {
     let mut collide = 0;
 
     for (i, u) in hail.iter().enumerate() {
@@ -45,7 +73,7 @@ fn part1(hail: &[Hailstone]) -> u32 {
                 y = f64::INFINITY;
             }
 
-            if MIN_AREA <= x && x <= MAX_AREA && MIN_AREA <= y && y <= MAX_AREA {
+            if area.contains(&x) && area.contains(&y) {
                 collide += 1;
             }
         }
@@ -55,69 +83,121 @@ fn part1(hail: &[Hailstone]) -> u32 {
 }
 
 fn part2(hail: &[Hailstone]) -> Result<u64> {
-    let Some([a, b, c]) = hail.get(..3) else {
+    if hail.len() < 3 {
         eyre::bail!("need at least 3 hailstones");
-    };
+    }
 
     // https://stackoverflow.com/questions/563198/how-do-you-detect-where-two-line-segments-intersect
+    //
+    // Some triples of hailstones may not determine the rock so we try
+    // consecutive triples until one does.
+    for triple in hail.windows(3) {
+        let [a, b, c] = [&triple[0].exact, &triple[1].exact, &triple[2].exact];
+
+        // unknowns are the rock's position followed by its velocity
+        let mut system = LinearSystem::new(6);
+
+        for (i, j) in [(0, 1), (1, 2), (0, 2)] {
+            for o in [b, c] {
+                let mut coeffs = [0; 6];
+                coeffs[i] = -(a.vel[j] - o.vel[j]);
+                coeffs[j] = a.vel[i] - o.vel[i];
+                coeffs[3 + i] = a.pos[j] - o.pos[j];
+                coeffs[3 + j] = -(a.pos[i] - o.pos[i]);
+
+                let rhs = (a.pos[j] * a.vel[i] - o.pos[j] * o.vel[i])
+                    - (a.pos[i] * a.vel[j] - o.pos[i] * o.vel[j]);
+
+                system.equation(coeffs, rhs);
+            }
+        }
 
-    let m = matrix![
-        -(a.vel[1] - b.vel[1]), a.vel[0] - b.vel[0], 0.0, a.pos[1] - b.pos[1], -(a.pos[0] - b.pos[0]), 0.0;
-        -(a.vel[1] - c.vel[1]), a.vel[0] - c.vel[0], 0.0, a.pos[1] - c.pos[1], -(a.pos[0] - c.pos[0]), 0.0;
+        let solution = system.solve();
 
-        0.0, -(a.vel[2] - b.vel[2]), a.vel[1] - b.vel[1], 0.0, a.pos[2] - b.pos[2], -(a.pos[1] - b.pos[1]);
-        0.0, -(a.vel[2] - c.vel[2]), a.vel[1] - c.vel[1], 0.0, a.pos[2] - c.pos[2], -(a.pos[1] - c.pos[1]);
+        let Some(rock) = solution.unique() else {
+            continue;
+        };
 
-        -(a.vel[2] - b.vel[2]), 0.0, a.vel[0] - b.vel[0], a.pos[2] - b.pos[2], 0.0, -(a.pos[0] - b.pos[0]);
-        -(a.vel[2] - c.vel[2]), 0.0, a.vel[0] - c.vel[0], a.pos[2] - c.pos[2], 0.0, -(a.pos[0] - c.pos[0]);
-    ];
+        let sum = rock[..3].iter().sum::<BigRational>();
 
-    let mut rhs = vector![
-        (a.pos[1] * a.vel[0] - b.pos[1] * b.vel[0]) - (a.pos[0] * a.vel[1] - b.pos[0] * b.vel[1]),
-        (a.pos[1] * a.vel[0] - c.pos[1] * c.vel[0]) - (a.pos[0] * a.vel[1] - c.pos[0] * c.vel[1]),
-        (a.pos[2] * a.vel[1] - b.pos[2] * b.vel[1]) - (a.pos[1] * a.vel[2] - b.pos[1] * b.vel[2]),
-        (a.pos[2] * a.vel[1] - c.pos[2] * c.vel[1]) - (a.pos[1] * a.vel[2] - c.pos[1] * c.vel[2]),
-        (a.pos[2] * a.vel[0] - b.pos[2] * b.vel[0]) - (a.pos[0] * a.vel[2] - b.pos[0] * b.vel[2]),
-        (a.pos[2] * a.vel[0] - c.pos[2] * c.vel[0]) - (a.pos[0] * a.vel[2] - c.pos[0] * c.vel[2]),
-    ];
+        if !sum.is_integer() {
+            eyre::bail!("rock position is not integral: {sum}");
+        }
 
-    if !LU::new(m).solve_mut(&mut rhs) {
-        eyre::bail!("matrix has no inverse");
+        return u64::try_from(sum.to_integer()).wrap_err("rock position out of range");
     }
 
-    // still an off-by-one :/
-    Ok(rhs.rows_range(0..3).sum().round() as u64)
+    eyre::bail!("no three hailstones determine the rock")
 }
 
 #[derive(Debug)]
 struct Hailstone {
     pos: Vector3<f64>,
     vel: Vector3<f64>,
+    exact: ExactHailstone,
 }
 
-impl FromStr for Hailstone {
-    type Err = Report;
+/// The hailstone's coordinates as parsed, without going through `f64`
+#[derive(Debug)]
+struct ExactHailstone {
+    pos: [i128; 3],
+    vel: [i128; 3],
+}
+
+impl Hailstone {
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let (front, back) =
+            line.split_once('@')
+                .context_at(input, line, "expected `<position> @ <velocity>`")?;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (front, back) = s.split_once('@').wrap_err("missing `@`")?;
+        let exact = ExactHailstone {
+            pos: parse_vector(input, front)?,
+            vel: parse_vector(input, back)?,
+        };
+
+        let approx = |[x, y, z]: [i128; 3]| vector![x as f64, y as f64, z as f64];
 
         Ok(Self {
-            pos: parse_vector(front)?,
-            vel: parse_vector(back)?,
+            pos: approx(exact.pos),
+            vel: approx(exact.vel),
+            exact,
         })
     }
 }
 
-fn parse_vector(s: &str) -> Result<Vector3<f64>> {
-    let mut split = s
-        .split(',')
-        .map(str::trim)
-        .map(str::parse)
-        .flat_map(Result::ok);
-
-    Ok(vector![
-        split.next().wrap_err("missing x")?,
-        split.next().wrap_err("missing y")?,
-        split.next().wrap_err("missing z")?,
-    ])
+fn parse_vector(input: &str, span: &str) -> Result<[i128; 3], ParseError> {
+    let mut split = span.split(',').map(str::trim);
+
+    let mut coord = || {
+        let n = split
+            .next()
+            .context_at(input, span, "expected `<x>, <y>, <z>`")?;
+
+        parse_at(input, n)
+    };
+
+    Ok([coord()?, coord()?, coord()?])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "\
+19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+        let params = Params {
+            min_area: 7.0,
+            max_area: 27.0,
+        };
+        let solution = run_with_params(input, &params).unwrap();
+        assert_eq!(solution.part1.to_string(), "2");
+        assert_eq!(solution.part2.to_string(), "47");
+    }
 }
@@ -2,12 +2,19 @@
 
 use std::cmp;
 
+use aoc_rust::timing::{self, Phase};
+use aoc_rust::util::parse::ParseContext;
 use aoc_rust::Solution;
 use eyre::Result;
 use fxhash::FxHashMap as HashMap;
 
+aoc_rust::register_day! {
+    day: 25,
+    title: "Snowverload",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let p1 = part1(input.trim())?;
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
 
     Ok(Solution::new().part1(p1).part2("x".to_owned()))
 }
@@ -17,8 +24,17 @@ fn part1(input: &str) -> Result<usize> {
     let mut ids = HashMap::<_, usize>::default();
     let mut next_id = 0;
 
-    for line in input.lines() {
-        let (from, back) = line.split_once(": ").unwrap();
+    let lines = input
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty());
+
+    for line in lines {
+        let (from, back) = line.split_once(": ").context_at(
+            input,
+            line,
+            "expected `<component>: <components>`",
+        )?;
 
         let from = *ids.entry(from).or_insert_with(|| {
             let id = next_id;
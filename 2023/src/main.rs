@@ -1,16 +1,15 @@
-use std::{fs, time::Instant};
+use std::fs;
 
+use aoc_rust::timing;
 use eyre::Result;
 
 fn main() -> Result<()> {
     let input = fs::read_to_string("./inputs/day25.txt")?;
 
-    let start = Instant::now();
-    let solution = aoc23::day25::run(&input)?;
-    let elapsed = start.elapsed();
+    let (solution, timings) = timing::time(|| aoc23::day25::run(&input));
 
-    println!("{solution}");
-    println!("Elapsed: {elapsed:?}");
+    println!("{}", solution?);
+    println!("Elapsed: {timings}");
 
     Ok(())
 }
@@ -1,46 +1,60 @@
-use aoc_rust::Solution;
+use aoc_rust::{
+    timing::{self, Phase},
+    util::parse::{parse_at, ParseContext, ParseError},
+    Solution,
+};
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 1,
+    title: "Historian Hysteria",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn parse(input: &str) -> (Vec<i32>, Vec<i32>) {
+fn parse(input: &str) -> Result<(Vec<i32>, Vec<i32>), ParseError> {
     input
         .lines()
         .map(|line| {
-            let mut iter = line
-                .split_ascii_whitespace()
-                .map(str::parse::<i32>)
-                .map(Result::unwrap);
+            let mut iter = line.split_ascii_whitespace();
+            let mut next = || {
+                iter.next()
+                    .context_at(input, line, "expected two numbers")
+                    .and_then(|n| parse_at::<i32>(input, n))
+            };
 
-            (iter.next().unwrap(), iter.next().unwrap())
+            Ok((next()?, next()?))
         })
-        .unzip()
+        .collect()
 }
 
-fn part1(input: &str) -> i32 {
-    let (mut left, mut right) = parse(input);
+fn part1(input: &str) -> Result<i32> {
+    let (mut left, mut right) = parse(input)?;
 
     left.sort_unstable();
     right.sort_unstable();
 
-    left.into_iter()
+    let sum = left
+        .into_iter()
         .zip(right)
-        .fold(0, |sum, (left, right)| sum + (right - left).abs())
+        .fold(0, |sum, (left, right)| sum + (right - left).abs());
+
+    Ok(sum)
 }
 
-fn part2(input: &str) -> i32 {
-    let (left, mut right) = parse(input);
+fn part2(input: &str) -> Result<i32> {
+    let (left, mut right) = parse(input)?;
 
     right.sort_unstable();
 
-    left.iter().fold(0, |sum, &left| {
+    let sum = left.iter().fold(0, |sum, &left| {
         let Ok(idx) = right.binary_search(&left) else {
             return sum;
         };
@@ -50,5 +64,7 @@ fn part2(input: &str) -> i32 {
         let to_right = to_right.iter().take_while(|&&n| n == left).count();
 
         sum + left * (to_left + to_right) as i32
-    })
+    });
+
+    Ok(sum)
 }
@@ -1,11 +1,27 @@
+use aoc_rust::timing::{self, Phase};
 use aoc_rust::Solution;
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 2,
+    title: "Red-Nosed Reports",
+    variants: ["bruteforce" => run_bruteforce],
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
+    let p1 = timing::phase(Phase::Part1, || part1(input));
+    let p2 = timing::phase(Phase::Part2, || part2_recursive(input));
+
+    Ok(Solution::new().part1(p1).part2(p2))
+}
+
+pub fn run_bruteforce(input: &str) -> Result<Solution> {
+    let input = input.trim();
+
     let p1 = part1(input);
-    let p2 = part2_recursive(input);
+    let p2 = part2_bruteforce(input);
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -1,13 +1,29 @@
 use std::ops::{Add, Mul};
 
+use aoc_rust::timing::{self, Phase};
 use aoc_rust::Solution;
 use eyre::Result;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
+aoc_rust::register_day! {
+    day: 4,
+    title: "Ceres Search",
+    variants: ["structured" => run_structured],
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input));
+    let p2 = timing::phase(Phase::Part2, || part2(input));
+
+    Ok(Solution::new().part1(p1).part2(p2))
+}
+
+pub fn run_structured(input: &str) -> Result<Solution> {
+    let input = input.trim();
+
+    let p1 = part1_structured(input);
     let p2 = part2(input);
 
     Ok(Solution::new().part1(p1).part2(p2))
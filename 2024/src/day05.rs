@@ -1,14 +1,23 @@
 use std::cell::RefCell;
 
-use aoc_rust::Solution;
+use aoc_rust::{
+    timing::{self, Phase},
+    util::parse::ParseError,
+    Solution,
+};
 use eyre::Result;
 use rayon::{iter::ParallelIterator, str::ParallelString};
 
+aoc_rust::register_day! {
+    day: 5,
+    title: "Print Queue",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -19,23 +28,22 @@ thread_local! {
 
 type Rules = [Vec<u8>; 100];
 
-fn parse_rules(input: &str) -> Option<(Rules, &str)> {
+fn parse_rules(input: &str) -> Result<(Rules, &str), ParseError> {
     let mut rules = [(); 100].map(|_| Vec::new());
 
     let mut x = 0;
     let mut y = 0;
     let mut curr = &mut x;
     let mut newline = false;
-    let mut chars = input.chars();
 
-    for ch in chars.by_ref() {
-        match ch as u8 {
-            digit @ b'0'..=b'9' => *curr = *curr * 10 + (digit & 0xF),
+    for (i, byte) in input.bytes().enumerate() {
+        match byte {
+            digit @ b'0'..=b'9' if *curr < 10 => *curr = *curr * 10 + (digit & 0xF),
             b'|' => {
                 curr = &mut y;
                 newline = false;
             }
-            b'\n' if newline => break,
+            b'\n' if newline => return Ok((rules, &input[i + 1..])),
             b'\n' => {
                 rules[x as usize].push(y);
                 newline = true;
@@ -43,11 +51,11 @@ fn parse_rules(input: &str) -> Option<(Rules, &str)> {
                 y = 0;
                 curr = &mut x;
             }
-            _ => return None,
+            _ => return Err(ParseError::at_offset(input, i, "expected rule `X|Y`")),
         }
     }
 
-    Some((rules, chars.as_str()))
+    Err(ParseError::new("missing empty line before updates"))
 }
 
 struct Update {
@@ -63,20 +71,27 @@ impl Update {
         }
     }
 
-    fn parse(&mut self, line: &str) {
+    fn parse(&mut self, input: &str, line: &str) -> Result<(), ParseError> {
         self.pages.clear();
         self.indices.fill(u8::MAX);
 
-        let iter = line.as_bytes().chunks(3).enumerate().map(|(i, chunk)| {
-            let [a, b, ..] = chunk else { unreachable!() };
+        for (i, chunk) in line.as_bytes().chunks(3).enumerate() {
+            let (&[a @ b'0'..=b'9', b @ b'0'..=b'9'] | &[a @ b'0'..=b'9', b @ b'0'..=b'9', b',']) =
+                chunk
+            else {
+                return Err(ParseError::at(
+                    input,
+                    &line[i * 3..],
+                    "expected two-digit page",
+                ));
+            };
 
             let n = (a & 0xF) * 10 + (b & 0xF);
             self.indices[n as usize] = i as u8;
+            self.pages.push(n);
+        }
 
-            n
-        });
-
-        self.pages.extend(iter);
+        Ok(())
     }
 
     fn find(&self, needle: u8) -> Option<u8> {
@@ -98,43 +113,37 @@ impl Update {
     }
 }
 
-pub fn part1(input: &str) -> u16 {
-    let Some((rules, updates)) = parse_rules(input) else {
-        return 0;
-    };
+pub fn part1(input: &str) -> Result<u16, ParseError> {
+    let (rules, updates) = parse_rules(input)?;
 
     updates
         .par_lines()
-        .filter_map(|line| {
+        .map(|line| {
             UPDATE.with_borrow_mut(|update| {
-                update.parse(line);
+                update.parse(input, line)?;
 
-                rules
-                    .iter()
-                    .zip(0..)
-                    .all(|(ys, x)| {
-                        let Some(ix) = update.find(x) else {
-                            return true;
-                        };
+                let correct = rules.iter().zip(0..).all(|(ys, x)| {
+                    let Some(ix) = update.find(x) else {
+                        return true;
+                    };
+
+                    ys.iter().all(|&y| update.find(y).is_none_or(|iy| ix < iy))
+                });
 
-                        ys.iter().all(|&y| update.find(y).is_none_or(|iy| ix < iy))
-                    })
-                    .then_some(update.middle())
+                Ok(if correct { update.middle() } else { 0 })
             })
         })
         .sum()
 }
 
-pub fn part2(input: &str) -> u16 {
-    let Some((rules, updates)) = parse_rules(input) else {
-        return 0;
-    };
+pub fn part2(input: &str) -> Result<u16, ParseError> {
+    let (rules, updates) = parse_rules(input)?;
 
     updates
         .par_lines()
-        .filter_map(|line| {
+        .map(|line| {
             UPDATE.with_borrow_mut(|update| {
-                update.parse(line);
+                update.parse(input, line)?;
 
                 let mut iters = 0;
                 let mut sorted = false;
@@ -159,7 +168,7 @@ pub fn part2(input: &str) -> u16 {
                     }
                 }
 
-                (iters > 1).then_some(update.middle())
+                Ok(if iters > 1 { update.middle() } else { 0 })
             })
         })
         .sum()
@@ -1,15 +1,21 @@
 use std::{borrow::Cow, cell::RefCell, collections::HashSet, hash::Hash, ops::Add, sync::OnceLock};
 
+use aoc_rust::timing::{self, Phase};
 use aoc_rust::Solution;
 use eyre::Result;
 use fxhash::FxBuildHasher;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
+aoc_rust::register_day! {
+    day: 6,
+    title: "Guard Gallivant",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input));
+    let p2 = timing::phase(Phase::Part2, || part2(input));
 
     rayon::broadcast(|_| {
         INPUT.with(|once| {
@@ -4,32 +4,51 @@ use std::{
     ops::{Add, Mul},
 };
 
-use aoc_rust::Solution;
+use aoc_rust::{
+    timing::{self, Phase},
+    util::parse::ParseError,
+    Solution,
+};
 use eyre::Result;
 use rayon::{prelude::ParallelIterator, str::ParallelString};
 
+aoc_rust::register_day! {
+    day: 7,
+    title: "Bridge Repair",
+    variants: ["dynamic" => run_dynamic],
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1_recursive(input);
-    let p2 = part2_recursive(input);
+    let p1 = timing::phase(Phase::Part1, || part1_recursive(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2_recursive(input))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-pub fn part1_recursive(input: &str) -> u64 {
+pub fn run_dynamic(input: &str) -> Result<Solution> {
+    let input = input.trim();
+
+    let p1 = part1_dynamic(input)?;
+    let p2 = part2_dynamic(input)?;
+
+    Ok(Solution::new().part1(p1).part2(p2))
+}
+
+pub fn part1_recursive(input: &str) -> Result<u64, ParseError> {
     solve_recursive::<Part1>(input)
 }
 
-pub fn part2_recursive(input: &str) -> u64 {
+pub fn part2_recursive(input: &str) -> Result<u64, ParseError> {
     solve_recursive::<Part2>(input)
 }
 
-pub fn part1_dynamic(input: &str) -> u64 {
+pub fn part1_dynamic(input: &str) -> Result<u64, ParseError> {
     solve_dynamic::<Part1>(input)
 }
 
-pub fn part2_dynamic(input: &str) -> u64 {
+pub fn part2_dynamic(input: &str) -> Result<u64, ParseError> {
     solve_dynamic::<Part2>(input)
 }
 
@@ -45,26 +64,26 @@ thread_local! {
     static DATA: RefCell<ThreadData> = RefCell::new(ThreadData::default());
 }
 
-fn solve_recursive<C: Check>(input: &str) -> u64 {
+fn solve_recursive<C: Check>(input: &str) -> Result<u64, ParseError> {
     solve(input, |eq, _| eq.check_recursive::<C>())
 }
 
-fn solve_dynamic<C: Check>(input: &str) -> u64 {
+fn solve_dynamic<C: Check>(input: &str) -> Result<u64, ParseError> {
     solve(input, |eq, Dp(dp)| eq.check_dynamic::<C>(dp))
 }
 
-fn solve(input: &str, check_fn: fn(&Equation<'_>, Dp) -> bool) -> u64 {
+fn solve(input: &str, check_fn: fn(&Equation<'_>, Dp) -> bool) -> Result<u64, ParseError> {
     input
         .par_lines()
         .map(|line| {
             DATA.with_borrow_mut(|data| {
                 let ThreadData { eq_buf, dp } = data;
-                let eq = Equation::parse(line, eq_buf);
+                let eq = Equation::parse(input, line, eq_buf)?;
 
                 if check_fn(&eq, Dp(dp)) {
-                    eq.value
+                    Ok(eq.value)
                 } else {
-                    0
+                    Ok(0)
                 }
             })
         })
@@ -77,36 +96,43 @@ struct Equation<'a> {
 }
 
 impl<'a> Equation<'a> {
-    fn parse(line: &str, buf: &'a mut Vec<u64>) -> Self {
+    fn parse(input: &str, line: &str, buf: &'a mut Vec<u64>) -> Result<Self, ParseError> {
         let digit = |byte| (byte & 0xF) as u64;
+        let unexpected = |i: usize, expected| ParseError::at(input, &line[i..], expected);
 
-        let mut bytes = line.bytes();
+        let mut bytes = line.bytes().enumerate();
         let mut value = 0;
 
         loop {
             match bytes.next() {
-                Some(byte @ b'0'..=b'9') => value = value * 10 + digit(byte),
-                Some(b':') => break,
-                None | Some(_) => unreachable!(),
+                Some((_, byte @ b'0'..=b'9')) => value = value * 10 + digit(byte),
+                Some((_, b':')) => break,
+                Some((i, _)) => return Err(unexpected(i, "expected digit or `:`")),
+                None => return Err(unexpected(line.len(), "expected `:`")),
             }
         }
 
-        debug_assert_eq!(bytes.next(), Some(b' '));
+        match bytes.next() {
+            Some((_, b' ')) => {}
+            Some((i, _)) => return Err(unexpected(i, "expected ` `")),
+            None => return Err(unexpected(line.len(), "expected numbers")),
+        }
+
         buf.clear();
         let mut curr = 0;
 
         loop {
             match bytes.next() {
-                Some(byte @ b'0'..=b'9') => curr = curr * 10 + digit(byte),
-                Some(b' ') => buf.push(mem::replace(&mut curr, 0)),
-                Some(b'\n') | None => break,
-                Some(_) => unreachable!(),
+                Some((_, byte @ b'0'..=b'9')) => curr = curr * 10 + digit(byte),
+                Some((_, b' ')) => buf.push(mem::replace(&mut curr, 0)),
+                Some((_, b'\n')) | None => break,
+                Some((i, _)) => return Err(unexpected(i, "expected digit or ` `")),
             }
         }
 
         buf.push(curr);
 
-        Self { value, nums: &*buf }
+        Ok(Self { value, nums: &*buf })
     }
 
     fn check_recursive<C: Check>(&self) -> bool {
@@ -3,23 +3,32 @@ use std::{
     ops::{Add, AddAssign, Sub, SubAssign},
 };
 
-use aoc_rust::Solution;
+use aoc_rust::{
+    timing::{self, Phase},
+    util::parse::ParseError,
+    Solution,
+};
 use eyre::Result;
 use fxhash::FxBuildHasher;
 
+aoc_rust::register_day! {
+    day: 8,
+    title: "Resonant Collinearity",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> usize {
-    let map = Map::parse(input);
+fn part1(input: &str) -> Result<usize> {
+    let map = Map::parse(input)?;
 
-    map.n_antinodes(|antinodes, x, y| {
+    let antinodes = map.n_antinodes(|antinodes, x, y| {
         let diff = x - y;
 
         let nx = x + diff;
@@ -32,13 +41,15 @@ fn part1(input: &str) -> usize {
         if map.contains(ny) {
             antinodes.insert(ny);
         }
-    })
+    });
+
+    Ok(antinodes)
 }
 
-fn part2(input: &str) -> usize {
-    let map = Map::parse(input);
+fn part2(input: &str) -> Result<usize> {
+    let map = Map::parse(input)?;
 
-    map.n_antinodes(|antinodes, mut x, mut y| {
+    let antinodes = map.n_antinodes(|antinodes, mut x, mut y| {
         let diff = x - y;
 
         while map.contains(x) {
@@ -50,7 +61,9 @@ fn part2(input: &str) -> usize {
             antinodes.insert(y);
             y -= diff;
         }
-    })
+    });
+
+    Ok(antinodes)
 }
 
 type Antinodes = HashSet<Pos, FxBuildHasher>;
@@ -62,7 +75,7 @@ struct Map {
 }
 
 impl Map {
-    fn parse(input: &str) -> Self {
+    fn parse(input: &str) -> Result<Self, ParseError> {
         let mut antennas = HashMap::with_hasher(FxBuildHasher::default());
 
         let mut find_antennas = |y, line| {
@@ -75,7 +88,9 @@ impl Map {
 
         let mut lines = input.lines().enumerate();
 
-        let (_, line) = lines.next().unwrap();
+        let Some((_, line)) = lines.next() else {
+            return Err(ParseError::new("empty map"));
+        };
         find_antennas(0, line);
 
         let w = line.len() as i16;
@@ -86,7 +101,7 @@ impl Map {
             find_antennas(y, line);
         }
 
-        Self { w, h, antennas }
+        Ok(Self { w, h, antennas })
     }
 
     fn n_antinodes(&self, f: impl Fn(&mut Antinodes, Pos, Pos)) -> usize {
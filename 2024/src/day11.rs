@@ -1,14 +1,39 @@
 use std::{cell::RefCell, collections::HashMap, thread::LocalKey};
 
+use aoc_rust::timing::{self, Phase};
 use aoc_rust::Solution;
 use eyre::Result;
 use fxhash::FxBuildHasher;
 use rayon::{iter::ParallelIterator, str::ParallelString};
 
+aoc_rust::register_day! {
+    day: 11,
+    title: "Plutonian Pebbles",
+    variants: ["sequential" => run_sequential, "parallel" => run_parallel],
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
+    let p1 = timing::phase(Phase::Part1, || part1_sequential(input));
+    let p2 = timing::phase(Phase::Part2, || part2_parallel(input));
+
+    Ok(Solution::new().part1(p1).part2(p2))
+}
+
+pub fn run_sequential(input: &str) -> Result<Solution> {
+    let input = input.trim();
+
     let p1 = part1_sequential(input);
+    let p2 = part2_sequential(input);
+
+    Ok(Solution::new().part1(p1).part2(p2))
+}
+
+pub fn run_parallel(input: &str) -> Result<Solution> {
+    let input = input.trim();
+
+    let p1 = part1_parallel(input);
     let p2 = part2_parallel(input);
 
     Ok(Solution::new().part1(p1).part2(p2))
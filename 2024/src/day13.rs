@@ -1,4 +1,11 @@
-use aoc_rust::Solution;
+use aoc_rust::{
+    timing::{self, Phase},
+    util::{
+        linear::{BigInt, LinearSystem},
+        parse::ParseError,
+    },
+    Solution,
+};
 use eyre::Result;
 use nom::{
     bytes::complete as by,
@@ -8,61 +15,65 @@ use nom::{
     IResult,
 };
 
+aoc_rust::register_day! {
+    day: 13,
+    title: "Claw Contraption",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> i64 {
+fn part1(input: &str) -> Result<i64, ParseError> {
     input
         .split("\n\n")
         .map(|chunk| {
-            let (_, machine) = Machine::parse(chunk).unwrap_or_else(|err| panic!("{err}"));
+            let (_, machine) =
+                Machine::parse(chunk).map_err(|err| ParseError::from_nom(input, err))?;
 
-            solve(&machine)
+            Ok(solve(&machine))
         })
         .sum()
 }
 
-fn part2(input: &str) -> i64 {
+fn part2(input: &str) -> Result<i64, ParseError> {
     const BIG: i64 = 10_000_000_000_000;
 
     input
         .split("\n\n")
         .map(|chunk| {
-            let (_, mut machine) = Machine::parse(chunk).unwrap_or_else(|err| panic!("{err}"));
+            let (_, mut machine) =
+                Machine::parse(chunk).map_err(|err| ParseError::from_nom(input, err))?;
             machine.x += BIG;
             machine.y += BIG;
 
-            solve(&machine)
+            Ok(solve(&machine))
         })
         .sum()
 }
 
 fn solve(machine: &Machine) -> i64 {
-    let b_num = machine.x * machine.a.y - machine.y * machine.a.x;
-    let b_denom = machine.b.x * machine.a.y - machine.a.x * machine.b.y;
+    let mut system = LinearSystem::new(2);
+    system
+        .equation([machine.a.x, machine.b.x], machine.x)
+        .equation([machine.a.y, machine.b.y], machine.y);
 
-    if b_num % b_denom != 0 {
+    // parallel buttons would allow multiple solutions but never appear in inputs
+    let Some(presses) = system.solve().unique_integers() else {
         return 0;
-    }
-
-    let b = b_num / b_denom;
+    };
 
-    let a_num = machine.y - b * machine.b.y;
-    let a_denom = machine.a.y;
-
-    if a_num % a_denom != 0 {
-        return 0;
+    match presses.as_slice() {
+        [a, b] if *a >= BigInt::ZERO && *b >= BigInt::ZERO => {
+            i64::try_from(a * 3 + b).unwrap_or_default()
+        }
+        _ => 0,
     }
-
-    let a = a_num / a_denom;
-
-    a * 3 + b
 }
 
 struct Machine {
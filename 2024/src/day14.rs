@@ -1,6 +1,10 @@
 use std::ops::ControlFlow;
 
-use aoc_rust::Solution;
+use aoc_rust::{
+    timing::{self, Phase},
+    util::parse::ParseError,
+    Solution,
+};
 use eyre::Result;
 use nom::{
     bytes::complete as by,
@@ -9,13 +13,21 @@ use nom::{
     sequence::{preceded, separated_pair},
     IResult,
 };
-use rayon::{iter::ParallelIterator, str::ParallelString};
+use rayon::{
+    iter::{IntoParallelIterator, ParallelIterator},
+    str::ParallelString,
+};
+
+aoc_rust::register_day! {
+    day: 14,
+    title: "Restroom Redoubt",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -24,11 +36,15 @@ const W: i32 = 101;
 const H: i32 = 103;
 const STEPS: i32 = 100;
 
-fn part1(input: &str) -> u32 {
-    input
+fn part1(input: &str) -> Result<u32, ParseError> {
+    let robots: Vec<_> = input
         .par_lines()
-        .filter_map(|line| {
-            let (_, mut robot) = Robot::parse(line).unwrap();
+        .map(|line| Robot::parse_line(input, line))
+        .collect::<Result<_, _>>()?;
+
+    let product = robots
+        .into_par_iter()
+        .filter_map(|mut robot| {
             robot.run_n(STEPS);
 
             let (y, x) = robot.pos();
@@ -61,20 +77,20 @@ fn part1(input: &str) -> u32 {
             },
         )
         .into_iter()
-        .product()
+        .product();
+
+    Ok(product)
 }
 
-fn part2(input: &str) -> usize {
+/// The first second at which the robots form a tree. Their positions repeat
+/// after `W * H` seconds so the search stops there.
+fn part2(input: &str) -> Result<usize> {
     let mut robots: Vec<_> = input
         .lines()
-        .map(|line| {
-            let (_, robot) = Robot::parse(line).unwrap();
-
-            robot
-        })
-        .collect();
+        .map(|line| Robot::parse_line(input, line))
+        .collect::<Result<_, _>>()?;
 
-    (1..)
+    let seconds = (1..=(W * H) as usize)
         .find(|_| {
             robots.iter_mut().for_each(Robot::run_once);
             robots.sort_unstable_by_key(Robot::pos);
@@ -94,7 +110,9 @@ fn part2(input: &str) -> usize {
                     .is_break()
             })
         })
-        .unwrap()
+        .ok_or_else(|| eyre::eyre!("robots form no tree within {} seconds", W * H))?;
+
+    Ok(seconds)
 }
 
 #[derive(Copy, Clone)]
@@ -106,6 +124,12 @@ struct Robot {
 }
 
 impl Robot {
+    fn parse_line(input: &str, line: &str) -> Result<Self, ParseError> {
+        Self::parse(line)
+            .map(|(_, robot)| robot)
+            .map_err(|err| ParseError::from_nom(input, err))
+    }
+
     fn parse(line: &str) -> IResult<&str, Self> {
         let xy = || separated_pair(ch::i32, by::tag(","), ch::i32);
         let eq = |prefix| preceded(by::tag(prefix), preceded(by::tag("="), xy()));
@@ -128,3 +152,14 @@ impl Robot {
         (self.y, self.x)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_tree() {
+        let err = run("p=0,4 v=3,-3\np=6,3 v=-1,-3").unwrap_err();
+        assert_eq!(err.to_string(), "robots form no tree within 10403 seconds");
+    }
+}
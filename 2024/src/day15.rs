@@ -4,56 +4,69 @@ use std::{
     ops::{Index, IndexMut},
 };
 
-use aoc_rust::Solution;
+use aoc_rust::{
+    timing::{self, Phase},
+    util::parse::ParseError,
+    Solution,
+};
 use eyre::Result;
 use fxhash::FxBuildHasher;
 
+aoc_rust::register_day! {
+    day: 15,
+    title: "Warehouse Woes",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> usize {
-    let (mut grid, movements) = Grid::new(input, false);
-    let mut pos = grid.start();
+fn part1(input: &str) -> Result<usize, ParseError> {
+    let (mut grid, movements) = Grid::new(input, false)?;
+    let mut pos = grid.start()?;
 
-    for movement in movements.bytes() {
+    for (i, movement) in movements.bytes().enumerate() {
         let delta = match movement {
             b'>' => 1,
             b'<' => -1,
             b'v' => grid.w,
             b'^' => -grid.w,
             b'\n' => continue,
-            _ => unreachable!(),
+            _ => return Err(invalid_movement(input, &movements[i..])),
         };
 
         grid.step(&mut pos, delta);
     }
 
-    grid.gps_sum()
+    Ok(grid.gps_sum())
 }
 
-fn part2(input: &str) -> usize {
-    let (mut grid, movements) = Grid::new(input, true);
-    let mut pos = grid.start();
+fn part2(input: &str) -> Result<usize, ParseError> {
+    let (mut grid, movements) = Grid::new(input, true)?;
+    let mut pos = grid.start()?;
     let mut bufs = Buffers::default();
 
-    for movement in movements.bytes() {
+    for (i, movement) in movements.bytes().enumerate() {
         match movement {
             b'>' => grid.step_horizontal(&mut pos, 1),
             b'<' => grid.step_horizontal(&mut pos, -1),
             b'v' => grid.step_vertical(&mut pos, grid.w, &mut bufs),
             b'^' => grid.step_vertical(&mut pos, -grid.w, &mut bufs),
             b'\n' => continue,
-            _ => unreachable!(),
+            _ => return Err(invalid_movement(input, &movements[i..])),
         }
     }
 
-    grid.gps_sum()
+    Ok(grid.gps_sum())
+}
+
+fn invalid_movement(input: &str, movement: &str) -> ParseError {
+    ParseError::at(input, movement, "invalid movement")
 }
 
 struct Grid {
@@ -62,27 +75,35 @@ struct Grid {
 }
 
 impl Grid {
-    fn new(map: &str, double_width: bool) -> (Self, &str) {
-        fn extend(dst: &mut Vec<u8>, src: &[u8], double_width: bool) {
-            if double_width {
-                dst.extend(src.iter().flat_map(|byte| match byte {
+    fn new(map: &str, double_width: bool) -> Result<(Self, &str), ParseError> {
+        let extend = |dst: &mut Vec<u8>, src: &str| {
+            if !double_width {
+                dst.extend_from_slice(src.as_bytes());
+
+                return Ok(());
+            }
+
+            for (i, byte) in src.bytes().enumerate() {
+                let double = match byte {
                     b'#' => [b'#', b'#'],
                     b'.' => [b'.', b'.'],
                     b'O' => [b'[', b']'],
                     b'@' => [b'@', b'.'],
-                    _ => unreachable!(),
-                }));
-            } else {
-                dst.extend_from_slice(src);
+                    _ => return Err(ParseError::at(map, &src[i..], "invalid tile")),
+                };
+
+                dst.extend(double);
             }
-        }
+
+            Ok(())
+        };
 
         let mut bytes = Vec::new();
         let map_bytes = map.as_bytes();
         let mut iter = memchr::memchr_iter(b'\n', map_bytes);
 
         let mut w = iter.next().unwrap_or(map.len());
-        extend(&mut bytes, &map_bytes[..w], double_width);
+        extend(&mut bytes, &map[..w])?;
         let mut start = w + 1;
 
         if double_width {
@@ -91,18 +112,22 @@ impl Grid {
 
         for i in iter {
             if start == i {
-                return (Self { bytes, w: w as i32 }, &map[i + 1..]);
+                return Ok((Self { bytes, w: w as i32 }, &map[i + 1..]));
             }
 
-            extend(&mut bytes, &map_bytes[start..i], double_width);
+            extend(&mut bytes, &map[start..i])?;
             start = i + 1;
         }
 
-        panic!("missing double newline")
+        Err(ParseError::new(
+            "missing empty line between map and movements",
+        ))
     }
 
-    fn start(&self) -> i32 {
-        memchr::memchr(b'@', &self.bytes).expect("missing @") as i32
+    fn start(&self) -> Result<i32, ParseError> {
+        memchr::memchr(b'@', &self.bytes)
+            .map(|i| i as i32)
+            .ok_or_else(|| ParseError::new("missing robot `@`"))
     }
 
     fn gps_sum(&self) -> usize {
@@ -1,147 +1,98 @@
-use std::{
-    cmp::{Ordering, Reverse},
-    collections::{BinaryHeap, HashSet},
-};
+use std::collections::HashSet;
 
-use aoc_rust::Solution;
-use eyre::Result;
+use aoc_rust::{
+    search::{self, Visited},
+    timing::{self, Phase},
+    util::parse::ParseError,
+    Solution,
+};
+use eyre::{ContextCompat, Result};
 use fxhash::FxBuildHasher;
 
+aoc_rust::register_day! {
+    day: 16,
+    title: "Reindeer Maze",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> u32 {
-    let bytes = input.as_bytes();
-    let w = memchr::memchr(b'\n', bytes).unwrap() as i32 + 1;
-    let start = memchr::memchr(b'S', bytes).unwrap() as i32;
+/// Position within the maze's bytes and index of the facing direction
+type State = (i32, usize);
 
-    let mut heap = BinaryHeap::new();
-    heap.push((Reverse(0), start, 1));
+fn part1(input: &str) -> Result<u32> {
+    let (bytes, w, start) = parse_maze(input)?;
+    let dirs = directions(w);
 
-    let mut dists = vec![[u32::MAX; 4]; bytes.len()];
-    dists[start as usize][1] = 0;
+    let path = search::dijkstra_with(
+        (start, 1),
+        |&state| successors(bytes, dirs, state),
+        |&(pos, _)| bytes[pos as usize] == b'E',
+        Visited::dense(
+            |&(pos, dir): &State| pos as usize * 4 + dir,
+            bytes.len() * 4,
+        ),
+    )
+    .wrap_err("no path found")?;
+
+    Ok(path.cost)
+}
 
+fn part2(input: &str) -> Result<usize> {
+    let (bytes, w, start) = parse_maze(input)?;
     let dirs = directions(w);
 
-    while let Some((Reverse(score), pos, dir)) = heap.pop() {
-        if bytes[pos as usize] == b'E' {
-            return score;
-        }
-
-        for (i, delta) in dirs.into_iter().enumerate() {
-            let next = pos + delta;
+    let paths = search::dijkstra_all_with(
+        (start, 1),
+        |&state| successors(bytes, dirs, state),
+        |&(pos, _)| bytes[pos as usize] == b'E',
+        Visited::dense(
+            |&(pos, dir): &State| pos as usize * 4 + dir,
+            bytes.len() * 4,
+        ),
+    )
+    .wrap_err("no path found")?;
 
-            if bytes[next as usize] == b'#' {
-                continue;
-            }
+    let tiles: HashSet<_, FxBuildHasher> = paths.nodes().map(|&(pos, _)| pos).collect();
 
-            let weight = if dirs[dir] == delta {
-                1
-            } else if dirs[dir] == -delta {
-                continue;
-            } else {
-                1001
-            };
+    Ok(tiles.len())
+}
 
-            let next_score = score + weight;
+fn successors(
+    bytes: &[u8],
+    dirs: [i32; 4],
+    (pos, dir): State,
+) -> impl Iterator<Item = (State, u32)> + '_ {
+    dirs.into_iter().enumerate().filter_map(move |(i, delta)| {
+        let next = pos + delta;
 
-            if dists[next as usize][i] > next_score {
-                dists[next as usize][i] = next_score;
-                heap.push((Reverse(next_score), next, i));
-            }
+        if bytes[next as usize] == b'#' || dirs[dir] == -delta {
+            return None;
         }
-    }
 
-    panic!("no path found")
+        let weight = if dirs[dir] == delta { 1 } else { 1001 };
+
+        Some(((next, i), weight))
+    })
 }
 
-fn part2(input: &str) -> usize {
+/// The maze's bytes, the width of a line including its newline, and the start
+fn parse_maze(input: &str) -> Result<(&[u8], i32, i32), ParseError> {
     let bytes = input.as_bytes();
-    let w = memchr::memchr(b'\n', bytes).unwrap() as i32 + 1;
-    let start = memchr::memchr(b'S', bytes).unwrap() as i32;
-
-    let mut heap = BinaryHeap::new();
-    heap.push((Reverse(0), start, 1));
-
-    let mut best = u32::MAX;
 
-    let mut dists = vec![[u32::MAX; 4]; bytes.len()];
-    dists[start as usize][1] = 0;
+    let w =
+        memchr::memchr(b'\n', bytes).ok_or_else(|| ParseError::new("expected multiple lines"))?;
 
-    let mut prevs: Vec<_> = (0..bytes.len())
-        .map(|_| [(); 4].map(|_| HashSet::with_hasher(FxBuildHasher::default())))
-        .collect();
-
-    let mut run_back = Vec::new();
-
-    let dirs = directions(w);
-
-    while let Some((Reverse(score), pos, dir)) = heap.pop() {
-        if dists[pos as usize][dir] < score {
-            continue;
-        } else if score > best {
-            break;
-        } else if bytes[pos as usize] == b'E' {
-            best = score;
-            run_back.push((pos, dir));
-
-            continue;
-        }
-
-        for (i, delta) in dirs.into_iter().enumerate() {
-            let next = pos + delta;
-
-            if bytes[next as usize] == b'#' {
-                continue;
-            }
-
-            let weight = if dirs[dir] == delta {
-                1
-            } else if dirs[dir] == -delta {
-                continue;
-            } else {
-                1001
-            };
-
-            let next_score = score + weight;
-
-            match dists[next as usize][i].cmp(&next_score) {
-                Ordering::Greater => {
-                    dists[next as usize][i] = next_score;
-                    heap.push((Reverse(next_score), next, i));
-
-                    prevs[next as usize][i].clear();
-                    prevs[next as usize][i].insert((pos, dir));
-                }
-                Ordering::Equal => {
-                    prevs[next as usize][i].insert((pos, dir));
-                }
-                Ordering::Less => {}
-            }
-        }
-    }
-
-    let mut set = HashSet::with_hasher(FxBuildHasher::default());
-    let mut seen = HashSet::with_hasher(FxBuildHasher::default());
-
-    while let Some((pos, dir)) = run_back.pop() {
-        if !seen.insert((pos, dir)) {
-            continue;
-        }
-
-        for &(prev, dir) in prevs[pos as usize][dir].iter() {
-            set.insert(prev);
-            run_back.push((prev, dir));
-        }
-    }
+    let start =
+        memchr::memchr(b'S', bytes).ok_or_else(|| ParseError::new("missing start tile `S`"))?;
 
-    1 + set.len()
+    Ok((bytes, w as i32 + 1, start as i32))
 }
 
 fn directions(w: i32) -> [i32; 4] {
@@ -1,169 +1,563 @@
-use aoc_rust::Solution;
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter, Write},
+};
+
+use aoc_rust::{
+    machine::{Exit, Instruction, Machine, Step},
+    timing::{self, Phase},
+    util::parse::{parse_at, ParseContext, ParseError},
+    Solution,
+};
 use eyre::Result;
 use itoa::Buffer;
-use nom::{
-    bytes::complete as by,
-    character::complete as ch,
-    combinator::all_consuming,
-    error::Error as NomError,
-    multi::separated_list1,
-    sequence::{delimited, preceded},
-    Err as NomErr,
-};
+use nom::{bytes::complete as by, character::complete as ch, sequence::delimited, IResult};
+
+aoc_rust::register_day! {
+    day: 17,
+    title: "Chronospatial Computer",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> String {
-    let (mut state, program) = State::parse(input).unwrap();
+fn part1(input: &str) -> Result<String> {
+    let (mut machine, numbers) = parse(input)?;
 
     let mut buf = Buffer::new();
     let mut output = String::new();
 
-    let Some(val) = state.run_until_print(&program) else {
-        return output;
+    // the machine is deterministic so a repeated state never halts
+    let mut seen = HashSet::new();
+
+    loop {
+        let exit = machine.run_with(|machine| {
+            let Registers { a, b, c } = machine.state;
+
+            seen.insert((machine.pc, a, b, c))
+        });
+
+        match exit {
+            Exit::Output(val) => {
+                if !output.is_empty() {
+                    output.push(',');
+                }
+
+                output.push_str(buf.format(val));
+            }
+            Exit::Halted => return Ok(output),
+            _ => {
+                let pos = Layout::new(numbers.len()).pos(machine.pc);
+
+                eyre::bail!("the program repeats its state at position {pos} and never halts");
+            }
+        }
+    }
+}
+
+/// Run until the program halts or outputs a value but stop with
+/// [`Exit::Loop`] at a jump onto itself that would never stop.
+fn execute(machine: &mut Machine<Op>) -> Exit<u8> {
+    let exit = machine.run_with(|machine| {
+        !matches!(
+            machine.current(),
+            Some(&Op::Jnz(target)) if target == machine.pc && machine.state.a != 0
+        )
+    });
+
+    match exit {
+        Exit::Stopped => Exit::Loop,
+        exit => exit,
+    }
+}
+
+fn part2(input: &str) -> Result<i64> {
+    let (mut machine, numbers) = parse(input)?;
+    let regs = Registers {
+        a: 0,
+        ..machine.state
     };
 
-    output.push_str(buf.format(val));
+    if let Err(reason) = check_shape(machine.program(), &numbers) {
+        let disassembly = disassemble_program(machine.program(), &numbers);
 
-    while let Some(val) = state.run_until_print(&program) {
-        output.push(',');
-        output.push_str(buf.format(val));
+        eyre::bail!(
+            "{reason}, can't search for a quine in\n{}",
+            disassembly.trim_end()
+        );
     }
 
-    output
+    quine(&mut machine, &numbers, regs, 0).ok_or_else(|| {
+        let disassembly = disassemble_program(machine.program(), &numbers);
+
+        eyre::eyre!(
+            "no value of register A makes the program output itself\n{}",
+            disassembly.trim_end()
+        )
+    })
 }
 
-fn part2(input: &str) -> i64 {
-    let (mut state, program) = State::parse(input).unwrap();
+/// Depth-first search for the smallest `a` whose output is `numbers`, given
+/// the `a` that produces its last `matched` numbers.
+///
+/// Each output consumes the lowest three bits of `a` so every additional
+/// number corresponds to three more bits. Trying them in ascending order
+/// finds the minimal `a` first, backtracking whenever no bits fit.
+fn quine(
+    machine: &mut Machine<Op>,
+    numbers: &[u8],
+    regs: Registers,
+    matched: usize,
+) -> Option<i64> {
+    if matched == numbers.len() {
+        return Some(regs.a);
+    }
 
-    let mut a = 0;
-    let b = state.b;
-    let c = state.c;
+    let suffix = &numbers[numbers.len() - matched - 1..];
 
-    let mut stack = program.clone();
+    (0..8).find_map(|bits| {
+        let regs = Registers {
+            a: regs.a.checked_mul(8)? + bits,
+            ..regs
+        };
 
-    'stack: while let Some(next) = stack.pop() {
-        a *= 8;
+        outputs(machine, regs, suffix)
+            .then(|| quine(machine, numbers, regs, matched + 1))
+            .flatten()
+    })
+}
 
-        'sim: loop {
-            a += 1;
-            state = State::new(a, b, c);
+/// Whether running the program on `regs` outputs exactly `expected`.
+fn outputs(machine: &mut Machine<Op>, regs: Registers, expected: &[u8]) -> bool {
+    machine.reset(regs);
 
-            if state.run_until_print(&program) == Some(next) {
-                continue 'stack;
-            } else {
-                continue 'sim;
-            }
+    expected
+        .iter()
+        .all(|&n| execute(machine) == Exit::Output(n))
+        && execute(machine) == Exit::Halted
+}
+
+/// Check that the program is a single loop that outputs one number per
+/// iteration and shifts `a` by three bits, with `b` and `c` being written
+/// before they're read. Otherwise the search would not be exhaustive.
+fn check_shape(program: &[Op], numbers: &[u8]) -> Result<(), &'static str> {
+    if reachable(numbers)
+        .into_iter()
+        .skip(1)
+        .step_by(2)
+        .any(|reached| reached)
+    {
+        return Err("expected no jumps to odd positions");
+    }
+
+    let evens = &program[..Layout::new(numbers.len()).evens()];
+
+    let Some((Op::Jnz(0), body)) = evens.split_last() else {
+        return Err("expected the program to end with `jnz 0`");
+    };
+
+    let count = |f: fn(&Op) -> bool| body.iter().filter(|op| f(op)).count();
+
+    if count(|op| matches!(op, Op::Jnz(_) | Op::Halt)) > 0 {
+        return Err("expected no jumps besides the last instruction");
+    }
+
+    if count(|op| matches!(op, Op::Out(_))) != 1 {
+        return Err("expected exactly one output per iteration");
+    }
+
+    if count(|op| matches!(op, Op::Adv(_))) != 1
+        || count(|op| matches!(op, Op::Adv(Combo::Literal(3)))) != 1
+    {
+        return Err("expected `a` to be shifted by three bits once per iteration");
+    }
+
+    let mut written = A;
+
+    for op in body {
+        let (reads, writes) = op.registers();
+
+        if reads & !written != 0 {
+            return Err("expected `b` and `c` to be written before they're read");
         }
+
+        written |= writes;
     }
 
-    a
+    Ok(())
 }
 
-struct State {
-    ip: usize,
+/// The program in readable form, one instruction per line prefixed by its
+/// position in the input.
+///
+/// Instructions at odd positions are only listed if a jump can reach them.
+pub fn disassemble(input: &str) -> Result<String, ParseError> {
+    parse(input.trim()).map(|(machine, numbers)| disassemble_program(machine.program(), &numbers))
+}
+
+fn disassemble_program(program: &[Op], numbers: &[u8]) -> String {
+    let layout = Layout::new(numbers.len());
+    let mut disassembly = String::new();
+
+    for (pos, reached) in reachable(numbers).into_iter().enumerate() {
+        if !reached {
+            continue;
+        }
+
+        let _ = match program[layout.index(pos)] {
+            Op::Adv(combo) => writeln!(disassembly, "{pos:>2}: a >>= {combo}"),
+            Op::Bxl(operand) => writeln!(disassembly, "{pos:>2}: b ^= {operand}"),
+            Op::Bst(combo) => writeln!(disassembly, "{pos:>2}: b = {combo} % 8"),
+            Op::Jnz(target) => {
+                writeln!(
+                    disassembly,
+                    "{pos:>2}: if a != 0 goto {}",
+                    layout.pos(target)
+                )
+            }
+            Op::Halt => writeln!(disassembly, "{pos:>2}: halt"),
+            Op::Bxc => writeln!(disassembly, "{pos:>2}: b ^= c"),
+            Op::Out(combo) => writeln!(disassembly, "{pos:>2}: out {combo} % 8"),
+            Op::Bdv(combo) => writeln!(disassembly, "{pos:>2}: b = a >> {combo}"),
+            Op::Cdv(combo) => writeln!(disassembly, "{pos:>2}: c = a >> {combo}"),
+        };
+    }
+
+    disassembly
+}
+
+#[derive(Copy, Clone)]
+struct Registers {
     a: i64,
     b: i64,
     c: i64,
 }
 
-impl State {
-    fn new(a: i64, b: i64, c: i64) -> Self {
-        State { ip: 0, a, b, c }
+#[derive(Copy, Clone, Debug)]
+enum Combo {
+    Literal(u8),
+    A,
+    B,
+    C,
+}
+
+impl Combo {
+    fn new(operand: u8) -> Self {
+        match operand {
+            4 => Self::A,
+            5 => Self::B,
+            6 => Self::C,
+            _ => Self::Literal(operand),
+        }
     }
 
-    fn parse(input: &str) -> Result<(Self, Vec<u8>), NomErr<NomError<&str>>> {
-        let name = |name| delimited(by::tag("Register "), by::tag(name), by::tag(": "));
-        let register = |n| delimited(name(n), ch::i64, ch::newline);
+    fn get(self, regs: &Registers) -> i64 {
+        match self {
+            Self::Literal(n) => n as i64,
+            Self::A => regs.a,
+            Self::B => regs.b,
+            Self::C => regs.c,
+        }
+    }
 
-        let (rest, a) = register("A")(input)?;
-        let (rest, b) = register("B")(rest)?;
-        let (rest, c) = register("C")(rest)?;
+    /// The register as bitmask of [`A`], [`B`], and [`C`].
+    fn register(self) -> u8 {
+        match self {
+            Self::Literal(_) => 0,
+            Self::A => A,
+            Self::B => B,
+            Self::C => C,
+        }
+    }
+}
+
+impl Display for Combo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Literal(n) => Display::fmt(n, f),
+            Self::A => f.write_str("a"),
+            Self::B => f.write_str("b"),
+            Self::C => f.write_str("c"),
+        }
+    }
+}
 
-        let program = preceded(ch::newline, by::tag("Program: "));
-        let list = separated_list1(by::tag(","), ch::u8);
-        let (_, program) = all_consuming(preceded(program, list))(rest)?;
+const A: u8 = 1;
+const B: u8 = 2;
+const C: u8 = 4;
+
+#[derive(Copy, Clone, Debug)]
+enum Op {
+    Adv(Combo),
+    Bxl(u8),
+    Bst(Combo),
+    /// Index of the instruction to jump to, see [`Layout`]
+    Jnz(usize),
+    Bxc,
+    Out(Combo),
+    Bdv(Combo),
+    Cdv(Combo),
+    /// An opcode without operand or a placeholder that's never executed
+    Halt,
+}
 
-        Ok((Self::new(a, b, c), program))
+impl Op {
+    /// Bitmasks of the registers that are read and written.
+    fn registers(self) -> (u8, u8) {
+        match self {
+            Op::Adv(combo) => (A | combo.register(), A),
+            Op::Bxl(_) => (B, B),
+            Op::Bst(combo) => (combo.register(), B),
+            Op::Jnz(_) => (A, 0),
+            Op::Halt => (0, 0),
+            Op::Bxc => (B | C, B),
+            Op::Out(combo) => (combo.register(), 0),
+            Op::Bdv(combo) => (A | combo.register(), B),
+            Op::Cdv(combo) => (A | combo.register(), C),
+        }
     }
+}
 
-    fn run_until_print(&mut self, program: &[u8]) -> Option<u8> {
-        while self.ip < program.len() {
-            let op = program[self.ip];
-            let operand = program[self.ip + 1];
+impl Instruction for Op {
+    type State = Registers;
+    type Output = u8;
+
+    fn execute(&self, regs: &mut Registers) -> Step<Self> {
+        match *self {
+            Op::Adv(combo) => regs.a = shr(regs.a, combo.get(regs)),
+            Op::Bxl(operand) => regs.b ^= operand as i64,
+            Op::Bst(combo) => regs.b = combo.get(regs) % 8,
+            Op::Jnz(target) if regs.a != 0 => return Step::Goto(target),
+            Op::Jnz(_) => {}
+            Op::Halt => return Step::Halt,
+            Op::Bxc => regs.b ^= regs.c,
+            Op::Out(combo) => return Step::Output((combo.get(regs) % 8) as u8),
+            Op::Bdv(combo) => regs.b = shr(regs.a, combo.get(regs)),
+            Op::Cdv(combo) => regs.c = shr(regs.a, combo.get(regs)),
+        }
 
-            let combo = || match operand {
-                0..=3 => operand as i64,
-                4 => self.a,
-                5 => self.b,
-                6 => self.c,
-                _ => panic!("bad combo operand"),
-            };
+        Step::Next
+    }
+}
 
-            match op {
-                0 => self.a >>= combo() as u32,
-                1 => self.b ^= operand as i64,
-                2 => self.b = combo() % 8,
-                3 => {
-                    if self.a != 0 && self.ip != operand as usize {
-                        self.ip = operand as usize;
+/// Division by a power of two that doesn't overflow for large exponents.
+fn shr(n: i64, exp: i64) -> i64 {
+    match u32::try_from(exp) {
+        Ok(exp) if exp < i64::BITS => n >> exp,
+        _ => 0,
+    }
+}
 
-                        continue;
-                    }
-                }
-                4 => self.b ^= self.c,
-                5 => {
-                    self.ip += 2;
+/// Where the instructions of each position end up in the machine's program.
+///
+/// Jumps may target odd positions which reads the program's numbers in
+/// pairs shifted by one. Instructions at even positions come first, followed
+/// by a halt and those at odd positions, so that each instruction is
+/// followed by the one two positions further.
+#[derive(Copy, Clone)]
+struct Layout {
+    len: usize,
+}
 
-                    return Some((combo() % 8) as u8);
-                }
-                6 => self.b = self.a >> (combo() as u32),
-                7 => self.c = self.a >> (combo() as u32),
-                _ => panic!("bad op code"),
-            }
+impl Layout {
+    fn new(len: usize) -> Self {
+        Self { len }
+    }
+
+    /// Amount of even positions.
+    fn evens(self) -> usize {
+        self.len.div_ceil(2)
+    }
 
-            self.ip += 2;
+    fn size(self) -> usize {
+        self.evens() + 1 + self.len / 2
+    }
+
+    /// Index of the instruction at the position, positions past the end
+    /// halt the machine.
+    fn index(self, pos: usize) -> usize {
+        if pos >= self.len {
+            usize::MAX
+        } else if pos.is_multiple_of(2) {
+            pos / 2
+        } else {
+            self.evens() + 1 + pos / 2
         }
+    }
 
-        None
+    /// Position of the instruction at the index, the end of the program for
+    /// indices that halt.
+    fn pos(self, idx: usize) -> usize {
+        if idx < self.evens() {
+            2 * idx
+        } else if idx > self.evens() && idx < self.size() {
+            2 * (idx - self.evens() - 1) + 1
+        } else {
+            self.len
+        }
     }
 }
 
-#[allow(unused)]
-fn unga_bunga(input: &str) -> i64 {
-    let (state, program) = State::parse(input).unwrap();
-    let mut a = 0;
+/// Positions at which an instruction may start: all even ones and odd ones
+/// following the target of a jump.
+fn reachable(numbers: &[u8]) -> Vec<bool> {
+    let mut reached = vec![false; numbers.len()];
+    let mut stack = vec![0];
 
-    let start = std::time::Instant::now();
+    while let Some(pos) = stack.pop() {
+        if pos >= numbers.len() || reached[pos] {
+            continue;
+        }
 
-    loop {
-        fn drive_state(state: &mut State, program: &[u8]) -> bool {
-            for &n in program.iter() {
-                if state.run_until_print(program) != Some(n) {
-                    return false;
-                }
-            }
+        reached[pos] = true;
+        stack.push(pos + 2);
 
-            state.run_until_print(program).is_none()
+        if let (3, Some(&target)) = (numbers[pos], numbers.get(pos + 1)) {
+            stack.push(target as usize);
         }
+    }
 
-        let mut state = State::new(a, state.b, state.c);
+    reached
+}
 
-        if drive_state(&mut state, &program) {
-            return a;
-        }
+/// The machine alongside the program's numbers as they appear in the input.
+fn parse(input: &str) -> Result<(Machine<Op>, Vec<u8>), ParseError> {
+    let (rest, (a, b, c)) =
+        parse_registers(input).map_err(|err| ParseError::from_nom(input, err))?;
+
+    let list = rest
+        .strip_prefix("\nProgram: ")
+        .context_at(input, rest, "expected `Program: `")?;
+
+    let spans: Vec<_> = list.split(',').collect();
+
+    let numbers = spans
+        .iter()
+        .map(|span| parse_3bit(input, span))
+        .collect::<Result<Vec<_>, _>>()?;
 
-        a += 1;
+    let layout = Layout::new(numbers.len());
+    let mut program = vec![Op::Halt; layout.size()];
 
-        if a % 1_000_000_000 == 0 {
-            println!("[{:?}] {a}", start.elapsed());
+    for (pos, reached) in reachable(&numbers).into_iter().enumerate() {
+        // reading an opcode without operand halts
+        let (true, Some(&operand)) = (reached, numbers.get(pos + 1)) else {
+            continue;
+        };
+
+        let opcode = numbers[pos];
+
+        if matches!(opcode, 0 | 2 | 5 | 6 | 7) && operand == 7 {
+            return Err(ParseError::at(
+                input,
+                spans[pos + 1],
+                "reserved combo operand",
+            ));
         }
+
+        let combo = Combo::new(operand);
+
+        program[layout.index(pos)] = match opcode {
+            0 => Op::Adv(combo),
+            1 => Op::Bxl(operand),
+            2 => Op::Bst(combo),
+            3 => Op::Jnz(layout.index(operand as usize)),
+            4 => Op::Bxc,
+            5 => Op::Out(combo),
+            6 => Op::Bdv(combo),
+            _ => Op::Cdv(combo),
+        };
+    }
+
+    Ok((Machine::new(program, Registers { a, b, c }), numbers))
+}
+
+fn parse_registers(input: &str) -> IResult<&str, (i64, i64, i64)> {
+    let name = |name| delimited(by::tag("Register "), by::tag(name), by::tag(": "));
+    let register = |n| delimited(name(n), ch::i64, ch::newline);
+
+    let (rest, a) = register("A")(input)?;
+    let (rest, b) = register("B")(rest)?;
+    let (rest, c) = register("C")(rest)?;
+
+    Ok((rest, (a, b, c)))
+}
+
+fn parse_3bit(input: &str, span: &str) -> Result<u8, ParseError> {
+    parse_at(input, span).ok().filter(|&n| n < 8).context_at(
+        input,
+        span,
+        "expected a number from 0 to 7",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program(a: i64, numbers: &str) -> String {
+        format!("Register A: {a}\nRegister B: 0\nRegister C: 0\n\nProgram: {numbers}")
+    }
+
+    #[test]
+    fn quine() {
+        let input = program(2024, "0,3,5,4,3,0");
+        assert_eq!(part1(&input).unwrap(), "5,7,3,0");
+        assert_eq!(part2(&input).unwrap(), 117440);
+
+        let disassembly = " 0: a >>= 3\n 2: out a % 8\n 4: if a != 0 goto 0\n";
+        assert_eq!(disassemble(&input).unwrap(), disassembly);
+
+        // shifts by one bit, no quine search
+        assert!(part2(&program(729, "0,1,5,4,3,0")).is_err());
+
+        // always outputs 0, no quine exists
+        assert!(part2(&program(1, "0,3,5,3,3,0")).is_err());
+    }
+
+    #[test]
+    fn jumps() {
+        // jumps to 3 which reads `adv 3`, `out a`, and `jnz 0` from there
+        let odd = program(64, "3,3,1,0,3,5,4,3,0");
+        assert_eq!(part1(&odd).unwrap(), "0,1,0");
+        assert!(part2(&odd).is_err());
+
+        let disassembly = disassemble(&odd).unwrap();
+        assert!(
+            disassembly.starts_with(" 0: if a != 0 goto 3\n"),
+            "{disassembly}"
+        );
+        assert!(disassembly.contains(" 3: a >>= 3\n"), "{disassembly}");
+        assert!(disassembly.ends_with(" 8: halt\n"), "{disassembly}");
+
+        // jumping to the end halts
+        let to_end = program(5, "5,4,3,4");
+        assert_eq!(part1(&to_end).unwrap(), "5");
+        assert_eq!(
+            disassemble(&to_end).unwrap(),
+            " 0: out a % 8\n 2: if a != 0 goto 4\n"
+        );
+
+        // jumping onto itself never halts
+        let onto_itself = program(5, "5,4,3,2");
+        let err = part1(&onto_itself).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the program repeats its state at position 2 and never halts"
+        );
+        assert_eq!(part1(&program(0, "5,4,3,2")).unwrap(), "0");
+
+        // neither does outputting forever without changing `a`
+        let err = part1(&program(1, "5,4,3,0")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the program repeats its state at position 0 and never halts"
+        );
     }
 }
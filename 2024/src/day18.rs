@@ -1,176 +1,111 @@
-use std::collections::{BinaryHeap, HashMap, HashSet};
-
-use aoc_rust::{util::lines::Lines, Solution};
+use std::collections::HashSet;
+
+use aoc_rust::{
+    search::{self, Path, Visited},
+    timing::{self, Phase},
+    util::{lines::Lines, parse::ParseError},
+    Solution,
+};
 use eyre::Result;
 use fxhash::FxBuildHasher;
 
+aoc_rust::register_day! {
+    day: 18,
+    title: "RAM Run",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn parse_line(line: &str) -> (i16, i16) {
+fn parse_line(input: &str, line: &str) -> Result<Pos, ParseError> {
     let mut x = 0;
     let mut y = 0;
     let mut curr = &mut x;
 
-    for byte in line.bytes() {
-        if byte == b',' {
-            curr = &mut y;
-        } else {
-            *curr *= 10;
-            *curr += (byte & 0xF) as i16;
+    for (i, byte) in line.bytes().enumerate() {
+        match byte {
+            b',' => curr = &mut y,
+            b'0'..=b'9' => {
+                *curr *= 10;
+                *curr += (byte & 0xF) as i16;
+            }
+            _ => return Err(ParseError::at(input, &line[i..], "expected `X,Y`")),
+        }
+
+        if *curr > DIM {
+            return Err(ParseError::at(input, line, "position out of bounds"));
         }
     }
 
-    (x, y)
+    Ok((x, y))
 }
 
 const DIM: i16 = 70;
 const TAKE: usize = 1024;
 
-type Corrupted = HashSet<(i16, i16), FxBuildHasher>;
+type Pos = (i16, i16);
+type Corrupted = HashSet<Pos, FxBuildHasher>;
 
-fn part1(input: &str) -> i16 {
-    let corrupted: Corrupted = Lines::new(input).take(TAKE).map(parse_line).collect();
-    let mut dijkstra = Dijkstra::default();
+fn part1(input: &str) -> Result<usize> {
+    let corrupted: Corrupted = Lines::new(input)
+        .take(TAKE)
+        .map(|line| parse_line(input, line))
+        .collect::<Result<_, _>>()?;
 
-    dijkstra
-        .run(&corrupted, true)
-        .best()
-        .expect("no path found")
+    shortest_path(&corrupted)
+        .map(|path| path.cost)
+        .ok_or_else(|| eyre::eyre!("no path found"))
 }
 
-fn part2(input: &str) -> Box<str> {
+fn part2(input: &str) -> Result<Box<str>> {
     let mut lines = Lines::new(input);
 
-    let mut corrupted: Corrupted = lines.by_ref().take(TAKE).map(parse_line).collect();
-    let mut dijkstra = Dijkstra::default();
-    let mut path = HashSet::with_hasher(FxBuildHasher::default());
-
-    if let Some(line) = lines.next() {
-        corrupted.insert(parse_line(line));
-
-        if dijkstra.run(&corrupted, false).no_path() {
-            return Box::from(line);
-        }
-
-        dijkstra.collect_path(&mut path);
+    let mut corrupted: Corrupted = lines
+        .by_ref()
+        .take(TAKE)
+        .map(|line| parse_line(input, line))
+        .collect::<Result<_, _>>()?;
 
-        for line in lines {
-            let pos = parse_line(line);
-            corrupted.insert(pos);
+    let mut path = HashSet::with_hasher(FxBuildHasher::default());
 
-            if !path.contains(&pos) {
-                continue;
-            }
+    for line in lines {
+        let pos = parse_line(input, line)?;
+        corrupted.insert(pos);
 
-            if dijkstra.run(&corrupted, false).no_path() {
-                return Box::from(line);
-            }
-
-            dijkstra.collect_path(&mut path);
+        // only search again if the current path got blocked
+        if !path.is_empty() && !path.contains(&pos) {
+            continue;
         }
-    }
-
-    panic!("end always reachable")
-}
-
-#[derive(Default)]
-struct Dijkstra {
-    heap: BinaryHeap<(i16, i16, i16)>,
-    dists: HashMap<(i16, i16), i16, FxBuildHasher>,
-    prevs: HashMap<(i16, i16), (i16, i16), FxBuildHasher>,
-}
-
-impl Dijkstra {
-    fn run(&mut self, corrupted: &Corrupted, keep_going: bool) -> DijkstraResult {
-        let Self { heap, dists, prevs } = self;
 
-        heap.clear();
-        dists.clear();
-        prevs.clear();
+        let Some(next) = shortest_path(&corrupted) else {
+            return Ok(Box::from(line));
+        };
 
-        heap.push((0, 0, 0));
-        dists.insert((0, 0), 0);
-
-        let mut best = i16::MAX;
-
-        while let Some((_, x, y)) = heap.pop() {
-            let dist = dists[&(x, y)];
-
-            if dist > best {
-                continue;
-            } else if (x, y) == (DIM, DIM) {
-                if !keep_going {
-                    return DijkstraResult::PathFound;
-                }
-
-                if best > dist {
-                    best = dist;
-                }
-
-                continue;
-            }
-
-            for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
-                let nx = x + dx;
-                let ny = y + dy;
-
-                if !(0..=DIM).contains(&nx)
-                    || !(0..=DIM).contains(&ny)
-                    || corrupted.contains(&(nx, ny))
-                {
-                    continue;
-                }
-
-                let dn = dists.entry((nx, ny)).or_insert(i16::MAX);
-
-                if *dn > dist + 1 {
-                    *dn = dist + 1;
-                    prevs.insert((nx, ny), (x, y));
-                    heap.push((nx + ny, nx, ny));
-                }
-            }
-        }
-
-        if best < i16::MAX {
-            DijkstraResult::Best(best)
-        } else {
-            DijkstraResult::NoPath
-        }
+        path.clear();
+        path.extend(next.nodes);
     }
 
-    fn collect_path(&self, set: &mut HashSet<(i16, i16), FxBuildHasher>) {
-        set.clear();
-        let mut curr = (DIM, DIM);
-
-        while curr != (0, 0) {
-            set.insert(curr);
-            curr = self.prevs[&curr];
-        }
-    }
+    eyre::bail!("end is always reachable")
 }
 
-enum DijkstraResult {
-    Best(i16),
-    NoPath,
-    PathFound,
-}
+fn shortest_path(corrupted: &Corrupted) -> Option<Path<Pos, usize>> {
+    let successors = |&(x, y): &Pos| {
+        [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .map(move |(dx, dy)| (x + dx, y + dy))
+            .filter(|pos @ (x, y)| {
+                (0..=DIM).contains(x) && (0..=DIM).contains(y) && !corrupted.contains(pos)
+            })
+    };
 
-impl DijkstraResult {
-    fn best(self) -> Option<i16> {
-        match self {
-            DijkstraResult::Best(best) => Some(best),
-            _ => None,
-        }
-    }
+    const LEN: usize = (DIM as usize + 1) * (DIM as usize + 1);
+    let visited = Visited::dense(|&(x, y): &Pos| (y * (DIM + 1) + x) as usize, LEN);
 
-    fn no_path(self) -> bool {
-        matches!(self, DijkstraResult::NoPath)
-    }
+    search::bfs_with((0, 0), successors, |&pos| pos == (DIM, DIM), visited)
 }
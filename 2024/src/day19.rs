@@ -1,35 +1,45 @@
 use std::{collections::HashMap, convert::identity, mem};
 
-use aoc_rust::Solution;
+use aoc_rust::{
+    timing::{self, Phase},
+    util::parse::ParseError,
+    Solution,
+};
 use eyre::Result;
 use nom::{
     bytes::complete as by,
     character::complete as ch,
-    combinator::{flat_map, map, opt},
+    combinator::{all_consuming, flat_map, map, opt},
     multi::fold_many1,
     sequence::terminated,
+    IResult,
 };
 
+aoc_rust::register_day! {
+    day: 19,
+    title: "Linen Layout",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
 type Cache<'a, P> = HashMap<&'a str, <P as Part>::Cached, ahash::RandomState>;
 
-fn part1(input: &str) -> usize {
+fn part1(input: &str) -> Result<usize, ParseError> {
     solve::<Part1>(input)
 }
 
-fn part2(input: &str) -> i64 {
+fn part2(input: &str) -> Result<i64, ParseError> {
     solve::<Part2>(input)
 }
 
-fn solve<P: Part>(input: &str) -> P::Output {
+fn solve<P: Part>(input: &str) -> Result<P::Output, ParseError> {
     let pattern = terminated(ch::alpha1, opt(by::tag(", ")));
     let fold_trie = fold_many1(pattern, Trie::default, |mut trie, next| {
         trie.insert(next);
@@ -40,15 +50,16 @@ fn solve<P: Part>(input: &str) -> P::Output {
 
     let applied = |trie| {
         let mut cache = Cache::<P>::default();
-        let line = terminated(ch::alpha1, opt(ch::newline::<_, ()>));
+        let line = terminated(ch::alpha1, opt(ch::newline));
         let apply = move |line| P::recurse(&trie, line, &mut cache);
 
         fold_many1(map(line, apply), <P::Output>::default, P::fold_output)
     };
 
-    let (_, res) = flat_map(trie, applied)(input).expect("bad input");
+    let res: IResult<_, _> = all_consuming(flat_map(trie, applied))(input);
 
-    res
+    res.map(|(_, output)| output)
+        .map_err(|err| ParseError::from_nom(input, err))
 }
 
 trait Part {
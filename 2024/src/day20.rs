@@ -1,59 +1,45 @@
-use std::{cmp::Reverse, collections::BinaryHeap, ops::Index};
-
-use aoc_rust::Solution;
+use std::ops::Index;
+
+use aoc_rust::{
+    search::{self, Visited},
+    timing::{self, Phase},
+    util::parse::ParseError,
+    Solution,
+};
 use eyre::Result;
-use fxhash::FxHashMap;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 
+aoc_rust::register_day! {
+    day: 20,
+    title: "Race Condition",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> u32 {
+fn part1(input: &str) -> Result<u32> {
     solve::<2, 100>(input)
 }
 
-fn part2(input: &str) -> u32 {
+fn part2(input: &str) -> Result<u32> {
     solve::<20, 100>(input)
 }
 
-fn solve<const MAX_CHEAT: u16, const AT_LEAST: u16>(input: &str) -> u32 {
+fn solve<const MAX_CHEAT: u16, const AT_LEAST: u16>(input: &str) -> Result<u32> {
     let track = RaceTrack::new(input);
-    let start = track.find(b'S');
-    let end = track.find(b'E');
-
-    let end_dists = track.trace(end, start);
-    let fair_dist = end_dists[&start];
-
-    let mut curr = start;
-    let mut dist = fair_dist;
-    let mut path = Vec::with_capacity(fair_dist as usize + 1);
-
-    while curr != end {
-        let (next, next_dist) = [(-1, 0), (1, 0), (0, -1), (0, 1)]
-            .into_iter()
-            .filter_map(|(dx, dy)| {
-                let next = (curr.0 + dx, curr.1 + dy);
-                let next_dist = *end_dists.get(&next)?;
-
-                Some((next, next_dist))
-            })
-            .min_by(|(_, a), (_, b)| a.cmp(b))
-            .unwrap();
-
-        path.push((curr, dist));
-        curr = next;
-        dist = next_dist;
-    }
+    let start = track.find(b'S')?;
+    let end = track.find(b'E')?;
 
-    path.push((end, 0));
+    let path = track.trace(start, end)?;
 
-    path.par_iter()
+    let count = path
+        .par_iter()
         .enumerate()
         .map(|(i, &(a, dist_a_end))| {
             let mut count = 0;
@@ -76,7 +62,9 @@ fn solve<const MAX_CHEAT: u16, const AT_LEAST: u16>(input: &str) -> u32 {
 
             count
         })
-        .sum()
+        .sum();
+
+    Ok(count)
 }
 
 fn manhatten_dist((ax, ay): (i16, i16), (bx, by): (i16, i16)) -> u16 {
@@ -96,43 +84,36 @@ impl<'a> RaceTrack<'a> {
         Self { bytes, w }
     }
 
-    fn find(&self, byte: u8) -> (i16, i16) {
-        let pos = memchr::memchr(byte, self.bytes).unwrap() as i16;
+    fn find(&self, byte: u8) -> Result<(i16, i16), ParseError> {
+        let pos = memchr::memchr(byte, self.bytes)
+            .ok_or_else(|| ParseError::new(format_args!("missing `{}`", byte as char)))?
+            as i16;
 
-        (pos % self.w, pos / self.w)
+        Ok((pos % self.w, pos / self.w))
     }
 
-    fn trace(&self, start: (i16, i16), end: (i16, i16)) -> FxHashMap<(i16, i16), u16> {
-        let mut heap = BinaryHeap::new();
-        let mut dists = FxHashMap::default();
-
-        heap.push((Reverse(manhatten_dist(start, end)), start));
-        dists.insert(start, 0);
-
-        while let Some((_, (x, y))) = heap.pop() {
-            let dist = dists[&(x, y)];
+    /// All positions from `start` to `end` alongside their remaining
+    /// distance to `end`.
+    fn trace(&self, start: (i16, i16), end: (i16, i16)) -> Result<Vec<((i16, i16), u16)>> {
+        let successors = |&(x, y): &(i16, i16)| {
+            [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                .into_iter()
+                .map(move |(dx, dy)| (x + dx, y + dy))
+                .filter(|&next| self[next] != b'#')
+        };
 
-            if (x, y) == end {
-                return dists;
-            }
-
-            for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
-                let next = (x + dx, y + dy);
+        let w = self.w;
+        let visited = Visited::dense(
+            |&(x, y): &(i16, i16)| (y * w + x) as usize,
+            self.bytes.len(),
+        );
 
-                if self[next] == b'#' {
-                    continue;
-                }
+        let path = search::bfs_with(start, successors, |&pos| pos == end, visited)
+            .ok_or_else(|| eyre::eyre!("no path found"))?;
 
-                let entry = dists.entry(next).or_insert(u16::MAX);
-
-                if *entry > dist + 1 {
-                    *entry = dist + 1;
-                    heap.push((Reverse(manhatten_dist(next, end)), next));
-                }
-            }
-        }
+        let dists = (0..=path.cost as u16).rev();
 
-        panic!("no path found")
+        Ok(path.nodes.into_iter().zip(dists).collect())
     }
 }
 
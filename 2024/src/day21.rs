@@ -1,132 +1,108 @@
-use std::{cmp::Reverse, collections::BinaryHeap, iter, ops::Add};
-
-use aoc_rust::Solution;
+use std::{iter, ops::Add};
+
+use aoc_rust::{
+    search,
+    timing::{self, Phase},
+    util::parse::{parse_at, ParseContext, ParseError},
+    Solution,
+};
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 21,
+    title: "Keypad Conundrum",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> u64 {
+fn part1(input: &str) -> Result<u64, ParseError> {
     solve(input, 2)
 }
 
-fn part2(input: &str) -> u64 {
+fn part2(input: &str) -> Result<u64, ParseError> {
     solve(input, 25)
 }
 
 type Cost = u64;
-type Heap = BinaryHeap<(Reverse<Cost>, Pos, Button)>;
 
-fn solve(input: &str, depth: u8) -> u64 {
-    let mut heap = Heap::new();
-    let costs = directional_costs(depth, &mut heap);
+fn solve(input: &str, depth: u8) -> Result<u64, ParseError> {
+    let costs = directional_costs(depth);
 
     input
         .lines()
         .map(|line| {
-            let code: u64 = line[..3].parse().unwrap();
+            let code: u64 = line
+                .get(..3)
+                .context_at(input, line, "expected a numeric code")
+                .and_then(|code| parse_at(input, code))?;
+
+            if let Some(i) = line
+                .bytes()
+                .position(|byte| !matches!(byte, b'0'..=b'9' | b'A'))
+            {
+                return Err(ParseError::at(input, &line[i..], "invalid button"));
+            }
 
             let from = iter::once(b'A').chain(line.bytes());
             let to = line.bytes();
 
             let min_len: u64 = from
                 .zip(to)
-                .map(|(from, to)| numeric_cost(from, to, &costs, &mut heap))
+                .map(|(from, to)| press_cost(from.pos(), to.pos(), &costs, Pos::is_numeric_button))
                 .sum();
 
-            min_len * code
+            Ok(min_len * code)
         })
         .sum()
 }
 
-fn numeric_cost(from: u8, to: u8, costs: &[[Cost; 5]; 5], heap: &mut Heap) -> Cost {
-    let from_pos = from.pos();
-    let to_pos = to.pos();
-
-    heap.clear();
-    heap.push((Reverse(0), from_pos, Button::A));
-
-    while let Some((Reverse(cost), pos, button)) = heap.pop() {
-        if pos == to_pos {
-            if button == Button::A {
-                return cost;
-            }
-
-            let next_cost = cost + costs[button as usize][Button::A as usize];
-            heap.push((Reverse(next_cost), pos, Button::A));
-
-            continue;
-        }
-
-        for delta in Pos::deltas() {
-            let next_pos = pos + delta;
-
-            if !next_pos.is_numeric_button() {
-                continue;
-            }
-
-            let next_button = Button::from_delta(delta);
-            let next_cost = cost + costs[button as usize][next_button as usize];
-            heap.push((Reverse(next_cost), next_pos, next_button));
-        }
-    }
-
-    unreachable!()
-}
-
-fn directional_costs(depth: u8, heap: &mut Heap) -> [[Cost; 5]; 5] {
+fn directional_costs(depth: u8) -> [[Cost; 5]; 5] {
     if depth == 0 {
         return [[1; 5]; 5];
     }
 
-    let prev_costs = directional_costs(depth - 1, heap);
-    let mut curr_costs = [[u64::MAX; 5]; 5];
-
-    for from in Button::enumerate() {
-        let mut seen = [false; 5];
-        heap.push((Reverse(0), from.pos(), Button::A));
-
-        while let Some((Reverse(cost), pos, button)) = heap.pop() {
-            let Some(to) = pos.directional_button() else {
-                continue;
-            };
+    let prev_costs = directional_costs(depth - 1);
+    let is_button = |pos: Pos| pos.directional_button().is_some();
 
-            if button == Button::A && cost > 0 {
-                curr_costs[from as usize][to as usize] =
-                    curr_costs[from as usize][to as usize].min(cost);
+    Button::enumerate().map(|from| {
+        Button::enumerate().map(|to| press_cost(from.pos(), to.pos(), &prev_costs, is_button))
+    })
+}
 
-                continue;
-            }
+/// Cheapest cost to move an arm from `from` to `to` and press the button
+/// there while the arm itself is directed through a keypad with the given
+/// `costs` for pressing one button after another.
+fn press_cost(from: Pos, to: Pos, costs: &[[Cost; 5]; 5], is_button: fn(Pos) -> bool) -> Cost {
+    // the arm's position, the last button pressed on the directing keypad,
+    // and whether the target button was pressed
+    type State = (Pos, Button, bool);
 
-            let next_cost = cost + prev_costs[button as usize][Button::A as usize];
-            heap.push((Reverse(next_cost), pos, Button::A));
+    let successors = |&(pos, button, _): &State| {
+        let cost = move |next: Button| costs[button as usize][next as usize];
 
-            seen[to as usize] = true;
+        let press = (pos == to).then(|| ((pos, Button::A, true), cost(Button::A)));
 
-            for delta in Pos::deltas() {
-                let next_pos = pos + delta;
+        let moves = Pos::deltas().into_iter().filter_map(move |delta| {
+            let next_pos = pos + delta;
+            let next_button = Button::from_delta(delta);
 
-                if next_pos
-                    .directional_button()
-                    .is_none_or(|n| seen[n as usize])
-                {
-                    continue;
-                }
+            is_button(next_pos).then(|| ((next_pos, next_button, false), cost(next_button)))
+        });
 
-                let next_button = Button::from_delta(delta);
-                let next_cost = cost + prev_costs[button as usize][next_button as usize];
-                heap.push((Reverse(next_cost), next_pos, next_button));
-            }
-        }
-    }
+        press.into_iter().chain(moves)
+    };
 
-    curr_costs
+    search::dijkstra((from, Button::A, false), successors, |&(.., pressed)| {
+        pressed
+    })
+    .map_or(Cost::MAX, |path| path.cost)
 }
 
 trait NumericButton {
@@ -152,7 +128,7 @@ impl NumericButton for u8 {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 enum Button {
     Up,
     Down,
@@ -187,7 +163,7 @@ impl Button {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct Pos {
     x: i8,
     y: i8,
@@ -230,3 +206,23 @@ impl Add for Pos {
         Self::new(self.x + rhs.x, self.y + rhs.y)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "029A\n980A\n179A\n456A\n379A";
+        assert_eq!(part1(input).unwrap(), 126384);
+        assert_eq!(part2(input).unwrap(), 154115708116294);
+    }
+
+    #[test]
+    fn repeated_presses() {
+        // each repeated press is one more `A` on every directional keypad
+        let single = part1("100A").unwrap() / 100;
+        let double = part1("1000A").unwrap() / 100;
+        assert_eq!(double, single + 1);
+    }
+}
@@ -3,16 +3,22 @@ use std::{
     collections::{HashMap, HashSet},
 };
 
+use aoc_rust::timing::{self, Phase};
 use aoc_rust::Solution;
 use eyre::Result;
 use fxhash::FxBuildHasher;
 use rayon::{prelude::ParallelIterator, str::ParallelString};
 
+aoc_rust::register_day! {
+    day: 22,
+    title: "Monkey Market",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input));
+    let p2 = timing::phase(Phase::Part2, || part2(input));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
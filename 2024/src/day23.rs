@@ -6,22 +6,55 @@ use std::{
     sync::Mutex,
 };
 
-use aoc_rust::Solution;
+use aoc_rust::{
+    timing::{self, Phase},
+    util::parse::ParseError,
+    Solution,
+};
 use eyre::Result;
 use fxhash::{FxHashMap, FxHashSet};
 
+aoc_rust::register_day! {
+    day: 23,
+    title: "LAN Party",
+    variants: [
+        "naive" => run_naive,
+        "bk" => run_bk,
+        "bk-pivot" => run_bk_pivot,
+        "bk-degeneracy" => run_bk_degeneracy,
+        "bk-degeneracy-arena" => run_bk_degeneracy_arena,
+    ],
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    run_with(input, part2_bk_pivot_arena)
+}
+
+pub fn run_naive(input: &str) -> Result<Solution> {
+    run_with(input, part2_naive)
+}
+
+pub fn run_bk(input: &str) -> Result<Solution> {
+    run_with(input, part2_bk)
+}
+
+pub fn run_bk_pivot(input: &str) -> Result<Solution> {
+    run_with(input, part2_bk_pivot)
+}
+
+pub fn run_bk_degeneracy(input: &str) -> Result<Solution> {
+    run_with(input, part2_bk_degeneracy)
+}
+
+pub fn run_bk_degeneracy_arena(input: &str) -> Result<Solution> {
+    run_with(input, part2_bk_degeneracy_arena)
+}
 
-    let p1 = part1(input);
-    let p2 = part2_bk_pivot_arena(input);
+fn run_with(input: &str, part2: fn(&str) -> Result<String, ParseError>) -> Result<Solution> {
+    let input = input.trim();
 
-    // assert_eq!(p2, part2_naive(input));
-    // assert_eq!(p2, part2_bk(input));
-    // assert_eq!(p2, part2_bk_pivot(input));
-    // assert_eq!(p2, part2_bk_pivot_arena(input));
-    assert_eq!(p2, part2_bk_degeneracy(input));
-    assert_eq!(p2, part2_bk_degeneracy_arena(input));
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -38,32 +71,46 @@ struct Connection {
 }
 
 impl Connection {
-    fn parse(line: &str) -> Self {
-        let [a, b, b'-', c, d] = line.as_bytes() else {
-            panic!("bad input")
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let &[a, b, b'-', c, d] = line.as_bytes() else {
+            return Err(ParseError::at(input, line, "expected `xx-yy`"));
         };
 
-        Self {
-            a: [*a, *b],
-            b: [*c, *d],
+        if let Some(i) = [a, b, c, d].iter().position(|n| !n.is_ascii_lowercase()) {
+            let offset = if i < 2 { i } else { i + 1 };
+
+            return Err(ParseError::at(
+                input,
+                &line[offset..],
+                "invalid computer name",
+            ));
         }
+
+        Ok(Self {
+            a: [a, b],
+            b: [c, d],
+        })
     }
 }
 
-fn parse_input(input: &str) -> Map {
+fn parse_input(input: &str) -> Result<Map, ParseError> {
     let mut map = Map::<_, Set>::default();
 
     for line in input.lines() {
-        let Connection { a, b } = Connection::parse(line);
+        let Connection { a, b } = Connection::parse(input, line)?;
         map.entry(a).or_default().insert(b);
         map.entry(b).or_default().insert(a);
     }
 
-    map
+    if map.is_empty() {
+        return Err(ParseError::new("no connections"));
+    }
+
+    Ok(map)
 }
 
-pub fn part1(input: &str) -> usize {
-    let map = parse_input(input);
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    let map = parse_input(input)?;
 
     let mut seen = Set::default();
     let mut count = 0;
@@ -89,12 +136,12 @@ pub fn part1(input: &str) -> usize {
         }
     }
 
-    count
+    Ok(count)
 }
 
 // awful solution :D
-pub fn part2_naive(input: &str) -> String {
-    let map = parse_input(input);
+pub fn part2_naive(input: &str) -> Result<String, ParseError> {
+    let map = parse_input(input)?;
 
     fn recurse(n: usize, curr: List, map: &Map, cache: &mut Set<List>) -> Option<List> {
         if curr.len() == n {
@@ -141,7 +188,7 @@ pub fn part2_naive(input: &str) -> String {
         break;
     }
 
-    password(&longest.unwrap())
+    Ok(password(&longest.unwrap()))
 }
 
 fn password(list: &List) -> String {
@@ -171,8 +218,8 @@ fn map_to_password(c: &BTreeMap<usize, Set>) -> String {
 }
 
 // <https://en.wikipedia.org/wiki/Bron%E2%80%93Kerbosch_algorithm>
-pub fn part2_bk(input: &str) -> String {
-    let map = parse_input(input);
+pub fn part2_bk(input: &str) -> Result<String, ParseError> {
+    let map = parse_input(input)?;
 
     let r = Set::default();
     let p = map.keys().copied().collect();
@@ -180,7 +227,7 @@ pub fn part2_bk(input: &str) -> String {
     let mut c = BTreeMap::default();
     bk(r, p, x, &mut c, &map);
 
-    map_to_password(&c)
+    Ok(map_to_password(&c))
 }
 
 fn bk(r: Set, mut p: Set, mut x: Set, c: &mut BTreeMap<usize, Set>, n: &Map) {
@@ -208,8 +255,8 @@ fn bk(r: Set, mut p: Set, mut x: Set, c: &mut BTreeMap<usize, Set>, n: &Map) {
 }
 
 // definitely faster than part2_bk
-pub fn part2_bk_pivot(input: &str) -> String {
-    let map = parse_input(input);
+pub fn part2_bk_pivot(input: &str) -> Result<String, ParseError> {
+    let map = parse_input(input)?;
 
     let r = Set::default();
     let p = map.keys().copied().collect();
@@ -217,7 +264,7 @@ pub fn part2_bk_pivot(input: &str) -> String {
     let mut c = BTreeMap::default();
     bk_pivot(r, p, x, &mut c, &map);
 
-    map_to_password(&c)
+    Ok(map_to_password(&c))
 }
 
 fn bk_pivot(r: Set, mut p: Set, mut x: Set, c: &mut BTreeMap<usize, Set>, n: &Map) {
@@ -245,8 +292,8 @@ fn bk_pivot(r: Set, mut p: Set, mut x: Set, c: &mut BTreeMap<usize, Set>, n: &Ma
 }
 
 // the arena was worth it yay
-pub fn part2_bk_pivot_arena(input: &str) -> String {
-    let map = parse_input(input);
+pub fn part2_bk_pivot_arena(input: &str) -> Result<String, ParseError> {
+    let map = parse_input(input)?;
 
     let arena = Arena::default();
 
@@ -257,7 +304,7 @@ pub fn part2_bk_pivot_arena(input: &str) -> String {
     let mut c = BTreeMap::default();
     bk_pivot_arena(r, p, x, &mut c, &map, &arena);
 
-    map_to_password(&c)
+    Ok(map_to_password(&c))
 }
 
 /// Accumulator of allocated sets to allow later re-use.
@@ -379,13 +426,13 @@ fn bk_pivot_arena(
 }
 
 // the more favorable vertex order doesn't seem quite worth its calculation time
-pub fn part2_bk_degeneracy(input: &str) -> String {
-    let map = parse_input(input);
+pub fn part2_bk_degeneracy(input: &str) -> Result<String, ParseError> {
+    let map = parse_input(input)?;
 
     let mut c = BTreeMap::default();
     bk_degeneracy(&mut c, &map);
 
-    map_to_password(&c)
+    Ok(map_to_password(&c))
 }
 
 fn bk_degeneracy(c: &mut BTreeMap<usize, Set>, n: &Map) {
@@ -446,14 +493,14 @@ fn degeneracy_graph(n: &Map) -> List {
     l
 }
 
-pub fn part2_bk_degeneracy_arena(input: &str) -> String {
-    let map = parse_input(input);
+pub fn part2_bk_degeneracy_arena(input: &str) -> Result<String, ParseError> {
+    let map = parse_input(input)?;
 
     let arena = Arena::default();
     let mut c = BTreeMap::default();
     bk_degeneracy_arena(&mut c, &map, &arena);
 
-    map_to_password(&c)
+    Ok(map_to_password(&c))
 }
 
 fn bk_degeneracy_arena(c: &mut BTreeMap<usize, Set>, n: &Map, arena: &Arena) {
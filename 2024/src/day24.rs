@@ -1,311 +1,144 @@
-use std::{
-    borrow::Borrow,
-    hash::{Hash, Hasher},
-    mem,
-    ops::{BitAnd, BitOr, BitXor},
-    str::Lines,
-};
+use std::str::Lines;
 
-use ahash::HashSetExt;
-use aoc_rust::Solution;
+use aoc_rust::{
+    netlist::{self, Logic, Netlist, Wire},
+    timing::{self, Phase},
+    util::parse::ParseError,
+    Solution,
+};
 use eyre::Result;
-use fxhash::{FxHashMap, FxHashSet};
 use nom::{
     branch::alt,
     bytes::complete as by,
     character::complete as ch,
-    combinator::all_consuming,
-    sequence::{delimited, preceded, separated_pair},
+    combinator::{all_consuming, map, value},
+    sequence::{delimited, preceded, separated_pair, tuple},
     IResult,
 };
 
+aoc_rust::register_day! {
+    day: 24,
+    title: "Crossed Wires",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    let (netlist, inputs) = parse(input.trim())?;
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(&netlist, inputs))?;
+    let p2 = timing::phase(Phase::Part2, || part2(&netlist))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> u64 {
-    let mut lines = input.lines();
-    let mut inouts = parse_inputs(&mut lines);
-    let gates = parse_gates(&mut lines);
-
-    gates.iter().fold(0, |acc, gate| match gate.out.z() {
-        Some(z) => acc | (gate.out.eval(&mut inouts, &gates) as u64) << z,
-        None => acc,
-    })
-}
-
-fn part2(input: &str) -> String {
-    let (_, input) = input.split_once("\n\n").expect("bad input");
-    let gates = parse_gates(&mut input.lines());
-
-    let mut swapped = FxHashSet::with_capacity(8);
-
-    let x00 = InOut(*b"x00");
-    let y00 = InOut(*b"y00");
-    let z00 = InOut(*b"z00");
+fn part1(netlist: &Netlist<'_, Logic>, inputs: Inputs) -> Result<u64> {
+    let signals = netlist.evaluate(inputs)?;
 
-    /*
-        <https://en.wikipedia.org/wiki/Adder_(electronics)>
-
-        x ---\--------\
-             AND-\    XOR--- z
-        y ---/----+---/
-                   \-------- C
-    */
-
-    let xor_out = gates
-        .iter()
-        .find(|gate| gate.has_lhs(x00, XOR, y00))
-        .unwrap()
-        .out;
-
-    if xor_out != z00 {
-        swapped.insert(xor_out);
-    }
-
-    let mut carry = gates
-        .iter()
-        .find(|gate| gate.has_lhs(x00, AND, y00))
-        .unwrap()
-        .out;
-
-    let mut i = 1;
-
-    loop {
-        let a = (i / 10) + b'0';
-        let b = (i % 10) + b'0';
-        i += 1;
-
-        let x = InOut([b'x', a, b]);
-        let y = InOut([b'y', a, b]);
-        let z = InOut([b'z', a, b]);
-
-        /*
-            x ---\
-                 XOR1---\
-            y ---/      XOR2 --- z
-            C ----------/
-        */
-
-        let Some(xor1) = gates.iter().find(|gate| gate.has_lhs(x, XOR, y)) else {
-            break;
+    netlist.wires().try_fold(0, |acc, wire| {
+        let Some(z) = z_index(netlist.name(wire)) else {
+            return Ok(acc);
         };
 
-        let xor2 = gates
-            .iter()
-            .find(|gate| gate.op == XOR && gate.has_either_input(xor1.out, carry))
-            .unwrap();
+        let value = signals
+            .get(wire)
+            .ok_or_else(|| eyre::eyre!("wire `{}` has no value", netlist.name(wire)))?;
 
-        if xor2.out != z {
-            swapped.insert(z);
-            swapped.insert(xor2.out);
-        }
-
-        if !xor2.has_input(xor1.out) {
-            swapped.insert(xor1.out);
-        }
-
-        if !xor2.has_input(carry) {
-            swapped.insert(carry);
-        }
-
-        /*
-            x ---\--------\
-                 XOR1-\   AND1----\
-            y ---/----+---/       OR--- C
-                      |           |
-                     AND2--------/
-            C -------/
-        */
-
-        let and1_out = gates
-            .iter()
-            .find(|gate| gate.has_lhs(x, AND, y))
-            .unwrap()
-            .out;
-
-        let and2 = gates
-            .iter()
-            .find(|gate| gate.op == AND && gate.has_either_input(xor1.out, carry))
-            .unwrap();
-
-        if !and2.has_input(xor1.out) {
-            swapped.insert(xor1.out);
-        }
-
-        if !and2.has_input(carry) {
-            swapped.insert(carry);
-        }
-
-        let or = gates
-            .iter()
-            .find(|gate| gate.op == OR && gate.has_either_input(and1_out, and2.out))
-            .unwrap();
-
-        if !or.has_input(and1_out) {
-            swapped.insert(and1_out);
-        }
-
-        if !or.has_input(and2.out) {
-            swapped.insert(and2.out);
-        }
-
-        carry = or.out;
-    }
-
-    let mut swapped: Vec<_> = swapped.into_iter().collect();
-    swapped.sort_unstable();
-
-    swapped.join(",")
+        Ok(acc | (value as u64) << z)
+    })
 }
 
-fn parse_inputs(lines: &mut Lines<'_>) -> FxHashMap<InOut, bool> {
-    let mut inouts = FxHashMap::default();
+fn part2(netlist: &Netlist<'_, Logic>) -> Result<String> {
+    let swapped = netlist::check_adder(netlist, ["x", "y", "z"])?;
+    let names: Vec<_> = swapped.into_iter().map(|wire| netlist.name(wire)).collect();
 
-    for line in lines {
-        if line.is_empty() {
-            return inouts;
-        }
-
-        let (_, (inout, value)) =
-            all_consuming(separated_pair(InOut::parse, by::tag(": "), ch::u8))(line)
-                .expect("bad input");
+    Ok(names.join(","))
+}
 
-        inouts.insert(inout, value == 1);
-    }
+/// The circuit in Graphviz' DOT format, wires that part 2 considers
+/// miswired being highlighted.
+pub fn dot(input: &str) -> Result<String> {
+    let (netlist, _) = parse(input.trim())?;
+    let miswired = netlist::check_adder(&netlist, ["x", "y", "z"]).unwrap_or_default();
 
-    panic!("missing gates")
+    Ok(netlist.dot().highlight(miswired).to_string())
 }
 
-fn parse_gates(lines: &mut Lines<'_>) -> FxHashSet<Gate> {
-    let mut gates = FxHashSet::default();
-
-    for line in lines {
-        let (_, gate) = all_consuming(Gate::parse)(line).expect("bad input");
-        gates.insert(gate);
+fn z_index(name: &str) -> Option<u8> {
+    if let [b'z', a, b] = name.as_bytes() {
+        Some((a & 0xF) * 10 + (b & 0xF))
+    } else {
+        None
     }
-
-    gates
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct InOut([u8; 3]);
+/// Initial values of the input wires
+type Inputs = Vec<(Wire, bool)>;
 
-impl InOut {
-    fn parse(input: &str) -> IResult<&str, Self> {
-        let (rest, name) = by::take(3_usize)(input)?;
+/// The circuit alongside the initial values of its input wires.
+fn parse(input: &str) -> Result<(Netlist<'_, Logic>, Inputs), ParseError> {
+    let mut lines = input.lines();
+    let mut netlist = Netlist::new();
+    let inputs = parse_inputs(input, &mut lines, &mut netlist)?;
+    parse_gates(input, &mut lines, &mut netlist)?;
 
-        Ok((rest, Self(name.as_bytes().try_into().unwrap())))
-    }
+    Ok((netlist, inputs))
+}
 
-    fn z(self) -> Option<u8> {
-        if let Self([b'z', a, b]) = self {
-            Some((a & 0xF) * 10 + (b & 0xF))
-        } else {
-            None
-        }
-    }
+fn parse_inputs<'a>(
+    input: &str,
+    lines: &mut Lines<'a>,
+    netlist: &mut Netlist<'a, Logic>,
+) -> Result<Inputs, ParseError> {
+    let mut inputs = Vec::new();
 
-    fn eval(self, values: &mut FxHashMap<InOut, bool>, gates: &FxHashSet<Gate>) -> bool {
-        if let Some(value) = values.get(&self) {
-            return *value;
+    for line in lines {
+        if line.is_empty() {
+            return Ok(inputs);
         }
 
-        let value = gates.get(&self).unwrap().eval(values, gates);
-        values.insert(self, value);
+        let bit = map(ch::one_of("01"), |bit| bit == '1');
+        let (_, (name, value)) =
+            all_consuming(separated_pair(parse_name, by::tag(": "), bit))(line)
+                .map_err(|err| ParseError::from_nom(input, err))?;
 
-        value
+        inputs.push((netlist.wire(name), value));
     }
-}
 
-impl Borrow<str> for InOut {
-    fn borrow(&self) -> &str {
-        std::str::from_utf8(&self.0).unwrap()
-    }
-}
-
-#[derive(PartialEq, Eq)]
-struct Gate {
-    in1: InOut,
-    op: fn(bool, bool) -> bool,
-    in2: InOut,
-    out: InOut,
+    Err(ParseError::new("missing empty line before gates"))
 }
 
-const AND: fn(bool, bool) -> bool = bool::bitand;
-const OR: fn(bool, bool) -> bool = bool::bitor;
-const XOR: fn(bool, bool) -> bool = bool::bitxor;
-
-impl Gate {
-    fn has_lhs(&self, a: InOut, op: fn(bool, bool) -> bool, b: InOut) -> bool {
-        self.in1 == a && self.op == op && self.in2 == b
-    }
-
-    fn has_input(&self, input: InOut) -> bool {
-        self.in1 == input || self.in2 == input
-    }
-
-    fn has_either_input(&self, a: InOut, b: InOut) -> bool {
-        self.has_input(a) || self.has_input(b)
-    }
-
-    fn parse(input: &str) -> IResult<&str, Self> {
-        let parse_op = |input| {
-            let (rest, op) = alt((by::tag("AND"), by::tag("OR"), by::tag("XOR")))(input)?;
-
-            let op = match op {
-                "AND" => AND,
-                "OR" => OR,
-                "XOR" => XOR,
-                _ => unreachable!(),
-            };
-
-            Ok((rest, op))
-        };
-
-        let (rest, mut in1) = InOut::parse(input)?;
-        let (rest, op) = delimited(by::tag(" "), parse_op, by::tag(" "))(rest)?;
-        let (rest, mut in2) = InOut::parse(rest)?;
-        let (rest, out) = preceded(by::tag(" -> "), InOut::parse)(rest)?;
-
-        if in2 < in1 {
-            mem::swap(&mut in1, &mut in2);
+fn parse_gates<'a>(
+    input: &str,
+    lines: &mut Lines<'a>,
+    netlist: &mut Netlist<'a, Logic>,
+) -> Result<(), ParseError> {
+    for line in lines {
+        let logic = alt((
+            value(Logic::And, by::tag("AND")),
+            value(Logic::Or, by::tag("OR")),
+            value(Logic::Xor, by::tag("XOR")),
+        ));
+
+        let gate = tuple((
+            parse_name,
+            delimited(by::tag(" "), logic, by::tag(" ")),
+            parse_name,
+            preceded(by::tag(" -> "), parse_name),
+        ));
+
+        let (_, (in1, logic, in2, out)) =
+            all_consuming(gate)(line).map_err(|err| ParseError::from_nom(input, err))?;
+
+        let inputs = vec![netlist.wire(in1), netlist.wire(in2)];
+        let wire = netlist.wire(out);
+
+        if netlist.drive(wire, logic, inputs).is_some() {
+            return Err(ParseError::at(input, out, "wire is driven twice"));
         }
-
-        Ok((rest, Self { in1, op, in2, out }))
-    }
-
-    fn eval(&self, values: &mut FxHashMap<InOut, bool>, gates: &FxHashSet<Gate>) -> bool {
-        let Self {
-            in1,
-            op,
-            in2,
-            out: _,
-        } = self;
-
-        (op)(in1.eval(values, gates), in2.eval(values, gates))
-    }
-}
-
-impl PartialEq<InOut> for Gate {
-    fn eq(&self, gate: &InOut) -> bool {
-        self.out == *gate
     }
-}
 
-impl Hash for Gate {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.out.hash(state);
-    }
+    Ok(())
 }
 
-impl Borrow<InOut> for Gate {
-    fn borrow(&self) -> &InOut {
-        &self.out
-    }
+fn parse_name(input: &str) -> IResult<&str, &str> {
+    by::take_while_m_n(3, 3, |c: char| c.is_ascii_alphanumeric())(input)
 }
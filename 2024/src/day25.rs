@@ -1,22 +1,31 @@
-use aoc_rust::Solution;
+use aoc_rust::{
+    timing::{self, Phase},
+    util::parse::ParseError,
+    Solution,
+};
 use eyre::Result;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
+aoc_rust::register_day! {
+    day: 25,
+    title: "Code Chronicle",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
     let input = input.trim();
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input))?;
+    let p2 = timing::phase(Phase::Part2, || part2(input));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> usize {
+fn part1(input: &str) -> Result<usize, ParseError> {
     let mut locks = Vec::new();
     let mut keys = Vec::new();
 
     for grid in input.split("\n\n") {
-        let (schematic, is_lock) = Schematic::parse(grid);
+        let (schematic, is_lock) = Schematic::parse(input, grid)?;
 
         if is_lock {
             locks.push(schematic);
@@ -28,7 +37,7 @@ fn part1(input: &str) -> usize {
     locks.sort_unstable_by_key(|lock| lock.extremum);
     keys.sort_unstable_by_key(|key| key.extremum);
 
-    locks
+    let count = locks
         .par_iter()
         .map(|lock| {
             let mut count = 0;
@@ -45,7 +54,9 @@ fn part1(input: &str) -> usize {
 
             count
         })
-        .sum()
+        .sum();
+
+    Ok(count)
 }
 
 struct Schematic {
@@ -54,34 +65,101 @@ struct Schematic {
 }
 
 impl Schematic {
-    fn parse(input: &str) -> (Self, bool) {
-        let mut chunks = input.as_bytes().chunks(6);
+    fn parse(input: &str, grid: &str) -> Result<(Self, bool), ParseError> {
+        let rows: Vec<_> = grid.lines().collect();
+
+        for line in rows.iter() {
+            if line.len() != 5 {
+                return Err(ParseError::at(input, line, "expected 5 columns"));
+            } else if let Some(i) = line.bytes().position(|byte| byte != b'#' && byte != b'.') {
+                return Err(ParseError::at(input, &line[i..], "expected `#` or `.`"));
+            }
+        }
 
-        let chunk = chunks.next().unwrap();
-        let mut heights = [-1; 5];
+        let [first, rest @ ..] = rows.as_slice() else {
+            return Err(ParseError::at(input, grid, "expected 7 rows"));
+        };
 
-        for (byte, height) in chunk.iter().zip(heights.iter_mut()) {
-            *height += i8::from(*byte == b'#');
+        if rest.len() != 6 {
+            return Err(ParseError::at(input, grid, "expected 7 rows"));
         }
 
-        let is_lock = heights == [0; 5];
+        let is_lock = first.bytes().all(|byte| byte == b'#');
+        let mut heights = [-1; 5];
 
-        for chunk in chunks.take(6) {
-            for (byte, height) in chunk.iter().zip(heights.iter_mut()) {
-                *height += i8::from(*byte == b'#');
+        for row in rows.iter() {
+            for (byte, height) in row.bytes().zip(heights.iter_mut()) {
+                *height += i8::from(byte == b'#');
             }
         }
 
         let extremum = if is_lock {
-            *heights.iter().min().unwrap()
+            heights.into_iter().fold(i8::MAX, i8::min)
         } else {
-            *heights.iter().max().unwrap()
+            heights.into_iter().fold(i8::MIN, i8::max)
         };
 
-        (Self { heights, extremum }, is_lock)
+        Ok((Self { heights, extremum }, is_lock))
     }
 }
 
 fn part2(_: &str) -> Box<str> {
     Box::from("x")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "\
+#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+#####
+##.##
+.#.##
+...##
+...#.
+...#.
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+
+.....
+.....
+#.#..
+###..
+###.#
+###.#
+#####
+
+.....
+.....
+.....
+#....
+#.#..
+#.#.#
+#####";
+
+        assert_eq!(part1(input).unwrap(), 3);
+    }
+
+    #[test]
+    fn truncated() {
+        let err = part1("#####\n.####\n.....").unwrap_err();
+        assert!(err.to_string().contains("expected 7 rows"), "{err}");
+    }
+}
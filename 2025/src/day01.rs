@@ -1,27 +1,57 @@
-use aoc_rust::Solution;
+use aoc_rust::{
+    Solution,
+    timing::{self, Phase},
+    util::parse::{ParseError, parse_at},
+};
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 1,
+    title: "Secret Entrance",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    let rotations = timing::phase(Phase::Parse, || parse_rotations(input))?;
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(&rotations));
+    let p2 = timing::phase(Phase::Part2, || part2(&rotations));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> u64 {
+#[derive(Copy, Clone)]
+enum Direction {
+    Left,
+    Right,
+}
+
+fn parse_rotations(input: &str) -> Result<Vec<(Direction, i32)>, ParseError> {
+    input
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (dir, n) = if let Some(n) = line.strip_prefix('L') {
+                (Direction::Left, n)
+            } else if let Some(n) = line.strip_prefix('R') {
+                (Direction::Right, n)
+            } else {
+                return Err(ParseError::at(input, line, "expected `L<n>` or `R<n>`"));
+            };
+
+            Ok((dir, parse_at::<u16>(input, n)? as i32))
+        })
+        .collect()
+}
+
+fn part1(rotations: &[(Direction, i32)]) -> u64 {
     let mut curr = 50;
     let mut count = 0;
 
-    for line in input.lines() {
-        let (a, b) = line.split_at(1);
-        let n: i32 = b.parse().unwrap();
-
-        match a {
-            "L" => curr -= n,
-            "R" => curr += n,
-            _ => unreachable!(),
+    for &(dir, n) in rotations {
+        match dir {
+            Direction::Left => curr -= n,
+            Direction::Right => curr += n,
         }
 
         curr = curr.rem_euclid(100);
@@ -34,21 +64,17 @@ fn part1(input: &str) -> u64 {
     count
 }
 
-fn part2(input: &str) -> i32 {
+fn part2(rotations: &[(Direction, i32)]) -> i32 {
     let mut curr = 50;
     let mut count = 0;
 
-    for line in input.lines() {
-        let (a, b) = line.split_at(1);
-        let mut n: i32 = b.parse().unwrap();
-
+    for &(dir, mut n) in rotations {
         count += n / 100;
         n %= 100;
 
-        let next = match a {
-            "L" => curr - n,
-            "R" => curr + n,
-            _ => unreachable!(),
+        let next = match dir {
+            Direction::Left => curr - n,
+            Direction::Right => curr + n,
         };
 
         if (next <= 0 && curr != 0) || next >= 100 {
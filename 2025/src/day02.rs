@@ -1,23 +1,44 @@
-use aoc_rust::Solution;
+use aoc_rust::{
+    Solution,
+    timing::{self, Phase},
+    util::parse::{ParseContext, ParseError, parse_at},
+};
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 2,
+    title: "Gift Shop",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    let ranges = timing::phase(Phase::Parse, || parse_ranges(input))?;
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(&ranges));
+    let p2 = timing::phase(Phase::Part2, || part2(&ranges));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> u64 {
-    let mut sum = 0;
+fn parse_ranges(input: &str) -> Result<Vec<(u64, u64)>, ParseError> {
+    input
+        .trim()
+        .split(',')
+        .map(str::trim)
+        .map(|range| {
+            let (first, last) =
+                range
+                    .split_once('-')
+                    .context_at(input, range, "expected `<first>-<last>`")?;
+
+            Ok((parse_at(input, first)?, parse_at(input, last)?))
+        })
+        .collect()
+}
 
-    for range in input.split(',') {
-        let (first, last) = range.split_once('-').unwrap();
-        let first: u64 = first.parse().unwrap();
-        let last: u64 = last.parse().unwrap();
+fn part1(ranges: &[(u64, u64)]) -> u64 {
+    let mut sum = 0;
 
+    for &(first, last) in ranges {
         for n in first..=last {
             fn invalid(n: u64) -> bool {
                 let mut digits = 0;
@@ -51,14 +72,10 @@ fn part1(input: &str) -> u64 {
     sum
 }
 
-fn part2(input: &str) -> u64 {
+fn part2(ranges: &[(u64, u64)]) -> u64 {
     let mut sum = 0;
 
-    for range in input.split(',') {
-        let (first, last) = range.split_once('-').unwrap();
-        let first: u64 = first.parse().unwrap();
-        let last: u64 = last.parse().unwrap();
-
+    for &(first, last) in ranges {
         for n in first..=last {
             fn invalid(n: u64) -> bool {
                 let mut factor = 10;
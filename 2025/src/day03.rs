@@ -1,41 +1,73 @@
-use aoc_rust::Solution;
+use aoc_rust::{
+    Solution,
+    timing::{self, Phase},
+    util::parse::ParseError,
+};
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 3,
+    title: "Lobby",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    let banks = timing::phase(Phase::Parse, || parse_banks(input))?;
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(&banks));
+    let p2 = timing::phase(Phase::Part2, || part2(&banks));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> u64 {
-    solve::<2>(input)
+fn parse_banks(input: &str) -> Result<Vec<&[u8]>, ParseError> {
+    input
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            if let Some(i) = line.bytes().position(|byte| !byte.is_ascii_digit()) {
+                return Err(ParseError::at(input, &line[i..], "expected digit"));
+            }
+
+            if line.len() < 12 {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    "bank must have at least 12 batteries",
+                ));
+            }
+
+            Ok(line.as_bytes())
+        })
+        .collect()
+}
+
+fn part1(banks: &[&[u8]]) -> u64 {
+    solve::<2>(banks)
 }
 
-fn part2(input: &str) -> u64 {
-    solve::<12>(input)
+fn part2(banks: &[&[u8]]) -> u64 {
+    solve::<12>(banks)
 }
 
-fn solve<const N: usize>(input: &str) -> u64 {
+fn solve<const N: usize>(banks: &[&[u8]]) -> u64 {
     let mut sum = 0;
 
-    for bank in input.lines() {
-        let mut remaining = bank;
+    for bank in banks {
+        let mut remaining = *bank;
         let mut joltage = 0;
 
         for i in (0..N).rev() {
             let (j, max) = remaining[..remaining.len() - i]
-                .bytes()
+                .iter()
                 .enumerate()
                 .rev() // `max_by_key` takes the *last* biggest
-                .max_by_key(|(_, joltage)| *joltage)
+                .max_by_key(|(_, joltage)| **joltage)
                 .unwrap();
 
             remaining = &remaining[j + 1..];
             joltage *= 10;
-            joltage += (max as u8 - b'0') as u64;
+            joltage += (max - b'0') as u64;
         }
 
         sum += joltage;
@@ -1,26 +1,58 @@
 use std::cmp;
 
-use aoc_rust::Solution;
+use aoc_rust::{
+    Solution,
+    timing::{self, Phase},
+    util::parse::ParseError,
+};
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 4,
+    title: "Printing Department",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    let grid = timing::phase(Phase::Parse, || parse_grid(input))?;
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(grid.clone()));
+    let p2 = timing::phase(Phase::Part2, || part2(grid));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> u32 {
-    let mut grid: Vec<_> = input.lines().map(str::as_bytes).map(Box::from).collect();
+fn parse_grid(input: &str) -> Result<Vec<Box<[u8]>>, ParseError> {
+    let lines = input
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty());
 
-    scan_diagram::<false>(&mut grid)
+    let mut grid: Vec<Box<[u8]>> = Vec::new();
+
+    for line in lines {
+        if let Some(width) = grid.first().map(|row| row.len())
+            && line.len() != width
+        {
+            let msg = format!("expected {width} positions per row");
+
+            return Err(ParseError::at(input, line, msg));
+        }
+
+        if let Some(i) = line.bytes().position(|byte| byte != b'@' && byte != b'.') {
+            return Err(ParseError::at(input, &line[i..], "expected `@` or `.`"));
+        }
+
+        grid.push(Box::from(line.as_bytes()));
+    }
+
+    Ok(grid)
 }
 
-fn part2(input: &str) -> u32 {
-    let mut grid: Vec<Box<[_]>> = input.lines().map(str::as_bytes).map(Box::from).collect();
+fn part1(mut grid: Vec<Box<[u8]>>) -> u32 {
+    scan_diagram::<false>(&mut grid)
+}
 
+fn part2(mut grid: Vec<Box<[u8]>>) -> u32 {
     let mut total = 0;
 
     loop {
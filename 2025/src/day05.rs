@@ -1,21 +1,68 @@
-use aoc_rust::Solution;
+use aoc_rust::{
+    Solution,
+    timing::{self, Phase},
+    util::parse::{ParseContext, ParseError, parse_at},
+};
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 5,
+    title: "Cafeteria",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    let (ranges, ids) = timing::phase(Phase::Parse, || parse_input(input))?;
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(&ranges, &ids));
+    let p2 = timing::phase(Phase::Part2, || part2(&ranges));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> usize {
-    let mut lines = input.lines();
+type Range = (u64, u64);
+
+fn parse_input(input: &str) -> Result<(Vec<Range>, Vec<u64>), ParseError> {
+    let mut lines = input
+        .lines()
+        .map(str::trim_end)
+        .skip_while(|line| line.is_empty());
+
+    let ranges = lines
+        .by_ref()
+        .take_while(|line| !line.is_empty())
+        .map(|line| {
+            let (from, to) =
+                line.split_once('-')
+                    .context_at(input, line, "expected `<from>-<to>`")?;
+
+            let from = parse_at(input, from)?;
+
+            let to = match parse_at::<u64>(input, to)? {
+                u64::MAX => return Err(ParseError::at(input, to, "number is too large")),
+                to if to < from => {
+                    let msg = "range must not end before it starts";
+
+                    return Err(ParseError::at(input, line, msg));
+                }
+                to => to,
+            };
 
-    let mut events = parse_ranges(lines.by_ref());
+            Ok((from, to))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let ids = lines
+        .filter(|line| !line.is_empty())
+        .map(|line| parse_at(input, line))
+        .collect::<Result<_, _>>()?;
+
+    Ok((ranges, ids))
+}
+
+fn part1(ranges: &[Range], ids: &[u64]) -> usize {
+    let mut events = range_events(ranges);
 
-    let ids = lines.map(str::parse).flat_map(Result::ok).map(|id| Event {
+    let ids = ids.iter().map(|&id| Event {
         value: id,
         kind: EventKind::Query,
     });
@@ -38,8 +85,8 @@ fn part1(input: &str) -> usize {
         .count()
 }
 
-fn part2(input: &str) -> u64 {
-    let mut events = parse_ranges(input.lines());
+fn part2(ranges: &[Range]) -> u64 {
+    let mut events = range_events(ranges);
     events.sort_unstable();
 
     let mut total = 0;
@@ -76,14 +123,10 @@ struct Event {
     kind: EventKind,
 }
 
-fn parse_ranges<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<Event> {
-    let ranges = lines
-        .take_while(|line| !line.is_empty())
-        .flat_map(|line| line.split_once('-'))
-        .flat_map(|(from, to)| from.parse::<u64>().ok().zip(to.parse::<u64>().ok()));
-
+fn range_events(ranges: &[Range]) -> Vec<Event> {
     ranges
-        .flat_map(|(from, to)| {
+        .iter()
+        .flat_map(|&(from, to)| {
             [
                 Event {
                     value: from,
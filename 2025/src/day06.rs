@@ -1,41 +1,72 @@
-use aoc_rust::Solution;
-use eyre::Result;
+use aoc_rust::{
+    Solution,
+    timing::{self, Phase},
+    util::parse::{ParseError, parse_at},
+};
+use eyre::{ContextCompat, Result};
+
+aoc_rust::register_day! {
+    day: 6,
+    title: "Trash Compactor",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    let (lines, ops) = timing::phase(Phase::Parse, || parse_worksheet(input))?;
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(input, &lines, &ops))?;
+    let p2 = timing::phase(Phase::Part2, || part2(lines, &ops))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> u64 {
-    let mut lines = input.lines();
-    let ops = parse_ops(lines.by_ref());
+/// Returns the lines of numbers and the operators of the last line.
+fn parse_worksheet(input: &str) -> Result<(Vec<&str>, Vec<Op>), ParseError> {
+    let mut lines: Vec<_> = input
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+
+    let ops = lines
+        .pop()
+        .ok_or_else(|| ParseError::new("missing operators"))?
+        .split_ascii_whitespace()
+        .map(|op| Op::parse(input, op))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for line in lines.iter() {
+        if let Some(i) = line.bytes().position(|b| b != b' ' && !b.is_ascii_digit()) {
+            return Err(ParseError::at(input, &line[i..], "expected digit or space"));
+        }
+
+        if line.split_ascii_whitespace().count() != ops.len() {
+            let msg = format!("expected {} numbers per row", ops.len());
+
+            return Err(ParseError::at(input, line, msg));
+        }
+    }
+
+    Ok((lines, ops))
+}
+
+fn part1(input: &str, lines: &[&str], ops: &[Op]) -> Result<u64, ParseError> {
     let mut curr: Vec<_> = ops.iter().copied().map(Op::init).collect();
 
     for line in lines {
         let iter = line
             .split_ascii_whitespace()
-            .map(str::parse)
-            .map(Result::unwrap)
             .zip(ops.iter().copied())
             .zip(curr.iter_mut());
 
         for ((n, op), curr) in iter {
-            *curr = op.apply(*curr, n);
+            *curr = op.apply(*curr, parse_at(input, n)?);
         }
     }
 
-    curr.into_iter().sum()
+    Ok(curr.into_iter().sum())
 }
 
-fn part2(input: &str) -> u64 {
-    let mut lines = input.lines();
-    let ops = parse_ops(lines.by_ref());
-
-    let mut lines: Vec<_> = lines.collect();
+fn part2(mut lines: Vec<&str>, ops: &[Op]) -> Result<u64> {
     let mut col = Vec::new();
 
     let mut curr_i = 0;
@@ -55,18 +86,18 @@ fn part2(input: &str) -> u64 {
         col.clear();
 
         for line in lines.iter_mut() {
-            col.push(line[..max_len].as_bytes());
+            col.push(&line.as_bytes()[..max_len.min(line.len())]);
             *line = line.get(max_len + 1..).unwrap_or_default();
         }
 
-        let op = ops[curr_i];
+        let op = *ops.get(curr_i).wrap_err("more columns than operators")?;
         let mut curr = op.init();
 
         for i in (0..max_len).rev() {
             let mut n = 0;
 
             for row in col.iter() {
-                let digit = row[i];
+                let digit = row.get(i).copied().unwrap_or(b' ');
 
                 if digit != b' ' {
                     n *= 10;
@@ -81,16 +112,7 @@ fn part2(input: &str) -> u64 {
         sum += curr;
     }
 
-    sum
-}
-
-fn parse_ops<'a>(mut lines: impl DoubleEndedIterator<Item = &'a str>) -> Vec<Op> {
-    lines
-        .next_back()
-        .into_iter()
-        .flat_map(str::split_ascii_whitespace)
-        .map(Op::parse)
-        .collect()
+    Ok(sum)
 }
 
 #[derive(Copy, Clone)]
@@ -114,11 +136,11 @@ impl Op {
         }
     }
 
-    fn parse(s: &str) -> Self {
+    fn parse(input: &str, s: &str) -> Result<Self, ParseError> {
         match s {
-            "+" => Self::Add,
-            "*" => Self::Mul,
-            _ => unreachable!(),
+            "+" => Ok(Self::Add),
+            "*" => Ok(Self::Mul),
+            _ => Err(ParseError::at(input, s, "expected `+` or `*`")),
         }
     }
 }
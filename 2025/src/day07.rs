@@ -1,32 +1,87 @@
 use std::mem;
 
-use aoc_rust::Solution;
+use aoc_rust::{
+    Solution,
+    timing::{self, Phase},
+    util::parse::{ParseContext, ParseError},
+};
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 7,
+    title: "Laboratories",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    let manifold = timing::phase(Phase::Parse, || Manifold::parse(input))?;
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(&manifold));
+    let p2 = timing::phase(Phase::Part2, || part2(&manifold));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> u64 {
-    let mut lines = input.lines();
+struct Manifold<'a> {
+    width: usize,
+    start: usize,
+    rows: Vec<&'a [u8]>,
+}
+
+impl<'a> Manifold<'a> {
+    fn parse(input: &'a str) -> Result<Self, ParseError> {
+        let mut lines = input
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty());
+
+        let first = lines
+            .next()
+            .ok_or_else(|| ParseError::new("empty manifold"))?;
+
+        let width = first.len();
 
-    let Some(start) = lines.next().and_then(|line| line.find('S')) else {
-        return 0;
-    };
+        let start = first
+            .find('S')
+            .context_at(input, first, "missing start `S`")?;
 
+        let mut rows = Vec::new();
+
+        for line in lines {
+            if line.len() != width {
+                let msg = format!("expected {width} positions per row");
+
+                return Err(ParseError::at(input, line, msg));
+            }
+
+            for (i, byte) in line.bytes().enumerate() {
+                match byte {
+                    b'.' => {}
+                    b'^' if i > 0 && i < width - 1 => {}
+                    b'^' => {
+                        let msg = "splitters can not be at the edge";
+
+                        return Err(ParseError::at(input, &line[i..], msg));
+                    }
+                    _ => return Err(ParseError::at(input, &line[i..], "expected `.` or `^`")),
+                }
+            }
+
+            rows.push(line.as_bytes());
+        }
+
+        Ok(Self { width, start, rows })
+    }
+}
+
+fn part1(manifold: &Manifold<'_>) -> u64 {
     let mut curr = Vec::with_capacity(32);
     let mut next = Vec::with_capacity(32);
 
-    curr.push(start);
+    curr.push(manifold.start);
 
     let mut splits = 0;
 
-    for line in lines.map(str::as_bytes) {
+    for line in manifold.rows.iter() {
         for c in curr.drain(..) {
             if line[c] == b'.' {
                 next.push(c);
@@ -45,23 +100,13 @@ fn part1(input: &str) -> u64 {
     splits
 }
 
-fn part2(input: &str) -> u64 {
-    let mut lines = input.lines();
-
-    let Some(first) = lines.next() else { return 0 };
-
-    let width = first.len();
-
-    let Some(start) = first.find('S') else {
-        return 0;
-    };
-
-    let mut curr = vec![None; width];
-    let mut next = vec![None; width];
+fn part2(manifold: &Manifold<'_>) -> u64 {
+    let mut curr = vec![None; manifold.width];
+    let mut next = vec![None; manifold.width];
 
-    curr[start] = Some(1_u64);
+    curr[manifold.start] = Some(1_u64);
 
-    for line in lines.map(str::as_bytes) {
+    for line in manifold.rows.iter() {
         for (x, c) in curr.iter_mut().enumerate() {
             let Some(timelines) = c.take() else { continue };
 
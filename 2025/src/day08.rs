@@ -1,13 +1,22 @@
-use std::{cmp::Reverse, str::FromStr};
-
-use aoc_rust::Solution;
-use eyre::{ContextCompat, Result};
+use std::cmp::Reverse;
+
+use aoc_rust::{
+    Solution,
+    timing::{self, Phase},
+    util::parse::{ParseContext, ParseError, parse_at},
+};
+use eyre::Result;
+
+aoc_rust::register_day! {
+    day: 8,
+    title: "Playground",
+}
 
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    let boxes = timing::phase(Phase::Parse, || JunctionBox::parse_all(input))?;
 
-    let p1 = part1(input)?;
-    let p2 = part2(input)?;
+    let p1 = timing::phase(Phase::Part1, || part1(&boxes))?;
+    let p2 = timing::phase(Phase::Part2, || part2(&boxes))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
@@ -20,16 +29,43 @@ struct JunctionBox {
 }
 
 impl JunctionBox {
-    fn parse_all(input: &str) -> Result<Vec<Self>> {
+    fn parse_all(input: &str) -> Result<Vec<Self>, ParseError> {
+        let lines = input
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty());
+
         let mut boxes = Vec::new();
 
-        for line in input.lines() {
-            boxes.push(line.parse()?);
+        for line in lines {
+            boxes.push(Self::parse(input, line)?);
+        }
+
+        if boxes.len() < 2 {
+            return Err(ParseError::new("expected at least two junction boxes"));
         }
 
         Ok(boxes)
     }
 
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let mut nums = line.split(',');
+
+        let mut num = || {
+            let n = nums
+                .next()
+                .context_at(input, line, "expected `<x>,<y>,<z>`")?;
+
+            parse_at(input, n)
+        };
+
+        Ok(Self {
+            x: num()?,
+            y: num()?,
+            z: num()?,
+        })
+    }
+
     fn all_pairs(boxes: &[Self]) -> Vec<(usize, usize)> {
         let mut pairs = Vec::with_capacity((boxes.len() * (boxes.len() - 1)) / 2);
 
@@ -50,23 +86,8 @@ impl JunctionBox {
     }
 }
 
-impl FromStr for JunctionBox {
-    type Err = eyre::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut nums = s.split(',').map(str::parse);
-
-        Ok(Self {
-            x: nums.next().wrap_err("missing x")??,
-            y: nums.next().wrap_err("missing y")??,
-            z: nums.next().wrap_err("missing z")??,
-        })
-    }
-}
-
-fn part1(input: &str) -> Result<usize> {
-    let boxes = JunctionBox::parse_all(input)?;
-    let pairs = JunctionBox::all_pairs(&boxes);
+fn part1(boxes: &[JunctionBox]) -> Result<usize> {
+    let pairs = JunctionBox::all_pairs(boxes);
 
     let mut circuits = vec![None; boxes.len()];
     let mut next_circuit = 0_u16;
@@ -113,6 +134,8 @@ fn part1(input: &str) -> Result<usize> {
 
     lengths.push(len);
 
+    eyre::ensure!(lengths.len() > 3, "fewer than four circuits");
+
     let (biggest, _, _) = lengths.select_nth_unstable_by_key(3, |n| Reverse(*n));
 
     println!("{biggest:?}");
@@ -120,9 +143,8 @@ fn part1(input: &str) -> Result<usize> {
     Ok(biggest.into_iter().map(|a| *a).product())
 }
 
-fn part2(input: &str) -> Result<i64> {
-    let boxes = JunctionBox::parse_all(input)?;
-    let pairs = JunctionBox::all_pairs(&boxes);
+fn part2(boxes: &[JunctionBox]) -> Result<i64> {
+    let pairs = JunctionBox::all_pairs(boxes);
 
     let mut circuits = vec![None; boxes.len()];
     let mut next_circuit = 0_u16;
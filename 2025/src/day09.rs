@@ -1,20 +1,27 @@
 use std::{cmp, collections::HashSet};
 
-use aoc_rust::Solution;
+use aoc_rust::{
+    Solution,
+    timing::{self, Phase},
+    util::parse::{ParseContext, ParseError, parse_at},
+};
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 9,
+    title: "Movie Theater",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    let tiles = timing::phase(Phase::Parse, || Tile::parse_all(input))?;
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(&tiles));
+    let p2 = timing::phase(Phase::Part2, || part2(&tiles));
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> u64 {
-    let tiles = Tile::parse_all(input);
-
+fn part1(tiles: &[Tile]) -> u64 {
     let mut max = 0;
 
     for i in 0..tiles.len() - 1 {
@@ -33,9 +40,7 @@ fn part1(input: &str) -> u64 {
     max
 }
 
-fn part2(input: &str) -> u64 {
-    let tiles = Tile::parse_all(input);
-
+fn part2(tiles: &[Tile]) -> u64 {
     let mut max_x = 0;
     let mut max_y = 0;
 
@@ -184,18 +189,28 @@ impl Tile {
         Self { x, y }
     }
 
-    fn parse_all(input: &str) -> Vec<Self> {
-        input
+    fn parse_all(input: &str) -> Result<Vec<Self>, ParseError> {
+        let tiles = input
             .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
             .map(|line| {
-                let (x, y) = line.split_once(',').unwrap();
-
-                Tile {
-                    x: x.parse().unwrap(),
-                    y: y.parse().unwrap(),
-                }
+                let (x, y) = line
+                    .split_once(',')
+                    .context_at(input, line, "expected `<x>,<y>`")?;
+
+                Ok(Tile {
+                    x: parse_at::<u32>(input, x)?.into(),
+                    y: parse_at::<u32>(input, y)?.into(),
+                })
             })
-            .collect()
+            .collect::<Result<Vec<_>, ParseError>>()?;
+
+        if tiles.len() < 2 {
+            return Err(ParseError::new("expected at least two red tiles"));
+        }
+
+        Ok(tiles)
     }
 
     fn area(a: Self, b: Self) -> u64 {
@@ -1,36 +1,89 @@
-use std::{cmp, ops::BitXor};
-
-use aoc_rust::Solution;
-use eyre::Result;
+use std::cmp;
+
+use aoc_rust::{
+    Solution,
+    timing::{self, Phase},
+    util::{
+        linear::{BigInt, LinearSystem},
+        parse::{ParseContext, ParseError, parse_at},
+    },
+};
+use eyre::{ContextCompat, Result};
 use fxhash::FxHashMap;
 
-pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+aoc_rust::register_day! {
+    day: 10,
+    title: "Factory",
+}
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+pub fn run(input: &str) -> Result<Solution> {
+    let machines = timing::phase(Phase::Parse, || {
+        input
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .map(|line| Machine::parse(input, line))
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+
+    let p1 = timing::phase(Phase::Part1, || part1(&machines))?;
+    let p2 = timing::phase(Phase::Part2, || part2(&machines))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> usize {
-    let mut sum = 0;
+struct Machine {
+    lights: u16,
+    buttons: Vec<Box<[usize]>>,
+    joltage: Box<[u16]>,
+}
 
-    for line in input.lines() {
+impl Machine {
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
         let mut iter = line.split_ascii_whitespace();
 
-        let lights = parse_lights(iter.next().unwrap());
-        let _joltage = iter.next_back();
+        let (lights, joltage) = iter.next().zip(iter.next_back()).context_at(
+            input,
+            line,
+            "expected `[<lights>] <buttons> {<joltage>}`",
+        )?;
+
+        let (lights, len) = parse_lights(input, lights)?;
+        let joltage = parse_joltage(input, joltage)?;
+
+        if joltage.len() != len {
+            let msg = format!("expected {len} joltage requirements");
+
+            return Err(ParseError::at(input, line, msg));
+        }
+
+        let buttons = iter
+            .map(|button| parse_button(input, button, len))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            lights,
+            buttons,
+            joltage,
+        })
+    }
+}
+
+fn part1(machines: &[Machine]) -> Result<usize> {
+    let mut sum = 0;
 
-        let buttons: Vec<u16> = iter
-            .map(parse_button)
+    for machine in machines {
+        let buttons: Vec<u16> = machine
+            .buttons
+            .iter()
             .map(|button| button.iter().fold(0, |button, &i| button | (1 << i)))
             .collect();
 
-        sum += solve_parity(lights, &buttons).unwrap();
+        sum += solve_parity(machine.lights, &buttons)
+            .wrap_err("no button presses configure the indicator lights")?;
     }
 
-    sum
+    Ok(sum)
 }
 
 type CacheP1<'a> = FxHashMap<(u16, &'a [u16]), Option<usize>>;
@@ -81,145 +134,194 @@ fn solve_parity(lights: u16, buttons: &[u16]) -> Option<usize> {
     recurse_outer(lights, *button, rest, &mut cache)
 }
 
-fn part2(input: &str) -> usize {
+fn part2(machines: &[Machine]) -> Result<usize> {
     let mut sum = 0;
 
-    for line in input.lines() {
-        let mut iter = line.split_ascii_whitespace();
-
-        let _lights = iter.next();
-        let joltage = parse_joltage(iter.next_back().unwrap());
+    for machine in machines {
+        sum += min_presses(&machine.joltage, &machine.buttons)
+            .wrap_err("no button presses configure the joltage counters")?;
+    }
 
-        let buttons: Vec<u16> = iter
-            .map(parse_button)
-            .map(|button| button.iter().fold(0, |button, &i| button | (1 << i)))
-            .collect();
+    Ok(sum)
+}
 
-        let mut cache = CacheP2::default();
+/// Solves the system `Σ presses[b] = joltage[i]` over all buttons `b` that
+/// increase counter `i`, and searches the free variables for the solution
+/// with the fewest presses.
+fn min_presses(joltage: &[u16], buttons: &[Box<[usize]>]) -> Option<usize> {
+    let mut system = LinearSystem::new(buttons.len());
 
-        sum += f(joltage, &buttons, &mut cache).unwrap();
+    for (i, &target) in joltage.iter().enumerate() {
+        let coeffs = buttons.iter().map(|button| u8::from(button.contains(&i)));
+        system.equation(coeffs, target);
     }
 
-    sum
+    let solution = system.solve();
+    let scaled = solution.scaled()?;
+
+    let to_i64 = |n: BigInt| i64::try_from(n).ok();
+    let denom = to_i64(scaled.denom)?;
+    let mut particular: Vec<_> = scaled
+        .particular
+        .into_iter()
+        .map(to_i64)
+        .collect::<Option<_>>()?;
+    let nullspace: Vec<Vec<_>> = scaled
+        .nullspace
+        .into_iter()
+        .map(|basis| basis.into_iter().map(to_i64).collect::<Option<_>>())
+        .collect::<Option<_>>()?;
+
+    // A button can't be pressed more often than its lowest counter's joltage
+    let bounds: Vec<i64> = solution
+        .free
+        .iter()
+        .map(|&b| {
+            buttons[b]
+                .iter()
+                .map(|&i| joltage[i] as i64)
+                .min()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let search = Search::new(denom, nullspace, bounds);
+    let mut best = None;
+    search.run(0, &mut particular, &mut best);
+
+    best
 }
 
-type CacheP2<'a> = FxHashMap<Box<[u16]>, Option<usize>>;
-
-// https://www.reddit.com/r/adventofcode/comments/1pk87hl/2025_day_10_part_2_bifurcate_your_way_to_victory/
-fn f(joltage: Box<[u16]>, buttons: &[u16], cache: &mut CacheP2) -> Option<usize> {
-    /// Passes each combination of buttons in `rest` to `f` together with the
-    /// current `best`.
-    fn recurse_button_combis<'a, F>(
-        rest: &[u16],
-        buttons: &mut Vec<u16>,
-        best: &mut Option<usize>,
-        cache: &mut CacheP2<'a>,
-        f: F,
-    ) where
-        F: Fn(&[u16], &mut Option<usize>, &mut CacheP2<'a>) + Copy,
-    {
-        let Some((button, rest)) = rest.split_first() else {
-            return f(buttons, best, cache);
-        };
+/// Depth-first search over the presses of free buttons
+struct Search {
+    denom: i64,
+    nullspace: Vec<Vec<i64>>,
+    bounds: Vec<i64>,
+    /// For each depth, how much each scaled press count can still increase
+    max_gain: Vec<Vec<i64>>,
+    /// For each depth, how much the scaled total can still decrease
+    max_saving: Vec<i64>,
+}
 
-        recurse_button_combis(rest, buttons, best, cache, f);
+impl Search {
+    fn new(denom: i64, nullspace: Vec<Vec<i64>>, bounds: Vec<i64>) -> Self {
+        let vars = nullspace.first().map_or(0, Vec::len);
+        let mut max_gain = vec![vec![0; vars]; nullspace.len() + 1];
+        let mut max_saving = vec![0; nullspace.len() + 1];
 
-        buttons.push(*button);
-        recurse_button_combis(rest, buttons, best, cache, f);
-        buttons.pop();
-    }
+        for depth in (0..nullspace.len()).rev() {
+            for v in 0..vars {
+                let gain = nullspace[depth][v].max(0) * bounds[depth];
+                max_gain[depth][v] = max_gain[depth + 1][v] + gain;
+            }
 
-    if joltage.iter().all(|&n| n == 0) {
-        return Some(0);
-    }
+            let saving = (-nullspace[depth].iter().sum::<i64>()).max(0) * bounds[depth];
+            max_saving[depth] = max_saving[depth + 1] + saving;
+        }
 
-    if let Some(cached) = cache.get(&joltage) {
-        return *cached;
+        Self {
+            denom,
+            nullspace,
+            bounds,
+            max_gain,
+            max_saving,
+        }
     }
 
-    // Converting joltage to binary representation, i.e. "lights" of part 1
-    let lights = joltage
-        .iter()
-        .copied()
-        .rev()
-        .fold(0, |lights, next| (lights << 1) | (next % 2));
-
-    let mut best = None;
-
-    recurse_button_combis(
-        &buttons,
-        &mut Vec::with_capacity(buttons.len()),
-        &mut best,
-        cache,
-        |pressed, best, cache| {
-            let xor = pressed.iter().copied().reduce(u16::bitxor).unwrap_or(0);
+    fn run(&self, depth: usize, scaled: &mut [i64], best: &mut Option<usize>) {
+        let unreachable = scaled
+            .iter()
+            .zip(&self.max_gain[depth])
+            .any(|(n, gain)| n + gain < 0);
 
-            if xor != lights {
-                return;
-            }
+        let total: i64 = scaled.iter().sum();
+        let worse =
+            best.is_some_and(|best| total - self.max_saving[depth] >= best as i64 * self.denom);
 
-            // Subtracting the `pressed` buttons from `joltage`
-            let mut next_joltage = joltage.clone();
+        if unreachable || worse {
+            return;
+        }
 
-            for button in pressed {
-                let mut button = *button;
-                let mut i = 0;
+        let Some(basis) = self.nullspace.get(depth) else {
+            let mut total = 0;
 
-                while button > 0 {
-                    if button & 1 == 1 {
-                        if next_joltage[i] == 0 {
-                            return;
-                        }
+            for &n in scaled.iter() {
+                if n % self.denom != 0 {
+                    return;
+                }
 
-                        next_joltage[i] -= 1;
-                    }
+                total += (n / self.denom) as usize;
+            }
 
-                    i += 1;
-                    button >>= 1;
-                }
+            if best.is_none_or(|best| total < best) {
+                *best = Some(total);
             }
 
-            // Halving the next joltage before we recurse
-            next_joltage.iter_mut().for_each(|j| *j /= 2);
+            return;
+        };
 
-            let Some(res) = f(next_joltage, buttons, cache) else {
-                return;
-            };
+        let bound = self.bounds[depth];
 
-            let curr = pressed.len() + 2 * res;
-            let best = best.get_or_insert(usize::MAX);
+        for presses in 0..=bound {
+            self.run(depth + 1, scaled, best);
 
-            if curr < *best {
-                *best = curr;
+            if presses < bound {
+                scaled.iter_mut().zip(basis).for_each(|(n, b)| *n += b);
             }
-        },
-    );
-
-    cache.insert(joltage, best);
+        }
 
-    best
+        scaled
+            .iter_mut()
+            .zip(basis)
+            .for_each(|(n, b)| *n -= b * bound);
+    }
 }
 
-fn parse_lights(s: &str) -> u16 {
-    s[1..s.len() - 1]
+/// Returns the lights as bitmask and their amount.
+fn parse_lights(input: &str, s: &str) -> Result<(u16, usize), ParseError> {
+    let inner = s
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .filter(|inner| (1..=16).contains(&inner.len()))
+        .context_at(input, s, "expected `[<1 to 16 lights>]`")?;
+
+    if let Some(i) = inner.bytes().position(|b| b != b'.' && b != b'#') {
+        return Err(ParseError::at(input, &inner[i..], "expected `.` or `#`"));
+    }
+
+    let lights = inner
         .bytes()
         .rev()
         .map(|b| b == b'#')
-        .fold(0, |lights, bit| (lights << 1) | bit as u16)
+        .fold(0, |lights, bit| (lights << 1) | bit as u16);
+
+    Ok((lights, inner.len()))
 }
 
-fn parse_button(s: &str) -> Box<[usize]> {
-    s[1..s.len() - 1]
+fn parse_button(input: &str, s: &str, lights: usize) -> Result<Box<[usize]>, ParseError> {
+    let inner = s
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .context_at(input, s, "expected `(<lights>)`")?;
+
+    inner
         .split(',')
-        .map(str::parse)
-        .flat_map(Result::ok)
+        .map(|n| match parse_at(input, n)? {
+            i if i < lights => Ok(i),
+            _ => Err(ParseError::at(
+                input,
+                n,
+                format!("expected light below {lights}"),
+            )),
+        })
         .collect()
 }
 
-fn parse_joltage(s: &str) -> Box<[u16]> {
-    s[1..s.len() - 1]
-        .split(',')
-        .map(str::parse)
-        .flat_map(Result::ok)
-        .collect()
+fn parse_joltage(input: &str, s: &str) -> Result<Box<[u16]>, ParseError> {
+    let inner = s
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .context_at(input, s, "expected `{<joltage>}`")?;
+
+    inner.split(',').map(|n| parse_at(input, n)).collect()
 }
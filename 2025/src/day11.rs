@@ -1,57 +1,78 @@
-use aoc_rust::Solution;
+use aoc_rust::{
+    Solution,
+    timing::{self, Phase},
+    util::parse::{ParseContext, ParseError},
+};
 use eyre::Result;
 use fxhash::FxHashMap;
 
+aoc_rust::register_day! {
+    day: 11,
+    title: "Reactor",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    let devices = timing::phase(Phase::Parse, || parse_devices(input))?;
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(&devices));
+    let p2 = timing::phase(Phase::Part2, || part2(&devices))?;
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> usize {
-    let devices = parse_devices(input);
+fn part1(devices: &Devices<'_>) -> usize {
     let mut cache = Cache::default();
 
-    bfs("you", "out", &devices, &mut cache)
+    bfs("you", "out", devices, &mut cache)
 }
 
-fn part2(input: &str) -> usize {
-    let devices = parse_devices(input);
+fn part2(devices: &Devices<'_>) -> Result<usize> {
     let mut cache = Cache::default();
 
-    let fft_to_dac = bfs("fft", "dac", &devices, &mut cache);
-    let dac_to_fft = bfs("dac", "fft", &devices, &mut cache);
+    let fft_to_dac = bfs("fft", "dac", devices, &mut cache);
+    let dac_to_fft = bfs("dac", "fft", devices, &mut cache);
 
     if dac_to_fft > 0 {
-        let svr_to_dac = bfs("svr", "dac", &devices, &mut cache);
-        let fft_to_out = bfs("fft", "out", &devices, &mut cache);
+        let svr_to_dac = bfs("svr", "dac", devices, &mut cache);
+        let fft_to_out = bfs("fft", "out", devices, &mut cache);
 
-        svr_to_dac * dac_to_fft * fft_to_out
+        Ok(svr_to_dac * dac_to_fft * fft_to_out)
     } else if fft_to_dac > 0 {
-        let svr_to_fft = bfs("svr", "fft", &devices, &mut cache);
-        let dac_to_out = bfs("dac", "out", &devices, &mut cache);
+        let svr_to_fft = bfs("svr", "fft", devices, &mut cache);
+        let dac_to_out = bfs("dac", "out", devices, &mut cache);
 
-        svr_to_fft * fft_to_dac * dac_to_out
+        Ok(svr_to_fft * fft_to_dac * dac_to_out)
     } else {
-        unreachable!()
+        eyre::bail!("no path connects `dac` and `fft`")
     }
 }
 
 type Devices<'a> = FxHashMap<&'a str, Vec<&'a str>>;
 
-fn parse_devices(input: &str) -> Devices<'_> {
+fn parse_devices(input: &str) -> Result<Devices<'_>, ParseError> {
     let mut devices = Devices::default();
 
-    for device in input.lines() {
-        let (name, outputs) = device.split_once(':').unwrap();
+    let lines = input
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty());
+
+    for device in lines {
+        let (name, outputs) =
+            device
+                .split_once(':')
+                .context_at(input, device, "expected `<device>: <outputs>`")?;
+
         let outputs: Vec<_> = outputs.split_ascii_whitespace().collect();
-        devices.insert(name, outputs);
+
+        if devices.insert(name, outputs).is_some() {
+            let msg = format!("device `{name}` is declared twice");
+
+            return Err(ParseError::at(input, name, msg));
+        }
     }
 
-    devices
+    Ok(devices)
 }
 
 type Cache<'a> = FxHashMap<(&'a str, &'a str), usize>;
@@ -1,63 +1,91 @@
-use aoc_rust::Solution;
+use aoc_rust::{
+    Solution,
+    timing::{self, Phase},
+    util::parse::{ParseContext, ParseError, parse_at},
+};
 use eyre::Result;
 
+aoc_rust::register_day! {
+    day: 12,
+    title: "Christmas Tree Farm",
+}
+
 pub fn run(input: &str) -> Result<Solution> {
-    let input = input.trim();
+    let (presents_size, regions) = timing::phase(Phase::Parse, || parse_input(input))?;
 
-    let p1 = part1(input);
-    let p2 = part2(input);
+    let p1 = timing::phase(Phase::Part1, || part1(&presents_size, &regions));
+    let p2 = timing::phase(Phase::Part2, part2);
 
     Ok(Solution::new().part1(p1).part2(p2))
 }
 
-fn part1(input: &str) -> usize {
-    struct Region {
-        area: usize,
-        quantities: Vec<usize>,
-    }
+struct Region {
+    area: usize,
+    quantities: Vec<usize>,
+}
 
-    let mut lines = input.lines();
+/// Returns the amount of tiles of each present and the regions below the trees.
+fn parse_input(input: &str) -> Result<(Vec<usize>, Vec<Region>), ParseError> {
+    let mut presents_size = Vec::new();
+    let mut regions = Vec::new();
+
+    let lines = input
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty());
 
-    let regions: Vec<_> = lines
-        .by_ref()
-        .rev()
-        .take_while(|line| !line.is_empty())
-        .map(|line| {
-            let (dim, quantities) = line.split_once(": ").unwrap();
-            let (w, l) = dim.split_once('x').unwrap();
+    for line in lines {
+        if let Some((dim, quantities)) = line.split_once(": ") {
+            let (w, l) =
+                dim.split_once('x')
+                    .context_at(input, dim, "expected `<width>x<length>`")?;
 
-            let w: usize = w.parse().unwrap();
-            let l: usize = l.parse().unwrap();
+            let w: u32 = parse_at(input, w)?;
+            let l: u32 = parse_at(input, l)?;
 
             let quantities = quantities
                 .split(' ')
-                .map(str::parse)
-                .map(Result::unwrap)
-                .collect();
+                .map(|n| parse_at(input, n))
+                .collect::<Result<Vec<_>, _>>()?;
 
-            Region {
-                area: w * l,
+            if quantities.len() > presents_size.len() {
+                let msg = format!("expected at most {} quantities", presents_size.len());
+
+                return Err(ParseError::at(input, line, msg));
+            }
+
+            regions.push(Region {
+                area: w as usize * l as usize,
                 quantities,
+            });
+        } else if let Some(idx) = line.strip_suffix(':') {
+            if !regions.is_empty() {
+                let msg = "presents must be listed before the regions";
+
+                return Err(ParseError::at(input, line, msg));
             }
-        })
-        .collect();
 
-    let mut presents_size = Vec::new();
+            parse_at::<usize>(input, idx)?;
+            presents_size.push(0);
+        } else {
+            let Some(size) = presents_size.last_mut().filter(|_| regions.is_empty()) else {
+                let msg = "expected `<index>:` or `<width>x<length>: <quantities>`";
 
-    while lines.next().is_some() {
-        let mut size = 0;
+                return Err(ParseError::at(input, line, msg));
+            };
 
-        while let Some(line) = lines.next() {
-            if line.is_empty() {
-                break;
+            if let Some(i) = line.bytes().position(|b| b != b'#' && b != b'.') {
+                return Err(ParseError::at(input, &line[i..], "expected `#` or `.`"));
             }
 
-            size += line.bytes().filter(|b| *b == b'#').count();
+            *size += line.bytes().filter(|b| *b == b'#').count();
         }
-
-        presents_size.push(size);
     }
 
+    Ok((presents_size, regions))
+}
+
+fn part1(presents_size: &[usize], regions: &[Region]) -> usize {
     regions
         .iter()
         .filter(|region| {
@@ -73,6 +101,6 @@ fn part1(input: &str) -> usize {
         .count()
 }
 
-fn part2(_input: &str) -> String {
+fn part2() -> String {
     "x".to_owned()
 }
@@ -10,3 +10,8 @@ pub mod day09;
 pub mod day10;
 pub mod day11;
 pub mod day12;
+
+aoc_rust::register_year! {
+    2025:
+        day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12,
+}
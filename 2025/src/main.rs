@@ -1,5 +1,6 @@
-use std::{fs, time::Instant};
+use std::fs;
 
+use aoc_rust::timing;
 use eyre::Result;
 
 fn main() -> Result<()> {
@@ -15,12 +16,10 @@ fn main() -> Result<()> {
     let (path, run) = load!(day10);
     let input = fs::read_to_string(path)?;
 
-    let start = Instant::now();
-    let solution = run(&input)?;
-    let elapsed = start.elapsed();
+    let (solution, timings) = timing::time(|| run(&input));
 
-    println!("{solution}");
-    println!("Elapsed: {elapsed:?}");
+    println!("{}", solution?);
+    println!("Elapsed: {timings}");
 
     Ok(())
 }
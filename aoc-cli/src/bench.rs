@@ -0,0 +1,319 @@
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    fs,
+    hint::black_box,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use aoc_registry::{Day, RunFn};
+use clap::Args;
+use eyre::{Result, WrapErr};
+use serde::{Deserialize, Serialize};
+
+use crate::input;
+
+#[derive(Args)]
+pub struct BenchArgs {
+    /// Only bench this year, defaults to all years
+    #[arg(long)]
+    year: Option<u16>,
+    /// Only bench this day
+    #[arg(long, requires = "year")]
+    day: Option<u8>,
+    /// Amount of samples per implementation
+    #[arg(long, default_value_t = 10)]
+    samples: usize,
+    /// Minimum duration of a sample in milliseconds, fast days are repeated
+    /// within a sample until it's reached
+    #[arg(long, default_value_t = 10)]
+    sample_ms: u64,
+    /// Write the results as JSON to this path
+    #[arg(long)]
+    json: Option<PathBuf>,
+    /// Write the results as CSV to this path
+    #[arg(long)]
+    csv: Option<PathBuf>,
+    /// JSON results of a previous run to compare with
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+    /// Percentage by which the median may exceed the baseline's median
+    /// before it's flagged as regression
+    #[arg(long, default_value_t = 10.0, requires = "baseline")]
+    threshold: f64,
+}
+
+/// Bench every implementation of the selected days on their local input.
+pub fn bench(args: BenchArgs) -> Result<()> {
+    let baseline = args.baseline.as_deref().map(load).transpose()?;
+
+    let years = match args.year {
+        Some(year) if aoc_registry::years().contains(&year) => vec![year],
+        Some(year) => eyre::bail!("no solutions for year {year}"),
+        None => aoc_registry::years().to_vec(),
+    };
+
+    let days = years
+        .into_iter()
+        .flat_map(aoc_registry::days)
+        .filter(|day| args.day.is_none_or(|n| day.day == n));
+
+    let sample_time = Duration::from_millis(args.sample_ms);
+    let mut records = Vec::new();
+    let mut failed = 0;
+
+    for day in days {
+        let Ok(input) = input::read_for(day, None) else {
+            eprintln!("skipping {} day {:02}: no input", day.year, day.day);
+
+            continue;
+        };
+
+        for (variant, run) in day.implementations() {
+            let res = Record::measure(day, variant, run, &input, args.samples, sample_time);
+
+            let record = match res {
+                Ok(record) => record,
+                Err(err) => {
+                    eprintln!("{err:?}");
+                    failed += 1;
+
+                    continue;
+                }
+            };
+
+            let comparison = baseline.as_ref().map(|baseline| baseline.compare(&record));
+            println!("{}", record.describe(comparison, args.threshold));
+            records.push(record);
+        }
+    }
+
+    if let Some(path) = args.json {
+        let json = serde_json::to_string_pretty(&records)?;
+        write(&path, &json)?;
+    }
+
+    if let Some(path) = args.csv {
+        write(&path, &to_csv(&records))?;
+    }
+
+    if failed > 0 {
+        eyre::bail!("{failed} implementation(s) failed");
+    }
+
+    let Some(baseline) = baseline else {
+        return Ok(());
+    };
+
+    let regressions = records
+        .iter()
+        .filter(|record| {
+            baseline
+                .compare(record)
+                .is_some_and(|change| change > args.threshold)
+        })
+        .count();
+
+    if regressions > 0 {
+        eyre::bail!("{regressions} regression(s) above {}%", args.threshold);
+    }
+
+    Ok(())
+}
+
+/// Measurement of one implementation of a day.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Record {
+    year: u16,
+    day: u8,
+    variant: String,
+    /// Runs per sample
+    iterations: u32,
+    samples: usize,
+    median_ns: u64,
+    mean_ns: u64,
+    min_ns: u64,
+}
+
+impl Record {
+    fn measure(
+        day: &Day,
+        variant: Option<&str>,
+        run: RunFn,
+        input: &str,
+        samples: usize,
+        sample_time: Duration,
+    ) -> Result<Self> {
+        let variant = variant.unwrap_or("default").to_owned();
+
+        // the first run warms up and checks that the day succeeds at all
+        let start = Instant::now();
+        run(input).wrap_err_with(|| {
+            format!("failed to run {} day {:02} ({variant})", day.year, day.day)
+        })?;
+        let once = start.elapsed();
+
+        let iterations = (sample_time.as_nanos() / once.as_nanos().max(1)).clamp(1, 1_000_000);
+        let iterations = iterations as u32;
+
+        let mut times: Vec<_> = (0..samples.max(1))
+            .map(|_| {
+                let start = Instant::now();
+
+                for _ in 0..iterations {
+                    let _ = black_box(run(black_box(input)));
+                }
+
+                start.elapsed() / iterations
+            })
+            .collect();
+
+        times.sort_unstable();
+        let mean = times.iter().sum::<Duration>() / times.len() as u32;
+
+        Ok(Self {
+            year: day.year,
+            day: day.day,
+            variant,
+            iterations,
+            samples: times.len(),
+            median_ns: times[times.len() / 2].as_nanos() as u64,
+            mean_ns: mean.as_nanos() as u64,
+            min_ns: times[0].as_nanos() as u64,
+        })
+    }
+
+    /// Line to print, including the change compared to the baseline.
+    fn describe(&self, comparison: Option<Option<f64>>, threshold: f64) -> String {
+        let mut line = format!(
+            "{} day {:02} {:<20} median: {:>12?} mean: {:>12?} min: {:>12?}",
+            self.year,
+            self.day,
+            self.variant,
+            Duration::from_nanos(self.median_ns),
+            Duration::from_nanos(self.mean_ns),
+            Duration::from_nanos(self.min_ns),
+        );
+
+        match comparison {
+            Some(Some(change)) if change > threshold => {
+                let _ = write!(line, " {change:+.1}% REGRESSION");
+            }
+            Some(Some(change)) => {
+                let _ = write!(line, " {change:+.1}%");
+            }
+            Some(None) => line.push_str(" (new)"),
+            None => {}
+        }
+
+        line
+    }
+}
+
+/// Results of a previous run, keyed by year, day, and variant.
+struct Baseline {
+    medians: HashMap<(u16, u8, String), u64>,
+}
+
+impl Baseline {
+    fn new(records: Vec<Record>) -> Self {
+        let medians = records
+            .into_iter()
+            .map(|record| ((record.year, record.day, record.variant), record.median_ns))
+            .collect();
+
+        Self { medians }
+    }
+
+    /// Change of the median in percent or `None` if the baseline doesn't
+    /// contain the implementation.
+    fn compare(&self, record: &Record) -> Option<f64> {
+        let key = (record.year, record.day, record.variant.clone());
+        let baseline = *self.medians.get(&key)?;
+
+        Some((record.median_ns as f64 / baseline.max(1) as f64 - 1.0) * 100.0)
+    }
+}
+
+fn load(path: &Path) -> Result<Baseline> {
+    let json = fs::read_to_string(path)
+        .wrap_err_with(|| format!("failed to read baseline at `{}`", path.display()))?;
+
+    let records = serde_json::from_str(&json)
+        .wrap_err_with(|| format!("invalid baseline at `{}`", path.display()))?;
+
+    Ok(Baseline::new(records))
+}
+
+fn write(path: &Path, content: &str) -> Result<()> {
+    fs::write(path, content)
+        .wrap_err_with(|| format!("failed to write results to `{}`", path.display()))
+}
+
+fn to_csv(records: &[Record]) -> String {
+    let mut csv = "year,day,variant,iterations,samples,median_ns,mean_ns,min_ns\n".to_owned();
+
+    for record in records {
+        let Record {
+            year,
+            day,
+            variant,
+            iterations,
+            samples,
+            median_ns,
+            mean_ns,
+            min_ns,
+        } = record;
+
+        let _ = writeln!(
+            csv,
+            "{year},{day},{variant},{iterations},{samples},{median_ns},{mean_ns},{min_ns}"
+        );
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(variant: &str, median_ns: u64) -> Record {
+        Record {
+            year: 2024,
+            day: 23,
+            variant: variant.to_owned(),
+            iterations: 1,
+            samples: 1,
+            median_ns,
+            mean_ns: median_ns,
+            min_ns: median_ns,
+        }
+    }
+
+    #[test]
+    fn compare_with_baseline() {
+        let baseline = Baseline::new(vec![record("default", 100), record("bk", 200)]);
+
+        assert_eq!(baseline.compare(&record("default", 150)), Some(50.0));
+        assert_eq!(baseline.compare(&record("bk", 100)), Some(-50.0));
+        assert_eq!(baseline.compare(&record("naive", 100)), None);
+
+        let line = record("default", 150).describe(Some(Some(50.0)), 10.0);
+        assert!(line.ends_with("+50.0% REGRESSION"));
+    }
+
+    #[test]
+    fn json_round_trip_and_csv() {
+        let records = vec![record("default", 100), record("bk", 200)];
+        let json = serde_json::to_string(&records).unwrap();
+        let parsed: Vec<Record> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, records);
+
+        let csv = to_csv(&records);
+        let mut lines = csv.lines().skip(1);
+        assert_eq!(lines.next(), Some("2024,23,default,1,1,100,100,100"));
+        assert_eq!(lines.next(), Some("2024,23,bk,1,1,200,200,200"));
+    }
+}
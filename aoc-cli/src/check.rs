@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+use aoc_rust::timing;
+use clap::Args;
+use eyre::{Result, WrapErr};
+
+use crate::input;
+
+#[derive(Args)]
+pub struct CheckArgs {
+    #[arg(long)]
+    year: u16,
+    #[arg(long)]
+    day: u8,
+    /// Path to the puzzle input or `-` to read from stdin.
+    /// Defaults to `{year}/inputs/day{day:02}.txt`.
+    #[arg(long)]
+    input: Option<PathBuf>,
+}
+
+/// Run all implementations of the day and make sure they agree.
+pub fn check(args: CheckArgs) -> Result<()> {
+    let CheckArgs { year, day, input } = args;
+
+    let Some(day) = aoc_registry::day(year, day) else {
+        eyre::bail!("day {day} of {year} is not solved");
+    };
+
+    if day.variants.is_empty() {
+        eyre::bail!("day {} of {year} has no variants", day.day);
+    }
+
+    let input = input::read_for(day, input)?;
+
+    let solution = day.cross_check_with(|variant, run| {
+        let (res, timings) = timing::time(|| run(&input));
+        let name = variant.unwrap_or("default");
+        println!("{name}: {timings}");
+
+        res.wrap_err_with(|| format!("failed to run `{name}`"))
+    })?;
+
+    println!();
+    print!("{solution}");
+    println!("All {} implementations agree", day.variants.len() + 1);
+
+    Ok(())
+}
@@ -0,0 +1,285 @@
+use std::{
+    env, fs,
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+
+use clap::Args;
+use eyre::{Result, WrapErr};
+use serde::Deserialize;
+
+use crate::input::{self, PROJECT_DIR};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Minimum time between two requests to be polite towards the server
+const MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+const USER_AGENT: &str = concat!("aoc-cli/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Args)]
+pub struct FetchArgs {
+    #[arg(long)]
+    year: u16,
+    /// Defaults to all solved days of the year that need an input
+    #[arg(long)]
+    day: Option<u8>,
+    /// Server to download from, overrides `AOC_BASE_URL` and the config.
+    /// Defaults to `https://adventofcode.com`.
+    #[arg(long)]
+    base_url: Option<String>,
+}
+
+/// Download the input of the day or of all solved days into
+/// `{year}/inputs/day{day:02}.txt`.
+///
+/// Inputs that are already cached are never downloaded again.
+pub fn fetch(args: FetchArgs) -> Result<()> {
+    let FetchArgs {
+        year,
+        day,
+        base_url,
+    } = args;
+
+    let days: Vec<_> = match day {
+        Some(day) => vec![day],
+        None => aoc_registry::days(year)
+            .iter()
+            .filter(|day| day.reads_input)
+            .map(|day| day.day)
+            .collect(),
+    };
+
+    if days.is_empty() {
+        eyre::bail!("no days to fetch for {year}");
+    }
+
+    let config = Config::load()?;
+    let mut fetcher = Fetcher::new(config.base_url(base_url), config.session()?);
+
+    for day in days {
+        let path = input::default_path(year, day);
+
+        if fetcher.fetch(year, day, &path)? {
+            println!("Fetched {year} day {day:02} into `{}`", path.display());
+        } else {
+            println!(
+                "{year} day {day:02} is already cached at `{}`",
+                path.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Content of the optional `aoc.toml` in the project's root directory.
+#[derive(Default, Deserialize)]
+struct Config {
+    /// Value of the `session` cookie
+    session: Option<String>,
+    base_url: Option<String>,
+}
+
+impl Config {
+    fn load() -> Result<Self> {
+        let path = Path::new(PROJECT_DIR).join("aoc.toml");
+
+        let Ok(content) = fs::read_to_string(&path) else {
+            return Ok(Self::default());
+        };
+
+        toml::from_str(&content).wrap_err_with(|| format!("invalid config at `{}`", path.display()))
+    }
+
+    /// The session token from `AOC_SESSION` or the config.
+    fn session(&self) -> Result<String> {
+        env::var("AOC_SESSION")
+            .ok()
+            .or_else(|| self.session.clone())
+            .map(|session| session.trim().to_owned())
+            .filter(|session| !session.is_empty())
+            .ok_or_else(|| {
+                eyre::eyre!("missing session token, set `AOC_SESSION` or `session` in `aoc.toml`")
+            })
+    }
+
+    /// The base URL from the argument, `AOC_BASE_URL`, the config, or the
+    /// default in that order.
+    fn base_url(&self, arg: Option<String>) -> String {
+        arg.or_else(|| env::var("AOC_BASE_URL").ok())
+            .or_else(|| self.base_url.clone())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_owned())
+    }
+}
+
+/// Downloads inputs while keeping a minimum interval between requests.
+struct Fetcher {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    min_interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Fetcher {
+    fn new(base_url: String, session: String) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session,
+            min_interval: MIN_INTERVAL,
+            last_request: None,
+        }
+    }
+
+    /// Download the input into `path` unless it already exists.
+    ///
+    /// Returns whether the input was downloaded.
+    fn fetch(&mut self, year: u16, day: u8, path: &Path) -> Result<bool> {
+        if path.exists() {
+            return Ok(false);
+        }
+
+        let input = self.download(year, day)?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("failed to create `{}`", dir.display()))?;
+        }
+
+        fs::write(path, input)
+            .wrap_err_with(|| format!("failed to write input to `{}`", path.display()))?;
+
+        Ok(true)
+    }
+
+    fn download(&mut self, year: u16, day: u8) -> Result<String> {
+        if let Some(last) = self.last_request {
+            thread::sleep(self.min_interval.saturating_sub(last.elapsed()));
+        }
+
+        self.last_request = Some(Instant::now());
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        let input = match response {
+            Ok(response) => response
+                .into_string()
+                .wrap_err_with(|| format!("failed to read response of `{url}`"))?,
+            Err(ureq::Error::Status(404, _)) => {
+                eyre::bail!("no input at `{url}`, is the day unlocked yet?")
+            }
+            Err(ureq::Error::Status(code @ (400 | 401 | 403 | 500), _)) => {
+                eyre::bail!("status {code} for `{url}`, is the session token valid?")
+            }
+            Err(err) => return Err(err).wrap_err_with(|| format!("failed to request `{url}`")),
+        };
+
+        if input.is_empty() {
+            eyre::bail!("received empty input from `{url}`");
+        }
+
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    /// Serve a single request and return the request's lines.
+    fn stand_in(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let request: Vec<_> = BufReader::new(&stream)
+                .lines()
+                .map(Result::unwrap)
+                .take_while(|line| !line.is_empty())
+                .collect();
+
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+
+            stream.write_all(response.as_bytes()).unwrap();
+
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        env::temp_dir()
+            .join(format!("aoc-fetch-{}-{name}", std::process::id()))
+            .join("day01.txt")
+    }
+
+    #[test]
+    fn downloads_once() {
+        let (base_url, server) = stand_in("200 OK", "1 2 3\n");
+        let path = temp_path("once");
+        let mut fetcher = Fetcher::new(base_url, "secret".to_owned());
+
+        assert!(fetcher.fetch(2015, 1, &path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2 3\n");
+
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "GET /2015/day/1/input HTTP/1.1");
+        assert!(request.contains(&"Cookie: session=secret".to_owned()));
+
+        // the server is gone so a second request would fail
+        assert!(!fetcher.fetch(2015, 1, &path).unwrap());
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn locked_day() {
+        let (base_url, server) = stand_in("404 Not Found", "");
+        let path = temp_path("locked");
+        let mut fetcher = Fetcher::new(base_url, "secret".to_owned());
+
+        let err = fetcher.fetch(2015, 1, &path).unwrap_err();
+        assert!(err.to_string().contains("unlocked"));
+        assert!(!path.exists());
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn rate_limited() {
+        let mut fetcher = Fetcher::new("http://127.0.0.1:9".to_owned(), "secret".to_owned());
+        fetcher.min_interval = Duration::from_millis(200);
+
+        let start = Instant::now();
+        let _ = fetcher.download(2015, 1);
+        let _ = fetcher.download(2015, 2);
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,52 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use aoc_registry::Day;
+use eyre::{Result, WrapErr};
+
+/// Root directory of the project i.e. the directory containing all years.
+pub const PROJECT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+/// Default location of a day's input: `{year}/inputs/day{day:02}.txt`
+pub fn default_path(year: u16, day: u8) -> PathBuf {
+    Path::new(PROJECT_DIR)
+        .join(year.to_string())
+        .join("inputs")
+        .join(format!("day{day:02}.txt"))
+}
+
+/// Read the input either from the given path or from stdin if the path is `-`.
+pub fn read(path: &Path) -> Result<String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+
+        io::stdin()
+            .read_to_string(&mut input)
+            .wrap_err("failed to read input from stdin")?;
+
+        return Ok(input);
+    }
+
+    fs::read_to_string(path)
+        .wrap_err_with(|| format!("failed to read input at `{}`", path.display()))
+}
+
+/// Read the input at the given path or at the day's default path unless the
+/// day does not need any input.
+pub fn read_for(day: &Day, path: Option<PathBuf>) -> Result<String> {
+    if !day.reads_input && path.is_none() {
+        return Ok(String::new());
+    }
+
+    let Some(path) = path else {
+        let (year, day) = (day.year, day.day);
+
+        return read(&default_path(year, day))
+            .wrap_err_with(|| format!("fetch it through `aoc fetch --year {year} --day {day}`"));
+    };
+
+    read(&path)
+}
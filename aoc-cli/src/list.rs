@@ -0,0 +1,52 @@
+use aoc_registry::Day;
+use clap::Args;
+use eyre::Result;
+
+#[derive(Args)]
+pub struct ListArgs {
+    /// Only list the days of this year
+    #[arg(long)]
+    year: Option<u16>,
+}
+
+pub fn list(args: ListArgs) -> Result<()> {
+    let years = match args.year {
+        Some(year) if aoc_registry::years().contains(&year) => vec![year],
+        Some(year) => eyre::bail!("no solutions for year {year}"),
+        None => aoc_registry::years().to_vec(),
+    };
+
+    for year in years {
+        println!("{year}:");
+
+        for day in aoc_registry::days(year) {
+            println!("  {}", describe(day));
+        }
+    }
+
+    Ok(())
+}
+
+fn describe(day: &Day) -> String {
+    let mut line = format!("Day {:02}: {}", day.day, day.title);
+
+    if !day.reads_input {
+        line.push_str(" (no input)");
+    }
+
+    if !day.variants.is_empty() {
+        let names: Vec<_> = day.variants.iter().map(|variant| variant.name).collect();
+        line.push_str(&format!(" [variants: {}]", names.join(", ")));
+    }
+
+    if day.params.is_some() {
+        let params: Vec<_> = day
+            .default_params()
+            .into_iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect();
+        line.push_str(&format!(" [params: {}]", params.join(", ")));
+    }
+
+    line
+}
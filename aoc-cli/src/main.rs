@@ -0,0 +1,41 @@
+use clap::{Parser, Subcommand};
+use eyre::Result;
+
+mod bench;
+mod check;
+mod fetch;
+mod input;
+mod list;
+mod run;
+
+/// Advent of Code runner for all years
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a single day or all days of a year
+    Run(run::RunArgs),
+    /// List all solved days with their titles and variants
+    List(list::ListArgs),
+    /// Run all variants of a day and make sure they agree on the solution
+    Check(check::CheckArgs),
+    /// Bench all implementations of the selected days on their local input
+    Bench(bench::BenchArgs),
+    /// Download and cache puzzle inputs
+    Fetch(fetch::FetchArgs),
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Run(args) => run::run(args),
+        Command::List(args) => list::list(args),
+        Command::Check(args) => check::check(args),
+        Command::Bench(args) => bench::bench(args),
+        Command::Fetch(args) => fetch::fetch(args),
+    }
+}
@@ -0,0 +1,123 @@
+use std::{path::PathBuf, time::Duration};
+
+use aoc_registry::{Day, Overrides};
+use aoc_rust::{timing, Solution};
+use clap::Args;
+use eyre::{Result, WrapErr};
+
+use crate::input;
+
+#[derive(Args)]
+pub struct RunArgs {
+    #[arg(long)]
+    year: u16,
+    #[arg(long, required_unless_present = "all")]
+    day: Option<u8>,
+    /// Run all solved days of the year
+    #[arg(long, conflicts_with_all = ["day", "input", "variant", "params"])]
+    all: bool,
+    /// Path to the puzzle input or `-` to read from stdin.
+    /// Defaults to `{year}/inputs/day{day:02}.txt`.
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Run an alternative implementation of the day, see `aoc list`
+    #[arg(long, conflicts_with = "params")]
+    variant: Option<String>,
+    /// Override a puzzle parameter e.g. to solve the example, see `aoc list`
+    #[arg(long = "param", value_name = "NAME=VALUE")]
+    params: Vec<String>,
+}
+
+pub fn run(args: RunArgs) -> Result<()> {
+    let RunArgs {
+        year,
+        day,
+        all,
+        input,
+        variant,
+        params,
+    } = args;
+
+    if !aoc_registry::years().contains(&year) {
+        eyre::bail!("no solutions for year {year}");
+    }
+
+    if all {
+        return run_all(year);
+    }
+
+    let Some(day) = day else {
+        eyre::bail!("either `--day` or `--all` must be specified");
+    };
+
+    let Some(day) = aoc_registry::day(year, day) else {
+        eyre::bail!("day {day} of {year} is not solved");
+    };
+
+    let input = input::read_for(day, input)?;
+
+    if params.is_empty() {
+        return solve(day, variant.as_deref(), &input).map(|_| ());
+    }
+
+    let mut overrides = Overrides::default();
+
+    for param in params {
+        overrides.set_all(&param)?;
+    }
+
+    // parameters only apply to the default implementation
+    report(day, || day.run_with(&input, &overrides)).map(|_| ())
+}
+
+fn run_all(year: u16) -> Result<()> {
+    let mut total = Duration::ZERO;
+    let mut failed = 0;
+
+    for day in aoc_registry::days(year) {
+        println!("Day {:02}: {}", day.day, day.title);
+
+        let res = input::read_for(day, None).and_then(|input| solve(day, None, &input));
+
+        match res {
+            Ok(elapsed) => total += elapsed,
+            Err(err) => {
+                eprintln!("{err:?}");
+                failed += 1;
+            }
+        }
+
+        println!();
+    }
+
+    println!("Total elapsed: {total:?}");
+
+    if failed > 0 {
+        eyre::bail!("{failed} day(s) of {year} failed");
+    }
+
+    Ok(())
+}
+
+/// Solve the day through the given implementation, see [`report`].
+fn solve(day: &Day, variant: Option<&str>, input: &str) -> Result<Duration> {
+    let Some(run) = day.run_fn(variant) else {
+        let variant = variant.unwrap_or_default();
+        eyre::bail!("day {} of {} has no variant `{variant}`", day.day, day.year);
+    };
+
+    report(day, || run(input))
+}
+
+/// Solve the day, print the solution, and return the elapsed time.
+fn report(day: &Day, run: impl FnOnce() -> Result<Solution>) -> Result<Duration> {
+    let Day { year, day, .. } = *day;
+
+    let (res, timings) = timing::time(run);
+    let solution = res.wrap_err_with(|| format!("failed to run day {day:02} of {year}"))?;
+
+    print!("{solution}");
+    println!("Elapsed: {timings}");
+
+    Ok(timings.total)
+}
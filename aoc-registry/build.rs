@@ -0,0 +1,46 @@
+//! Generate the `years!` invocation from the year crates among the
+//! dependencies so that `Cargo.toml` is the only place listing them.
+//!
+//! A year crate is a dependency named `aoc{yy}` whose path is the year's
+//! directory next to `aoc-registry`, e.g. `aoc15 = { path = "../2015" }`.
+
+use std::{env, fmt::Write as _, fs, path::Path};
+
+fn main() {
+    let manifest = fs::read_to_string("Cargo.toml").expect("failed to read Cargo.toml");
+
+    let mut years = String::from("years! {\n");
+
+    for line in manifest.lines() {
+        let Some((name, dependency)) = line.split_once('=') else {
+            continue;
+        };
+
+        let name = name.trim();
+
+        let is_year = name
+            .strip_prefix("aoc")
+            .is_some_and(|yy| yy.len() == 2 && yy.bytes().all(|byte| byte.is_ascii_digit()));
+
+        if !is_year {
+            continue;
+        }
+
+        let Some(dir) = dependency
+            .split_once("path = \"../")
+            .and_then(|(_, path)| path.split_once('"'))
+            .map(|(dir, _)| dir)
+        else {
+            panic!("expected `{name}` to be a path dependency next to aoc-registry");
+        };
+
+        let _ = writeln!(years, "    {name} => {dir:?},");
+    }
+
+    years.push_str("}\n");
+
+    let out_dir = env::var_os("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("years.rs"), years).expect("failed to write years.rs");
+
+    println!("cargo:rerun-if-changed=Cargo.toml");
+}
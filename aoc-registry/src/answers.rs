@@ -0,0 +1,99 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use serde::Deserialize;
+
+use crate::Day;
+
+/// Root directory of the project i.e. the directory containing all years.
+const PROJECT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+/// Content of a `{year}/answers.toml` file, keyed by `day{:02}`.
+type YearAnswers = HashMap<String, DayAnswers>;
+
+#[derive(Deserialize)]
+struct DayAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+/// Check all given days, see [`check`], and panic with every failure.
+pub fn check_all(days: &[&Day]) {
+    let failures: Vec<_> = days.iter().filter_map(|day| check(day).err()).collect();
+
+    if !failures.is_empty() {
+        panic!("{} day(s) failed:\n{}", failures.len(), failures.join("\n"));
+    }
+}
+
+/// Run the day and all of its variants on the local input and compare their
+/// solutions with the stored answers.
+///
+/// Skips the check if either the input or the answers are not available.
+pub fn check(day: &Day) -> Result<(), String> {
+    let implementations = day.implementations();
+
+    let Day {
+        year,
+        day,
+        reads_input,
+        ..
+    } = *day;
+
+    let year_dir = Path::new(PROJECT_DIR).join(year.to_string());
+    let input_path = year_dir.join(format!("inputs/day{day:02}.txt"));
+
+    let input = if !reads_input {
+        String::new()
+    } else if let Ok(input) = fs::read_to_string(&input_path) {
+        input
+    } else {
+        eprintln!(
+            "skipping {year} day {day:02}: no input at `{}`",
+            input_path.display()
+        );
+
+        return Ok(());
+    };
+
+    let Some(answers) = load_answers(&year_dir, day) else {
+        eprintln!("skipping {year} day {day:02}: no answers");
+
+        return Ok(());
+    };
+
+    for (variant, run) in implementations {
+        let name = match variant {
+            Some(variant) => format!("{year} day {day:02} ({variant})"),
+            None => format!("{year} day {day:02}"),
+        };
+
+        let solution = match run(&input) {
+            Ok(solution) => solution,
+            Err(err) => return Err(format!("failed to run {name}: {err:?}")),
+        };
+
+        if let Some(ref part1) = answers.part1 {
+            if let Err(err) = solution.part1.check_str(part1) {
+                return Err(format!("{name} part 1: {err}"));
+            }
+        }
+
+        if let Some(ref part2) = answers.part2 {
+            if let Err(err) = solution.part2.check_str(part2) {
+                return Err(format!("{name} part 2: {err}"));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn load_answers(year_dir: &Path, day: u8) -> Option<DayAnswers> {
+    let path = year_dir.join("answers.toml");
+    let content = fs::read_to_string(&path).ok()?;
+
+    let mut answers: YearAnswers = toml::from_str(&content)
+        .unwrap_or_else(|err| panic!("invalid answers at `{}`: {err}", path.display()));
+
+    answers.remove(&format!("day{day:02}"))
+}
@@ -0,0 +1,158 @@
+//! Lookup table of all solved days across all years.
+//!
+//! Days register themselves through `aoc_rust::register_day!` and each
+//! year's crate collects them through `aoc_rust::register_year!`, see
+//! `aoc_rust::registry`. The years' crates only have to be listed as
+//! dependencies, `build.rs` picks them up from there.
+
+use std::{collections::BTreeMap, sync::LazyLock};
+
+pub use aoc_rust::registry::{Day, Overrides, ParamsSpec, PuzzleParams, RunFn, Variant};
+
+macro_rules! years {
+    ( $( $krate:ident => $dir:literal ),* $(,)? ) => {
+        /// Each year crate's days alongside the crate's directory.
+        static CRATES: &[(&[&Day], &str)] = &[ $( ($krate::DAYS, $dir) ),* ];
+
+        #[cfg(test)]
+        mod answer_tests {
+            $(
+                #[test]
+                fn $krate() {
+                    crate::answers::check_all($krate::DAYS);
+                }
+            )*
+        }
+    };
+}
+
+include!(concat!(env!("OUT_DIR"), "/years.rs"));
+
+/// Registered days grouped by year, both in order.
+struct Registry {
+    years: Vec<u16>,
+    days: Vec<Vec<&'static Day>>,
+}
+
+static REGISTRY: LazyLock<Registry> = LazyLock::new(|| {
+    let mut by_year = BTreeMap::<_, Vec<_>>::new();
+
+    for &(days, _) in CRATES {
+        for &day in days {
+            by_year.entry(day.year).or_default().push(day);
+        }
+    }
+
+    let (years, days) = by_year
+        .into_iter()
+        .map(|(year, mut days)| {
+            days.sort_unstable_by_key(|day| day.day);
+
+            (year, days)
+        })
+        .unzip();
+
+    Registry { years, days }
+});
+
+/// All years with at least one registered day.
+pub fn years() -> &'static [u16] {
+    &REGISTRY.years
+}
+
+/// All registered days of the year in order.
+pub fn days(year: u16) -> &'static [&'static Day] {
+    REGISTRY
+        .years
+        .iter()
+        .position(|&y| y == year)
+        .map_or(&[], |idx| &REGISTRY.days[idx])
+}
+
+/// The registered day, if any.
+pub fn day(year: u16, day: u8) -> Option<&'static Day> {
+    days(year).iter().copied().find(|d| d.day == day)
+}
+
+/// Bitset of the registered days of the year, the lowest bit being day 1.
+pub fn solved_days(year: u16) -> u32 {
+    days(year)
+        .iter()
+        .fold(0, |bits, day| bits | (1 << (day.day - 1)))
+}
+
+#[cfg(test)]
+mod answers;
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::*;
+
+    #[test]
+    fn registrations_are_consistent() {
+        for &year in years() {
+            let days = days(year);
+            assert!(!days.is_empty(), "{year} has no days");
+
+            for pair in days.windows(2) {
+                assert!(
+                    pair[0].day < pair[1].day,
+                    "{year} day {} is registered twice",
+                    pair[0].day
+                );
+            }
+
+            for day in days {
+                assert_eq!(day.year, year);
+                assert!((1..=25).contains(&day.day), "{year} day {}", day.day);
+                assert!(!day.title.is_empty(), "{year} day {} has no title", day.day);
+            }
+        }
+    }
+
+    #[test]
+    fn every_day_is_registered() {
+        for &(_, dir) in CRATES {
+            let src = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("..")
+                .join(dir)
+                .join("src");
+
+            for entry in fs::read_dir(&src).unwrap() {
+                let name = entry.unwrap().file_name();
+                let name = name.to_string_lossy();
+
+                // `dayNN.rs`, `dayNN/`, or `dayNN_YYYY.rs` for another year
+                let module = name.strip_suffix(".rs").unwrap_or(&name);
+
+                let Some(day) = module.strip_prefix("day") else {
+                    continue;
+                };
+
+                let (day, year) = day.split_once('_').unwrap_or((day, dir));
+
+                let (Ok(day), Ok(year)) = (day.parse(), year.parse()) else {
+                    continue;
+                };
+
+                assert!(
+                    super::day(year, day).is_some(),
+                    "`{dir}/src/{name}` is not registered as day {day} of {year}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn lookup() {
+        let day = day(2022, 12).unwrap();
+        assert_eq!(day.title, "Hill Climbing Algorithm");
+        assert!(day.run_fn(Some("a-star")).is_some());
+
+        assert!(super::day(2016, 1).is_none());
+        assert_eq!(super::day(2018, 1).unwrap().title, "Chronal Calibration");
+        assert_eq!(solved_days(2015), 0b1001111);
+    }
+}
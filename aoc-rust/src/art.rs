@@ -0,0 +1,156 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::util::ocr;
+
+/// Monochrome image of pixels, e.g. the letters drawn by a puzzle's screen.
+///
+/// Displays as the recognized letters if possible, otherwise as the raw image.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AsciiArt {
+    width: usize,
+    pixels: Box<[bool]>,
+}
+
+impl AsciiArt {
+    const ON: &'static str = "█";
+    const OFF: &'static str = " ";
+
+    /// Create an image from row-major pixels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the amount of pixels is not a multiple of `width`.
+    pub fn new(width: usize, pixels: impl Into<Box<[bool]>>) -> Self {
+        let pixels = pixels.into();
+
+        assert!(
+            width > 0 && pixels.len() % width == 0,
+            "pixel count {} is not a multiple of width {width}",
+            pixels.len(),
+        );
+
+        Self { width, pixels }
+    }
+
+    /// Create an image from lines of characters where `on` marks a lit pixel.
+    ///
+    /// Shorter lines are padded with unlit pixels.
+    pub fn from_chars(s: &str, on: char) -> Self {
+        let width = s
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut pixels = Vec::new();
+
+        for line in s.lines() {
+            let len = pixels.len();
+            pixels.extend(line.chars().map(|c| c == on));
+            pixels.resize(len + width, false);
+        }
+
+        Self {
+            width: width.max(1),
+            pixels: pixels.into(),
+        }
+    }
+
+    /// Create an image of the bounding box around the given lit pixels.
+    pub fn from_points<I>(points: I) -> Self
+    where
+        I: IntoIterator<Item = (i64, i64)>,
+    {
+        let points: Vec<_> = points.into_iter().collect();
+
+        let Some(&(x, y)) = points.first() else {
+            return Self::new(1, []);
+        };
+
+        let (min_x, max_x, min_y, max_y) =
+            points
+                .iter()
+                .fold((x, x, y, y), |(min_x, max_x, min_y, max_y), &(x, y)| {
+                    (min_x.min(x), max_x.max(x), min_y.min(y), max_y.max(y))
+                });
+
+        let width = (max_x - min_x + 1) as usize;
+        let height = (max_y - min_y + 1) as usize;
+        let mut pixels = vec![false; width * height];
+
+        for (x, y) in points {
+            pixels[(y - min_y) as usize * width + (x - min_x) as usize] = true;
+        }
+
+        Self::new(width, pixels)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.pixels.len() / self.width
+    }
+
+    /// Row-major pixels
+    pub fn pixels(&self) -> &[bool] {
+        &self.pixels
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.width
+            && self
+                .pixels
+                .get(y * self.width + x)
+                .copied()
+                .unwrap_or(false)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[bool]> {
+        self.pixels.chunks_exact(self.width)
+    }
+
+    /// The letters drawn by the image, if they can be recognized.
+    pub fn text(&self) -> Option<String> {
+        ocr::recognize(&self.pixels, self.width)
+    }
+
+    /// Displays the raw image.
+    pub fn render(&self) -> RenderArt<'_> {
+        RenderArt(self)
+    }
+}
+
+impl Display for AsciiArt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.text() {
+            Some(text) => f.write_str(&text),
+            None => Display::fmt(&self.render(), f),
+        }
+    }
+}
+
+pub struct RenderArt<'a>(&'a AsciiArt);
+
+impl Display for RenderArt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let art = self.0;
+        let mut rows = art.rows();
+
+        if let Some(row) = rows.next() {
+            for &px in row {
+                f.write_str(if px { AsciiArt::ON } else { AsciiArt::OFF })?;
+            }
+        }
+
+        for row in rows {
+            f.write_str("\n")?;
+
+            for &px in row {
+                f.write_str(if px { AsciiArt::ON } else { AsciiArt::OFF })?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,577 @@
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Formatter, Result as FmtResult},
+    ops::{Index, IndexMut},
+    slice::{ChunksExact, Iter, IterMut},
+};
+
+use crate::util::parse::ParseError;
+
+use super::{Direction, Pos2, Pos2Iter};
+
+/// Offsets of the orthogonal neighbors:
+///    1
+///   2 3
+///    4
+pub const NEIGHBORS_4: [Pos2<isize>; 4] = [
+    Pos2::new(0, -1),
+    Pos2::new(-1, 0),
+    Pos2::new(1, 0),
+    Pos2::new(0, 1),
+];
+
+/// Offsets of all adjacent neighbors:
+///   123
+///   4 5
+///   678
+pub const NEIGHBORS_8: [Pos2<isize>; 8] = [
+    Pos2::new(-1, -1),
+    Pos2::new(0, -1),
+    Pos2::new(1, -1),
+    Pos2::new(-1, 0),
+    Pos2::new(1, 0),
+    Pos2::new(-1, 1),
+    Pos2::new(0, 1),
+    Pos2::new(1, 1),
+];
+
+/// How neighbors beyond the edge of a [`Grid`] are handled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Edge {
+    /// Neighbors outside of the grid are skipped.
+    Clamp,
+    /// The grid wraps around so the opposite edge is adjacent.
+    Wrap,
+}
+
+/// A dense rectangular grid stored row by row.
+///
+/// A grid always has at least one column but may have no rows.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    entries: Box<[T]>,
+    width: usize,
+}
+
+/// Error when creating a [`Grid`] without columns.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ZeroWidth;
+
+impl Display for ZeroWidth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("a grid must have at least one column")
+    }
+}
+
+impl StdError for ZeroWidth {}
+
+impl<T: Clone + Default> Grid<T> {
+    #[inline]
+    pub fn new(columns: usize, rows: usize) -> Result<Self, ZeroWidth> {
+        Self::filled(columns, rows, T::default())
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    #[inline]
+    pub fn filled(columns: usize, rows: usize, value: T) -> Result<Self, ZeroWidth> {
+        if columns == 0 {
+            return Err(ZeroWidth);
+        }
+
+        Ok(Self {
+            entries: vec![value; columns * rows].into_boxed_slice(),
+            width: columns,
+        })
+    }
+
+    /// Swap rows and columns.
+    ///
+    /// A grid without rows can't become a grid without columns so it stays
+    /// empty with a single column.
+    pub fn transpose(&self) -> Self {
+        let entries = (0..self.width)
+            .flat_map(|x| self.rows().map(move |row| row[x].clone()))
+            .collect();
+
+        Self::from_vec(entries, self.height().max(1))
+    }
+
+    /// Rotate by 90° clockwise.
+    pub fn rotate_right(&self) -> Self {
+        let mut rotated = self.transpose();
+        rotated.flip_horizontal();
+
+        rotated
+    }
+
+    /// Rotate by 90° counter-clockwise.
+    pub fn rotate_left(&self) -> Self {
+        let mut rotated = self.transpose();
+        rotated.flip_vertical();
+
+        rotated
+    }
+}
+
+impl<T> Grid<T> {
+    /// Panics if `width` is zero or the length of `vec` is not a multiple of
+    /// `width`.
+    #[inline]
+    pub fn from_vec(vec: Vec<T>, width: usize) -> Self {
+        assert!(
+            width > 0 && vec.len().is_multiple_of(width),
+            "length must be a multiple of the width"
+        );
+
+        Self {
+            entries: vec.into_boxed_slice(),
+            width,
+        }
+    }
+
+    /// Parse a non-empty rectangular grid, converting each byte through `f`.
+    /// `expected` describes valid bytes for the error message.
+    pub fn parse<F>(input: &str, expected: &str, f: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(u8) -> Option<T>,
+    {
+        Self::parse_section(input, input, expected, f)
+    }
+
+    /// Same as [`Grid::parse`] for a `section` of `input` so that error
+    /// positions refer to the whole input.
+    pub fn parse_section<F>(
+        input: &str,
+        section: &str,
+        expected: &str,
+        mut f: F,
+    ) -> Result<Self, ParseError>
+    where
+        F: FnMut(u8) -> Option<T>,
+    {
+        let mut entries = Vec::with_capacity(section.len());
+        let mut width = None;
+
+        for line in section.lines().map(str::trim_end) {
+            if line.is_empty() || *width.get_or_insert(line.len()) != line.len() {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    "rows must be non-empty and of equal width",
+                ));
+            }
+
+            for (i, byte) in line.bytes().enumerate() {
+                let entry = f(byte).ok_or_else(|| {
+                    ParseError::at(input, &line[i..], format_args!("expected {expected}"))
+                })?;
+
+                entries.push(entry);
+            }
+        }
+
+        let width = width.ok_or_else(|| ParseError::at(input, section, "empty grid"))?;
+
+        Ok(Self::from_vec(entries, width))
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.entries.len() / self.width
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[inline]
+    pub fn contains<I>(&self, pos: Pos2<I>) -> bool
+    where
+        usize: TryFrom<I>,
+    {
+        self.checked_idx(pos).is_some()
+    }
+
+    #[inline]
+    pub fn get<I>(&self, pos: Pos2<I>) -> Option<&T>
+    where
+        usize: TryFrom<I>,
+    {
+        self.checked_idx(pos).map(|idx| &self.entries[idx])
+    }
+
+    #[inline]
+    pub fn get_mut<I>(&mut self, pos: Pos2<I>) -> Option<&mut T>
+    where
+        usize: TryFrom<I>,
+    {
+        self.checked_idx(pos).map(|idx| &mut self.entries[idx])
+    }
+
+    fn checked_idx<I>(&self, pos: Pos2<I>) -> Option<usize>
+    where
+        usize: TryFrom<I>,
+    {
+        let x = usize::try_from(pos.x).ok()?;
+        let y = usize::try_from(pos.y).ok()?;
+
+        (x < self.width && y < self.height()).then_some(y * self.width + x)
+    }
+
+    /// Iterates over neighbors:
+    ///    1
+    ///   2 3
+    ///    4
+    #[inline]
+    pub fn neighbors_4(&self, pos: Pos2<usize>) -> impl Iterator<Item = Pos2<usize>> {
+        self.neighbors(pos, &NEIGHBORS_4, Edge::Clamp)
+    }
+
+    /// Iterates over neighbors:
+    ///   123
+    ///   4 5
+    ///   678
+    #[inline]
+    pub fn neighbors_8(&self, pos: Pos2<usize>) -> impl Iterator<Item = Pos2<usize>> {
+        self.neighbors(pos, &NEIGHBORS_8, Edge::Clamp)
+    }
+
+    /// Iterates over `pos` shifted by each of the `offsets`.
+    pub fn neighbors<'o>(
+        &self,
+        pos: Pos2<usize>,
+        offsets: &'o [Pos2<isize>],
+        edge: Edge,
+    ) -> impl Iterator<Item = Pos2<usize>> + 'o {
+        let w = self.width as isize;
+        let h = self.height() as isize;
+        let pos = Pos2::new(pos.x as isize, pos.y as isize);
+
+        offsets.iter().filter_map(move |offset| {
+            let Pos2 { x, y } = pos + *offset;
+
+            match edge {
+                Edge::Clamp => {
+                    (x >= 0 && y >= 0 && x < w && y < h).then(|| Pos2::new(x as usize, y as usize))
+                }
+                Edge::Wrap => Some(Pos2::new(
+                    x.rem_euclid(w) as usize,
+                    y.rem_euclid(h) as usize,
+                )),
+            }
+        })
+    }
+
+    /// Step from `pos` into `direction`.
+    #[inline]
+    pub fn step(&self, pos: Pos2<usize>, direction: Direction, edge: Edge) -> Option<Pos2<usize>> {
+        self.neighbors(pos, &[direction.delta()], edge).next()
+    }
+
+    /// Iterates over all positions row by row.
+    #[inline]
+    pub fn positions(&self) -> Pos2Iter {
+        match self.height().checked_sub(1) {
+            Some(max_y) => Pos2Iter::new(0, self.width - 1, 0, max_y),
+            // an empty range of rows
+            None => Pos2Iter::new(0, self.width - 1, 1, 0),
+        }
+    }
+
+    /// Position of the first entry that satisfies `f`.
+    #[inline]
+    pub fn find(&self, f: impl FnMut(&T) -> bool) -> Option<Pos2<usize>> {
+        self.entries
+            .iter()
+            .position(f)
+            .map(|idx| Pos2::new(idx % self.width, idx / self.width))
+    }
+
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.entries.iter()
+    }
+
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.entries.iter_mut()
+    }
+
+    /// Iterates over all entries alongside their position.
+    #[inline]
+    pub fn enumerate(&self) -> impl Iterator<Item = (Pos2<usize>, &T)> {
+        self.positions().zip(self.entries.iter())
+    }
+
+    #[inline]
+    pub fn rows(&self) -> ChunksExact<'_, T> {
+        self.entries.chunks_exact(self.width)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            entries: self.entries.iter().map(f).collect(),
+            width: self.width,
+        }
+    }
+
+    /// Mirror along the vertical axis i.e. reverse each row.
+    pub fn flip_horizontal(&mut self) {
+        for row in self.entries.chunks_exact_mut(self.width) {
+            row.reverse();
+        }
+    }
+
+    /// Mirror along the horizontal axis i.e. reverse the order of rows.
+    pub fn flip_vertical(&mut self) {
+        let height = self.height();
+
+        for y in 0..height / 2 {
+            let (top, bottom) = self.entries.split_at_mut((height - 1 - y) * self.width);
+            top[y * self.width..][..self.width].swap_with_slice(&mut bottom[..self.width]);
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Count how many neighbors of `(x,y)` equal `n`.
+    pub fn count_neighbors(&self, x: usize, y: usize, n: T) -> u8 {
+        self.neighbors_8(Pos2::new(x, y))
+            .filter(|&pos| self[pos] == n)
+            .count() as u8
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.entries.index(y * self.width + x)
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        self.entries.index_mut(y * self.width + x)
+    }
+}
+
+impl<T> Index<Pos2<usize>> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, pos: Pos2<usize>) -> &Self::Output {
+        self.entries.index(pos.y * self.width + pos.x)
+    }
+}
+
+impl<T> IndexMut<Pos2<usize>> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, pos: Pos2<usize>) -> &mut Self::Output {
+        self.entries.index_mut(pos.y * self.width + pos.x)
+    }
+}
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = [T];
+
+    #[inline]
+    fn index(&self, y: usize) -> &Self::Output {
+        let row = y * self.width;
+        self.entries.index(row..row + self.width)
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, y: usize) -> &mut Self::Output {
+        let row = y * self.width;
+        self.entries.index_mut(row..row + self.width)
+    }
+}
+
+/// Renders one line per row without separators between entries.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                f.write_str("\n")?;
+            }
+
+            for entry in row {
+                Display::fmt(entry, f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn basic_grid() -> Grid<u8> {
+        let vec = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+        Grid::from_vec(vec, 3)
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(Grid::<u8>::new(0, 3), Err(ZeroWidth));
+        assert_eq!(Grid::filled(0, 0, b'.'), Err(ZeroWidth));
+
+        let grid = Grid::<u8>::new(3, 0).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 0));
+        assert!(grid.is_empty());
+        assert_eq!(grid.positions().next(), None);
+        assert_eq!(grid.enumerate().count(), 0);
+        assert!(grid.get(Pos2::new(0, 0)).is_none());
+        assert!(grid.transpose().is_empty());
+    }
+
+    #[test]
+    fn neighbors_4_centre() {
+        let grid = basic_grid();
+
+        let mut neighbors = grid.neighbors_4(Pos2::new(1, 1));
+
+        assert_eq!(neighbors.next(), Some(Pos2::new(1, 0)));
+        assert_eq!(neighbors.next(), Some(Pos2::new(0, 1)));
+        assert_eq!(neighbors.next(), Some(Pos2::new(2, 1)));
+        assert_eq!(neighbors.next(), Some(Pos2::new(1, 2)));
+        assert_eq!(neighbors.next(), None);
+    }
+
+    #[test]
+    fn neighbors_4_edge() {
+        let grid = basic_grid();
+
+        let mut neighbors = grid.neighbors_4(Pos2::new(0, 1));
+
+        assert_eq!(neighbors.next(), Some(Pos2::new(0, 0)));
+        assert_eq!(neighbors.next(), Some(Pos2::new(1, 1)));
+        assert_eq!(neighbors.next(), Some(Pos2::new(0, 2)));
+        assert_eq!(neighbors.next(), None);
+    }
+
+    #[test]
+    fn neighbors_8_centre() {
+        let grid = basic_grid();
+
+        let mut neighbors = grid.neighbors_8(Pos2::new(1, 1));
+
+        assert_eq!(neighbors.next(), Some(Pos2::new(0, 0)));
+        assert_eq!(neighbors.next(), Some(Pos2::new(1, 0)));
+        assert_eq!(neighbors.next(), Some(Pos2::new(2, 0)));
+        assert_eq!(neighbors.next(), Some(Pos2::new(0, 1)));
+        assert_eq!(neighbors.next(), Some(Pos2::new(2, 1)));
+        assert_eq!(neighbors.next(), Some(Pos2::new(0, 2)));
+        assert_eq!(neighbors.next(), Some(Pos2::new(1, 2)));
+        assert_eq!(neighbors.next(), Some(Pos2::new(2, 2)));
+        assert_eq!(neighbors.next(), None);
+    }
+
+    #[test]
+    fn neighbors_8_edge() {
+        let grid = basic_grid();
+
+        let mut neighbors = grid.neighbors_8(Pos2::new(0, 1));
+
+        assert_eq!(neighbors.next(), Some(Pos2::new(0, 0)));
+        assert_eq!(neighbors.next(), Some(Pos2::new(1, 0)));
+        assert_eq!(neighbors.next(), Some(Pos2::new(1, 1)));
+        assert_eq!(neighbors.next(), Some(Pos2::new(0, 2)));
+        assert_eq!(neighbors.next(), Some(Pos2::new(1, 2)));
+        assert_eq!(neighbors.next(), None);
+    }
+
+    #[test]
+    fn neighbors_wrap() {
+        let grid = basic_grid();
+
+        let neighbors: Vec<_> = grid
+            .neighbors(Pos2::new(0, 0), &NEIGHBORS_4, Edge::Wrap)
+            .collect();
+
+        let expected = [(0, 2), (2, 0), (1, 0), (0, 1)].map(Pos2::from);
+        assert_eq!(neighbors, expected);
+
+        let step = grid.step(Pos2::new(2, 1), Direction::Right, Edge::Wrap);
+        assert_eq!(step, Some(Pos2::new(0, 1)));
+        assert_eq!(
+            grid.step(Pos2::new(2, 1), Direction::Right, Edge::Clamp),
+            None
+        );
+    }
+
+    #[test]
+    fn count_neighbors_centre() {
+        let grid = basic_grid();
+
+        assert_eq!(grid.count_neighbors(1, 1, 2), 1);
+    }
+
+    #[test]
+    fn count_neighbors_edge() {
+        let grid = basic_grid();
+
+        assert_eq!(grid.count_neighbors(0, 1, 7), 1);
+        assert_eq!(grid.count_neighbors(0, 1, 9), 0);
+    }
+
+    #[test]
+    fn parse_digits() {
+        let digit = |byte: u8| byte.is_ascii_digit().then_some(byte & 0x0F);
+
+        let grid = Grid::parse("123\n456\n789\n", "a digit", digit).unwrap();
+        assert_eq!(grid, basic_grid());
+
+        let err = Grid::parse("123\n4x6", "a digit", digit).unwrap_err();
+        assert_eq!((err.line(), err.column()), (Some(2), Some(2)));
+
+        let err = Grid::parse("123\n45", "a digit", digit).unwrap_err();
+        assert_eq!(err.line(), Some(2));
+    }
+
+    #[test]
+    fn index() {
+        let grid = basic_grid();
+
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid[Pos2::new(2, 1)], 6);
+        assert_eq!(&grid[1], &[4, 5, 6]);
+        assert_eq!(grid.get(Pos2::new(-1, 0)), None);
+    }
+
+    #[test]
+    fn transformations() {
+        let grid = Grid::from_vec(vec![1, 2, 3, 4, 5, 6], 3);
+
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+        assert_eq!(grid.rotate_right().to_string(), "41\n52\n63");
+        assert_eq!(grid.rotate_left().to_string(), "36\n25\n14");
+
+        let mut flipped = grid.clone();
+        flipped.flip_horizontal();
+        assert_eq!(flipped.to_string(), "321\n654");
+
+        flipped.flip_vertical();
+        assert_eq!(flipped.to_string(), "654\n321");
+    }
+}
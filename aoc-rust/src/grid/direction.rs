@@ -0,0 +1,132 @@
+use super::Pos2;
+
+/// The four orthogonal directions, ordered clockwise starting at the top.
+///
+/// `y` values grow downwards, i.e. [`Direction::Up`] decrements `y`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// A change of [`Direction`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+    Around,
+    Straight,
+}
+
+impl Direction {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Parse arrows `^>v<`, letters `UDLR`, or compass points `NESW`.
+    pub const fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'^' | b'U' | b'N' => Some(Self::Up),
+            b'>' | b'R' | b'E' => Some(Self::Right),
+            b'v' | b'D' | b'S' => Some(Self::Down),
+            b'<' | b'L' | b'W' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub const fn turn(self, turn: Turn) -> Self {
+        let offset = match turn {
+            Turn::Straight => 0,
+            Turn::Right => 1,
+            Turn::Around => 2,
+            Turn::Left => 3,
+        };
+
+        Self::ALL[(self as usize + offset) % 4]
+    }
+
+    #[inline]
+    pub const fn turn_left(self) -> Self {
+        self.turn(Turn::Left)
+    }
+
+    #[inline]
+    pub const fn turn_right(self) -> Self {
+        self.turn(Turn::Right)
+    }
+
+    #[inline]
+    pub const fn reverse(self) -> Self {
+        self.turn(Turn::Around)
+    }
+
+    /// The turn that leads from `self` to `other`.
+    #[inline]
+    pub const fn turn_to(self, other: Self) -> Turn {
+        match (other as u8 + 4 - self as u8) % 4 {
+            0 => Turn::Straight,
+            1 => Turn::Right,
+            2 => Turn::Around,
+            _ => Turn::Left,
+        }
+    }
+
+    #[inline]
+    pub const fn is_horizontal(self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
+
+    /// The offset of a single step into this direction.
+    #[inline]
+    pub fn delta<T: From<i8>>(self) -> Pos2<T> {
+        let (x, y) = match self {
+            Self::Up => (0, -1),
+            Self::Right => (1, 0),
+            Self::Down => (0, 1),
+            Self::Left => (-1, 0),
+        };
+
+        Pos2::new(T::from(x), T::from(y))
+    }
+
+    /// Step from `pos` into this direction, returning `None` on underflow.
+    #[inline]
+    pub fn step(self, pos: Pos2<usize>) -> Option<Pos2<usize>> {
+        let Pos2 { x, y } = pos;
+
+        let (x, y) = match self {
+            Self::Up => (x, y.checked_sub(1)?),
+            Self::Right => (x + 1, y),
+            Self::Down => (x, y + 1),
+            Self::Left => (x.checked_sub(1)?, y),
+        };
+
+        Some(Pos2::new(x, y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Right.reverse(), Direction::Left);
+
+        for a in Direction::ALL {
+            for b in Direction::ALL {
+                assert_eq!(a.turn(a.turn_to(b)), b);
+            }
+        }
+    }
+
+    #[test]
+    fn delta() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.delta::<i32>().rotate_right(), dir.turn_right().delta());
+        }
+    }
+}
@@ -0,0 +1,8 @@
+//! Dense and sparse 2D grids alongside positions and directions.
+
+mod dense;
+mod direction;
+mod pos;
+mod sparse;
+
+pub use self::{dense::*, direction::*, pos::*, sparse::*};
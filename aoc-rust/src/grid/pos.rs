@@ -0,0 +1,376 @@
+use std::{
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    ops::{
+        Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, RangeInclusive, Rem,
+        RemAssign, Sub, SubAssign,
+    },
+};
+
+#[derive(Copy, Clone, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct Pos2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Copy, Clone, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct Pos3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Pos2<T> {
+    #[inline]
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Pos3<T> {
+    #[inline]
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> Pos2<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    /// Return the manhattan distance to another position.
+    #[inline]
+    pub fn manhattan_dist(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+}
+
+impl<T> Pos3<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    /// Return the manhattan distance to another position.
+    #[inline]
+    pub fn manhattan_dist(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+}
+
+fn abs_diff<T: PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> Pos2<T> {
+    /// Rotate by 90° clockwise around the origin, assuming `y` points down.
+    #[inline]
+    pub fn rotate_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Rotate by 90° counter-clockwise around the origin, assuming `y` points down.
+    #[inline]
+    pub fn rotate_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+}
+
+macro_rules! impl_decimal {
+    ($($type:ty),*) => {
+        $(
+            impl Pos2<$type> {
+                /// Return the position's length.
+                #[inline]
+                pub fn len(&self) -> $type {
+                    self.x.hypot(self.y)
+                }
+
+                /// Return the distance to another position.
+                #[inline]
+                pub fn dist(&self, other: Self) -> $type {
+                    (*self - other).len()
+                }
+
+                /// Normalize the coordinates with respect to the vector's length.
+                #[inline]
+                pub fn normalize(self) -> Self {
+                    self / self.len()
+                }
+            }
+
+            impl Pos3<$type> {
+                /// Return the position's length.
+                #[inline]
+                pub fn len(&self) -> $type {
+                    (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+                }
+
+                /// Return the distance to another position.
+                #[inline]
+                pub fn dist(&self, other: Self) -> $type {
+                    (*self - other).len()
+                }
+
+                /// Normalize the coordinates with respect to the vector's length.
+                #[inline]
+                pub fn normalize(self) -> Self {
+                    self / self.len()
+                }
+            }
+        )*
+    };
+}
+
+impl_decimal!(f32, f64);
+
+impl<T: Debug> Debug for Pos2<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Pos2")
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .finish()
+    }
+}
+
+impl<T: Debug> Debug for Pos3<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Pos3")
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .field("z", &self.z)
+            .finish()
+    }
+}
+
+impl<T: Display> Display for Pos2<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Pos3<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "({},{},{})", self.x, self.y, self.z)
+    }
+}
+
+impl<T> From<(T, T)> for Pos2<T> {
+    #[inline]
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<(T, T, T)> for Pos3<T> {
+    #[inline]
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+macro_rules! impl_ops {
+    ($pos:ident { $($field:ident),+ } $len:literal) => {
+        impl<T: Add<Output = T>> Add for $pos<T> {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, rhs: Self) -> Self::Output {
+                Self { $( $field: self.$field + rhs.$field, )+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $pos<T> {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self { $( $field: self.$field - rhs.$field, )+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $pos<T> {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self::Output {
+                Self { $( $field: -self.$field, )+ }
+            }
+        }
+
+        impl<U: Copy, T: Mul<U, Output = T>> Mul<U> for $pos<T> {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, rhs: U) -> Self::Output {
+                Self { $( $field: self.$field * rhs, )+ }
+            }
+        }
+
+        impl<U: Copy, T: Div<U, Output = T>> Div<U> for $pos<T> {
+            type Output = Self;
+
+            #[inline]
+            fn div(self, rhs: U) -> Self::Output {
+                Self { $( $field: self.$field / rhs, )+ }
+            }
+        }
+
+        impl<U: Copy, T: Rem<U, Output = T>> Rem<U> for $pos<T> {
+            type Output = Self;
+
+            #[inline]
+            fn rem(self, rhs: U) -> Self::Output {
+                Self { $( $field: self.$field % rhs, )+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $pos<T> {
+            #[inline]
+            fn add_assign(&mut self, rhs: Self) {
+                $( self.$field += rhs.$field; )+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $pos<T> {
+            #[inline]
+            fn sub_assign(&mut self, rhs: Self) {
+                $( self.$field -= rhs.$field; )+
+            }
+        }
+
+        impl<U: Copy, T: MulAssign<U>> MulAssign<U> for $pos<T> {
+            #[inline]
+            fn mul_assign(&mut self, rhs: U) {
+                $( self.$field *= rhs; )+
+            }
+        }
+
+        impl<U: Copy, T: DivAssign<U>> DivAssign<U> for $pos<T> {
+            #[inline]
+            fn div_assign(&mut self, rhs: U) {
+                $( self.$field /= rhs; )+
+            }
+        }
+
+        impl<U: Copy, T: RemAssign<U>> RemAssign<U> for $pos<T> {
+            #[inline]
+            fn rem_assign(&mut self, rhs: U) {
+                $( self.$field %= rhs; )+
+            }
+        }
+
+        impl<T> Index<usize> for $pos<T> {
+            type Output = T;
+
+            #[inline]
+            fn index(&self, idx: usize) -> &Self::Output {
+                let fields = [$( &self.$field ),+];
+
+                fields
+                    .get(idx)
+                    .copied()
+                    .unwrap_or_else(|| panic!("index must be below {}", $len))
+            }
+        }
+
+        impl<T> IndexMut<usize> for $pos<T> {
+            #[inline]
+            fn index_mut(&mut self, idx: usize) -> &mut Self::Output {
+                let fields = [$( &mut self.$field ),+];
+
+                fields
+                    .into_iter()
+                    .nth(idx)
+                    .unwrap_or_else(|| panic!("index must be below {}", $len))
+            }
+        }
+    };
+}
+
+impl_ops!(Pos2 { x, y } 2);
+impl_ops!(Pos3 { x, y, z } 3);
+
+/// Iterates row by row over all positions of a rectangle.
+// Can be generic once `std::iter::Step` is stabilized
+#[derive(Clone, Debug)]
+pub struct Pos2Iter {
+    x_range_curr: RangeInclusive<usize>,
+    x_range: RangeInclusive<usize>,
+    y_range: RangeInclusive<usize>,
+    y: Option<usize>,
+}
+
+impl Pos2Iter {
+    #[inline]
+    pub fn new(min_x: usize, max_x: usize, min_y: usize, max_y: usize) -> Self {
+        let x_range = min_x..=max_x;
+        let mut y_range = min_y..=max_y;
+
+        Self {
+            x_range_curr: x_range.clone(),
+            y: y_range.next(),
+            x_range,
+            y_range,
+        }
+    }
+}
+
+impl Iterator for Pos2Iter {
+    type Item = Pos2<usize>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (x, y) = match self.x_range_curr.next() {
+            Some(x) => (x, self.y?),
+            None => {
+                self.x_range_curr = self.x_range.clone();
+                self.y = self.y_range.next();
+
+                (self.x_range_curr.next()?, self.y?)
+            }
+        };
+
+        Some(Pos2::new(x, y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iter() {
+        let mut iter = Pos2Iter::new(2, 4, 0, 1);
+
+        assert_eq!(iter.next(), Some(Pos2::new(2, 0)));
+        assert_eq!(iter.next(), Some(Pos2::new(3, 0)));
+        assert_eq!(iter.next(), Some(Pos2::new(4, 0)));
+        assert_eq!(iter.next(), Some(Pos2::new(2, 1)));
+        assert_eq!(iter.next(), Some(Pos2::new(3, 1)));
+        assert_eq!(iter.next(), Some(Pos2::new(4, 1)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn ops() {
+        let a = Pos3::new(1, -2, 3);
+        let b = Pos3::new(-4, 5, 6);
+
+        assert_eq!(a + b, Pos3::new(-3, 3, 9));
+        assert_eq!(a - b, Pos3::new(5, -7, -3));
+        assert_eq!(-a * 2, Pos3::new(-2, 4, -6));
+        assert_eq!(a.manhattan_dist(&b), 5 + 7 + 3);
+        assert_eq!(b[2], 6);
+    }
+
+    #[test]
+    fn rotate() {
+        let right = Pos2::new(1, 0);
+
+        assert_eq!(right.rotate_right(), Pos2::new(0, 1));
+        assert_eq!(right.rotate_left(), Pos2::new(0, -1));
+        assert_eq!(right.rotate_left().rotate_left(), -right);
+    }
+}
@@ -0,0 +1,197 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fmt::{Display, Formatter, Result as FmtResult},
+    ops::{Index, IndexMut},
+};
+
+use super::{Grid, Pos2};
+
+/// An unbounded grid that only stores the positions it was given.
+///
+/// Coordinates can be negative and the grid grows as entries are inserted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    entries: HashMap<Pos2<i32>, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[inline]
+    pub fn insert(&mut self, pos: Pos2<i32>, value: T) -> Option<T> {
+        self.entries.insert(pos, value)
+    }
+
+    #[inline]
+    pub fn remove(&mut self, pos: Pos2<i32>) -> Option<T> {
+        self.entries.remove(&pos)
+    }
+
+    #[inline]
+    pub fn get(&self, pos: Pos2<i32>) -> Option<&T> {
+        self.entries.get(&pos)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, pos: Pos2<i32>) -> Option<&mut T> {
+        self.entries.get_mut(&pos)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    #[inline]
+    pub fn contains(&self, pos: Pos2<i32>) -> bool {
+        self.entries.contains_key(&pos)
+    }
+
+    #[inline]
+    pub fn entry(&mut self, pos: Pos2<i32>) -> Entry<'_, Pos2<i32>, T> {
+        self.entries.entry(pos)
+    }
+
+    /// Iterates over all entries in arbitrary order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (Pos2<i32>, &T)> {
+        self.entries.iter().map(|(pos, value)| (*pos, value))
+    }
+
+    /// The smallest and largest coordinates of all entries, or `None` if
+    /// the grid is empty.
+    pub fn bounds(&self) -> Option<(Pos2<i32>, Pos2<i32>)> {
+        let mut positions = self.entries.keys();
+        let first = *positions.next()?;
+
+        let bounds = positions.fold((first, first), |(min, max), pos| {
+            let min = Pos2::new(min.x.min(pos.x), min.y.min(pos.y));
+            let max = Pos2::new(max.x.max(pos.x), max.y.max(pos.y));
+
+            (min, max)
+        });
+
+        Some(bounds)
+    }
+
+    /// Convert into a dense grid that covers all entries, filling gaps with
+    /// `fill`. Also returns the position of the dense grid's top left corner.
+    pub fn to_dense(&self, fill: T) -> Option<(Pos2<i32>, Grid<T>)>
+    where
+        T: Clone,
+    {
+        let (min, max) = self.bounds()?;
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+
+        let mut grid = Grid::filled(width, height, fill).ok()?;
+
+        for (pos, value) in self.entries.iter() {
+            let x = (pos.x - min.x) as usize;
+            let y = (pos.y - min.y) as usize;
+            grid[(x, y)] = value.clone();
+        }
+
+        Some((min, grid))
+    }
+}
+
+/// Panics if there is no entry at the position.
+impl<T> Index<Pos2<i32>> for SparseGrid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, pos: Pos2<i32>) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| panic!("no entry at {pos}"))
+    }
+}
+
+/// Panics if there is no entry at the position.
+impl<T> IndexMut<Pos2<i32>> for SparseGrid<T> {
+    #[inline]
+    fn index_mut(&mut self, pos: Pos2<i32>) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("no entry at {pos}"))
+    }
+}
+
+impl<T> FromIterator<(Pos2<i32>, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Pos2<i32>, T)>>(iter: I) -> Self {
+        Self {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<(Pos2<i32>, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Pos2<i32>, T)>>(&mut self, iter: I) {
+        self.entries.extend(iter);
+    }
+}
+
+/// Renders the bounding box of all entries; missing positions are blank.
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let Some((min, max)) = self.bounds() else {
+            return Ok(());
+        };
+
+        for y in min.y..=max.y {
+            if y > min.y {
+                f.write_str("\n")?;
+            }
+
+            for x in min.x..=max.x {
+                match self.get(Pos2::new(x, y)) {
+                    Some(value) => Display::fmt(value, f)?,
+                    None => f.write_str(" ")?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render() {
+        let grid: SparseGrid<char> = [((-1, 2), '#'), ((1, 0), '@'), ((0, 1), '#')]
+            .into_iter()
+            .map(|(pos, c)| (Pos2::from(pos), c))
+            .collect();
+
+        assert_eq!(grid.bounds(), Some((Pos2::new(-1, 0), Pos2::new(1, 2))));
+        assert_eq!(grid[Pos2::new(1, 0)], '@');
+        assert_eq!(grid.to_string(), "  @\n # \n#  ");
+
+        let (corner, dense) = grid.to_dense('.').unwrap();
+        assert_eq!(corner, Pos2::new(-1, 0));
+        assert_eq!(dense.to_string(), "..@\n.#.\n#..");
+    }
+}
@@ -1,5 +1,13 @@
+mod art;
+pub mod grid;
+pub mod machine;
 mod macros;
+pub mod netlist;
+pub mod registry;
+pub mod search;
 mod solution;
+pub mod timing;
 pub mod util;
 
-pub use solution::{Solution, SolutionType};
+pub use art::{AsciiArt, RenderArt};
+pub use solution::{Solution, SolutionMismatch, SolutionType};
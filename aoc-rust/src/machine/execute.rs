@@ -0,0 +1,224 @@
+use super::{Instruction, Peephole, Step};
+
+/// Why a run of a [`Machine`] stopped.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Exit<O> {
+    /// The program counter left the program or an instruction halted
+    Halted,
+    /// An instruction emitted a value, the run can be resumed afterwards
+    Output(O),
+    /// The inspection passed to [`Machine::run_with`] stopped the run
+    Stopped,
+    /// The next instruction would be executed a second time, see
+    /// [`Machine::run_until_loop`]
+    Loop,
+}
+
+/// Executes a program of instructions on a state.
+pub struct Machine<I: Instruction> {
+    pub state: I::State,
+    /// Index of the next instruction
+    pub pc: usize,
+    /// Amount of executed instructions, fused ones counting once
+    pub steps: u64,
+    program: Vec<I>,
+    /// The program with all peepholes applied, this is what's executed
+    optimized: Vec<I>,
+    peepholes: Vec<Peephole<I>>,
+}
+
+impl<I: Instruction> Machine<I> {
+    pub fn new(program: Vec<I>, state: I::State) -> Self {
+        Self {
+            state,
+            pc: 0,
+            steps: 0,
+            optimized: program.clone(),
+            program,
+            peepholes: Vec::new(),
+        }
+    }
+
+    /// Optimize the program through `peephole`, also after it patched itself.
+    pub fn with_peephole(mut self, peephole: Peephole<I>) -> Self {
+        self.peepholes.push(peephole);
+        self.optimize();
+
+        self
+    }
+
+    /// The program as it was given, including patches but without
+    /// optimizations.
+    pub fn program(&self) -> &[I] {
+        &self.program
+    }
+
+    /// The instruction that is executed next, possibly a fused one.
+    pub fn current(&self) -> Option<&I> {
+        self.optimized.get(self.pc)
+    }
+
+    /// Replace the instruction at `idx`, e.g. to repair the program.
+    pub fn patch(&mut self, idx: usize, instruction: I) {
+        self.program[idx] = instruction;
+        self.optimize();
+    }
+
+    /// Start over from the first instruction on a new state, keeping patches.
+    pub fn reset(&mut self, state: I::State) {
+        self.state = state;
+        self.pc = 0;
+        self.steps = 0;
+    }
+
+    fn optimize(&mut self) {
+        let program = &self.program;
+
+        self.optimized = (0..program.len())
+            .map(|idx| {
+                self.peepholes
+                    .iter()
+                    .find_map(|peephole| peephole(program, idx))
+                    .unwrap_or_else(|| program[idx].clone())
+            })
+            .collect();
+    }
+
+    /// Execute the next instruction.
+    ///
+    /// Returns `None` if the program continues afterwards.
+    pub fn step(&mut self) -> Option<Exit<I::Output>> {
+        let Some(instruction) = self.optimized.get(self.pc) else {
+            return Some(Exit::Halted);
+        };
+
+        let step = instruction.execute(&mut self.state);
+        self.steps += 1;
+
+        match step {
+            Step::Next => self.pc += 1,
+            Step::Jump(offset) => match usize::try_from(self.pc as i64 + offset) {
+                Ok(pc) => self.pc = pc,
+                Err(_) => return self.halt(),
+            },
+            Step::Goto(pc) => self.pc = pc,
+            Step::Output(value) => {
+                self.pc += 1;
+
+                return Some(Exit::Output(value));
+            }
+            Step::Patch { offset, patch } => {
+                let target = usize::try_from(self.pc as i64 + offset).ok();
+
+                if let Some(target) = target.filter(|&target| target < self.program.len()) {
+                    let patched = patch(&self.program[target]);
+                    self.patch(target, patched);
+                }
+
+                self.pc += 1;
+            }
+            Step::Halt => return self.halt(),
+        }
+
+        None
+    }
+
+    fn halt(&mut self) -> Option<Exit<I::Output>> {
+        self.pc = usize::MAX;
+
+        Some(Exit::Halted)
+    }
+
+    /// Run until the program halts or emits a value.
+    pub fn run(&mut self) -> Exit<I::Output> {
+        self.run_with(|_| true)
+    }
+
+    /// Same as [`Machine::run`] but `inspect` is called before each
+    /// instruction and stops the run by returning `false`.
+    pub fn run_with<F>(&mut self, mut inspect: F) -> Exit<I::Output>
+    where
+        F: FnMut(&Self) -> bool,
+    {
+        loop {
+            if self.pc >= self.optimized.len() {
+                return Exit::Halted;
+            }
+
+            if !inspect(self) {
+                return Exit::Stopped;
+            }
+
+            if let Some(exit) = self.step() {
+                return exit;
+            }
+        }
+    }
+
+    /// Same as [`Machine::run`] but passes each instruction alongside its
+    /// index and the state before executing it to `trace`.
+    pub fn run_traced<F>(&mut self, mut trace: F) -> Exit<I::Output>
+    where
+        F: FnMut(usize, &I, &I::State),
+    {
+        self.run_with(|machine| {
+            trace(machine.pc, &machine.optimized[machine.pc], &machine.state);
+
+            true
+        })
+    }
+
+    /// Same as [`Machine::run`] but stops right before an instruction would
+    /// be executed a second time.
+    ///
+    /// Only a loop for programs whose jumps don't depend on the state.
+    pub fn run_until_loop(&mut self) -> Exit<I::Output> {
+        let mut seen = vec![false; self.optimized.len()];
+
+        let exit = self.run_with(|machine| !std::mem::replace(&mut seen[machine.pc], true));
+
+        match exit {
+            Exit::Stopped => Exit::Loop,
+            exit => exit,
+        }
+    }
+
+    /// Patch one instruction at a time through `patch` and return the first
+    /// result that `check` produces for a copy of the patched machine.
+    ///
+    /// Instructions for which `patch` returns `None` are skipped.
+    pub fn try_patches<T, P, C>(&self, mut patch: P, mut check: C) -> Option<T>
+    where
+        I::State: Clone,
+        P: FnMut(&I) -> Option<I>,
+        C: FnMut(&mut Self) -> Option<T>,
+    {
+        self.program
+            .iter()
+            .enumerate()
+            .find_map(|(idx, instruction)| {
+                let patched = patch(instruction)?;
+                let mut machine = self.clone();
+                machine.patch(idx, patched);
+
+                check(&mut machine)
+            })
+    }
+}
+
+impl<I> Clone for Machine<I>
+where
+    I: Instruction,
+    I::State: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            pc: self.pc,
+            steps: self.steps,
+            program: self.program.clone(),
+            optimized: self.optimized.clone(),
+            peepholes: self.peepholes.clone(),
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//! Small register machines as found in assembly-style puzzles.
+//!
+//! An instruction set is a type implementing [`Instruction`] whose
+//! instructions act on some [`Instruction::State`], usually registers.
+//! Programs are parsed line by line through [`parse_program`] and executed by
+//! a [`Machine`] which takes care of the program counter, tracing, loop
+//! detection, patching, and [peephole optimizations](Peephole).
+
+mod execute;
+mod parse;
+mod peephole;
+
+pub use self::{
+    execute::{Exit, Machine},
+    parse::{parse_program, Operand, Words},
+    peephole::{add_loop, mul_loop, Fused, Peephole, Shape, Shaped},
+};
+
+/// An instruction of a register machine.
+pub trait Instruction: Clone {
+    /// Registers and whatever else the instructions act on
+    type State;
+    /// Values emitted through [`Step::Output`]
+    type Output;
+
+    /// Apply the instruction to the state and tell the machine how to
+    /// continue.
+    fn execute(&self, state: &mut Self::State) -> Step<Self>;
+}
+
+/// How the machine continues after executing an instruction.
+pub enum Step<I: Instruction> {
+    /// Continue with the next instruction
+    Next,
+    /// Jump relative to the current instruction
+    Jump(i64),
+    /// Jump to the instruction at the index
+    Goto(usize),
+    /// Emit a value and continue with the next instruction
+    Output(I::Output),
+    /// Replace the instruction at an offset relative to the current one with
+    /// the result of `patch` and continue with the next instruction.
+    ///
+    /// Offsets outside of the program are ignored.
+    Patch { offset: i64, patch: fn(&I) -> I },
+    /// Stop the program
+    Halt,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse::ParseError;
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    enum Op {
+        Cpy(Operand, u8),
+        Inc(u8),
+        Dec(u8),
+        Jnz(Operand, i64),
+        Tgl(u8),
+        Out(u8),
+        Fused(Fused),
+    }
+
+    impl Instruction for Op {
+        type State = [i64; 4];
+        type Output = i64;
+
+        fn execute(&self, regs: &mut [i64; 4]) -> Step<Self> {
+            match *self {
+                Op::Cpy(src, dst) => regs[dst as usize] = src.get(regs),
+                Op::Inc(reg) => regs[reg as usize] += 1,
+                Op::Dec(reg) => regs[reg as usize] -= 1,
+                Op::Jnz(cond, offset) if cond.get(regs) != 0 => return Step::Jump(offset),
+                Op::Jnz(..) => {}
+                Op::Tgl(reg) => {
+                    let patch = |op: &Op| match *op {
+                        Op::Inc(reg) => Op::Dec(reg),
+                        Op::Dec(reg) => Op::Inc(reg),
+                        op => op,
+                    };
+
+                    return Step::Patch {
+                        offset: regs[reg as usize],
+                        patch,
+                    };
+                }
+                Op::Out(reg) => return Step::Output(regs[reg as usize]),
+                Op::Fused(fused) => return fused.execute(regs),
+            }
+
+            Step::Next
+        }
+    }
+
+    impl Shaped for Op {
+        fn shape(&self) -> Shape {
+            match *self {
+                Op::Cpy(src, dst) => Shape::Copy { src, dst },
+                Op::Inc(dst) => Shape::Add {
+                    dst,
+                    amount: Operand::Value(1),
+                },
+                Op::Dec(dst) => Shape::Add {
+                    dst,
+                    amount: Operand::Value(-1),
+                },
+                Op::Jnz(cond, offset) => Shape::JumpNonZero {
+                    cond,
+                    offset: Operand::Value(offset),
+                },
+                _ => Shape::Other,
+            }
+        }
+
+        fn fuse(fused: Fused) -> Self {
+            Op::Fused(fused)
+        }
+    }
+
+    const REGISTERS: &str = "abcd";
+
+    fn parse(input: &str) -> Result<Vec<Op>, ParseError> {
+        parse_program(input, |mnemonic, words| {
+            let op = match mnemonic {
+                "cpy" => Op::Cpy(words.operand(REGISTERS)?, words.register(REGISTERS)?),
+                "inc" => Op::Inc(words.register(REGISTERS)?),
+                "dec" => Op::Dec(words.register(REGISTERS)?),
+                "jnz" => Op::Jnz(words.operand(REGISTERS)?, words.value()?),
+                "tgl" => Op::Tgl(words.register(REGISTERS)?),
+                "out" => Op::Out(words.register(REGISTERS)?),
+                _ => return Err(words.unknown(mnemonic)),
+            };
+
+            Ok(op)
+        })
+    }
+
+    // a = 3 * 4 + 5
+    const MULTIPLY: &str = "
+        cpy 3 b
+        cpy 4 d
+        cpy b c
+        inc a
+        dec c
+        jnz c -2
+        dec d
+        jnz d -5
+        cpy 5 c
+        inc a
+        dec c
+        jnz c -2
+    ";
+
+    #[test]
+    fn parsing() {
+        let program = parse("cpy 41 a\n\ninc a\njnz a -1\n").unwrap();
+        assert_eq!(
+            program,
+            [
+                Op::Cpy(Operand::Value(41), 0),
+                Op::Inc(0),
+                Op::Jnz(Operand::Register(0), -1)
+            ]
+        );
+
+        let err = parse("inc e").unwrap_err();
+        assert_eq!(err.message(), "expected one of the registers `abcd`");
+        assert_eq!(
+            parse("mul a b").unwrap_err().message(),
+            "unknown instruction"
+        );
+        assert_eq!(parse("cpy 1").unwrap_err().message(), "expected more words");
+        assert_eq!(parse("inc a b").unwrap_err().message(), "unexpected word");
+    }
+
+    #[test]
+    fn peepholes_agree() {
+        let program = parse(MULTIPLY).unwrap();
+
+        let mut plain = Machine::new(program.clone(), [0; 4]);
+        assert_eq!(plain.run(), Exit::Halted);
+        assert_eq!(plain.state, [17, 3, 0, 0]);
+
+        let mut optimized = Machine::new(program, [0; 4])
+            .with_peephole(mul_loop)
+            .with_peephole(add_loop);
+
+        optimized.pc = 2;
+        assert!(matches!(
+            optimized.current(),
+            Some(Op::Fused(Fused::MulLoop { dst: 0, .. }))
+        ));
+
+        optimized.pc = 9;
+        assert!(matches!(
+            optimized.current(),
+            Some(Op::Fused(Fused::AddLoop { dst: 0, .. }))
+        ));
+
+        optimized.reset([0; 4]);
+        assert_eq!(optimized.run(), Exit::Halted);
+        assert_eq!(optimized.state, plain.state);
+        assert!(optimized.steps < plain.steps / 2);
+    }
+
+    #[test]
+    fn outputs_and_traces() {
+        let program = parse("cpy 2 a\nout a\ndec a\njnz a -2").unwrap();
+        let mut machine = Machine::new(program, [0; 4]);
+
+        assert_eq!(machine.run(), Exit::Output(2));
+        assert_eq!(machine.run(), Exit::Output(1));
+
+        let mut pcs = Vec::new();
+        let exit = machine.run_traced(|pc, _, regs| pcs.push((pc, regs[0])));
+        assert_eq!(exit, Exit::Halted);
+        assert_eq!(pcs, [(2, 1), (3, 0)]);
+        assert_eq!(machine.run(), Exit::Halted);
+    }
+
+    #[test]
+    fn loops_and_patches() {
+        let program = parse("inc a\ninc b\njnz 1 -2\ninc c").unwrap();
+        let mut machine = Machine::new(program, [0; 4]);
+        let fresh = machine.clone();
+
+        assert_eq!(machine.run_until_loop(), Exit::Loop);
+        assert_eq!((machine.pc, machine.state), (0, [1, 1, 0, 0]));
+
+        let repaired = fresh.try_patches(
+            |op| match *op {
+                Op::Jnz(cond, _) => Some(Op::Jnz(cond, 1)),
+                _ => None,
+            },
+            |machine| (machine.run_until_loop() == Exit::Halted).then_some(machine.state),
+        );
+        assert_eq!(repaired, Some([1, 1, 1, 0]));
+    }
+
+    #[test]
+    fn self_modifying() {
+        // toggles `inc a` into `dec a` which has to be fused anew
+        let program = parse("cpy 2 d\ntgl d\ncpy 3 b\ninc a\ndec b\njnz b -2").unwrap();
+
+        let mut plain = Machine::new(program.clone(), [0; 4]);
+        assert_eq!(plain.run(), Exit::Halted);
+        assert_eq!(plain.state[0], -3);
+
+        let mut optimized = Machine::new(program, [0; 4]).with_peephole(add_loop);
+        assert_eq!(optimized.run(), Exit::Halted);
+        assert_eq!(optimized.program()[3], Op::Dec(0));
+        assert_eq!(optimized.state, plain.state);
+
+        let mut rounds = 0;
+        let mut endless = Machine::new(parse("jnz 1 0").unwrap(), [0; 4]);
+        let exit = endless.run_with(|_| {
+            rounds += 1;
+            rounds <= 10
+        });
+        assert_eq!((exit, endless.steps), (Exit::Stopped, 10));
+    }
+}
@@ -0,0 +1,113 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::util::parse::{parse_at, ParseError};
+
+/// Parse each non-empty line of `input` into an instruction.
+///
+/// `parse` receives the line's first word, the mnemonic, and the remaining
+/// words. Words that are left over afterwards are an error.
+pub fn parse_program<'i, I, F>(input: &'i str, mut parse: F) -> Result<Vec<I>, ParseError>
+where
+    F: FnMut(&'i str, &mut Words<'i>) -> Result<I, ParseError>,
+{
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let mut words = Words {
+                input,
+                line,
+                iter: line.split_ascii_whitespace(),
+            };
+
+            let mnemonic = words.word()?;
+            let instruction = parse(mnemonic, &mut words)?;
+
+            match words.iter.next() {
+                Some(word) => Err(ParseError::at(input, word, "unexpected word")),
+                None => Ok(instruction),
+            }
+        })
+        .collect()
+}
+
+/// The words of an instruction's line.
+pub struct Words<'i> {
+    input: &'i str,
+    line: &'i str,
+    iter: std::str::SplitAsciiWhitespace<'i>,
+}
+
+impl<'i> Words<'i> {
+    /// The next word.
+    pub fn word(&mut self) -> Result<&'i str, ParseError> {
+        self.iter
+            .next()
+            .ok_or_else(|| ParseError::at(self.input, self.line, "expected more words"))
+    }
+
+    /// Parse the next word through its [`FromStr`] implementation.
+    pub fn value<T>(&mut self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        parse_at(self.input, self.word()?)
+    }
+
+    /// Parse the next word as register, i.e. a single character of `names`,
+    /// and return its index.
+    pub fn register(&mut self, names: &str) -> Result<u8, ParseError> {
+        let word = self.word()?;
+
+        let mut chars = word.chars();
+
+        let idx = match (chars.next(), chars.next()) {
+            (Some(name), None) => names.chars().position(|c| c == name),
+            _ => None,
+        };
+
+        match idx {
+            Some(idx) => Ok(idx as u8),
+            None => Err(ParseError::at(
+                self.input,
+                word,
+                format_args!("expected one of the registers `{names}`"),
+            )),
+        }
+    }
+
+    /// Parse the next word as register of `names` or as value.
+    pub fn operand(&mut self, names: &str) -> Result<Operand, ParseError> {
+        let word = self.iter.clone().next().unwrap_or_default();
+
+        if word.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            self.register(names).map(Operand::Register)
+        } else {
+            self.value().map(Operand::Value)
+        }
+    }
+
+    /// Error about the line's mnemonic, or any other word, being unknown.
+    pub fn unknown(&self, word: &str) -> ParseError {
+        ParseError::at(self.input, word, "unknown instruction")
+    }
+}
+
+/// Operand of instructions whose registers are `i64`s indexed by `u8`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Operand {
+    Register(u8),
+    Value(i64),
+}
+
+impl Operand {
+    #[inline]
+    pub fn get(self, registers: &[i64]) -> i64 {
+        match self {
+            Self::Register(reg) => registers[reg as usize],
+            Self::Value(value) => value,
+        }
+    }
+}
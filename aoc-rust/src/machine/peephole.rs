@@ -0,0 +1,201 @@
+use super::{Instruction, Operand, Step};
+
+/// Replacement for the instruction at the given index of a program, usually
+/// one that executes it and its successors at once and jumps past them.
+///
+/// The successors stay in place so that jumps into the middle of a replaced
+/// sequence still behave as before.
+pub type Peephole<I> = fn(&[I], usize) -> Option<I>;
+
+/// What an instruction does in terms of the built-in peepholes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Shape {
+    /// `dst = src`
+    Copy { src: Operand, dst: u8 },
+    /// `dst += amount`
+    Add { dst: u8, amount: Operand },
+    /// Jump by `offset` if `cond` is not zero
+    JumpNonZero { cond: Operand, offset: Operand },
+    /// Anything else
+    Other,
+}
+
+/// Instruction sets over `i64` registers that support [`add_loop`] and
+/// [`mul_loop`].
+pub trait Shaped: Instruction {
+    fn shape(&self) -> Shape;
+
+    /// Instruction that executes the fused sequence through
+    /// [`Fused::execute`].
+    fn fuse(fused: Fused) -> Self;
+}
+
+/// A sequence of instructions that is executed at once.
+///
+/// Loops are assumed to terminate, i.e. their counters start out positive.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Fused {
+    /// `dst += factor * counter; counter = 0`
+    AddLoop { dst: u8, factor: i64, counter: u8 },
+    /// `dst += factor * src * counter; scratch = 0; counter = 0`
+    MulLoop {
+        dst: u8,
+        factor: i64,
+        src: Operand,
+        scratch: u8,
+        counter: u8,
+    },
+}
+
+impl Fused {
+    /// Amount of replaced instructions.
+    pub const fn span(self) -> usize {
+        match self {
+            Self::AddLoop { .. } => 3,
+            Self::MulLoop { .. } => 6,
+        }
+    }
+
+    /// Apply the sequence to the registers and jump past it.
+    #[inline]
+    pub fn execute<I: Instruction>(self, registers: &mut [i64]) -> Step<I> {
+        match self {
+            Self::AddLoop {
+                dst,
+                factor,
+                counter,
+            } => {
+                registers[dst as usize] += factor * registers[counter as usize];
+                registers[counter as usize] = 0;
+            }
+            Self::MulLoop {
+                dst,
+                factor,
+                src,
+                scratch,
+                counter,
+            } => {
+                let product = src.get(registers) * registers[counter as usize];
+                registers[dst as usize] += factor * product;
+                registers[scratch as usize] = 0;
+                registers[counter as usize] = 0;
+            }
+        }
+
+        Step::Jump(self.span() as i64)
+    }
+}
+
+/// Fuse loops that add a multiple of a counter to a register:
+///
+/// ```text
+/// inc a
+/// dec b
+/// jnz b -2
+/// ```
+///
+/// The first two instructions may come in either order.
+pub fn add_loop<I: Shaped>(program: &[I], at: usize) -> Option<I> {
+    let [first, second, jump] = program.get(at..at + 3)? else {
+        return None;
+    };
+
+    add_loop_shapes([first.shape(), second.shape(), jump.shape()]).map(I::fuse)
+}
+
+fn add_loop_shapes(shapes: [Shape; 3]) -> Option<Fused> {
+    let [first, second, jump] = shapes;
+
+    let Shape::JumpNonZero {
+        cond: Operand::Register(counter),
+        offset: Operand::Value(-2),
+    } = jump
+    else {
+        return None;
+    };
+
+    let decrement = Shape::Add {
+        dst: counter,
+        amount: Operand::Value(-1),
+    };
+
+    let add = match (first, second) {
+        (add, dec) | (dec, add) if dec == decrement => add,
+        _ => return None,
+    };
+
+    match add {
+        Shape::Add {
+            dst,
+            amount: Operand::Value(factor),
+        } if dst != counter => Some(Fused::AddLoop {
+            dst,
+            factor,
+            counter,
+        }),
+        _ => None,
+    }
+}
+
+/// Fuse an [`add_loop`] that is repeated by an outer counter, multiplying the
+/// outer counter with the inner one's initial value:
+///
+/// ```text
+/// cpy b c
+/// inc a
+/// dec c
+/// jnz c -2
+/// dec d
+/// jnz d -5
+/// ```
+pub fn mul_loop<I: Shaped>(program: &[I], at: usize) -> Option<I> {
+    let [copy, first, second, jump, dec, outer_jump] = program.get(at..at + 6)? else {
+        return None;
+    };
+
+    let Shape::Copy { src, dst: scratch } = copy.shape() else {
+        return None;
+    };
+
+    let Fused::AddLoop {
+        dst,
+        factor,
+        counter: inner,
+    } = add_loop_shapes([first.shape(), second.shape(), jump.shape()])?
+    else {
+        return None;
+    };
+
+    let Shape::Add {
+        dst: counter,
+        amount: Operand::Value(-1),
+    } = dec.shape()
+    else {
+        return None;
+    };
+
+    let expected_jump = Shape::JumpNonZero {
+        cond: Operand::Register(counter),
+        offset: Operand::Value(-5),
+    };
+
+    let src_reg = match src {
+        Operand::Register(reg) => Some(reg),
+        Operand::Value(_) => None,
+    };
+
+    let distinct = inner == scratch
+        && counter != scratch
+        && counter != dst
+        && src_reg.is_none_or(|reg| reg != scratch && reg != counter && reg != dst);
+
+    (distinct && outer_jump.shape() == expected_jump).then(|| {
+        I::fuse(Fused::MulLoop {
+            dst,
+            factor,
+            src,
+            scratch,
+            counter,
+        })
+    })
+}
@@ -0,0 +1,159 @@
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+use super::{Gate, Netlist, Wire};
+
+/// Boolean gates over any amount of inputs.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Logic {
+    And,
+    Or,
+    Xor,
+}
+
+impl Gate for Logic {
+    type Signal = bool;
+
+    fn eval(&self, inputs: &[bool]) -> Option<bool> {
+        let (&first, rest) = inputs.split_first()?;
+
+        let output = match self {
+            Logic::And => rest.iter().fold(first, |acc, &input| acc & input),
+            Logic::Or => rest.iter().fold(first, |acc, &input| acc | input),
+            Logic::Xor => rest.iter().fold(first, |acc, &input| acc ^ input),
+        };
+
+        Some(output)
+    }
+}
+
+impl Display for Logic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Logic::And => f.write_str("AND"),
+            Logic::Or => f.write_str("OR"),
+            Logic::Xor => f.write_str("XOR"),
+        }
+    }
+}
+
+/// Check that the circuit is a ripple-carry adder and return the outputs of
+/// gates that are miswired, sorted by name.
+///
+/// The wires of both operands and the sum are named by their prefix of
+/// `[x, y, z]` followed by the two-digit bit index, e.g. `x00`.
+///
+/// <https://en.wikipedia.org/wiki/Adder_(electronics)>
+///
+/// ```text
+/// x ---\--------\
+///      XOR1-\   AND1----\
+/// y ---/----+---/       OR--- C
+///           |           |
+///          AND2--------/
+///           |
+///           XOR2--- z
+/// C -------/
+/// ```
+///
+/// The least significant bit is a half adder without incoming carry. Gates
+/// are identified by one correct input so the other input being wrong, or
+/// the output not going where it should, marks that wire as miswired.
+pub fn check_adder(netlist: &Netlist<'_, Logic>, [x, y, z]: [&str; 3]) -> eyre::Result<Vec<Wire>> {
+    let wire = |prefix: &str, bit: usize| netlist.get(&format!("{prefix}{bit:02}"));
+    let not_an_adder = |bit| eyre::eyre!("circuit is not a ripple-carry adder at bit {bit}");
+
+    let mut miswired = HashSet::new();
+
+    let (Some(x00), Some(y00)) = (wire(x, 0), wire(y, 0)) else {
+        eyre::bail!("missing wires `{x}00` and `{y}00`");
+    };
+
+    let sum = gate(netlist, Logic::Xor, x00, y00).ok_or_else(|| not_an_adder(0))?;
+    check_output(&mut miswired, sum, wire(z, 0));
+
+    let mut carry = gate(netlist, Logic::And, x00, y00).ok_or_else(|| not_an_adder(0))?;
+    let mut bit = 1;
+
+    while let (Some(x), Some(y)) = (wire(x, bit), wire(y, bit)) {
+        let (sum, next_carry) =
+            full_adder(netlist, [x, y, carry], &mut miswired).ok_or_else(|| not_an_adder(bit))?;
+
+        check_output(&mut miswired, sum, wire(z, bit));
+        carry = next_carry;
+        bit += 1;
+    }
+
+    check_output(&mut miswired, carry, wire(z, bit));
+
+    let mut miswired: Vec<_> = miswired.into_iter().collect();
+    miswired.sort_unstable_by_key(|&wire| netlist.name(wire));
+
+    Ok(miswired)
+}
+
+/// Find the gates of a full adder and return its sum and carry.
+fn full_adder(
+    netlist: &Netlist<'_, Logic>,
+    [x, y, carry]: [Wire; 3],
+    miswired: &mut HashSet<Wire>,
+) -> Option<(Wire, Wire)> {
+    let xor1 = gate(netlist, Logic::Xor, x, y)?;
+    let xor2 = gate_of_either(netlist, Logic::Xor, xor1, carry)?;
+    let and1 = gate(netlist, Logic::And, x, y)?;
+    let and2 = gate_of_either(netlist, Logic::And, xor1, carry)?;
+    let or = gate_of_either(netlist, Logic::Or, and1, and2)?;
+
+    let inputs = [
+        (xor2, xor1),
+        (xor2, carry),
+        (and2, xor1),
+        (and2, carry),
+        (or, and1),
+        (or, and2),
+    ];
+
+    for (out, input) in inputs {
+        if !reads(netlist, out, input) {
+            miswired.insert(input);
+        }
+    }
+
+    Some((xor2, or))
+}
+
+fn check_output(miswired: &mut HashSet<Wire>, out: Wire, expected: Option<Wire>) {
+    if Some(out) != expected {
+        miswired.insert(out);
+        miswired.extend(expected);
+    }
+}
+
+/// Output of a gate that reads both `a` and `b`.
+fn gate(netlist: &Netlist<'_, Logic>, logic: Logic, a: Wire, b: Wire) -> Option<Wire> {
+    netlist
+        .readers(a)
+        .find(|&out| is_logic(netlist, out, logic) && reads(netlist, out, b))
+}
+
+/// Output of a gate that reads `a` or `b`.
+fn gate_of_either(netlist: &Netlist<'_, Logic>, logic: Logic, a: Wire, b: Wire) -> Option<Wire> {
+    netlist
+        .readers(a)
+        .chain(netlist.readers(b))
+        .find(|&out| is_logic(netlist, out, logic))
+}
+
+fn is_logic(netlist: &Netlist<'_, Logic>, wire: Wire, logic: Logic) -> bool {
+    netlist
+        .driver(wire)
+        .is_some_and(|driver| driver.gate == logic)
+}
+
+fn reads(netlist: &Netlist<'_, Logic>, wire: Wire, input: Wire) -> bool {
+    netlist
+        .driver(wire)
+        .is_some_and(|driver| driver.inputs.contains(&input))
+}
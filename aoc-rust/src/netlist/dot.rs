@@ -0,0 +1,106 @@
+use std::fmt::{Display, Formatter, Result as FmtResult, Write};
+
+use super::{Netlist, Wire};
+
+/// Graphviz representation of a [`Netlist`], created through
+/// [`Netlist::dot`].
+///
+/// Each wire is a node labeled with its name and the gate that drives it,
+/// edges point from a gate's inputs to its output.
+pub struct Dot<'n, 'a, G> {
+    netlist: &'n Netlist<'a, G>,
+    highlighted: Vec<bool>,
+}
+
+impl<'n, 'a, G> Dot<'n, 'a, G> {
+    pub(super) fn new(netlist: &'n Netlist<'a, G>) -> Self {
+        Self {
+            netlist,
+            highlighted: vec![false; netlist.len()],
+        }
+    }
+
+    /// Color the given wires red, e.g. those that are miswired.
+    pub fn highlight<I>(mut self, wires: I) -> Self
+    where
+        I: IntoIterator<Item = Wire>,
+    {
+        for wire in wires {
+            self.highlighted[wire.index()] = true;
+        }
+
+        self
+    }
+}
+
+impl<G: Display> Display for Dot<'_, '_, G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let netlist = self.netlist;
+
+        f.write_str("digraph {\n    rankdir=LR;\n")?;
+
+        let mut label = String::new();
+
+        for wire in netlist.wires() {
+            let name = netlist.name(wire);
+            let mut attrs = Vec::new();
+
+            if let Some(driver) = netlist.driver(wire) {
+                label.clear();
+                let _ = write!(label, "{name}\n{}", driver.gate);
+
+                if label.len() > name.len() + 1 {
+                    attrs.push(format!("label={}", quoted(&label)));
+                }
+            }
+
+            if self.highlighted[wire.index()] {
+                attrs.push("color=red, fontcolor=red".to_owned());
+            }
+
+            if attrs.is_empty() {
+                writeln!(f, "    {};", quoted(name))?;
+            } else {
+                writeln!(f, "    {} [{}];", quoted(name), attrs.join(", "))?;
+            }
+        }
+
+        for wire in netlist.wires() {
+            let Some(driver) = netlist.driver(wire) else {
+                continue;
+            };
+
+            for &input in driver.inputs.iter() {
+                let input = netlist.name(input);
+                writeln!(
+                    f,
+                    "    {} -> {};",
+                    quoted(input),
+                    quoted(netlist.name(wire))
+                )?;
+            }
+        }
+
+        f.write_str("}\n")
+    }
+}
+
+/// A DOT string literal, line breaks being escaped as `\n` which Graphviz
+/// renders as centered line break.
+fn quoted(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+
+    quoted
+}
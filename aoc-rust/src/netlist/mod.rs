@@ -0,0 +1,544 @@
+//! Circuits of named wires, each driven by at most one gate.
+//!
+//! A [`Netlist`] only describes how wires are connected. Circuits of pure
+//! gates implement [`Gate`] to be [evaluated](Netlist::evaluate) in
+//! topological order, while stateful ones like flip-flops simulate
+//! themselves on top of [`Netlist::driver`] and [`Netlist::readers`].
+//!
+//! Any netlist whose gates implement `Display` can be exported to Graphviz
+//! through [`Netlist::dot`]:
+//!
+//! ```ignore
+//! std::fs::write("circuit.dot", netlist.dot().highlight(miswired).to_string())?;
+//! ```
+//!
+//! which renders via `dot -Tsvg circuit.dot -o circuit.svg`.
+
+mod adder;
+mod dot;
+
+use std::{
+    collections::{HashMap, VecDeque},
+    error::Error as StdError,
+    fmt::{Display, Formatter, Result as FmtResult},
+    mem,
+};
+
+pub use self::{
+    adder::{check_adder, Logic},
+    dot::Dot,
+};
+
+/// Handle of a wire within its [`Netlist`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Wire(u32);
+
+impl Wire {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A gate's logic over the signals of its inputs.
+pub trait Gate {
+    type Signal: Copy;
+
+    /// The output signal for the signals of the gate's inputs, in the order
+    /// they were connected, or `None` if it has none.
+    fn eval(&self, inputs: &[Self::Signal]) -> Option<Self::Signal>;
+}
+
+/// The gate that drives a wire alongside the wires it reads.
+#[derive(Clone, Debug)]
+pub struct Driver<G> {
+    pub gate: G,
+    pub inputs: Vec<Wire>,
+}
+
+/// Named wires and the gates between them.
+#[derive(Clone, Debug)]
+pub struct Netlist<'a, G> {
+    names: Vec<&'a str>,
+    wires: HashMap<&'a str, Wire>,
+    drivers: Vec<Option<Driver<G>>>,
+    /// For each wire the outputs of the gates that read it
+    readers: Vec<Vec<Wire>>,
+}
+
+impl<'a, G> Netlist<'a, G> {
+    pub fn new() -> Self {
+        Self {
+            names: Vec::new(),
+            wires: HashMap::new(),
+            drivers: Vec::new(),
+            readers: Vec::new(),
+        }
+    }
+
+    /// The wire of the given name, adding it if it doesn't exist yet.
+    pub fn wire(&mut self, name: &'a str) -> Wire {
+        if let Some(&wire) = self.wires.get(name) {
+            return wire;
+        }
+
+        let wire = Wire(self.names.len() as u32);
+        self.names.push(name);
+        self.wires.insert(name, wire);
+        self.drivers.push(None);
+        self.readers.push(Vec::new());
+
+        wire
+    }
+
+    /// The wire of the given name if it exists.
+    pub fn get(&self, name: &str) -> Option<Wire> {
+        self.wires.get(name).copied()
+    }
+
+    pub fn name(&self, wire: Wire) -> &'a str {
+        self.names[wire.index()]
+    }
+
+    /// Amount of wires.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// All wires in the order they were added.
+    pub fn wires(&self) -> impl Iterator<Item = Wire> {
+        (0..self.names.len() as u32).map(Wire)
+    }
+
+    /// Drive `out` through `gate` reading `inputs`, replacing and returning
+    /// its previous driver.
+    pub fn drive(&mut self, out: Wire, gate: G, inputs: Vec<Wire>) -> Option<Driver<G>> {
+        self.replace_driver(out, Some(Driver { gate, inputs }))
+    }
+
+    /// Swap the gates that drive `a` and `b`, e.g. to repair miswired
+    /// outputs.
+    pub fn swap_drivers(&mut self, a: Wire, b: Wire) {
+        let driver_a = self.replace_driver(a, None);
+        let driver_b = self.replace_driver(b, driver_a);
+        self.replace_driver(a, driver_b);
+    }
+
+    fn replace_driver(&mut self, out: Wire, driver: Option<Driver<G>>) -> Option<Driver<G>> {
+        let prev = mem::replace(&mut self.drivers[out.index()], driver);
+
+        for input in prev.iter().flat_map(|prev| prev.inputs.iter()) {
+            let readers = &mut self.readers[input.index()];
+
+            if let Some(idx) = readers.iter().position(|&reader| reader == out) {
+                readers.remove(idx);
+            }
+        }
+
+        let driver = self.drivers[out.index()].as_ref();
+
+        for input in driver.iter().flat_map(|driver| driver.inputs.iter()) {
+            self.readers[input.index()].push(out);
+        }
+
+        prev
+    }
+
+    /// Add `input` to the inputs of the gate that drives `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` has no driver.
+    pub fn connect(&mut self, input: Wire, out: Wire) {
+        let Some(driver) = self.drivers[out.index()].as_mut() else {
+            panic!("wire `{}` has no driver", self.names[out.index()]);
+        };
+
+        driver.inputs.push(input);
+        self.readers[input.index()].push(out);
+    }
+
+    /// The gate that drives `wire` if any.
+    pub fn driver(&self, wire: Wire) -> Option<&Driver<G>> {
+        self.drivers[wire.index()].as_ref()
+    }
+
+    /// Outputs of the gates that read `wire` in the order they were
+    /// connected.
+    pub fn readers(&self, wire: Wire) -> impl Iterator<Item = Wire> + '_ {
+        self.readers[wire.index()].iter().copied()
+    }
+
+    /// All wires such that each comes after the inputs of its driver.
+    pub fn topological_order(&self) -> Result<Vec<Wire>, Cycle> {
+        let mut pending: Vec<_> = self
+            .drivers
+            .iter()
+            .map(|driver| driver.as_ref().map_or(0, |driver| driver.inputs.len()))
+            .collect();
+
+        let mut queue: VecDeque<_> = self
+            .wires()
+            .filter(|wire| pending[wire.index()] == 0)
+            .collect();
+
+        let mut order = Vec::with_capacity(self.len());
+
+        while let Some(wire) = queue.pop_front() {
+            order.push(wire);
+
+            for reader in self.readers(wire) {
+                pending[reader.index()] -= 1;
+
+                if pending[reader.index()] == 0 {
+                    queue.push_back(reader);
+                }
+            }
+        }
+
+        if order.len() == self.len() {
+            return Ok(order);
+        }
+
+        // every wire that's still pending has a pending input so following
+        // them must lead into a cycle
+        let start = self.wires().find(|wire| pending[wire.index()] > 0);
+        let mut path = Vec::from_iter(start);
+
+        let cycle_start = loop {
+            let wire = path[path.len() - 1];
+
+            let input = self.drivers[wire.index()]
+                .iter()
+                .flat_map(|driver| driver.inputs.iter().copied())
+                .find(|input| pending[input.index()] > 0)
+                .expect("pending wire must have a pending input");
+
+            if let Some(idx) = path.iter().position(|&wire| wire == input) {
+                break idx;
+            }
+
+            path.push(input);
+        };
+
+        let mut wires: Vec<_> = path[cycle_start..]
+            .iter()
+            .rev()
+            .map(|&wire| self.name(wire).to_owned())
+            .collect();
+
+        wires.push(wires[0].clone());
+
+        Err(Cycle { wires })
+    }
+
+    /// Graphviz representation of the circuit.
+    pub fn dot(&self) -> Dot<'_, 'a, G> {
+        Dot::new(self)
+    }
+}
+
+impl<G: Gate> Netlist<'_, G> {
+    /// Evaluate every wire in topological order.
+    ///
+    /// Wires without driver take their signal from `inputs`. A wire has no
+    /// signal if any of its gate's inputs has none.
+    pub fn evaluate<I>(&self, inputs: I) -> Result<Signals<G::Signal>, Cycle>
+    where
+        I: IntoIterator<Item = (Wire, G::Signal)>,
+    {
+        let mut signals = vec![None; self.len()];
+
+        for (wire, signal) in inputs {
+            signals[wire.index()] = Some(signal);
+        }
+
+        let mut buf = Vec::new();
+
+        for wire in self.topological_order()? {
+            let Some(driver) = self.driver(wire) else {
+                continue;
+            };
+
+            buf.clear();
+
+            let all_set = driver
+                .inputs
+                .iter()
+                .all(|input| match signals[input.index()] {
+                    Some(signal) => {
+                        buf.push(signal);
+
+                        true
+                    }
+                    None => false,
+                });
+
+            signals[wire.index()] = if all_set {
+                driver.gate.eval(&buf)
+            } else {
+                None
+            };
+        }
+
+        Ok(Signals(signals))
+    }
+}
+
+impl<G> Default for Netlist<'_, G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Signals of all wires of a [`Netlist`] after evaluating it.
+#[derive(Clone, Debug)]
+pub struct Signals<S>(Vec<Option<S>>);
+
+impl<S: Copy> Signals<S> {
+    pub fn get(&self, wire: Wire) -> Option<S> {
+        self.0[wire.index()]
+    }
+}
+
+/// Wires that depend on themselves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// Names of the wires along the cycle, the first being repeated at the
+    /// end
+    pub wires: Vec<String>,
+}
+
+impl Display for Cycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "wires form a cycle: {}", self.wires.join(" -> "))
+    }
+}
+
+impl StdError for Cycle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Copy, Clone, Debug)]
+    enum Op {
+        Value(u16),
+        And,
+        Not,
+    }
+
+    impl Gate for Op {
+        type Signal = u16;
+
+        fn eval(&self, inputs: &[u16]) -> Option<u16> {
+            match (self, inputs) {
+                (Op::Value(value), []) => Some(*value),
+                (Op::And, [a, b]) => Some(a & b),
+                (Op::Not, [a]) => Some(!a),
+                _ => None,
+            }
+        }
+    }
+
+    impl Display for Op {
+        fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+            match self {
+                Op::Value(value) => Display::fmt(value, f),
+                Op::And => f.write_str("AND"),
+                Op::Not => f.write_str("NOT"),
+            }
+        }
+    }
+
+    /// `a AND b -> c`, `NOT c -> d`, with `b` being driven after `d`
+    fn netlist() -> (Netlist<'static, Op>, [Wire; 4]) {
+        let mut netlist = Netlist::new();
+        let [a, b, c, d] = ["a", "b", "c", "d"].map(|name| netlist.wire(name));
+
+        netlist.drive(d, Op::Not, vec![c]);
+        netlist.drive(c, Op::And, vec![a, b]);
+        netlist.drive(b, Op::Value(0b1100), Vec::new());
+
+        (netlist, [a, b, c, d])
+    }
+
+    #[test]
+    fn evaluate() {
+        let (mut netlist, [a, b, c, d]) = netlist();
+
+        let order = netlist.topological_order().unwrap();
+        let pos = |wire| order.iter().position(|&w| w == wire).unwrap();
+        assert!(pos(a) < pos(c) && pos(b) < pos(c) && pos(c) < pos(d));
+
+        let signals = netlist.evaluate([(a, 0b1010)]).unwrap();
+        assert_eq!(signals.get(c), Some(0b1000));
+        assert_eq!(signals.get(d), Some(!0b1000));
+
+        // without a signal for `a` neither `c` nor `d` have one
+        let signals = netlist.evaluate([]).unwrap();
+        assert_eq!(signals.get(b), Some(0b1100));
+        assert_eq!(signals.get(d), None);
+
+        // overriding a driver updates the readers
+        netlist.drive(c, Op::Not, vec![b]);
+        assert_eq!(netlist.readers(a).count(), 0);
+        assert_eq!(netlist.readers(b).collect::<Vec<_>>(), [c]);
+
+        let signals = netlist.evaluate([]).unwrap();
+        assert_eq!(signals.get(d), Some(0b1100));
+    }
+
+    #[test]
+    fn cycles() {
+        let (mut netlist, [a, _, c, d]) = netlist();
+        netlist.connect(d, c);
+        netlist.drive(a, Op::Not, vec![a]);
+
+        let cycle = netlist.topological_order().unwrap_err();
+        assert_eq!(cycle.wires, ["a", "a"]);
+
+        netlist.drive(a, Op::Value(1), Vec::new());
+
+        let cycle = netlist.evaluate([]).unwrap_err();
+        assert_eq!(cycle.wires, ["d", "c", "d"]);
+        assert_eq!(cycle.to_string(), "wires form a cycle: d -> c -> d");
+    }
+
+    #[test]
+    fn dot() {
+        let (netlist, [_, _, c, _]) = netlist();
+
+        let expected = r#"digraph {
+    rankdir=LR;
+    "a";
+    "b" [label="b\n12"];
+    "c" [label="c\nAND", color=red, fontcolor=red];
+    "d" [label="d\nNOT"];
+    "a" -> "c";
+    "b" -> "c";
+    "c" -> "d";
+}
+"#;
+
+        assert_eq!(netlist.dot().highlight([c]).to_string(), expected);
+    }
+
+    #[test]
+    fn adder() {
+        // x + y over two bits with the sum's bits 0 and 1 swapped
+        let gates = [
+            ("x00", Logic::Xor, "y00", "z01"),
+            ("x00", Logic::And, "y00", "c00"),
+            ("x01", Logic::Xor, "y01", "s01"),
+            ("s01", Logic::Xor, "c00", "z00"),
+            ("x01", Logic::And, "y01", "a01"),
+            ("s01", Logic::And, "c00", "b01"),
+            ("a01", Logic::Or, "b01", "z02"),
+        ];
+
+        let mut netlist = Netlist::new();
+
+        for (in1, logic, in2, out) in gates {
+            let inputs = vec![netlist.wire(in1), netlist.wire(in2)];
+            let out = netlist.wire(out);
+            netlist.drive(out, logic, inputs);
+        }
+
+        let miswired = check_adder(&netlist, ["x", "y", "z"]).unwrap();
+        let names: Vec<_> = miswired.iter().map(|&wire| netlist.name(wire)).collect();
+        assert_eq!(names, ["z00", "z01"]);
+
+        let [z00, z01] = [miswired[0], miswired[1]];
+        netlist.swap_drivers(z00, z01);
+
+        assert!(check_adder(&netlist, ["x", "y", "z"]).unwrap().is_empty());
+
+        for (x, y) in (0..4).flat_map(|x| (0..4).map(move |y| (x, y))) {
+            let bits = [
+                ("x00", x & 1),
+                ("x01", x >> 1),
+                ("y00", y & 1),
+                ("y01", y >> 1),
+            ];
+            let inputs = bits.map(|(name, bit)| (netlist.get(name).unwrap(), bit == 1));
+            let signals = netlist.evaluate(inputs).unwrap();
+
+            let sum = ["z00", "z01", "z02"]
+                .iter()
+                .enumerate()
+                .map(|(i, name)| (signals.get(netlist.get(name).unwrap()).unwrap() as u8) << i)
+                .sum::<u8>();
+
+            assert_eq!(sum, x + y);
+        }
+
+        netlist.drive(z00, Logic::And, Vec::new());
+        assert!(check_adder(&netlist, ["x", "y", "z"]).is_err());
+    }
+
+    #[test]
+    fn adder_internal_swaps() {
+        // x + y over four bits with the XOR and AND of bit 1's operands
+        // swapped, and bit 2's sum swapped with its carry
+        let gates = [
+            ("x00", Logic::Xor, "y00", "z00"),
+            ("x00", Logic::And, "y00", "c00"),
+            ("x01", Logic::Xor, "y01", "a01"),
+            ("s01", Logic::Xor, "c00", "z01"),
+            ("x01", Logic::And, "y01", "s01"),
+            ("s01", Logic::And, "c00", "b01"),
+            ("a01", Logic::Or, "b01", "c01"),
+            ("x02", Logic::Xor, "y02", "s02"),
+            ("s02", Logic::Xor, "c01", "c02"),
+            ("x02", Logic::And, "y02", "a02"),
+            ("s02", Logic::And, "c01", "b02"),
+            ("a02", Logic::Or, "b02", "z02"),
+            ("x03", Logic::Xor, "y03", "s03"),
+            ("s03", Logic::Xor, "c02", "z03"),
+            ("x03", Logic::And, "y03", "a03"),
+            ("s03", Logic::And, "c02", "b03"),
+            ("a03", Logic::Or, "b03", "z04"),
+        ];
+
+        let mut netlist = Netlist::new();
+
+        for (in1, logic, in2, out) in gates {
+            let inputs = vec![netlist.wire(in1), netlist.wire(in2)];
+            let out = netlist.wire(out);
+            netlist.drive(out, logic, inputs);
+        }
+
+        let miswired = check_adder(&netlist, ["x", "y", "z"]).unwrap();
+        let names: Vec<_> = miswired.iter().map(|&wire| netlist.name(wire)).collect();
+        assert_eq!(names, ["a01", "c02", "s01", "z02"]);
+
+        let [a01, c02, s01, z02] = [miswired[0], miswired[1], miswired[2], miswired[3]];
+        netlist.swap_drivers(a01, s01);
+        netlist.swap_drivers(c02, z02);
+
+        assert!(check_adder(&netlist, ["x", "y", "z"]).unwrap().is_empty());
+
+        for (x, y) in (0..16).flat_map(|x| (0..16).map(move |y| (x, y))) {
+            let inputs = (0..4).flat_map(|i| {
+                [("x", x), ("y", y)].map(|(prefix, n)| {
+                    let wire = netlist.get(&format!("{prefix}{i:02}")).unwrap();
+                    (wire, (n >> i) & 1 == 1)
+                })
+            });
+            let signals = netlist.evaluate(inputs).unwrap();
+
+            let sum = (0..5)
+                .map(|i| {
+                    let wire = netlist.get(&format!("z{i:02}")).unwrap();
+                    (signals.get(wire).unwrap() as u8) << i
+                })
+                .sum::<u8>();
+
+            assert_eq!(sum, x + y);
+        }
+    }
+}
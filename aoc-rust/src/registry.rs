@@ -0,0 +1,612 @@
+//! Days registering themselves so that runners can find them.
+//!
+//! Each day's module calls [`register_day!`](crate::register_day) and each
+//! year's crate lists its days through
+//! [`register_year!`](crate::register_year):
+//!
+//! ```ignore
+//! // src/day01.rs
+//! aoc_rust::register_day! {
+//!     day: 1,
+//!     title: "Not Quite Lisp",
+//! }
+//!
+//! pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
+//!     ...
+//! }
+//!
+//! // src/lib.rs
+//! pub mod day01;
+//!
+//! aoc_rust::register_year!(2015: day01);
+//! ```
+//!
+//! Days whose puzzle bakes values into the description rather than the input,
+//! e.g. a target row that differs between the example and the real input,
+//! define them through [`params!`](crate::params), register the type as
+//! `params`, and provide a `run_with_params` function next to `run`:
+//!
+//! ```ignore
+//! aoc_rust::params! {
+//!     pub struct Params {
+//!         /// Row whose covered positions are counted
+//!         row: i32 = 2_000_000,
+//!     }
+//! }
+//!
+//! aoc_rust::register_day! {
+//!     day: 15,
+//!     title: "Beacon Exclusion Zone",
+//!     params: Params,
+//! }
+//!
+//! pub fn run(input: &str) -> eyre::Result<aoc_rust::Solution> {
+//!     run_with_params(input, &Params::default())
+//! }
+//!
+//! pub fn run_with_params(input: &str, params: &Params) -> eyre::Result<aoc_rust::Solution> {
+//!     let row = params.row;
+//!     ...
+//! }
+//! ```
+//!
+//! Runners only deal with text: they collect [`Overrides`] and
+//! [`Day::run_with`] parses them into the day's parameters.
+
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+use crate::Solution;
+
+/// Signature of a day's `run` function.
+pub type RunFn = fn(&str) -> eyre::Result<Solution>;
+
+/// Signature of a day's `run_with_params` function.
+pub type RunWithParamsFn<P> = fn(&str, &P) -> eyre::Result<Solution>;
+
+/// A solved day and its metadata.
+#[derive(Copy, Clone)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    /// Whether the puzzle input is required or the day has its input baked in
+    pub reads_input: bool,
+    /// The default implementation
+    pub run: RunFn,
+    /// Alternative implementations next to the default one
+    pub variants: &'static [Variant],
+    /// Parameters of the puzzle, only set if the day has any
+    pub params: Option<ParamsSpec>,
+}
+
+impl Day {
+    /// The parameters' names and their values for the real input.
+    pub fn default_params(&self) -> Vec<(&'static str, String)> {
+        let Some(spec) = self.params else {
+            return Vec::new();
+        };
+
+        spec.names.iter().copied().zip((spec.defaults)()).collect()
+    }
+
+    /// Run the default implementation with the given parameters overridden.
+    pub fn run_with(&self, input: &str, overrides: &Overrides) -> eyre::Result<Solution> {
+        match self.params {
+            Some(spec) => (spec.run)(input, overrides),
+            None if overrides.is_empty() => (self.run)(input),
+            None => eyre::bail!("the day has no parameters"),
+        }
+    }
+
+    /// The implementation of the given name or the default one if `name` is
+    /// `None`.
+    pub fn run_fn(&self, name: Option<&str>) -> Option<RunFn> {
+        let Some(name) = name else {
+            return Some(self.run);
+        };
+
+        self.variants
+            .iter()
+            .find(|variant| variant.name == name)
+            .map(|variant| variant.run)
+    }
+
+    /// The default implementation followed by all variants, the former being
+    /// named `None`.
+    pub fn implementations(&self) -> impl Iterator<Item = (Option<&'static str>, RunFn)> + '_ {
+        let variants = self
+            .variants
+            .iter()
+            .map(|variant| (Some(variant.name), variant.run));
+
+        [(None, self.run)].into_iter().chain(variants)
+    }
+
+    /// Run the default implementation and all variants on `input` and make
+    /// sure that they agree on the solution.
+    pub fn cross_check(&self, input: &str) -> eyre::Result<Solution> {
+        self.cross_check_with(|_, run| run(input))
+    }
+
+    /// Same as [`Day::cross_check`] but runs each implementation through
+    /// `run` e.g. to time it.
+    pub fn cross_check_with<F>(&self, mut run: F) -> eyre::Result<Solution>
+    where
+        F: FnMut(Option<&'static str>, RunFn) -> eyre::Result<Solution>,
+    {
+        let expected = run(None, self.run)?;
+
+        for variant in self.variants {
+            let solution = run(Some(variant.name), variant.run)?;
+            let parts = [
+                (1, &expected.part1, &solution.part1),
+                (2, &expected.part2, &solution.part2),
+            ];
+
+            for (part, expected, actual) in parts {
+                // variants may use different types for the same answer
+                if *expected != *actual.to_string() {
+                    eyre::bail!(
+                        "variant `{}` disagrees on part {part}: expected `{expected}`, got `{actual}`",
+                        variant.name,
+                    );
+                }
+            }
+        }
+
+        Ok(expected)
+    }
+}
+
+impl Debug for Day {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Day")
+            .field("year", &self.year)
+            .field("day", &self.day)
+            .field("title", &self.title)
+            .field("reads_input", &self.reads_input)
+            .field("variants", &self.variants)
+            .field("params", &self.params)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An alternative implementation of a day.
+#[derive(Copy, Clone)]
+pub struct Variant {
+    pub name: &'static str,
+    pub run: RunFn,
+}
+
+impl Debug for Variant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Debug::fmt(self.name, f)
+    }
+}
+
+/// Typed parameters of a puzzle, e.g. a grid size that differs between the
+/// example and the real input. Usually defined through
+/// [`params!`](crate::params).
+///
+/// The [`Default`] values are those of the real input.
+pub trait PuzzleParams: Default {
+    /// Names of the parameters in declaration order
+    const NAMES: &'static [&'static str];
+
+    /// Override a parameter by parsing its value.
+    fn set(&mut self, name: &str, value: &str) -> eyre::Result<()>;
+
+    /// The parameters' values in the order of [`PuzzleParams::NAMES`].
+    fn values(&self) -> Vec<String>;
+}
+
+/// A day's [`PuzzleParams`] for runners that only deal with text.
+#[derive(Copy, Clone)]
+pub struct ParamsSpec {
+    pub names: &'static [&'static str],
+    /// The values for the real input
+    pub defaults: fn() -> Vec<String>,
+    /// The default implementation with overridden parameters
+    pub run: fn(&str, &Overrides) -> eyre::Result<Solution>,
+}
+
+impl ParamsSpec {
+    /// Erase the parameter type of a day's `run_with_params`, used by
+    /// [`register_day!`](crate::register_day).
+    #[doc(hidden)]
+    pub fn run_overridden<P: PuzzleParams>(
+        input: &str,
+        overrides: &Overrides,
+        run: RunWithParamsFn<P>,
+    ) -> eyre::Result<Solution> {
+        let mut params = P::default();
+        overrides.apply(&mut params)?;
+
+        run(input, &params)
+    }
+}
+
+impl Debug for ParamsSpec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Debug::fmt(self.names, f)
+    }
+}
+
+/// Textual `name=value` overrides of a day's parameters as given by the user.
+#[derive(Clone, Debug, Default)]
+pub struct Overrides {
+    pairs: Vec<(String, String)>,
+}
+
+impl Overrides {
+    /// Override the value of a parameter.
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.pairs.push((name.to_owned(), value.trim().to_owned()));
+    }
+
+    /// Override values through `name=value` pairs that are separated by
+    /// commas or whitespace.
+    pub fn set_all(&mut self, overrides: &str) -> eyre::Result<()> {
+        let pairs = overrides
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|pair| !pair.is_empty());
+
+        for pair in pairs {
+            let Some((name, value)) = pair.split_once('=') else {
+                eyre::bail!("expected `name=value`, got `{pair}`");
+            };
+
+            self.set(name, value);
+        }
+
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// The overridden names and values in the order they were set.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.pairs
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Parse the overrides into `params`.
+    pub fn apply<P: PuzzleParams>(&self, params: &mut P) -> eyre::Result<()> {
+        for (name, value) in self.iter() {
+            if !P::NAMES.contains(&name) {
+                eyre::bail!(
+                    "unknown parameter `{name}`, expected one of: {}",
+                    P::NAMES.join(", ")
+                );
+            }
+
+            params.set(name, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Define a struct of [`PuzzleParams`] whose fields default to the given
+/// values. Fields are parsed through `FromStr` and shown through `Display`.
+///
+/// ```
+/// aoc_rust::params! {
+///     pub struct Params {
+///         /// Size of the grid
+///         size: u32 = 71,
+///     }
+/// }
+///
+/// assert_eq!(Params::default().size, 71);
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        $( #[$meta:meta] )*
+        $vis:vis struct $name:ident {
+            $(
+                $( #[$field_meta:meta] )*
+                $field:ident: $ty:ty = $default:expr
+            ),+ $(,)?
+        }
+    ) => {
+        $( #[$meta] )*
+        #[derive(Clone, Debug, PartialEq)]
+        $vis struct $name {
+            $(
+                $( #[$field_meta] )*
+                pub $field: $ty,
+            )+
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self {
+                    $( $field: $default, )+
+                }
+            }
+        }
+
+        impl $crate::registry::PuzzleParams for $name {
+            const NAMES: &'static [&'static str] = &[ $( stringify!($field) ),+ ];
+
+            fn set(&mut self, name: &str, value: &str) -> eyre::Result<()> {
+                use eyre::WrapErr as _;
+
+                match name {
+                    $(
+                        stringify!($field) => {
+                            self.$field = value.parse().wrap_err_with(|| {
+                                format!("invalid value `{value}` for parameter `{name}`")
+                            })?;
+                        }
+                    )+
+                    _ => eyre::bail!("unknown parameter `{name}`"),
+                }
+
+                Ok(())
+            }
+
+            fn values(&self) -> Vec<String> {
+                vec![ $( self.$field.to_string() ),+ ]
+            }
+        }
+    };
+}
+
+/// Register the day of the current module as `pub static DAY`.
+///
+/// The module must contain a `run` function of type [`RunFn`] and its parent
+/// i.e. the crate root must have a `YEAR` constant as defined by
+/// [`register_year!`](crate::register_year) unless the day specifies its
+/// `year`, e.g. because it's solved in another year's crate. `reads_input`
+/// defaults to `true`, `variants` and `params` to none. Variants may carry
+/// attributes such as `#[cfg(feature = "...")]`. Days with `params`
+/// must also contain a `run_with_params` function of type
+/// [`RunWithParamsFn`] for their [`PuzzleParams`] type.
+#[macro_export]
+macro_rules! register_day {
+    (
+        $( year: $year:literal, )?
+        day: $day:literal,
+        title: $title:literal
+        $( , reads_input: $reads_input:literal )?
+        $( , variants: [ $( $( #[$attr:meta] )* $name:literal => $variant:path ),* $(,)? ] )?
+        $( , params: $params:ty )?
+        $(,)?
+    ) => {
+        /// Registration of this day, see `aoc_rust::registry`.
+        pub static DAY: $crate::registry::Day = $crate::registry::Day {
+            year: $crate::register_day!(@year $( $year )?),
+            day: $day,
+            title: $title,
+            reads_input: $crate::register_day!(@reads_input $( $reads_input )?),
+            run,
+            variants: &[ $( $(
+                $( #[$attr] )*
+                $crate::registry::Variant { name: $name, run: $variant }
+            ),* )? ],
+            params: $crate::register_day!(@params $( $params )?),
+        };
+    };
+    (@params) => {
+        None
+    };
+    (@params $params:ty) => {
+        Some($crate::registry::ParamsSpec {
+            names: <$params as $crate::registry::PuzzleParams>::NAMES,
+            defaults: || {
+                let params = <$params as ::std::default::Default>::default();
+                $crate::registry::PuzzleParams::values(&params)
+            },
+            run: |input, overrides| {
+                $crate::registry::ParamsSpec::run_overridden::<$params>(
+                    input,
+                    overrides,
+                    run_with_params,
+                )
+            },
+        })
+    };
+    (@year) => {
+        super::YEAR
+    };
+    (@year $year:literal) => {
+        $year
+    };
+    (@reads_input) => {
+        true
+    };
+    (@reads_input $reads_input:literal) => {
+        $reads_input
+    };
+}
+
+/// Define the crate's `YEAR` and collect its days' registrations in
+/// `pub static DAYS`.
+///
+/// Each day must call [`register_day!`](crate::register_day). Days of other
+/// years that are solved in this crate are listed too, `aoc-registry` sorts
+/// them by year and day.
+#[macro_export]
+macro_rules! register_year {
+    ( $year:literal: $( $day:ident ),* $(,)? ) => {
+        pub const YEAR: u16 = $year;
+
+        /// Registrations of all days solved in this crate, see
+        /// `aoc_rust::registry`.
+        pub static DAYS: &[&$crate::registry::Day] = &[ $( &$day::DAY ),* ];
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YEAR: u16 = 2000;
+
+    mod day01 {
+        use crate::Solution;
+
+        crate::register_day! {
+            day: 1,
+            title: "First",
+        }
+
+        pub fn run(_: &str) -> eyre::Result<Solution> {
+            Ok(Solution::new().part1(1).part2(2))
+        }
+    }
+
+    mod day02 {
+        use crate::Solution;
+
+        crate::register_day! {
+            day: 2,
+            title: "Second",
+            reads_input: false,
+            variants: [
+                "fast" => run_fast,
+                #[cfg(any())]
+                "disabled" => run_fast,
+            ],
+        }
+
+        pub fn run(_: &str) -> eyre::Result<Solution> {
+            Ok(Solution::new().part1(3).part2(4))
+        }
+
+        fn run_fast(_: &str) -> eyre::Result<Solution> {
+            Ok(Solution::new().part1(3).part2(4))
+        }
+    }
+
+    mod day03 {
+        use crate::Solution;
+
+        crate::params! {
+            pub struct Params {
+                size: u32 = 10,
+                name: String = "real".to_owned(),
+            }
+        }
+
+        crate::register_day! {
+            day: 3,
+            title: "Third",
+            params: Params,
+        }
+
+        pub fn run(input: &str) -> eyre::Result<Solution> {
+            run_with_params(input, &Params::default())
+        }
+
+        pub fn run_with_params(_: &str, params: &Params) -> eyre::Result<Solution> {
+            Ok(Solution::new()
+                .part1(params.size * 2)
+                .part2(params.name.clone()))
+        }
+    }
+
+    mod day01_1999 {
+        use crate::Solution;
+
+        crate::register_day! {
+            year: 1999,
+            day: 1,
+            title: "Earlier",
+        }
+
+        pub fn run(_: &str) -> eyre::Result<Solution> {
+            Ok(Solution::new().part1(5).part2(6))
+        }
+    }
+
+    static DAYS: &[&Day] = &[&day01::DAY, &day02::DAY];
+
+    #[test]
+    fn registered() {
+        let [first, second] = DAYS else {
+            panic!("expected two days");
+        };
+
+        assert_eq!((first.year, first.day, first.title), (2000, 1, "First"));
+        assert!(first.reads_input);
+        assert!(first.variants.is_empty());
+
+        let earlier = &day01_1999::DAY;
+        assert_eq!((earlier.year, earlier.day), (1999, 1));
+
+        assert_eq!((second.year, second.day, second.title), (2000, 2, "Second"));
+        assert!(!second.reads_input);
+        assert!(second.run_fn(Some("fast")).is_some());
+        assert!(second.run_fn(Some("slow")).is_none());
+
+        let solution = (second.run_fn(None).unwrap())("").unwrap();
+        assert_eq!(solution.part2.to_string(), "4");
+
+        let names: Vec<_> = second.implementations().map(|(name, _)| name).collect();
+        assert_eq!(names, [None, Some("fast")]);
+    }
+
+    #[test]
+    fn cross_check() {
+        assert!(day02::DAY.cross_check("").is_ok());
+
+        let mut disagreeing = day02::DAY;
+        disagreeing.run = day01::run;
+        let err = disagreeing.cross_check("").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "variant `fast` disagrees on part 1: expected `1`, got `3`"
+        );
+    }
+
+    #[test]
+    fn params() {
+        assert!(day01::DAY.params.is_none());
+        assert!(day01::DAY.default_params().is_empty());
+
+        let mut overrides = Overrides::default();
+        assert!(day01::DAY.run_with("", &overrides).is_ok());
+
+        let day = &day03::DAY;
+        let solution = (day.run)("").unwrap();
+        assert_eq!(solution.part1.to_string(), "20");
+        assert_eq!(solution.part2.to_string(), "real");
+
+        let defaults = day.default_params();
+        assert_eq!(
+            defaults,
+            [("size", "10".to_owned()), ("name", "real".to_owned())]
+        );
+
+        overrides.set_all("size=3, name=example").unwrap();
+        let solution = day.run_with("", &overrides).unwrap();
+        assert_eq!(solution.part1.to_string(), "6");
+        assert_eq!(solution.part2.to_string(), "example");
+
+        let values: Vec<_> = overrides.iter().collect();
+        assert_eq!(values, [("size", "3"), ("name", "example")]);
+        assert!(overrides.set_all("size").is_err());
+
+        let err = day01::DAY.run_with("", &overrides).unwrap_err();
+        assert_eq!(err.to_string(), "the day has no parameters");
+
+        let mut unknown = Overrides::default();
+        unknown.set("width", "3");
+        let err = day.run_with("", &unknown).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown parameter `width`, expected one of: size, name"
+        );
+
+        overrides.set("size", "big");
+        let err = day.run_with("", &overrides).unwrap_err();
+        assert_eq!(err.to_string(), "invalid value `big` for parameter `size`");
+    }
+}
@@ -0,0 +1,93 @@
+use std::{collections::VecDeque, hash::Hash};
+
+use super::{reconstruct, HashVisited, NodeIndex, Path, Record, Visited, NO_PARENT};
+
+/// Breadth-first search from `start` until a state satisfies `goal`.
+pub fn bfs<N, FN, IN, FG>(start: N, successors: FN, goal: FG) -> Option<Path<N, usize>>
+where
+    N: Clone + Hash + Eq,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+{
+    bfs_with(start, successors, goal, HashVisited::hashed())
+}
+
+/// Same as [`bfs`] but tracks states through `visited`.
+pub fn bfs_with<N, K, FN, IN, FG, FK, I>(
+    start: N,
+    mut successors: FN,
+    mut goal: FG,
+    mut visited: Visited<FK, I>,
+) -> Option<Path<N, usize>>
+where
+    N: Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+    FK: FnMut(&N) -> K,
+    I: NodeIndex<K>,
+{
+    let (key, _) = visited.get(&start);
+    visited.insert(key, 0);
+
+    let mut records = vec![Record {
+        node: start,
+        parent: NO_PARENT,
+        cost: 0,
+    }];
+
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(idx) = queue.pop_front() {
+        let node = records[idx].node.clone();
+
+        if goal(&node) {
+            return Some(reconstruct(&records, idx));
+        }
+
+        let cost = records[idx].cost + 1;
+
+        for next in successors(&node) {
+            let (key, None) = visited.get(&next) else {
+                continue;
+            };
+
+            visited.insert(key, records.len());
+            queue.push_back(records.len());
+
+            records.push(Record {
+                node: next,
+                parent: idx,
+                cost,
+            });
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortest_path() {
+        // 0 -> 1 -> 2 -> 5 and 0 -> 3 -> 5
+        let edges = |n: &u8| match n {
+            0 => vec![1, 3],
+            1 => vec![2],
+            2 | 3 => vec![5],
+            _ => vec![],
+        };
+
+        let path = bfs(0, edges, |&n| n == 5).unwrap();
+        assert_eq!(path.cost, 2);
+        assert_eq!(path.nodes, [0, 3, 5]);
+
+        assert!(bfs(0, edges, |&n| n == 4).is_none());
+
+        let dense = Visited::dense(|&n: &u8| n as usize, 6);
+        assert_eq!(bfs_with(0, edges, |&n| n == 5, dense), Some(path));
+    }
+}
@@ -0,0 +1,213 @@
+use std::{collections::HashMap, hash::Hash};
+
+use super::{Path, NO_PARENT};
+
+/// Breadth-first search from both `start` and `goal` until they meet.
+///
+/// `predecessors` must yield all states that lead to the given one which,
+/// for undirected graphs, is the same as `successors`.
+pub fn bidirectional_bfs<N, FS, IS, FP, IP>(
+    start: N,
+    goal: N,
+    mut successors: FS,
+    mut predecessors: FP,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Hash + Eq,
+    FS: FnMut(&N) -> IS,
+    IS: IntoIterator<Item = N>,
+    FP: FnMut(&N) -> IP,
+    IP: IntoIterator<Item = N>,
+{
+    if start == goal {
+        return Some(Path {
+            cost: 0,
+            nodes: vec![start],
+        });
+    }
+
+    let mut forward = Side::new(start);
+    let mut backward = Side::new(goal);
+
+    loop {
+        let meeting = if forward.frontier.len() <= backward.frontier.len() {
+            forward.expand(&mut successors, &backward)
+        } else {
+            backward
+                .expand(&mut predecessors, &forward)
+                .map(|(b, f)| (f, b))
+        };
+
+        if let Some((f, b)) = meeting {
+            let mut nodes = forward.chain(f);
+            nodes.reverse();
+            let rest = backward.chain(b);
+            let cost = nodes.len() + rest.len() - 2;
+            nodes.extend(rest.into_iter().skip(1));
+
+            return Some(Path { cost, nodes });
+        }
+
+        if forward.frontier.is_empty() || backward.frontier.is_empty() {
+            return None;
+        }
+    }
+}
+
+struct Side<N> {
+    /// Each state alongside the index of its parent
+    records: Vec<(N, usize)>,
+    indices: HashMap<N, usize>,
+    frontier: Vec<usize>,
+}
+
+impl<N: Clone + Hash + Eq> Side<N> {
+    fn new(start: N) -> Self {
+        Self {
+            indices: HashMap::from([(start.clone(), 0)]),
+            records: vec![(start, NO_PARENT)],
+            frontier: vec![0],
+        }
+    }
+
+    /// Expand the whole frontier by one step. If the other side was reached,
+    /// returns the record indices of the cheapest meeting on both sides.
+    fn expand<F, I>(&mut self, neighbors: &mut F, other: &Self) -> Option<(usize, usize)>
+    where
+        F: FnMut(&N) -> I,
+        I: IntoIterator<Item = N>,
+    {
+        let mut next_frontier = Vec::new();
+        let mut meeting: Option<(usize, usize, usize)> = None;
+
+        for &idx in self.frontier.iter() {
+            let node = self.records[idx].0.clone();
+
+            for next in neighbors(&node) {
+                if self.indices.contains_key(&next) {
+                    continue;
+                }
+
+                let next_idx = self.records.len();
+
+                if let Some(&other_idx) = other.indices.get(&next) {
+                    let len = other.chain_len(other_idx);
+
+                    if meeting.is_none_or(|(best, ..)| len < best) {
+                        meeting = Some((len, next_idx, other_idx));
+                    }
+                }
+
+                self.indices.insert(next.clone(), next_idx);
+                self.records.push((next, idx));
+                next_frontier.push(next_idx);
+            }
+        }
+
+        self.frontier = next_frontier;
+
+        meeting.map(|(_, this, other)| (this, other))
+    }
+
+    fn chain_len(&self, mut idx: usize) -> usize {
+        let mut len = 0;
+
+        while idx != NO_PARENT {
+            len += 1;
+            idx = self.records[idx].1;
+        }
+
+        len
+    }
+
+    /// States from the record at `idx` back to the side's origin.
+    fn chain(&self, mut idx: usize) -> Vec<N> {
+        let mut nodes = Vec::new();
+
+        while idx != NO_PARENT {
+            nodes.push(self.records[idx].0.clone());
+            idx = self.records[idx].1;
+        }
+
+        nodes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::bfs;
+
+    #[test]
+    fn meet_in_the_middle() {
+        let neighbors = |&n: &i32| {
+            [n - 1, n + 1, n * 2]
+                .into_iter()
+                .filter(|n| (0..100).contains(n))
+        };
+
+        let path = bidirectional_bfs(3, 50, neighbors, |&n: &i32| {
+            let mut prev = vec![n - 1, n + 1];
+
+            if n % 2 == 0 {
+                prev.push(n / 2);
+            }
+
+            prev.into_iter().filter(|n| (0..100).contains(n))
+        })
+        .unwrap();
+
+        // 3 -> 6 -> 12 -> 24 -> 25 -> 50
+        assert_eq!(path.cost, 5);
+        assert_eq!(path.nodes.first(), Some(&3));
+        assert_eq!(path.nodes.last(), Some(&50));
+        assert!(path
+            .nodes
+            .windows(2)
+            .all(|w| neighbors(&w[0]).any(|n| n == w[1])));
+
+        let none = bidirectional_bfs(3, 200, neighbors, neighbors);
+        assert_eq!(none, None);
+        assert_eq!(
+            bidirectional_bfs(7, 7, neighbors, neighbors).unwrap().cost,
+            0
+        );
+    }
+
+    #[test]
+    fn same_cost_as_bfs() {
+        let open = |(x, y): (i32, i32)| {
+            (0..20).contains(&x)
+                && (0..20).contains(&y)
+                && (x * x + 3 * x + 2 * x * y + y + y * y + 10).count_ones() % 2 == 0
+        };
+
+        let neighbors = |&(x, y): &(i32, i32)| {
+            [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+                .into_iter()
+                .filter(|&pos| open(pos))
+        };
+
+        let goals = (0..20).flat_map(|x| (0..20).map(move |y| (x, y)));
+        let mut unreachable = 0;
+
+        for goal in goals.filter(|&goal| open(goal)) {
+            let expected = bfs((1, 1), neighbors, |&pos| pos == goal).map(|path| path.cost);
+            let path = bidirectional_bfs((1, 1), goal, neighbors, neighbors);
+            unreachable += usize::from(expected.is_none());
+
+            assert_eq!(path.as_ref().map(|path| path.cost), expected, "{goal:?}");
+
+            if let Some(path) = path {
+                assert_eq!(path.nodes.len(), path.cost + 1);
+                assert!(path
+                    .nodes
+                    .windows(2)
+                    .all(|w| neighbors(&w[0]).any(|n| n == w[1])));
+            }
+        }
+
+        // walled in goals are covered as well
+        assert!(unreachable > 0);
+    }
+}
@@ -0,0 +1,387 @@
+use std::{cmp::Ordering, collections::BinaryHeap, hash::Hash, ops::Add};
+
+use num_traits::Zero;
+
+use super::{reconstruct, HashVisited, NodeIndex, Path, Record, Visited, NO_PARENT};
+
+/// Cheapest path from `start` to a state that satisfies `goal`.
+///
+/// Successors are yielded alongside the cost to reach them.
+pub fn dijkstra<N, C, FN, IN, FG>(start: N, successors: FN, goal: FG) -> Option<Path<N, C>>
+where
+    N: Clone + Hash + Eq,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool,
+{
+    dijkstra_with(start, successors, goal, HashVisited::hashed())
+}
+
+/// Same as [`dijkstra`] but tracks states through `visited`.
+pub fn dijkstra_with<N, C, K, FN, IN, FG, FK, I>(
+    start: N,
+    successors: FN,
+    goal: FG,
+    visited: Visited<FK, I>,
+) -> Option<Path<N, C>>
+where
+    N: Clone,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool,
+    FK: FnMut(&N) -> K,
+    I: NodeIndex<K>,
+{
+    astar_with(start, successors, |_| C::zero(), goal, visited)
+}
+
+/// Same as [`dijkstra`] but guided by a `heuristic` that must never
+/// overestimate the remaining cost to a goal.
+pub fn astar<N, C, FN, IN, FH, FG>(
+    start: N,
+    successors: FN,
+    heuristic: FH,
+    goal: FG,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Hash + Eq,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FG: FnMut(&N) -> bool,
+{
+    astar_with(start, successors, heuristic, goal, HashVisited::hashed())
+}
+
+/// Same as [`astar`] but tracks states through `visited`.
+pub fn astar_with<N, C, K, FN, IN, FH, FG, FK, I>(
+    start: N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut goal: FG,
+    mut visited: Visited<FK, I>,
+) -> Option<Path<N, C>>
+where
+    N: Clone,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FG: FnMut(&N) -> bool,
+    FK: FnMut(&N) -> K,
+    I: NodeIndex<K>,
+{
+    let (key, _) = visited.get(&start);
+    visited.insert(key, 0);
+
+    let mut heap = BinaryHeap::from([Entry {
+        priority: heuristic(&start),
+        cost: C::zero(),
+        idx: 0,
+    }]);
+
+    let mut records = vec![Record {
+        node: start,
+        parent: NO_PARENT,
+        cost: C::zero(),
+    }];
+
+    while let Some(Entry { cost, idx, .. }) = heap.pop() {
+        // skip outdated entries
+        if cost > records[idx].cost {
+            continue;
+        }
+
+        let node = records[idx].node.clone();
+
+        if goal(&node) {
+            return Some(reconstruct(&records, idx));
+        }
+
+        for (next, weight) in successors(&node) {
+            let next_cost = cost + weight;
+
+            let next_idx = match visited.get(&next) {
+                (_, Some(next_idx)) if records[next_idx].cost <= next_cost => continue,
+                (_, Some(next_idx)) => {
+                    records[next_idx] = Record {
+                        node: next,
+                        parent: idx,
+                        cost: next_cost,
+                    };
+
+                    next_idx
+                }
+                (key, None) => {
+                    visited.insert(key, records.len());
+
+                    records.push(Record {
+                        node: next,
+                        parent: idx,
+                        cost: next_cost,
+                    });
+
+                    records.len() - 1
+                }
+            };
+
+            heap.push(Entry {
+                priority: next_cost + heuristic(&records[next_idx].node),
+                cost: next_cost,
+                idx: next_idx,
+            });
+        }
+    }
+
+    None
+}
+
+/// Cheapest paths from `start` to all states that satisfy `goal` and can
+/// be reached at the lowest cost.
+///
+/// Goal states are not expanded any further.
+pub fn dijkstra_all<N, C, FN, IN, FG>(start: N, successors: FN, goal: FG) -> Option<AllPaths<N, C>>
+where
+    N: Clone + Hash + Eq,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool,
+{
+    dijkstra_all_with(start, successors, goal, HashVisited::hashed())
+}
+
+/// Same as [`dijkstra_all`] but tracks states through `visited`.
+pub fn dijkstra_all_with<N, C, K, FN, IN, FG, FK, I>(
+    start: N,
+    mut successors: FN,
+    mut goal: FG,
+    mut visited: Visited<FK, I>,
+) -> Option<AllPaths<N, C>>
+where
+    N: Clone,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool,
+    FK: FnMut(&N) -> K,
+    I: NodeIndex<K>,
+{
+    let (key, _) = visited.get(&start);
+    visited.insert(key, 0);
+
+    let mut heap = BinaryHeap::from([Entry {
+        priority: C::zero(),
+        cost: C::zero(),
+        idx: 0,
+    }]);
+
+    let mut records = vec![DagRecord {
+        node: start,
+        parents: Vec::new(),
+        cost: C::zero(),
+    }];
+
+    let mut best = None;
+    let mut goals = Vec::new();
+
+    while let Some(Entry { cost, idx, .. }) = heap.pop() {
+        if cost > records[idx].cost {
+            continue;
+        } else if best.is_some_and(|best| cost > best) {
+            break;
+        }
+
+        let node = records[idx].node.clone();
+
+        if goal(&node) {
+            best = Some(cost);
+            goals.push(idx);
+
+            continue;
+        }
+
+        for (next, weight) in successors(&node) {
+            let next_cost = cost + weight;
+
+            match visited.get(&next) {
+                (_, Some(next_idx)) => {
+                    let record = &mut records[next_idx];
+
+                    match record.cost.cmp(&next_cost) {
+                        Ordering::Less => {}
+                        Ordering::Equal => record.parents.push(idx),
+                        Ordering::Greater => {
+                            *record = DagRecord {
+                                node: next,
+                                parents: vec![idx],
+                                cost: next_cost,
+                            };
+
+                            heap.push(Entry {
+                                priority: next_cost,
+                                cost: next_cost,
+                                idx: next_idx,
+                            });
+                        }
+                    }
+                }
+                (key, None) => {
+                    visited.insert(key, records.len());
+
+                    heap.push(Entry {
+                        priority: next_cost,
+                        cost: next_cost,
+                        idx: records.len(),
+                    });
+
+                    records.push(DagRecord {
+                        node: next,
+                        parents: vec![idx],
+                        cost: next_cost,
+                    });
+                }
+            }
+        }
+    }
+
+    let cost = best?;
+
+    Some(AllPaths {
+        records,
+        goals,
+        cost,
+    })
+}
+
+struct DagRecord<N, C> {
+    node: N,
+    parents: Vec<usize>,
+    cost: C,
+}
+
+/// All cheapest paths of a search as a DAG of predecessors.
+pub struct AllPaths<N, C> {
+    records: Vec<DagRecord<N, C>>,
+    goals: Vec<usize>,
+    cost: C,
+}
+
+impl<N, C: Copy> AllPaths<N, C> {
+    /// The cost of each path.
+    pub fn cost(&self) -> C {
+        self.cost
+    }
+
+    /// All goal states that were reached at the lowest cost.
+    pub fn goals(&self) -> impl Iterator<Item = &N> {
+        self.goals.iter().map(|&idx| &self.records[idx].node)
+    }
+
+    /// Every state that lies on at least one cheapest path, each one once.
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        let mut seen = vec![false; self.records.len()];
+        let mut stack = self.goals.clone();
+        let mut nodes = Vec::new();
+
+        while let Some(idx) = stack.pop() {
+            if !std::mem::replace(&mut seen[idx], true) {
+                nodes.push(&self.records[idx].node);
+                stack.extend_from_slice(&self.records[idx].parents);
+            }
+        }
+
+        nodes.into_iter()
+    }
+}
+
+/// Max-heap entry that orders by lowest priority first.
+struct Entry<C> {
+    priority: C,
+    cost: C,
+    idx: usize,
+}
+
+impl<C: Ord> Ord for Entry<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .cmp(&self.priority)
+            // prefer entries that are further along
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+impl<C: Ord> PartialOrd for Entry<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Ord> PartialEq for Entry<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<C: Ord> Eq for Entry<C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -1-> 1 -1-> 3
+    // 0 -2-> 2 -0-> 3
+    // 0 -5-> 3
+    fn edges(n: &u8) -> Vec<(u8, u32)> {
+        match n {
+            0 => vec![(1, 1), (2, 2), (3, 5)],
+            1 => vec![(3, 1)],
+            2 => vec![(3, 0)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn cheapest_path() {
+        let path = dijkstra(0, edges, |&n| n == 3).unwrap();
+        assert_eq!(path.cost, 2);
+        assert_eq!(path.nodes.first(), Some(&0));
+        assert_eq!(path.nodes.last(), Some(&3));
+
+        let path = astar(0, edges, |&n| u32::from(n < 3), |&n| n == 3).unwrap();
+        assert_eq!(path.cost, 2);
+
+        assert!(dijkstra(1, edges, |&n| n == 0).is_none());
+    }
+
+    #[test]
+    fn all_cheapest_paths() {
+        let paths = dijkstra_all(0, edges, |&n| n == 3).unwrap();
+        assert_eq!(paths.cost(), 2);
+        assert_eq!(paths.goals().collect::<Vec<_>>(), [&3]);
+
+        let mut nodes: Vec<_> = paths.nodes().copied().collect();
+        nodes.sort_unstable();
+        assert_eq!(nodes, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn grid_with_keys() {
+        // walk along a line where only the position matters, not the steps taken
+        let successors = |&(pos, steps): &(i32, u32)| {
+            [(pos - 1, steps + 1), (pos + 1, steps + 1)]
+                .into_iter()
+                .filter(|(pos, _)| (0..10).contains(pos))
+                .map(|next| (next, 1))
+        };
+
+        let visited = Visited::dense(|&(pos, _): &(i32, u32)| pos as usize, 10);
+        let path = dijkstra_with((0, 0), successors, |&(pos, _)| pos == 9, visited).unwrap();
+        assert_eq!(path.cost, 9);
+        assert_eq!(path.nodes.last(), Some(&(9, 9)));
+    }
+}
@@ -0,0 +1,48 @@
+//! Graph searches over states whose successors are produced by a closure.
+//!
+//! Each search comes as a plain function that tracks visited states in a
+//! `HashMap` and a `_with` variant that takes a [`Visited`] strategy instead.
+
+mod bfs;
+mod bidirectional;
+mod dijkstra;
+mod visited;
+
+pub use self::{
+    bfs::{bfs, bfs_with},
+    bidirectional::bidirectional_bfs,
+    dijkstra::{
+        astar, astar_with, dijkstra, dijkstra_all, dijkstra_all_with, dijkstra_with, AllPaths,
+    },
+    visited::{DenseIndex, HashVisited, NodeIndex, Visited},
+};
+
+/// A path found by a search alongside its total cost.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub cost: C,
+    /// All states from the start up to and including the goal
+    pub nodes: Vec<N>,
+}
+
+const NO_PARENT: usize = usize::MAX;
+
+struct Record<N, C> {
+    node: N,
+    parent: usize,
+    cost: C,
+}
+
+fn reconstruct<N: Clone, C: Copy>(records: &[Record<N, C>], mut idx: usize) -> Path<N, C> {
+    let cost = records[idx].cost;
+    let mut nodes = Vec::new();
+
+    while idx != NO_PARENT {
+        nodes.push(records[idx].node.clone());
+        idx = records[idx].parent;
+    }
+
+    nodes.reverse();
+
+    Path { cost, nodes }
+}
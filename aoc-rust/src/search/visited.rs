@@ -0,0 +1,108 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, Hash},
+};
+
+/// Maps the key of a state to the index of its record within a search.
+pub trait NodeIndex<K> {
+    fn get(&self, key: &K) -> Option<usize>;
+
+    fn insert(&mut self, key: K, idx: usize);
+}
+
+impl<K: Hash + Eq, S: BuildHasher> NodeIndex<K> for HashMap<K, usize, S> {
+    #[inline]
+    fn get(&self, key: &K) -> Option<usize> {
+        HashMap::get(self, key).copied()
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, idx: usize) {
+        HashMap::insert(self, key, idx);
+    }
+}
+
+/// Array-backed [`NodeIndex`] for keys in `0..len`.
+///
+/// Much faster than hashing when states can be numbered densely, e.g. by
+/// their position within a grid.
+#[derive(Clone, Debug)]
+pub struct DenseIndex {
+    indices: Box<[u32]>,
+}
+
+impl DenseIndex {
+    const NONE: u32 = u32::MAX;
+
+    pub fn new(len: usize) -> Self {
+        Self {
+            indices: vec![Self::NONE; len].into_boxed_slice(),
+        }
+    }
+}
+
+impl NodeIndex<usize> for DenseIndex {
+    #[inline]
+    fn get(&self, key: &usize) -> Option<usize> {
+        match self.indices[*key] {
+            Self::NONE => None,
+            idx => Some(idx as usize),
+        }
+    }
+
+    #[inline]
+    fn insert(&mut self, key: usize, idx: usize) {
+        self.indices[key] = idx as u32;
+    }
+}
+
+/// Decides which states are considered the same and how visited states are
+/// tracked.
+///
+/// States with equal keys are only explored once so a key may omit parts of
+/// a state that don't affect its successors.
+pub struct Visited<FK, I> {
+    key: FK,
+    index: I,
+}
+
+impl<FK, I> Visited<FK, I> {
+    pub fn new(key: FK, index: I) -> Self {
+        Self { key, index }
+    }
+
+    pub(super) fn get<N, K>(&mut self, node: &N) -> (K, Option<usize>)
+    where
+        FK: FnMut(&N) -> K,
+        I: NodeIndex<K>,
+    {
+        let key = (self.key)(node);
+        let idx = self.index.get(&key);
+
+        (key, idx)
+    }
+
+    pub(super) fn insert<K>(&mut self, key: K, idx: usize)
+    where
+        I: NodeIndex<K>,
+    {
+        self.index.insert(key, idx);
+    }
+}
+
+/// Visited states keyed by the states themselves.
+pub type HashVisited<N> = Visited<fn(&N) -> N, HashMap<N, usize>>;
+
+impl<N: Clone + Hash + Eq> HashVisited<N> {
+    pub fn hashed() -> Self {
+        Self::new(N::clone, HashMap::new())
+    }
+}
+
+impl<FK> Visited<FK, DenseIndex> {
+    /// Track states through a [`DenseIndex`] of length `len`; `key` must
+    /// map states into `0..len`.
+    pub fn dense(key: FK, len: usize) -> Self {
+        Self::new(key, DenseIndex::new(len))
+    }
+}
@@ -1,16 +1,45 @@
 macro_rules! solution {
-    ( $( $variant:ident($ty:ty) ,)* ) => {
-        #[derive(Clone, Debug, Default)]
+    ( $( $variant:ident($ty:ty) = $kind:literal ,)* ) => {
+        #[derive(Clone, Debug, Default, PartialEq)]
         pub enum SolutionType {
             $( $variant($ty), )*
+            Art(AsciiArt),
+            List(Vec<SolutionType>),
             #[default]
             Unsolved,
         }
 
+        impl SolutionType {
+            /// Name of the variant's type
+            pub fn kind(&self) -> &'static str {
+                match self {
+                    $( Self::$variant(_) => $kind, )*
+                    Self::Art(_) => "art",
+                    Self::List(_) => "list",
+                    Self::Unsolved => "unsolved",
+                }
+            }
+        }
+
         impl Display for SolutionType {
             fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
                 match self {
                     $( Self::$variant(n) => Display::fmt(n, f) ,)*
+                    Self::Art(art) => Display::fmt(art, f),
+                    Self::List(list) => {
+                        let mut iter = list.iter();
+
+                        if let Some(elem) = iter.next() {
+                            Display::fmt(elem, f)?;
+
+                            for elem in iter {
+                                f.write_str(",")?;
+                                Display::fmt(elem, f)?;
+                            }
+                        }
+
+                        Ok(())
+                    }
                     Self::Unsolved => f.write_str("TODO"),
                 }
             }
@@ -26,34 +55,89 @@ macro_rules! solution {
 
             impl PartialEq<$ty> for SolutionType {
                 fn eq(&self, other: &$ty) -> bool {
-                    if let Self::$variant(n) = self {
-                        n == other
-                    } else {
-                        panic!("comparing different solution types")
-                    }
+                    matches!(self, Self::$variant(n) if n == other)
                 }
             }
         )*
     };
 }
 
-use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+use num_bigint::BigInt;
+
+use crate::AsciiArt;
 
 solution! {
-    U8(u8),
-    U16(u16),
-    U32(u32),
-    U64(u64),
-    Usize(usize),
-    I8(i8),
-    I16(i16),
-    I32(i32),
-    I64(i64),
-    Isize(isize),
-    F32(f32),
-    F64(f64),
-    String(String),
-    BoxStr(Box<str>),
+    U8(u8) = "u8",
+    U16(u16) = "u16",
+    U32(u32) = "u32",
+    U64(u64) = "u64",
+    U128(u128) = "u128",
+    Usize(usize) = "usize",
+    I8(i8) = "i8",
+    I16(i16) = "i16",
+    I32(i32) = "i32",
+    I64(i64) = "i64",
+    I128(i128) = "i128",
+    Isize(isize) = "isize",
+    BigInt(BigInt) = "bigint",
+    F32(f32) = "f32",
+    F64(f64) = "f64",
+    String(String) = "string",
+    BoxStr(Box<str>) = "string",
+}
+
+impl SolutionType {
+    /// Compare with an expected solution.
+    ///
+    /// Unlike `==`, this reports *why* the two are not equal.
+    pub fn check(&self, expected: &SolutionType) -> Result<(), SolutionMismatch> {
+        if self.kind() != expected.kind() {
+            return Err(SolutionMismatch::Type {
+                expected: expected.kind(),
+                actual: self.kind(),
+            });
+        }
+
+        let eq = match (self, expected) {
+            (Self::String(a), Self::BoxStr(b)) | (Self::BoxStr(b), Self::String(a)) => {
+                a.as_str() == b.as_ref()
+            }
+            _ => self == expected,
+        };
+
+        if eq {
+            Ok(())
+        } else {
+            Err(SolutionMismatch::Value {
+                expected: expected.to_string(),
+                actual: self.to_string(),
+            })
+        }
+    }
+
+    /// Compare with the string representation of an expected solution.
+    pub fn check_str(&self, expected: &str) -> Result<(), SolutionMismatch> {
+        if let Self::Unsolved = self {
+            return Err(SolutionMismatch::Type {
+                expected: "string",
+                actual: self.kind(),
+            });
+        }
+
+        if self == expected {
+            Ok(())
+        } else {
+            Err(SolutionMismatch::Value {
+                expected: expected.to_owned(),
+                actual: self.to_string(),
+            })
+        }
+    }
 }
 
 impl PartialEq<str> for SolutionType {
@@ -69,7 +153,56 @@ impl PartialEq<str> for SolutionType {
     }
 }
 
-#[derive(Clone, Default)]
+impl PartialEq<AsciiArt> for SolutionType {
+    fn eq(&self, other: &AsciiArt) -> bool {
+        matches!(self, Self::Art(art) if art == other)
+    }
+}
+
+impl From<AsciiArt> for SolutionType {
+    #[inline]
+    fn from(art: AsciiArt) -> Self {
+        Self::Art(art)
+    }
+}
+
+impl<T> From<Vec<T>> for SolutionType
+where
+    SolutionType: From<T>,
+{
+    fn from(list: Vec<T>) -> Self {
+        Self::List(list.into_iter().map(Self::from).collect())
+    }
+}
+
+/// Reason why a [`SolutionType`] did not match its expected value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolutionMismatch {
+    /// The solutions are of different types
+    Type {
+        expected: &'static str,
+        actual: &'static str,
+    },
+    /// The solutions are of the same type but have different values
+    Value { expected: String, actual: String },
+}
+
+impl Display for SolutionMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Type { expected, actual } => {
+                write!(f, "expected solution of type `{expected}`, got `{actual}`")
+            }
+            Self::Value { expected, actual } => {
+                write!(f, "expected `{expected}`, got `{actual}`")
+            }
+        }
+    }
+}
+
+impl StdError for SolutionMismatch {}
+
+#[derive(Clone, Debug, Default)]
 pub struct Solution {
     pub part1: SolutionType,
     pub part2: SolutionType,
@@ -103,9 +236,62 @@ impl Solution {
 
 impl Display for Solution {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        writeln!(f, "Part 1:\n{}", self.part1)?;
-        writeln!(f, "Part 2:\n{}", self.part2)?;
+        fmt_part(f, 1, &self.part1)?;
+        fmt_part(f, 2, &self.part2)?;
 
         Ok(())
     }
 }
+
+fn fmt_part(f: &mut Formatter<'_>, part: u8, solution: &SolutionType) -> FmtResult {
+    writeln!(f, "Part {part}:\n{solution}")?;
+
+    // recognized letters are displayed with their image below
+    match solution {
+        SolutionType::Art(art) if art.text().is_some() => writeln!(f, "{}", art.render()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_mismatch() {
+        let solution = SolutionType::from(42_u32);
+
+        assert_eq!(solution.check(&SolutionType::from(42_u32)), Ok(()));
+        assert_eq!(solution.check_str("42"), Ok(()));
+
+        assert_eq!(
+            solution.check(&SolutionType::from(42_u64)),
+            Err(SolutionMismatch::Type {
+                expected: "u64",
+                actual: "u32",
+            })
+        );
+
+        assert_eq!(
+            solution.check_str("43"),
+            Err(SolutionMismatch::Value {
+                expected: "43".to_owned(),
+                actual: "42".to_owned(),
+            })
+        );
+
+        assert!(solution != 42_u64);
+    }
+
+    #[test]
+    fn display_rich() {
+        let list = SolutionType::from(vec![1_u8, 2, 3]);
+        assert_eq!(list.to_string(), "1,2,3");
+
+        let art = SolutionType::from(AsciiArt::new(2, [false, true, true, false]));
+        assert_eq!(art.to_string(), " █\n█ ");
+
+        let big = SolutionType::from(BigInt::from(u128::MAX) * 2);
+        assert!(big == *"680564733841876926926749214863536422910");
+    }
+}
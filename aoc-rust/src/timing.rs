@@ -0,0 +1,261 @@
+//! Breaking down how long a day takes into its phases.
+//!
+//! Runners wrap a day's `run` in [`time`] and days run each of their phases
+//! through [`phase`] or mark their end through [`lap`]. Days that do neither
+//! only report their total duration.
+
+use std::{
+    cell::RefCell,
+    fmt::{Display, Formatter, Result as FmtResult},
+    time::Duration,
+};
+
+/// A phase of a day's `run`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    fn next(self) -> Option<Self> {
+        match self {
+            Self::Parse => Some(Self::Part1),
+            Self::Part1 => Some(Self::Part2),
+            Self::Part2 => None,
+        }
+    }
+}
+
+/// Durations of a day's `run` and its phases.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub total: Duration,
+    pub parse: Option<Duration>,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+impl Timings {
+    fn get_mut(&mut self, phase: Phase) -> &mut Option<Duration> {
+        match phase {
+            Phase::Parse => &mut self.parse,
+            Phase::Part1 => &mut self.part1,
+            Phase::Part2 => &mut self.part2,
+        }
+    }
+
+    /// Whether the day reported any of its phases.
+    pub fn has_phases(&self) -> bool {
+        self.parse.is_some() || self.part1.is_some() || self.part2.is_some()
+    }
+}
+
+impl Display for Timings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{:?}", self.total)?;
+
+        let phases = [
+            ("parse", self.parse),
+            ("part 1", self.part1),
+            ("part 2", self.part2),
+        ];
+
+        let mut phases = phases
+            .into_iter()
+            .filter_map(|(name, elapsed)| Some((name, elapsed?)));
+
+        if let Some((name, elapsed)) = phases.next() {
+            write!(f, " ({name}: {elapsed:?}")?;
+
+            for (name, elapsed) in phases {
+                write!(f, ", {name}: {elapsed:?}")?;
+            }
+
+            f.write_str(")")?;
+        }
+
+        Ok(())
+    }
+}
+
+struct Timer {
+    /// Time since an arbitrary but fixed point
+    clock: Box<dyn Fn() -> Duration>,
+    start: Duration,
+    last: Duration,
+    last_phase: Option<Phase>,
+    timings: Timings,
+}
+
+thread_local! {
+    static TIMER: RefCell<Option<Timer>> = const { RefCell::new(None) };
+}
+
+/// Run `f` while recording the phases it marks through [`lap`].
+///
+/// If `f` marks the end of a phase but not of the one after it, the
+/// remaining time is attributed to the latter.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn time<R>(f: impl FnOnce() -> R) -> (R, Timings) {
+    let start = std::time::Instant::now();
+
+    time_with(move || start.elapsed(), f)
+}
+
+/// Same as [`time`] but measures through `clock`.
+///
+/// `clock` provides the time since an arbitrary but fixed point so that
+/// platforms without `std::time::Instant` can provide their own.
+pub fn time_with<R>(clock: impl Fn() -> Duration + 'static, f: impl FnOnce() -> R) -> (R, Timings) {
+    let start = clock();
+
+    let timer = Timer {
+        clock: Box::new(clock),
+        start,
+        last: start,
+        last_phase: None,
+        timings: Timings::default(),
+    };
+
+    let restore = Restore {
+        prev: TIMER.with(|cell| cell.replace(Some(timer))),
+    };
+
+    let res = f();
+    let timer = TIMER.with(RefCell::take);
+    drop(restore);
+
+    let Some(timer) = timer else {
+        unreachable!("timer is only taken after running")
+    };
+
+    let now = (timer.clock)();
+    let mut timings = timer.timings;
+    timings.total = now - timer.start;
+
+    if let Some(next) = timer.last_phase.and_then(Phase::next) {
+        timings.get_mut(next).get_or_insert(now - timer.last);
+    }
+
+    (res, timings)
+}
+
+/// Puts back the timer of an enclosing [`time_with`], even if `f` panics.
+struct Restore {
+    prev: Option<Timer>,
+}
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let prev = self.prev.take();
+        let _ = TIMER.try_with(|cell| cell.replace(prev));
+    }
+}
+
+/// Run `f` as the given phase and mark its end.
+pub fn phase<R>(phase: Phase, f: impl FnOnce() -> R) -> R {
+    let res = f();
+    lap(phase);
+
+    res
+}
+
+/// Mark the end of a phase.
+///
+/// Does nothing if the day is not run through [`time`] or [`time_with`].
+pub fn lap(phase: Phase) {
+    TIMER.with(|cell| {
+        if let Some(timer) = cell.borrow_mut().as_mut() {
+            let now = (timer.clock)();
+            *timer.timings.get_mut(phase) = Some(now - timer.last);
+            timer.last = now;
+            timer.last_phase = Some(phase);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    thread_local! {
+        static NOW: Cell<u64> = const { Cell::new(0) };
+    }
+
+    fn clock() -> Duration {
+        Duration::from_millis(NOW.with(Cell::get))
+    }
+
+    fn advance(millis: u64) {
+        NOW.with(|now| now.set(now.get() + millis));
+    }
+
+    #[test]
+    fn phases() {
+        let (res, timings) = time_with(clock, || {
+            advance(3);
+            lap(Phase::Parse);
+            advance(5);
+            lap(Phase::Part1);
+            advance(7);
+
+            42
+        });
+
+        assert_eq!(res, 42);
+        assert_eq!(timings.total, Duration::from_millis(15));
+        assert_eq!(timings.parse, Some(Duration::from_millis(3)));
+        assert_eq!(timings.part1, Some(Duration::from_millis(5)));
+        assert_eq!(timings.part2, Some(Duration::from_millis(7)));
+        assert_eq!(
+            timings.to_string(),
+            "15ms (parse: 3ms, part 1: 5ms, part 2: 7ms)"
+        );
+    }
+
+    #[test]
+    fn without_phases() {
+        let ((), timings) = time_with(clock, || advance(4));
+        assert!(!timings.has_phases());
+        assert_eq!(timings.to_string(), "4ms");
+
+        // not being timed
+        lap(Phase::Parse);
+    }
+
+    #[test]
+    fn through_phase() {
+        let (res, timings) = time_with(clock, || {
+            let n = phase(Phase::Parse, || {
+                advance(2);
+                21
+            });
+
+            phase(Phase::Part1, || advance(4));
+
+            n * 2
+        });
+
+        assert_eq!(res, 42);
+        assert_eq!(timings.parse, Some(Duration::from_millis(2)));
+        assert_eq!(timings.part1, Some(Duration::from_millis(4)));
+        assert_eq!(timings.part2, Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn panic_restores_outer() {
+        let ((), timings) = time_with(clock, || {
+            let res = std::panic::catch_unwind(|| time_with(clock, || panic!("inner")));
+            assert!(res.is_err());
+
+            advance(6);
+            lap(Phase::Parse);
+        });
+
+        assert_eq!(timings.parse, Some(Duration::from_millis(6)));
+    }
+}
@@ -0,0 +1,213 @@
+//! Detecting cycles in repeatedly applied state transitions in order to skip
+//! ahead to far away iterations.
+//!
+//! States are compared through a key so that only the parts of a state that
+//! determine its future have to be hashed or compared.
+
+use std::{collections::HashMap, hash::Hash};
+
+use super::numbers;
+
+/// The cycle of a sequence `x_0, x_1 = f(x_0), x_2 = f(x_1), ...`.
+///
+/// `x_i == x_{i + len}` holds for all `i >= start`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// Index of the first state that is part of the cycle
+    pub start: usize,
+    /// Amount of steps until a state repeats
+    pub len: usize,
+}
+
+impl Cycle {
+    /// Index below `start + len` whose state is the same as the state after
+    /// `n` steps.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+
+    /// Extrapolate a metric that grows by the same amount each time the cycle
+    /// is completed, e.g. a height or a score.
+    ///
+    /// `metric(i)` must provide the metric after `i` steps for all
+    /// `i <= start + len`.
+    pub fn extrapolate<F>(&self, n: usize, mut metric: F) -> i64
+    where
+        F: FnMut(usize) -> i64,
+    {
+        if n < self.start {
+            return metric(n);
+        }
+
+        let cycles = ((n - self.start) / self.len) as i64;
+        let per_cycle = metric(self.start + self.len) - metric(self.start);
+
+        metric(self.reduce(n)) + cycles * per_cycle
+    }
+}
+
+/// The states of a sequence up to the point where it starts repeating.
+#[derive(Clone, Debug)]
+pub struct History<S> {
+    pub cycle: Cycle,
+    /// All states from the initial one up to and including the first
+    /// repetition i.e. `cycle.start + cycle.len + 1` states
+    pub states: Vec<S>,
+}
+
+impl<S> History<S> {
+    /// The state after `n` steps.
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.cycle.reduce(n)]
+    }
+
+    /// Extrapolate a metric of the states, see [`Cycle::extrapolate`].
+    pub fn extrapolate<F>(&self, n: usize, mut metric: F) -> i64
+    where
+        F: FnMut(&S) -> i64,
+    {
+        self.cycle.extrapolate(n, |i| metric(&self.states[i]))
+    }
+}
+
+/// Apply `step` until the key of a state repeats, remembering the index of
+/// every key in a `HashMap`.
+///
+/// Keeps all states around so that any of them can be looked up afterwards.
+/// If `step` never cycles, neither does this function.
+pub fn find_cycle<S, K, FS, FK>(start: S, mut step: FS, mut key: FK) -> History<S>
+where
+    K: Hash + Eq,
+    FS: FnMut(&S) -> S,
+    FK: FnMut(&S) -> K,
+{
+    let mut seen = HashMap::new();
+    seen.insert(key(&start), 0);
+    let mut states = vec![start];
+
+    loop {
+        let next = step(&states[states.len() - 1]);
+        let idx = states.len();
+
+        if let Some(start) = seen.insert(key(&next), idx) {
+            states.push(next);
+            let cycle = Cycle {
+                start,
+                len: idx - start,
+            };
+
+            return History { cycle, states };
+        }
+
+        states.push(next);
+    }
+}
+
+/// Find the cycle of `step` through Brent's algorithm.
+///
+/// Only a constant amount of states is kept in memory at any time at the
+/// cost of applying `step` a few more times than [`find_cycle`] would.
+/// If `step` never cycles, neither does this function.
+pub fn brent<S, K, FS, FK>(start: S, mut step: FS, mut key: FK) -> Cycle
+where
+    S: Clone,
+    K: PartialEq,
+    FS: FnMut(&S) -> S,
+    FK: FnMut(&S) -> K,
+{
+    // Find the cycle length by letting the hare search for the tortoise
+    // within increasing powers of two
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = key(&start);
+    let mut hare = step(&start);
+    let mut hare_key = key(&hare);
+
+    while tortoise != hare_key {
+        if power == len {
+            tortoise = hare_key;
+            power *= 2;
+            len = 0;
+        }
+
+        hare = step(&hare);
+        hare_key = key(&hare);
+        len += 1;
+    }
+
+    // Move both `len` apart and advance them until they meet at the start
+    let mut tortoise = start.clone();
+    let mut hare = start;
+
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, len }
+}
+
+/// The first step at which all of the independent cycles of the given
+/// lengths are back at their first state simultaneously.
+///
+/// Only meaningful for cycles that start with their initial state, i.e. for
+/// which `start` is zero. Cycles that are offset against each other can be
+/// combined through [`numbers::crt`] instead.
+pub fn combine_lengths<I>(lens: I) -> u64
+where
+    I: IntoIterator<Item = usize>,
+{
+    lens.into_iter()
+        .fold(1, |acc, len| numbers::lcm(acc, len as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Values of at least 10 lead towards the cycle 0 -> 3 -> 6 -> ... -> 7 -> 0
+    fn step(n: &u32) -> u32 {
+        if *n >= 10 {
+            n - 10
+        } else {
+            (n + 3) % 10
+        }
+    }
+
+    #[test]
+    fn hashed() {
+        let history = find_cycle(22, step, |&n| n);
+        assert_eq!(history.cycle, Cycle { start: 2, len: 10 });
+        assert_eq!(history.states.len(), 13);
+        assert_eq!(*history.state_at(1), 12);
+        assert_eq!(*history.state_at(1_000_000_003), 5);
+        assert_eq!(history.extrapolate(25, |&n| n as i64), 1);
+
+        let steps = history.cycle.extrapolate(1_000_001, |i| i as i64);
+        assert_eq!(steps, 1_000_001);
+    }
+
+    #[test]
+    fn brent_matches_hashed() {
+        for start in [0, 7, 15, 22] {
+            let cycle = brent(start, step, |&n| n);
+            assert_eq!(cycle, find_cycle(start, step, |&n| n).cycle);
+        }
+    }
+
+    #[test]
+    fn combined() {
+        assert_eq!(combine_lengths([4, 6, 10]), 60);
+    }
+}
@@ -0,0 +1,270 @@
+//! Exact solving of linear systems with integer coefficients.
+//!
+//! Elimination is fraction-free: rows are combined through integer
+//! multiplication and kept small by dividing out their gcd, so results are
+//! exact no matter how large the coefficients get.
+
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+pub use num_bigint::BigInt;
+pub use num_rational::BigRational;
+
+/// A system of linear equations `A·x = b`.
+#[derive(Clone, Debug, Default)]
+pub struct LinearSystem {
+    vars: usize,
+    /// Augmented rows, i.e. the coefficients followed by the right-hand side
+    rows: Vec<Vec<BigInt>>,
+}
+
+impl LinearSystem {
+    /// Create an empty system over `vars` variables.
+    pub fn new(vars: usize) -> Self {
+        Self {
+            vars,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Add the equation `coeffs·x = rhs`.
+    ///
+    /// Panics if the amount of coefficients does not match the amount of variables.
+    pub fn equation<C, R>(&mut self, coeffs: impl IntoIterator<Item = C>, rhs: R) -> &mut Self
+    where
+        C: Into<BigInt>,
+        R: Into<BigInt>,
+    {
+        let mut row: Vec<BigInt> = coeffs.into_iter().map(Into::into).collect();
+        assert_eq!(row.len(), self.vars, "expected {} coefficients", self.vars);
+        row.push(rhs.into());
+        self.rows.push(row);
+
+        self
+    }
+
+    pub fn solve(&self) -> LinearSolution {
+        let vars = self.vars;
+        let mut rows = self.rows.clone();
+        let mut pivots = Vec::new();
+
+        for col in 0..vars {
+            let rank = pivots.len();
+
+            let Some(pivot) = (rank..rows.len()).find(|&r| !rows[r][col].is_zero()) else {
+                continue;
+            };
+
+            rows.swap(rank, pivot);
+            let pivot_row = rows[rank].clone();
+
+            for (r, row) in rows.iter_mut().enumerate() {
+                if r == rank || row[col].is_zero() {
+                    continue;
+                }
+
+                let factor = row[col].clone();
+
+                for (value, pivot_value) in row.iter_mut().zip(&pivot_row) {
+                    *value = &*value * &pivot_row[col] - pivot_value * &factor;
+                }
+
+                reduce(row);
+            }
+
+            pivots.push(col);
+        }
+
+        let rank = pivots.len();
+        let consistent = rows[rank..].iter().all(|row| row[vars].is_zero());
+        let free: Vec<_> = (0..vars).filter(|col| !pivots.contains(col)).collect();
+
+        let particular = consistent.then(|| {
+            let mut x = vec![BigRational::zero(); vars];
+
+            for (row, &col) in rows.iter().zip(&pivots) {
+                x[col] = BigRational::new(row[vars].clone(), row[col].clone());
+            }
+
+            x
+        });
+
+        let nullspace = free
+            .iter()
+            .map(|&free_col| {
+                let mut x = vec![BigRational::zero(); vars];
+                x[free_col] = BigRational::one();
+
+                for (row, &col) in rows.iter().zip(&pivots) {
+                    x[col] = BigRational::new(-row[free_col].clone(), row[col].clone());
+                }
+
+                x
+            })
+            .collect();
+
+        LinearSolution {
+            rank,
+            particular,
+            nullspace,
+            free,
+        }
+    }
+}
+
+/// Divide all values of the row by their gcd.
+fn reduce(row: &mut [BigInt]) {
+    let gcd = row.iter().fold(BigInt::zero(), |gcd, value| gcd.gcd(value));
+
+    if !gcd.is_zero() && !gcd.is_one() {
+        row.iter_mut().for_each(|value| *value /= &gcd);
+    }
+}
+
+/// All solutions of a [`LinearSystem`].
+///
+/// Every solution is `particular + Σ tᵢ·nullspace[i]` for arbitrary `tᵢ`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinearSolution {
+    /// Rank of the coefficient matrix
+    pub rank: usize,
+    /// A solution in which all free variables are zero, `None` if the system is inconsistent
+    pub particular: Option<Vec<BigRational>>,
+    /// Basis of the coefficient matrix' nullspace; its i-th vector is one
+    /// at the i-th free variable and zero at all other free variables
+    pub nullspace: Vec<Vec<BigRational>>,
+    /// Indices of variables that are not determined by the system
+    pub free: Vec<usize>,
+}
+
+impl LinearSolution {
+    /// The solution if there is exactly one.
+    pub fn unique(&self) -> Option<&[BigRational]> {
+        self.particular
+            .as_deref()
+            .filter(|_| self.nullspace.is_empty())
+    }
+
+    /// Express all solutions with integers only.
+    ///
+    /// Returns `None` if the system is inconsistent.
+    pub fn scaled(&self) -> Option<ScaledSolution> {
+        let particular = self.particular.as_ref()?;
+
+        let denom = particular
+            .iter()
+            .chain(self.nullspace.iter().flatten())
+            .fold(BigInt::one(), |denom, x| denom.lcm(x.denom()));
+
+        let scale = |x: &BigRational| (x * &denom).to_integer();
+
+        Some(ScaledSolution {
+            particular: particular.iter().map(scale).collect(),
+            nullspace: self
+                .nullspace
+                .iter()
+                .map(|basis| basis.iter().map(scale).collect())
+                .collect(),
+            denom,
+        })
+    }
+
+    /// The solution if there is exactly one and it consists only of integers.
+    pub fn unique_integers(&self) -> Option<Vec<BigInt>> {
+        self.unique()?
+            .iter()
+            .map(|x| x.is_integer().then(|| x.to_integer()))
+            .collect()
+    }
+}
+
+/// All solutions of a [`LinearSystem`] scaled by a common denominator.
+///
+/// Every solution is `(particular + Σ tᵢ·nullspace[i]) / denom` for arbitrary `tᵢ`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScaledSolution {
+    pub denom: BigInt,
+    pub particular: Vec<BigInt>,
+    pub nullspace: Vec<Vec<BigInt>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ratio(numer: i64, denom: i64) -> BigRational {
+        BigRational::new(numer.into(), denom.into())
+    }
+
+    #[test]
+    fn unique() {
+        let mut system = LinearSystem::new(2);
+        system.equation([94, 22], 8400).equation([34, 67], 5400);
+        let solution = system.solve();
+
+        assert_eq!(solution.rank, 2);
+        assert_eq!(solution.unique_integers(), Some(vec![80.into(), 40.into()]));
+
+        let mut system = LinearSystem::new(2);
+        system.equation([2, 0], 1).equation([0, 3], 1);
+        let solution = system.solve();
+
+        assert_eq!(solution.unique(), Some(&[ratio(1, 2), ratio(1, 3)][..]));
+        assert_eq!(solution.unique_integers(), None);
+    }
+
+    #[test]
+    fn inconsistent() {
+        let mut system = LinearSystem::new(2);
+        system.equation([1, 1], 1).equation([2, 2], 3);
+        let solution = system.solve();
+
+        assert_eq!(solution.rank, 1);
+        assert_eq!(solution.particular, None);
+    }
+
+    #[test]
+    fn underdetermined() {
+        let mut system = LinearSystem::new(3);
+        system.equation([1, 2, 3], 6).equation([2, 4, 7], 13);
+        let solution = system.solve();
+
+        assert_eq!(solution.rank, 2);
+        assert_eq!(solution.free, [1]);
+        assert_eq!(
+            solution.particular,
+            Some(vec![ratio(3, 1), ratio(0, 1), ratio(1, 1)])
+        );
+        assert_eq!(
+            solution.nullspace,
+            [vec![ratio(-2, 1), ratio(1, 1), ratio(0, 1)]]
+        );
+        assert_eq!(solution.unique(), None);
+
+        let mut system = LinearSystem::new(2);
+        system.equation([2, 4], 3);
+
+        assert_eq!(
+            system.solve().scaled(),
+            Some(ScaledSolution {
+                denom: 2.into(),
+                particular: vec![3.into(), 0.into()],
+                nullspace: vec![vec![(-4).into(), 2.into()]],
+            })
+        );
+    }
+
+    #[test]
+    fn big_coefficients() {
+        let big = BigInt::from(i128::MAX);
+        let mut system = LinearSystem::new(2);
+        system
+            .equation([big.clone(), 1.into()], &big * 5 + 7)
+            .equation([1.into(), big.clone()], &big * 7 + 5);
+
+        assert_eq!(
+            system.solve().unique_integers(),
+            Some(vec![5.into(), 7.into()])
+        );
+    }
+}
@@ -36,7 +36,7 @@ impl<'a> Iterator for Lines<'a> {
                 Some(line)
             }
             None if self.rest.is_empty() => None,
-            None => Some(mem::replace(&mut self.rest, "")),
+            None => Some(mem::take(&mut self.rest)),
         }
     }
 }
@@ -1,3 +1,7 @@
+pub mod cycle;
 pub mod int_hasher;
+pub mod linear;
 pub mod lines;
 pub mod numbers;
+pub mod ocr;
+pub mod parse;
@@ -29,3 +29,63 @@ pub fn mod_pow(b: i64, p: i64, m: i64) -> i64 {
         _ => (b * mod_pow(b, p - 1, m)) % m,
     }
 }
+
+/// Extended euclidean algorithm, returns `(g, x, y)` such that
+/// `a*x + b*y = g = gcd(a, b)`
+pub fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if a == 0 {
+        (b, 0, 1)
+    } else {
+        let (g, x, y) = egcd(b % a, a);
+
+        (g, y - (b / a) * x, x)
+    }
+}
+
+/// Chinese remainder theorem: combine congruences `x ≡ residue (mod modulus)`
+/// into a single `(residue, modulus)` pair.
+///
+/// Moduli need not be pairwise coprime. Returns `None` if the congruences
+/// contradict each other or if the combined modulus does not fit an `i64`.
+pub fn crt<I>(congruences: I) -> Option<(i64, i64)>
+where
+    I: IntoIterator<Item = (i64, i64)>,
+{
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(r1, m1), (r2, m2)| {
+            let (g, x, _) = egcd(m1, m2);
+
+            let [r1, m1, r2, m2, g, x] = [r1, m1, r2, m2, g, x].map(i128::from);
+
+            if (r2 - r1) % g != 0 {
+                return None;
+            }
+
+            let m = m1 / g * m2;
+            let step = (r2 - r1) / g * x % (m2 / g);
+            let r = (r1 + m1 * step).rem_euclid(m);
+
+            Some((i64::try_from(r).ok()?, i64::try_from(m).ok()?))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chinese_remainder() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+
+        let (a, b) = (2_147_483_647, 2_147_483_629);
+        let (r, m) = crt([(a - 1, a), (b - 2, b)]).unwrap();
+        assert_eq!(m, a * b);
+        assert_eq!((r % a, r % b), (a - 1, b - 2));
+
+        assert_eq!(crt([(1, 4_294_967_291), (2, 4_294_967_279)]), None);
+    }
+}
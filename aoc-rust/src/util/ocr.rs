@@ -0,0 +1,268 @@
+//! Recognize the block letters that some puzzles draw as their answer.
+//!
+//! Two fonts are supported:
+//! - the small font with glyphs of height 6 placed in cells of width 5
+//! - the large font with glyphs of height 10 placed in cells of width 8
+
+use crate::AsciiArt;
+
+struct Font {
+    height: usize,
+    cell_width: usize,
+    glyphs: &'static [(char, &'static [&'static str])],
+}
+
+const SMALL: Font = Font {
+    height: 6,
+    cell_width: 5,
+    glyphs: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+const LARGE: Font = Font {
+    height: 10,
+    cell_width: 8,
+    glyphs: &[
+        (
+            'A',
+            &[
+                "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'B',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+                "#....#", "#####.",
+            ],
+        ),
+        (
+            'C',
+            &[
+                ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+                "#....#", ".####.",
+            ],
+        ),
+        (
+            'E',
+            &[
+                "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+        (
+            'F',
+            &[
+                "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "#.....",
+            ],
+        ),
+        (
+            'G',
+            &[
+                ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+                "#...##", ".###.#",
+            ],
+        ),
+        (
+            'H',
+            &[
+                "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'J',
+            &[
+                "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+                "#...#.", ".###..",
+            ],
+        ),
+        (
+            'K',
+            &[
+                "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+                "#...#.", "#....#",
+            ],
+        ),
+        (
+            'L',
+            &[
+                "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+        (
+            'N',
+            &[
+                "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+                "#...##", "#....#",
+            ],
+        ),
+        (
+            'P',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "#.....",
+            ],
+        ),
+        (
+            'R',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'X',
+            &[
+                "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'Z',
+            &[
+                "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+    ],
+};
+
+/// Decode the letters of a row-major image with the given width.
+///
+/// Blank rows above and below the letters are ignored. Since images are
+/// sometimes cropped to the bounding box of their lit pixels, missing blank
+/// columns at the start or end are tolerated as well.
+///
+/// Returns `None` if the image does not consist of known glyphs.
+pub fn recognize(pixels: &[bool], width: usize) -> Option<String> {
+    if width == 0 {
+        return None;
+    }
+
+    let rows: Vec<&[bool]> = pixels
+        .chunks_exact(width)
+        .skip_while(|row| !row.contains(&true))
+        .collect();
+
+    let end = rows.iter().rposition(|row| row.contains(&true))? + 1;
+    let rows = &rows[..end];
+
+    let font = [SMALL, LARGE]
+        .into_iter()
+        .find(|font| font.height == rows.len())?;
+
+    (0..font.cell_width).find_map(|offset| font.decode(rows, width, offset))
+}
+
+/// Decode the letters of an image given as lines of characters where `on`
+/// marks a lit pixel.
+pub fn recognize_chars(s: &str, on: char) -> Option<String> {
+    let art = AsciiArt::from_chars(s, on);
+
+    recognize(art.pixels(), art.width())
+}
+
+impl Font {
+    /// Decode the rows assuming `offset` blank columns were cropped at the start.
+    fn decode(&self, rows: &[&[bool]], width: usize, offset: usize) -> Option<String> {
+        let cells = (offset + width).div_ceil(self.cell_width);
+
+        let px = |x: usize, y: usize| {
+            x.checked_sub(offset)
+                .and_then(|x| rows[y].get(x))
+                .copied()
+                .unwrap_or(false)
+        };
+
+        (0..cells)
+            .map(|cell| {
+                let start = cell * self.cell_width;
+
+                self.glyphs
+                    .iter()
+                    .find(|(_, glyph)| {
+                        (0..self.height).all(|y| {
+                            let row = glyph[y].as_bytes();
+
+                            (0..self.cell_width).all(|x| {
+                                let expected = row.get(x).is_some_and(|&b| b == b'#');
+
+                                px(start + x, y) == expected
+                            })
+                        })
+                    })
+                    .map(|(letter, _)| *letter)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_font() {
+        let screen = "\
+████ █   ████  █    █  █
+   █ █   ██  █ █    █  █
+  █   █ █ ███  █    ████
+ █     █  █  █ █    █  █
+█      █  █  █ █    █  █
+████   █  ███  ████ █  █ ";
+
+        assert_eq!(recognize_chars(screen, '█').as_deref(), Some("ZYBLH"));
+    }
+
+    #[test]
+    fn small_font_trimmed() {
+        let screen = "\
+█  █ ████ ███  ███  ███  ████  ██    ██
+█  █ █    █  █ █  █ █  █ █    █  █    █
+█  █ ███  █  █ █  █ █  █ ███  █       █
+█  █ █    ███  ███  ███  █    █ ██    █
+█  █ █    █ █  █    █ █  █    █  █ █  █
+ ██  ████ █  █ █    █  █ █     ███  ██ ";
+
+        assert_eq!(recognize_chars(screen, '█').as_deref(), Some("UERPRFGJ"));
+    }
+
+    #[test]
+    fn large_font() {
+        let screen = "\
+#....#..#####.
+#....#..#....#
+#....#..#....#
+#....#..#....#
+######..#####.
+#....#..#.....
+#....#..#.....
+#....#..#.....
+#....#..#.....
+#....#..#.....";
+
+        assert_eq!(recognize_chars(screen, '#').as_deref(), Some("HP"));
+    }
+}
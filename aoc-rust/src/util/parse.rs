@@ -0,0 +1,234 @@
+//! Errors for malformed puzzle input that point at the offending position.
+//!
+//! Parsers usually work on subslices of the input, e.g. a line or a single
+//! number. [`ParseError::at`] recovers the position of such a subslice within
+//! the whole input so the error can name the line and column and show an
+//! excerpt of the line.
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+};
+
+/// Amount of chars of a line that are shown around the offending column
+const EXCERPT_WIDTH: usize = 60;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+    position: Option<Position>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Position {
+    /// 1-based line number
+    line: usize,
+    /// 1-based column, counted in chars
+    column: usize,
+    /// The offending line, shortened to [`EXCERPT_WIDTH`] chars
+    excerpt: String,
+    /// Offset of the column within the excerpt, counted in chars
+    caret: usize,
+}
+
+impl ParseError {
+    /// Create an error that does not point at a position.
+    pub fn new(message: impl Display) -> Self {
+        Self {
+            message: message.to_string(),
+            position: None,
+        }
+    }
+
+    /// Create an error pointing at the start of `span`.
+    ///
+    /// `span` is expected to be a subslice of `input`, otherwise the error
+    /// does not carry a position.
+    pub fn at(input: &str, span: &str, message: impl Display) -> Self {
+        let offset = (span.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| offset + span.len() <= input.len());
+
+        match offset {
+            Some(offset) => Self::at_offset(input, offset, message),
+            None => Self::new(message),
+        }
+    }
+
+    /// Create an error pointing at the byte `offset` of `input`.
+    pub fn at_offset(input: &str, offset: usize, message: impl Display) -> Self {
+        let mut offset = offset.min(input.len());
+
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        let line = input[..start].matches('\n').count() + 1;
+        let column = input[start..offset].chars().count() + 1;
+        let (excerpt, caret) = excerpt(input[start..end].trim_end_matches('\r'), column - 1);
+
+        Self {
+            message: message.to_string(),
+            position: Some(Position {
+                line,
+                column,
+                excerpt,
+                caret,
+            }),
+        }
+    }
+
+    /// Create an error from a nom parser that failed on a subslice of `input`.
+    #[cfg(feature = "nom")]
+    pub fn from_nom(input: &str, err: nom::Err<nom::error::Error<&str>>) -> Self {
+        match err {
+            nom::Err::Error(err) | nom::Err::Failure(err) => Self::at(
+                input,
+                err.input,
+                format_args!("unexpected input, expected {}", err.code.description()),
+            ),
+            nom::Err::Incomplete(_) => Self::new("incomplete input"),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// 1-based line number of the offending position
+    pub fn line(&self) -> Option<usize> {
+        self.position.as_ref().map(|position| position.line)
+    }
+
+    /// 1-based column of the offending position, counted in chars
+    pub fn column(&self) -> Option<usize> {
+        self.position.as_ref().map(|position| position.column)
+    }
+}
+
+/// Shorten long lines to a window around `column`.
+fn excerpt(line: &str, column: usize) -> (String, usize) {
+    let len = line.chars().count();
+
+    if len <= EXCERPT_WIDTH {
+        return (line.to_owned(), column);
+    }
+
+    let skip = column
+        .saturating_sub(EXCERPT_WIDTH / 2)
+        .min(len - EXCERPT_WIDTH);
+
+    let mut excerpt: String = line.chars().skip(skip).take(EXCERPT_WIDTH).collect();
+    let mut caret = column - skip;
+
+    if skip > 0 {
+        excerpt.insert_str(0, "...");
+        caret += 3;
+    }
+
+    if skip + EXCERPT_WIDTH < len {
+        excerpt.push_str("...");
+    }
+
+    (excerpt, caret)
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let Some(ref position) = self.position else {
+            return f.write_str(&self.message);
+        };
+
+        let Position {
+            line,
+            column,
+            ref excerpt,
+            caret,
+        } = *position;
+
+        write!(
+            f,
+            "line {line}, column {column}: {}\n{excerpt}\n{:caret$}^",
+            self.message, ""
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Turn failures into a [`ParseError`] pointing at a subslice of the input.
+pub trait ParseContext<T> {
+    fn context_at(self, input: &str, span: &str, message: impl Display) -> Result<T, ParseError>;
+}
+
+impl<T> ParseContext<T> for Option<T> {
+    fn context_at(self, input: &str, span: &str, message: impl Display) -> Result<T, ParseError> {
+        self.ok_or_else(|| ParseError::at(input, span, message))
+    }
+}
+
+impl<T, E: Display> ParseContext<T> for Result<T, E> {
+    fn context_at(self, input: &str, span: &str, message: impl Display) -> Result<T, ParseError> {
+        self.map_err(|err| ParseError::at(input, span, format_args!("{message}: {err}")))
+    }
+}
+
+/// Parse `span`, a subslice of `input`, through its [`FromStr`] implementation.
+pub fn parse_at<T>(input: &str, span: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    span.parse()
+        .context_at(input, span, format_args!("failed to parse `{span}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position() {
+        let input = "Button A: X+94, Y+34\nButton B: X+2a, Y+67\n";
+        let line = input.lines().nth(1).unwrap();
+        let err = parse_at::<u32>(input, &line[12..14]).unwrap_err();
+
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(err.column(), Some(13));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 13: failed to parse `2a`: invalid digit found in string
+Button B: X+2a, Y+67
+            ^"
+        );
+    }
+
+    #[test]
+    fn foreign_span() {
+        let owned = String::from("12");
+        let err = ParseError::at("input", &owned, "unexpected");
+
+        assert_eq!(err.line(), None);
+        assert_eq!(err.to_string(), "unexpected");
+    }
+
+    #[test]
+    fn long_line() {
+        let input = "x".repeat(100) + "?" + &"x".repeat(100);
+        let err = ParseError::at_offset(&input, 100, "unexpected `?`");
+        let excerpt = format!("...{}?{}...", "x".repeat(30), "x".repeat(29));
+        let caret = format!("{}^", " ".repeat(33));
+
+        assert_eq!(err.column(), Some(101));
+        assert_eq!(
+            err.to_string(),
+            format!("line 1, column 101: unexpected `?`\n{excerpt}\n{caret}")
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub fn main() {
+    aoc_solver::run_worker();
+}
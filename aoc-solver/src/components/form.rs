@@ -1,11 +1,13 @@
-use leptos::{component, view, IntoView};
+use leptos::{component, create_effect, create_signal, view, IntoView, SignalGet, SignalSet};
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
 
 use crate::{
-    components::{solver::SolveInput, SelectDays},
+    components::{solver::SolveInput, ParamsInput, SelectDays, SelectVariant},
     year::Year,
 };
 
+const DEFAULT_TIMEOUT_SECS: u32 = 30;
+
 #[component]
 pub fn SolverForm<Y, I>(year: Y, on_input: I) -> impl IntoView
 where
@@ -14,6 +16,25 @@ where
 {
     let solved_days = move || year().solved_days();
 
+    // the last day is selected initially
+    let (chosen_day, set_chosen_day) = create_signal(None);
+    create_effect(move |_| {
+        year();
+        set_chosen_day.set(None);
+    });
+
+    let on_day_change = move |day| set_chosen_day.set(Some(day));
+
+    let day = move || {
+        chosen_day
+            .get()
+            .or_else(|| solved_days().last_day())
+            .and_then(|day| year().day(day))
+    };
+
+    let variants = move || day().map_or(&[][..], |day| day.variants);
+    let params = move || day().map(|day| day.default_params()).unwrap_or_default();
+
     let on_submit = move |ev: web_sys::SubmitEvent| {
         ev.prevent_default();
         ev.stop_propagation();
@@ -57,9 +78,26 @@ where
             <div class="field is-grouped">
                 <div class="control">
                     <div>
-                        <SelectDays solved_days/>
+                        <SelectDays year solved_days on_change=on_day_change/>
                     </div>
                 </div>
+                <div class="control">
+                    <SelectVariant variants/>
+                </div>
+                <div class="control">
+                    <ParamsInput params/>
+                </div>
+                <div class="control">
+                    <input
+                        class="input"
+                        type="number"
+                        name="timeout"
+                        min="1"
+                        value=DEFAULT_TIMEOUT_SECS
+                        placeholder="Timeout (s)"
+                        title="Timeout in seconds, empty for none"
+                    />
+                </div>
                 <div class="control">
                     <input
                         class="button is-primary"
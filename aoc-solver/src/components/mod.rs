@@ -3,11 +3,19 @@
 mod footer;
 mod form;
 mod navbar;
+mod params_input;
 mod select_days;
+mod select_variant;
 mod solution;
 mod solver;
 
 pub use self::{
-    footer::Footer, form::SolverForm, navbar::Navbar, select_days::SelectDays, solution::Solution,
-    solver::Solver,
+    footer::Footer,
+    form::SolverForm,
+    navbar::Navbar,
+    params_input::ParamsInput,
+    select_days::SelectDays,
+    select_variant::SelectVariant,
+    solution::Solution,
+    solver::{DaySolution, Implementation, SolveInput, SolvedPart, Solver},
 };
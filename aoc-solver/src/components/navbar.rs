@@ -25,7 +25,7 @@ pub fn Navbar<Y: Fn() -> Year + 'static>(year: Y) -> impl IntoView {
                         </div>
                         <div class="navbar-dropdown">
                             <For
-                                each = crate::generated::years
+                                each = aoc_registry::years
                                 key = |year| *year
                                 children = move |&year| view! { <NavbarYear year path/> }
                             />
@@ -0,0 +1,28 @@
+use leptos::{component, view, IntoView, Show};
+
+#[component]
+pub fn ParamsInput<P>(params: P) -> impl IntoView
+where
+    P: Fn() -> Vec<(&'static str, String)> + Copy + 'static,
+{
+    // the defaults show which parameters exist and how to override them
+    let placeholder = move || {
+        params()
+            .into_iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    view! {
+        <Show when=move || !params().is_empty()>
+            <input
+                class="input"
+                type="text"
+                name="params"
+                placeholder=placeholder
+                title="Puzzle parameters e.g. for the example, empty for the real input's"
+            />
+        </Show>
+    }
+}
@@ -1,12 +1,25 @@
-use leptos::{component, view, For, IntoView};
+use leptos::{component, event_target_value, view, For, IntoView};
 
-use crate::day::SolvedDays;
+use crate::{
+    day::{SolvedDay, SolvedDays},
+    year::Year,
+};
 
 #[component]
-pub fn SelectDays<F: Fn() -> SolvedDays + Copy + 'static>(solved_days: F) -> impl IntoView {
+pub fn SelectDays<Y, F, C>(year: Y, solved_days: F, on_change: C) -> impl IntoView
+where
+    Y: Fn() -> Year + Copy + 'static,
+    F: Fn() -> SolvedDays + Copy + 'static,
+    C: Fn(SolvedDay) + 'static,
+{
+    let on_change = move |ev| match event_target_value(&ev).parse() {
+        Ok(day) => on_change(SolvedDay(day)),
+        Err(err) => error!(%err, "Invalid day"),
+    };
+
     view! {
         <div class="select">
-            <select name="day">
+            <select name="day" on:change=on_change>
                 <For
                     each=solved_days
                     key=move |day| *day
@@ -20,8 +33,9 @@ pub fn SelectDays<F: Fn() -> SolvedDays + Copy + 'static>(solved_days: F) -> imp
                                 value={ day.to_string() }
                                 selected={ selected }
                             >
-                                { move || {
-                                    format!("Day {day:0>2}")
+                                { move || match year().day(day) {
+                                    Some(solved) => format!("Day {day:0>2}: {}", solved.title),
+                                    None => format!("Day {day:0>2}"),
                                 }}
                             </option>
                         }
@@ -0,0 +1,34 @@
+use aoc_registry::Variant;
+use leptos::{component, view, For, IntoView, Show};
+
+use crate::components::Implementation;
+
+#[component]
+pub fn SelectVariant<V>(variants: V) -> impl IntoView
+where
+    V: Fn() -> &'static [Variant] + Copy + 'static,
+{
+    view! {
+        <Show when=move || !variants().is_empty()>
+            <div class="select">
+                <select name="variant" title="Implementation to run">
+                    <option value="" selected=true>
+                        "Default"
+                    </option>
+                    <For
+                        each=variants
+                        key=|variant| variant.name
+                        let:variant
+                    >
+                        <option value=variant.name>
+                            { variant.name }
+                        </option>
+                    </For>
+                    <option value=Implementation::CROSS_CHECK>
+                        "All (cross-check)"
+                    </option>
+                </select>
+            </div>
+        </Show>
+    }
+}
@@ -1,37 +1,59 @@
-use aoc_rust::SolutionType;
-use eyre::{Report, Result};
+use std::time::Duration;
+
+use aoc_rust::timing::Timings;
+use eyre::Report;
 use leptos::{component, view, CollectView, IntoView, ReadSignal, SignalWith, View};
 
-use crate::components::solver::DaySolution;
+use crate::{
+    components::solver::{DaySolution, SolveState, SolvedPart},
+    day::SolvedDay,
+    year::Year,
+};
 
 #[component]
-pub fn Solution(solved: ReadSignal<Option<Result<DaySolution>>>) -> impl IntoView {
-    let solved_opt = move || {
-        solved.with(|opt| {
-            opt.as_ref()
-                .map(|res| res.as_ref().map(Clone::clone).map_err(solution_err))
+pub fn Solution<C>(state: ReadSignal<Option<SolveState>>, on_cancel: C) -> impl IntoView
+where
+    C: Fn() + Copy + 'static,
+{
+    let shown = move || {
+        state.with(|opt| match opt {
+            Some(SolveState::Running { year, day }) => Shown::Running(*year, *day),
+            Some(SolveState::Done(Ok(solution))) => Shown::Solved(solution.clone()),
+            Some(SolveState::Done(Err(err))) => Shown::Failed(solution_err(err)),
+            None => Shown::Nothing,
         })
     };
 
     view! {
         { move || {
-            match solved_opt() {
-                Some(Ok(solution)) => {
-                    let DaySolution { solution, elapsed } = solution;
+            match shown() {
+                Shown::Solved(solution) => {
+                    let DaySolution { part1, part2, timings } = solution;
+                    let Timings { total, parse, part1: elapsed1, part2: elapsed2 } = timings;
 
                     view! {
                         <article class="message is-primary ml-5 mr-5">
                             <div class="message-body">
                                 <table class="table">
                                     <tbody>
-                                        <Part part=1 solution=solution.part1/>
-                                        <Part part=2 solution=solution.part2/>
+                                        <Part part=1 solution=part1 elapsed=elapsed1/>
+                                        <Part part=2 solution=part2 elapsed=elapsed2/>
                                     </tbody>
                                     <tfoot>
+                                        { parse.map(|parse| view! {
+                                            <tr>
+                                                <th> "Parse" </th>
+                                                <th/>
+                                                <th>
+                                                    { format!("{parse:?}") }
+                                                </th>
+                                            </tr>
+                                        })}
                                         <tr>
                                             <th> "Elapsed" </th>
+                                            <th/>
                                             <th>
-                                                { format!("{elapsed:?}") }
+                                                { format!("{total:?}") }
                                             </th>
                                         </tr>
                                     </tfoot>
@@ -40,7 +62,18 @@ pub fn Solution(solved: ReadSignal<Option<Result<DaySolution>>>) -> impl IntoVie
                         </article>
                     }.into_view()
                 },
-                Some(Err(err)) => view! {
+                Shown::Running(year, day) => view! {
+                    <article class="message is-info ml-5 mr-5">
+                        <div class="message-body">
+                            <p class="mb-3"> "Running day " { day.to_string() } " of " { year } "…" </p>
+                            <progress class="progress is-small is-info mb-3"/>
+                            <button class="button is-small" on:click=move |_| on_cancel()>
+                                "Cancel"
+                            </button>
+                        </div>
+                    </article>
+                }.into_view(),
+                Shown::Failed(err) => view! {
                     <article class="message is-danger ml-5 mr-5">
                         <div class="message-header">
                             <p> "Error" </p>
@@ -50,14 +83,24 @@ pub fn Solution(solved: ReadSignal<Option<Result<DaySolution>>>) -> impl IntoVie
                         </div>
                     </article>
                 }.into_view(),
-                None => ().into_view(),
+                Shown::Nothing => ().into_view(),
             }
         }}
     }
 }
 
+enum Shown {
+    Running(Year, SolvedDay),
+    Solved(DaySolution),
+    Failed(View),
+    Nothing,
+}
+
 #[component]
-fn Part(part: u8, solution: SolutionType) -> impl IntoView {
+fn Part(part: u8, solution: SolvedPart, elapsed: Option<Duration>) -> impl IntoView {
+    let SolvedPart { text, art } = solution;
+    let elapsed = elapsed.map(|elapsed| format!("{elapsed:?}"));
+
     view! {
         <tr>
             <th style="vertical-align: middle;">
@@ -65,8 +108,17 @@ fn Part(part: u8, solution: SolutionType) -> impl IntoView {
             </th>
             <th>
                 <pre>
-                    { solution.to_string() }
+                    { text }
                 </pre>
+                // recognized letters are shown with their image below
+                { art.map(|art| view! {
+                    <pre>
+                        { art }
+                    </pre>
+                })}
+            </th>
+            <th style="vertical-align: middle;">
+                { elapsed }
             </th>
         </tr>
     }
@@ -85,7 +137,7 @@ fn solution_err(err: &Report) -> View {
 
     let mapped_chain = chain.map(|e| {
         view! {
-            <p> "- caused by: " { e.to_string() } </p>
+            <pre> "- caused by: " { e.to_string() } </pre>
         }
     });
 
@@ -1,15 +1,21 @@
-use std::time::Duration;
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    time::Duration,
+};
 
-use aoc_rust::Solution;
-use eyre::Result;
-use leptos::{component, create_signal, view, IntoView, SignalGet};
+use aoc_rust::{timing::Timings, SolutionType};
+use eyre::{Result, WrapErr};
+use leptos::{
+    component, create_signal, leptos_dom::helpers::TimeoutHandle, set_timeout_with_handle,
+    store_value, view, IntoView, SignalGet, SignalSet,
+};
 use leptos_router::use_query;
-use wasm_timer::Instant;
 use web_sys::FormData;
 
 use crate::{
     components::{Navbar, Solution as SolutionComponent, SolverForm},
     day::SolvedDay,
+    worker::SolverWorker,
     year::Year,
 };
 
@@ -17,25 +23,85 @@ use crate::{
 pub fn Solver() -> impl IntoView {
     let year_param = use_query::<Year>();
     let year = move || year_param.get().unwrap_or_default();
-    let (solved, set_solved) = create_signal(None);
-    let on_input = move |input| set_solved(Some(solve(input)));
+    let (state, set_state) = create_signal(None);
+    let timeout = store_value(None::<TimeoutHandle>);
+
+    let clear_timeout = move || {
+        if let Some(handle) = timeout.get_value() {
+            handle.clear();
+        }
+    };
+
+    let worker = store_value(SolverWorker::new(move |solved| {
+        clear_timeout();
+        set_state.set(Some(SolveState::Done(solved)));
+    }));
+
+    let on_input = move |input: SolveInput| {
+        clear_timeout();
+
+        if let Err(err) = worker.with_value(|worker| worker.solve(&input)) {
+            set_state.set(Some(SolveState::Done(Err(err))));
+
+            return;
+        }
+
+        set_state.set(Some(SolveState::Running {
+            year: input.year,
+            day: input.day,
+        }));
+
+        let Some(duration) = input.timeout else {
+            return;
+        };
+
+        let on_timeout = move || {
+            worker.with_value(SolverWorker::cancel);
+            let err = eyre::eyre!("timed out after {duration:?}");
+            set_state.set(Some(SolveState::Done(Err(err))));
+        };
+
+        match set_timeout_with_handle(on_timeout, duration) {
+            Ok(handle) => timeout.set_value(Some(handle)),
+            Err(err) => error!(?err, "Failed to set timeout"),
+        }
+    };
+
+    let on_cancel = move || {
+        clear_timeout();
+        worker.with_value(SolverWorker::cancel);
+        set_state.set(None);
+    };
 
     view! {
         <Navbar year/>
         <SolverForm year on_input/>
-        <SolutionComponent solved/>
+        <SolutionComponent state on_cancel/>
     }
 }
 
 #[derive(Clone)]
 pub struct SolveInput {
-    year: Year,
-    day: SolvedDay,
-    input: String,
+    pub year: Year,
+    pub day: SolvedDay,
+    pub input: String,
+    pub implementation: Implementation,
+    /// Overrides of the day's parameters as `name=value` pairs
+    pub params: String,
+    /// Only relevant for the page, the worker is not aware of it
+    pub timeout: Option<Duration>,
 }
 
 impl SolveInput {
     pub fn new(form: &FormData) -> Result<Self> {
+        let timeout = form
+            .get("timeout")
+            .as_string()
+            .filter(|secs| !secs.is_empty())
+            .map(|secs| secs.parse().map(Duration::from_secs_f64))
+            .transpose()
+            .wrap_err("invalid timeout")?;
+
         Ok(Self {
             year: form.get("year").try_into()?,
             day: form.get("day").try_into()?,
@@ -43,24 +109,82 @@ impl SolveInput {
                 .get("input")
                 .as_string()
                 .ok_or(eyre::eyre!("invalid input"))?,
+            implementation: form
+                .get("variant")
+                .as_string()
+                .map(Implementation::from)
+                .unwrap_or_default(),
+            params: form.get("params").as_string().unwrap_or_default(),
+            timeout,
         })
     }
 }
 
-#[derive(Clone)]
-pub struct DaySolution {
-    pub solution: Solution,
-    pub elapsed: Duration,
+/// Which of a day's implementations to run.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Implementation {
+    #[default]
+    Default,
+    Variant(String),
+    /// Run all implementations and make sure they agree
+    CrossCheck,
+}
+
+impl Implementation {
+    /// Value of the form's option that selects [`Implementation::CrossCheck`].
+    pub const CROSS_CHECK: &'static str = "*";
 }
 
-fn solve(input: SolveInput) -> Result<DaySolution> {
-    let SolveInput { year, day, input } = input;
+impl From<String> for Implementation {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "" => Self::Default,
+            Self::CROSS_CHECK => Self::CrossCheck,
+            _ => Self::Variant(value),
+        }
+    }
+}
 
-    let solve_fn = year.solve_fn(day);
+impl Display for Implementation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Default => Ok(()),
+            Self::Variant(name) => f.write_str(name),
+            Self::CrossCheck => f.write_str(Self::CROSS_CHECK),
+        }
+    }
+}
 
-    let start = Instant::now();
-    let solution = solve_fn(&input)?;
-    let elapsed = start.elapsed().max(Duration::from_millis(1));
+pub enum SolveState {
+    Running { year: Year, day: SolvedDay },
+    Done(Result<DaySolution>),
+}
 
-    Ok(DaySolution { solution, elapsed })
+#[derive(Clone, Debug)]
+pub struct DaySolution {
+    pub part1: SolvedPart,
+    pub part2: SolvedPart,
+    pub timings: Timings,
+}
+
+/// A part's solution as it is displayed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolvedPart {
+    pub text: String,
+    /// Rendered image of recognized letters
+    pub art: Option<String>,
+}
+
+impl From<&SolutionType> for SolvedPart {
+    fn from(solution: &SolutionType) -> Self {
+        let art = match solution {
+            SolutionType::Art(art) if art.text().is_some() => Some(art.render().to_string()),
+            _ => None,
+        };
+
+        Self {
+            text: solution.to_string(),
+            art,
+        }
+    }
 }
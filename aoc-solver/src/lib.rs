@@ -4,10 +4,12 @@ extern crate tracing;
 mod app;
 mod components;
 mod day;
+mod worker;
 mod year;
 
 pub use app::App;
 
-mod generated {
-    include!(concat!(env!("OUT_DIR"), "/years.rs"));
+/// Entry point of the web worker that runs the solutions.
+pub fn run_worker() {
+    worker::run();
 }
@@ -0,0 +1,568 @@
+//! Solving runs inside a dedicated web worker so that slow days don't freeze
+//! the page and panics don't take down the app.
+//!
+//! The page sends a [`SolveInput`] to the worker and receives a [`Response`]
+//! in return. Both are passed as plain JS objects.
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+
+use aoc_registry::Overrides;
+use aoc_rust::timing::{self, Timings};
+use eyre::{Report, Result, WrapErr};
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue, UnwrapThrowExt};
+use web_sys::{DedicatedWorkerGlobalScope, ErrorEvent, MessageEvent, Worker};
+
+use crate::components::{DaySolution, Implementation, SolveInput, SolvedPart};
+
+/// Script created by trunk that loads the `worker` binary.
+const WORKER_URL: &str = "./worker_loader.js";
+
+/// Message sent by the worker.
+#[derive(Debug)]
+pub enum Response {
+    /// The worker is initialized and listens for input
+    Ready,
+    Solved(DaySolution),
+    Failed(Report),
+    /// Message of the panic that aborted the worker
+    Panicked(String),
+}
+
+impl From<&SolveInput> for JsValue {
+    fn from(input: &SolveInput) -> Self {
+        object([
+            ("year", input.year.to_string().into()),
+            ("day", input.day.to_string().into()),
+            ("input", input.input.as_str().into()),
+            ("variant", input.implementation.to_string().into()),
+            ("params", input.params.as_str().into()),
+        ])
+    }
+}
+
+impl TryFrom<JsValue> for SolveInput {
+    type Error = Report;
+
+    fn try_from(value: JsValue) -> Result<Self, Self::Error> {
+        Ok(Self {
+            year: get(&value, "year").try_into()?,
+            day: get(&value, "day").try_into()?,
+            input: get(&value, "input")
+                .as_string()
+                .ok_or(eyre::eyre!("invalid input"))?,
+            implementation: get(&value, "variant")
+                .as_string()
+                .map(Implementation::from)
+                .unwrap_or_default(),
+            params: get(&value, "params").as_string().unwrap_or_default(),
+            timeout: None,
+        })
+    }
+}
+
+impl From<&Response> for JsValue {
+    fn from(response: &Response) -> Self {
+        match response {
+            Response::Ready => object([("kind", "ready".into())]),
+            Response::Solved(solved) => object([
+                ("kind", "solved".into()),
+                ("part1", part_to_js(&solved.part1)),
+                ("part2", part_to_js(&solved.part2)),
+                ("timings", timings_to_js(&solved.timings)),
+            ]),
+            Response::Failed(err) => {
+                let chain: Array = err.chain().map(|e| JsValue::from(e.to_string())).collect();
+
+                object([("kind", "failed".into()), ("chain", chain.into())])
+            }
+            Response::Panicked(msg) => object([
+                ("kind", "panicked".into()),
+                ("message", msg.as_str().into()),
+            ]),
+        }
+    }
+}
+
+impl TryFrom<JsValue> for Response {
+    type Error = Report;
+
+    fn try_from(value: JsValue) -> Result<Self, Self::Error> {
+        let kind = get(&value, "kind").as_string();
+
+        let response = match kind.as_deref() {
+            Some("ready") => Self::Ready,
+            Some("solved") => Self::Solved(DaySolution {
+                part1: part_from_js(&get(&value, "part1"))?,
+                part2: part_from_js(&get(&value, "part2"))?,
+                timings: timings_from_js(&get(&value, "timings"))?,
+            }),
+            Some("failed") => {
+                let chain = get(&value, "chain")
+                    .dyn_into::<Array>()
+                    .map_err(|_| eyre::eyre!("invalid error chain"))?;
+
+                // Rebuild the report from its root cause outwards
+                let mut chain = chain
+                    .iter()
+                    .rev()
+                    .map(|e| e.as_string().unwrap_or_default());
+                let root = chain.next().unwrap_or_default();
+                let err = chain.fold(Report::msg(root), Report::wrap_err);
+
+                Self::Failed(err)
+            }
+            Some("panicked") => {
+                Self::Panicked(get(&value, "message").as_string().unwrap_or_default())
+            }
+            _ => eyre::bail!("invalid worker response"),
+        };
+
+        Ok(response)
+    }
+}
+
+fn part_to_js(part: &SolvedPart) -> JsValue {
+    let art = part.art.as_deref().map_or(JsValue::NULL, JsValue::from);
+
+    object([("text", part.text.as_str().into()), ("art", art)])
+}
+
+fn part_from_js(value: &JsValue) -> Result<SolvedPart> {
+    Ok(SolvedPart {
+        text: get(value, "text")
+            .as_string()
+            .ok_or(eyre::eyre!("invalid part"))?,
+        art: get(value, "art").as_string(),
+    })
+}
+
+fn timings_to_js(timings: &Timings) -> JsValue {
+    // nanoseconds are integers so they survive the conversion to f64
+    let nanos = |elapsed: Duration| JsValue::from(elapsed.as_nanos() as f64);
+    let phase = |elapsed: Option<Duration>| elapsed.map_or(JsValue::NULL, nanos);
+
+    object([
+        ("total", nanos(timings.total)),
+        ("parse", phase(timings.parse)),
+        ("part1", phase(timings.part1)),
+        ("part2", phase(timings.part2)),
+    ])
+}
+
+fn timings_from_js(value: &JsValue) -> Result<Timings> {
+    let phase = |key| {
+        get(value, key)
+            .as_f64()
+            .map(|nanos| Duration::from_nanos(nanos as u64))
+    };
+
+    Ok(Timings {
+        total: phase("total").ok_or(eyre::eyre!("invalid elapsed time"))?,
+        parse: phase("parse"),
+        part1: phase("part1"),
+        part2: phase("part2"),
+    })
+}
+
+fn object<const N: usize>(entries: [(&str, JsValue); N]) -> JsValue {
+    let obj = Object::new();
+
+    for (key, value) in entries {
+        Reflect::set(&obj, &key.into(), &value).unwrap_throw();
+    }
+
+    obj.into()
+}
+
+fn get(value: &JsValue, key: &str) -> JsValue {
+    Reflect::get(value, &key.into()).unwrap_or(JsValue::UNDEFINED)
+}
+
+/// Entry point of the `worker` binary.
+pub fn run() {
+    let scope: DedicatedWorkerGlobalScope = js_sys::global().unchecked_into();
+
+    // Panics abort the worker so the page has to be told beforehand
+    std::panic::set_hook(Box::new(|info| {
+        console_error_panic_hook::hook(info);
+
+        let scope: DedicatedWorkerGlobalScope = js_sys::global().unchecked_into();
+        let response = Response::Panicked(info.to_string());
+        let _ = scope.post_message(&(&response).into());
+    }));
+
+    let on_message = Closure::<dyn Fn(MessageEvent)>::new(|event: MessageEvent| {
+        let scope: DedicatedWorkerGlobalScope = js_sys::global().unchecked_into();
+
+        let response =
+            match SolveInput::try_from(event.data()).and_then(|input| solve(&scope, input)) {
+                Ok(solved) => Response::Solved(solved),
+                Err(err) => Response::Failed(err),
+            };
+
+        scope.post_message(&(&response).into()).unwrap_throw();
+    });
+
+    scope.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    on_message.forget();
+
+    scope
+        .post_message(&(&Response::Ready).into())
+        .unwrap_throw();
+}
+
+fn solve(scope: &DedicatedWorkerGlobalScope, input: SolveInput) -> Result<DaySolution> {
+    let SolveInput {
+        year,
+        day,
+        input,
+        implementation,
+        params,
+        ..
+    } = input;
+
+    let solved_day = year
+        .day(day)
+        .ok_or_else(|| eyre::eyre!("day {day} of {year} is not solved"))?;
+
+    // `None` cross-checks all implementations
+    let run = match implementation {
+        Implementation::Default => Some(solved_day.run),
+        Implementation::Variant(ref name) => match solved_day.run_fn(Some(name)) {
+            Some(run) => Some(run),
+            None => eyre::bail!("day {day} of {year} has no variant `{name}`"),
+        },
+        Implementation::CrossCheck => None,
+    };
+
+    let params = if params.trim().is_empty() {
+        None
+    } else if implementation != Implementation::Default {
+        eyre::bail!("parameters only apply to the default implementation");
+    } else {
+        let mut overrides = Overrides::default();
+        overrides.set_all(&params)?;
+
+        Some(overrides)
+    };
+
+    // `std::time::Instant` is not available on wasm
+    let performance = scope
+        .performance()
+        .ok_or(eyre::eyre!("performance not available"))?;
+
+    let clock = move || Duration::from_secs_f64(performance.now() / 1000.0);
+    let (res, mut timings) = timing::time_with(clock, || match (run, params) {
+        (_, Some(params)) => solved_day.run_with(&input, &params),
+        (Some(run), None) => run(&input),
+        (None, None) => solved_day.cross_check(&input),
+    });
+    let solution = res.wrap_err_with(|| format!("failed to run day {day:02} of {year}"))?;
+    timings.total = timings.total.max(Duration::from_millis(1));
+
+    Ok(DaySolution {
+        part1: SolvedPart::from(&solution.part1),
+        part2: SolvedPart::from(&solution.part2),
+        timings,
+    })
+}
+
+/// Handle to the worker on the page's side.
+///
+/// The worker is spawned lazily and replaced whenever it was cancelled or
+/// aborted.
+pub struct SolverWorker {
+    spawned: RefCell<Option<Spawned>>,
+    on_solved: Rc<dyn Fn(Result<DaySolution>)>,
+}
+
+struct Spawned {
+    worker: Worker,
+    shared: Rc<Shared>,
+    _on_message: Closure<dyn Fn(MessageEvent)>,
+    _on_error: Closure<dyn Fn(ErrorEvent)>,
+}
+
+impl Spawned {
+    fn is_idle(&self) -> bool {
+        self.shared.alive.get() && !self.shared.busy.get()
+    }
+}
+
+#[derive(Default)]
+struct Shared {
+    ready: Cell<bool>,
+    alive: Cell<bool>,
+    busy: Cell<bool>,
+    /// Input that was sent before the worker was ready
+    pending: RefCell<Option<JsValue>>,
+}
+
+impl SolverWorker {
+    pub fn new(on_solved: impl Fn(Result<DaySolution>) + 'static) -> Self {
+        Self {
+            spawned: RefCell::new(None),
+            on_solved: Rc::new(on_solved),
+        }
+    }
+
+    /// Solve the input, cancelling whatever is currently running.
+    pub fn solve(&self, input: &SolveInput) -> Result<()> {
+        let mut slot = self.spawned.borrow_mut();
+
+        let spawned = match slot.take() {
+            Some(spawned) if spawned.is_idle() => slot.insert(spawned),
+            busy => {
+                if let Some(busy) = busy {
+                    busy.worker.terminate();
+                }
+
+                slot.insert(self.spawn()?)
+            }
+        };
+
+        let msg = JsValue::from(input);
+        spawned.shared.busy.set(true);
+
+        if spawned.shared.ready.get() {
+            spawned
+                .worker
+                .post_message(&msg)
+                .map_err(|_| eyre::eyre!("failed to send input to worker"))?;
+        } else {
+            spawned.shared.pending.replace(Some(msg));
+        }
+
+        Ok(())
+    }
+
+    /// Stop the running solve, if any.
+    ///
+    /// A solve cannot be interrupted from the outside so the worker is
+    /// terminated and a new one will be spawned for the next input.
+    pub fn cancel(&self) {
+        if let Some(spawned) = self.spawned.take() {
+            spawned.worker.terminate();
+        }
+    }
+
+    fn spawn(&self) -> Result<Spawned> {
+        let worker = Worker::new(WORKER_URL).map_err(|_| eyre::eyre!("failed to spawn worker"))?;
+
+        let shared = Rc::new(Shared::default());
+        shared.alive.set(true);
+
+        let on_message = {
+            let worker = worker.clone();
+            let shared = Rc::clone(&shared);
+            let on_solved = Rc::clone(&self.on_solved);
+
+            Closure::<dyn Fn(MessageEvent)>::new(move |event: MessageEvent| {
+                if !shared.alive.get() {
+                    return;
+                }
+
+                let response = match Response::try_from(event.data()) {
+                    Ok(response) => response,
+                    Err(err) => return on_solved(Err(err)),
+                };
+
+                match response {
+                    Response::Ready => {
+                        shared.ready.set(true);
+
+                        if let Some(msg) = shared.pending.take() {
+                            if worker.post_message(&msg).is_err() {
+                                on_solved(Err(eyre::eyre!("failed to send input to worker")));
+                            }
+                        }
+                    }
+                    Response::Solved(solved) => {
+                        shared.busy.set(false);
+                        on_solved(Ok(solved));
+                    }
+                    Response::Failed(err) => {
+                        shared.busy.set(false);
+                        on_solved(Err(err));
+                    }
+                    Response::Panicked(msg) => {
+                        shared.alive.set(false);
+                        worker.terminate();
+                        on_solved(Err(Report::msg(msg).wrap_err("solution panicked")));
+                    }
+                }
+            })
+        };
+
+        let on_error = {
+            let worker = worker.clone();
+            let shared = Rc::clone(&shared);
+            let on_solved = Rc::clone(&self.on_solved);
+
+            Closure::<dyn Fn(ErrorEvent)>::new(move |event: ErrorEvent| {
+                if !shared.alive.replace(false) {
+                    return;
+                }
+
+                worker.terminate();
+                let err = Report::msg(event.message()).wrap_err("worker crashed");
+                on_solved(Err(err));
+            })
+        };
+
+        worker.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        worker.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+        Ok(Spawned {
+            worker,
+            shared,
+            _on_message: on_message,
+            _on_error: on_error,
+        })
+    }
+}
+
+impl Drop for SolverWorker {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use wasm_bindgen_test::*;
+
+    use super::*;
+
+    // `solve` runs inside the worker and needs its global scope
+    wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+    fn input(year: &str, day: &str, input: &str) -> SolveInput {
+        input_with(year, day, input, "")
+    }
+
+    fn input_with(year: &str, day: &str, input: &str, variant: &str) -> SolveInput {
+        input_with_params(year, day, input, variant, "")
+    }
+
+    fn input_with_params(
+        year: &str,
+        day: &str,
+        input: &str,
+        variant: &str,
+        params: &str,
+    ) -> SolveInput {
+        let msg = object([
+            ("year", year.into()),
+            ("day", day.into()),
+            ("input", input.into()),
+            ("variant", variant.into()),
+            ("params", params.into()),
+        ]);
+
+        SolveInput::try_from(msg).unwrap()
+    }
+
+    fn round_trip(response: &Response) -> Response {
+        Response::try_from(JsValue::from(response)).unwrap()
+    }
+
+    fn scope() -> DedicatedWorkerGlobalScope {
+        js_sys::global().unchecked_into()
+    }
+
+    #[wasm_bindgen_test]
+    fn input_round_trip() {
+        let input = input("2015", "1", "())");
+        let sent = SolveInput::try_from(JsValue::from(&input)).unwrap();
+
+        assert_eq!(sent.year, input.year);
+        assert!(sent.day == input.day);
+        assert_eq!(sent.input, input.input);
+        assert_eq!(sent.implementation, Implementation::Default);
+
+        let input = input_with("2024", "23", "", "bk");
+        let sent = SolveInput::try_from(JsValue::from(&input)).unwrap();
+        assert_eq!(
+            sent.implementation,
+            Implementation::Variant("bk".to_owned())
+        );
+
+        let input = input_with_params("2016", "13", "10", "", "goal_x=7 goal_y=4");
+        let sent = SolveInput::try_from(JsValue::from(&input)).unwrap();
+        assert_eq!(sent.params, "goal_x=7 goal_y=4");
+        assert!(SolveInput::try_from(JsValue::NULL).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn solved() {
+        let solved = solve(&scope(), input("2015", "1", "())")).unwrap();
+        assert_eq!(solved.part1.text, "-1");
+        assert_eq!(solved.part2.text, "3");
+        assert!(solved.part1.art.is_none());
+
+        let Response::Solved(received) = round_trip(&Response::Solved(solved.clone())) else {
+            panic!("expected solution");
+        };
+
+        assert_eq!(received.part1, solved.part1);
+        assert_eq!(received.part2, solved.part2);
+        assert_eq!(received.timings, solved.timings);
+        assert!(received.timings.total >= Duration::from_millis(1));
+    }
+
+    #[wasm_bindgen_test]
+    fn variants() {
+        let input = "A Y\nB X\nC Z\n";
+        let solved = solve(&scope(), input_with("2022", "2", input, "const-lookup")).unwrap();
+        assert_eq!(solved.part1.text, "15");
+
+        let checked = solve(&scope(), input_with("2022", "2", input, "*")).unwrap();
+        assert_eq!(checked.part2.text, "12");
+
+        assert!(solve(&scope(), input_with("2022", "2", input, "unknown")).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn params() {
+        let input = input_with_params("2016", "13", "10", "", "goal_x=7, goal_y=4");
+        let solved = solve(&scope(), input).unwrap();
+        assert_eq!(solved.part1.text, "11");
+
+        let input = input_with_params("2016", "13", "10", "", "goal=7");
+        assert!(solve(&scope(), input).is_err());
+
+        let input = input_with_params("2022", "2", "A Y\n", "const-lookup", "row=10");
+        assert!(solve(&scope(), input).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn failed_keeps_chain() {
+        let err = solve(&scope(), input("2023", "14", "")).unwrap_err();
+        let expected: Vec<_> = err.chain().map(ToString::to_string).collect();
+        assert_eq!(expected[0], "failed to run day 14 of 2023");
+
+        let Response::Failed(received) = round_trip(&Response::Failed(err)) else {
+            panic!("expected error");
+        };
+
+        let received: Vec<_> = received.chain().map(ToString::to_string).collect();
+        assert_eq!(received, expected);
+    }
+
+    #[wasm_bindgen_test]
+    fn panicked() {
+        let Response::Panicked(msg) = round_trip(&Response::Panicked("oops".to_owned())) else {
+            panic!("expected panic");
+        };
+
+        assert_eq!(msg, "oops");
+        assert!(matches!(round_trip(&Response::Ready), Response::Ready));
+        assert!(Response::try_from(JsValue::NULL).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt::{Display, Formatter, Result as FmtResult};
 
-use aoc_rust::Solution;
+use aoc_registry::Day;
 use eyre::{Report, Result};
 use leptos::{Attribute, IntoAttribute, IntoView, View};
 use leptos_router::{Params, ParamsError, ParamsMap};
@@ -15,11 +15,11 @@ impl Year {
     const DEFAULT_YEAR: u16 = 2025;
 
     pub fn solved_days(self) -> SolvedDays {
-        SolvedDays::new(crate::generated::solved_days(self.0))
+        SolvedDays::new(aoc_registry::solved_days(self.0))
     }
 
-    pub fn solve_fn(self, day: SolvedDay) -> fn(&str) -> Result<Solution> {
-        crate::generated::solve_fn(self.0, day.0)
+    pub fn day(self, day: SolvedDay) -> Option<&'static Day> {
+        aoc_registry::day(self.0, day.0)
     }
 }
 